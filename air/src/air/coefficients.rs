@@ -96,6 +96,11 @@ pub struct ConstraintCompositionCoefficients<E: FieldElement> {
 /// * $\alpha_i$ is a composition coefficient for the $i$th trace polynomial.
 /// * $\beta_j$ is a composition coefficient for the $j$th constraint column polynomial.
 ///
+/// When the proof options specify more than one out-of-domain point (see
+/// [ProofOptions::num_ood_points()](crate::ProofOptions::num_ood_points)), $Y(x)$ is the sum of
+/// the above expression over all out-of-domain points $z_1, \ldots, z_s$; the same coefficients
+/// are used for all points.
+///
 /// The soundness of the resulting protocol with batching as above is given in Theorem 8 in
 /// https://eprint.iacr.org/2022/1216 and it relies on two points:
 ///
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{air::TransitionConstraintDegree, ProofOptions, ProofOptionsError, TraceInfo};
 use core::cmp;
 use math::StarkField;
 use utils::collections::Vec;
@@ -23,6 +23,7 @@ pub struct AirContext<B: StarkField> {
     pub(super) trace_domain_generator: B,
     pub(super) lde_domain_generator: B,
     pub(super) num_transition_exemptions: usize,
+    pub(super) num_main_extension_columns: usize,
 }
 
 impl<B: StarkField> AirContext<B> {
@@ -40,9 +41,10 @@ impl<B: StarkField> AirContext<B> {
     /// Panics if
     /// * `transition_constraint_degrees` is an empty vector.
     /// * `num_assertions` is zero.
-    /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
-    ///   of the specified transition constraints.
     /// * `trace_info` describes a multi-segment execution trace.
+    ///
+    /// Proof options are not validated against the computation; see
+    /// [validate_options()](AirContext::validate_options).
     pub fn new(
         trace_info: TraceInfo,
         transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -83,8 +85,9 @@ impl<B: StarkField> AirContext<B> {
     /// * `trace_info.is_multi_segment() == false` but:
     ///   - `aux_transition_constraint_degrees` is a non-empty vector.
     ///   - `num_aux_assertions` is greater than zero.
-    /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
-    ///   of the specified transition constraints.
+    ///
+    /// Proof options are not validated against the computation; see
+    /// [validate_options()](AirContext::validate_options).
     pub fn new_multi_segment(
         trace_info: TraceInfo,
         main_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -134,13 +137,6 @@ impl<B: StarkField> AirContext<B> {
             }
         }
 
-        assert!(
-            options.blowup_factor() >= ce_blowup_factor,
-            "blowup factor too small; expected at least {}, but was {}",
-            ce_blowup_factor,
-            options.blowup_factor()
-        );
-
         let trace_length = trace_info.length();
         let lde_domain_size = trace_length * options.blowup_factor();
 
@@ -155,6 +151,7 @@ impl<B: StarkField> AirContext<B> {
             trace_domain_generator: B::get_root_of_unity(trace_length.ilog2()),
             lde_domain_generator: B::get_root_of_unity(lde_domain_size.ilog2()),
             num_transition_exemptions: 1,
+            num_main_extension_columns: 0,
         }
     }
 
@@ -209,6 +206,18 @@ impl<B: StarkField> AirContext<B> {
         self.aux_transition_constraint_degrees.len()
     }
 
+    /// Returns degree descriptors of transition constraints placed against the main trace
+    /// segment.
+    pub fn main_transition_constraint_degrees(&self) -> &[TransitionConstraintDegree] {
+        &self.main_transition_constraint_degrees
+    }
+
+    /// Returns degree descriptors of transition constraints placed against all auxiliary trace
+    /// segments.
+    pub fn aux_transition_constraint_degrees(&self) -> &[TransitionConstraintDegree] {
+        &self.aux_transition_constraint_degrees
+    }
+
     /// Returns the total number of assertions defined for a computation.
     ///
     /// The number of assertions consists of the assertions placed against the main segment of an
@@ -227,6 +236,36 @@ impl<B: StarkField> AirContext<B> {
         self.num_transition_exemptions
     }
 
+    /// Returns the number of columns of the main trace segment which hold extension field
+    /// elements.
+    ///
+    /// Extension field columns are the last columns of main segment evaluation frames passed to
+    /// [Air::evaluate_transition()](crate::Air::evaluate_transition). In the committed main trace
+    /// segment, each such column occupies as many consecutive columns as the degree of the field
+    /// extension specified by proof options. By default, there are no extension field columns.
+    pub fn num_main_extension_columns(&self) -> usize {
+        self.num_main_extension_columns
+    }
+
+    /// Returns the number of columns of the main trace segment which hold base field elements.
+    ///
+    /// These are the leading columns of both the committed main trace segment and of main
+    /// segment evaluation frames.
+    pub fn num_main_base_columns(&self) -> usize {
+        let extension_degree = self.options.field_extension().degree() as usize;
+        self.trace_info.layout().main_trace_width()
+            - self.num_main_extension_columns * extension_degree
+    }
+
+    /// Returns the number of columns in main segment evaluation frames passed to
+    /// [Air::evaluate_transition()](crate::Air::evaluate_transition).
+    ///
+    /// This is the same as the width of the main trace segment, unless some of its columns hold
+    /// extension field elements.
+    pub fn main_frame_width(&self) -> usize {
+        self.num_main_base_columns() + self.num_main_extension_columns
+    }
+
     /// Returns the number of columns needed to store the constraint composition polynomial.
     ///
     /// This is the maximum of:
@@ -271,6 +310,30 @@ impl<B: StarkField> AirContext<B> {
         cmp::max(num_constraint_col, 1)
     }
 
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the proof options of this context can be used to prove and verify the
+    /// computation described by this context.
+    ///
+    /// The prover and the verifier perform this check right after an AIR is instantiated, and
+    /// thus, instantiating an AIR with such options does not panic.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The blowup factor is too small to accommodate degrees of the transition constraints.
+    /// * The domain offset is not a valid offset for the LDE domain of the computation (see
+    ///   [ProofOptions::validate_domain_offset()]).
+    pub fn validate_options(&self) -> Result<(), ProofOptionsError> {
+        if self.options.blowup_factor() < self.ce_blowup_factor {
+            return Err(ProofOptionsError::BlowupFactorTooSmall(
+                self.ce_blowup_factor,
+                self.options.blowup_factor(),
+            ));
+        }
+        self.options.validate_domain_offset::<B>(self.lde_domain_size())
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -316,4 +379,35 @@ impl<B: StarkField> AirContext<B> {
         self.num_transition_exemptions = n;
         self
     }
+
+    /// Sets the number of columns of the main trace segment which hold extension field elements.
+    ///
+    /// The last `n` columns of main segment evaluation frames then contain elements of the field
+    /// extension specified by proof options, and transition constraints of the main segment are
+    /// always evaluated over the extension field. In the committed main trace segment, each of
+    /// these columns occupies as many consecutive columns as the degree of the extension, which
+    /// hold coordinates of the extension field elements (as returned by
+    /// [FieldElement::slice_as_base_elements()](math::FieldElement::slice_as_base_elements)).
+    /// Assertions against the main trace segment refer to columns of the committed segment; thus,
+    /// an extension field column is constrained by assertions against its coordinates.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `n` is greater than zero, but proof options do not specify a field extension.
+    /// * The main trace segment does not have enough columns to hold `n` extension field columns.
+    pub fn set_num_main_extension_columns(mut self, n: usize) -> Self {
+        let extension_degree = self.options.field_extension().degree() as usize;
+        assert!(
+            n == 0 || extension_degree > 1,
+            "extension field columns require proof options with a field extension"
+        );
+        let main_trace_width = self.trace_info.layout().main_trace_width();
+        assert!(
+            n * extension_degree <= main_trace_width,
+            "main trace segment of width {main_trace_width} cannot hold {n} extension field columns of degree {extension_degree}"
+        );
+
+        self.num_main_extension_columns = n;
+        self
+    }
 }
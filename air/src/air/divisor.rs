@@ -128,7 +128,7 @@ impl<B: StarkField> ConstraintDivisor<B> {
         // compute the numerator value
         let mut numerator = E::ONE;
         for (degree, constant) in self.numerator.iter() {
-            let v = x.exp((*degree as u32).into());
+            let v = x.exp((*degree as u64).into());
             let v = v - E::from(*constant);
             numerator *= v;
         }
@@ -226,6 +226,18 @@ mod tests {
         assert_eq!(expected, div.evaluate_at(BaseElement::new(4)));
     }
 
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn constraint_divisor_evaluation_large_degree() {
+        // numerator degrees are not truncated to 32 bits: x^(2^33) - 1
+        let degree = 1_usize << 33;
+        let div = ConstraintDivisor::new(vec![(degree, BaseElement::ONE)], vec![]);
+        let x = BaseElement::new(3);
+        let expected = x.exp((degree as u64).into()) - BaseElement::ONE;
+        assert_ne!(BaseElement::ZERO, expected);
+        assert_eq!(expected, div.evaluate_at(x));
+    }
+
     #[test]
     fn constraint_divisor_equivalence() {
         let n = 8_usize;
@@ -4,11 +4,15 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::ProofOptions;
-use crypto::{RandomCoin, RandomCoinError};
+use crypto::{hashers::Blake3_256, Digest, Hasher, RandomCoin, RandomCoinError};
 use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
-use utils::collections::{BTreeMap, Vec};
+use utils::{
+    collections::{BTreeMap, Vec},
+    ByteWriter, Serializable,
+};
 
 mod trace_info;
+pub(crate) use trace_info::NUM_AUX_SEGMENTS;
 pub use trace_info::{TraceInfo, TraceLayout};
 
 mod context;
@@ -172,6 +176,21 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// * Override [Air::get_aux_assertions()] method. This method is similar to the
 ///   [Air::get_assertions()] method, but it should return assertions against columns of the
 ///   auxiliary trace segments.
+///
+/// ### Extension field columns
+/// Some computations have witnesses which naturally live in an extension of the base field. When
+/// proof options specify a field extension, the last columns of the main trace segment can be
+/// declared to hold elements of this extension via [AirContext::set_num_main_extension_columns()].
+/// For such computations:
+/// * The execution trace stores each extension field column as consecutive columns holding
+///   coordinates of the extension field elements; the main trace segment is committed to in this
+///   form.
+/// * Evaluation frames passed to [Air::evaluate_transition()] and [Air::evaluate_aux_transition()]
+///   contain a single value for each extension field column (see
+///   [AirContext::main_frame_width()]), and these methods are always invoked with `E` being the
+///   extension field.
+/// * Assertions against the main trace segment refer to columns of the committed segment, and
+///   thus, constrain individual coordinates of extension field elements.
 pub trait Air: Send + Sync {
     /// Base field for the computation described by this AIR. STARK protocol for this computation
     /// may be executed in the base field, or in an extension of the base fields as specified
@@ -182,6 +201,16 @@ pub trait Air: Send + Sync {
     /// This could be any type as long as it can be serialized into a sequence of field elements.
     type PublicInputs: ToElements<Self::BaseField>;
 
+    /// Identifier of the computation described by this AIR.
+    ///
+    /// The identifier is embedded into the context of every proof generated for this AIR, and
+    /// thus, proofs for different computations can be told apart without verifying them (e.g.,
+    /// to dispatch a proof to the right AIR via `AirRegistry` of the verifier). The verifier
+    /// rejects proofs whose identifier differs from the identifier of the AIR.
+    ///
+    /// The default value is 0, meaning that the computation is not identified.
+    const AIR_ID: u32 = 0;
+
     // REQUIRED METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -207,7 +236,8 @@ pub trait Air: Send + Sync {
     ///
     /// We define type `E` separately from `Self::BaseField` to allow evaluation of constraints
     /// over the out-of-domain evaluation frame, which may be defined over an extension field
-    /// (when extension fields are used).
+    /// (when extension fields are used). If some columns of the main trace segment hold extension
+    /// field elements, `E` is always the extension field.
     fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -419,7 +449,9 @@ pub trait Air: Send + Sync {
     /// hightest transition constraint degree. For example, if the hightest transition
     /// constraint degree = 3, `ce_blowup_factor` will be set to 4.
     ///
-    /// `ce_blowup_factor` is guaranteed to be smaller than or equal to the `lde_blowup_factor`.
+    /// `ce_blowup_factor` is guaranteed to be smaller than or equal to the `lde_blowup_factor` for
+    /// AIRs which pass [AirContext::validate_options()]; the prover and the verifier reject all
+    /// other AIRs.
     fn ce_blowup_factor(&self) -> usize {
         self.context().ce_blowup_factor
     }
@@ -460,6 +492,51 @@ pub trait Air: Send + Sync {
         self.context().options.domain_offset()
     }
 
+    /// Returns a digest of the structure of the computation described by this AIR.
+    ///
+    /// The digest commits to the AIR identifier, proof options, trace layout (including the
+    /// number of main trace columns in the extension field), degrees of all transition
+    /// constraints, numbers of assertions and transition exemptions, number of constraint
+    /// composition columns, and values of all periodic columns. Length and metadata of the
+    /// execution trace are not included as they are bound to a proof via its context directly.
+    ///
+    /// The digest is embedded into the context of every proof generated for this AIR, and the
+    /// verifier rejects proofs whose digest differs from the digest of the AIR instantiated by
+    /// the verifier. Thus, a proof generated for one computation cannot be verified against a
+    /// structurally different computation, even when both computations accept the same public
+    /// inputs.
+    fn fingerprint(&self) -> [u8; 32] {
+        let context = self.context();
+        let mut bytes = Vec::new();
+        bytes.write_u32(Self::AIR_ID);
+        context.options.write_into(&mut bytes);
+        context.trace_info.layout().write_fingerprint_into(&mut bytes);
+        bytes.write_usize(context.num_main_extension_columns);
+
+        for degrees in [
+            &context.main_transition_constraint_degrees,
+            &context.aux_transition_constraint_degrees,
+        ] {
+            bytes.write_usize(degrees.len());
+            for degree in degrees.iter() {
+                degree.write_into(&mut bytes);
+            }
+        }
+        bytes.write_usize(context.num_main_assertions);
+        bytes.write_usize(context.num_aux_assertions);
+        bytes.write_usize(context.num_transition_exemptions);
+        bytes.write_usize(context.num_constraint_composition_columns());
+
+        let periodic_columns = self.get_periodic_column_values();
+        bytes.write_usize(periodic_columns.len());
+        for column in periodic_columns.iter() {
+            bytes.write_usize(column.len());
+            column.write_into(&mut bytes);
+        }
+
+        Blake3_256::<Self::BaseField>::hash(&bytes).as_bytes()
+    }
+
     // TRACE SEGMENT RANDOMNESS
     // --------------------------------------------------------------------------------------------
 
@@ -516,6 +593,10 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combinations during construction of DEEP
     /// composition polynomial.
+    ///
+    /// If [ProofOptions::deep_coefficient_powers()] is set, a single challenge $\gamma$ is drawn
+    /// from the public coin, and the coefficients are $1, \gamma, \gamma^2, \ldots$ (trace
+    /// polynomial coefficients first); otherwise, every coefficient is drawn independently.
     fn get_deep_composition_coefficients<E, R>(
         &self,
         public_coin: &mut R,
@@ -524,13 +605,30 @@ pub trait Air: Send + Sync {
         E: FieldElement<BaseField = Self::BaseField>,
         R: RandomCoin<BaseField = Self::BaseField>,
     {
+        let trace_width = self.trace_info().width();
+        let num_columns = self.context().num_constraint_composition_columns();
+        if self.options().deep_coefficient_powers() {
+            let challenge: E = public_coin.draw()?;
+            let mut coefficients = Vec::with_capacity(trace_width + num_columns);
+            let mut power = E::ONE;
+            for _ in 0..trace_width + num_columns {
+                coefficients.push(power);
+                power *= challenge;
+            }
+            let c_coefficients = coefficients.split_off(trace_width);
+            return Ok(DeepCompositionCoefficients {
+                trace: coefficients,
+                constraints: c_coefficients,
+            });
+        }
+
         let mut t_coefficients = Vec::new();
-        for _ in 0..self.trace_info().width() {
+        for _ in 0..trace_width {
             t_coefficients.push(public_coin.draw()?);
         }
 
         let mut c_coefficients = Vec::new();
-        for _ in 0..self.context().num_constraint_composition_columns() {
+        for _ in 0..num_columns {
             c_coefficients.push(public_coin.draw()?);
         }
 
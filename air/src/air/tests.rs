@@ -7,9 +7,12 @@ use super::{
     Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
     TransitionConstraintDegree,
 };
-use crate::{AuxTraceRandElements, FieldExtension};
+use crate::{AuxTraceRandElements, FieldExtension, ProofOptionsError};
 use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
-use math::{fields::f64::BaseElement, get_power_series, polynom, FieldElement, StarkField};
+use math::{
+    fields::{f64::BaseElement, QuadExtension},
+    get_power_series, polynom, FieldElement, StarkField,
+};
 use utils::collections::{BTreeMap, Vec};
 
 // PERIODIC COLUMNS
@@ -186,6 +189,151 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&5], constraint.cc().clone());
 }
 
+// EXTENSION FIELD COLUMNS
+// ================================================================================================
+
+#[test]
+fn main_extension_columns() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::Quadratic, 4, 31);
+    let context = AirContext::<BaseElement>::new(
+        TraceInfo::new(5, 16),
+        vec![TransitionConstraintDegree::new(2)],
+        1,
+        options,
+    );
+    assert_eq!(0, context.num_main_extension_columns());
+    assert_eq!(5, context.num_main_base_columns());
+    assert_eq!(5, context.main_frame_width());
+
+    let context = context.set_num_main_extension_columns(2);
+    assert_eq!(2, context.num_main_extension_columns());
+    assert_eq!(1, context.num_main_base_columns());
+    assert_eq!(3, context.main_frame_width());
+}
+
+#[test]
+fn main_extension_columns_fingerprint() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::Quadratic, 4, 31);
+    let context = AirContext::<BaseElement>::new(
+        TraceInfo::new(5, 16),
+        vec![TransitionConstraintDegree::new(2)],
+        1,
+        options,
+    );
+    let air = MockAir {
+        context: context.clone(),
+        assertions: Vec::new(),
+        periodic_columns: Vec::new(),
+    };
+    let ext_air = MockAir {
+        context: context.set_num_main_extension_columns(2),
+        assertions: Vec::new(),
+        periodic_columns: Vec::new(),
+    };
+    assert_ne!(air.fingerprint(), ext_air.fingerprint());
+}
+
+#[test]
+#[should_panic(expected = "extension field columns require proof options with a field extension")]
+fn main_extension_columns_no_field_extension() {
+    build_context::<BaseElement>(16, 4, 1).set_num_main_extension_columns(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "main trace segment of width 5 cannot hold 3 extension field columns of degree 2"
+)]
+fn main_extension_columns_too_many() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::Quadratic, 4, 31);
+    AirContext::<BaseElement>::new(
+        TraceInfo::new(5, 16),
+        vec![TransitionConstraintDegree::new(2)],
+        1,
+        options,
+    )
+    .set_num_main_extension_columns(3);
+}
+
+#[test]
+fn read_extension_columns() {
+    let current = (1..6).map(BaseElement::new).collect::<Vec<_>>();
+    let next = (6..11).map(BaseElement::new).collect::<Vec<_>>();
+    let frame = EvaluationFrame::from_rows(current, next);
+
+    // coordinates of extension field elements are combined, base field columns are copied
+    let mut ext_frame = EvaluationFrame::<QuadExtension<BaseElement>>::new(3);
+    ext_frame.read_extension_columns(&frame, 1);
+    let expected_current = vec![
+        QuadExtension::from(BaseElement::new(1)),
+        QuadExtension::new(BaseElement::new(2), BaseElement::new(3)),
+        QuadExtension::new(BaseElement::new(4), BaseElement::new(5)),
+    ];
+    let expected_next = vec![
+        QuadExtension::from(BaseElement::new(6)),
+        QuadExtension::new(BaseElement::new(7), BaseElement::new(8)),
+        QuadExtension::new(BaseElement::new(9), BaseElement::new(10)),
+    ];
+    assert_eq!(expected_current, ext_frame.current());
+    assert_eq!(expected_next, ext_frame.next());
+
+    // coordinates which are extension field elements themselves are combined in the same way
+    let lifted_frame = EvaluationFrame::from_rows(
+        frame.current().iter().map(|&value| QuadExtension::from(value)).collect(),
+        frame.next().iter().map(|&value| QuadExtension::from(value)).collect(),
+    );
+    let mut lifted_ext_frame = EvaluationFrame::<QuadExtension<BaseElement>>::new(3);
+    lifted_ext_frame.read_extension_columns(&lifted_frame, 1);
+    assert_eq!(ext_frame.current(), lifted_ext_frame.current());
+    assert_eq!(ext_frame.next(), lifted_ext_frame.next());
+}
+
+#[test]
+#[should_panic(
+    expected = "frame of width 4 cannot hold 1 base field columns and 2 extension field columns"
+)]
+fn read_extension_columns_invalid_width() {
+    let frame = EvaluationFrame::<BaseElement>::new(4);
+    let mut ext_frame = EvaluationFrame::<QuadExtension<BaseElement>>::new(3);
+    ext_frame.read_extension_columns(&frame, 1);
+}
+
+// OPTIONS VALIDATION
+// ================================================================================================
+
+#[test]
+fn validate_options() {
+    let trace_info = TraceInfo::new(4, 16);
+    let degree = TransitionConstraintDegree::new(5);
+    let min_blowup_factor = degree.min_blowup_factor();
+    let options = ProofOptions::new(32, min_blowup_factor, 0, FieldExtension::None, 4, 7);
+    let context =
+        AirContext::<BaseElement>::new(trace_info.clone(), vec![degree.clone()], 1, options);
+    assert_eq!(Ok(()), context.validate_options());
+
+    // blowup factor smaller than required by the constraint degrees is rejected
+    let options = ProofOptions::new(32, min_blowup_factor / 2, 0, FieldExtension::None, 4, 7);
+    let context =
+        AirContext::<BaseElement>::new(trace_info.clone(), vec![degree.clone()], 1, options);
+    assert_eq!(
+        Err(ProofOptionsError::BlowupFactorTooSmall(
+            min_blowup_factor,
+            min_blowup_factor / 2
+        )),
+        context.validate_options()
+    );
+
+    // domain offset in the subgroup spanned by the LDE domain is rejected
+    let lde_domain_size = 16 * min_blowup_factor;
+    let root = BaseElement::get_root_of_unity(lde_domain_size.ilog2()).as_int();
+    let options = ProofOptions::new(32, min_blowup_factor, 0, FieldExtension::None, 4, 7)
+        .with_domain_offset(root);
+    let context = AirContext::<BaseElement>::new(trace_info, vec![degree], 1, options);
+    assert_eq!(
+        Err(ProofOptionsError::UnsafeDomainOffset(root, lde_domain_size)),
+        context.validate_options()
+    );
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -13,7 +13,7 @@ use utils::{
 // ================================================================================================
 
 /// Number of allowed auxiliary trace segments.
-const NUM_AUX_SEGMENTS: usize = 1;
+pub(crate) const NUM_AUX_SEGMENTS: usize = 1;
 
 // TRACE INFO
 // ================================================================================================
@@ -33,8 +33,9 @@ pub struct TraceInfo {
 impl TraceInfo {
     /// Smallest allowed execution trace length; currently set at 8.
     pub const MIN_TRACE_LENGTH: usize = 8;
-    /// Maximum number of columns in an execution trace (across all segments); currently set at 255.
-    pub const MAX_TRACE_WIDTH: usize = 255;
+    /// Maximum number of columns in an execution trace (across all segments); currently set at
+    /// 65535.
+    pub const MAX_TRACE_WIDTH: usize = 65535;
     /// Maximum number of bytes in trace metadata; currently set at 65535.
     pub const MAX_META_LENGTH: usize = 65535;
     /// Maximum number of random elements per auxiliary trace segment; currently set to 255.
@@ -49,7 +50,7 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
         Self::with_meta(width, length, vec![])
@@ -61,7 +62,7 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     /// * Length of `meta` is greater than 65535;
     pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
@@ -75,7 +76,7 @@ impl TraceInfo {
     /// # Panics
     /// Panics if:
     /// * The width of the first trace segment is zero.
-    /// * Total width of all trace segments is greater than 255.
+    /// * Total width of all trace segments is greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     pub fn new_multi_segment(layout: TraceLayout, length: usize, meta: Vec<u8>) -> Self {
         assert!(
@@ -113,7 +114,7 @@ impl TraceInfo {
 
     /// Returns the total number of columns in an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 65535.
     pub fn width(&self) -> usize {
         self.layout.main_trace_width() + self.layout().aux_trace_width()
     }
@@ -136,6 +137,51 @@ impl TraceInfo {
     }
 }
 
+impl Serializable for TraceInfo {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.layout.write_into(target);
+        target.write_u8(self.length.ilog2() as u8); // store as power of two
+        target.write_u16(self.meta.len() as u16);
+        target.write_bytes(&self.meta);
+    }
+}
+
+impl Deserializable for TraceInfo {
+    /// Reads [TraceInfo] from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error of a valid [TraceInfo] struct could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let layout = TraceLayout::read_from(source)?;
+
+        // read and validate trace length (which was stored as a power of two)
+        let length = source.read_u8()?;
+        if length < Self::MIN_TRACE_LENGTH.ilog2() as u8 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length cannot be smaller than 2^{}, but was 2^{}",
+                Self::MIN_TRACE_LENGTH.ilog2(),
+                length
+            )));
+        }
+        if length as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length cannot be greater than 2^{}, but was 2^{}",
+                usize::BITS - 1,
+                length
+            )));
+        }
+        let length = 2_usize.pow(length as u32);
+
+        // read trace metadata
+        let num_meta_bytes = source.read_u16()? as usize;
+        let meta = source.read_vec(num_meta_bytes)?;
+
+        Ok(Self::new_multi_segment(layout, length, meta))
+    }
+}
+
 // TRACE LAYOUT
 // ================================================================================================
 
@@ -170,7 +216,7 @@ impl TraceLayout {
     /// # Panics
     /// Panics if:
     /// * Width of the main trace segment is set to zero.
-    /// * Sum of all segment widths exceeds 255.
+    /// * Sum of all segment widths exceeds 65535.
     /// * A zero entry in auxiliary segment width array is followed by a non-zero entry.
     /// * Number of random elements for an auxiliary trace segment of non-zero width is set to zero.
     /// * Number of random elements for an auxiliary trace segment of zero width is set to non-zero.
@@ -232,7 +278,7 @@ impl TraceLayout {
 
     /// Returns the number of columns in the main segment of an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 65535.
     pub fn main_trace_width(&self) -> usize {
         self.main_segment_width
     }
@@ -268,11 +314,18 @@ impl TraceLayout {
 
 impl<E: StarkField> ToElements<E> for TraceLayout {
     fn to_elements(&self) -> Vec<E> {
+        // layouts with segments wider than 255 columns are encoded differently; layouts with
+        // narrower segments are encoded in the same way as before wider segments were supported,
+        // so that context elements (and thus, proofs) for such layouts remain unchanged
+        if self.has_wide_segments() {
+            return self.to_wide_elements();
+        }
+
         let mut result = Vec::new();
 
         // main segment width, number of auxiliary segments, and parameters of the first auxiliary
         // segment (if present) go into the first field element; we assume that each parameter can
-        // be encoded in 8 bits (which is enforced by the constructor)
+        // be encoded in 8 bits (which is checked above)
         let mut buf = self.main_segment_width as u32;
         buf = (buf << 8) | self.num_aux_segments as u32;
         if self.num_aux_segments == 1 {
@@ -292,13 +345,77 @@ impl<E: StarkField> ToElements<E> for TraceLayout {
     }
 }
 
+impl TraceLayout {
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes this layout into the specified `target` in the encoding used to compute AIR
+    /// fingerprints.
+    ///
+    /// Layouts without segments wider than 255 columns are written with segment widths encoded as
+    /// u8 values (as layouts were serialized before proof formats were versioned), so that
+    /// fingerprints of AIRs with such layouts remain unchanged; other layouts are written as a zero byte (which is never a valid
+    /// main segment width) followed by the serialized layout.
+    pub(crate) fn write_fingerprint_into<W: ByteWriter>(&self, target: &mut W) {
+        if self.has_wide_segments() {
+            target.write_u8(0);
+            self.write_into(target);
+        } else {
+            target.write_u8(self.main_segment_width as u8);
+            for &width in self.aux_segment_widths.iter() {
+                target.write_u8(width as u8);
+            }
+            for &num_rand_elements in self.aux_segment_rands.iter() {
+                target.write_u8(num_rand_elements as u8);
+            }
+        }
+    }
+
+    /// Returns true if any segment of this layout is wider than 255 columns.
+    fn has_wide_segments(&self) -> bool {
+        self.main_segment_width > u8::MAX as usize
+            || self.aux_segment_widths.iter().any(|&width| width > u8::MAX as usize)
+    }
+
+    /// Encodes this layout into field elements when a segment of the layout is wider than 255
+    /// columns.
+    ///
+    /// Main segment width, number of auxiliary segments, and parameters of the first auxiliary
+    /// segment (if present) go into the first field element, with segment widths encoded in 16
+    /// bits; bit 48 of the element is set, and thus, the element is never equal to the first
+    /// element of a layout with narrow segments (which is smaller than 2^32). The resulting values
+    /// are smaller than 2^49, and thus, are not reduced in any of the supported fields.
+    fn to_wide_elements<E: StarkField>(&self) -> Vec<E> {
+        let mut result = Vec::new();
+
+        let mut buf = 1u64;
+        buf = (buf << 16) | self.main_segment_width as u64;
+        buf = (buf << 8) | self.num_aux_segments as u64;
+        if self.num_aux_segments == 1 {
+            buf = (buf << 16) | self.aux_segment_widths[0] as u64;
+            buf = (buf << 8) | self.aux_segment_rands[0] as u64;
+        }
+        result.push(E::from(buf));
+
+        for i in 1..self.num_aux_segments {
+            buf = self.aux_segment_widths[i] as u64;
+            buf = (buf << 8) | self.aux_segment_rands[i] as u64;
+            result.push(E::from(buf));
+        }
+
+        result
+    }
+}
+
 impl Serializable for TraceLayout {
     /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// Segment widths are written as u16 values and numbers of random elements as u8 values.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_u8(self.main_segment_width as u8);
+        target.write_u16(self.main_segment_width as u16);
         for &w in self.aux_segment_widths.iter() {
-            debug_assert!(w <= u8::MAX as usize, "aux segment width does not fit into u8 value");
-            target.write_u8(w as u8);
+            debug_assert!(w <= u16::MAX as usize, "aux segment width does not fit into u16 value");
+            target.write_u16(w as u16);
         }
         for &rc in self.aux_segment_rands.iter() {
             debug_assert!(
@@ -317,7 +434,7 @@ impl Deserializable for TraceLayout {
     /// Returns an error of a valid [TraceLayout] struct could not be read from the specified
     /// `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let main_width = source.read_u8()? as usize;
+        let main_width = source.read_u16()? as usize;
         if main_width == 0 {
             return Err(DeserializationError::InvalidValue(
                 "main trace segment width must be greater than zero".to_string(),
@@ -328,7 +445,7 @@ impl Deserializable for TraceLayout {
         let mut was_zero_width = false;
         let mut aux_widths = [0; NUM_AUX_SEGMENTS];
         for width in aux_widths.iter_mut() {
-            *width = source.read_u8()? as usize;
+            *width = source.read_u16()? as usize;
             if *width != 0 {
                 if was_zero_width {
                     return Err(DeserializationError::InvalidValue(
@@ -341,7 +458,7 @@ impl Deserializable for TraceLayout {
         }
 
         let full_trace_width = main_width + aux_widths.iter().sum::<usize>();
-        if full_trace_width >= TraceInfo::MAX_TRACE_WIDTH {
+        if full_trace_width > TraceInfo::MAX_TRACE_WIDTH {
             return Err(DeserializationError::InvalidValue(format!(
                 "full trace width cannot be greater than {}, but was {}",
                 TraceInfo::MAX_TRACE_WIDTH,
@@ -380,8 +497,9 @@ impl Deserializable for TraceLayout {
 
 #[cfg(test)]
 mod tests {
-    use super::{ToElements, TraceLayout};
+    use super::{ToElements, TraceInfo, TraceLayout, Vec};
     use math::fields::f64::BaseElement;
+    use utils::{Deserializable, Serializable};
 
     #[test]
     fn trace_layout_to_elements() {
@@ -408,4 +526,32 @@ mod tests {
             TraceLayout::new(main_width as usize, [aux_width as usize], [aux_rands as usize]);
         assert_eq!(expected, layout.to_elements());
     }
+
+    #[test]
+    fn trace_info_serialization() {
+        let layout = TraceLayout::new(20, [5], [3]);
+        let info = TraceInfo::new_multi_segment(layout, 1024, vec![1, 2, 3]);
+        let bytes = info.to_bytes();
+        assert_eq!(info, TraceInfo::read_from_bytes(&bytes).unwrap());
+
+        // trace length smaller than the minimum should be rejected
+        let mut bytes = bytes;
+        bytes[5] = 2;
+        assert!(TraceInfo::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn wide_trace_layout() {
+        let layout = TraceLayout::new(1000, [300], [4]);
+        assert_eq!(1300, TraceInfo::new_multi_segment(layout.clone(), 1024, vec![]).width());
+        assert_eq!(layout, TraceLayout::read_from_bytes(&layout.to_bytes()).unwrap());
+
+        // wide layouts are encoded differently from layouts with the same parameters truncated to
+        // 8 bits
+        let expected = (1u64 << 48) | (1000 << 32) | (1 << 24) | (300 << 8) | 4;
+        assert_eq!(vec![BaseElement::from(expected)], layout.to_elements());
+        let narrow_layout = TraceLayout::new(1000 % 256, [300 % 256], [4]);
+        let narrow_elements: Vec<BaseElement> = narrow_layout.to_elements();
+        assert_ne!(narrow_elements, layout.to_elements());
+    }
 }
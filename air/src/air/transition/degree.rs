@@ -5,6 +5,7 @@
 
 use super::{super::super::ProofOptions, Vec, MIN_CYCLE_LENGTH};
 use core::cmp;
+use utils::{ByteWriter, Serializable};
 
 // TRANSITION CONSTRAINT DEGREE
 // ================================================================================================
@@ -99,6 +100,16 @@ impl TransitionConstraintDegree {
         result
     }
 
+    /// Returns the number of trace columns multiplied together by a constraint of this degree.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    /// Returns cycle lengths of periodic columns multiplied by a constraint of this degree.
+    pub fn cycles(&self) -> &[usize] {
+        &self.cycles
+    }
+
     /// Returns a minimum blowup factor needed to evaluate constraint of this degree.
     ///
     /// This is guaranteed to be a power of two, greater than one.
@@ -118,3 +129,14 @@ impl TransitionConstraintDegree {
         cmp::max(degree_bound.next_power_of_two(), ProofOptions::MIN_BLOWUP_FACTOR)
     }
 }
+
+impl Serializable for TransitionConstraintDegree {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.base);
+        target.write_usize(self.cycles.len());
+        for &cycle_length in self.cycles.iter() {
+            target.write_usize(cycle_length);
+        }
+    }
+}
@@ -5,6 +5,12 @@
 
 use super::{FieldElement, Vec};
 
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum degree of field extensions supported by proof options.
+const MAX_EXTENSION_DEGREE: usize = 3;
+
 // EVALUATION FRAME
 // ================================================================================================
 /// A set of execution trace rows required for evaluation of transition constraints.
@@ -64,6 +70,48 @@ impl<E: FieldElement> EvaluationFrame<E> {
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Reads the specified frame of the committed main trace segment into this frame, combining
+    /// coordinates of extension field columns into elements of `E`.
+    ///
+    /// The first `num_base_columns` columns of `frame` are copied into this frame as is. The
+    /// remaining columns of `frame` are split into groups of `E::EXTENSION_DEGREE` consecutive
+    /// columns, each holding coordinates of a single element of `E`. Values in `frame` may also
+    /// be elements of `E` (e.g., evaluations of trace polynomials at an out-of-domain point), in
+    /// which case the coordinates are combined in the same way.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Extension degree of `E` is greater than 3.
+    /// * The number of columns in `frame` is not consistent with the number of columns in this
+    ///   frame.
+    pub fn read_extension_columns<F>(&mut self, frame: &EvaluationFrame<F>, num_base_columns: usize)
+    where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: From<F>,
+    {
+        let degree = E::EXTENSION_DEGREE;
+        let num_extension_columns = self.current.len() - num_base_columns;
+        assert_eq!(
+            frame.current.len(),
+            num_base_columns + num_extension_columns * degree,
+            "frame of width {} cannot hold {num_base_columns} base field columns and {num_extension_columns} extension field columns",
+            frame.current.len()
+        );
+
+        // get elements of the basis of E over the base field; basis[i] is the element whose i-th
+        // coordinate is ONE and all other coordinates are ZERO
+        let mut basis = [E::ZERO; MAX_EXTENSION_DEGREE];
+        let mut coordinates = [E::BaseField::ZERO; MAX_EXTENSION_DEGREE];
+        for (i, element) in basis.iter_mut().enumerate().take(degree) {
+            coordinates[i] = E::BaseField::ONE;
+            *element = E::slice_from_base_elements(&coordinates[..degree])[0];
+            coordinates[i] = E::BaseField::ZERO;
+        }
+
+        combine_row(&frame.current, &mut self.current, num_base_columns, &basis[..degree]);
+        combine_row(&frame.next, &mut self.next, num_base_columns, &basis[..degree]);
+    }
+
     /// Returns a mutable reference to the current row.
     #[inline(always)]
     pub fn current_mut(&mut self) -> &mut [E] {
@@ -76,3 +124,26 @@ impl<E: FieldElement> EvaluationFrame<E> {
         &mut self.next
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Copies base field columns of the source row into the target row, and combines coordinates of
+/// each extension field column of the source row into a single element of the target row.
+fn combine_row<F, E>(source: &[F], target: &mut [E], num_base_columns: usize, basis: &[E])
+where
+    F: FieldElement,
+    E: FieldElement + From<F>,
+{
+    let (base_source, ext_source) = source.split_at(num_base_columns);
+    let (base_target, ext_target) = target.split_at_mut(num_base_columns);
+    for (target, &value) in base_target.iter_mut().zip(base_source) {
+        *target = E::from(value);
+    }
+    for (target, coordinates) in ext_target.iter_mut().zip(ext_source.chunks(basis.len())) {
+        *target = coordinates
+            .iter()
+            .zip(basis)
+            .fold(E::ZERO, |acc, (&coordinate, &element)| acc + element * E::from(coordinate));
+    }
+}
@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::fmt;
+use utils::string::String;
 
 // ASSERTION ERROR
 // ================================================================================================
@@ -42,3 +43,148 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+impl AssertionError {
+    /// Returns a stable code identifying the kind of this error.
+    ///
+    /// Codes have the form `assertion.<kind>` and are stable across releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::TraceWidthTooShort(_, _) => "assertion.trace_width_too_short",
+            Self::TraceLengthNotPowerOfTwo(_) => "assertion.trace_length_not_power_of_two",
+            Self::TraceLengthTooShort(_, _) => "assertion.trace_length_too_short",
+            Self::TraceLengthNotExact(_, _) => "assertion.trace_length_not_exact",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AssertionError {}
+
+// PROOF OPTIONS ERROR
+// ================================================================================================
+/// Represents an error returned when building [ProofOptions](crate::ProofOptions) via
+/// [ProofOptionsBuilder](crate::ProofOptionsBuilder) or when parsing them from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofOptionsError {
+    /// This error occurs when the number of queries is zero or is greater than 65535.
+    InvalidNumQueries(usize),
+    /// This error occurs when the blowup factor is not a power of two between 2 and 128.
+    InvalidBlowupFactor(usize),
+    /// This error occurs when the query seed grinding factor is greater than 32.
+    InvalidGrindingFactor(u32),
+    /// This error occurs when the FRI folding factor is not a power of two between 2 and 32.
+    InvalidFriFoldingFactor(usize),
+    /// This error occurs when the max degree of the FRI remainder is greater than 65535 or is not
+    /// one less than a power of two.
+    InvalidFriRemainderDegree(usize),
+    /// This error occurs when the FRI layer grinding factor is greater than 32.
+    InvalidFriLayerGrindingFactor(u32),
+    /// This error occurs when the number of out-of-domain points is zero or greater than 8.
+    InvalidNumOodPoints(usize),
+    /// This error occurs when the domain offset is zero or one.
+    InvalidDomainOffset(u64),
+    /// This error occurs when the domain offset is not smaller than the modulus of the base
+    /// field, or is in the subgroup spanned by the LDE domain of the specified size.
+    UnsafeDomainOffset(u64, usize),
+    /// This error occurs when the blowup factor is smaller than the blowup factor required to
+    /// evaluate the declared transition constraints.
+    BlowupFactorTooSmall(usize, usize),
+    /// This error occurs when the max degree of the FRI remainder is not smaller than the
+    /// declared trace length.
+    FriRemainderDegreeTooLarge(usize, usize),
+    /// This error occurs when distinct queries are required, but the number of queries is not
+    /// smaller than the size of the LDE domain implied by the declared trace length.
+    TooManyDistinctQueries(usize, usize),
+    /// This error occurs when an options string contains an unknown option.
+    UnknownOption(String),
+    /// This error occurs when an options string specifies the same option more than once.
+    DuplicateOption(String),
+    /// This error occurs when an option in an options string has a value which cannot be parsed.
+    InvalidOptionValue(String, String),
+}
+
+impl fmt::Display for ProofOptionsError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumQueries(actual) => {
+                write!(f, "number of queries must be between 1 and 65535, but was {actual}")
+            }
+            Self::InvalidBlowupFactor(actual) => {
+                write!(f, "blowup factor must be a power of two between 2 and 128, but was {actual}")
+            }
+            Self::InvalidGrindingFactor(actual) => {
+                write!(f, "grinding factor cannot be greater than 32, but was {actual}")
+            }
+            Self::InvalidFriFoldingFactor(actual) => {
+                write!(f, "FRI folding factor must be a power of two between 2 and 32, but was {actual}")
+            }
+            Self::InvalidFriRemainderDegree(actual) => {
+                write!(f, "FRI remainder max degree must be one less than a power of two and cannot be greater than 65535, but was {actual}")
+            }
+            Self::InvalidFriLayerGrindingFactor(actual) => {
+                write!(f, "FRI layer grinding factor cannot be greater than 32, but was {actual}")
+            }
+            Self::InvalidNumOodPoints(actual) => {
+                write!(f, "number of OOD points must be between 1 and 8, but was {actual}")
+            }
+            Self::InvalidDomainOffset(actual) => {
+                write!(f, "domain offset must be greater than 1, but was {actual}")
+            }
+            Self::UnsafeDomainOffset(offset, lde_domain_size) => {
+                write!(f, "domain offset {offset} is not a field element outside of the subgroup spanned by the LDE domain of size {lde_domain_size}")
+            }
+            Self::BlowupFactorTooSmall(expected, actual) => {
+                write!(f, "blowup factor too small for the declared constraint degrees; expected at least {expected}, but was {actual}")
+            }
+            Self::FriRemainderDegreeTooLarge(trace_length, actual) => {
+                write!(f, "FRI remainder max degree must be smaller than the trace length of {trace_length}, but was {actual}")
+            }
+            Self::TooManyDistinctQueries(lde_domain_size, actual) => {
+                write!(f, "number of distinct queries must be smaller than the LDE domain size of {lde_domain_size}, but was {actual}")
+            }
+            Self::UnknownOption(key) => {
+                write!(f, "option '{key}' is not a known proof option")
+            }
+            Self::DuplicateOption(key) => {
+                write!(f, "option '{key}' is specified more than once")
+            }
+            Self::InvalidOptionValue(key, value) => {
+                write!(f, "value '{value}' is not valid for option '{key}'")
+            }
+        }
+    }
+}
+
+impl ProofOptionsError {
+    /// Returns a stable code identifying the kind of this error.
+    ///
+    /// Codes have the form `proof_options.<kind>` and are stable across releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidNumQueries(_) => "proof_options.invalid_num_queries",
+            Self::InvalidBlowupFactor(_) => "proof_options.invalid_blowup_factor",
+            Self::InvalidGrindingFactor(_) => "proof_options.invalid_grinding_factor",
+            Self::InvalidFriFoldingFactor(_) => "proof_options.invalid_fri_folding_factor",
+            Self::InvalidFriRemainderDegree(_) => "proof_options.invalid_fri_remainder_degree",
+            Self::InvalidFriLayerGrindingFactor(_) => {
+                "proof_options.invalid_fri_layer_grinding_factor"
+            }
+            Self::InvalidNumOodPoints(_) => "proof_options.invalid_num_ood_points",
+            Self::InvalidDomainOffset(_) => "proof_options.invalid_domain_offset",
+            Self::UnsafeDomainOffset(_, _) => "proof_options.unsafe_domain_offset",
+            Self::BlowupFactorTooSmall(_, _) => "proof_options.blowup_factor_too_small",
+            Self::FriRemainderDegreeTooLarge(_, _) => {
+                "proof_options.fri_remainder_degree_too_large"
+            }
+            Self::TooManyDistinctQueries(_, _) => "proof_options.too_many_distinct_queries",
+            Self::UnknownOption(_) => "proof_options.unknown_option",
+            Self::DuplicateOption(_) => "proof_options.duplicate_option",
+            Self::InvalidOptionValue(_, _) => "proof_options.invalid_option_value",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProofOptionsError {}
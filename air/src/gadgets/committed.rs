@@ -0,0 +1,187 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::RpMerklePath;
+use crate::Assertion;
+use core::ops::Range;
+use crypto::{hashers::Rp64_256, Hasher, MerkleTree};
+use math::{fields::f64::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of data elements in a single leaf of the tree.
+const LEAF_SIZE: usize = 4;
+
+// COMMITTED PUBLIC DATA
+// ================================================================================================
+
+/// Public data of a computation committed to via a Merkle tree built with
+/// [Rp64_256](crypto::hashers::Rp64_256) hash function.
+///
+/// Statements over large amounts of public data (e.g., a batch of transactions) do not need to
+/// pass the data to the verifier in full. Instead, the data is split into leaves of 4 elements
+/// each, and only the root of the tree built from these leaves (together with the number of data
+/// elements) is included in the public inputs of the computation. The execution trace then
+/// contains an [RpMerklePath] component for each leaf the computation reads; the component proves
+/// that the leaf is a part of the committed data, and the AIR relates the leaf columns of the
+/// component (see [RpCommittedData::leaf_columns()]) to the rest of the trace. Thus, the size of
+/// the transcript and the work of the verifier depend only on the number of opened leaves and
+/// the depth of the tree rather than on the size of the data.
+///
+/// Data is padded with ZERO elements to fill the last leaf, and the number of leaves is padded to
+/// a power of two (but no fewer than 2) with leaves consisting of ZERO elements. Data with
+/// trailing ZERO elements could have the same root as shorter data; to make the commitment
+/// unambiguous, the number of data elements should be included in the public inputs as well.
+pub struct RpCommittedData {
+    tree: MerkleTree<Rp64_256>,
+    num_elements: usize,
+}
+
+impl RpCommittedData {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Number of data elements in a single leaf of the tree.
+    pub const LEAF_SIZE: usize = LEAF_SIZE;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to the specified data.
+    ///
+    /// # Panics
+    /// Panics if `data` is empty.
+    pub fn new(data: &[BaseElement]) -> Self {
+        assert!(!data.is_empty(), "committed data must contain at least one element");
+
+        let num_leaves = Self::num_leaves(data.len());
+        let mut leaves = Vec::with_capacity(num_leaves);
+        for chunk in data.chunks(LEAF_SIZE) {
+            let mut leaf = [BaseElement::ZERO; LEAF_SIZE];
+            leaf[..chunk.len()].copy_from_slice(chunk);
+            leaves.push(leaf.into());
+        }
+        leaves.resize(num_leaves, [BaseElement::ZERO; LEAF_SIZE].into());
+
+        let tree = MerkleTree::new(leaves).expect("number of leaves is a power of two");
+        Self {
+            tree,
+            num_elements: data.len(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree committing to the data.
+    pub fn root(&self) -> [BaseElement; 4] {
+        (*self.tree.root()).into()
+    }
+
+    /// Returns the root of the tree committing to the data as an
+    /// [Rp64_256](crypto::hashers::Rp64_256) digest.
+    pub fn commitment(&self) -> <Rp64_256 as Hasher>::Digest {
+        *self.tree.root()
+    }
+
+    /// Returns the number of data elements committed to (excluding padding).
+    pub fn num_elements(&self) -> usize {
+        self.num_elements
+    }
+
+    /// Returns the depth of the tree committing to the data.
+    pub fn depth(&self) -> usize {
+        self.tree.depth()
+    }
+
+    /// Returns the leaf at the specified index.
+    ///
+    /// # Panics
+    /// Panics if `index` is not smaller than the number of leaves in the tree.
+    pub fn get_leaf(&self, index: usize) -> [BaseElement; 4] {
+        self.tree.leaves()[index].into()
+    }
+
+    /// Returns the leaf at the specified index together with its authentication path (from the
+    /// leaf's sibling towards the root).
+    ///
+    /// # Panics
+    /// Panics if `index` is not smaller than the number of leaves in the tree.
+    pub fn open(&self, index: usize) -> ([BaseElement; 4], Vec<[BaseElement; 4]>) {
+        let path = self.tree.prove(index).expect("leaf index is out of bounds");
+        let leaf = path[0].into();
+        let path = path[1..].iter().map(|&node| node.into()).collect();
+        (leaf, path)
+    }
+
+    // CONSTRAINT DESCRIPTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns depth of the tree committing to data with the specified number of elements.
+    ///
+    /// This is the depth of the [RpMerklePath] components which open leaves of the data, and can
+    /// be computed by the verifier from the public inputs.
+    pub fn tree_depth(num_elements: usize) -> usize {
+        Self::num_leaves(num_elements).ilog2() as usize
+    }
+
+    /// Returns the index of the leaf containing the data element at the specified index, together
+    /// with the position of the element in the leaf.
+    pub fn element_position(element_index: usize) -> (usize, usize) {
+        (element_index / LEAF_SIZE, element_index % LEAF_SIZE)
+    }
+
+    /// Returns the range of trace columns which contain the leaf opened by an [RpMerklePath]
+    /// component starting at `first_column`; the leaf is located in the first row of the
+    /// component.
+    ///
+    /// Transition constraints or assertions of the AIR should reference these columns to
+    /// constrain the opened data elements.
+    pub fn leaf_columns(first_column: usize, leaf_index: usize) -> Range<usize> {
+        let columns = RpMerklePath::leaf_columns(leaf_index);
+        first_column + columns.start..first_column + columns.end
+    }
+
+    /// Returns assertions which bind an [RpMerklePath] component starting at `first_column` and
+    /// `first_step` to the leaf at `leaf_index` of data with the specified number of elements
+    /// committed to by `root`.
+    ///
+    /// The value of the leaf itself is not asserted, and thus, is not revealed to the verifier.
+    ///
+    /// # Panics
+    /// Panics if `leaf_index` is not smaller than the number of leaves in the tree.
+    pub fn get_assertions(
+        first_column: usize,
+        first_step: usize,
+        num_elements: usize,
+        leaf_index: usize,
+        root: [BaseElement; 4],
+    ) -> Vec<Assertion<BaseElement>> {
+        let depth = Self::tree_depth(num_elements);
+        RpMerklePath::get_path_assertions(first_column, first_step, depth, leaf_index, root)
+    }
+
+    // WITNESS GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds the columns of an [RpMerklePath] component which opens the leaf at the specified
+    /// index.
+    ///
+    /// # Panics
+    /// Panics if `leaf_index` is not smaller than the number of leaves in the tree.
+    pub fn build_columns(&self, leaf_index: usize) -> Vec<Vec<BaseElement>> {
+        let (leaf, path) = self.open(leaf_index);
+        RpMerklePath::build_columns(leaf, leaf_index, &path)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn num_leaves(num_elements: usize) -> usize {
+        num_elements.div_ceil(LEAF_SIZE).next_power_of_two().max(2)
+    }
+}
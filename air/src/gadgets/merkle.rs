@@ -0,0 +1,334 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Assertion, TransitionConstraintDegree};
+use core::ops::Range;
+use crypto::hashers::Rp64_256;
+use math::{fields::f64::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Width of the Rp64_256 hasher state.
+const STATE_WIDTH: usize = Rp64_256::STATE_WIDTH;
+
+/// Number of rounds in a single Rp64_256 permutation.
+const NUM_ROUNDS: usize = Rp64_256::NUM_ROUNDS;
+
+/// Index of the first element of the digest in the hasher state.
+const DIGEST_START: usize = Rp64_256::DIGEST_RANGE.start;
+
+/// Index of the first element of the second half of the rate portion of the hasher state.
+const SIBLING_START: usize = DIGEST_START + DIGEST_SIZE;
+
+/// Number of field elements in an Rp64_256 digest.
+const DIGEST_SIZE: usize = 4;
+
+/// Index of the column holding index bits of the path.
+const INDEX_BIT_COL: usize = STATE_WIDTH;
+
+// RP64_256 MERKLE PATH COMPONENT
+// ================================================================================================
+
+/// AIR component which verifies a Merkle authentication path in a tree built with
+/// [Rp64_256](crypto::hashers::Rp64_256) hash function.
+///
+/// The component occupies [RpMerklePath::TRACE_WIDTH] columns laid out as follows:
+/// * Columns 0 through 11 contain the state of the hasher; the digest of a node is located in
+///   columns 4 through 7.
+/// * Column 12 contains the bit of the leaf index for the current level of the tree.
+///
+/// Each level of the tree is processed in a cycle of [RpMerklePath::CYCLE_LENGTH] rows. The first
+/// row of a cycle contains the initial state for merging a node with its sibling; the next 7 rows
+/// contain the hasher state after each of the 7 rounds of the Rescue permutation. Thus, the
+/// digest of the parent node is located in the last row of the cycle. When moving to the next
+/// cycle, the digest is copied either into the first (for index bit 0) or into the second (for
+/// index bit 1) half of the rate portion of the state, and the other half is filled with the
+/// sibling from the authentication path.
+///
+/// For a tree of depth `d`, the component requires `8 * d` rows; the root of the tree is located
+/// in the last row of these rows. Since the length of an execution trace must be a power of two,
+/// trees with depth which is not a power of two require the trace to be padded.
+pub struct RpMerklePath;
+
+impl RpMerklePath {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Number of trace columns used by this component.
+    pub const TRACE_WIDTH: usize = STATE_WIDTH + 1;
+
+    /// Number of rows required to process a single level of a Merkle tree.
+    pub const CYCLE_LENGTH: usize = NUM_ROUNDS + 1;
+
+    /// Number of transition constraints defined by this component.
+    pub const NUM_CONSTRAINTS: usize = STATE_WIDTH + 1;
+
+    /// Number of periodic columns used by this component.
+    pub const NUM_PERIODIC_COLUMNS: usize = 2 * STATE_WIDTH + 1;
+
+    // CONSTRAINT DESCRIPTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of trace rows required to verify a path in a tree of the specified
+    /// depth.
+    pub fn trace_length(depth: usize) -> usize {
+        depth * Self::CYCLE_LENGTH
+    }
+
+    /// Returns degrees of all transition constraints of this component.
+    pub fn transition_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+        let mut result = (0..STATE_WIDTH)
+            .map(|_| TransitionConstraintDegree::with_cycles(7, vec![Self::CYCLE_LENGTH]))
+            .collect::<Vec<_>>();
+        result.push(TransitionConstraintDegree::new(2));
+        result
+    }
+
+    /// Returns values of periodic columns required by this component.
+    ///
+    /// The first column is a flag which is set to ONE on rows where a round of the Rescue
+    /// permutation is applied; the next 24 columns contain round constants of the permutation.
+    pub fn get_periodic_column_values() -> Vec<Vec<BaseElement>> {
+        let mut hash_flag = vec![BaseElement::ONE; Self::CYCLE_LENGTH];
+        hash_flag[NUM_ROUNDS] = BaseElement::ZERO;
+
+        let mut result = vec![hash_flag];
+        for ark in [Rp64_256::ARK1, Rp64_256::ARK2] {
+            for i in 0..STATE_WIDTH {
+                let mut column = vec![BaseElement::ZERO; Self::CYCLE_LENGTH];
+                for (round, constants) in ark.iter().enumerate() {
+                    column[round] = constants[i];
+                }
+                result.push(column);
+            }
+        }
+        result
+    }
+
+    /// Evaluates transition constraints of this component.
+    ///
+    /// `current` and `next` must contain [RpMerklePath::TRACE_WIDTH] values of the component
+    /// columns in the current and next rows, `periodic_values` must contain values of the
+    /// columns returned from [RpMerklePath::get_periodic_column_values()], and the results are
+    /// written into the first [RpMerklePath::NUM_CONSTRAINTS] slots of `result`.
+    pub fn evaluate_transition<E: FieldElement + From<BaseElement>>(
+        current: &[E],
+        next: &[E],
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        debug_assert_eq!(Self::TRACE_WIDTH, current.len());
+        debug_assert_eq!(Self::TRACE_WIDTH, next.len());
+        debug_assert_eq!(Self::NUM_PERIODIC_COLUMNS, periodic_values.len());
+
+        let hash_flag = periodic_values[0];
+        let ark1 = &periodic_values[1..STATE_WIDTH + 1];
+        let ark2 = &periodic_values[STATE_WIDTH + 1..];
+
+        // when hash_flag = 1, enforce a single round of the Rescue permutation. a round maps
+        // state s to MDS * (MDS * s^7 + ark1)^(1/7) + ark2; to avoid computing the inverse
+        // S-box, we enforce MDS * s^7 + ark1 = (INV_MDS * (next - ark2))^7 instead.
+        let mut forward = [E::ZERO; STATE_WIDTH];
+        for (value, &s) in forward.iter_mut().zip(current) {
+            *value = exp7(s);
+        }
+        let forward = mds_multiply(&Rp64_256::MDS, &forward);
+
+        let mut backward = [E::ZERO; STATE_WIDTH];
+        for (i, value) in backward.iter_mut().enumerate() {
+            *value = next[i] - ark2[i];
+        }
+        let backward = mds_multiply(&Rp64_256::INV_MDS, &backward);
+
+        // when hash_flag = 0, make sure the next state is initialized for merging the digest in
+        // the current row with its sibling: the capacity is set to [8, 0, 0, 0], and the digest is
+        // moved into the first or the second half of the rate depending on the next index bit
+        let init_flag = E::ONE - hash_flag;
+        let bit = next[INDEX_BIT_COL];
+        let not_bit = E::ONE - bit;
+        let mut init = [E::ZERO; STATE_WIDTH];
+        init[0] = next[0] - E::from(BaseElement::new(2 * DIGEST_SIZE as u64));
+        init[1..DIGEST_START].copy_from_slice(&next[1..DIGEST_START]);
+        for i in 0..DIGEST_SIZE {
+            let digest = current[DIGEST_START + i];
+            init[DIGEST_START + i] = not_bit * (next[DIGEST_START + i] - digest);
+            init[SIBLING_START + i] = bit * (next[SIBLING_START + i] - digest);
+        }
+
+        for i in 0..STATE_WIDTH {
+            let round = forward[i] + ark1[i] - exp7(backward[i]);
+            result[i] = hash_flag * round + init_flag * init[i];
+        }
+
+        // index bits must always be binary
+        let bit = current[INDEX_BIT_COL];
+        result[STATE_WIDTH] = bit * bit - bit;
+    }
+
+    /// Returns assertions which bind the component to the specified leaf, leaf index, and root
+    /// of a tree of the specified depth.
+    ///
+    /// `first_column` is the index of the first column of the component in the execution trace,
+    /// and `first_step` is the step at which the component starts.
+    ///
+    /// # Panics
+    /// Panics if `index` is not smaller than 2^`depth`.
+    pub fn get_assertions(
+        first_column: usize,
+        first_step: usize,
+        depth: usize,
+        index: usize,
+        leaf: [BaseElement; 4],
+        root: [BaseElement; 4],
+    ) -> Vec<Assertion<BaseElement>> {
+        let mut result = Self::get_path_assertions(first_column, first_step, depth, index, root);
+
+        // the leaf must be placed into the rate according to the first index bit
+        let leaf_columns = Self::leaf_columns(index);
+        for (i, &value) in leaf_columns.zip(leaf.iter()) {
+            result.push(Assertion::single(first_column + i, first_step, value));
+        }
+
+        result
+    }
+
+    /// Returns assertions which bind the component to the specified leaf index and root of a
+    /// tree of the specified depth, leaving the value of the leaf unconstrained.
+    ///
+    /// This is useful when the leaf is not known to the verifier, e.g., when the leaf is an
+    /// element of committed data which the AIR constrains in some other way. The leaf is located
+    /// in [RpMerklePath::leaf_columns()] (relative to `first_column`) at `first_step`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not smaller than 2^`depth`.
+    pub fn get_path_assertions(
+        first_column: usize,
+        first_step: usize,
+        depth: usize,
+        index: usize,
+        root: [BaseElement; 4],
+    ) -> Vec<Assertion<BaseElement>> {
+        assert!(index < (1 << depth), "index {index} is not valid for a tree of depth {depth}");
+        let mut result = Vec::new();
+
+        // the capacity of the initial state must be set to [8, 0, 0, 0]
+        result.push(Assertion::single(
+            first_column,
+            first_step,
+            BaseElement::new(2 * DIGEST_SIZE as u64),
+        ));
+        for i in 1..DIGEST_START {
+            result.push(Assertion::single(first_column + i, first_step, BaseElement::ZERO));
+        }
+
+        // index bits must be equal to the bits of the leaf index
+        for level in 0..depth {
+            let bit = BaseElement::new(((index >> level) & 1) as u64);
+            let step = first_step + level * Self::CYCLE_LENGTH;
+            result.push(Assertion::single(first_column + INDEX_BIT_COL, step, bit));
+        }
+
+        // the last row must contain the root of the tree
+        let last_step = first_step + Self::trace_length(depth) - 1;
+        for (i, &value) in root.iter().enumerate() {
+            result.push(Assertion::single(first_column + DIGEST_START + i, last_step, value));
+        }
+
+        result
+    }
+
+    /// Returns the range of component columns which contain the leaf in the first row of the
+    /// component when verifying a path for the leaf at the specified index.
+    ///
+    /// The leaf occupies the first half of the rate portion of the hasher state if the index is
+    /// even, and the second half otherwise.
+    pub fn leaf_columns(index: usize) -> Range<usize> {
+        let leaf_start = if index & 1 == 0 { DIGEST_START } else { SIBLING_START };
+        leaf_start..leaf_start + DIGEST_SIZE
+    }
+
+    // WITNESS GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds the columns of this component for verifying that `leaf` is located at the specified
+    /// `index` in a tree given the authentication `path` (from the leaf's sibling towards the
+    /// root).
+    ///
+    /// The returned columns are [RpMerklePath::TRACE_WIDTH] vectors of
+    /// [RpMerklePath::trace_length()] elements each.
+    ///
+    /// # Panics
+    /// Panics if `index` is not smaller than 2^`path.len()`.
+    pub fn build_columns(
+        leaf: [BaseElement; 4],
+        index: usize,
+        path: &[[BaseElement; 4]],
+    ) -> Vec<Vec<BaseElement>> {
+        let depth = path.len();
+        assert!(index < (1 << depth), "index {index} is not valid for a tree of depth {depth}");
+
+        let trace_length = Self::trace_length(depth);
+        let mut columns = (0..Self::TRACE_WIDTH)
+            .map(|_| Vec::with_capacity(trace_length))
+            .collect::<Vec<_>>();
+
+        let mut digest = leaf;
+        for (level, sibling) in path.iter().enumerate() {
+            let bit = (index >> level) & 1;
+
+            // initialize the state for merging the current digest with its sibling
+            let mut state = [BaseElement::ZERO; STATE_WIDTH];
+            state[0] = BaseElement::new(2 * DIGEST_SIZE as u64);
+            let (left, right) = if bit == 0 {
+                (&digest, sibling)
+            } else {
+                (sibling, &digest)
+            };
+            state[DIGEST_START..SIBLING_START].copy_from_slice(left);
+            state[SIBLING_START..].copy_from_slice(right);
+
+            // record the state after each round of the permutation
+            for round in 0..Self::CYCLE_LENGTH {
+                if round > 0 {
+                    Rp64_256::apply_round(&mut state, round - 1);
+                }
+                for (column, &value) in columns.iter_mut().zip(state.iter()) {
+                    column.push(value);
+                }
+                columns[INDEX_BIT_COL].push(BaseElement::new(bit as u64));
+            }
+
+            digest.copy_from_slice(&state[DIGEST_START..SIBLING_START]);
+        }
+
+        columns
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+fn exp7<E: FieldElement>(x: E) -> E {
+    let x2 = x.square();
+    let x4 = x2.square();
+    x4 * x2 * x
+}
+
+#[inline(always)]
+fn mds_multiply<E: FieldElement + From<BaseElement>>(
+    matrix: &[[BaseElement; STATE_WIDTH]; STATE_WIDTH],
+    state: &[E; STATE_WIDTH],
+) -> [E; STATE_WIDTH] {
+    let mut result = [E::ZERO; STATE_WIDTH];
+    for (value, row) in result.iter_mut().zip(matrix) {
+        for (&coefficient, &element) in row.iter().zip(state) {
+            *value += E::from(coefficient) * element;
+        }
+    }
+    result
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Reusable AIR components for verifying STARK proofs inside of other computations.
+//!
+//! A component describes a fixed group of trace columns together with the transition
+//! constraints, periodic columns, and assertions over these columns, and comes with a matching
+//! witness generator which builds the columns. An [Air](crate::Air) implementation can embed a
+//! component by placing its columns at some offset in the execution trace and delegating to the
+//! component from [Air::evaluate_transition()](crate::Air::evaluate_transition),
+//! [Air::get_periodic_column_values()](crate::Air::get_periodic_column_values), and
+//! [Air::get_assertions()](crate::Air::get_assertions).
+//!
+//! Currently, the following components are available:
+//! * [RpMerklePath] - verifies a Merkle authentication path in a tree built with the
+//!   [Rp64_256](crypto::hashers::Rp64_256) hash function.
+//! * [RpCommittedData] - commits to large public data via an [Rp64_256](crypto::hashers::Rp64_256)
+//!   Merkle tree, so that the public inputs contain only the root of the tree, and elements of
+//!   the data read by the computation are opened in the trace via [RpMerklePath] components.
+//!
+//! Merkle path verification is the main hashing workload of a STARK verifier: every query
+//! against the trace, the constraint composition polynomial, and every FRI layer is checked
+//! against a Merkle commitment. Thus, proofs generated with [Rp64_256](crypto::hashers::Rp64_256)
+//! (a recursion-friendly hash function over the 64-bit field) can have their openings checked
+//! by this component. The remaining checks performed by the verifier (i.e., evaluating
+//! constraints at the out-of-domain point, computing DEEP composition, and folding FRI layers)
+//! are arithmetic over the field referenced by the proof and are not yet provided as components.
+
+mod merkle;
+pub use merkle::RpMerklePath;
+
+mod committed;
+pub use committed::RpCommittedData;
+
+#[cfg(test)]
+mod tests;
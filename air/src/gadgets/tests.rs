@@ -0,0 +1,165 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{RpCommittedData, RpMerklePath};
+use crypto::{hashers::Rp64_256, ElementHasher, Hasher, MerkleTree};
+use math::{fields::f64::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+type Digest = <Rp64_256 as Hasher>::Digest;
+
+// RP64_256 MERKLE PATH
+// ================================================================================================
+
+#[test]
+fn rp_merkle_path_witness() {
+    let (tree, leaves) = build_tree(16);
+    let index = 5;
+    let path = get_path(&tree, index);
+
+    let columns = RpMerklePath::build_columns(leaves[index], index, &path);
+    assert_eq!(RpMerklePath::TRACE_WIDTH, columns.len());
+    assert_eq!(RpMerklePath::trace_length(4), columns[0].len());
+
+    // all transition constraints should be satisfied
+    assert!(evaluate_transitions(&columns).iter().all(|&value| value == BaseElement::ZERO));
+
+    // all assertions should be satisfied, and the last row should contain the root of the tree
+    let root = digest_to_elements(tree.root());
+    let assertions = RpMerklePath::get_assertions(0, 0, 4, index, leaves[index], root);
+    for assertion in assertions {
+        assertion.apply(columns[0].len(), |step, value| {
+            assert_eq!(value, columns[assertion.column()][step], "{assertion} is not satisfied");
+        });
+    }
+}
+
+#[test]
+fn rp_merkle_path_witness_tampered() {
+    let (tree, leaves) = build_tree(8);
+    let index = 2;
+    let mut path = get_path(&tree, index);
+
+    // an invalid sibling still results in a valid transition, but the root is different
+    path[1][0] += BaseElement::ONE;
+    let columns = RpMerklePath::build_columns(leaves[index], index, &path);
+    assert!(evaluate_transitions(&columns).iter().all(|&value| value == BaseElement::ZERO));
+    let last_step = columns[0].len() - 1;
+    let root = digest_to_elements(tree.root());
+    assert_ne!(root.to_vec(), (4..8).map(|i| columns[i][last_step]).collect::<Vec<_>>());
+
+    // changing a value of the hasher state violates round constraints
+    let path = get_path(&tree, index);
+    let mut columns = RpMerklePath::build_columns(leaves[index], index, &path);
+    columns[6][11] += BaseElement::ONE;
+    assert!(evaluate_transitions(&columns).iter().any(|&value| value != BaseElement::ZERO));
+
+    // moving the digest into the wrong half of the rate violates initialization constraints
+    let mut columns = RpMerklePath::build_columns(leaves[index], index, &path);
+    for i in 0..4 {
+        columns.swap(4 + i, 8 + i);
+    }
+    assert!(evaluate_transitions(&columns).iter().any(|&value| value != BaseElement::ZERO));
+}
+
+// COMMITTED PUBLIC DATA
+// ================================================================================================
+
+#[test]
+fn rp_committed_data_layout() {
+    let data = (0..21).map(|i| BaseElement::new(i as u64)).collect::<Vec<_>>();
+    let committed = RpCommittedData::new(&data);
+
+    // 21 elements fill 6 leaves, which are padded to 8 leaves
+    assert_eq!(21, committed.num_elements());
+    assert_eq!(3, committed.depth());
+    assert_eq!(3, RpCommittedData::tree_depth(data.len()));
+    assert_eq!((5, 1), RpCommittedData::element_position(21));
+    let zero = BaseElement::ZERO;
+    assert_eq!([data[20], zero, zero, zero], committed.get_leaf(5));
+    assert_eq!([zero; 4], committed.get_leaf(7));
+
+    // the root is the root of a tree built from the padded leaves
+    let leaves = (0..8).map(|i| committed.get_leaf(i).into()).collect::<Vec<Digest>>();
+    let tree = MerkleTree::<Rp64_256>::new(leaves).unwrap();
+    assert_eq!(*tree.root(), committed.commitment());
+    assert_eq!(digest_to_elements(tree.root()), committed.root());
+
+    // a single element is committed to by a tree with two leaves
+    assert_eq!(1, RpCommittedData::new(&data[..1]).depth());
+}
+
+#[test]
+fn rp_committed_data_opening() {
+    let data = (0..64).map(|i| BaseElement::new(i as u64 * 3)).collect::<Vec<_>>();
+    let committed = RpCommittedData::new(&data);
+    let (leaf_index, offset) = RpCommittedData::element_position(27);
+
+    let columns = committed.build_columns(leaf_index);
+    assert_eq!(RpMerklePath::trace_length(4), columns[0].len());
+    assert!(evaluate_transitions(&columns).iter().all(|&value| value == BaseElement::ZERO));
+
+    // assertions bind the component to the root without revealing the leaf
+    let assertions =
+        RpCommittedData::get_assertions(0, 0, data.len(), leaf_index, committed.root());
+    let leaf_columns = RpCommittedData::leaf_columns(0, leaf_index);
+    for assertion in assertions.iter() {
+        assert!(!leaf_columns.contains(&assertion.column()) || assertion.first_step() != 0);
+        assertion.apply(columns[0].len(), |step, value| {
+            assert_eq!(value, columns[assertion.column()][step], "{assertion} is not satisfied");
+        });
+    }
+
+    // the opened element is located in the leaf columns of the first row
+    assert_eq!(data[27], columns[leaf_columns.start + offset][0]);
+
+    // the root of different data does not satisfy the assertions
+    let mut other_data = data.clone();
+    other_data[27] += BaseElement::ONE;
+    let other = RpCommittedData::new(&other_data);
+    let columns = other.build_columns(leaf_index);
+    let last_step = columns[0].len() - 1;
+    assert_ne!(
+        committed.root().to_vec(),
+        (4..8).map(|i| columns[i][last_step]).collect::<Vec<_>>()
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_tree(num_leaves: usize) -> (MerkleTree<Rp64_256>, Vec<[BaseElement; 4]>) {
+    let leaves = (0..num_leaves)
+        .map(|i| Rp64_256::hash_elements(&[BaseElement::new(i as u64)]))
+        .collect::<Vec<Digest>>();
+    let tree = MerkleTree::new(leaves.clone()).unwrap();
+    (tree, leaves.iter().map(digest_to_elements).collect())
+}
+
+fn get_path(tree: &MerkleTree<Rp64_256>, index: usize) -> Vec<[BaseElement; 4]> {
+    tree.prove(index).unwrap().iter().skip(1).map(digest_to_elements).collect()
+}
+
+fn digest_to_elements(digest: &Digest) -> [BaseElement; 4] {
+    (*digest).into()
+}
+
+/// Evaluates transition constraints over all pairs of consecutive rows and returns the results.
+fn evaluate_transitions(columns: &[Vec<BaseElement>]) -> Vec<BaseElement> {
+    let periodic_columns = RpMerklePath::get_periodic_column_values();
+    let mut result = Vec::new();
+    for step in 0..columns[0].len() - 1 {
+        let current = columns.iter().map(|column| column[step]).collect::<Vec<_>>();
+        let next = columns.iter().map(|column| column[step + 1]).collect::<Vec<_>>();
+        let periodic_values = periodic_columns
+            .iter()
+            .map(|column| column[step % column.len()])
+            .collect::<Vec<_>>();
+        let mut evaluations = vec![BaseElement::ZERO; RpMerklePath::NUM_CONSTRAINTS];
+        RpMerklePath::evaluate_transition(&current, &next, &periodic_values, &mut evaluations);
+        result.extend_from_slice(&evaluations);
+    }
+    result
+}
@@ -0,0 +1,765 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Export of constraint systems described by AIRs into a portable intermediate representation.
+//!
+//! A [ConstraintSystem] is extracted from an [Air] by evaluating its transition constraints and
+//! assertions over [symbolic](math::symbolic) field elements. Thus, the exported representation
+//! describes the expressions which the AIR actually computes (rather than the expressions which
+//! the author of the AIR intended to compute), and can be audited, diffed across versions of an
+//! AIR, and consumed by external analysis tools.
+//!
+//! A constraint system consists of:
+//! * A list of [Variable]s: values of trace columns in the current and the next rows of an
+//!   evaluation frame, values of periodic columns, and random elements drawn for auxiliary trace
+//!   segments.
+//! * A list of expression [Node]s over these variables. Arguments of every node precede the node
+//!   in the list, every expression appears in the list once, and the first two nodes are
+//!   constants ZERO and ONE.
+//! * Transition constraints for the main and the auxiliary trace segments, each referring to the
+//!   node which evaluates to zero when the constraint is satisfied.
+//! * Assertions for the main and the auxiliary trace segments, with asserted values referring to
+//!   nodes (values of auxiliary assertions may depend on random elements).
+//!
+//! The JSON representation of a constraint system returned by
+//! [to_json()](ConstraintSystem::to_json) has the following layout (field elements are written
+//! as hex strings of their little-endian encodings):
+//!
+//! ```text
+//! {
+//!   "version": 1,
+//!   "air_id", "air_fingerprint", "field_modulus",
+//!   "trace": { "main_width", "aux_widths": [..], "aux_rands": [..], "length" },
+//!   "num_transition_exemptions",
+//!   "periodic_columns": [[<value>, ..], ..],
+//!   "variables": [{ "kind", "index" [, "segment"] }, ..],
+//!   "nodes": [{ "op", "value" | "variable" | "args": [..] [, "power"] }, ..],
+//!   "transition_constraints": [
+//!     { "node", "degree": { "base", "cycles": [..] }, "expression_degree" }, ..
+//!   ],
+//!   "aux_transition_constraints": [..],
+//!   "assertions": [{ "column", "first_step", "stride", "values": [<node>, ..] }, ..],
+//!   "aux_assertions": [..]
+//! }
+//! ```
+//!
+//! Variable kinds are `main_current`, `main_next`, `aux_current`, `aux_next`, `periodic`, and
+//! `aux_rand`; node operations are `const`, `var`, `add`, `sub`, `mul`, `neg`, `inv`, and `exp`.
+//! The CBOR representation returned by [to_cbor()](ConstraintSystem::to_cbor) has the same
+//! structure, with field elements encoded as byte strings.
+//!
+//! This module is available only when the crate is compiled with both `export` and `std`
+//! features.
+
+use crate::{
+    proof::export::Value, Air, Assertion, AuxTraceRandElements, EvaluationFrame,
+    TransitionConstraintDegree,
+};
+use math::{
+    symbolic::{self, Node, Symbolic},
+    FieldElement, StarkField,
+};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the constraint system export schema.
+pub const IR_VERSION: u64 = 1;
+
+// VARIABLES
+// ================================================================================================
+
+/// An input of the expressions of a constraint system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variable {
+    /// Value of the main trace column with the specified index in the current row.
+    ///
+    /// If some columns of the main trace segment hold extension field elements, the index refers
+    /// to a column of the main evaluation frame (see
+    /// [AirContext::main_frame_width()](crate::AirContext::main_frame_width)).
+    MainCurrent(usize),
+    /// Value of the main trace column with the specified index in the next row.
+    MainNext(usize),
+    /// Value of the auxiliary trace column with the specified index (across all auxiliary
+    /// segments) in the current row.
+    AuxCurrent(usize),
+    /// Value of the auxiliary trace column with the specified index (across all auxiliary
+    /// segments) in the next row.
+    AuxNext(usize),
+    /// Value of the periodic column with the specified index.
+    Periodic(usize),
+    /// Random element with the specified index drawn for the auxiliary trace segment with the
+    /// specified index.
+    AuxRand { segment: usize, index: usize },
+}
+
+// CONSTRAINTS
+// ================================================================================================
+
+/// A transition constraint of a constraint system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionConstraint {
+    node: usize,
+    degree: TransitionConstraintDegree,
+    expression_degree: Option<usize>,
+}
+
+impl TransitionConstraint {
+    /// Returns the index of the node which evaluates to zero when this constraint is satisfied.
+    pub fn node(&self) -> usize {
+        self.node
+    }
+
+    /// Returns the degree of this constraint as declared by the AIR.
+    pub fn degree(&self) -> &TransitionConstraintDegree {
+        &self.degree
+    }
+
+    /// Returns the degree of the expression of this constraint in trace columns, or None if the
+    /// expression is not a polynomial in trace columns (i.e., it inverts a trace column).
+    ///
+    /// Periodic columns and random elements are treated as constants, and thus, the returned
+    /// degree is comparable to the base of the declared [degree()](Self::degree).
+    pub fn expression_degree(&self) -> Option<usize> {
+        self.expression_degree
+    }
+}
+
+/// An assertion of a constraint system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundaryAssertion {
+    column: usize,
+    first_step: usize,
+    stride: usize,
+    values: Vec<usize>,
+}
+
+impl BoundaryAssertion {
+    /// Returns the index of the column against which this assertion is placed.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the first step of the execution trace against which this assertion is placed.
+    pub fn first_step(&self) -> usize {
+        self.first_step
+    }
+
+    /// Returns the interval at which this assertion repeats; zero for single assertions.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns indexes of the nodes which evaluate to the asserted values.
+    pub fn values(&self) -> &[usize] {
+        &self.values
+    }
+}
+
+// CONSTRAINT SYSTEM
+// ================================================================================================
+
+/// Constraint system described by an AIR in a portable intermediate representation.
+///
+/// See [module documentation](self) for details.
+#[derive(Debug, Clone)]
+pub struct ConstraintSystem<B: StarkField> {
+    air_id: u32,
+    air_fingerprint: [u8; 32],
+    main_width: usize,
+    aux_widths: Vec<usize>,
+    aux_rands: Vec<usize>,
+    trace_length: usize,
+    num_transition_exemptions: usize,
+    periodic_columns: Vec<Vec<B>>,
+    variables: Vec<Variable>,
+    nodes: Vec<Node<B>>,
+    main_constraints: Vec<TransitionConstraint>,
+    aux_constraints: Vec<TransitionConstraint>,
+    main_assertions: Vec<BoundaryAssertion>,
+    aux_assertions: Vec<BoundaryAssertion>,
+}
+
+impl<B: StarkField> ConstraintSystem<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the constraint system described by the specified AIR.
+    ///
+    /// Nodes which do not contribute to any of the constraints or asserted values are discarded.
+    ///
+    /// # Panics
+    /// Panics if the AIR performs operations which cannot be performed on symbolic elements
+    /// (e.g., branches on values of trace columns or converts them into integers).
+    pub fn from_air<A: Air<BaseField = B>>(air: &A) -> Self {
+        let context = air.context();
+        let layout = air.trace_layout();
+        let main_width = layout.main_trace_width();
+        let main_frame_width = context.main_frame_width();
+        let aux_width = layout.aux_trace_width();
+        let periodic_columns = air.get_periodic_column_values();
+
+        // build the list of variables; the index of a variable in the list is the index of the
+        // corresponding symbolic element
+        let mut variables = Vec::new();
+        variables.extend((0..main_frame_width).map(Variable::MainCurrent));
+        variables.extend((0..main_frame_width).map(Variable::MainNext));
+        variables.extend((0..aux_width).map(Variable::AuxCurrent));
+        variables.extend((0..aux_width).map(Variable::AuxNext));
+        variables.extend((0..periodic_columns.len()).map(Variable::Periodic));
+        for segment in 0..layout.num_aux_segments() {
+            let num_rands = layout.get_aux_segment_rand_elements(segment);
+            variables.extend((0..num_rands).map(|index| Variable::AuxRand { segment, index }));
+        }
+
+        // evaluate constraints and assertions over symbolic elements
+        let ((roots, main_assertions, aux_assertions), nodes) = symbolic::record::<B, _>(|| {
+            let mut inputs = (0..variables.len()).map(Symbolic::<B>::variable);
+            let mut take = |n: usize| inputs.by_ref().take(n).collect::<Vec<_>>();
+
+            let main_frame =
+                EvaluationFrame::from_rows(take(main_frame_width), take(main_frame_width));
+            let aux_frame = EvaluationFrame::from_rows(take(aux_width), take(aux_width));
+            let periodic_values = take(periodic_columns.len());
+            let mut aux_rand_elements = AuxTraceRandElements::new();
+            for segment in 0..layout.num_aux_segments() {
+                let elements = take(layout.get_aux_segment_rand_elements(segment));
+                aux_rand_elements.add_segment_elements(elements);
+            }
+
+            let mut roots = vec![Symbolic::ZERO; context.num_transition_constraints()];
+            let (main_roots, aux_roots) =
+                roots.split_at_mut(context.num_main_transition_constraints());
+            air.evaluate_transition(&main_frame, &periodic_values, main_roots);
+            if layout.num_aux_segments() > 0 {
+                air.evaluate_aux_transition(
+                    &main_frame,
+                    &aux_frame,
+                    &periodic_values,
+                    &aux_rand_elements,
+                    aux_roots,
+                );
+            }
+            let roots = roots.iter().map(Symbolic::index).collect::<Vec<_>>();
+
+            let main_assertions = air
+                .get_assertions()
+                .into_iter()
+                .map(|assertion| {
+                    build_assertion(&assertion, |&value| Symbolic::constant(value).index())
+                })
+                .collect::<Vec<_>>();
+            let aux_assertions = if layout.num_aux_segments() > 0 {
+                air.get_aux_assertions(&aux_rand_elements)
+                    .iter()
+                    .map(|assertion| build_assertion(assertion, Symbolic::index))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            (roots, main_assertions, aux_assertions)
+        });
+
+        // discard nodes which are not reachable from constraints and assertions
+        let value_roots = main_assertions.iter().chain(aux_assertions.iter());
+        let value_roots = value_roots.flat_map(|assertion| assertion.values.iter().copied());
+        let (nodes, new_indexes) = prune(nodes, roots.iter().copied().chain(value_roots));
+        let expression_degrees = get_expression_degrees(&nodes, &variables);
+
+        let build_constraints = |roots: &[usize], degrees: &[TransitionConstraintDegree]| {
+            roots
+                .iter()
+                .zip(degrees)
+                .map(|(&root, degree)| TransitionConstraint {
+                    node: new_indexes[root],
+                    degree: degree.clone(),
+                    expression_degree: expression_degrees[new_indexes[root]],
+                })
+                .collect::<Vec<_>>()
+        };
+        let (main_roots, aux_roots) = roots.split_at(context.num_main_transition_constraints());
+        let main_constraints =
+            build_constraints(main_roots, context.main_transition_constraint_degrees());
+        let aux_constraints =
+            build_constraints(aux_roots, context.aux_transition_constraint_degrees());
+
+        let renumber = |assertions: Vec<BoundaryAssertion>| {
+            assertions
+                .into_iter()
+                .map(|mut assertion| {
+                    assertion.values.iter_mut().for_each(|value| *value = new_indexes[*value]);
+                    assertion
+                })
+                .collect()
+        };
+
+        ConstraintSystem {
+            air_id: A::AIR_ID,
+            air_fingerprint: air.fingerprint(),
+            main_width,
+            aux_widths: (0..layout.num_aux_segments())
+                .map(|i| layout.get_aux_segment_width(i))
+                .collect(),
+            aux_rands: (0..layout.num_aux_segments())
+                .map(|i| layout.get_aux_segment_rand_elements(i))
+                .collect(),
+            trace_length: air.trace_length(),
+            num_transition_exemptions: context.num_transition_exemptions(),
+            periodic_columns,
+            variables,
+            nodes,
+            main_constraints,
+            aux_constraints,
+            main_assertions: renumber(main_assertions),
+            aux_assertions: renumber(aux_assertions),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the inputs of the expressions of this constraint system; [Node::Variable] refers
+    /// to a variable by its index in the returned list.
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    /// Returns the nodes of the expressions of this constraint system.
+    pub fn nodes(&self) -> &[Node<B>] {
+        &self.nodes
+    }
+
+    /// Returns values of periodic columns of this constraint system.
+    pub fn periodic_columns(&self) -> &[Vec<B>] {
+        &self.periodic_columns
+    }
+
+    /// Returns transition constraints placed against the main trace segment.
+    pub fn main_transition_constraints(&self) -> &[TransitionConstraint] {
+        &self.main_constraints
+    }
+
+    /// Returns transition constraints placed against all auxiliary trace segments.
+    pub fn aux_transition_constraints(&self) -> &[TransitionConstraint] {
+        &self.aux_constraints
+    }
+
+    /// Returns assertions placed against the main trace segment.
+    pub fn main_assertions(&self) -> &[BoundaryAssertion] {
+        &self.main_assertions
+    }
+
+    /// Returns assertions placed against all auxiliary trace segments.
+    pub fn aux_assertions(&self) -> &[BoundaryAssertion] {
+        &self.aux_assertions
+    }
+
+    // EXPORT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a JSON representation of this constraint system.
+    ///
+    /// See [module documentation](self) for the description of the schema.
+    pub fn to_json(&self) -> String {
+        let mut result = String::new();
+        self.export().write_json(&mut result);
+        result
+    }
+
+    /// Returns a CBOR ([RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)) representation of this
+    /// constraint system.
+    ///
+    /// The representation has the same structure as the one returned by
+    /// [to_json()](ConstraintSystem::to_json), with field elements encoded as CBOR byte strings.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.export().write_cbor(&mut result);
+        result
+    }
+
+    /// Builds a tree of named sections describing this constraint system.
+    fn export(&self) -> Value {
+        let usizes =
+            |values: &[usize]| Value::List(values.iter().copied().map(Value::from_usize).collect());
+
+        let trace = Value::Map(vec![
+            ("main_width", Value::from_usize(self.main_width)),
+            ("aux_widths", usizes(&self.aux_widths)),
+            ("aux_rands", usizes(&self.aux_rands)),
+            ("length", Value::from_usize(self.trace_length)),
+        ]);
+
+        let periodic_columns = self
+            .periodic_columns
+            .iter()
+            .map(|column| Value::List(column.iter().map(export_element).collect()))
+            .collect();
+
+        let variables = self.variables.iter().map(export_variable).collect();
+        let nodes = self.nodes.iter().map(export_node).collect();
+
+        let export_constraints = |constraints: &[TransitionConstraint]| {
+            Value::List(
+                constraints
+                    .iter()
+                    .map(|constraint| {
+                        let degree = Value::Map(vec![
+                            ("base", Value::from_usize(constraint.degree.base())),
+                            ("cycles", usizes(constraint.degree.cycles())),
+                        ]);
+                        let expression_degree =
+                            constraint.expression_degree.map_or(Value::Null, Value::from_usize);
+                        Value::Map(vec![
+                            ("node", Value::from_usize(constraint.node)),
+                            ("degree", degree),
+                            ("expression_degree", expression_degree),
+                        ])
+                    })
+                    .collect(),
+            )
+        };
+
+        let export_assertions = |assertions: &[BoundaryAssertion]| {
+            Value::List(
+                assertions
+                    .iter()
+                    .map(|assertion| {
+                        Value::Map(vec![
+                            ("column", Value::from_usize(assertion.column)),
+                            ("first_step", Value::from_usize(assertion.first_step)),
+                            ("stride", Value::from_usize(assertion.stride)),
+                            ("values", usizes(&assertion.values)),
+                        ])
+                    })
+                    .collect(),
+            )
+        };
+
+        Value::Map(vec![
+            ("version", Value::UInt(IR_VERSION)),
+            ("air_id", Value::UInt(self.air_id as u64)),
+            ("air_fingerprint", Value::Bytes(self.air_fingerprint.to_vec())),
+            ("field_modulus", Value::Bytes(B::get_modulus_le_bytes())),
+            ("trace", trace),
+            ("num_transition_exemptions", Value::from_usize(self.num_transition_exemptions)),
+            ("periodic_columns", Value::List(periodic_columns)),
+            ("variables", Value::List(variables)),
+            ("nodes", Value::List(nodes)),
+            ("transition_constraints", export_constraints(&self.main_constraints)),
+            ("aux_transition_constraints", export_constraints(&self.aux_constraints)),
+            ("assertions", export_assertions(&self.main_assertions)),
+            ("aux_assertions", export_assertions(&self.aux_assertions)),
+        ])
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts the specified assertion into an assertion whose values are indexes of nodes.
+fn build_assertion<E: FieldElement>(
+    assertion: &Assertion<E>,
+    mut to_node: impl FnMut(&E) -> usize,
+) -> BoundaryAssertion {
+    BoundaryAssertion {
+        column: assertion.column(),
+        first_step: assertion.first_step(),
+        stride: assertion.stride(),
+        values: assertion.values().iter().map(&mut to_node).collect(),
+    }
+}
+
+/// Removes nodes which are not reachable from the specified roots (except for the first two
+/// constant nodes) and returns the remaining nodes together with a map from old indexes of nodes
+/// to their new indexes.
+fn prune<B: StarkField>(
+    nodes: Vec<Node<B>>,
+    roots: impl Iterator<Item = usize>,
+) -> (Vec<Node<B>>, Vec<usize>) {
+    // arguments precede the nodes which refer to them, and thus, reachability can be determined
+    // in a single pass from the last node to the first one
+    let mut reachable = vec![false; nodes.len()];
+    reachable[0] = true;
+    reachable[1] = true;
+    roots.for_each(|root| reachable[root] = true);
+    for (i, node) in nodes.iter().enumerate().rev() {
+        if reachable[i] {
+            for argument in arguments(node) {
+                reachable[argument] = true;
+            }
+        }
+    }
+
+    let mut new_indexes = vec![usize::MAX; nodes.len()];
+    let mut result = Vec::new();
+    for (i, node) in nodes.into_iter().enumerate() {
+        if reachable[i] {
+            new_indexes[i] = result.len();
+            result.push(match node {
+                Node::Add(a, b) => Node::Add(new_indexes[a], new_indexes[b]),
+                Node::Sub(a, b) => Node::Sub(new_indexes[a], new_indexes[b]),
+                Node::Mul(a, b) => Node::Mul(new_indexes[a], new_indexes[b]),
+                Node::Neg(a) => Node::Neg(new_indexes[a]),
+                Node::Inv(a) => Node::Inv(new_indexes[a]),
+                Node::Exp(a, power) => Node::Exp(new_indexes[a], power),
+                node => node,
+            });
+        }
+    }
+    (result, new_indexes)
+}
+
+/// Returns indexes of arguments of the specified node.
+fn arguments<B: StarkField>(node: &Node<B>) -> Vec<usize> {
+    match *node {
+        Node::Constant(_) | Node::Variable(_) => vec![],
+        Node::Add(a, b) | Node::Sub(a, b) | Node::Mul(a, b) => vec![a, b],
+        Node::Neg(a) | Node::Inv(a) | Node::Exp(a, _) => vec![a],
+    }
+}
+
+/// Returns degrees of the specified nodes in trace columns; None indicates that a node is not a
+/// polynomial in trace columns.
+fn get_expression_degrees<B: StarkField>(
+    nodes: &[Node<B>],
+    variables: &[Variable],
+) -> Vec<Option<usize>> {
+    let mut result: Vec<Option<usize>> = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let degree = match *node {
+            Node::Constant(_) => Some(0),
+            Node::Variable(i) => match variables[i] {
+                Variable::MainCurrent(_)
+                | Variable::MainNext(_)
+                | Variable::AuxCurrent(_)
+                | Variable::AuxNext(_) => Some(1),
+                Variable::Periodic(_) | Variable::AuxRand { .. } => Some(0),
+            },
+            Node::Add(a, b) | Node::Sub(a, b) => result[a].zip(result[b]).map(|(a, b)| a.max(b)),
+            Node::Mul(a, b) => result[a].zip(result[b]).and_then(|(a, b)| a.checked_add(b)),
+            Node::Neg(a) => result[a],
+            Node::Inv(a) => result[a].filter(|&degree| degree == 0),
+            Node::Exp(a, power) => result[a].and_then(|degree| {
+                usize::try_from(power).ok().and_then(|power| degree.checked_mul(power))
+            }),
+        };
+        result.push(degree);
+    }
+    result
+}
+
+/// Returns the exported representation of the specified field element.
+fn export_element<B: StarkField>(value: &B) -> Value {
+    Value::Bytes(value.to_bytes())
+}
+
+/// Returns the exported representation of the specified variable.
+fn export_variable(variable: &Variable) -> Value {
+    let (kind, index) = match *variable {
+        Variable::MainCurrent(index) => ("main_current", index),
+        Variable::MainNext(index) => ("main_next", index),
+        Variable::AuxCurrent(index) => ("aux_current", index),
+        Variable::AuxNext(index) => ("aux_next", index),
+        Variable::Periodic(index) => ("periodic", index),
+        Variable::AuxRand { segment, index } => {
+            return Value::Map(vec![
+                ("kind", Value::Text("aux_rand".to_string())),
+                ("segment", Value::from_usize(segment)),
+                ("index", Value::from_usize(index)),
+            ]);
+        }
+    };
+    Value::Map(vec![
+        ("kind", Value::Text(kind.to_string())),
+        ("index", Value::from_usize(index)),
+    ])
+}
+
+/// Returns the exported representation of the specified node; powers which do not fit into 64
+/// bits are exported as 16-byte little-endian byte strings.
+fn export_node<B: StarkField>(node: &Node<B>) -> Value {
+    let op = |name: &str| ("op", Value::Text(name.to_string()));
+    let args = |args: &[usize]| {
+        ("args", Value::List(args.iter().copied().map(Value::from_usize).collect()))
+    };
+    Value::Map(match *node {
+        Node::Constant(value) => vec![op("const"), ("value", export_element(&value))],
+        Node::Variable(index) => vec![op("var"), ("variable", Value::from_usize(index))],
+        Node::Add(a, b) => vec![op("add"), args(&[a, b])],
+        Node::Sub(a, b) => vec![op("sub"), args(&[a, b])],
+        Node::Mul(a, b) => vec![op("mul"), args(&[a, b])],
+        Node::Neg(a) => vec![op("neg"), args(&[a])],
+        Node::Inv(a) => vec![op("inv"), args(&[a])],
+        Node::Exp(a, power) => {
+            let power = match u64::try_from(power) {
+                Ok(power) => Value::UInt(power),
+                Err(_) => Value::Bytes(power.to_le_bytes().to_vec()),
+            };
+            vec![op("exp"), args(&[a]), ("power", power)]
+        }
+    })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ConstraintSystem, Node, Variable, IR_VERSION};
+    use crate::{
+        Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, FieldExtension,
+        ProofOptions, TraceInfo, TraceLayout, TransitionConstraintDegree,
+    };
+    use math::{fields::f64::BaseElement, ExtensionOf, FieldElement};
+    use utils::collections::Vec;
+
+    #[test]
+    fn constraint_system_from_air() {
+        let air = TestAir::new(
+            TraceInfo::new(2, 16),
+            (),
+            ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
+        );
+        let system = ConstraintSystem::from_air(&air);
+        let nodes = system.nodes();
+        let node_of = |variable| {
+            let index = system.variables().iter().position(|&v| v == variable).unwrap();
+            nodes.iter().position(|node| *node == Node::Variable(index)).unwrap()
+        };
+
+        assert_eq!(Node::Constant(BaseElement::ZERO), nodes[0]);
+        assert_eq!(Node::Constant(BaseElement::ONE), nodes[1]);
+        assert_eq!(8, system.variables().len());
+
+        // next[0] - (current[0] + current[1])
+        let constraints = system.main_transition_constraints();
+        let sum = match nodes[constraints[0].node()] {
+            Node::Sub(next, sum) => {
+                assert_eq!(node_of(Variable::MainNext(0)), next);
+                sum
+            }
+            ref node => panic!("unexpected node {node:?}"),
+        };
+        let (a, b) = (node_of(Variable::MainCurrent(0)), node_of(Variable::MainCurrent(1)));
+        assert_eq!(Node::Add(a, b), nodes[sum]);
+        assert_eq!(Some(1), constraints[0].expression_degree());
+        assert_eq!(Some(2), constraints[1].expression_degree());
+        assert_eq!(&[4], constraints[1].degree().cycles());
+
+        // the auxiliary constraint and assertion refer to the random element
+        let aux_constraints = system.aux_transition_constraints();
+        assert_eq!(1, aux_constraints.len());
+        assert_eq!(Some(2), aux_constraints[0].expression_degree());
+        let rand = node_of(Variable::AuxRand {
+            segment: 0,
+            index: 0,
+        });
+        assert_eq!(&[rand], system.aux_assertions()[0].values());
+
+        // main assertions refer to constants, and unused nodes are discarded
+        let value = system.main_assertions()[0].values()[0];
+        assert_eq!(Node::Constant(BaseElement::new(3)), nodes[value]);
+        assert!(!nodes.contains(&Node::Constant(BaseElement::new(5))));
+
+        let json = system.to_json();
+        assert!(json.starts_with(&format!("{{\"version\":{IR_VERSION},\"air_id\":0,")));
+        assert!(json.contains(
+            "\"variables\":[{\"kind\":\"main_current\",\"index\":0},\
+            {\"kind\":\"main_current\",\"index\":1},"
+        ));
+        assert!(json.contains("{\"kind\":\"aux_rand\",\"segment\":0,\"index\":0}]"));
+        assert!(json.contains("\"nodes\":[{\"op\":\"const\",\"value\":\"0000000000000000\"},"));
+
+        // a map with 13 sections, starting with the version
+        let cbor = system.to_cbor();
+        assert_eq!(&[0xad, 0x67], &cbor[..2]);
+        assert_eq!(b"version", &cbor[2..9]);
+        assert_eq!(IR_VERSION as u8, cbor[9]);
+    }
+
+    // TEST AIR
+    // --------------------------------------------------------------------------------------------
+
+    struct TestAir {
+        context: AirContext<BaseElement>,
+    }
+
+    impl Air for TestAir {
+        type BaseField = BaseElement;
+        type PublicInputs = ();
+
+        fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+            let trace_info = TraceInfo::new_multi_segment(
+                TraceLayout::new(trace_info.width(), [1], [1]),
+                trace_info.length(),
+                Vec::new(),
+            );
+            let main_degrees = vec![
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::with_cycles(2, vec![4]),
+            ];
+            let aux_degrees = vec![TransitionConstraintDegree::new(2)];
+            let context =
+                AirContext::new_multi_segment(trace_info, main_degrees, aux_degrees, 1, 1, options);
+            TestAir { context }
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+
+        fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+            vec![vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::ZERO, BaseElement::ZERO]]
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            vec![Assertion::single(0, 0, BaseElement::new(3))]
+        }
+
+        fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+            &self,
+            frame: &EvaluationFrame<E>,
+            periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            let current = frame.current();
+            let next = frame.next();
+
+            // an intermediate value which does not contribute to any of the constraints
+            let _unused = next[1] * E::from(5u32);
+
+            result[0] = next[0] - (current[0] + current[1]);
+            result[1] = next[1] - current[0] * current[1] * periodic_values[0];
+        }
+
+        fn evaluate_aux_transition<F, E>(
+            &self,
+            main_frame: &EvaluationFrame<F>,
+            aux_frame: &EvaluationFrame<E>,
+            _periodic_values: &[F],
+            aux_rand_elements: &AuxTraceRandElements<E>,
+            result: &mut [E],
+        ) where
+            F: FieldElement<BaseField = Self::BaseField>,
+            E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+        {
+            let rand = aux_rand_elements.get_segment_elements(0)[0];
+            result[0] = aux_frame.next()[0]
+                - aux_frame.current()[0] * (rand + E::from(main_frame.current()[0]));
+        }
+
+        fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+            &self,
+            aux_rand_elements: &AuxTraceRandElements<E>,
+        ) -> Vec<Assertion<E>> {
+            vec![Assertion::single(0, 0, aux_rand_elements.get_segment_elements(0)[0])]
+        }
+    }
+}
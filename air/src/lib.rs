@@ -35,11 +35,21 @@ extern crate alloc;
 
 pub mod proof;
 
+pub mod gadgets;
+
+pub mod metrics;
+
+#[cfg(all(feature = "export", feature = "std"))]
+pub mod ir;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
 mod errors;
-pub use errors::AssertionError;
+pub use errors::{AssertionError, ProofOptionsError};
 
 mod options;
-pub use options::{FieldExtension, ProofOptions};
+pub use options::{FieldExtension, ProofOptions, ProofOptionsBuilder, TuningObjective};
 
 mod air;
 pub use air::{
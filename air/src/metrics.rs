@@ -0,0 +1,111 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Hooks for collecting metrics of proof generation and verification.
+//!
+//! Proving services usually export metrics (e.g., to Prometheus or OpenTelemetry) about the proofs
+//! they generate and verify. Rather than wrapping every call to the prover and the verifier, an
+//! application can implement [Metrics] trait for its metrics backend and install it once via
+//! [set_metrics()]; the prover and the verifier then report the metrics listed below to the
+//! installed backend. Until a backend is installed, metrics are discarded.
+//!
+//! | Metric | Kind | Description |
+//! | ------ | ---- | ----------- |
+//! | [PROOFS_GENERATED] | counter | Number of proofs generated by the prover. |
+//! | [PROVING_FAILURES] | counter | Number of proofs which the prover failed to generate. |
+//! | [PROOF_SIZE] | histogram | Size of generated proofs in bytes. |
+//! | [PROVING_TIME] | histogram | Time in seconds of generating a proof. |
+//! | [PROOFS_VERIFIED] | counter | Number of proofs accepted by the verifier. |
+//! | [VERIFICATION_FAILURES] | counter | Number of proofs rejected by the verifier. |
+//!
+//! In addition, the prover reports the time in seconds of every phase of proof generation using a
+//! histogram named after the phase (see `ProvingPhase::duration_metric()` in the prover crate).
+//! Time is not measured when the prover is compiled without `std` feature.
+
+// METRIC NAMES
+// ================================================================================================
+
+/// Counter incremented every time the prover generates a proof.
+pub const PROOFS_GENERATED: &str = "winterfell_proofs_generated_total";
+
+/// Counter incremented every time the prover fails to generate a proof (including proofs whose
+/// generation was cancelled).
+pub const PROVING_FAILURES: &str = "winterfell_proving_failures_total";
+
+/// Histogram of sizes of generated proofs in bytes.
+pub const PROOF_SIZE: &str = "winterfell_proof_size_bytes";
+
+/// Histogram of time in seconds taken to generate a proof.
+pub const PROVING_TIME: &str = "winterfell_proving_seconds";
+
+/// Counter incremented every time the verifier accepts a proof.
+pub const PROOFS_VERIFIED: &str = "winterfell_proofs_verified_total";
+
+/// Counter incremented every time the verifier rejects a proof.
+pub const VERIFICATION_FAILURES: &str = "winterfell_verification_failures_total";
+
+// METRICS TRAIT
+// ================================================================================================
+
+/// Defines a backend to which the prover and the verifier report metrics.
+///
+/// Metrics are identified by static names (see [module documentation](self) for the list of
+/// reported metrics). Methods of this trait are invoked from the threads executing proof
+/// generation and verification, and thus, should return quickly. The default implementations
+/// discard all metrics.
+pub trait Metrics: Send + Sync {
+    /// Increments the counter with the specified name by `value`.
+    fn increment_counter(&self, _name: &'static str, _value: u64) {}
+
+    /// Records `value` in the histogram with the specified name.
+    fn record_histogram(&self, _name: &'static str, _value: f64) {}
+}
+
+/// A metrics backend which discards all metrics.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {}
+
+// GLOBAL BACKEND
+// ================================================================================================
+
+#[cfg(feature = "std")]
+static METRICS: std::sync::OnceLock<&'static dyn Metrics> = std::sync::OnceLock::new();
+
+/// Installs the metrics backend to which the prover and the verifier report metrics.
+///
+/// The backend can be installed only once for the lifetime of the process.
+///
+/// This function is available only when the crate is compiled with `std` feature.
+///
+/// # Errors
+/// Returns the specified backend if a backend has already been installed.
+#[cfg(feature = "std")]
+pub fn set_metrics(metrics: &'static dyn Metrics) -> Result<(), &'static dyn Metrics> {
+    METRICS.set(metrics)
+}
+
+/// Returns the installed metrics backend, or [NoopMetrics] if no backend has been installed.
+///
+/// When the crate is compiled without `std` feature, a backend cannot be installed, and thus,
+/// [NoopMetrics] is always returned.
+pub fn metrics() -> &'static dyn Metrics {
+    #[cfg(feature = "std")]
+    if let Some(metrics) = METRICS.get() {
+        return *metrics;
+    }
+    &NoopMetrics
+}
+
+/// Returns true if a metrics backend has been installed via [set_metrics()].
+///
+/// This can be used to skip computing values of metrics which are expensive to compute.
+pub fn is_enabled() -> bool {
+    #[cfg(feature = "std")]
+    return METRICS.get().is_some();
+    #[cfg(not(feature = "std"))]
+    false
+}
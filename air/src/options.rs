@@ -3,18 +3,27 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::proof::{
+    get_conjectured_security, get_conjectured_security_breakdown, get_proven_security,
+    get_proven_security_breakdown, SecurityReport,
+};
+use crate::{ProofOptionsError, TransitionConstraintDegree};
+use core::{cmp, fmt, str::FromStr};
+use crypto::Hasher;
 use fri::FriOptions;
-use math::{StarkField, ToElements};
+use math::{ExtensibleField, StarkField, ToElements};
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable,
 };
 
 // CONSTANTS
 // ================================================================================================
 
-// most of these constants are set so that values fit into a u8 integer.
+// most of these constants are set so that values fit into a u8 integer; the number of queries is
+// encoded as a u16 integer.
 
-const MAX_NUM_QUERIES: usize = 255;
+const MAX_NUM_QUERIES: usize = u16::MAX as usize;
 
 const MIN_BLOWUP_FACTOR: usize = 2;
 const MAX_BLOWUP_FACTOR: usize = 128;
@@ -22,8 +31,16 @@ const MAX_BLOWUP_FACTOR: usize = 128;
 const MAX_GRINDING_FACTOR: u32 = 32;
 
 const FRI_MIN_FOLDING_FACTOR: usize = 2;
-const FRI_MAX_FOLDING_FACTOR: usize = 16;
-const FRI_MAX_REMAINDER_DEGREE: usize = 255;
+const FRI_MAX_FOLDING_FACTOR: usize = 32;
+const FRI_MAX_REMAINDER_DEGREE: usize = 65535;
+
+const MAX_OOD_POINTS: usize = 8;
+
+// parameters used by ProofOptions::for_security()
+const SECURITY_BLOWUP_FACTOR: usize = 8;
+const SECURITY_GRINDING_FACTOR: u32 = 16;
+const SECURITY_FRI_FOLDING_FACTOR: usize = 8;
+const SECURITY_FRI_REMAINDER_MAX_DEGREE: usize = 31;
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -52,6 +69,22 @@ pub enum FieldExtension {
     Cubic = 3,
 }
 
+/// Cost minimized when tuning grinding factor and number of queries of [ProofOptions] via
+/// [ProofOptions::tune_grinding()].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TuningObjective {
+    /// Minimize the size of proofs, i.e., the number of queries.
+    ///
+    /// Among parameters with the smallest number of queries, the ones with the smallest grinding
+    /// factor are selected.
+    ProofSize,
+    /// Minimize the time the prover spends on grinding and on assembling query openings.
+    ///
+    /// Grinding requires `2^grinding_factor` hash evaluations on average, and every query is
+    /// assumed to cost as much as one hash evaluation per Merkle path node revealed for it.
+    ProverTime,
+}
+
 /// STARK protocol parameters.
 ///
 /// These parameters have a direct impact on proof soundness, proof generation time, and proof
@@ -76,12 +109,17 @@ pub enum FieldExtension {
 /// with 128-bit collision resistance is used, soundness of a STARK proof cannot exceed 128 bits.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ProofOptions {
-    num_queries: u8,
+    num_queries: u16,
     blowup_factor: u8,
     grinding_factor: u8,
     field_extension: FieldExtension,
     fri_folding_factor: u8,
-    fri_remainder_max_degree: u8,
+    fri_remainder_max_degree: u16,
+    fri_layer_grinding_factor: u8,
+    distinct_queries: bool,
+    num_ood_points: u8,
+    deep_coefficient_powers: bool,
+    domain_offset: Option<u64>,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -97,17 +135,22 @@ impl ProofOptions {
     /// have a blowup factor smaller than 2.
     pub const MIN_BLOWUP_FACTOR: usize = MIN_BLOWUP_FACTOR;
 
+    /// Largest allowed number of out-of-domain points which is currently set to 8.
+    pub const MAX_OOD_POINTS: usize = MAX_OOD_POINTS;
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [ProofOptions] struct constructed from the specified parameters.
     ///
     /// # Panics
     /// Panics if:
-    /// - `num_queries` is zero or greater than 255.
+    /// - `num_queries` is zero or greater than 65535.
     /// - `blowup_factor` is smaller than 2, greater than 128, or is not a power of two.
     /// - `grinding_factor` is greater than 32.
-    /// - `fri_folding_factor` is not 2, 4, 8, or 16.
-    /// - `fri_remainder_max_degree` is greater than 255 or is not a power of two minus 1.
+    /// - `fri_folding_factor` is not a power of two between 2 and 32.
+    /// - `fri_remainder_max_degree` is greater than 65535 or is not a power of two minus 1.
+    ///
+    /// To validate untrusted parameters without panicking, use [ProofOptionsBuilder::build()].
     #[rustfmt::skip]
     pub const fn new(
         num_queries: usize,
@@ -117,9 +160,8 @@ impl ProofOptions {
         fri_folding_factor: usize,
         fri_remainder_max_degree: usize,
     ) -> ProofOptions {
-        // TODO: return errors instead of panicking
         assert!(num_queries > 0, "number of queries must be greater than 0");
-        assert!(num_queries <= MAX_NUM_QUERIES, "number of queries cannot be greater than 255");
+        assert!(num_queries <= MAX_NUM_QUERIES, "number of queries cannot be greater than 65535");
 
         assert!(blowup_factor.is_power_of_two(), "blowup factor must be a power of 2");
         assert!(blowup_factor >= MIN_BLOWUP_FACTOR, "blowup factor cannot be smaller than 2");
@@ -129,7 +171,7 @@ impl ProofOptions {
 
         assert!(fri_folding_factor.is_power_of_two(), "FRI folding factor must be a power of 2");
         assert!(fri_folding_factor >= FRI_MIN_FOLDING_FACTOR, "FRI folding factor cannot be smaller than 2");
-        assert!(fri_folding_factor <= FRI_MAX_FOLDING_FACTOR, "FRI folding factor cannot be greater than 16");
+        assert!(fri_folding_factor <= FRI_MAX_FOLDING_FACTOR, "FRI folding factor cannot be greater than 32");
 
         assert!(
             (fri_remainder_max_degree + 1).is_power_of_two(),
@@ -137,19 +179,194 @@ impl ProofOptions {
         );
         assert!(
             fri_remainder_max_degree <= FRI_MAX_REMAINDER_DEGREE,
-            "FRI polynomial remainder degree cannot be greater than 255"
+            "FRI polynomial remainder degree cannot be greater than 65535"
         );
 
         ProofOptions {
-            num_queries: num_queries as u8,
+            num_queries: num_queries as u16,
             blowup_factor: blowup_factor as u8,
             grinding_factor: grinding_factor as u8,
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
-            fri_remainder_max_degree: fri_remainder_max_degree as u8,
+            fri_remainder_max_degree: fri_remainder_max_degree as u16,
+            fri_layer_grinding_factor: 0,
+            distinct_queries: false,
+            num_ood_points: 1,
+            deep_coefficient_powers: false,
+            domain_offset: None,
         }
     }
 
+    /// Returns a new instance of [ProofOptions] with the specified FRI layer grinding factor.
+    ///
+    /// When set, the prover performs proof-of-work with the specified number of bits after
+    /// committing to each FRI layer (see [FriOptions::with_layer_grinding_factor()]). This
+    /// increases the proven security of the commit phase of FRI, and thus, for parameters where
+    /// the commit phase limits the security level, the same security level may be reached with
+    /// fewer queries and smaller proofs.
+    ///
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 32.
+    pub const fn with_fri_layer_grinding_factor(mut self, grinding_factor: u32) -> ProofOptions {
+        assert!(
+            grinding_factor <= MAX_GRINDING_FACTOR,
+            "FRI layer grinding factor cannot be greater than 32"
+        );
+        self.fri_layer_grinding_factor = grinding_factor as u8;
+        self
+    }
+
+    /// Returns a new instance of [ProofOptions] which specifies whether query positions must be
+    /// distinct.
+    ///
+    /// By default, query positions are drawn independently of each other, and thus, some positions
+    /// may be drawn more than once; duplicate positions are opened only once, but they do not
+    /// contribute to the soundness of a proof. When distinct queries are required, duplicate
+    /// positions are rejected and re-drawn, so that every query contributes to soundness. This
+    /// is reflected in the security level of a proof, and is most significant for small domains.
+    pub const fn with_distinct_queries(mut self, distinct_queries: bool) -> ProofOptions {
+        self.distinct_queries = distinct_queries;
+        self
+    }
+
+    /// Returns a new instance of [ProofOptions] with the specified number of out-of-domain
+    /// points.
+    ///
+    /// By default, trace and constraint composition polynomials are evaluated at a single
+    /// out-of-domain point. When more points are used, the verifier checks consistency of the
+    /// constraint composition polynomial at every point, and quotients for all points are batched
+    /// into the DEEP composition polynomial. A dishonest prover then needs all points to be
+    /// favorable, which increases the proven security of the DEEP step; this is most useful for
+    /// small fields, for which the DEEP step limits the proven security level. Every additional
+    /// point adds one out-of-domain frame to the proof.
+    ///
+    /// # Panics
+    /// Panics if `num_ood_points` is zero or greater than 8.
+    pub const fn with_num_ood_points(mut self, num_ood_points: usize) -> ProofOptions {
+        assert!(num_ood_points > 0, "number of OOD points must be greater than 0");
+        assert!(
+            num_ood_points <= MAX_OOD_POINTS,
+            "number of OOD points cannot be greater than 8"
+        );
+        self.num_ood_points = num_ood_points as u8;
+        self
+    }
+
+    /// Returns a new instance of [ProofOptions] which specifies whether DEEP composition
+    /// coefficients are powers of a single random challenge.
+    ///
+    /// By default, a coefficient is drawn independently for every trace polynomial and every
+    /// constraint composition column polynomial. When powers are used, the verifier draws a
+    /// single challenge and combines the values of every queried row (and the out-of-domain
+    /// evaluations) via Horner's method; this replaces one public coin draw per column with a
+    /// single draw, which matters most when the verifier is executed on-chain or inside another
+    /// proof. The batching error then grows with the number of batched polynomials, and so,
+    /// log2 of this number is subtracted from the field-limited terms of the conjectured and the
+    /// proven security levels (see [security_level()](ProofOptions::security_level)). Thus, the
+    /// option should be used only with fields for which a loss of a few bits of field security
+    /// is acceptable.
+    pub const fn with_deep_coefficient_powers(mut self, deep_coefficient_powers: bool) -> Self {
+        self.deep_coefficient_powers = deep_coefficient_powers;
+        self
+    }
+
+    /// Returns a new instance of [ProofOptions] with the low-degree extension domain shifted by
+    /// the specified offset.
+    ///
+    /// By default, the LDE domain (and the constraint evaluation domain) is a coset of the trace
+    /// domain shifted by the primitive element of the base field. External verifier
+    /// implementations may assume a different offset (e.g., a small constant shared by all
+    /// fields they support); setting the offset here makes proofs verifiable by such
+    /// implementations. The offset is interpreted as an element of the base field of the
+    /// computation, and must be smaller than the field modulus; it must also not be in the
+    /// subgroup spanned by the LDE domain, as then the LDE domain would contain the trace domain.
+    /// The latter properties depend on the field and the trace length, and are checked via
+    /// [validate_domain_offset()](ProofOptions::validate_domain_offset) when the AIR of a
+    /// computation is instantiated.
+    ///
+    /// # Panics
+    /// Panics if `offset` is zero or one.
+    pub const fn with_domain_offset(mut self, offset: u64) -> ProofOptions {
+        assert!(offset > 1, "domain offset must be greater than 1");
+        self.domain_offset = Some(offset);
+        self
+    }
+
+    /// Returns a builder initialized with the parameters of these options.
+    ///
+    /// See [ProofOptionsBuilder] for details.
+    pub fn to_builder(&self) -> ProofOptionsBuilder {
+        ProofOptionsBuilder::from_options(self.clone())
+    }
+
+    /// Returns proof options which provide at least the specified security level (in bits) for
+    /// computations over the base field `B` with execution traces of up to `max_trace_length`
+    /// steps, or `None` if the security level cannot be reached.
+    ///
+    /// When `conjectured` is true, the options are selected for the conjectured security level;
+    /// otherwise, the options are selected for the proven security level (see
+    /// [StarkProof::security_level()](crate::proof::StarkProof::security_level)).
+    ///
+    /// The options are selected as follows:
+    /// - Field extension is the smallest extension of `B` which is supported by `B` and for which
+    ///   the required security level can be reached.
+    /// - Blowup factor is set to 8, and thus, the options can be used for computations with
+    ///   constraints of degree up to 9 (excluding the degree of periodic columns).
+    /// - Grinding factor is set to 16 bits.
+    /// - Number of queries is the smallest number of queries for which the required security level
+    ///   is reached.
+    /// - FRI folding factor is set to 8, and max degree of FRI remainder is set to 31.
+    ///
+    /// Security of a proof is also limited by the collision resistance of the hash function used
+    /// to generate it; the hash function is not considered here, and thus, it must provide
+    /// collision resistance of at least `security_level` bits.
+    ///
+    /// # Panics
+    /// Panics if `max_trace_length` is smaller than 8 or is not a power of two.
+    pub fn for_security<B>(
+        security_level: u32,
+        conjectured: bool,
+        max_trace_length: usize,
+    ) -> Option<ProofOptions>
+    where
+        B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    {
+        assert!(max_trace_length >= 8, "max trace length must be at least 8");
+        assert!(max_trace_length.is_power_of_two(), "max trace length must be a power of 2");
+
+        let extensions = [
+            (FieldExtension::None, true),
+            (FieldExtension::Quadratic, <B as ExtensibleField<2>>::is_supported()),
+            (FieldExtension::Cubic, <B as ExtensibleField<3>>::is_supported()),
+        ];
+
+        for (field_extension, _) in extensions.into_iter().filter(|(_, supported)| *supported) {
+            let options = ProofOptions::new(
+                1,
+                SECURITY_BLOWUP_FACTOR,
+                SECURITY_GRINDING_FACTOR,
+                field_extension,
+                SECURITY_FRI_FOLDING_FACTOR,
+                SECURITY_FRI_REMAINDER_MAX_DEGREE,
+            );
+            // DEEP composition coefficients are drawn independently, and thus, the security level
+            // does not depend on the width of the execution trace
+            let num_queries = options.find_num_queries(
+                security_level,
+                B::MODULUS_BITS,
+                max_trace_length,
+                0,
+                conjectured,
+                u32::MAX,
+            );
+            if let Some(num_queries) = num_queries {
+                return Some(options.with_num_queries(num_queries));
+            }
+        }
+
+        None
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -183,6 +400,31 @@ impl ProofOptions {
         self.grinding_factor as u32
     }
 
+    /// Returns the number of bits of proof-of-work performed after committing to each FRI layer.
+    ///
+    /// Zero (the default) means that FRI layers are not ground.
+    pub const fn fri_layer_grinding_factor(&self) -> u32 {
+        self.fri_layer_grinding_factor as u32
+    }
+
+    /// Returns true if query positions for a STARK proof must be distinct.
+    pub const fn distinct_queries(&self) -> bool {
+        self.distinct_queries
+    }
+
+    /// Returns the number of out-of-domain points at which trace and constraint composition
+    /// polynomials are evaluated.
+    ///
+    /// One (the default) means that a single out-of-domain point is used.
+    pub const fn num_ood_points(&self) -> usize {
+        self.num_ood_points as usize
+    }
+
+    /// Returns true if DEEP composition coefficients are powers of a single random challenge.
+    pub const fn deep_coefficient_powers(&self) -> bool {
+        self.deep_coefficient_powers
+    }
+
     /// Specifies whether composition polynomial should be constructed in an extension field
     /// of STARK protocol.
     ///
@@ -195,16 +437,318 @@ impl ProofOptions {
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
-    /// Currently, this is hard-coded to the primitive element of the underlying base field.
-    pub const fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+    /// Unless set via [with_domain_offset()](ProofOptions::with_domain_offset), this is the
+    /// primitive element of the underlying base field.
+    pub fn domain_offset<B: StarkField>(&self) -> B {
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
+    }
+
+    /// Returns the domain offset set via [with_domain_offset()](ProofOptions::with_domain_offset),
+    /// or `None` if the primitive element of the base field is used.
+    pub const fn custom_domain_offset(&self) -> Option<u64> {
+        self.domain_offset
+    }
+
+    /// Checks that the domain offset of these options can be used for an LDE domain of the
+    /// specified size over the base field `B`.
+    ///
+    /// The primitive element of a field is always a valid offset. A custom offset is valid if it
+    /// is smaller than the modulus of `B`, and if raising it to the power of `lde_domain_size`
+    /// does not yield one (i.e., the shifted domain is disjoint from the LDE domain, and thus,
+    /// from the trace domain and from all domains derived from it by FRI folding).
+    ///
+    /// # Errors
+    /// Returns an error if the custom offset is not a valid offset for the specified domain.
+    pub fn validate_domain_offset<B: StarkField>(
+        &self,
+        lde_domain_size: usize,
+    ) -> Result<(), ProofOptionsError> {
+        let offset = match self.domain_offset {
+            Some(offset) => offset,
+            None => return Ok(()),
+        };
+        let element = B::from(offset);
+        if element.as_int() != B::PositiveInteger::from(offset)
+            || element.exp((lde_domain_size as u64).into()) == B::ONE
+        {
+            return Err(ProofOptionsError::UnsafeDomainOffset(offset, lde_domain_size));
+        }
+        Ok(())
+    }
+
+    // SECURITY ESTIMATES
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the security level (in bits) of proofs generated with these options using hash
+    /// function `H` for execution traces of `trace_length` steps and `trace_width` columns (in
+    /// all trace segments) over a base field with a modulus of `base_field_bits` bits.
+    ///
+    /// When `conjectured` is true, conjectured security level is returned; otherwise, proven
+    /// security level is returned. This is the same estimate as the one returned by
+    /// [StarkProof::security_level()](crate::proof::StarkProof::security_level) for proofs
+    /// generated with these options, and thus, parameters can be audited without generating a
+    /// proof. The estimate depends on the number of queries, blowup factor, field extension,
+    /// grinding factors, the number of out-of-domain points, and on whether queries are distinct;
+    /// FRI folding factor and the max degree of the FRI remainder do not affect it. The trace
+    /// width affects the estimate only when DEEP composition coefficients are powers of a single
+    /// challenge (see [with_deep_coefficient_powers()](ProofOptions::with_deep_coefficient_powers)).
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is smaller than 8 or is not a power of two.
+    pub fn security_level<H: Hasher>(
+        &self,
+        base_field_bits: u32,
+        trace_length: usize,
+        trace_width: usize,
+        conjectured: bool,
+    ) -> u32 {
+        assert!(trace_length >= 8, "trace length must be at least 8");
+        assert!(trace_length.is_power_of_two(), "trace length must be a power of 2");
+        self.estimate_security(
+            base_field_bits,
+            trace_length,
+            trace_width,
+            conjectured,
+            H::COLLISION_RESISTANCE,
+        )
+    }
+
+    /// Returns a breakdown of the conjectured and the proven security levels of proofs generated
+    /// with these options using hash function `H` for execution traces of `trace_length` steps
+    /// and `trace_width` columns over a base field with a modulus of `base_field_bits` bits.
+    ///
+    /// The security levels contained in the report are the same as the ones returned by
+    /// [security_level()](ProofOptions::security_level).
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is smaller than 8 or is not a power of two.
+    pub fn security_report<H: Hasher>(
+        &self,
+        base_field_bits: u32,
+        trace_length: usize,
+        trace_width: usize,
+    ) -> SecurityReport {
+        assert!(trace_length >= 8, "trace length must be at least 8");
+        assert!(trace_length.is_power_of_two(), "trace length must be a power of 2");
+        self.build_security_report(
+            base_field_bits,
+            trace_length,
+            trace_width,
+            H::COLLISION_RESISTANCE,
+        )
+    }
+
+    /// Returns the smallest number of queries for which proofs generated with these options
+    /// (with the number of queries replaced) using hash function `H` reach the specified
+    /// security level, or `None` if the security level cannot be reached with at most 65535
+    /// queries.
+    ///
+    /// The security level is estimated as described for
+    /// [security_level()](ProofOptions::security_level).
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is smaller than 8 or is not a power of two.
+    pub fn min_num_queries<H: Hasher>(
+        &self,
+        security_level: u32,
+        base_field_bits: u32,
+        trace_length: usize,
+        trace_width: usize,
+        conjectured: bool,
+    ) -> Option<usize> {
+        assert!(trace_length >= 8, "trace length must be at least 8");
+        assert!(trace_length.is_power_of_two(), "trace length must be a power of 2");
+        self.find_num_queries(
+            security_level,
+            base_field_bits,
+            trace_length,
+            trace_width,
+            conjectured,
+            H::COLLISION_RESISTANCE,
+        )
+    }
+
+    /// Returns a copy of these options with grinding factor and number of queries selected to
+    /// reach the specified security level for proofs generated using hash function `H`, or `None`
+    /// if the security level cannot be reached with any grinding factor.
+    ///
+    /// Each bit of grinding replaces roughly `1 / log2(blowup_factor)` queries: it makes proofs
+    /// smaller, but doubles the expected work of the prover's proof-of-work search. For every
+    /// grinding factor between 0 and 32, the smallest number of queries reaching the security
+    /// level is found (as via [min_num_queries()](ProofOptions::min_num_queries)), and the pair
+    /// which minimizes the cost defined by `objective` is returned; all other parameters remain
+    /// unchanged. The selected parameters are a part of the proof options, and thus, are recorded
+    /// in the context of the generated proofs (see
+    /// [Context::options()](crate::proof::Context::options)).
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is smaller than 8 or is not a power of two.
+    #[allow(clippy::too_many_arguments)]
+    pub fn tune_grinding<H: Hasher>(
+        &self,
+        security_level: u32,
+        base_field_bits: u32,
+        trace_length: usize,
+        trace_width: usize,
+        conjectured: bool,
+        objective: TuningObjective,
+    ) -> Option<ProofOptions> {
+        assert!(trace_length >= 8, "trace length must be at least 8");
+        assert!(trace_length.is_power_of_two(), "trace length must be a power of 2");
+
+        // a query reveals a Merkle path for each trace segment, the constraint commitment, and
+        // each FRI layer; paths are approximated by the depth of the LDE domain tree
+        let lde_domain_size = trace_length * self.blowup_factor();
+        let num_fri_layers = self.to_fri_options().num_fri_layers(lde_domain_size);
+        let query_cost = lde_domain_size.ilog2() as u64 * (num_fri_layers as u64 + 2);
+
+        (0..=MAX_GRINDING_FACTOR)
+            .filter_map(|grinding_factor| {
+                let mut options = self.clone();
+                options.grinding_factor = grinding_factor as u8;
+                let num_queries = options.find_num_queries(
+                    security_level,
+                    base_field_bits,
+                    trace_length,
+                    trace_width,
+                    conjectured,
+                    H::COLLISION_RESISTANCE,
+                )?;
+                Some(options.with_num_queries(num_queries))
+            })
+            .min_by_key(|options| {
+                let num_queries = options.num_queries() as u64;
+                let grinding_factor = options.grinding_factor();
+                match objective {
+                    TuningObjective::ProofSize => (num_queries, grinding_factor as u64),
+                    TuningObjective::ProverTime => {
+                        ((1 << grinding_factor) + num_queries * query_cost, num_queries)
+                    }
+                }
+            })
     }
 
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let remainder_max_degree = self.fri_remainder_max_degree as usize;
-        FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+        let options = FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+            .with_layer_grinding_factor(self.fri_layer_grinding_factor());
+        match self.domain_offset {
+            Some(offset) => options.with_domain_offset(offset),
+            None => options,
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a copy of these options with the number of queries set to the specified value.
+    fn with_num_queries(&self, num_queries: usize) -> ProofOptions {
+        debug_assert!(num_queries > 0 && num_queries <= MAX_NUM_QUERIES);
+        let mut options = self.clone();
+        options.num_queries = num_queries as u16;
+        options
+    }
+
+    /// Returns estimated security level of proofs generated with these options.
+    fn estimate_security(
+        &self,
+        base_field_bits: u32,
+        trace_length: usize,
+        trace_width: usize,
+        conjectured: bool,
+        collision_resistance: u32,
+    ) -> u32 {
+        if conjectured {
+            get_conjectured_security(
+                self,
+                base_field_bits,
+                trace_length,
+                trace_width,
+                collision_resistance,
+            )
+        } else {
+            get_proven_security(
+                self,
+                base_field_bits,
+                trace_length,
+                trace_width,
+                collision_resistance,
+            )
+        }
+    }
+
+    /// Returns a breakdown of the security levels of proofs generated with these options.
+    pub(crate) fn build_security_report(
+        &self,
+        base_field_bits: u32,
+        trace_length: usize,
+        trace_width: usize,
+        collision_resistance: u32,
+    ) -> SecurityReport {
+        SecurityReport {
+            field_bits: base_field_bits * self.field_extension().degree(),
+            collision_resistance,
+            conjectured: get_conjectured_security_breakdown(
+                self,
+                base_field_bits,
+                trace_length,
+                trace_width,
+                collision_resistance,
+            ),
+            proven: get_proven_security_breakdown(
+                self,
+                base_field_bits,
+                trace_length,
+                trace_width,
+                collision_resistance,
+            ),
+        }
+    }
+
+    /// Returns the smallest number of queries for which the estimated security level reaches
+    /// `security_level`.
+    ///
+    /// The estimated security level never decreases as the number of queries grows, and thus, the
+    /// number of queries is found via binary search.
+    fn find_num_queries(
+        &self,
+        security_level: u32,
+        base_field_bits: u32,
+        trace_length: usize,
+        trace_width: usize,
+        conjectured: bool,
+        collision_resistance: u32,
+    ) -> Option<usize> {
+        let is_secure = |num_queries: usize| {
+            let options = self.with_num_queries(num_queries);
+            let security = options.estimate_security(
+                base_field_bits,
+                trace_length,
+                trace_width,
+                conjectured,
+                collision_resistance,
+            );
+            security >= security_level
+        };
+
+        if !is_secure(MAX_NUM_QUERIES) {
+            return None;
+        }
+        let (mut low, mut high) = (1, MAX_NUM_QUERIES);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if is_secure(mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Some(low)
     }
 }
 
@@ -213,26 +757,46 @@ impl<E: StarkField> ToElements<E> for ProofOptions {
         // encode field extension and FRI parameters into a single field element
         let mut buf = self.field_extension as u32;
         buf = (buf << 8) | self.fri_folding_factor as u32;
-        buf = (buf << 8) | self.fri_remainder_max_degree as u32;
+        buf = (buf << 16) | self.fri_remainder_max_degree as u32;
 
-        vec![
+        // FRI layer grinding factor, the query sampling mode, the DEEP coefficient mode, and the
+        // number of additional OOD points are encoded together with the query grinding factor;
+        // a flag also marks whether a custom domain offset follows the number of queries
+        let mut grinding = self.num_ood_points as u32 - 1;
+        let flags = (self.domain_offset.is_some() as u32) << 2
+            | (self.deep_coefficient_powers as u32) << 1
+            | self.distinct_queries as u32;
+        grinding = (grinding << 8) | flags;
+        grinding = (grinding << 8) | self.fri_layer_grinding_factor as u32;
+        grinding = (grinding << 8) | self.grinding_factor as u32;
+
+        let mut result = vec![
             E::from(buf),
-            E::from(self.grinding_factor),
+            E::from(grinding),
             E::from(self.blowup_factor),
             E::from(self.num_queries),
-        ]
+        ];
+        if let Some(offset) = self.domain_offset {
+            result.push(E::from(offset));
+        }
+        result
     }
 }
 
 impl Serializable for ProofOptions {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_u8(self.num_queries);
+        target.write_u16(self.num_queries);
         target.write_u8(self.blowup_factor);
         target.write_u8(self.grinding_factor);
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
-        target.write_u8(self.fri_remainder_max_degree);
+        target.write_u16(self.fri_remainder_max_degree);
+        target.write_u8(self.fri_layer_grinding_factor);
+        target.write_bool(self.distinct_queries);
+        target.write_u8(self.num_ood_points);
+        target.write_bool(self.deep_coefficient_powers);
+        target.write_u64(self.domain_offset.unwrap_or(0));
     }
 }
 
@@ -242,15 +806,478 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-            source.read_u8()? as u32,
-            FieldExtension::read_from(source)?,
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
+        let mut builder = ProofOptionsBuilder::new()
+            .with_num_queries(source.read_u16()? as usize)
+            .with_blowup_factor(source.read_u8()? as usize)
+            .with_grinding_factor(source.read_u8()? as u32)
+            .with_field_extension(FieldExtension::read_from(source)?)
+            .with_fri_folding_factor(source.read_u8()? as usize)
+            .with_fri_remainder_max_degree(source.read_u16()? as usize)
+            .with_fri_layer_grinding_factor(source.read_u8()? as u32)
+            .with_distinct_queries(source.read_bool()?)
+            .with_num_ood_points(source.read_u8()? as usize)
+            .with_deep_coefficient_powers(source.read_bool()?);
+        // a zero offset marks the default domain offset
+        let offset = source.read_u64()?;
+        if offset != 0 {
+            builder = builder.with_domain_offset(offset);
+        }
+        builder
+            .build()
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// STRING REPRESENTATION
+// ================================================================================================
+
+impl fmt::Display for ProofOptions {
+    /// Writes the options in the compact format accepted by [ProofOptions::from_str()], e.g.,
+    /// `q=54,b=8,g=16,ext=quad,fri=4/255`.
+    ///
+    /// FRI layer grinding factor, the query sampling mode, the number of out-of-domain points, the
+    /// DEEP coefficient mode, and the domain offset are written only when they differ from their
+    /// defaults.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field_extension = match self.field_extension {
+            FieldExtension::None => "none",
+            FieldExtension::Quadratic => "quad",
+            FieldExtension::Cubic => "cubic",
+        };
+        write!(
+            f,
+            "q={},b={},g={},ext={},fri={}/{}",
+            self.num_queries,
+            self.blowup_factor,
+            self.grinding_factor,
+            field_extension,
+            self.fri_folding_factor,
+            self.fri_remainder_max_degree
+        )?;
+        if self.fri_layer_grinding_factor != 0 {
+            write!(f, ",fg={}", self.fri_layer_grinding_factor)?;
+        }
+        if self.distinct_queries {
+            write!(f, ",dq=true")?;
+        }
+        if self.num_ood_points != 1 {
+            write!(f, ",ood={}", self.num_ood_points)?;
+        }
+        if self.deep_coefficient_powers {
+            write!(f, ",dp=true")?;
+        }
+        if let Some(offset) = self.domain_offset {
+            write!(f, ",off={offset}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ProofOptions {
+    type Err = ProofOptionsError;
+
+    /// Parses proof options from a comma-separated list of `key=value` pairs, e.g.,
+    /// `q=54,b=8,g=16,ext=quad,fri=4/255`.
+    ///
+    /// The following keys are supported:
+    /// - `q` - number of queries.
+    /// - `b` - blowup factor.
+    /// - `g` - query seed grinding factor.
+    /// - `ext` - field extension; one of `none`, `quad`, or `cubic`.
+    /// - `fri` - FRI folding factor and max degree of FRI remainder separated by `/`.
+    /// - `fg` - FRI layer grinding factor.
+    /// - `dq` - whether query positions must be distinct; either `true` or `false`.
+    /// - `ood` - number of out-of-domain points.
+    /// - `dp` - whether DEEP composition coefficients are powers of a single challenge; either
+    ///   `true` or `false`.
+    /// - `off` - offset of the LDE domain; the primitive element of the base field is used when
+    ///   not specified.
+    ///
+    /// Options which are not specified are set to the defaults of [ProofOptionsBuilder::new()].
+    /// Whitespace around keys and values is ignored.
+    ///
+    /// # Errors
+    /// Returns an error if the string contains an unknown key, specifies a key more than once,
+    /// contains a value which cannot be parsed, or if the resulting options are invalid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut builder = ProofOptionsBuilder::new();
+        let mut keys = Vec::new();
+        for entry in s.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (key, value) = match entry.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return Err(ProofOptionsError::UnknownOption(entry.to_string())),
+            };
+            if keys.contains(&key) {
+                return Err(ProofOptionsError::DuplicateOption(key.to_string()));
+            }
+            keys.push(key);
+
+            builder = match key {
+                "q" => builder.with_num_queries(parse_option(key, value)?),
+                "b" => builder.with_blowup_factor(parse_option(key, value)?),
+                "g" => builder.with_grinding_factor(parse_option(key, value)?),
+                "ext" => builder.with_field_extension(match value {
+                    "none" => FieldExtension::None,
+                    "quad" => FieldExtension::Quadratic,
+                    "cubic" => FieldExtension::Cubic,
+                    _ => return Err(invalid_option_value(key, value)),
+                }),
+                "fri" => {
+                    let (folding_factor, remainder_max_degree) =
+                        value.split_once('/').ok_or_else(|| invalid_option_value(key, value))?;
+                    builder
+                        .with_fri_folding_factor(parse_option(key, folding_factor.trim())?)
+                        .with_fri_remainder_max_degree(parse_option(
+                            key,
+                            remainder_max_degree.trim(),
+                        )?)
+                }
+                "fg" => builder.with_fri_layer_grinding_factor(parse_option(key, value)?),
+                "dq" => builder.with_distinct_queries(parse_option(key, value)?),
+                "ood" => builder.with_num_ood_points(parse_option(key, value)?),
+                "dp" => builder.with_deep_coefficient_powers(parse_option(key, value)?),
+                "off" => builder.with_domain_offset(parse_option(key, value)?),
+                _ => return Err(ProofOptionsError::UnknownOption(key.to_string())),
+            };
+        }
+        builder.build()
+    }
+}
+
+/// Parses the value of the specified option from a string.
+fn parse_option<T: FromStr>(key: &str, value: &str) -> Result<T, ProofOptionsError> {
+    value.parse().map_err(|_| invalid_option_value(key, value))
+}
+
+fn invalid_option_value(key: &str, value: &str) -> ProofOptionsError {
+    ProofOptionsError::InvalidOptionValue(key.to_string(), value.to_string())
+}
+
+// PROOF OPTIONS BUILDER
+// ================================================================================================
+/// A builder of [ProofOptions] which validates the options before building them.
+///
+/// Unlike [ProofOptions::new()], which panics on invalid parameters, the builder returns a
+/// descriptive [ProofOptionsError] from [build()](ProofOptionsBuilder::build). Besides checking
+/// that every parameter is within its supported range, the builder can check the options against
+/// the declared properties of a computation: the transition constraint degrees (via
+/// [with_constraint_degrees()](ProofOptionsBuilder::with_constraint_degrees)) and the length of
+/// the execution trace (via [with_trace_length()](ProofOptionsBuilder::with_trace_length)).
+///
+/// A builder can be initialized from one of the named presets, from existing options, or from the
+/// default parameters returned by [ProofOptionsBuilder::new()]. Security levels of the presets are
+/// given for base fields with a modulus of at least 64 bits and for execution traces of up to
+/// 2^20 steps; actual security levels can be checked via
+/// [ProofOptions::security_level()].
+///
+/// # Examples
+/// ```
+/// # use winter_air::{FieldExtension, ProofOptionsBuilder, ProofOptionsError};
+/// let options = ProofOptionsBuilder::secure_96()
+///     .with_field_extension(FieldExtension::Cubic)
+///     .build()
+///     .unwrap();
+/// assert_eq!(FieldExtension::Cubic, options.field_extension());
+///
+/// let result = ProofOptionsBuilder::secure_96().with_blowup_factor(3).build();
+/// assert_eq!(Err(ProofOptionsError::InvalidBlowupFactor(3)), result);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofOptionsBuilder {
+    num_queries: usize,
+    blowup_factor: usize,
+    grinding_factor: u32,
+    field_extension: FieldExtension,
+    fri_folding_factor: usize,
+    fri_remainder_max_degree: usize,
+    fri_layer_grinding_factor: u32,
+    distinct_queries: bool,
+    num_ood_points: usize,
+    deep_coefficient_powers: bool,
+    domain_offset: Option<u64>,
+    min_blowup_factor: usize,
+    trace_length: Option<usize>,
+}
+
+impl ProofOptionsBuilder {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new builder initialized with the default parameters used by
+    /// [ProofOptions::for_security()]: blowup factor of 8, grinding factor of 16, no field
+    /// extension, FRI folding factor of 8, and max degree of FRI remainder of 31; the number of
+    /// queries is set to 27.
+    pub fn new() -> Self {
+        Self::from_options(ProofOptions::new(
+            27,
+            SECURITY_BLOWUP_FACTOR,
+            SECURITY_GRINDING_FACTOR,
+            FieldExtension::None,
+            SECURITY_FRI_FOLDING_FACTOR,
+            SECURITY_FRI_REMAINDER_MAX_DEGREE,
         ))
     }
+
+    /// Returns a new builder initialized with the parameters of the specified options.
+    pub fn from_options(options: ProofOptions) -> Self {
+        Self {
+            num_queries: options.num_queries(),
+            blowup_factor: options.blowup_factor(),
+            grinding_factor: options.grinding_factor(),
+            field_extension: options.field_extension(),
+            fri_folding_factor: options.fri_folding_factor as usize,
+            fri_remainder_max_degree: options.fri_remainder_max_degree as usize,
+            fri_layer_grinding_factor: options.fri_layer_grinding_factor(),
+            distinct_queries: options.distinct_queries(),
+            num_ood_points: options.num_ood_points(),
+            deep_coefficient_powers: options.deep_coefficient_powers(),
+            domain_offset: options.custom_domain_offset(),
+            min_blowup_factor: MIN_BLOWUP_FACTOR,
+            trace_length: None,
+        }
+    }
+
+    /// Returns a builder initialized with options providing 96 bits of conjectured security.
+    ///
+    /// The options use 27 queries, blowup factor of 8, grinding factor of 16, quadratic field
+    /// extension, FRI folding factor of 8, and max degree of FRI remainder of 31.
+    pub fn secure_96() -> Self {
+        Self::new().with_field_extension(FieldExtension::Quadratic)
+    }
+
+    /// Returns a builder initialized with options providing 128 bits of conjectured security.
+    ///
+    /// The options use 38 queries, blowup factor of 8, grinding factor of 16, cubic field
+    /// extension, FRI folding factor of 8, and max degree of FRI remainder of 31.
+    pub fn secure_128_conjectured() -> Self {
+        Self::new().with_num_queries(38).with_field_extension(FieldExtension::Cubic)
+    }
+
+    /// Returns a builder initialized with options which keep the cost of verifying a proof small
+    /// (e.g., when the verifier is executed inside another proof), while providing 100 bits of
+    /// conjectured security.
+    ///
+    /// The options trade prover time for fewer queries: they use 21 distinct queries, blowup
+    /// factor of 16, grinding factor of 20, quadratic field extension, FRI folding factor of 4,
+    /// and max degree of FRI remainder of 7.
+    pub fn recursive_friendly() -> Self {
+        Self::new()
+            .with_num_queries(21)
+            .with_blowup_factor(16)
+            .with_grinding_factor(20)
+            .with_field_extension(FieldExtension::Quadratic)
+            .with_fri_folding_factor(4)
+            .with_fri_remainder_max_degree(7)
+            .with_distinct_queries(true)
+    }
+
+    /// Returns a builder initialized with options which keep proofs small when they are verified
+    /// on the EVM (where the cost of verification is dominated by the cost of calldata), while
+    /// providing 100 bits of conjectured security.
+    ///
+    /// The options use 21 distinct queries, blowup factor of 16, grinding factor of 20, quadratic
+    /// field extension, FRI folding factor of 8, and max degree of FRI remainder of 31. These are
+    /// meant to be used together with [Keccak256](crypto::hashers::Keccak256) hash function; see
+    /// [evm](crate::proof::evm) module for the rest of the EVM-aligned profile.
+    pub fn evm_friendly() -> Self {
+        Self::new()
+            .with_num_queries(21)
+            .with_blowup_factor(16)
+            .with_grinding_factor(20)
+            .with_field_extension(FieldExtension::Quadratic)
+            .with_distinct_queries(true)
+    }
+
+    // PARAMETERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the number of queries.
+    pub fn with_num_queries(mut self, num_queries: usize) -> Self {
+        self.num_queries = num_queries;
+        self
+    }
+
+    /// Sets the blowup factor.
+    pub fn with_blowup_factor(mut self, blowup_factor: usize) -> Self {
+        self.blowup_factor = blowup_factor;
+        self
+    }
+
+    /// Sets the query seed grinding factor.
+    pub fn with_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        self.grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Sets the field extension.
+    pub fn with_field_extension(mut self, field_extension: FieldExtension) -> Self {
+        self.field_extension = field_extension;
+        self
+    }
+
+    /// Sets the FRI folding factor.
+    pub fn with_fri_folding_factor(mut self, fri_folding_factor: usize) -> Self {
+        self.fri_folding_factor = fri_folding_factor;
+        self
+    }
+
+    /// Sets the max degree of the FRI remainder polynomial.
+    pub fn with_fri_remainder_max_degree(mut self, fri_remainder_max_degree: usize) -> Self {
+        self.fri_remainder_max_degree = fri_remainder_max_degree;
+        self
+    }
+
+    /// Sets the number of bits of proof-of-work performed after committing to each FRI layer.
+    pub fn with_fri_layer_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        self.fri_layer_grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Specifies whether query positions must be distinct.
+    pub fn with_distinct_queries(mut self, distinct_queries: bool) -> Self {
+        self.distinct_queries = distinct_queries;
+        self
+    }
+
+    /// Sets the number of out-of-domain points.
+    pub fn with_num_ood_points(mut self, num_ood_points: usize) -> Self {
+        self.num_ood_points = num_ood_points;
+        self
+    }
+
+    /// Specifies whether DEEP composition coefficients are powers of a single random challenge.
+    pub fn with_deep_coefficient_powers(mut self, deep_coefficient_powers: bool) -> Self {
+        self.deep_coefficient_powers = deep_coefficient_powers;
+        self
+    }
+
+    /// Sets the offset of the LDE domain (see [ProofOptions::with_domain_offset()]).
+    pub fn with_domain_offset(mut self, offset: u64) -> Self {
+        self.domain_offset = Some(offset);
+        self
+    }
+
+    // COMPUTATION PROPERTIES
+    // --------------------------------------------------------------------------------------------
+
+    /// Declares the degrees of the transition constraints of the computation the options are
+    /// built for; building the options fails if the blowup factor is too small to evaluate these
+    /// constraints.
+    pub fn with_constraint_degrees(mut self, degrees: &[TransitionConstraintDegree]) -> Self {
+        self.min_blowup_factor = degrees
+            .iter()
+            .map(|degree| degree.min_blowup_factor())
+            .fold(MIN_BLOWUP_FACTOR, cmp::max);
+        self
+    }
+
+    /// Declares the length of the execution trace of the computation the options are built for;
+    /// building the options fails if the FRI remainder or the number of distinct queries do not
+    /// fit into the domains implied by this trace length.
+    pub fn with_trace_length(mut self, trace_length: usize) -> Self {
+        self.trace_length = Some(trace_length);
+        self
+    }
+
+    // BUILDER
+    // --------------------------------------------------------------------------------------------
+
+    /// Validates the parameters of this builder and returns the resulting [ProofOptions].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - `num_queries` is zero or greater than 65535.
+    /// - `blowup_factor` is smaller than 2, greater than 128, or is not a power of two.
+    /// - `grinding_factor` or the FRI layer grinding factor is greater than 32.
+    /// - FRI folding factor is not a power of two between 2 and 32.
+    /// - Max degree of FRI remainder is greater than 65535 or is not a power of two minus 1.
+    /// - The number of out-of-domain points is zero or greater than 8.
+    /// - The domain offset is zero or one.
+    /// - The blowup factor is smaller than the one required by the declared constraint degrees.
+    /// - The max degree of FRI remainder is not smaller than the declared trace length.
+    /// - Distinct queries are required, and the number of queries is not smaller than the size
+    ///   of the LDE domain implied by the declared trace length.
+    pub fn build(self) -> Result<ProofOptions, ProofOptionsError> {
+        if self.num_queries == 0 || self.num_queries > MAX_NUM_QUERIES {
+            return Err(ProofOptionsError::InvalidNumQueries(self.num_queries));
+        }
+        if !self.blowup_factor.is_power_of_two()
+            || !(MIN_BLOWUP_FACTOR..=MAX_BLOWUP_FACTOR).contains(&self.blowup_factor)
+        {
+            return Err(ProofOptionsError::InvalidBlowupFactor(self.blowup_factor));
+        }
+        if self.grinding_factor > MAX_GRINDING_FACTOR {
+            return Err(ProofOptionsError::InvalidGrindingFactor(self.grinding_factor));
+        }
+        if !self.fri_folding_factor.is_power_of_two()
+            || !(FRI_MIN_FOLDING_FACTOR..=FRI_MAX_FOLDING_FACTOR).contains(&self.fri_folding_factor)
+        {
+            return Err(ProofOptionsError::InvalidFriFoldingFactor(self.fri_folding_factor));
+        }
+        if self.fri_remainder_max_degree > FRI_MAX_REMAINDER_DEGREE
+            || !(self.fri_remainder_max_degree + 1).is_power_of_two()
+        {
+            return Err(ProofOptionsError::InvalidFriRemainderDegree(
+                self.fri_remainder_max_degree,
+            ));
+        }
+        if self.fri_layer_grinding_factor > MAX_GRINDING_FACTOR {
+            return Err(ProofOptionsError::InvalidFriLayerGrindingFactor(
+                self.fri_layer_grinding_factor,
+            ));
+        }
+        if self.num_ood_points == 0 || self.num_ood_points > MAX_OOD_POINTS {
+            return Err(ProofOptionsError::InvalidNumOodPoints(self.num_ood_points));
+        }
+        if let Some(offset) = self.domain_offset.filter(|&offset| offset <= 1) {
+            return Err(ProofOptionsError::InvalidDomainOffset(offset));
+        }
+
+        if self.blowup_factor < self.min_blowup_factor {
+            return Err(ProofOptionsError::BlowupFactorTooSmall(
+                self.min_blowup_factor,
+                self.blowup_factor,
+            ));
+        }
+        if let Some(trace_length) = self.trace_length {
+            if self.fri_remainder_max_degree >= trace_length {
+                return Err(ProofOptionsError::FriRemainderDegreeTooLarge(
+                    trace_length,
+                    self.fri_remainder_max_degree,
+                ));
+            }
+            let lde_domain_size = trace_length * self.blowup_factor;
+            if self.distinct_queries && self.num_queries >= lde_domain_size {
+                return Err(ProofOptionsError::TooManyDistinctQueries(
+                    lde_domain_size,
+                    self.num_queries,
+                ));
+            }
+        }
+
+        let options = ProofOptions::new(
+            self.num_queries,
+            self.blowup_factor,
+            self.grinding_factor,
+            self.field_extension,
+            self.fri_folding_factor,
+            self.fri_remainder_max_degree,
+        )
+        .with_fri_layer_grinding_factor(self.fri_layer_grinding_factor)
+        .with_distinct_queries(self.distinct_queries)
+        .with_num_ood_points(self.num_ood_points)
+        .with_deep_coefficient_powers(self.deep_coefficient_powers);
+        Ok(match self.domain_offset {
+            Some(offset) => options.with_domain_offset(offset),
+            None => options,
+        })
+    }
+}
+
+impl Default for ProofOptionsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // FIELD EXTENSION IMPLEMENTATION
@@ -306,8 +1333,17 @@ impl Deserializable for FieldExtension {
 
 #[cfg(test)]
 mod tests {
-    use super::{FieldExtension, ProofOptions, ToElements};
-    use math::fields::f64::BaseElement;
+    use super::{
+        get_conjectured_security, get_proven_security, Deserializable, FieldExtension,
+        ProofOptions, ProofOptionsBuilder, Serializable, ToElements, ToString, TuningObjective,
+    };
+    use crate::proof::SecurityBottleneck;
+    use crate::{ProofOptionsError, TransitionConstraintDegree};
+    use crypto::hashers::{Blake3_192, Blake3_256};
+    use math::{
+        fields::{f128, f64::BaseElement},
+        StarkField,
+    };
 
     #[test]
     fn proof_options_to_elements() {
@@ -320,9 +1356,9 @@ mod tests {
 
         let ext_fri = u32::from_le_bytes([
             fri_remainder_max_degree,
+            0,
             fri_folding_factor,
             field_extension as u8,
-            0,
         ]);
         let expected = vec![
             BaseElement::from(ext_fri),
@@ -341,4 +1377,514 @@ mod tests {
         );
         assert_eq!(expected, options.to_elements());
     }
+
+    #[test]
+    fn proof_options_large_fri_remainder() {
+        let fri_remainder_max_degree = 4095;
+        let options = ProofOptions::new(30, 8, 0, FieldExtension::Quadratic, 4, 4095);
+
+        let ext_fri = u32::from_le_bytes([
+            (fri_remainder_max_degree & 0xff) as u8,
+            (fri_remainder_max_degree >> 8) as u8,
+            4,
+            FieldExtension::Quadratic as u8,
+        ]);
+        assert_eq!(BaseElement::from(ext_fri), options.to_elements()[0]);
+
+        let fri_options = options.to_fri_options();
+        assert_eq!(fri_remainder_max_degree, fri_options.remainder_max_degree());
+        assert_eq!(0, fri_options.num_fri_layers(4096 * 8));
+        assert_eq!(1, fri_options.num_fri_layers(4096 * 8 * 4));
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn proof_options_fri_layer_grinding() {
+        let options = ProofOptions::new(120, 8, 16, FieldExtension::Quadratic, 8, 31);
+        assert_eq!(0, options.fri_layer_grinding_factor());
+        let ground_options = options.clone().with_fri_layer_grinding_factor(12);
+        assert_eq!(12, ground_options.fri_layer_grinding_factor());
+        assert_eq!(12, ground_options.to_fri_options().layer_grinding_factor());
+        assert_eq!(BaseElement::from(12u32 << 8 | 16), ground_options.to_elements()[1]);
+
+        let bytes = ground_options.to_bytes();
+        assert_eq!(ground_options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // with many queries, proven security is limited by the commit phase of FRI, and thus,
+        // layer grinding increases it
+        let trace_length = 1 << 20;
+        let security = |options: &ProofOptions| {
+            get_proven_security(options, BaseElement::MODULUS_BITS, trace_length, 4, u32::MAX)
+        };
+        assert!(security(&ground_options) > security(&options));
+    }
+
+    #[test]
+    fn proof_options_many_queries() {
+        let options = ProofOptions::new(1000, 2, 0, FieldExtension::Cubic, 8, 31);
+        assert_eq!(1000, options.num_queries());
+        assert_eq!(BaseElement::from(1000u32), options.to_elements()[3]);
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // with a blowup factor of 2, more than 255 queries are needed for 128 bits of proven
+        // security
+        let trace_length = 1 << 20;
+        let num_queries = options
+            .min_num_queries::<Blake3_256<BaseElement>>(128, 64, trace_length, 4, false)
+            .unwrap();
+        assert!(num_queries > 255);
+        assert_eq!(
+            Err(ProofOptionsError::InvalidNumQueries(65536)),
+            options.to_builder().with_num_queries(65536).build()
+        );
+    }
+
+    #[test]
+    fn proof_options_distinct_queries() {
+        let options = ProofOptions::new(40, 8, 0, FieldExtension::Quadratic, 4, 7);
+        assert!(!options.distinct_queries());
+        let distinct_options = options.clone().with_distinct_queries(true);
+        assert!(distinct_options.distinct_queries());
+        assert_eq!(BaseElement::from(1u32 << 16), distinct_options.to_elements()[1]);
+
+        let bytes = distinct_options.to_bytes();
+        assert_eq!(distinct_options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // for small domains, distinct queries provide noticeably more security
+        let trace_length = 32;
+        let conjectured = |options: &ProofOptions| {
+            get_conjectured_security(options, 128, trace_length, 4, u32::MAX)
+        };
+        assert!(conjectured(&distinct_options) > conjectured(&options));
+        let proven =
+            |options: &ProofOptions| get_proven_security(options, 128, trace_length, 4, u32::MAX);
+        assert!(proven(&distinct_options) >= proven(&options));
+
+        // for large domains, the difference is negligible
+        let trace_length = 1 << 20;
+        assert_eq!(
+            get_conjectured_security(&options, 128, trace_length, 4, u32::MAX),
+            get_conjectured_security(&distinct_options, 128, trace_length, 4, u32::MAX)
+        );
+    }
+
+    #[test]
+    fn proof_options_num_ood_points() {
+        let options = ProofOptions::new(40, 8, 0, FieldExtension::None, 4, 7);
+        assert_eq!(1, options.num_ood_points());
+        let multi_point_options = options.clone().with_num_ood_points(3);
+        assert_eq!(3, multi_point_options.num_ood_points());
+        assert_eq!(BaseElement::from(2u32 << 24), multi_point_options.to_elements()[1]);
+
+        let bytes = multi_point_options.to_bytes();
+        assert_eq!(multi_point_options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        assert_eq!(multi_point_options, multi_point_options.to_string().parse().unwrap());
+        assert_eq!(
+            Err(ProofOptionsError::InvalidNumOodPoints(9)),
+            options.to_builder().with_num_ood_points(9).build()
+        );
+
+        // every additional point reduces the out-of-domain sampling error; the other terms of
+        // proven security and conjectured security do not change
+        type H = Blake3_256<BaseElement>;
+        let trace_length = 1 << 16;
+        let report = options.security_report::<H>(64, trace_length, 4);
+        let multi_point_report = multi_point_options.security_report::<H>(64, trace_length, 4);
+        assert!(multi_point_report.proven.deep_security > 2 * report.proven.deep_security);
+        assert_eq!(
+            report.proven.fri_commit_security,
+            multi_point_report.proven.fri_commit_security
+        );
+        assert_eq!(report.conjectured, multi_point_report.conjectured);
+    }
+
+    #[test]
+    fn proof_options_deep_coefficient_powers() {
+        let options = ProofOptions::new(40, 8, 0, FieldExtension::Quadratic, 4, 7);
+        assert!(!options.deep_coefficient_powers());
+        let power_options = options.clone().with_deep_coefficient_powers(true);
+        assert!(power_options.deep_coefficient_powers());
+        assert_eq!(BaseElement::from(2u32 << 16), power_options.to_elements()[1]);
+
+        let bytes = power_options.to_bytes();
+        assert_eq!(power_options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        assert_eq!(power_options, power_options.to_string().parse().unwrap());
+        assert_eq!(
+            Ok(power_options),
+            options.to_builder().with_deep_coefficient_powers(true).build()
+        );
+    }
+
+    #[test]
+    fn proof_options_deep_coefficient_powers_security() {
+        type H = Blake3_256<BaseElement>;
+        let trace_length = 1 << 16;
+        let options = ProofOptions::new(80, 8, 0, FieldExtension::Quadratic, 4, 7);
+        let power_options = options.clone().with_deep_coefficient_powers(true);
+
+        // with independent coefficients, the trace width does not affect security
+        assert_eq!(
+            options.security_report::<H>(64, trace_length, 8),
+            options.security_report::<H>(64, trace_length, 120)
+        );
+
+        // with powers of a single challenge, log2 of the number of trace and composition columns
+        // (bounded by the blowup factor) is subtracted from field-limited terms
+        let report = options.security_report::<H>(64, trace_length, 120);
+        let power_report = power_options.security_report::<H>(64, trace_length, 120);
+        assert_eq!(report.conjectured.field_security - 7, power_report.conjectured.field_security);
+        assert_eq!(report.conjectured.query_security, power_report.conjectured.query_security);
+        assert!(power_report.proven.deep_security + 6 <= report.proven.deep_security);
+        assert_eq!(report.proven.ali_security, power_report.proven.ali_security);
+
+        // the loss grows with the width of the trace
+        let wide_report = power_options.security_report::<H>(64, trace_length, 1016);
+        assert_eq!(
+            power_report.conjectured.field_security - 3,
+            wide_report.conjectured.field_security
+        );
+        assert!(
+            power_options.security_level::<H>(64, trace_length, 1016, true)
+                < options.security_level::<H>(64, trace_length, 1016, true)
+        );
+    }
+
+    #[test]
+    fn proof_options_domain_offset() {
+        let options = ProofOptions::new(40, 8, 0, FieldExtension::Quadratic, 4, 7);
+        assert_eq!(None, options.custom_domain_offset());
+        assert_eq!(BaseElement::GENERATOR, options.domain_offset::<BaseElement>());
+        let offset_options = options.clone().with_domain_offset(3);
+        assert_eq!(Some(3), offset_options.custom_domain_offset());
+        assert_eq!(BaseElement::from(3u32), offset_options.domain_offset::<BaseElement>());
+        assert_eq!(BaseElement::from(3u32), offset_options.to_fri_options().domain_offset());
+
+        // the offset is bound to the transcript
+        let elements: Vec<BaseElement> = offset_options.to_elements();
+        assert_eq!(BaseElement::from(4u32 << 16), elements[1]);
+        assert_eq!(Some(&BaseElement::from(3u32)), elements.get(4));
+        assert_eq!(4, ToElements::<BaseElement>::to_elements(&options).len());
+
+        let bytes = offset_options.to_bytes();
+        assert_eq!(offset_options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        assert_eq!(options, ProofOptions::read_from_bytes(&options.to_bytes()).unwrap());
+        assert_eq!(offset_options, offset_options.to_string().parse().unwrap());
+        assert_eq!(Ok(offset_options.clone()), options.to_builder().with_domain_offset(3).build());
+        assert_eq!(
+            Err(ProofOptionsError::InvalidDomainOffset(1)),
+            options.to_builder().with_domain_offset(1).build()
+        );
+
+        // the offset must be a field element outside of the subgroup spanned by the LDE domain
+        let lde_domain_size = 1 << 10;
+        assert_eq!(Ok(()), options.validate_domain_offset::<BaseElement>(lde_domain_size));
+        assert_eq!(Ok(()), offset_options.validate_domain_offset::<BaseElement>(lde_domain_size));
+        let root = BaseElement::get_root_of_unity(8).as_int();
+        assert_eq!(
+            Err(ProofOptionsError::UnsafeDomainOffset(root, lde_domain_size)),
+            options
+                .clone()
+                .with_domain_offset(root)
+                .validate_domain_offset::<BaseElement>(lde_domain_size)
+        );
+        let modulus = BaseElement::MODULUS;
+        assert_eq!(
+            Err(ProofOptionsError::UnsafeDomainOffset(modulus + 3, lde_domain_size)),
+            options
+                .with_domain_offset(modulus + 3)
+                .validate_domain_offset::<BaseElement>(lde_domain_size)
+        );
+    }
+
+    #[test]
+    fn proof_options_security_estimates() {
+        let trace_length = 1 << 16;
+        let options = ProofOptions::new(30, 8, 16, FieldExtension::Quadratic, 8, 31);
+        assert_eq!(
+            get_conjectured_security(&options, 64, trace_length, 4, 128),
+            options.security_level::<Blake3_256<BaseElement>>(64, trace_length, 4, true)
+        );
+        assert_eq!(
+            get_proven_security(&options, 64, trace_length, 4, 128),
+            options.security_level::<Blake3_256<BaseElement>>(64, trace_length, 4, false)
+        );
+
+        // the smallest number of queries reaching the security level is returned
+        let options = ProofOptions::new(30, 8, 16, FieldExtension::Cubic, 8, 31);
+        let num_queries = options
+            .min_num_queries::<Blake3_256<BaseElement>>(100, 64, trace_length, 4, false)
+            .unwrap();
+        let security = |num_queries: usize| {
+            let options = ProofOptions::new(num_queries, 8, 16, FieldExtension::Cubic, 8, 31);
+            options.security_level::<Blake3_256<BaseElement>>(64, trace_length, 4, false)
+        };
+        assert!(security(num_queries) >= 100);
+        assert!(security(num_queries - 1) < 100);
+
+        // the security level cannot exceed collision resistance of the hash function
+        assert_eq!(
+            None,
+            options.min_num_queries::<Blake3_192<BaseElement>>(100, 64, trace_length, 4, true)
+        );
+    }
+
+    #[test]
+    fn proof_options_security_report() {
+        let trace_length = 1 << 16;
+        type H = Blake3_256<BaseElement>;
+
+        // few queries limit conjectured security
+        let options = ProofOptions::new(20, 8, 16, FieldExtension::Quadratic, 8, 31);
+        let report = options.security_report::<H>(64, trace_length, 4);
+        let conjectured = report.conjectured;
+        assert_eq!(128, report.field_bits);
+        assert_eq!(
+            options.security_level::<H>(64, trace_length, 4, true),
+            conjectured.security_level
+        );
+        assert_eq!(
+            options.security_level::<H>(64, trace_length, 4, false),
+            report.proven.security_level
+        );
+        assert_eq!(60, conjectured.query_security);
+        assert_eq!(0, conjectured.grinding_security);
+        assert_eq!(SecurityBottleneck::Queries, conjectured.bottleneck);
+        assert_eq!(conjectured.query_security - 1, conjectured.security_level);
+
+        // without a field extension, the 64-bit field limits security
+        let options = ProofOptions::new(60, 8, 16, FieldExtension::None, 8, 31);
+        let report = options.security_report::<H>(64, trace_length, 4);
+        assert_eq!(64 - 19, report.conjectured.field_security);
+        assert_eq!(16, report.conjectured.grinding_security);
+        assert_eq!(SecurityBottleneck::Field, report.conjectured.bottleneck);
+        assert_eq!(SecurityBottleneck::Field, report.proven.bottleneck);
+        assert_eq!(report.conjectured.field_security - 1, report.conjectured.security_level);
+
+        // with enough queries and a large field, the hash function limits security
+        let options = ProofOptions::new(60, 8, 16, FieldExtension::Cubic, 8, 31);
+        let report = options.security_report::<Blake3_192<BaseElement>>(64, trace_length, 4);
+        assert_eq!(SecurityBottleneck::HashFunction, report.conjectured.bottleneck);
+        assert_eq!(report.collision_resistance, report.conjectured.security_level);
+        assert_eq!(
+            options.security_level::<Blake3_192<BaseElement>>(64, trace_length, 4, false),
+            report.proven.security_level
+        );
+    }
+
+    #[test]
+    fn proof_options_builder() {
+        let trace_length = 1 << 20;
+        let security = |options: &ProofOptions| {
+            options.security_level::<Blake3_256<BaseElement>>(
+                BaseElement::MODULUS_BITS,
+                trace_length,
+                4,
+                true,
+            )
+        };
+
+        // presets provide the documented security levels
+        let options = ProofOptionsBuilder::secure_96().build().unwrap();
+        assert_eq!(96, security(&options));
+        let options = ProofOptionsBuilder::secure_128_conjectured().build().unwrap();
+        assert!(security(&options) >= 128);
+        let options = ProofOptionsBuilder::recursive_friendly().build().unwrap();
+        assert!(security(&options) >= 100);
+        assert!(options.distinct_queries());
+        let options = ProofOptionsBuilder::evm_friendly().build().unwrap();
+        assert!(security(&options) >= 100);
+        assert!(options.distinct_queries());
+
+        // options can be rebuilt from existing options
+        assert_eq!(Ok(options.clone()), options.to_builder().build());
+
+        // out-of-range parameters are rejected
+        let builder = ProofOptionsBuilder::secure_96();
+        assert_eq!(
+            Err(ProofOptionsError::InvalidNumQueries(0)),
+            builder.clone().with_num_queries(0).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidBlowupFactor(256)),
+            builder.clone().with_blowup_factor(256).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidFriFoldingFactor(6)),
+            builder.clone().with_fri_folding_factor(6).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidFriRemainderDegree(32)),
+            builder.clone().with_fri_remainder_max_degree(32).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidFriLayerGrindingFactor(33)),
+            builder.clone().with_fri_layer_grinding_factor(33).build()
+        );
+
+        // inconsistent combinations are rejected
+        let degrees = [TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(10)];
+        assert_eq!(
+            Err(ProofOptionsError::BlowupFactorTooSmall(16, 8)),
+            builder.clone().with_constraint_degrees(&degrees).build()
+        );
+        assert!(builder
+            .clone()
+            .with_constraint_degrees(&degrees)
+            .with_blowup_factor(16)
+            .build()
+            .is_ok());
+        assert_eq!(
+            Err(ProofOptionsError::FriRemainderDegreeTooLarge(16, 31)),
+            builder.clone().with_trace_length(16).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::TooManyDistinctQueries(64, 64)),
+            builder
+                .with_trace_length(8)
+                .with_fri_remainder_max_degree(7)
+                .with_num_queries(64)
+                .with_distinct_queries(true)
+                .build()
+        );
+    }
+
+    #[test]
+    fn proof_options_invalid_deserialization() {
+        let mut bytes = ProofOptionsBuilder::secure_96().build().unwrap().to_bytes();
+        bytes[2] = 3;
+        assert!(ProofOptions::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn proof_options_from_str() {
+        let options: ProofOptions = "q=54,b=8,g=16,ext=quad,fri=4/255".parse().unwrap();
+        let expected = ProofOptions::new(54, 8, 16, FieldExtension::Quadratic, 4, 255);
+        assert_eq!(expected, options);
+        assert_eq!("q=54,b=8,g=16,ext=quad,fri=4/255", options.to_string());
+
+        // whitespace is ignored and unspecified options are set to defaults
+        let options: ProofOptions = " ext = cubic , dq=true, fg=4 ".parse().unwrap();
+        let expected = ProofOptionsBuilder::new()
+            .with_field_extension(FieldExtension::Cubic)
+            .with_distinct_queries(true)
+            .with_fri_layer_grinding_factor(4)
+            .build()
+            .unwrap();
+        assert_eq!(expected, options);
+        assert_eq!("q=27,b=8,g=16,ext=cubic,fri=8/31,fg=4,dq=true", options.to_string());
+        assert_eq!(Ok(options.clone()), options.to_string().parse());
+
+        // invalid strings are rejected
+        let parse = |s: &str| s.parse::<ProofOptions>();
+        assert_eq!(Err(ProofOptionsError::UnknownOption("hash".into())), parse("hash=blake3_256"));
+        assert_eq!(Err(ProofOptionsError::UnknownOption("q".into())), parse("q"));
+        assert_eq!(Err(ProofOptionsError::DuplicateOption("q".into())), parse("q=8,q=16"));
+        assert_eq!(
+            Err(ProofOptionsError::InvalidOptionValue("ext".into(), "quartic".into())),
+            parse("ext=quartic")
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InvalidOptionValue("fri".into(), "4".into())),
+            parse("fri=4")
+        );
+        assert_eq!(Err(ProofOptionsError::InvalidBlowupFactor(3)), parse("b=3"));
+    }
+
+    #[test]
+    fn proof_options_for_security() {
+        let trace_length = 1 << 20;
+
+        // the 64-bit field is too small for 100 bits of security without an extension
+        let options = ProofOptions::for_security::<BaseElement>(100, true, trace_length).unwrap();
+        assert_eq!(FieldExtension::Quadratic, options.field_extension());
+        let security = |num_queries: usize| {
+            let options = ProofOptions::new(num_queries, 8, 16, FieldExtension::Quadratic, 8, 31);
+            get_conjectured_security(&options, BaseElement::MODULUS_BITS, trace_length, 4, u32::MAX)
+        };
+        assert!(security(options.num_queries()) >= 100);
+        assert!(security(options.num_queries() - 1) < 100);
+
+        // proven security requires more queries than conjectured security
+        let proven = ProofOptions::for_security::<BaseElement>(100, false, trace_length).unwrap();
+        assert!(proven.num_queries() > options.num_queries());
+        let security =
+            get_proven_security(&proven, BaseElement::MODULUS_BITS, trace_length, 4, u32::MAX);
+        assert!(security >= 100);
+
+        // a large enough field does not need an extension
+        let options =
+            ProofOptions::for_security::<f128::BaseElement>(100, true, trace_length).unwrap();
+        assert_eq!(FieldExtension::None, options.field_extension());
+
+        // security level above the limit of all supported extensions cannot be reached
+        assert!(ProofOptions::for_security::<f128::BaseElement>(300, true, trace_length).is_none());
+    }
+
+    #[test]
+    fn proof_options_tune_grinding() {
+        let trace_length = 1 << 16;
+        let bits = BaseElement::MODULUS_BITS;
+        let base = ProofOptions::new(1, 8, 0, FieldExtension::Quadratic, 8, 31);
+        let tune = |objective| {
+            base.tune_grinding::<Blake3_256<BaseElement>>(
+                100,
+                bits,
+                trace_length,
+                4,
+                true,
+                objective,
+            )
+            .unwrap()
+        };
+
+        // minimizing proof size selects the largest useful grinding factor; any smaller grinding
+        // factor requires more queries
+        let small = tune(TuningObjective::ProofSize);
+        assert!(
+            small.security_level::<Blake3_256<BaseElement>>(bits, trace_length, 4, true) >= 100
+        );
+        let expected = base
+            .to_builder()
+            .with_grinding_factor(32)
+            .build()
+            .unwrap()
+            .min_num_queries::<Blake3_256<BaseElement>>(100, bits, trace_length, 4, true)
+            .unwrap();
+        assert_eq!(expected, small.num_queries());
+        let fewer_bits = base.to_builder().with_grinding_factor(small.grinding_factor() - 1);
+        let fewer_bits = fewer_bits.build().unwrap();
+        let num_queries = fewer_bits
+            .min_num_queries::<Blake3_256<BaseElement>>(100, bits, trace_length, 4, true)
+            .unwrap();
+        assert!(num_queries > small.num_queries());
+
+        // minimizing prover time selects a smaller grinding factor at the cost of more queries
+        let fast = tune(TuningObjective::ProverTime);
+        assert!(fast.security_level::<Blake3_256<BaseElement>>(bits, trace_length, 4, true) >= 100);
+        assert!(fast.grinding_factor() < small.grinding_factor());
+        assert!(fast.num_queries() > small.num_queries());
+
+        // other parameters are not changed
+        assert_eq!(base.field_extension(), fast.field_extension());
+        assert_eq!(base.blowup_factor(), fast.blowup_factor());
+        assert_eq!(base.to_fri_options(), fast.to_fri_options());
+
+        // the security level cannot exceed collision resistance of the hash function
+        let tune = |security_level| {
+            base.tune_grinding::<Blake3_192<BaseElement>>(
+                security_level,
+                bits,
+                trace_length,
+                4,
+                true,
+                TuningObjective::ProofSize,
+            )
+        };
+        assert!(tune(96).is_some());
+        assert!(tune(100).is_none());
+    }
 }
@@ -3,7 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::{get_conjectured_security, get_proven_security, SecurityReport};
 use crate::{ProofOptions, TraceInfo, TraceLayout};
+use crypto::Hasher;
 use math::{StarkField, ToElements};
 use utils::{
     collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
@@ -20,13 +22,39 @@ pub struct Context {
     trace_meta: Vec<u8>,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
+    air_id: u32,
+    air_fingerprint: [u8; 32],
+    salt: Option<[u8; 32]>,
 }
 
 impl Context {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Version of the format in which proofs are serialized.
+    ///
+    /// The version is written at the start of a serialized context (and thus, at the start of a
+    /// serialized proof) right after [Context::FORMAT_MARKER], and is incremented whenever the
+    /// serialization format of proofs changes. Only proofs serialized in the current version of
+    /// the format can be read.
+    pub const FORMAT_VERSION: u8 = 1;
+
+    /// Byte written at the start of a serialized context to indicate that it is followed by the
+    /// format version.
+    ///
+    /// Proofs serialized before the format was versioned start with the width of the main trace
+    /// segment, which is never zero; thus, the marker distinguishes versioned proofs from such
+    /// legacy proofs, and legacy proofs are rejected rather than being read in a wrong layout.
+    pub const FORMAT_MARKER: u8 = 0;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new context for a computation described by the specified field, trace info, and
     /// proof options.
+    ///
+    /// The computation is not identified by the context (i.e., its AIR identifier is set to 0
+    /// and its AIR fingerprint consists of all zeros); these can be set via [Context::set_air_id()]
+    /// and [Context::set_air_fingerprint()] respectively. The context is not salted.
     pub fn new<B: StarkField>(trace_info: &TraceInfo, options: ProofOptions) -> Self {
         Context {
             trace_layout: trace_info.layout().clone(),
@@ -34,9 +62,40 @@ impl Context {
             trace_meta: trace_info.meta().to_vec(),
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
+            air_id: 0,
+            air_fingerprint: [0; 32],
+            salt: None,
         }
     }
 
+    /// Sets the identifier of the AIR describing the computation of this context.
+    ///
+    /// See [Air::AIR_ID](crate::Air::AIR_ID) for details.
+    pub fn set_air_id(mut self, air_id: u32) -> Self {
+        self.air_id = air_id;
+        self
+    }
+
+    /// Sets the digest of the structure of the AIR describing the computation of this context.
+    ///
+    /// See [Air::fingerprint()](crate::Air::fingerprint) for details.
+    pub fn set_air_fingerprint(mut self, air_fingerprint: [u8; 32]) -> Self {
+        self.air_fingerprint = air_fingerprint;
+        self
+    }
+
+    /// Sets the salt absorbed into the public coin at the start of the proof transcript.
+    ///
+    /// The salt is a part of the context, and thus, it is carried in the proof and the verifier
+    /// absorbs it in the same way as the prover. Since all values drawn from the public coin
+    /// depend on the salt, two proofs of the same statement generated with different salts do
+    /// not share query positions, out-of-domain evaluations, or any commitments other than the
+    /// commitment to the main trace segment (which depends on the execution trace only).
+    pub fn set_salt(mut self, salt: [u8; 32]) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -91,12 +150,69 @@ impl Context {
     pub fn options(&self) -> &ProofOptions {
         &self.options
     }
+
+    /// Returns the identifier of the AIR describing the computation of this context.
+    pub fn air_id(&self) -> u32 {
+        self.air_id
+    }
+
+    /// Returns the digest of the structure of the AIR describing the computation of this context.
+    pub fn air_fingerprint(&self) -> &[u8; 32] {
+        &self.air_fingerprint
+    }
+
+    /// Returns the transcript salt of this context, or `None` if the context is not salted.
+    pub fn salt(&self) -> Option<&[u8; 32]> {
+        self.salt.as_ref()
+    }
+
+    /// Returns security level (in bits) of a proof generated in this context using hash function
+    /// `H`.
+    ///
+    /// When `conjectured` is true, conjectured security level is returned; otherwise, provable
+    /// security level is returned.
+    pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
+        let trace_width =
+            self.trace_layout.main_trace_width() + self.trace_layout.aux_trace_width();
+        if conjectured {
+            get_conjectured_security(
+                &self.options,
+                self.num_modulus_bits(),
+                self.trace_length,
+                trace_width,
+                H::COLLISION_RESISTANCE,
+            )
+        } else {
+            get_proven_security(
+                &self.options,
+                self.num_modulus_bits(),
+                self.trace_length,
+                trace_width,
+                H::COLLISION_RESISTANCE,
+            )
+        }
+    }
+
+    /// Returns a breakdown of the conjectured and the proven security levels of a proof generated
+    /// in this context using hash function `H`.
+    pub fn security_report<H: Hasher>(&self) -> SecurityReport {
+        let trace_width =
+            self.trace_layout.main_trace_width() + self.trace_layout.aux_trace_width();
+        self.options.build_security_report(
+            self.num_modulus_bits(),
+            self.trace_length,
+            trace_width,
+            H::COLLISION_RESISTANCE,
+        )
+    }
 }
 
 impl<E: StarkField> ToElements<E> for Context {
     /// Converts this [Context] into a vector of field elements.
     ///
     /// The elements are laid out as follows:
+    /// - number of transcript salt elements [1 element].
+    /// - transcript salt [0 or 2 or more elements].
     /// - trace layout info [1 or more elements].
     /// - field modulus bytes [2 field elements].
     /// - field extension and FRI parameters [1 element].
@@ -104,10 +220,28 @@ impl<E: StarkField> ToElements<E> for Context {
     /// - blowup factor [1 element].
     /// - number of queries [1 element].
     /// - trace length [1 element].
+    /// - AIR identifier [1 element].
+    /// - AIR fingerprint [2 or more elements].
     /// - trace metadata [0 or more elements].
     fn to_elements(&self) -> Vec<E> {
+        let mut result = Vec::new();
+
+        // convert the salt (if any) first, so that it is absorbed at the start of the transcript;
+        // the salt is preceded by the number of its elements so that a salted context and an
+        // unsalted context never map to the same sequence of elements
+        match self.salt {
+            Some(salt) => {
+                let chunks = salt.chunks(E::ELEMENT_BYTES - 1);
+                result.push(E::from(chunks.len() as u32));
+                for chunk in chunks {
+                    result.push(bytes_to_element(chunk));
+                }
+            }
+            None => result.push(E::ZERO),
+        }
+
         // convert trace layout
-        let mut result = self.trace_layout.to_elements();
+        result.append(&mut self.trace_layout.to_elements());
 
         // convert field modulus bytes into 2 elements
         let num_modulus_bytes = self.field_modulus_bytes.len();
@@ -118,6 +252,10 @@ impl<E: StarkField> ToElements<E> for Context {
         // convert proof options and trace length to elements
         result.append(&mut self.options.to_elements());
         result.push(E::from(self.trace_length as u64));
+        result.push(E::from(self.air_id));
+        for chunk in self.air_fingerprint.chunks(E::ELEMENT_BYTES - 1) {
+            result.push(bytes_to_element(chunk));
+        }
 
         // convert trace metadata to elements; this is done by breaking trace metadata into chunks
         // of bytes which are slightly smaller than the number of bytes needed to encode a field
@@ -136,8 +274,11 @@ impl<E: StarkField> ToElements<E> for Context {
 // ================================================================================================
 
 impl Serializable for Context {
-    /// Serializes `self` and writes the resulting bytes into the `target`.
+    /// Serializes `self` in the current version of the proof format and writes the resulting
+    /// bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(Self::FORMAT_MARKER);
+        target.write_u8(Self::FORMAT_VERSION);
         self.trace_layout.write_into(target);
         target.write_u8(self.trace_length.ilog2() as u8); // store as power of two
         target.write_u16(self.trace_meta.len() as u16);
@@ -146,6 +287,12 @@ impl Serializable for Context {
         target.write_u8(self.field_modulus_bytes.len() as u8);
         target.write_bytes(&self.field_modulus_bytes);
         self.options.write_into(target);
+        target.write_u32(self.air_id);
+        target.write_bytes(&self.air_fingerprint);
+        target.write_bool(self.salt.is_some());
+        if let Some(salt) = self.salt {
+            target.write_bytes(&salt);
+        }
     }
 }
 
@@ -155,6 +302,26 @@ impl Deserializable for Context {
     /// # Errors
     /// Returns an error of a valid Context struct could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // make sure the context was not serialized in the legacy unversioned format; in this
+        // format, the first byte is the width of the main trace segment, which is never zero
+        let format_marker = source.read_u8()?;
+        if format_marker != Self::FORMAT_MARKER {
+            return Err(DeserializationError::InvalidValue(
+                "proof was serialized in the legacy unversioned format, which is no longer \
+                supported"
+                    .to_string(),
+            ));
+        }
+
+        // read and validate format version
+        let format_version = source.read_u8()?;
+        if format_version != Self::FORMAT_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "proof format version {format_version} is not supported; expected version {}",
+                Self::FORMAT_VERSION
+            )));
+        }
+
         // read and validate trace layout info
         let trace_layout = TraceLayout::read_from(source)?;
 
@@ -189,12 +356,26 @@ impl Deserializable for Context {
         // read options
         let options = ProofOptions::read_from(source)?;
 
+        // read AIR identifier
+        let air_id = source.read_u32()?;
+        let air_fingerprint = source.read_array()?;
+
+        // read the salt
+        let salt = if source.read_bool()? {
+            Some(source.read_array()?)
+        } else {
+            None
+        };
+
         Ok(Context {
             trace_layout,
             trace_length,
             trace_meta,
             field_modulus_bytes,
             options,
+            air_id,
+            air_fingerprint,
+            salt,
         })
     }
 }
@@ -226,7 +407,8 @@ fn bytes_to_element<B: StarkField>(bytes: &[u8]) -> B {
 mod tests {
     use super::{Context, ProofOptions, ToElements, TraceInfo};
     use crate::{FieldExtension, TraceLayout};
-    use math::fields::f64::BaseElement;
+    use math::{fields::f64::BaseElement, FieldElement, StarkField};
+    use utils::{Deserializable, Serializable};
 
     #[test]
     fn context_to_elements() {
@@ -242,17 +424,20 @@ mod tests {
         let aux_width = 9;
         let aux_rands = 12;
         let trace_length = 4096;
+        let air_id = 42;
+        let air_fingerprint = [1_u8; 32];
 
         let ext_fri = u32::from_le_bytes([
             fri_remainder_max_degree,
+            0,
             fri_folding_factor,
             field_extension as u8,
-            0,
         ]);
 
         let layout_info = u32::from_le_bytes([aux_rands, aux_width, num_aux_segments, main_width]);
 
         let expected = vec![
+            BaseElement::ZERO, // number of salt elements
             BaseElement::from(layout_info),
             BaseElement::from(1_u32),    // lower bits of field modulus
             BaseElement::from(u32::MAX), // upper bits of field modulus
@@ -261,6 +446,12 @@ mod tests {
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
             BaseElement::from(trace_length as u32),
+            BaseElement::from(air_id),
+            BaseElement::from(u64::from_le_bytes([1, 1, 1, 1, 1, 1, 1, 0])),
+            BaseElement::from(u64::from_le_bytes([1, 1, 1, 1, 1, 1, 1, 0])),
+            BaseElement::from(u64::from_le_bytes([1, 1, 1, 1, 1, 1, 1, 0])),
+            BaseElement::from(u64::from_le_bytes([1, 1, 1, 1, 1, 1, 1, 0])),
+            BaseElement::from(u64::from_le_bytes([1, 1, 1, 1, 0, 0, 0, 0])),
         ];
 
         let options = ProofOptions::new(
@@ -274,7 +465,59 @@ mod tests {
         let layout =
             TraceLayout::new(main_width as usize, [aux_width as usize], [aux_rands as usize]);
         let trace_info = TraceInfo::new_multi_segment(layout, trace_length, vec![]);
-        let context = Context::new::<BaseElement>(&trace_info, options);
+        let context = Context::new::<BaseElement>(&trace_info, options)
+            .set_air_id(air_id)
+            .set_air_fingerprint(air_fingerprint);
         assert_eq!(expected, context.to_elements());
     }
+
+    #[test]
+    fn context_salt() {
+        let options = ProofOptions::new(30, 8, 0, FieldExtension::None, 4, 31);
+        let trace_info = TraceInfo::new(4, 64);
+        let context = Context::new::<BaseElement>(&trace_info, options).set_air_id(7);
+        assert_eq!(None, context.salt());
+
+        // the salt is absorbed before the rest of the context
+        let salted = context.clone().set_salt([1; 32]);
+        assert_eq!(Some(&[1; 32]), salted.salt());
+        let elements: Vec<BaseElement> = context.to_elements();
+        let salted_elements: Vec<BaseElement> = salted.to_elements();
+        assert_eq!(elements.len() + 5, salted_elements.len());
+        assert_eq!(BaseElement::ZERO, elements[0]);
+        assert_eq!(BaseElement::from(5_u32), salted_elements[0]);
+        assert_eq!(
+            BaseElement::from(u64::from_le_bytes([1, 1, 1, 1, 1, 1, 1, 0])),
+            salted_elements[1]
+        );
+        assert_eq!(elements[1..], salted_elements[6..]);
+
+        // the salt is serialized with the context
+        let bytes = salted.to_bytes();
+        assert_eq!(salted, Context::read_from_bytes(&bytes).unwrap());
+        assert_eq!(context, Context::read_from_bytes(&context.to_bytes()).unwrap());
+
+        // contexts serialized in other versions of the format are rejected
+        let mut bytes = context.to_bytes();
+        bytes[1] = Context::FORMAT_VERSION + 1;
+        assert!(Context::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn context_legacy_format() {
+        // a context serialized in the legacy unversioned format: main trace width of 1 (which is
+        // the same value as the current format version), no auxiliary segments, trace length of
+        // 2^6, no metadata, field modulus, and proof options
+        let options = ProofOptions::new(30, 8, 0, FieldExtension::None, 4, 31);
+        let modulus = BaseElement::get_modulus_le_bytes();
+        let mut bytes = vec![1, 0, 0];
+        bytes.push(6);
+        bytes.extend_from_slice(&0_u16.to_le_bytes());
+        bytes.push(modulus.len() as u8);
+        bytes.extend_from_slice(&modulus);
+        bytes.extend_from_slice(&options.to_bytes());
+
+        let err = Context::read_from_bytes(&bytes).unwrap_err();
+        assert!(err.to_string().contains("legacy unversioned format"));
+    }
 }
@@ -0,0 +1,164 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::StarkProof;
+use math::StarkField;
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONTINUATION PROOF
+// ================================================================================================
+/// A proof of a long computation split into a chain of segments, with a STARK proof generated
+/// for every segment.
+///
+/// Segment `i` of the computation starts in boundary state `i` and ends in boundary state `i + 1`;
+/// thus, for `n` segments, a continuation proof contains `n` segment proofs and `n + 1` boundary
+/// states. A boundary state is a row of the main segment of the execution trace: the first row of
+/// segment `i + 1` is the same as the last row of segment `i`.
+///
+/// A continuation proof does not link the segments by itself. Rather, the AIR of the computation
+/// must accept the initial and the final state of a segment as public inputs, and bind them to the
+/// first and the last row of the execution trace via assertions. Since public inputs are used to
+/// seed the public coin of every segment proof, each proof is bound to the boundary states it was
+/// generated for, and a verifier can check the entire chain by verifying every segment proof
+/// against the boundary states adjacent to it.
+///
+/// This makes it possible to prove computations which require more steps than the maximum trace
+/// length supported for a single proof.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ContinuationProof<B: StarkField> {
+    boundary_states: Vec<Vec<B>>,
+    segment_proofs: Vec<StarkProof>,
+}
+
+impl<B: StarkField> ContinuationProof<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new continuation proof assembled from the specified boundary states and segment
+    /// proofs.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `segment_proofs` is empty.
+    /// * The number of boundary states is not equal to the number of segment proofs plus one.
+    /// * Not all boundary states have the same number of elements.
+    pub fn new(boundary_states: Vec<Vec<B>>, segment_proofs: Vec<StarkProof>) -> Self {
+        assert!(
+            !segment_proofs.is_empty(),
+            "a continuation proof must contain at least one segment"
+        );
+        assert_eq!(
+            boundary_states.len(),
+            segment_proofs.len() + 1,
+            "expected {} boundary states, but received {}",
+            segment_proofs.len() + 1,
+            boundary_states.len()
+        );
+        let state_width = boundary_states[0].len();
+        assert!(
+            boundary_states.iter().all(|state| state.len() == state_width),
+            "all boundary states must have the same number of elements"
+        );
+
+        Self {
+            boundary_states,
+            segment_proofs,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of segments in this proof.
+    pub fn num_segments(&self) -> usize {
+        self.segment_proofs.len()
+    }
+
+    /// Returns the state in which the first segment of the computation starts.
+    pub fn initial_state(&self) -> &[B] {
+        &self.boundary_states[0]
+    }
+
+    /// Returns the state in which the last segment of the computation ends.
+    pub fn final_state(&self) -> &[B] {
+        &self.boundary_states[self.num_segments()]
+    }
+
+    /// Returns all boundary states of this proof, starting with the initial state.
+    pub fn boundary_states(&self) -> &[Vec<B>] {
+        &self.boundary_states
+    }
+
+    /// Returns proofs for all segments of the computation.
+    pub fn segment_proofs(&self) -> &[StarkProof] {
+        &self.segment_proofs
+    }
+
+    /// Consumes this proof and returns a vector of boundary states and a vector of segment
+    /// proofs contained in it.
+    pub fn into_parts(self) -> (Vec<Vec<B>>, Vec<StarkProof>) {
+        (self.boundary_states, self.segment_proofs)
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this proof into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        Serializable::to_bytes(self)
+    }
+
+    /// Returns a continuation proof read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid continuation proof could not be read from the specified
+    /// `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        Deserializable::read_from_bytes(source)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<B: StarkField> Serializable for ContinuationProof<B> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.segment_proofs.len());
+        target.write_usize(self.boundary_states[0].len());
+        for state in self.boundary_states.iter() {
+            state.write_into(target);
+        }
+        self.segment_proofs.write_into(target);
+    }
+}
+
+impl<B: StarkField> Deserializable for ContinuationProof<B> {
+    /// Reads a continuation proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the source, or if the proof does
+    /// not contain any segments.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_segments = source.read_usize()?;
+        if num_segments == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a continuation proof must contain at least one segment".into(),
+            ));
+        }
+        let state_width = source.read_usize()?;
+
+        let mut boundary_states = Vec::new();
+        for _ in 0..num_segments + 1 {
+            boundary_states.push(B::read_batch_from(source, state_width)?);
+        }
+        let segment_proofs = StarkProof::read_batch_from(source, num_segments)?;
+
+        Ok(Self {
+            boundary_states,
+            segment_proofs,
+        })
+    }
+}
@@ -0,0 +1,601 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! EVM-aligned encoding of STARK proofs.
+//!
+//! Together with [Keccak256](crypto::hashers::Keccak256) hash function and
+//! [ProofOptionsBuilder::evm_friendly()](crate::ProofOptionsBuilder::evm_friendly) options, this
+//! module defines a proving profile for proofs which are meant to be verified on the EVM:
+//! * Proofs are encoded as calldata, i.e., as a sequence of 32-byte big-endian words (see
+//!   [StarkProof::to_evm_calldata()]), so that a contract can read any value of the proof with
+//!   a single `calldataload` and no bit manipulation.
+//! * Public inputs are committed to by a single BN254 scalar field element (see
+//!   [hash_public_inputs()]), so that the proof can be wrapped into (or aggregated with) SNARKs
+//!   over the BN254 curve.
+//!
+//! In the calldata, integers and field elements (in canonical form) are encoded as `uint256`
+//! words, and digests as left-aligned `bytes32` words. Each list is prefixed by a word with the
+//! number of its items, and byte strings are prefixed by their length and padded with zeros to a
+//! multiple of 32 bytes. Extension field elements are encoded as consecutive words of their base
+//! field components. The layout is:
+//!
+//! ```text
+//! version
+//! context:
+//!   main_width, num_aux_segments, [aux_width, aux_rands]..,
+//!   trace_length, trace_meta (bytes), field_modulus,
+//!   num_queries, blowup_factor, grinding_factor, field_extension, fri_folding_factor,
+//!   fri_remainder_max_degree, fri_layer_grinding_factor, distinct_queries, num_ood_points,
+//!   deep_coefficient_powers, domain_offset, air_id, air_fingerprint (bytes32), salt (bytes)
+//! num_unique_queries
+//! commitments (digests)
+//! trace_queries: [values (elements), paths (nodes)] for each trace segment
+//! constraint_queries: values (elements), paths (nodes)
+//! ood_frame: frame_size, trace_states (elements), evaluations (elements)
+//! fri_proof:
+//!   num_layers, [values (elements), paths (nodes)]..,
+//!   remainder (elements), num_partitions, layer_nonces (integers)
+//! pow_nonce
+//! metadata: num_entries, [key (bytes), value (bytes)]..
+//! ```
+//!
+//! where `nodes` are the internal nodes of a batch Merkle proof, encoded as a list of lists of
+//! digests. The domain offset is zero when the primitive element of the base field is used as the
+//! offset, and the salt is an empty byte string when the proof is not salted. The version is
+//! incremented whenever the layout changes.
+
+use super::{Commitments, Context, OodFrame, ProofMetadata, Queries, StarkProof};
+use crate::air::NUM_AUX_SEGMENTS;
+use crypto::{hashers::Keccak256, Digest, ElementHasher, Hasher};
+use fri::FriProof;
+use math::{StarkField, ToElements};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the calldata layout.
+pub const CALLDATA_VERSION: u64 = 5;
+
+/// Number of bytes in a calldata word.
+pub const WORD_SIZE: usize = 32;
+
+/// Number of bits in the public input hash; values with this many bits are always smaller than
+/// the modulus of the BN254 scalar field.
+const PUB_INPUTS_HASH_BITS: usize = 253;
+
+// STARK PROOF CALLDATA
+// ================================================================================================
+
+impl StarkProof {
+    /// Returns this proof encoded as EVM calldata.
+    ///
+    /// See [evm](crate::proof::evm) module for the description of the layout. `H` must be the
+    /// hash function with which the proof was generated.
+    ///
+    /// # Errors
+    /// Returns an error if components of this proof are not consistent with the field described by
+    /// the proof context or with the digests of `H` (e.g., if the proof was deserialized from
+    /// malformed bytes).
+    ///
+    /// # Panics
+    /// Panics if field elements or digests of `H` do not fit into a single word.
+    pub fn to_evm_calldata<H: Hasher>(&self) -> Result<Vec<u8>, DeserializationError> {
+        let element_bytes = self.context.field_modulus_bytes().len();
+        let digest_bytes = digest_size::<H>();
+        assert!(element_bytes <= WORD_SIZE, "field elements must fit into a single word");
+        assert!(digest_bytes <= WORD_SIZE, "digests must fit into a single word");
+
+        let mut target = CalldataWriter::default();
+        target.write_int(CALLDATA_VERSION);
+
+        // context
+        let context = &self.context;
+        let layout = context.trace_layout();
+        let options = context.options();
+        let fri_options = options.to_fri_options();
+        target.write_usize(layout.main_trace_width());
+        target.write_usize(layout.num_aux_segments());
+        for i in 0..layout.num_aux_segments() {
+            target.write_usize(layout.get_aux_segment_width(i));
+            target.write_usize(layout.get_aux_segment_rand_elements(i));
+        }
+        target.write_usize(context.trace_length());
+        target.write_bytes(context.get_trace_info().meta());
+        target.write_word(le_bytes_to_word(context.field_modulus_bytes()));
+        target.write_usize(options.num_queries());
+        target.write_usize(options.blowup_factor());
+        target.write_int(options.grinding_factor() as u64);
+        target.write_int(options.field_extension().degree() as u64);
+        target.write_usize(fri_options.folding_factor());
+        target.write_usize(fri_options.remainder_max_degree());
+        target.write_int(options.fri_layer_grinding_factor() as u64);
+        target.write_int(options.distinct_queries() as u64);
+        target.write_int(options.num_ood_points() as u64);
+        target.write_int(options.deep_coefficient_powers() as u64);
+        target.write_int(options.custom_domain_offset().unwrap_or(0));
+        target.write_int(context.air_id() as u64);
+        target.write_word(*context.air_fingerprint());
+        target.write_bytes(context.salt().map_or(&[], |salt| &salt[..]));
+
+        target.write_int(self.num_unique_queries as u64);
+        target.write_digests(&self.commitments.0, digest_bytes)?;
+
+        // queries
+        for queries in self.trace_queries.iter().chain([&self.constraint_queries]) {
+            target.write_elements(&queries.values, element_bytes)?;
+            target.write_nodes(&queries.paths, digest_bytes)?;
+        }
+
+        // out-of-domain frame; trace states are prefixed by the size of the frame
+        let (frame_size, trace_states) = self
+            .ood_frame
+            .trace_states
+            .split_first()
+            .ok_or_else(|| DeserializationError::InvalidValue("empty OOD frame".into()))?;
+        target.write_int(*frame_size as u64);
+        target.write_elements(trace_states, element_bytes)?;
+        target.write_elements(&self.ood_frame.evaluations, element_bytes)?;
+
+        // FRI proof
+        let fri_proof = &self.fri_proof;
+        target.write_usize(fri_proof.num_layers());
+        for i in 0..fri_proof.num_layers() {
+            let (values, paths) = fri_proof.layer_bytes(i);
+            target.write_elements(values, element_bytes)?;
+            target.write_nodes(paths, digest_bytes)?;
+        }
+        target.write_elements(fri_proof.remainder_bytes(), element_bytes)?;
+        target.write_usize(fri_proof.num_partitions());
+        target.write_usize(fri_proof.layer_nonces().len());
+        for &nonce in fri_proof.layer_nonces() {
+            target.write_int(nonce);
+        }
+
+        target.write_int(self.pow_nonce);
+
+        // metadata
+        target.write_usize(self.metadata.len());
+        for (key, value) in self.metadata.iter() {
+            target.write_bytes(key.as_bytes());
+            target.write_bytes(value.as_bytes());
+        }
+
+        Ok(target.0)
+    }
+
+    /// Returns a proof decoded from the specified EVM calldata.
+    ///
+    /// `B` must be the base field and `H` the hash function with which the proof was generated.
+    /// The result is a regular proof which can be verified by the verifier.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be decoded from the specified calldata, if the
+    /// calldata was encoded for a different field, or if not all of the calldata was consumed.
+    ///
+    /// # Panics
+    /// Panics if elements of `B` or digests of `H` do not fit into a single word.
+    pub fn from_evm_calldata<B: StarkField, H: Hasher>(
+        calldata: &[u8],
+    ) -> Result<Self, DeserializationError> {
+        let element_bytes = B::ELEMENT_BYTES;
+        let digest_bytes = digest_size::<H>();
+        assert!(element_bytes <= WORD_SIZE, "field elements must fit into a single word");
+        assert!(digest_bytes <= WORD_SIZE, "digests must fit into a single word");
+
+        let mut source = CalldataReader::new(calldata)?;
+        let version = source.read_int()?;
+        if version != CALLDATA_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "calldata version {version} is not supported; expected version {CALLDATA_VERSION}"
+            )));
+        }
+
+        // context; this is re-assembled in the serialization format of contexts so that it goes
+        // through exactly the same validation as contexts of serialized proofs
+        let mut context = Vec::new();
+        context.write_u8(Context::FORMAT_MARKER);
+        context.write_u8(Context::FORMAT_VERSION);
+        let main_width = source.read_usize(u16::MAX as usize)?;
+        let num_aux_segments = source.read_usize(NUM_AUX_SEGMENTS)?;
+        let mut aux_widths = [0; NUM_AUX_SEGMENTS];
+        let mut aux_rands = [0; NUM_AUX_SEGMENTS];
+        for i in 0..num_aux_segments {
+            aux_widths[i] = source.read_usize(u16::MAX as usize)?;
+            aux_rands[i] = source.read_usize(u8::MAX as usize)?;
+        }
+        context.write_u16(main_width as u16);
+        aux_widths.iter().for_each(|&width| context.write_u16(width as u16));
+        aux_rands.iter().for_each(|&rands| context.write_u8(rands as u8));
+        let trace_length = source.read_usize(usize::MAX)?;
+        if !trace_length.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length must be a power of two, but was {trace_length}"
+            )));
+        }
+        context.write_u8(trace_length.ilog2() as u8);
+        let trace_meta = source.read_bytes(u16::MAX as usize)?;
+        context.write_u16(trace_meta.len() as u16);
+        context.write_bytes(&trace_meta);
+        let modulus_bytes = B::get_modulus_le_bytes();
+        if source.read_word()? != le_bytes_to_word(&modulus_bytes) {
+            return Err(DeserializationError::InvalidValue(
+                "calldata was encoded for a different field".into(),
+            ));
+        }
+        context.write_u8(modulus_bytes.len() as u8);
+        context.write_bytes(&modulus_bytes);
+        context.write_u16(source.read_usize(u16::MAX as usize)? as u16);
+        context.write_u8(source.read_usize(u8::MAX as usize)? as u8);
+        context.write_u8(source.read_usize(u8::MAX as usize)? as u8);
+        context.write_u8(source.read_usize(u8::MAX as usize)? as u8);
+        context.write_u8(source.read_usize(u8::MAX as usize)? as u8);
+        context.write_u16(source.read_usize(u16::MAX as usize)? as u16);
+        context.write_u8(source.read_usize(u8::MAX as usize)? as u8);
+        context.write_u8(source.read_usize(1)? as u8);
+        context.write_u8(source.read_usize(u8::MAX as usize)? as u8);
+        context.write_u8(source.read_usize(1)? as u8);
+        context.write_u64(source.read_int()?);
+        context.write_u32(source.read_usize(u32::MAX as usize)? as u32);
+        context.write_bytes(&source.read_word()?);
+        let salt = source.read_bytes(32)?;
+        context.write_bool(!salt.is_empty());
+        context.write_bytes(&salt);
+        let context = Context::read_from_bytes(&context)?;
+
+        let num_unique_queries = source.read_usize(u16::MAX as usize)? as u16;
+        let commitments = Commitments(source.read_digests(digest_bytes)?);
+
+        // queries
+        let num_trace_segments = context.trace_layout().num_segments();
+        let mut trace_queries = Vec::with_capacity(num_trace_segments);
+        for _ in 0..num_trace_segments {
+            let values = source.read_elements(element_bytes)?;
+            let paths = source.read_nodes(digest_bytes)?;
+            trace_queries.push(Queries { paths, values });
+        }
+        let values = source.read_elements(element_bytes)?;
+        let paths = source.read_nodes(digest_bytes)?;
+        let constraint_queries = Queries { paths, values };
+
+        // out-of-domain frame
+        let mut trace_states = Vec::new();
+        trace_states.write_u8(source.read_usize(u8::MAX as usize)? as u8);
+        trace_states.append(&mut source.read_elements(element_bytes)?);
+        let evaluations = source.read_elements(element_bytes)?;
+        let ood_frame = OodFrame {
+            trace_states,
+            evaluations,
+        };
+
+        // FRI proof; this is re-assembled in the serialization format of FRI proofs
+        let mut fri_proof = Vec::new();
+        let num_layers = source.read_usize(u8::MAX as usize)?;
+        fri_proof.write_u8(num_layers as u8);
+        for _ in 0..num_layers {
+            let values = source.read_elements(element_bytes)?;
+            let paths = source.read_nodes(digest_bytes)?;
+            fri_proof.write_u32(values.len() as u32);
+            fri_proof.write_bytes(&values);
+            fri_proof.write_u32(paths.len() as u32);
+            fri_proof.write_bytes(&paths);
+        }
+        let remainder = source.read_elements(element_bytes)?;
+        fri_proof.write_u32(remainder.len() as u32);
+        fri_proof.write_bytes(&remainder);
+        let num_partitions = source.read_usize(usize::MAX)?;
+        if !num_partitions.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of FRI partitions must be a power of two, but was {num_partitions}"
+            )));
+        }
+        fri_proof.write_u8(num_partitions.ilog2() as u8);
+        let num_layer_nonces = source.read_usize(u8::MAX as usize)?;
+        fri_proof.write_u8(num_layer_nonces as u8);
+        for _ in 0..num_layer_nonces {
+            fri_proof.write_u64(source.read_int()?);
+        }
+        let fri_proof = FriProof::read_from_bytes(&fri_proof)?;
+
+        let pow_nonce = source.read_int()?;
+
+        // metadata; this is re-assembled in the serialization format of metadata so that keys
+        // are validated in the same way
+        let mut metadata = Vec::new();
+        let num_entries = source.read_usize(u8::MAX as usize)?;
+        metadata.write_u8(num_entries as u8);
+        for _ in 0..num_entries {
+            let key = source.read_bytes(u8::MAX as usize)?;
+            metadata.write_u8(key.len() as u8);
+            metadata.write_bytes(&key);
+            let value = source.read_bytes(u16::MAX as usize)?;
+            metadata.write_u16(value.len() as u16);
+            metadata.write_bytes(&value);
+        }
+        let metadata = ProofMetadata::read_from_bytes(&metadata)?;
+
+        if source.has_more_words() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok(StarkProof {
+            context,
+            num_unique_queries,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            pow_nonce,
+            metadata,
+        })
+    }
+}
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Returns a hash of the specified public inputs which is an element of the BN254 scalar field,
+/// encoded as a big-endian word.
+///
+/// The hash is computed as Keccak-256 of the elements of the public inputs (encoded as 32-byte
+/// big-endian words), reduced to its 253 least significant bits. Thus, in Solidity, it is equal
+/// to `uint256(keccak256(abi.encodePacked(inputs))) & ((1 << 253) - 1)`, and it is a valid BN254
+/// scalar without a modular reduction.
+pub fn hash_public_inputs<B, P>(pub_inputs: &P) -> [u8; WORD_SIZE]
+where
+    B: StarkField,
+    P: ToElements<B>,
+{
+    let mut result = Keccak256::<B>::hash_elements(&pub_inputs.to_elements()).as_bytes();
+    result[0] &= 0xff >> (WORD_SIZE * 8 - PUB_INPUTS_HASH_BITS);
+    result
+}
+
+// CALLDATA WRITER
+// ================================================================================================
+
+/// Writes values into a sequence of calldata words.
+#[derive(Default)]
+struct CalldataWriter(Vec<u8>);
+
+impl CalldataWriter {
+    /// Writes a single word.
+    fn write_word(&mut self, word: [u8; WORD_SIZE]) {
+        self.0.extend_from_slice(&word);
+    }
+
+    /// Writes an integer as a big-endian word.
+    fn write_int(&mut self, value: u64) {
+        self.write_word(le_bytes_to_word(&value.to_le_bytes()));
+    }
+
+    /// Writes an integer as a big-endian word.
+    fn write_usize(&mut self, value: usize) {
+        self.write_int(value as u64);
+    }
+
+    /// Writes the length of the byte string followed by the bytes padded to a whole number of
+    /// words.
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_usize(bytes.len());
+        for chunk in bytes.chunks(WORD_SIZE) {
+            let mut word = [0; WORD_SIZE];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_word(word);
+        }
+    }
+
+    /// Writes the number of serialized field elements followed by the elements, one per word.
+    fn write_elements(
+        &mut self,
+        bytes: &[u8],
+        element_bytes: usize,
+    ) -> Result<(), DeserializationError> {
+        if bytes.len() % element_bytes != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of element bytes ({}) does not divide into whole number of elements",
+                bytes.len()
+            )));
+        }
+        self.write_usize(bytes.len() / element_bytes);
+        bytes
+            .chunks(element_bytes)
+            .for_each(|element| self.write_word(le_bytes_to_word(element)));
+        Ok(())
+    }
+
+    /// Writes the number of serialized digests followed by the digests, one per word.
+    fn write_digests(
+        &mut self,
+        bytes: &[u8],
+        digest_bytes: usize,
+    ) -> Result<(), DeserializationError> {
+        if bytes.len() % digest_bytes != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of digest bytes ({}) does not divide into whole number of digests",
+                bytes.len()
+            )));
+        }
+        self.write_usize(bytes.len() / digest_bytes);
+        for digest in bytes.chunks(digest_bytes) {
+            let mut word = [0; WORD_SIZE];
+            word[..digest_bytes].copy_from_slice(digest);
+            self.write_word(word);
+        }
+        Ok(())
+    }
+
+    /// Writes internal nodes of a batch Merkle proof serialized via
+    /// [BatchMerkleProof::serialize_nodes()](crypto::BatchMerkleProof::serialize_nodes) as a list
+    /// of lists of digests.
+    fn write_nodes(
+        &mut self,
+        bytes: &[u8],
+        digest_bytes: usize,
+    ) -> Result<(), DeserializationError> {
+        let mut reader = SliceReader::new(bytes);
+        let num_node_vectors = reader.read_u16()? as usize;
+        self.write_usize(num_node_vectors);
+        for _ in 0..num_node_vectors {
+            let num_digests = reader.read_u8()? as usize;
+            self.write_digests(&reader.read_vec(num_digests * digest_bytes)?, digest_bytes)?;
+        }
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(())
+    }
+}
+
+// CALLDATA READER
+// ================================================================================================
+
+/// Reads values from a sequence of calldata words.
+struct CalldataReader<'a> {
+    words: core::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> CalldataReader<'a> {
+    /// Returns a new reader for the specified calldata.
+    ///
+    /// # Errors
+    /// Returns an error if the calldata does not consist of a whole number of words.
+    fn new(calldata: &'a [u8]) -> Result<Self, DeserializationError> {
+        if calldata.len() % WORD_SIZE != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "calldata length must be a multiple of {WORD_SIZE} bytes, but was {}",
+                calldata.len()
+            )));
+        }
+        Ok(Self {
+            words: calldata.chunks_exact(WORD_SIZE),
+        })
+    }
+
+    /// Returns true if there are words which have not been read yet.
+    fn has_more_words(&self) -> bool {
+        self.words.len() != 0
+    }
+
+    /// Reads a single word.
+    fn read_word(&mut self) -> Result<[u8; WORD_SIZE], DeserializationError> {
+        let word = self.words.next().ok_or(DeserializationError::UnexpectedEOF)?;
+        Ok(word.try_into().expect("word size is fixed"))
+    }
+
+    /// Reads the specified number of least significant bytes of a big-endian word in
+    /// little-endian order; all other bytes of the word must be zeros.
+    fn read_le_bytes(&mut self, num_bytes: usize) -> Result<Vec<u8>, DeserializationError> {
+        let word = self.read_word()?;
+        let (padding, value) = word.split_at(WORD_SIZE - num_bytes);
+        if padding.iter().any(|&byte| byte != 0) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "word value does not fit into {num_bytes} bytes"
+            )));
+        }
+        Ok(value.iter().rev().copied().collect())
+    }
+
+    /// Reads an integer from a big-endian word.
+    fn read_int(&mut self) -> Result<u64, DeserializationError> {
+        let bytes = self.read_le_bytes(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("integer size is fixed")))
+    }
+
+    /// Reads an integer from a big-endian word and makes sure it is not greater than `max`.
+    fn read_usize(&mut self, max: usize) -> Result<usize, DeserializationError> {
+        let value = self.read_int()?;
+        if value > max as u64 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "value {value} is greater than the maximum of {max}"
+            )));
+        }
+        Ok(value as usize)
+    }
+
+    /// Reads a byte string consisting of at most `max_len` bytes.
+    fn read_bytes(&mut self, max_len: usize) -> Result<Vec<u8>, DeserializationError> {
+        let len = self.read_usize(max_len)?;
+        let mut result = Vec::with_capacity(len);
+        while result.len() < len {
+            let word = self.read_word()?;
+            let num_bytes = (len - result.len()).min(WORD_SIZE);
+            if word[num_bytes..].iter().any(|&byte| byte != 0) {
+                return Err(DeserializationError::InvalidValue(
+                    "byte string padding must consist of zeros".into(),
+                ));
+            }
+            result.extend_from_slice(&word[..num_bytes]);
+        }
+        Ok(result)
+    }
+
+    /// Reads a list of field elements and returns them in their serialized form.
+    fn read_elements(&mut self, element_bytes: usize) -> Result<Vec<u8>, DeserializationError> {
+        let num_elements = self.read_usize(self.words.len())?;
+        let mut result = Vec::with_capacity(num_elements * element_bytes);
+        for _ in 0..num_elements {
+            result.append(&mut self.read_le_bytes(element_bytes)?);
+        }
+        Ok(result)
+    }
+
+    /// Reads a list of digests and returns them in their serialized form.
+    fn read_digests(&mut self, digest_bytes: usize) -> Result<Vec<u8>, DeserializationError> {
+        let num_digests = self.read_usize(self.words.len())?;
+        let mut result = Vec::with_capacity(num_digests * digest_bytes);
+        for _ in 0..num_digests {
+            let word = self.read_word()?;
+            if word[digest_bytes..].iter().any(|&byte| byte != 0) {
+                return Err(DeserializationError::InvalidValue(
+                    "digest padding must consist of zeros".into(),
+                ));
+            }
+            result.extend_from_slice(&word[..digest_bytes]);
+        }
+        Ok(result)
+    }
+
+    /// Reads internal nodes of a batch Merkle proof and returns them in the form produced by
+    /// [BatchMerkleProof::serialize_nodes()](crypto::BatchMerkleProof::serialize_nodes).
+    fn read_nodes(&mut self, digest_bytes: usize) -> Result<Vec<u8>, DeserializationError> {
+        let num_node_vectors = self.read_usize(u16::MAX as usize)?;
+        let mut result = Vec::new();
+        result.write_u16(num_node_vectors as u16);
+        for _ in 0..num_node_vectors {
+            let mut nodes = self.read_digests(digest_bytes)?;
+            let num_digests = nodes.len() / digest_bytes;
+            if num_digests > u8::MAX as usize {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "a Merkle path cannot contain more than 255 nodes, but {num_digests} were read"
+                )));
+            }
+            result.write_u8(num_digests as u8);
+            result.append(&mut nodes);
+        }
+        Ok(result)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a big-endian word with the value of the specified little-endian bytes.
+fn le_bytes_to_word(bytes: &[u8]) -> [u8; WORD_SIZE] {
+    let mut word = [0; WORD_SIZE];
+    for (target, &byte) in word.iter_mut().rev().zip(bytes) {
+        *target = byte;
+    }
+    word
+}
+
+/// Returns the number of bytes in a serialized digest of `H`.
+fn digest_size<H: Hasher>() -> usize {
+    H::Digest::default().to_bytes().len()
+}
@@ -0,0 +1,416 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Structured export of STARK proofs.
+//!
+//! A proof is exported as a tree of named sections which mirrors the binary layout of the proof.
+//! Parameters of the proof (e.g., the context and proof-of-work nonces) are exported as numbers;
+//! commitments, query values, Merkle authentication paths, out-of-domain evaluations, and the FRI
+//! remainder are exported in the same serialized form in which they are stored in the proof (as
+//! hex strings in JSON, and as byte strings in CBOR). Proof metadata is exported as a map of
+//! strings. The layout is:
+//!
+//! ```text
+//! {
+//!   "version": 6,
+//!   "context": {
+//!     "trace_layout": { "main_width", "aux_widths": [..], "aux_rands": [..] },
+//!     "trace_length", "trace_meta", "field_modulus",
+//!     "options": {
+//!       "num_queries", "blowup_factor", "grinding_factor", "field_extension",
+//!       "fri_folding_factor", "fri_remainder_max_degree", "fri_layer_grinding_factor",
+//!       "distinct_queries", "num_ood_points", "deep_coefficient_powers", "domain_offset"
+//!     },
+//!     "air_id", "air_fingerprint", "salt"
+//!   },
+//!   "num_unique_queries",
+//!   "commitments",
+//!   "trace_queries": [{ "values", "paths" }, ..],
+//!   "constraint_queries": { "values", "paths" },
+//!   "ood_frame": { "trace_states", "evaluations" },
+//!   "fri_proof": {
+//!     "layers": [{ "values", "paths" }, ..],
+//!     "remainder", "num_partitions", "layer_nonces": [..]
+//!   },
+//!   "pow_nonce",
+//!   "metadata": { "<key>": "<value>", .. }
+//! }
+//! ```
+//!
+//! The domain offset is exported as zero when the primitive element of the base field is used as
+//! the offset, and the salt is exported as an empty byte string when the proof is not salted.
+//! Sections are always emitted in this order. The version is incremented whenever a section is
+//! added, removed, or changes its meaning.
+
+use super::{Queries, StarkProof};
+use core::fmt::Write;
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the export schema.
+pub const EXPORT_VERSION: u64 = 6;
+
+// STARK PROOF EXPORT
+// ================================================================================================
+
+impl StarkProof {
+    /// Returns a JSON representation of this proof.
+    ///
+    /// See [export](crate::proof::export) module for the description of the schema.
+    pub fn to_json(&self) -> String {
+        let mut result = String::new();
+        self.export().write_json(&mut result);
+        result
+    }
+
+    /// Returns a CBOR ([RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)) representation of this
+    /// proof.
+    ///
+    /// The representation has the same structure as the one returned by
+    /// [to_json()](StarkProof::to_json), with serialized components encoded as CBOR byte strings.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.export().write_cbor(&mut result);
+        result
+    }
+
+    /// Builds a tree of named sections describing this proof.
+    fn export(&self) -> Value {
+        let context = &self.context;
+        let layout = context.trace_layout();
+        let options = context.options();
+        let fri_options = options.to_fri_options();
+
+        let aux_widths = (0..layout.num_aux_segments())
+            .map(|i| Value::from_usize(layout.get_aux_segment_width(i)))
+            .collect();
+        let aux_rands = (0..layout.num_aux_segments())
+            .map(|i| Value::from_usize(layout.get_aux_segment_rand_elements(i)))
+            .collect();
+        let trace_layout = Value::Map(vec![
+            ("main_width", Value::from_usize(layout.main_trace_width())),
+            ("aux_widths", Value::List(aux_widths)),
+            ("aux_rands", Value::List(aux_rands)),
+        ]);
+
+        let options = Value::Map(vec![
+            ("num_queries", Value::from_usize(options.num_queries())),
+            ("blowup_factor", Value::from_usize(options.blowup_factor())),
+            ("grinding_factor", Value::UInt(options.grinding_factor() as u64)),
+            ("field_extension", Value::UInt(options.field_extension().degree() as u64)),
+            ("fri_folding_factor", Value::from_usize(fri_options.folding_factor())),
+            (
+                "fri_remainder_max_degree",
+                Value::from_usize(fri_options.remainder_max_degree()),
+            ),
+            (
+                "fri_layer_grinding_factor",
+                Value::UInt(options.fri_layer_grinding_factor() as u64),
+            ),
+            ("distinct_queries", Value::Bool(options.distinct_queries())),
+            ("num_ood_points", Value::from_usize(options.num_ood_points())),
+            ("deep_coefficient_powers", Value::Bool(options.deep_coefficient_powers())),
+            ("domain_offset", Value::UInt(options.custom_domain_offset().unwrap_or(0))),
+        ]);
+
+        let context = Value::Map(vec![
+            ("trace_layout", trace_layout),
+            ("trace_length", Value::from_usize(context.trace_length())),
+            ("trace_meta", Value::Bytes(context.get_trace_info().meta().to_vec())),
+            ("field_modulus", Value::Bytes(context.field_modulus_bytes().to_vec())),
+            ("options", options),
+            ("air_id", Value::UInt(context.air_id() as u64)),
+            ("air_fingerprint", Value::Bytes(context.air_fingerprint().to_vec())),
+            ("salt", Value::Bytes(context.salt().map_or(Vec::new(), |salt| salt.to_vec()))),
+        ]);
+
+        let fri_layers = (0..self.fri_proof.num_layers())
+            .map(|i| {
+                let (values, paths) = self.fri_proof.layer_bytes(i);
+                Value::Map(vec![
+                    ("values", Value::Bytes(values.to_vec())),
+                    ("paths", Value::Bytes(paths.to_vec())),
+                ])
+            })
+            .collect();
+        let layer_nonces =
+            self.fri_proof.layer_nonces().iter().map(|&nonce| Value::UInt(nonce)).collect();
+        let fri_proof = Value::Map(vec![
+            ("layers", Value::List(fri_layers)),
+            ("remainder", Value::Bytes(self.fri_proof.remainder_bytes().to_vec())),
+            ("num_partitions", Value::from_usize(self.fri_proof.num_partitions())),
+            ("layer_nonces", Value::List(layer_nonces)),
+        ]);
+
+        let ood_frame = Value::Map(vec![
+            ("trace_states", Value::Bytes(self.ood_frame.trace_states.clone())),
+            ("evaluations", Value::Bytes(self.ood_frame.evaluations.clone())),
+        ]);
+
+        let metadata = Value::TextMap(
+            self.metadata.iter().map(|(key, value)| (key.clone(), value.clone())).collect(),
+        );
+
+        Value::Map(vec![
+            ("version", Value::UInt(EXPORT_VERSION)),
+            ("context", context),
+            ("num_unique_queries", Value::UInt(self.num_unique_queries as u64)),
+            ("commitments", Value::Bytes(self.commitments.0.clone())),
+            (
+                "trace_queries",
+                Value::List(self.trace_queries.iter().map(export_queries).collect()),
+            ),
+            ("constraint_queries", export_queries(&self.constraint_queries)),
+            ("ood_frame", ood_frame),
+            ("fri_proof", fri_proof),
+            ("pow_nonce", Value::UInt(self.pow_nonce)),
+            ("metadata", metadata),
+        ])
+    }
+}
+
+// EXPORTED VALUE
+// ================================================================================================
+
+/// A node in the tree of exported proof sections.
+///
+/// This is also used to export constraint systems (see [ir](crate::ir) module).
+pub(crate) enum Value {
+    Null,
+    UInt(u64),
+    Bool(bool),
+    Text(String),
+    Bytes(Vec<u8>),
+    List(Vec<Value>),
+    Map(Vec<(&'static str, Value)>),
+    TextMap(Vec<(String, String)>),
+}
+
+impl Value {
+    pub(crate) fn from_usize(value: usize) -> Self {
+        Self::UInt(value as u64)
+    }
+
+    // JSON ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes this value into the target as JSON; byte strings are written as hex strings, and
+    /// keys are known not to require escaping.
+    pub(crate) fn write_json(&self, target: &mut String) {
+        match self {
+            Self::Null => target.push_str("null"),
+            Self::UInt(value) => target.push_str(&value.to_string()),
+            Self::Bool(value) => target.push_str(if *value { "true" } else { "false" }),
+            Self::Text(text) => write_json_string(target, text),
+            Self::Bytes(bytes) => {
+                target.push('"');
+                for byte in bytes {
+                    write!(target, "{byte:02x}").expect("failed to write to string");
+                }
+                target.push('"');
+            }
+            Self::List(values) => {
+                target.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        target.push(',');
+                    }
+                    value.write_json(target);
+                }
+                target.push(']');
+            }
+            Self::Map(entries) => {
+                target.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        target.push(',');
+                    }
+                    target.push('"');
+                    target.push_str(key);
+                    target.push_str("\":");
+                    value.write_json(target);
+                }
+                target.push('}');
+            }
+            Self::TextMap(entries) => {
+                target.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        target.push(',');
+                    }
+                    write_json_string(target, key);
+                    target.push(':');
+                    write_json_string(target, value);
+                }
+                target.push('}');
+            }
+        }
+    }
+
+    // CBOR ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes this value into the target as CBOR using definite-length encoding.
+    pub(crate) fn write_cbor(&self, target: &mut Vec<u8>) {
+        match self {
+            Self::Null => target.push(0xf6),
+            Self::UInt(value) => write_cbor_head(target, 0, *value),
+            Self::Bool(value) => target.push(if *value { 0xf5 } else { 0xf4 }),
+            Self::Text(text) => {
+                write_cbor_head(target, 3, text.len() as u64);
+                target.extend_from_slice(text.as_bytes());
+            }
+            Self::Bytes(bytes) => {
+                write_cbor_head(target, 2, bytes.len() as u64);
+                target.extend_from_slice(bytes);
+            }
+            Self::List(values) => {
+                write_cbor_head(target, 4, values.len() as u64);
+                for value in values {
+                    value.write_cbor(target);
+                }
+            }
+            Self::Map(entries) => {
+                write_cbor_head(target, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    write_cbor_head(target, 3, key.len() as u64);
+                    target.extend_from_slice(key.as_bytes());
+                    value.write_cbor(target);
+                }
+            }
+            Self::TextMap(entries) => {
+                write_cbor_head(target, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    for text in [key, value] {
+                        write_cbor_head(target, 3, text.len() as u64);
+                        target.extend_from_slice(text.as_bytes());
+                    }
+                }
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the exported representation of the specified queries.
+fn export_queries(queries: &Queries) -> Value {
+    Value::Map(vec![
+        ("values", Value::Bytes(queries.values.clone())),
+        ("paths", Value::Bytes(queries.paths.clone())),
+    ])
+}
+
+/// Writes the specified text into the target as a JSON string, escaping quotes, backslashes, and
+/// control characters.
+fn write_json_string(target: &mut String, text: &str) {
+    target.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => target.push_str("\\\""),
+            '\\' => target.push_str("\\\\"),
+            '\n' => target.push_str("\\n"),
+            '\r' => target.push_str("\\r"),
+            '\t' => target.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                write!(target, "\\u{:04x}", c as u32).expect("failed to write to string")
+            }
+            c => target.push(c),
+        }
+    }
+    target.push('"');
+}
+
+/// Writes the head of a CBOR data item with the specified major type and argument.
+fn write_cbor_head(target: &mut Vec<u8>, major_type: u8, argument: u64) {
+    let major_type = major_type << 5;
+    if argument < 24 {
+        target.push(major_type | argument as u8);
+    } else if argument <= u8::MAX as u64 {
+        target.push(major_type | 24);
+        target.push(argument as u8);
+    } else if argument <= u16::MAX as u64 {
+        target.push(major_type | 25);
+        target.extend_from_slice(&(argument as u16).to_be_bytes());
+    } else if argument <= u32::MAX as u64 {
+        target.push(major_type | 26);
+        target.extend_from_slice(&(argument as u32).to_be_bytes());
+    } else {
+        target.push(major_type | 27);
+        target.extend_from_slice(&argument.to_be_bytes());
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{write_cbor_head, Value, EXPORT_VERSION};
+    use crate::proof::StarkProof;
+    use utils::string::String;
+
+    #[test]
+    fn export_stark_proof() {
+        let proof = StarkProof::new_dummy();
+
+        let json = proof.to_json();
+        assert!(json.starts_with(&format!("{{\"version\":{EXPORT_VERSION},\"context\":{{")));
+        assert!(json.contains("\"fri_proof\":{\"layers\":[],"));
+        assert!(json.ends_with("\"pow_nonce\":0,\"metadata\":{}}"));
+
+        // a map with 10 sections, starting with the version
+        let cbor = proof.to_cbor();
+        assert_eq!(&[0xaa, 0x67], &cbor[..2]);
+        assert_eq!(b"version", &cbor[2..9]);
+        assert_eq!(EXPORT_VERSION as u8, cbor[9]);
+    }
+
+    #[test]
+    fn export_json_encoding() {
+        let value = Value::Map(vec![
+            ("a", Value::UInt(7)),
+            ("b", Value::List(vec![Value::Bool(true), Value::Bytes(vec![0x01, 0xab])])),
+        ]);
+        let mut json = String::new();
+        value.write_json(&mut json);
+        assert_eq!(r#"{"a":7,"b":[true,"01ab"]}"#, json);
+
+        let value = Value::TextMap(vec![("k".into(), "a\"b\\c\n\u{1}".into())]);
+        let mut json = String::new();
+        value.write_json(&mut json);
+        assert_eq!(r#"{"k":"a\"b\\c\n\u0001"}"#, json);
+    }
+
+    #[test]
+    fn export_cbor_encoding() {
+        // examples from appendix A of RFC 8949
+        let encode = |argument| {
+            let mut result = vec![];
+            write_cbor_head(&mut result, 0, argument);
+            result
+        };
+        assert_eq!(vec![0x17], encode(23));
+        assert_eq!(vec![0x18, 0x18], encode(24));
+        assert_eq!(vec![0x19, 0x03, 0xe8], encode(1000));
+        assert_eq!(vec![0x1a, 0x00, 0x0f, 0x42, 0x40], encode(1000000));
+        assert_eq!(
+            vec![0x1b, 0x00, 0x00, 0x00, 0xe8, 0xd4, 0xa5, 0x10, 0x00],
+            encode(1000000000000)
+        );
+
+        let value =
+            Value::Map(vec![("a", Value::UInt(1)), ("b", Value::List(vec![Value::UInt(2)]))]);
+        let mut cbor = vec![];
+        value.write_cbor(&mut cbor);
+        assert_eq!(vec![0xa2, 0x61, 0x61, 0x01, 0x61, 0x62, 0x81, 0x02], cbor);
+    }
+}
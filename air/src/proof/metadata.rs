@@ -0,0 +1,163 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use utils::{
+    collections::{btree_map, BTreeMap},
+    string::String,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+const MAX_ENTRIES: usize = u8::MAX as usize;
+const MAX_KEY_BYTES: usize = u8::MAX as usize;
+const MAX_VALUE_BYTES: usize = u16::MAX as usize;
+
+// PROOF METADATA
+// ================================================================================================
+/// Key-value metadata attached to a STARK proof.
+///
+/// Metadata can be used to track and audit proofs (e.g., by recording the version of the prover,
+/// an identifier of the computation, or the time at which a proof was generated). Metadata is
+/// serialized together with the proof in a trailer section, but it is not a part of the protocol
+/// transcript: it is neither committed to by the prover nor checked by the verifier, and thus,
+/// anyone who has a proof can change its metadata without invalidating the proof. Metadata
+/// should therefore never be relied on for anything which affects the validity of a statement.
+///
+/// Entries are kept sorted by key. A proof can contain at most 255 entries; keys can be at most
+/// 255 bytes long, and values can be at most 65535 bytes long.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ProofMetadata {
+    entries: BTreeMap<String, String>,
+}
+
+impl ProofMetadata {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Key under which the version of the prover which generated a proof is recorded.
+    pub const PROVER_VERSION: &'static str = "prover_version";
+
+    /// Key under which an identifier of the proven computation is recorded.
+    pub const CIRCUIT_ID: &'static str = "circuit_id";
+
+    /// Key under which the time at which a proof was generated is recorded.
+    pub const CREATED_AT: &'static str = "created_at";
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns new empty metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the value recorded under the specified key, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|value| value.as_str())
+    }
+
+    /// Returns the number of entries in this metadata.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this metadata contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over all entries of this metadata sorted by key.
+    pub fn iter(&self) -> btree_map::Iter<'_, String, String> {
+        self.entries.iter()
+    }
+
+    // UPDATERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the specified value under the specified key, and returns the value previously
+    /// recorded under this key, if any.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `key` is longer than 255 bytes or `value` is longer than 65535 bytes.
+    /// - The key is new and this metadata already contains 255 entries.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        let key = key.into();
+        let value = value.into();
+        assert!(key.len() <= MAX_KEY_BYTES, "metadata key cannot be longer than 255 bytes");
+        assert!(
+            value.len() <= MAX_VALUE_BYTES,
+            "metadata value cannot be longer than 65535 bytes"
+        );
+        assert!(
+            self.entries.len() < MAX_ENTRIES || self.entries.contains_key(&key),
+            "metadata cannot contain more than 255 entries"
+        );
+        self.entries.insert(key, value)
+    }
+
+    /// Removes the entry with the specified key, and returns its value, if any.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.entries.remove(key)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for ProofMetadata {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.entries.len() as u8);
+        for (key, value) in self.entries.iter() {
+            target.write_u8(key.len() as u8);
+            target.write_bytes(key.as_bytes());
+            target.write_u16(value.len() as u16);
+            target.write_bytes(value.as_bytes());
+        }
+    }
+
+    /// Returns an estimate of how many bytes are needed to represent self.
+    fn get_size_hint(&self) -> usize {
+        let entries_size: usize =
+            self.entries.iter().map(|(key, value)| 3 + key.len() + value.len()).sum();
+        1 + entries_size
+    }
+}
+
+impl Deserializable for ProofMetadata {
+    /// Reads proof metadata from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid metadata could not be read from the specified `source`, if an
+    /// entry is not valid UTF-8, or if entries are not sorted by key.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_entries = source.read_u8()? as usize;
+        let mut entries = BTreeMap::new();
+        let mut last_key: Option<String> = None;
+        for _ in 0..num_entries {
+            let key_len = source.read_u8()? as usize;
+            let key = source.read_string(key_len)?;
+            let value_len = source.read_u16()? as usize;
+            let value = source.read_string(value_len)?;
+
+            // keys are written in sorted order, and thus, there is exactly one valid encoding of
+            // every metadata
+            if last_key.as_ref().is_some_and(|last_key| *last_key >= key) {
+                return Err(DeserializationError::InvalidValue(
+                    "metadata keys must be unique and sorted".into(),
+                ));
+            }
+            last_key = Some(key.clone());
+            entries.insert(key, value);
+        }
+        Ok(Self { entries })
+    }
+}
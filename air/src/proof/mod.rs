@@ -5,8 +5,8 @@
 
 //! Contains STARK proof struct and associated components.
 
-use crate::{ProofOptions, TraceInfo, TraceLayout};
-use core::cmp;
+use crate::{Air, ProofOptions, TraceInfo, TraceLayout};
+use core::{cmp, mem};
 use crypto::Hasher;
 use fri::FriProof;
 use math::FieldElement;
@@ -29,6 +29,25 @@ pub use ood_frame::OodFrame;
 mod table;
 pub use table::Table;
 
+mod continuation;
+pub use continuation::ContinuationProof;
+
+mod opening;
+pub use opening::TraceOpening;
+
+mod metadata;
+pub use metadata::ProofMetadata;
+
+mod security;
+pub use security::{ConjecturedSecurity, ProvenSecurity, SecurityBottleneck, SecurityReport};
+
+pub mod evm;
+
+pub mod stream;
+
+#[cfg(feature = "export")]
+pub mod export;
+
 #[cfg(test)]
 mod tests;
 
@@ -51,14 +70,15 @@ const MAX_PROXIMITY_PARAMETER: u64 = 1000;
 /// function.
 ///
 /// To estimate soundness of a proof (in bits), [security_level()](StarkProof::security_level)
-/// function can be used.
+/// function can be used. The size of a serialized proof can be predicted before the proof is
+/// generated via [estimate_size()](StarkProof::estimate_size) function.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct StarkProof {
     /// Basic metadata about the execution of the computation described by this proof.
     pub context: Context,
     /// Number of unique queries made by the verifier. This will be different from the
     /// context.options.num_queries if the same position in the domain was queried more than once.
-    pub num_unique_queries: u8,
+    pub num_unique_queries: u16,
     /// Commitments made by the prover during the commit phase of the protocol.
     pub commitments: Commitments,
     /// Decommitments of extended execution trace values (for all trace segments) at position
@@ -73,6 +93,9 @@ pub struct StarkProof {
     pub fri_proof: FriProof,
     /// Proof-of-work nonce for query seed grinding.
     pub pow_nonce: u64,
+    /// Key-value metadata attached to the proof; metadata is not a part of the protocol
+    /// transcript, and is not checked by the verifier.
+    pub metadata: ProofMetadata,
 }
 
 impl StarkProof {
@@ -111,21 +134,101 @@ impl StarkProof {
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
     /// security level.
     pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
-        if conjectured {
-            get_conjectured_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.trace_length(),
-                H::COLLISION_RESISTANCE,
-            )
+        self.context.security_level::<H>(conjectured)
+    }
+
+    /// Returns a breakdown of the conjectured and the proven security levels of this proof.
+    ///
+    /// The report lists the contributions of the field size, the queries, grinding, and the
+    /// collision resistance of hash function `H`, together with the term which limits each of
+    /// the security levels.
+    pub fn security_report<H: Hasher>(&self) -> SecurityReport {
+        self.context.security_report::<H>()
+    }
+
+    // SIZE ESTIMATE
+    // --------------------------------------------------------------------------------------------
+    /// Returns the expected size (in bytes) of a serialized proof for the specified AIR generated
+    /// using hash function `H`.
+    ///
+    /// The size is computed from the dimensions of the execution trace, the number of constraint
+    /// composition columns, and the proof options of the AIR alone, and thus, can be used to
+    /// select proof options which fit a given size budget before generating a proof (e.g., by
+    /// building the AIR with different candidate options). The query positions and thus, the
+    /// number of unique queries and the number of nodes shared between Merkle authentication
+    /// paths are random; the returned value is the size expected for uniformly drawn positions,
+    /// and sizes of actual proofs usually differ from it by no more than a few percent.
+    pub fn estimate_size<A: Air, H: Hasher>(air: &A) -> usize {
+        let options = air.options();
+        let base_bytes = <A::BaseField as FieldElement>::ELEMENT_BYTES;
+        let ext_bytes = base_bytes * options.field_extension().degree() as usize;
+        let digest_bytes = mem::size_of::<H::Digest>();
+
+        let lde_domain_size = air.lde_domain_size();
+        let layout = air.trace_layout();
+        let num_composition_columns = air.context().num_constraint_composition_columns();
+        let fri_options = options.to_fri_options();
+        let folding_factor = fri_options.folding_factor();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let num_layer_nonces = if fri_options.layer_grinding_factor() > 0 {
+            num_fri_layers
         } else {
-            get_proven_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.trace_length(),
-                H::COLLISION_RESISTANCE,
+            0
+        };
+
+        // context, number of unique queries, and commitments to all trace segments, to the
+        // constraint composition polynomial, to all FRI layers, and to the FRI remainder
+        let context = Context::new::<A::BaseField>(air.trace_info(), options.clone())
+            .set_air_id(A::AIR_ID)
+            .set_air_fingerprint(air.fingerprint());
+        let num_commitments = layout.num_segments() + 1 + num_fri_layers + 1;
+        let mut size = (context.to_bytes().len() + 2 + 2 + num_commitments * digest_bytes) as f64;
+
+        // trace and constraint queries are opened against trees with a leaf per position of the
+        // LDE domain
+        let queries_size = |leaf_bytes| {
+            estimate_queries_size(
+                lde_domain_size,
+                lde_domain_size,
+                leaf_bytes,
+                digest_bytes,
+                options,
             )
+        };
+        size += queries_size(layout.main_trace_width() * base_bytes);
+        for i in 0..layout.num_aux_segments() {
+            size += queries_size(layout.get_aux_segment_width(i) * ext_bytes);
         }
+        size += queries_size(num_composition_columns * ext_bytes);
+
+        // out-of-domain frame contains two rows of the trace and evaluations of all composition
+        // polynomial columns for every out-of-domain point
+        let trace_width = layout.main_trace_width() + layout.aux_trace_width();
+        let ood_point_bytes = (2 * trace_width + num_composition_columns) * ext_bytes;
+        size += (4 + 1 + 4 + options.num_ood_points() * ood_point_bytes) as f64;
+
+        // every FRI layer is committed to via a tree with one leaf per folded coset; query
+        // positions are folded into the domain of the tree
+        size += 1.0;
+        let mut domain_size = lde_domain_size;
+        for _ in 0..num_fri_layers {
+            domain_size /= folding_factor;
+            let leaf_bytes = folding_factor * ext_bytes;
+            size += estimate_queries_size(
+                domain_size,
+                lde_domain_size,
+                leaf_bytes,
+                digest_bytes,
+                options,
+            );
+        }
+        let remainder_size = domain_size / options.blowup_factor();
+        size += (4 + remainder_size * ext_bytes + 1 + 1 + num_layer_nonces * 8) as f64;
+
+        // proof-of-work nonce and empty metadata
+        size += 8.0 + 1.0;
+
+        ceil(size) as usize
     }
 
     // SERIALIZATION / DESERIALIZATION
@@ -170,6 +273,7 @@ impl StarkProof {
             ood_frame: OodFrame::default(),
             fri_proof: FriProof::new_dummy(),
             pow_nonce: 0,
+            metadata: ProofMetadata::new(),
         }
     }
 }
@@ -180,20 +284,21 @@ impl StarkProof {
 impl Serializable for StarkProof {
     fn write_into<W: utils::ByteWriter>(&self, target: &mut W) {
         self.context.write_into(target);
-        target.write_u8(self.num_unique_queries);
+        target.write_u16(self.num_unique_queries);
         self.commitments.write_into(target);
         self.trace_queries.write_into(target);
         self.constraint_queries.write_into(target);
         self.ood_frame.write_into(target);
         self.fri_proof.write_into(target);
-        self.pow_nonce.write_into(target)
+        self.pow_nonce.write_into(target);
+        self.metadata.write_into(target)
     }
 }
 
 impl Deserializable for StarkProof {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let context = Context::read_from(source)?;
-        let num_unique_queries = source.read_u8()?;
+        let num_unique_queries = source.read_u16()?;
         let commitments = Commitments::read_from(source)?;
         let num_trace_segments = context.trace_layout().num_segments();
         let mut trace_queries = Vec::with_capacity(num_trace_segments);
@@ -210,6 +315,7 @@ impl Deserializable for StarkProof {
             ood_frame: OodFrame::read_from(source)?,
             fri_proof: FriProof::read_from(source)?,
             pow_nonce: source.read_u64()?,
+            metadata: ProofMetadata::read_from(source)?,
         };
         Ok(proof)
     }
@@ -219,60 +325,127 @@ impl Deserializable for StarkProof {
 // ================================================================================================
 
 /// Computes conjectured security level for the specified proof parameters.
-fn get_conjectured_security(
+///
+/// `trace_width` is the total number of columns in all segments of the execution trace.
+pub(crate) fn get_conjectured_security(
     options: &ProofOptions,
     base_field_bits: u32,
     trace_domain_size: usize,
+    trace_width: usize,
     collision_resistance: u32,
 ) -> u32 {
-    // compute max security we can get for a given field size
+    get_conjectured_security_breakdown(
+        options,
+        base_field_bits,
+        trace_domain_size,
+        trace_width,
+        collision_resistance,
+    )
+    .security_level
+}
+
+/// Computes a breakdown of the conjectured security level for the specified proof parameters.
+pub(crate) fn get_conjectured_security_breakdown(
+    options: &ProofOptions,
+    base_field_bits: u32,
+    trace_domain_size: usize,
+    trace_width: usize,
+    collision_resistance: u32,
+) -> ConjecturedSecurity {
+    // compute max security we can get for a given field size; when DEEP composition coefficients
+    // are powers of a single challenge, the batching error grows with the number of batched
+    // polynomials
     let field_size = base_field_bits * options.field_extension().degree();
-    let field_security = field_size - (trace_domain_size * options.blowup_factor()).ilog2();
+    let field_security = (field_size - (trace_domain_size * options.blowup_factor()).ilog2())
+        .saturating_sub(ceil(get_deep_batching_loss(options, trace_width)) as u32);
 
     // compute security we get by executing multiple query rounds
-    let security_per_query = options.blowup_factor().ilog2();
-    let mut query_security = security_per_query * options.num_queries() as u32;
+    let query_security = if options.distinct_queries() {
+        let lde_domain_size = trace_domain_size * options.blowup_factor();
+        let rho = 1.0 / options.blowup_factor() as f64;
+        distinct_query_security(options.num_queries(), lde_domain_size, rho) as u32
+    } else {
+        let security_per_query = options.blowup_factor().ilog2();
+        security_per_query * options.num_queries() as u32
+    };
 
     // include grinding factor contributions only for proofs adequate security
-    if query_security >= GRINDING_CONTRIBUTION_FLOOR {
-        query_security += options.grinding_factor();
+    let grinding_security = if query_security >= GRINDING_CONTRIBUTION_FLOOR {
+        options.grinding_factor()
+    } else {
+        0
+    };
+
+    let protocol_security = cmp::min(field_security, query_security + grinding_security) - 1;
+    let bottleneck = if collision_resistance < protocol_security {
+        SecurityBottleneck::HashFunction
+    } else if field_security <= query_security + grinding_security {
+        SecurityBottleneck::Field
+    } else {
+        SecurityBottleneck::Queries
+    };
+
+    ConjecturedSecurity {
+        security_level: cmp::min(protocol_security, collision_resistance),
+        field_security,
+        query_security,
+        grinding_security,
+        bottleneck,
     }
-
-    cmp::min(cmp::min(field_security, query_security) - 1, collision_resistance)
 }
 
 /// Estimates proven security level for the specified proof parameters.
-fn get_proven_security(
+///
+/// `trace_width` is the total number of columns in all segments of the execution trace.
+pub(crate) fn get_proven_security(
     options: &ProofOptions,
     base_field_bits: u32,
     trace_domain_size: usize,
+    trace_width: usize,
     collision_resistance: u32,
 ) -> u32 {
+    get_proven_security_breakdown(
+        options,
+        base_field_bits,
+        trace_domain_size,
+        trace_width,
+        collision_resistance,
+    )
+    .security_level
+}
+
+/// Computes a breakdown of the proven security level for the specified proof parameters.
+pub(crate) fn get_proven_security_breakdown(
+    options: &ProofOptions,
+    base_field_bits: u32,
+    trace_domain_size: usize,
+    trace_width: usize,
+    collision_resistance: u32,
+) -> ProvenSecurity {
     let m_min: usize = 3;
-    let m_max = compute_upper_m(trace_domain_size);
+    let m_max = compute_upper_m(trace_domain_size, options.num_ood_points());
+    let deep_batching_loss = get_deep_batching_loss(options, trace_width);
 
-    let m_optimal = (m_min as u32..m_max as u32)
-        .max_by_key(|&a| {
+    let mut result = (m_min as u32..m_max as u32)
+        .map(|a| {
             proven_security_protocol_for_m(
                 options,
                 base_field_bits,
                 trace_domain_size,
+                deep_batching_loss,
                 a as usize,
             )
         })
+        .max_by_key(|security| security.security_level)
         .expect(
             "Should not fail since m_max is larger than m_min for all trace sizes of length greater than 4",
         );
 
-    cmp::min(
-        proven_security_protocol_for_m(
-            options,
-            base_field_bits,
-            trace_domain_size,
-            m_optimal as usize,
-        ),
-        collision_resistance as u64,
-    ) as u32
+    if collision_resistance < result.security_level {
+        result.security_level = collision_resistance;
+        result.bottleneck = SecurityBottleneck::HashFunction;
+    }
+    result
 }
 
 /// Computes proven security level for the specified proof parameters for a fixed
@@ -281,8 +454,10 @@ fn proven_security_protocol_for_m(
     options: &ProofOptions,
     base_field_bits: u32,
     trace_domain_size: usize,
+    deep_batching_loss: f64,
     m: usize,
-) -> u64 {
+) -> ProvenSecurity {
+    let proximity_parameter = m as u32;
     let extension_field_bits = (base_field_bits * options.field_extension().degree()) as f64;
     let num_fri_queries = options.num_queries() as f64;
     let m = m as f64;
@@ -297,17 +472,46 @@ fn proven_security_protocol_for_m(
     // Computes FRI commit-phase (i.e., pre-query) soundness error.
     // This considers only the first term given in eq. 7 in https://eprint.iacr.org/2022/1216.pdf,
     // i.e. 0.5 * (m + 0.5)^7 * n^2 / (rho^1.5.q) as all other terms are negligible in comparison.
+    // Proof-of-work performed after committing to each FRI layer must be redone every time the
+    // prover attempts to resample a folding challenge, and thus, reduces this error accordingly.
     let fri_commit_err_bits = extension_field_bits
-        - log2((0.5 * powf(m + 0.5, 7.0) / powf(rho, 1.5)) * powf(lde_domain_size, 2.0));
-
-    // Compute FRI query-phase soundness error
-    let fri_queries_err_bits =
-        options.grinding_factor() as f64 - log2(powf(1.0 - theta, num_fri_queries));
+        - log2((0.5 * powf(m + 0.5, 7.0) / powf(rho, 1.5)) * powf(lde_domain_size, 2.0))
+        + options.fri_layer_grinding_factor() as f64;
+
+    // Compute FRI query-phase soundness error; when queries are distinct, the probability that
+    // all of them fall into the agreement set is given by sampling without replacement
+    let fri_queries_err_bits = if options.distinct_queries() {
+        let lde_domain_size = lde_domain_size as usize;
+        distinct_query_security(options.num_queries(), lde_domain_size, 1.0 - theta)
+    } else {
+        -log2(powf(1.0 - theta, num_fri_queries))
+    };
+    let grinding_err_bits = options.grinding_factor() as u64;
 
     // Combined error for FRI
-    let fri_err_bits = cmp::min(fri_commit_err_bits as u64, fri_queries_err_bits as u64);
+    let fri_commit_err_bits = fri_commit_err_bits as u64;
+    let fri_queries_err_bits = fri_queries_err_bits as u64;
+    // with many queries, the FRI query error may underflow to zero, and thus, its security may
+    // saturate at the largest value
+    let fri_queries_grinding_err_bits = fri_queries_err_bits.saturating_add(grinding_err_bits);
+    let fri_err_bits = cmp::min(fri_commit_err_bits, fri_queries_grinding_err_bits);
+    let fri_bottleneck = if fri_commit_err_bits <= fri_queries_grinding_err_bits {
+        SecurityBottleneck::Field
+    } else {
+        SecurityBottleneck::Queries
+    };
+    let mut result = ProvenSecurity {
+        security_level: 0,
+        proximity_parameter,
+        fri_commit_security: fri_commit_err_bits as u32,
+        fri_query_security: fri_queries_err_bits as u32,
+        grinding_security: grinding_err_bits as u32,
+        ali_security: 0,
+        deep_security: 0,
+        bottleneck: fri_bottleneck,
+    };
     if fri_err_bits < 1 {
-        return 0;
+        return result;
     }
     let fri_err_bits = fri_err_bits - 1;
 
@@ -322,8 +526,10 @@ fn proven_security_protocol_for_m(
     // alpha <= rho_plus.sqrt() * (1 + 1/2m_plus). This m_plus will be used to define
     // the list-decoding list size in F(Z).
 
-    // Modified rate in function field F(Z)
-    let rho_plus = (trace_domain_size + 2.0) / lde_domain_size;
+    // Modified rate in function field F(Z); the quotients are batched over the set containing
+    // z and z * g for every out-of-domain point z
+    let num_ood_points = options.num_ood_points() as f64;
+    let rho_plus = (trace_domain_size + 2.0 * num_ood_points) / lde_domain_size;
     // New proximity parameter m_plus, corresponding to rho_plus, needed to make sure that
     //  alpha < rho_plus.sqrt() * (1 + 1 / (2 * m_plus))
     let m_plus = ceil(1.0 / (2.0 * (alpha / sqrt(rho_plus) - 1.0)));
@@ -337,27 +543,137 @@ fn proven_security_protocol_for_m(
 
     // DEEP related soundness error. Note that this uses that the denominator |F| - |D ∪ H|
     // can be approximated by |F| for all practical domain sizes. We also use the blow-up factor
-    // as an upper bound for the maximal constraint degree.
-    let deep_err_bits =
-        -log2(l_plus * (max_deg * (trace_domain_size + 1.0) + (trace_domain_size - 1.0)))
-            + extension_field_bits;
-
-    let min = cmp::min(cmp::min(fri_err_bits, ali_err_bits as u64), deep_err_bits as u64);
-    if min < 1 {
-        return 0;
+    // as an upper bound for the maximal constraint degree. For every codeword in the list, all
+    // out-of-domain points (which are drawn independently) need to be favorable to the prover,
+    // and thus, the per-point error is raised to the power of the number of points. When DEEP
+    // composition coefficients are powers of a single challenge, the error of batching the
+    // quotients is multiplied by the number of batched polynomials.
+    let deep_err_degree = max_deg * (trace_domain_size + 1.0) + (trace_domain_size - 1.0);
+    let deep_err_bits = -log2(l_plus * powf(deep_err_degree, num_ood_points))
+        + extension_field_bits * num_ood_points
+        - deep_batching_loss;
+
+    let ali_err_bits = ali_err_bits as u64;
+    let deep_err_bits = deep_err_bits as u64;
+    result.ali_security = ali_err_bits as u32;
+    result.deep_security = deep_err_bits as u32;
+    if cmp::min(ali_err_bits, deep_err_bits) < fri_err_bits {
+        result.bottleneck = SecurityBottleneck::Field;
     }
 
-    min - 1
+    let min = cmp::min(cmp::min(fri_err_bits, ali_err_bits), deep_err_bits);
+    if min >= 1 {
+        result.security_level = (min - 1) as u32;
+    }
+    result
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the number of bits of security lost when the polynomials batched into the DEEP
+/// composition polynomial are combined using powers of a single random challenge.
+///
+/// With powers of a single challenge, the batched combination of polynomials which are not all
+/// of low degree is of low degree for at most as many challenges as there are batched
+/// polynomials, and thus, the batching error grows by a factor equal to the number of batched
+/// polynomials. These are all trace columns and all constraint composition columns. The number
+/// of composition columns is not recorded in proof contexts and proof options; since it never
+/// exceeds the blowup factor, the blowup factor is used in its place. When coefficients are
+/// drawn independently, no security is lost.
+fn get_deep_batching_loss(options: &ProofOptions, trace_width: usize) -> f64 {
+    if options.deep_coefficient_powers() {
+        log2((trace_width + options.blowup_factor()) as f64)
+    } else {
+        0.0
+    }
+}
+
+/// Returns the number of bits of security provided by `num_queries` distinct queries against
+/// a domain of the specified size when the fraction `agreement` of the domain consists of
+/// positions at which a cheating prover passes a query.
+///
+/// This is -log2 of the probability that all queries sampled without replacement from the
+/// domain fall into the agreement set, and is never smaller than the security of the same
+/// number of queries sampled with replacement (i.e., `-num_queries * log2(agreement)`).
+fn distinct_query_security(num_queries: usize, domain_size: usize, agreement: f64) -> f64 {
+    let domain_size = domain_size as f64;
+    let agreement_size = agreement * domain_size;
+    let mut security = 0.0;
+    for i in 0..num_queries {
+        let i = i as f64;
+        if agreement_size - i < 1.0 {
+            // all positions in the agreement set have been exhausted by the queries, and thus,
+            // query security does not limit the security level
+            return f64::from(u16::MAX);
+        }
+        security -= log2((agreement_size - i) / (domain_size - i));
+    }
+    security
+}
+
+/// Returns the expected size (in bytes) of serialized queries opened against a Merkle tree with
+/// `num_leaves` leaves of `leaf_bytes` bytes each, when query positions are drawn from a domain of
+/// `query_domain_size` positions as specified by `options` and are then folded into the leaves of
+/// the tree (i.e., position `p` opens leaf `p % num_leaves`).
+///
+/// The proof contains the values of every opened leaf and, for every level of the tree, the
+/// siblings of the touched nodes which are not touched themselves; nodes are grouped into one
+/// vector per touched node at the first level above the leaves.
+fn estimate_queries_size(
+    num_leaves: usize,
+    query_domain_size: usize,
+    leaf_bytes: usize,
+    digest_bytes: usize,
+    options: &ProofOptions,
+) -> f64 {
+    let positions_per_leaf = query_domain_size / num_leaves;
+    let num_touched_nodes = |level: u32| {
+        let num_covered_positions = (positions_per_leaf << level) as f64;
+        let miss_probability =
+            query_miss_probability(options, query_domain_size, num_covered_positions);
+        (num_leaves >> level) as f64 * (1.0 - miss_probability)
+    };
+
+    let mut num_nodes = 0.0;
+    for level in 0..num_leaves.ilog2() {
+        num_nodes += 2.0 * num_touched_nodes(level + 1) - num_touched_nodes(level);
+    }
+
+    // values and paths are prefixed by their lengths; paths also contain the number of node
+    // vectors and the length of each vector
+    8.0 + num_touched_nodes(0) * leaf_bytes as f64
+        + 2.0
+        + num_touched_nodes(1)
+        + num_nodes * digest_bytes as f64
+}
+
+/// Returns the probability that none of the query positions drawn from a domain of the specified
+/// size falls into a fixed set of `set_size` positions of the domain.
+fn query_miss_probability(options: &ProofOptions, domain_size: usize, set_size: f64) -> f64 {
+    let domain_size = domain_size as f64;
+    if options.distinct_queries() {
+        let mut probability = 1.0;
+        for i in 0..options.num_queries() {
+            let i = i as f64;
+            if domain_size - set_size - i <= 0.0 {
+                return 0.0;
+            }
+            probability *= (domain_size - set_size - i) / (domain_size - i);
+        }
+        probability
+    } else {
+        powf(1.0 - set_size / domain_size, options.num_queries() as f64)
+    }
+}
+
 /// Computes the largest proximity parameter m needed for Theorem 8
-/// in <https://eprint.iacr.org/2022/1216.pdf> to work.
-fn compute_upper_m(h: usize) -> f64 {
+/// in <https://eprint.iacr.org/2022/1216.pdf> to work when the DEEP quotients are batched over
+/// `2 * num_ood_points` points.
+fn compute_upper_m(h: usize, num_ood_points: usize) -> f64 {
     let h = h as f64;
-    let m_max = ceil(0.25 * h * (1.0 + sqrt(1.0 + 2.0 / h)));
+    let k = num_ood_points as f64;
+    let m_max = ceil(0.25 * h / k * (1.0 + sqrt(1.0 + 2.0 * k / h)));
 
     // We cap the range to 1000 as the optimal m value will be in the lower range of [m_min, m_max]
     // since increasing m too much will lead to a deterioration in the FRI commit soundness making
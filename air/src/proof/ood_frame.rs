@@ -16,36 +16,38 @@ type ParsedOodFrame<E> = (Vec<E>, Vec<E>);
 
 // OUT-OF-DOMAIN FRAME
 // ================================================================================================
-/// Trace and constraint polynomial evaluations at an out-of-domain point.
+/// Trace and constraint polynomial evaluations at out-of-domain points.
 ///
-/// This struct contains the following evaluations:
+/// For every out-of-domain point *z*, this struct contains the following evaluations:
 /// * Evaluations of all trace polynomials at *z*.
 /// * Evaluations of all trace polynomials at *z * g*.
 /// * Evaluations of constraint composition column polynomials at *z*.
 ///
-/// where *z* is an out-of-domain point and *g* is the generator of the trace domain.
+/// where *g* is the generator of the trace domain. The number of out-of-domain points is defined
+/// by [ProofOptions::num_ood_points()](crate::ProofOptions::num_ood_points); evaluations are
+/// stored in the order in which the points were drawn.
 ///
 /// Internally, the evaluations are stored as a sequence of bytes. Thus, to retrieve the
 /// evaluations, [parse()](OodFrame::parse) function should be used.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct OodFrame {
-    trace_states: Vec<u8>,
-    evaluations: Vec<u8>,
+    pub(crate) trace_states: Vec<u8>,
+    pub(crate) evaluations: Vec<u8>,
 }
 
 impl OodFrame {
     // UPDATERS
     // --------------------------------------------------------------------------------------------
 
-    /// Updates the trace state portion of this out-of-domain frame. This also returns a compactified
-    /// version of the out-of-domain frame with the rows interleaved. This is done so that reseeding
-    /// of the random coin needs to be done only once as opposed to once per each row.
+    /// Appends trace states at the next out-of-domain point to the trace state portion of this
+    /// out-of-domain frame. This also returns a compactified version of the states with the rows
+    /// interleaved. This is done so that reseeding of the random coin needs to be done only once
+    /// per point as opposed to once per each row.
     ///
     /// # Panics
-    /// Panics if evaluation frame has already been set.
+    /// Panics if the number of rows in `trace_states` differs from the number of rows of the
+    /// trace states which have already been set for previous points.
     pub fn set_trace_states<E: FieldElement>(&mut self, trace_states: &[Vec<E>]) -> Vec<E> {
-        assert!(self.trace_states.is_empty(), "trace sates have already been set");
-
         // save the evaluations with the current and next evaluations interleaved for each polynomial
         let frame_size = trace_states.len();
         let width = trace_states[0].len();
@@ -57,20 +59,22 @@ impl OodFrame {
             }
         }
         debug_assert!(frame_size <= u8::MAX as usize);
-        self.trace_states.write_u8(frame_size as u8);
+        if self.trace_states.is_empty() {
+            self.trace_states.write_u8(frame_size as u8);
+        } else {
+            assert_eq!(self.trace_states[0] as usize, frame_size, "inconsistent frame size");
+        }
         result.write_into(&mut self.trace_states);
 
         result
     }
 
-    /// Updates constraint evaluation portion of this out-of-domain frame.
+    /// Appends constraint evaluations at the next out-of-domain point to the constraint
+    /// evaluation portion of this out-of-domain frame.
     ///
     /// # Panics
-    /// Panics if:
-    /// * Constraint evaluations have already been set.
-    /// * `evaluations` is an empty vector.
+    /// Panics if `evaluations` is an empty vector.
     pub fn set_constraint_evaluations<E: FieldElement>(&mut self, evaluations: &[E]) {
-        assert!(self.evaluations.is_empty(), "constraint evaluations have already been set");
         assert!(!evaluations.is_empty(), "cannot set to empty constraint evaluations");
         evaluations.write_into(&mut self.evaluations)
     }
@@ -80,8 +84,13 @@ impl OodFrame {
     /// Returns main and auxiliary (if any) trace evaluation frames and a vector of out-of-domain
     /// constraint evaluations contained in `self`.
     ///
+    /// Evaluations at all `num_ood_points` out-of-domain points are returned one point after
+    /// another; that is, the returned trace evaluations consist of `num_ood_points` frames, and
+    /// the returned constraint evaluations consist of `num_ood_points` chunks of
+    /// `num_evaluations` values.
+    ///
     /// # Panics
-    /// Panics if either `main_trace_width` or `num_evaluations` are equal to zero.
+    /// Panics if `main_trace_width`, `num_evaluations`, or `num_ood_points` are equal to zero.
     ///
     /// # Errors
     /// Returns an error if:
@@ -95,22 +104,25 @@ impl OodFrame {
         main_trace_width: usize,
         aux_trace_width: usize,
         num_evaluations: usize,
+        num_ood_points: usize,
     ) -> Result<ParsedOodFrame<E>, DeserializationError> {
         assert!(main_trace_width > 0, "trace width cannot be zero");
         assert!(num_evaluations > 0, "number of evaluations cannot be zero");
+        assert!(num_ood_points > 0, "number of OOD points cannot be zero");
 
         // parse main and auxiliary trace evaluation frames
         let mut reader = SliceReader::new(&self.trace_states);
         let frame_size = reader.read_u8()? as usize;
-        let trace =
-            E::read_batch_from(&mut reader, (main_trace_width + aux_trace_width) * frame_size)?;
+        let num_trace_evaluations =
+            (main_trace_width + aux_trace_width) * frame_size * num_ood_points;
+        let trace = E::read_batch_from(&mut reader, num_trace_evaluations)?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
         // parse the constraint evaluations
         let mut reader = SliceReader::new(&self.evaluations);
-        let evaluations = E::read_batch_from(&mut reader, num_evaluations)?;
+        let evaluations = E::read_batch_from(&mut reader, num_evaluations * num_ood_points)?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
@@ -125,18 +137,17 @@ impl OodFrame {
 impl Serializable for OodFrame {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        // write trace rows
-        target.write_u16(self.trace_states.len() as u16);
+        // write trace rows and constraint evaluations; these are prefixed by u32 lengths, since
+        // for wide traces and multiple out-of-domain points, they may not fit into 65535 bytes
+        target.write_u32(self.trace_states.len() as u32);
         target.write_bytes(&self.trace_states);
-
-        // write constraint evaluations row
-        target.write_u16(self.evaluations.len() as u16);
+        target.write_u32(self.evaluations.len() as u32);
         target.write_bytes(&self.evaluations)
     }
 
     /// Returns an estimate of how many bytes are needed to represent self.
     fn get_size_hint(&self) -> usize {
-        self.trace_states.len() + self.evaluations.len() + 4
+        self.trace_states.len() + self.evaluations.len() + 8
     }
 }
 
@@ -147,11 +158,11 @@ impl Deserializable for OodFrame {
     /// Returns an error of a valid OOD frame could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read trace rows
-        let num_trace_state_bytes = source.read_u16()? as usize;
+        let num_trace_state_bytes = source.read_u32()? as usize;
         let trace_states = source.read_vec(num_trace_state_bytes)?;
 
         // read constraint evaluations row
-        let num_constraint_evaluation_bytes = source.read_u16()? as usize;
+        let num_constraint_evaluation_bytes = source.read_u32()? as usize;
         let evaluations = source.read_vec(num_constraint_evaluation_bytes)?;
 
         Ok(OodFrame {
@@ -0,0 +1,226 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Context, Queries};
+use core::fmt;
+use crypto::Hasher;
+use fri::FriProof;
+use math::{FieldElement, ToElements};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// TRACE OPENING
+// ================================================================================================
+/// An opening of a column of the main execution trace segment at an arbitrary point, generated
+/// for a trace committed to in a [StarkProof](super::StarkProof).
+///
+/// An opening attests that the polynomial interpolated from the column of the trace committed to
+/// in the proof evaluates to `value` at `point`. It is generated after the proof (e.g., when an
+/// application needs to answer a query about the witness), and is checked against the trace
+/// commitment of the proof; thus, neither the computation nor the proof need to be re-proven.
+///
+/// For column polynomial *T(x)*, the opening consists of a FRI proof that the quotient
+/// *(T(x) - value) / (x - point)* is a polynomial of degree smaller than the trace length,
+/// together with the trace rows at the positions queried by the FRI verifier (authenticated
+/// against the trace commitment). The quotient is a polynomial only if *T(point) = value*.
+///
+/// The opening point must not lie in the LDE domain of the proof.
+pub struct TraceOpening<E: FieldElement, H: Hasher> {
+    column: usize,
+    point: E,
+    value: E,
+    fri_commitments: Vec<H::Digest>,
+    pow_nonce: u64,
+    trace_queries: Queries,
+    fri_proof: FriProof,
+}
+
+impl<E: FieldElement, H: Hasher> TraceOpening<E, H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new opening of the specified column at the specified point assembled from the
+    /// provided parts.
+    ///
+    /// `fri_commitments` are the commitments to the FRI layers (including the remainder) of the
+    /// quotient polynomial, `pow_nonce` is the proof-of-work nonce for the query seed, and
+    /// `trace_queries` are decommitments of the main trace segment at the queried positions.
+    pub fn new(
+        column: usize,
+        point: E,
+        value: E,
+        fri_commitments: Vec<H::Digest>,
+        pow_nonce: u64,
+        trace_queries: Queries,
+        fri_proof: FriProof,
+    ) -> Self {
+        Self {
+            column,
+            point,
+            value,
+            fri_commitments,
+            pow_nonce,
+            trace_queries,
+            fri_proof,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the opened column of the main trace segment.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the point at which the column polynomial is opened.
+    pub fn point(&self) -> E {
+        self.point
+    }
+
+    /// Returns the value of the column polynomial at the opening point.
+    pub fn value(&self) -> E {
+        self.value
+    }
+
+    /// Returns the commitments to the FRI layers of the quotient polynomial.
+    pub fn fri_commitments(&self) -> &[H::Digest] {
+        &self.fri_commitments
+    }
+
+    /// Returns the proof-of-work nonce for the query seed of this opening.
+    pub fn pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
+    /// Returns decommitments of the main trace segment at the queried positions.
+    pub fn trace_queries(&self) -> &Queries {
+        &self.trace_queries
+    }
+
+    /// Returns the FRI proof for the quotient polynomial.
+    pub fn fri_proof(&self) -> &FriProof {
+        &self.fri_proof
+    }
+
+    // PUBLIC COIN
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the seed of the public coin from which challenges of an opening of `column` at
+    /// `point` are drawn, for a proof with the specified `context`.
+    ///
+    /// The seed consists of the proof context, the column index, the opening point, and the
+    /// claimed value; the prover and the verifier then reseed the coin with the commitment to the
+    /// main trace segment. Thus, the challenges are bound to the opened claim and to the proof.
+    pub fn coin_seed(context: &Context, column: usize, point: E, value: E) -> Vec<E::BaseField> {
+        let mut seed = context.to_elements();
+        seed.push(E::BaseField::from(column as u32));
+        seed.extend_from_slice(E::slice_as_base_elements(&[point, value]));
+        seed
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this opening into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        Serializable::to_bytes(self)
+    }
+
+    /// Returns an opening read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid opening could not be read from the specified `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        Deserializable::read_from_bytes(source)
+    }
+}
+
+// TRAIT IMPLEMENTATIONS
+// ================================================================================================
+
+// these traits are implemented manually because hash functions do not necessarily implement
+// them, and thus, the derived implementations would not be available for most hash functions
+
+impl<E: FieldElement, H: Hasher> Clone for TraceOpening<E, H> {
+    fn clone(&self) -> Self {
+        Self {
+            column: self.column,
+            point: self.point,
+            value: self.value,
+            fri_commitments: self.fri_commitments.clone(),
+            pow_nonce: self.pow_nonce,
+            trace_queries: self.trace_queries.clone(),
+            fri_proof: self.fri_proof.clone(),
+        }
+    }
+}
+
+impl<E: FieldElement, H: Hasher> PartialEq for TraceOpening<E, H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.column == other.column
+            && self.point == other.point
+            && self.value == other.value
+            && self.fri_commitments == other.fri_commitments
+            && self.pow_nonce == other.pow_nonce
+            && self.trace_queries == other.trace_queries
+            && self.fri_proof == other.fri_proof
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Eq for TraceOpening<E, H> {}
+
+impl<E: FieldElement, H: Hasher> fmt::Debug for TraceOpening<E, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceOpening")
+            .field("column", &self.column)
+            .field("point", &self.point)
+            .field("value", &self.value)
+            .field("fri_commitments", &self.fri_commitments)
+            .field("pow_nonce", &self.pow_nonce)
+            .field("trace_queries", &self.trace_queries)
+            .field("fri_proof", &self.fri_proof)
+            .finish()
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement, H: Hasher> Serializable for TraceOpening<E, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.column);
+        self.point.write_into(target);
+        self.value.write_into(target);
+        target.write_u8(self.fri_commitments.len() as u8);
+        self.fri_commitments.write_into(target);
+        target.write_u64(self.pow_nonce);
+        self.trace_queries.write_into(target);
+        self.fri_proof.write_into(target);
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for TraceOpening<E, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let column = source.read_usize()?;
+        let point = E::read_from(source)?;
+        let value = E::read_from(source)?;
+        let num_fri_commitments = source.read_u8()? as usize;
+        let fri_commitments = H::Digest::read_batch_from(source, num_fri_commitments)?;
+        let pow_nonce = source.read_u64()?;
+        let trace_queries = Queries::read_from(source)?;
+        let fri_proof = FriProof::read_from(source)?;
+
+        Ok(Self {
+            column,
+            point,
+            value,
+            fri_commitments,
+            pow_nonce,
+            trace_queries,
+            fri_proof,
+        })
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+// SECURITY REPORT
+// ================================================================================================
+/// A breakdown of the security level (in bits) of proofs generated with a given set of proof
+/// options.
+///
+/// A report is built via [StarkProof::security_report()](super::StarkProof::security_report) or
+/// [ProofOptions::security_report()](crate::ProofOptions::security_report), and contains all
+/// terms from which the conjectured and the proven security levels are derived. The security
+/// levels contained in the report are the same as the ones returned by the corresponding
+/// `security_level()` functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecurityReport {
+    /// Size (in bits) of the field from which random values are drawn; this is the size of the
+    /// extension field if a field extension is used.
+    pub field_bits: u32,
+    /// Collision resistance (in bits) of the hash function used to build commitments.
+    pub collision_resistance: u32,
+    /// Breakdown of the conjectured security level.
+    pub conjectured: ConjecturedSecurity,
+    /// Breakdown of the proven security level.
+    pub proven: ProvenSecurity,
+}
+
+impl fmt::Display for SecurityReport {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let conjectured = &self.conjectured;
+        let proven = &self.proven;
+        writeln!(f, "field size: {} bits, hash collision resistance: {} bits", self.field_bits, self.collision_resistance)?;
+        writeln!(f, "conjectured security: {} bits (limited by {})", conjectured.security_level, conjectured.bottleneck)?;
+        writeln!(f, "- field: {} bits, queries: {} bits, grinding: {} bits", conjectured.field_security, conjectured.query_security, conjectured.grinding_security)?;
+        writeln!(f, "proven security: {} bits (limited by {}, m = {})", proven.security_level, proven.bottleneck, proven.proximity_parameter)?;
+        write!(f, "- FRI commit: {} bits, FRI queries: {} bits, grinding: {} bits, ALI: {} bits, DEEP: {} bits", proven.fri_commit_security, proven.fri_query_security, proven.grinding_security, proven.ali_security, proven.deep_security)
+    }
+}
+
+// CONJECTURED SECURITY
+// ================================================================================================
+/// A breakdown of the conjectured security level (in bits).
+///
+/// The conjectured security level is the smaller of the field security and the combined query
+/// and grinding security, reduced by one bit, and capped at the collision resistance of the hash
+/// function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConjecturedSecurity {
+    /// Conjectured security level.
+    pub security_level: u32,
+    /// Security limited by the size of the field, i.e., the field size in bits less log2 of the
+    /// size of the LDE domain. When DEEP composition coefficients are powers of a single
+    /// challenge, log2 of the number of polynomials batched into the DEEP composition polynomial
+    /// is subtracted as well.
+    pub field_security: u32,
+    /// Security provided by the queries made by the verifier, without grinding.
+    pub query_security: u32,
+    /// Security added by query seed grinding. Grinding is counted only when the query security
+    /// alone is adequate, and thus, this may be smaller than the grinding factor.
+    pub grinding_security: u32,
+    /// The term which limits the security level.
+    pub bottleneck: SecurityBottleneck,
+}
+
+// PROVEN SECURITY
+// ================================================================================================
+/// A breakdown of the proven security level (in bits).
+///
+/// The proven security level is computed in the list-decoding regime as described in
+/// <https://eprint.iacr.org/2022/1216.pdf>; all terms are given for the proximity parameter
+/// which maximizes the security level. The FRI soundness error is the smaller of the FRI commit
+/// and the combined FRI query and grinding security, reduced by one bit; the security level is
+/// the smallest of the FRI, ALI, and DEEP terms, reduced by one bit, and capped at the collision
+/// resistance of the hash function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvenSecurity {
+    /// Proven security level.
+    pub security_level: u32,
+    /// Proximity parameter m for which the security level is computed.
+    pub proximity_parameter: u32,
+    /// Security of the FRI commit phase, including per-layer FRI grinding.
+    pub fri_commit_security: u32,
+    /// Security provided by the FRI queries made by the verifier, without grinding.
+    pub fri_query_security: u32,
+    /// Security added by query seed grinding.
+    pub grinding_security: u32,
+    /// Security of the algebraic linking (ALI) step which combines constraints into a single
+    /// composition polynomial.
+    pub ali_security: u32,
+    /// Security of the DEEP step which checks the composition polynomial at the out-of-domain
+    /// points; this grows with the number of out-of-domain points, and is reduced by log2 of the
+    /// number of batched polynomials when DEEP composition coefficients are powers of a single
+    /// challenge.
+    pub deep_security: u32,
+    /// The term which limits the security level.
+    pub bottleneck: SecurityBottleneck,
+}
+
+// SECURITY BOTTLENECK
+// ================================================================================================
+/// Defines the term of a security estimate which limits a security level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityBottleneck {
+    /// Security is limited by the size of the field; it can be increased by using a field
+    /// extension (for the proven security level, this includes the FRI commit phase, ALI, and
+    /// DEEP terms).
+    Field,
+    /// Security is limited by the number of queries; it can be increased by using more queries,
+    /// a larger blowup factor, or a larger grinding factor.
+    Queries,
+    /// Security is limited by the collision resistance of the hash function.
+    HashFunction,
+}
+
+impl fmt::Display for SecurityBottleneck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field => write!(f, "field size"),
+            Self::Queries => write!(f, "queries"),
+            Self::HashFunction => write!(f, "hash function collision resistance"),
+        }
+    }
+}
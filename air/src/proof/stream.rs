@@ -0,0 +1,256 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Section-based encoding of STARK proofs which can be written while a proof is being generated.
+//!
+//! The regular serialization of a [StarkProof] can be written only once the proof is complete
+//! (e.g., the number of unique queries precedes all commitments). In the streamed encoding, a
+//! proof is a sequence of sections, each of which is written as soon as the prover has finalized
+//! it; thus, a proof can be uploaded while later phases of proof generation are still running.
+//! Every section is encoded as a `u8` tag (see [ProofSection]), followed by a `u32` length of the
+//! payload and the payload itself. The sections are:
+//!
+//! ```text
+//! context                 (serialized proof context)
+//! trace_commitment        (digest; one section for each trace segment)
+//! constraint_commitment   (digest)
+//! ood_frame               (serialized out-of-domain frame)
+//! fri_commitment          (digest; one section for each FRI layer and for the remainder)
+//! queries:
+//!   num_unique_queries, trace_queries (for each trace segment), constraint_queries,
+//!   fri_proof, pow_nonce, metadata
+//! ```
+//!
+//! A streamed proof is converted into a regular proof via [StarkProof::from_stream_bytes()]. The
+//! stream is versioned by the format version included in the proof context.
+
+use super::{Commitments, Context, OodFrame, ProofMetadata, Queries, StarkProof};
+use core::fmt;
+use crypto::Hasher;
+use fri::FriProof;
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// PROOF SECTION
+// ================================================================================================
+
+/// A section of a streamed proof.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ProofSection {
+    /// Proof context; this is always the first section of a stream.
+    Context = 1,
+    /// Commitment to a single trace segment.
+    TraceCommitment = 2,
+    /// Commitment to the evaluations of the constraint composition polynomial.
+    ConstraintCommitment = 3,
+    /// Trace and constraint polynomial evaluations at all out-of-domain points.
+    OodFrame = 4,
+    /// Commitment to a single FRI layer (or to the FRI remainder).
+    FriCommitment = 5,
+    /// Query decommitments, FRI proof, proof-of-work nonce, and metadata; this is always the
+    /// last section of a stream.
+    Queries = 6,
+}
+
+impl ProofSection {
+    /// Writes a section with the specified payload into `target`.
+    ///
+    /// # Panics
+    /// Panics if the payload is longer than `u32::MAX` bytes.
+    pub fn write_section<W: ByteWriter>(self, target: &mut W, payload: &[u8]) {
+        assert!(payload.len() <= u32::MAX as usize, "section payload is too long");
+        target.write_u8(self as u8);
+        target.write_u32(payload.len() as u32);
+        target.write_bytes(payload);
+    }
+
+    /// Reads the tag and the payload of a section from `source`.
+    fn read_section<R: ByteReader>(
+        source: &mut R,
+    ) -> Result<(ProofSection, Vec<u8>), DeserializationError> {
+        let section = match source.read_u8()? {
+            1 => Self::Context,
+            2 => Self::TraceCommitment,
+            3 => Self::ConstraintCommitment,
+            4 => Self::OodFrame,
+            5 => Self::FriCommitment,
+            6 => Self::Queries,
+            tag => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "{tag} is not a valid proof section tag"
+                )))
+            }
+        };
+        let len = source.read_u32()? as usize;
+        Ok((section, source.read_vec(len)?))
+    }
+
+    /// Reads a section from `source` and makes sure it is a section of the expected kind.
+    fn read_expected<R: ByteReader>(
+        source: &mut R,
+        expected: ProofSection,
+    ) -> Result<Vec<u8>, DeserializationError> {
+        let (section, payload) = Self::read_section(source)?;
+        if section != expected {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {expected} section, but found {section} section"
+            )));
+        }
+        Ok(payload)
+    }
+}
+
+impl fmt::Display for ProofSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Context => write!(f, "context"),
+            Self::TraceCommitment => write!(f, "trace commitment"),
+            Self::ConstraintCommitment => write!(f, "constraint commitment"),
+            Self::OodFrame => write!(f, "out-of-domain frame"),
+            Self::FriCommitment => write!(f, "FRI commitment"),
+            Self::Queries => write!(f, "queries"),
+        }
+    }
+}
+
+// STREAMED STARK PROOF
+// ================================================================================================
+
+impl StarkProof {
+    /// Returns the sections of this proof in the order in which they appear in a streamed proof.
+    ///
+    /// `H` must be the hash function with which the proof was generated.
+    ///
+    /// # Errors
+    /// Returns an error if commitments of this proof could not be parsed into digests of `H`.
+    pub fn to_stream_sections<H: Hasher>(
+        &self,
+    ) -> Result<Vec<(ProofSection, Vec<u8>)>, DeserializationError> {
+        let num_trace_segments = self.context.trace_layout().num_segments();
+        let num_fri_layers =
+            self.options().to_fri_options().num_fri_layers(self.context.lde_domain_size());
+        let (trace_roots, constraint_root, fri_roots) =
+            self.commitments.clone().parse::<H>(num_trace_segments, num_fri_layers)?;
+
+        let mut result = vec![(ProofSection::Context, self.context.to_bytes())];
+        for root in trace_roots {
+            result.push((ProofSection::TraceCommitment, root.to_bytes()));
+        }
+        result.push((ProofSection::ConstraintCommitment, constraint_root.to_bytes()));
+        result.push((ProofSection::OodFrame, self.ood_frame.to_bytes()));
+        for root in fri_roots {
+            result.push((ProofSection::FriCommitment, root.to_bytes()));
+        }
+
+        let mut queries = Vec::new();
+        queries.write_u16(self.num_unique_queries);
+        for segment_queries in self.trace_queries.iter() {
+            segment_queries.write_into(&mut queries);
+        }
+        self.constraint_queries.write_into(&mut queries);
+        self.fri_proof.write_into(&mut queries);
+        queries.write_u64(self.pow_nonce);
+        self.metadata.write_into(&mut queries);
+        result.push((ProofSection::Queries, queries));
+
+        Ok(result)
+    }
+
+    /// Returns this proof encoded as a streamed proof.
+    ///
+    /// The result is identical to the stream written by a prover which emits sections as they
+    /// are finalized (see [stream](crate::proof::stream) module). `H` must be the hash function
+    /// with which the proof was generated.
+    ///
+    /// # Errors
+    /// Returns an error if commitments of this proof could not be parsed into digests of `H`.
+    pub fn to_stream_bytes<H: Hasher>(&self) -> Result<Vec<u8>, DeserializationError> {
+        let mut result = Vec::new();
+        for (section, payload) in self.to_stream_sections::<H>()? {
+            section.write_section(&mut result, &payload);
+        }
+        Ok(result)
+    }
+
+    /// Returns a proof assembled from the specified streamed proof.
+    ///
+    /// # Errors
+    /// Returns an error if `source` is not a valid streamed proof; in particular, if the sections
+    /// are not in the expected order, if the number of trace commitments does not match the
+    /// trace layout of the proof context, or if any bytes remain after the last section.
+    pub fn from_stream_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+
+        let payload = ProofSection::read_expected(&mut source, ProofSection::Context)?;
+        let context = read_payload(&payload, |reader| Context::read_from(reader))?;
+        let num_trace_segments = context.trace_layout().num_segments();
+
+        let mut commitments = Vec::new();
+        for _ in 0..num_trace_segments {
+            let payload = ProofSection::read_expected(&mut source, ProofSection::TraceCommitment)?;
+            commitments.extend_from_slice(&payload);
+        }
+        let payload = ProofSection::read_expected(&mut source, ProofSection::ConstraintCommitment)?;
+        commitments.extend_from_slice(&payload);
+
+        let payload = ProofSection::read_expected(&mut source, ProofSection::OodFrame)?;
+        let ood_frame = read_payload(&payload, |reader| OodFrame::read_from(reader))?;
+
+        // FRI commitments are followed by the queries section
+        let payload = loop {
+            match ProofSection::read_section(&mut source)? {
+                (ProofSection::FriCommitment, payload) => commitments.extend_from_slice(&payload),
+                (ProofSection::Queries, payload) => break payload,
+                (section, _) => {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "expected FRI commitment or queries section, but found {section} section"
+                    )))
+                }
+            }
+        };
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        read_payload(&payload, |reader| {
+            let num_unique_queries = reader.read_u16()?;
+            let mut trace_queries = Vec::with_capacity(num_trace_segments);
+            for _ in 0..num_trace_segments {
+                trace_queries.push(Queries::read_from(reader)?);
+            }
+            Ok(StarkProof {
+                context,
+                num_unique_queries,
+                commitments: Commitments(commitments),
+                trace_queries,
+                constraint_queries: Queries::read_from(reader)?,
+                ood_frame,
+                fri_proof: FriProof::read_from(reader)?,
+                pow_nonce: reader.read_u64()?,
+                metadata: ProofMetadata::read_from(reader)?,
+            })
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads a value from the payload of a section via `read`, and makes sure the entire payload was
+/// consumed.
+fn read_payload<T, F>(payload: &[u8], read: F) -> Result<T, DeserializationError>
+where
+    F: FnOnce(&mut SliceReader) -> Result<T, DeserializationError>,
+{
+    let mut reader = SliceReader::new(payload);
+    let result = read(&mut reader)?;
+    if reader.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(result)
+}
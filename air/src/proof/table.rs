@@ -10,8 +10,8 @@ use math::FieldElement;
 // CONSTANTS
 // ================================================================================================
 
-const MAX_ROWS: usize = 255;
-const MAX_COLS: usize = 255;
+const MAX_ROWS: usize = u16::MAX as usize;
+const MAX_COLS: usize = u16::MAX as usize;
 
 // TABLE
 // ================================================================================================
@@ -34,8 +34,8 @@ impl<E: FieldElement> Table<E> {
     ///
     /// # Panics
     /// Panics if:
-    /// * Specified number of rows is 0 or greater than 255.
-    /// * Specified number of columns is 0 or greater than 255.
+    /// * Specified number of rows is 0 or greater than 65535.
+    /// * Specified number of columns is 0 or greater than 65535.
     /// * Provided bytes do not encode valid field elements required to fill the table.
     pub fn from_bytes(
         bytes: &[u8],
@@ -44,12 +44,12 @@ impl<E: FieldElement> Table<E> {
     ) -> Result<Self, DeserializationError> {
         assert!(num_rows > 0, "number of rows must be greater than 0");
         assert!(
-            num_rows < MAX_ROWS,
+            num_rows <= MAX_ROWS,
             "number of rows cannot exceed {MAX_ROWS}, but was {num_rows}"
         );
         assert!(num_cols > 0, "number of columns must be greater than 0");
         assert!(
-            num_cols < MAX_ROWS,
+            num_cols <= MAX_COLS,
             "number of columns cannot exceed {MAX_COLS}, but was {num_cols}"
         );
 
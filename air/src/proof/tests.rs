@@ -3,9 +3,82 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::StarkProof;
+use super::{OodFrame, ProofMetadata, StarkProof};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::{collections::Vec, Deserializable, Serializable};
+
+#[cfg(feature = "testing")]
+use crate::ProofOptions;
+#[cfg(feature = "testing")]
+use proptest::prelude::*;
 
 #[test]
 pub fn starkproof_new_dummy_doesnt_panic() {
     let _ = StarkProof::new_dummy();
 }
+
+#[test]
+pub fn proof_metadata_serialization() {
+    let mut metadata = ProofMetadata::new();
+    metadata.insert(ProofMetadata::PROVER_VERSION, "0.7.1");
+    metadata.insert("label", "nightly");
+    assert_eq!(Some("nightly"), metadata.insert("label", "release").as_deref());
+
+    let parsed = ProofMetadata::read_from_bytes(&metadata.to_bytes()).unwrap();
+    assert_eq!(metadata, parsed);
+    assert_eq!(Some("0.7.1"), parsed.get(ProofMetadata::PROVER_VERSION));
+    assert_eq!(
+        vec![("label", "release"), ("prover_version", "0.7.1")],
+        parsed.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect::<Vec<_>>()
+    );
+
+    // metadata with unsorted keys is rejected
+    let mut bytes = vec![2];
+    for key in ["b", "a"] {
+        bytes.extend_from_slice(&[1, key.as_bytes()[0], 0, 0]);
+    }
+    assert!(ProofMetadata::read_from_bytes(&bytes).is_err());
+}
+
+#[test]
+pub fn ood_frame_serialization() {
+    // constraint evaluations at several out-of-domain points may take more than 65535 bytes
+    let num_evaluations = 3000;
+    let num_ood_points = 3;
+    let mut frame = OodFrame::default();
+    let mut evaluations = Vec::new();
+    for i in 0..num_ood_points {
+        frame.set_trace_states(&[vec![BaseElement::new(i as u128)], vec![BaseElement::ONE]]);
+        let point_evaluations = (0..num_evaluations)
+            .map(|j| BaseElement::new((i * num_evaluations + j) as u128))
+            .collect::<Vec<_>>();
+        frame.set_constraint_evaluations(&point_evaluations);
+        evaluations.extend(point_evaluations);
+    }
+
+    let bytes = frame.to_bytes();
+    assert_eq!(frame.get_size_hint(), bytes.len());
+    let parsed = OodFrame::read_from_bytes(&bytes).unwrap();
+    assert_eq!(frame, parsed);
+    let (_, parsed_evaluations) =
+        parsed.parse::<BaseElement>(1, 0, num_evaluations, num_ood_points).unwrap();
+    assert_eq!(evaluations, parsed_evaluations);
+}
+
+#[cfg(feature = "testing")]
+proptest! {
+    #[test]
+    fn proof_options_serialization(options in any::<ProofOptions>()) {
+        prop_assert_eq!(&options, &ProofOptions::read_from_bytes(&options.to_bytes()).unwrap());
+    }
+}
+
+#[cfg(feature = "testing")]
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn starkproof_serialization(proof in any::<StarkProof>()) {
+        prop_assert_eq!(&proof, &StarkProof::from_bytes(&proof.to_bytes()).unwrap());
+    }
+}
@@ -0,0 +1,408 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Utilities for property-testing code which consumes STARK proofs.
+//!
+//! This module provides [Arbitrary] implementations for [ProofOptions], [TraceInfo], and
+//! [StarkProof], as well as the [build_random_proof()] function and [proof_strategy()] which
+//! generate STARK proofs for a specific execution trace shape. Generated proofs are structurally
+//! valid (i.e., they can be serialized, deserialized, and parsed by a verifier), but they contain
+//! random values, and thus, are not expected to pass verification.
+//!
+//! The parameter ranges used by the [Arbitrary] implementations are deliberately small so that
+//! proofs can be generated and processed quickly.
+//!
+//! In addition, [mutate_proof()] invalidates a specific component of a proof (see
+//! [ProofMutation]), which is useful for checking that code consuming proofs rejects tampered
+//! proofs.
+
+use crate::{
+    proof::{Commitments, Context, OodFrame, ProofMetadata, Queries, StarkProof},
+    FieldExtension, ProofOptions, TraceInfo, TraceLayout,
+};
+use core::cmp;
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, ElementHasher, MerkleTree, RandomCoin};
+use math::{
+    fields::{f64, CubeExtension, QuadExtension},
+    ExtensibleField, FieldElement, StarkField,
+};
+use proptest::prelude::*;
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Largest number of composition columns in proofs generated by [StarkProof::arbitrary()].
+const MAX_CONSTRAINT_COLUMNS: usize = 4;
+
+// PROOF OPTIONS
+// ================================================================================================
+
+impl Arbitrary for FieldExtension {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(FieldExtension::None),
+            Just(FieldExtension::Quadratic),
+            Just(FieldExtension::Cubic),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ProofOptions {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates options with up to 64 queries, any allowed blowup and FRI folding factors, FRI
+    /// remainder degree bounds up to 255, grinding factors up to 8 bits, any allowed number of
+    /// out-of-domain points, and either DEEP coefficient mode.
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (
+            (1..=64usize, 1..=7u32, 0..=8u32, any::<FieldExtension>()),
+            (1..=5u32, 0..=8u32, 0..=8u32, any::<bool>()),
+            (1..=ProofOptions::MAX_OOD_POINTS, any::<bool>()),
+        )
+            .prop_map(|((num_queries, blowup, grinding, extension), fri, deep)| {
+                let (folding, remainder, layer_grinding, distinct_queries) = fri;
+                let (num_ood_points, deep_coefficient_powers) = deep;
+                ProofOptions::new(
+                    num_queries,
+                    1 << blowup,
+                    grinding,
+                    extension,
+                    1 << folding,
+                    (1 << remainder) - 1,
+                )
+                .with_fri_layer_grinding_factor(layer_grinding)
+                .with_distinct_queries(distinct_queries)
+                .with_num_ood_points(num_ood_points)
+                .with_deep_coefficient_powers(deep_coefficient_powers)
+            })
+            .boxed()
+    }
+}
+
+// TRACE INFO
+// ================================================================================================
+
+impl Arbitrary for TraceInfo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates traces with up to 16 main and 8 auxiliary columns, between 8 and 256 rows, and up
+    /// to 16 bytes of metadata.
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        let aux_segment = prop_oneof![Just((0, 0)), (1..=8usize, 1..=4usize)];
+        (1..=16usize, aux_segment, 3..=8u32, prop::collection::vec(any::<u8>(), 0..=16))
+            .prop_map(|(main_width, (aux_width, aux_rands), length, meta)| {
+                let layout = TraceLayout::new(main_width, [aux_width], [aux_rands]);
+                TraceInfo::new_multi_segment(layout, 1 << length, meta)
+            })
+            .boxed()
+    }
+}
+
+// STARK PROOF
+// ================================================================================================
+
+impl Arbitrary for StarkProof {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates proofs over the 64-bit field using BLAKE3 hash function for arbitrary trace
+    /// shapes, proof options, and up to 4 composition columns.
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (any::<TraceInfo>(), 1..=MAX_CONSTRAINT_COLUMNS)
+            .prop_flat_map(|(trace_info, num_constraint_columns)| {
+                proof_strategy::<f64::BaseElement, Blake3_256<f64::BaseElement>>(
+                    trace_info,
+                    num_constraint_columns,
+                )
+            })
+            .boxed()
+    }
+}
+
+/// Returns a strategy which generates proofs with random values for an execution trace described
+/// by `trace_info` and a constraint composition polynomial with `num_constraint_columns` columns.
+///
+/// Proof options are drawn via [ProofOptions::arbitrary()]; options which require a cubic
+/// extension of a field which does not support it are skipped. See [build_random_proof()] for
+/// the properties of generated proofs.
+///
+/// # Panics
+/// Panics if `num_constraint_columns` is zero.
+pub fn proof_strategy<B, H>(
+    trace_info: TraceInfo,
+    num_constraint_columns: usize,
+) -> impl Strategy<Value = StarkProof>
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    H: ElementHasher<BaseField = B> + 'static,
+{
+    assert!(
+        num_constraint_columns > 0,
+        "number of constraint columns must be greater than zero"
+    );
+    (any::<ProofOptions>(), any::<u64>())
+        .prop_filter("unsupported field extension", |(options, _)| {
+            options.field_extension() != FieldExtension::Cubic || <CubeExtension<B>>::is_supported()
+        })
+        .prop_map(move |(options, seed)| {
+            build_random_proof::<B, H>(&trace_info, options, num_constraint_columns, seed)
+        })
+}
+
+/// Returns a proof with random values for an execution trace described by `trace_info` and a
+/// constraint composition polynomial with `num_constraint_columns` columns.
+///
+/// The returned proof is structurally valid: its context is built from `trace_info` and
+/// `options`; it contains all commitments, queries, out-of-domain evaluations, and FRI layers
+/// expected by a verifier for these parameters; and all queried values hash into Merkle
+/// authentication paths which resolve to the committed roots. Leaves of the committed trees which
+/// are not queried are set to default digests.
+///
+/// All randomness is derived from `seed`, and thus, the same proof is returned for the same set
+/// of parameters.
+///
+/// # Panics
+/// Panics if:
+/// * `num_constraint_columns` is zero.
+/// * `options` require a field extension which is not supported by the base field.
+pub fn build_random_proof<B, H>(
+    trace_info: &TraceInfo,
+    options: ProofOptions,
+    num_constraint_columns: usize,
+    seed: u64,
+) -> StarkProof
+where
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    H: ElementHasher<BaseField = B>,
+{
+    match options.field_extension() {
+        FieldExtension::None => {
+            build_proof::<B, B, H>(trace_info, options, num_constraint_columns, seed)
+        }
+        FieldExtension::Quadratic => {
+            assert!(<QuadExtension<B>>::is_supported(), "quadratic extension is not supported");
+            build_proof::<B, QuadExtension<B>, H>(trace_info, options, num_constraint_columns, seed)
+        }
+        FieldExtension::Cubic => {
+            assert!(<CubeExtension<B>>::is_supported(), "cubic extension is not supported");
+            build_proof::<B, CubeExtension<B>, H>(trace_info, options, num_constraint_columns, seed)
+        }
+    }
+}
+
+// PROOF MUTATIONS
+// ================================================================================================
+
+/// A modification which invalidates a specific component of a STARK proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofMutation {
+    /// Flips a bit in the commitment to the main trace segment.
+    TraceCommitment,
+    /// Flips a bit in the first queried value of the main trace segment.
+    TraceQueryValue,
+    /// Flips a bit in the last node of Merkle authentication paths for the main trace segment.
+    TraceQueryPath,
+    /// Flips a bit in the first queried value of the constraint composition polynomial.
+    ConstraintQueryValue,
+    /// Flips a bit in the first out-of-domain evaluation of the execution trace.
+    OodTraceState,
+    /// Flips a bit in the first out-of-domain evaluation of the constraint composition
+    /// polynomial.
+    OodConstraintEvaluation,
+    /// Changes the proof-of-work nonce, and thus, the positions queried by the verifier.
+    PowNonce,
+}
+
+impl ProofMutation {
+    /// All available mutations.
+    pub const ALL: [ProofMutation; 7] = [
+        ProofMutation::TraceCommitment,
+        ProofMutation::TraceQueryValue,
+        ProofMutation::TraceQueryPath,
+        ProofMutation::ConstraintQueryValue,
+        ProofMutation::OodTraceState,
+        ProofMutation::OodConstraintEvaluation,
+        ProofMutation::PowNonce,
+    ];
+}
+
+/// Returns a copy of the specified proof with the specified mutation applied.
+///
+/// Bits are flipped in the least significant bytes of field elements, and thus, mutated values
+/// remain valid field elements (unless they were within one of the field modulus). Mutated
+/// proofs can still be deserialized and parsed, but are expected to be rejected by a verifier.
+///
+/// # Panics
+/// Panics if the component to be mutated is empty (e.g., the proof is a dummy proof).
+pub fn mutate_proof(proof: &StarkProof, mutation: ProofMutation) -> StarkProof {
+    let mut result = proof.clone();
+    match mutation {
+        ProofMutation::TraceCommitment => flip_bit(&mut result.commitments.0, 0),
+        ProofMutation::TraceQueryValue => flip_bit(&mut result.trace_queries[0].values, 0),
+        ProofMutation::TraceQueryPath => {
+            let paths = &mut result.trace_queries[0].paths;
+            let last = paths.len().saturating_sub(1);
+            flip_bit(paths, last);
+        }
+        ProofMutation::ConstraintQueryValue => flip_bit(&mut result.constraint_queries.values, 0),
+        // the first byte of trace states encodes the size of the frame
+        ProofMutation::OodTraceState => flip_bit(&mut result.ood_frame.trace_states, 1),
+        ProofMutation::OodConstraintEvaluation => flip_bit(&mut result.ood_frame.evaluations, 0),
+        ProofMutation::PowNonce => result.pow_nonce ^= 1,
+    }
+    result
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Flips the least significant bit of the byte at the specified index.
+fn flip_bit(bytes: &mut [u8], index: usize) {
+    *bytes.get_mut(index).expect("mutated component of the proof is empty") ^= 1;
+}
+
+/// Builds a random proof in which all values beyond the main trace segment are in field `E`.
+fn build_proof<B, E, H>(
+    trace_info: &TraceInfo,
+    options: ProofOptions,
+    num_constraint_columns: usize,
+    seed: u64,
+) -> StarkProof
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    assert!(
+        num_constraint_columns > 0,
+        "number of constraint columns must be greater than zero"
+    );
+    let mut coin = DefaultRandomCoin::<H>::new(&[B::from(seed)]);
+
+    let context = Context::new::<B>(trace_info, options);
+    let lde_domain_size = context.lde_domain_size();
+    let options = context.options();
+
+    // draw query positions in the same way as the prover does; the number of queries cannot
+    // reach the size of the domain for small traces
+    let num_queries = cmp::min(options.num_queries(), lde_domain_size - 1);
+    let mut positions = coin
+        .draw_integers(num_queries, lde_domain_size, 0)
+        .expect("failed to draw query positions");
+    positions.sort_unstable();
+    positions.dedup();
+
+    // build trace queries; the main segment is in the base field, all other segments are in E
+    let layout = trace_info.layout();
+    let mut trace_roots = Vec::with_capacity(layout.num_segments());
+    let mut trace_queries = Vec::with_capacity(layout.num_segments());
+    let (queries, root) = build_random_queries::<B, H, _>(
+        lde_domain_size,
+        &positions,
+        layout.main_trace_width(),
+        &mut coin,
+    );
+    trace_roots.push(root);
+    trace_queries.push(queries);
+    for segment_idx in 0..layout.num_aux_segments() {
+        let (queries, root) = build_random_queries::<E, H, _>(
+            lde_domain_size,
+            &positions,
+            layout.get_aux_segment_width(segment_idx),
+            &mut coin,
+        );
+        trace_roots.push(root);
+        trace_queries.push(queries);
+    }
+
+    let (constraint_queries, constraint_root) = build_random_queries::<E, H, _>(
+        lde_domain_size,
+        &positions,
+        num_constraint_columns,
+        &mut coin,
+    );
+
+    // build the out-of-domain frame from the current and the next rows of the whole trace at
+    // every out-of-domain point
+    let mut ood_frame = OodFrame::default();
+    let trace_width = layout.main_trace_width() + layout.aux_trace_width();
+    for _ in 0..options.num_ood_points() {
+        let ood_trace_states = [
+            draw_elements::<E, _>(trace_width, &mut coin),
+            draw_elements(trace_width, &mut coin),
+        ];
+        ood_frame.set_trace_states(&ood_trace_states);
+        let ood_evaluations = draw_elements::<E, _>(num_constraint_columns, &mut coin);
+        ood_frame.set_constraint_evaluations(&ood_evaluations);
+    }
+
+    let (fri_proof, fri_roots) = fri::testing::build_random_proof::<E, H, _>(
+        &options.to_fri_options(),
+        lde_domain_size,
+        &positions,
+        &mut coin,
+    );
+
+    let pow_nonce =
+        coin.draw_integers(1, 1 << 32, 0).expect("failed to draw proof-of-work nonce")[0] as u64;
+
+    StarkProof {
+        context,
+        num_unique_queries: positions.len() as u16,
+        commitments: Commitments::new::<H>(trace_roots, constraint_root, fri_roots),
+        trace_queries,
+        constraint_queries,
+        ood_frame,
+        fri_proof,
+        pow_nonce,
+        metadata: ProofMetadata::new(),
+    }
+}
+
+/// Builds queries with rows of `width` random values at the specified positions of a domain, and
+/// returns them together with the root of the tree committing to these rows.
+fn build_random_queries<E, H, R>(
+    domain_size: usize,
+    positions: &[usize],
+    width: usize,
+    coin: &mut R,
+) -> (Queries, H::Digest)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    // only the queried leaves need to be backed by values; all other leaves are left empty
+    let mut leaves = vec![H::Digest::default(); domain_size];
+    let mut rows = Vec::with_capacity(positions.len());
+    for &position in positions {
+        let row = draw_elements::<E, _>(width, coin);
+        leaves[position] = H::hash_elements(&row);
+        rows.push(row);
+    }
+
+    let tree = MerkleTree::<H>::new(leaves).expect("failed to build a Merkle tree");
+    let proof = tree.prove_batch(positions).expect("failed to generate a batch Merkle proof");
+
+    (Queries::new::<H, E>(proof, rows), *tree.root())
+}
+
+/// Draws the specified number of random field elements from the specified coin.
+fn draw_elements<E, R>(num_elements: usize, coin: &mut R) -> Vec<E>
+where
+    E: FieldElement,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    (0..num_elements)
+        .map(|_| coin.draw().expect("failed to draw a random element"))
+        .collect()
+}
@@ -61,6 +61,26 @@ impl fmt::Display for MerkleTreeError {
     }
 }
 
+impl MerkleTreeError {
+    /// Returns a stable code identifying the kind of this error.
+    ///
+    /// Codes have the form `merkle_tree.<kind>` and are stable across releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::TooFewLeaves(_, _) => "merkle_tree.too_few_leaves",
+            Self::NumberOfLeavesNotPowerOfTwo(_) => "merkle_tree.number_of_leaves_not_power_of_two",
+            Self::LeafIndexOutOfBounds(_, _) => "merkle_tree.leaf_index_out_of_bounds",
+            Self::DuplicateLeafIndex => "merkle_tree.duplicate_leaf_index",
+            Self::TooFewLeafIndexes => "merkle_tree.too_few_leaf_indexes",
+            Self::TooManyLeafIndexes(_, _) => "merkle_tree.too_many_leaf_indexes",
+            Self::InvalidProof => "merkle_tree.invalid_proof",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MerkleTreeError {}
+
 // RANDOM COIN ERROR
 // ================================================================================================
 
@@ -89,3 +109,18 @@ impl fmt::Display for RandomCoinError {
         }
     }
 }
+
+impl RandomCoinError {
+    /// Returns a stable code identifying the kind of this error.
+    ///
+    /// Codes have the form `random_coin.<kind>` and are stable across releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::FailedToDrawFieldElement(_) => "random_coin.failed_to_draw_field_element",
+            Self::FailedToDrawIntegers(_, _, _) => "random_coin.failed_to_draw_integers",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RandomCoinError {}
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ByteDigest, ElementHasher, Hasher};
+use super::{ByteDigest, ElementHasher, HashFunction, Hasher};
 use core::{convert::TryInto, fmt::Debug, marker::PhantomData};
 use math::{FieldElement, StarkField};
 use utils::ByteWriter;
@@ -23,6 +23,7 @@ impl<B: StarkField> Hasher for Blake3_256<B> {
     type Digest = ByteDigest<32>;
 
     const COLLISION_RESISTANCE: u32 = 128;
+    const HASH_FUNCTION: Option<HashFunction> = Some(HashFunction::Blake3_256);
 
     fn hash(bytes: &[u8]) -> Self::Digest {
         ByteDigest(*blake3::hash(bytes).as_bytes())
@@ -71,6 +72,7 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
     type Digest = ByteDigest<24>;
 
     const COLLISION_RESISTANCE: u32 = 96;
+    const HASH_FUNCTION: Option<HashFunction> = Some(HashFunction::Blake3_192);
 
     fn hash(bytes: &[u8]) -> Self::Digest {
         let result = blake3::hash(bytes);
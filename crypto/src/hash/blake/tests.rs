@@ -28,3 +28,15 @@ fn hash_elements_padding() {
     let r2 = Blake3_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn hash_elements_canonical_encoding() {
+    use math::fields::f64::BaseElement as Goldilocks;
+    let elements: [Goldilocks; 8] = rand_array();
+
+    // elements are hashed in the canonical encoding shared with other Goldilocks implementations,
+    // and thus, the digest can be reproduced without access to Montgomery internals
+    let bytes = Goldilocks::elements_to_canonical_bytes(&elements);
+    let expected = blake3::hash(&bytes);
+    assert_eq!(expected.as_bytes(), &Blake3_256::hash_elements(&elements).0);
+}
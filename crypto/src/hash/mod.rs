@@ -11,7 +11,7 @@ mod blake;
 pub use blake::{Blake3_192, Blake3_256};
 
 mod sha;
-pub use sha::Sha3_256;
+pub use sha::{Keccak256, Sha3_256};
 
 mod mds;
 
@@ -21,6 +21,9 @@ pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
 mod griffin;
 pub use griffin::GriffinJive64_256;
 
+mod selector;
+pub use selector::{HashFnVisitor, HashFunction};
+
 // HASHER TRAITS
 // ================================================================================================
 
@@ -37,6 +40,10 @@ pub trait Hasher {
     /// Collision resistance of the hash function measured in bits.
     const COLLISION_RESISTANCE: u32;
 
+    /// The [HashFunction] variant corresponding to this hasher, or `None` if the hasher cannot
+    /// be selected at runtime.
+    const HASH_FUNCTION: Option<HashFunction> = None;
+
     /// Returns a hash of the provided sequence of bytes.
     fn hash(bytes: &[u8]) -> Self::Digest;
 
@@ -130,6 +137,17 @@ impl<const N: usize> Deserializable for ByteDigest<N> {
     }
 }
 
+#[cfg(feature = "testing")]
+impl<const N: usize> proptest::arbitrary::Arbitrary for ByteDigest<N> {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+        proptest::array::uniform(any::<u8>()).prop_map(Self::new).boxed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{ByteDigest, Digest};
@@ -85,6 +85,17 @@ impl From<ElementDigest> for [u8; 32] {
     }
 }
 
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for ElementDigest {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+        any::<[BaseElement; DIGEST_SIZE]>().prop_map(Self::new).boxed()
+    }
+}
+
 // TESTS
 // ================================================================================================
 
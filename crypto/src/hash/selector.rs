@@ -0,0 +1,120 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Blake3_192, Blake3_256, ElementHasher, Keccak256, Sha3_256};
+use core::fmt;
+use math::{fields::f64::BaseElement, StarkField};
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// HASH FUNCTION
+// ================================================================================================
+
+/// Identifies one of the hash functions which can be used with any base field.
+///
+/// Hash functions are usually specified statically via type parameters (e.g.,
+/// `Prover::HashFn`). This enum makes it possible to pick a hash function at runtime (e.g., per
+/// request in a proving service) and then dispatch to code instantiated for the selected hash
+/// function via [HashFunction::dispatch()]. Thus, a single entry point can serve all supported
+/// hash functions without manually matching on the hash function everywhere.
+///
+/// Algebraic hash functions (e.g., Rescue Prime) are defined only over specific fields, and thus,
+/// are not included here; these still need to be selected statically.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HashFunction {
+    /// BLAKE3 hash function with 192-bit output.
+    Blake3_192 = 1,
+    /// BLAKE3 hash function with 256-bit output.
+    Blake3_256 = 2,
+    /// SHA3 hash function with 256-bit output.
+    Sha3_256 = 3,
+    /// Keccak hash function with 256-bit output (as used by the EVM).
+    Keccak256 = 4,
+}
+
+impl HashFunction {
+    /// Returns collision resistance of this hash function measured in bits.
+    pub fn collision_resistance(&self) -> u32 {
+        // collision resistance of these hash functions does not depend on the base field
+        self.dispatch::<BaseElement, _>(CollisionResistance)
+    }
+
+    /// Invokes the specified visitor with the hasher corresponding to this hash function
+    /// instantiated over the base field `B`, and returns the result.
+    pub fn dispatch<B, V>(&self, visitor: V) -> V::Output
+    where
+        B: StarkField,
+        V: HashFnVisitor<B>,
+    {
+        match self {
+            Self::Blake3_192 => visitor.visit::<Blake3_192<B>>(),
+            Self::Blake3_256 => visitor.visit::<Blake3_256<B>>(),
+            Self::Sha3_256 => visitor.visit::<Sha3_256<B>>(),
+            Self::Keccak256 => visitor.visit::<Keccak256<B>>(),
+        }
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Blake3_192 => write!(f, "blake3_192"),
+            Self::Blake3_256 => write!(f, "blake3_256"),
+            Self::Sha3_256 => write!(f, "sha3_256"),
+            Self::Keccak256 => write!(f, "keccak_256"),
+        }
+    }
+}
+
+impl Serializable for HashFunction {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for HashFunction {
+    /// Reads a hash function identifier from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if the value does not correspond to a known hash function.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            1 => Ok(Self::Blake3_192),
+            2 => Ok(Self::Blake3_256),
+            3 => Ok(Self::Sha3_256),
+            4 => Ok(Self::Keccak256),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as HashFunction enum"
+            ))),
+        }
+    }
+}
+
+// HASH FUNCTION VISITOR
+// ================================================================================================
+
+/// Defines a computation which is generic over a hash function, and can be invoked for a hash
+/// function selected at runtime via [HashFunction::dispatch()].
+pub trait HashFnVisitor<B: StarkField> {
+    /// Result of the computation.
+    type Output;
+
+    /// Executes the computation using the hash function `H`.
+    fn visit<H: ElementHasher<BaseField = B>>(self) -> Self::Output;
+}
+
+// HELPER VISITORS
+// ================================================================================================
+
+struct CollisionResistance;
+
+impl<B: StarkField> HashFnVisitor<B> for CollisionResistance {
+    type Output = u32;
+
+    fn visit<H: ElementHasher<BaseField = B>>(self) -> u32 {
+        H::COLLISION_RESISTANCE
+    }
+}
@@ -3,12 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ByteDigest, ElementHasher, Hasher};
+use super::{ByteDigest, ElementHasher, HashFunction, Hasher};
 use core::marker::PhantomData;
 use math::{FieldElement, StarkField};
 use sha3::Digest;
 use utils::ByteWriter;
 
+#[cfg(test)]
+mod tests;
+
 // SHA3 WITH 256-BIT OUTPUT
 // ================================================================================================
 
@@ -20,6 +23,7 @@ impl<B: StarkField> Hasher for Sha3_256<B> {
     type Digest = ByteDigest<32>;
 
     const COLLISION_RESISTANCE: u32 = 128;
+    const HASH_FUNCTION: Option<HashFunction> = Some(HashFunction::Sha3_256);
 
     fn hash(bytes: &[u8]) -> Self::Digest {
         ByteDigest(sha3::Sha3_256::digest(bytes).into())
@@ -56,6 +60,71 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
     }
 }
 
+// KECCAK WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for Keccak hash function with 256-bit
+/// output.
+///
+/// This is the original Keccak-256 (as used by the EVM), which differs from SHA3-256 only in the
+/// padding rule. The hasher is defined so that a STARK verifier can be implemented on the EVM
+/// using the `keccak256` opcode:
+/// * Merging two digests hashes their 64-byte concatenation.
+/// * Merging a digest with an integer hashes the digest followed by the integer encoded as a
+///   32-byte big-endian word.
+/// * Field elements are hashed as a sequence of 32-byte big-endian words, one word per base
+///   field element in canonical form (i.e., the same way as Solidity's
+///   `abi.encodePacked(uint256[])` would encode them).
+pub struct Keccak256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+    const HASH_FUNCTION: Option<HashFunction> = Some(HashFunction::Keccak256);
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 64];
+        data[..32].copy_from_slice(&seed.0);
+        data[56..].copy_from_slice(&value.to_be_bytes());
+        ByteDigest(sha3::Keccak256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        let mut hasher = sha3::Keccak256::new();
+        for element in E::slice_as_base_elements(elements) {
+            hasher.update(element_to_word(element));
+        }
+        ByteDigest(hasher.finalize().into())
+    }
+}
+
+/// Returns a 32-byte big-endian word encoding the canonical value of the specified element.
+///
+/// # Panics
+/// Panics if the element does not fit into 32 bytes.
+fn element_to_word<B: StarkField>(element: &B) -> [u8; 32] {
+    let bytes = element.to_bytes();
+    assert!(bytes.len() <= 32, "field elements must fit into 32 bytes");
+    let mut word = [0; 32];
+    for (target, &byte) in word.iter_mut().rev().zip(bytes.iter()) {
+        *target = byte;
+    }
+    word
+}
+
 // SHA HASHER
 // ================================================================================================
 
@@ -0,0 +1,71 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, Keccak256};
+use math::{
+    fields::{f128, f64::BaseElement, QuadExtension},
+    FieldElement, StarkField,
+};
+use rand_utils::rand_array;
+use sha3::Digest;
+
+#[test]
+fn keccak_test_vectors() {
+    // these match the values returned by the EVM keccak256 opcode
+    let digest = Keccak256::<BaseElement>::hash(b"");
+    assert_eq!(
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        hex(&digest.0)
+    );
+    let digest = Keccak256::<BaseElement>::hash(b"abc");
+    assert_eq!(
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        hex(&digest.0)
+    );
+}
+
+#[test]
+fn keccak_merge_with_int() {
+    let seed = Keccak256::<BaseElement>::hash(b"seed");
+
+    // the integer is appended to the seed as a 32-byte big-endian word
+    let mut data = [0_u8; 64];
+    data[..32].copy_from_slice(&seed.0);
+    data[56..].copy_from_slice(&0x0102030405060708_u64.to_be_bytes());
+    let expected: [u8; 32] = sha3::Keccak256::digest(data).into();
+
+    assert_eq!(expected, Keccak256::<BaseElement>::merge_with_int(seed, 0x0102030405060708).0);
+}
+
+#[test]
+fn keccak_hash_elements_as_words() {
+    // each element is encoded as a big-endian word in canonical form
+    let elements = [BaseElement::new(1), BaseElement::new(0x0102030405060708), -BaseElement::ONE];
+    let mut data = [0_u8; 96];
+    data[31] = 1;
+    data[56..64].copy_from_slice(&0x0102030405060708_u64.to_be_bytes());
+    data[88..].copy_from_slice(&(BaseElement::MODULUS - 1).to_be_bytes());
+    let expected: [u8; 32] = sha3::Keccak256::digest(data).into();
+    assert_eq!(expected, Keccak256::hash_elements(&elements).0);
+
+    // extension elements are hashed as a sequence of their base field components
+    let elements: [QuadExtension<BaseElement>; 4] = rand_array();
+    let base_elements = QuadExtension::slice_as_base_elements(&elements);
+    assert_eq!(Keccak256::hash_elements(base_elements), Keccak256::hash_elements(&elements));
+
+    // elements of 128-bit fields also fit into a single word
+    let element = f128::BaseElement::new(u128::MAX >> 1);
+    let mut data = [0_u8; 32];
+    data[16..].copy_from_slice(&element.as_int().to_be_bytes());
+    let expected: [u8; 32] = sha3::Keccak256::digest(data).into();
+    assert_eq!(expected, Keccak256::hash_elements(&[element]).0);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
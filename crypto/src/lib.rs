@@ -23,13 +23,14 @@
 extern crate alloc;
 
 mod hash;
-pub use hash::{Digest, ElementHasher, Hasher};
+pub use hash::{Digest, ElementHasher, HashFnVisitor, HashFunction, Hasher};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
     pub use super::hash::Blake3_192;
     pub use super::hash::Blake3_256;
     pub use super::hash::GriffinJive64_256;
+    pub use super::hash::Keccak256;
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
     pub use super::hash::RpJive64_256;
@@ -43,7 +44,10 @@ pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
 pub use merkle::concurrent;
 
 mod random;
-pub use random::{DefaultRandomCoin, RandomCoin};
+pub use random::{DefaultRandomCoin, RandomCoin, ScriptedRandomCoin};
+
+#[cfg(feature = "std")]
+pub use random::{Transcript, TranscriptCoin, TranscriptEvent, TranscriptRecorder};
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
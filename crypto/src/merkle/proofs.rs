@@ -13,7 +13,7 @@ use utils::{
 // CONSTANTS
 // ================================================================================================
 
-pub(super) const MAX_PATHS: usize = 255;
+pub(super) const MAX_PATHS: usize = u16::MAX as usize;
 
 // BATCH MERKLE PROOF
 // ================================================================================================
@@ -25,7 +25,7 @@ pub(super) const MAX_PATHS: usize = 255;
 /// individual Merkle paths. The algorithm is for aggregation is a variation of
 /// [Octopus](https://eprint.iacr.org/2017/933).
 ///
-/// Currently, at most 255 paths can be aggregated into a single proof. This limitation is
+/// Currently, at most 65535 paths can be aggregated into a single proof. This limitation is
 /// imposed primarily for serialization purposes.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BatchMerkleProof<H: Hasher> {
@@ -43,7 +43,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Panics
     /// Panics if:
     /// * No paths have been provided (i.e., `paths` is an empty slice).
-    /// * More than 255 paths have been provided.
+    /// * More than 65535 paths have been provided.
     /// * Number of paths is not equal to the number of indexes.
     /// * Not all paths have the same length.
     pub fn from_paths(paths: &[Vec<H::Digest>], indexes: &[usize]) -> BatchMerkleProof<H> {
@@ -115,7 +115,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
+    /// * Number of provided indexes is greater than 65535.
     /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
     ///   tree for which this batch proof was generated.
     /// * List of indexes contains duplicates.
@@ -252,7 +252,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
+    /// * Number of provided indexes is greater than 65535.
     /// * Number of provided indexes does not match the number of leaf nodes in the proof.
     pub fn into_paths(self, indexes: &[usize]) -> Result<Vec<Vec<H::Digest>>, MerkleTreeError> {
         if indexes.is_empty() {
@@ -402,14 +402,14 @@ impl<H: Hasher> BatchMerkleProof<H> {
     ///
     /// # Panics
     /// Panics if:
-    /// * The proof contains more than 255 Merkle paths.
+    /// * The proof contains more than 65535 Merkle paths.
     /// * The Merkle paths consist of more than 255 nodes.
     pub fn serialize_nodes(&self) -> Vec<u8> {
         let mut result = Vec::new();
 
         // record total number of node vectors
-        assert!(self.nodes.len() <= u8::MAX as usize, "too many paths");
-        result.push(self.nodes.len() as u8);
+        assert!(self.nodes.len() <= MAX_PATHS, "too many paths");
+        result.extend_from_slice(&(self.nodes.len() as u16).to_le_bytes());
 
         // record each node vector as individual bytes
         for nodes in self.nodes.iter() {
@@ -430,7 +430,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Errors
     /// Returns an error if:
     /// * No leaves were provided (i.e., `leaves` is an empty slice).
-    /// * Number of provided leaves is greater than 255.
+    /// * Number of provided leaves is greater than 65535.
     /// * Tree `depth` was set to zero.
     /// * `node_bytes` could not be deserialized into a valid set of internal nodes.
     pub fn deserialize<R: ByteReader>(
@@ -456,7 +456,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
             )));
         }
 
-        let num_node_vectors = node_bytes.read_u8()? as usize;
+        let num_node_vectors = node_bytes.read_u16()? as usize;
         let mut nodes = Vec::with_capacity(num_node_vectors);
         for _ in 0..num_node_vectors {
             // read the number of digests in the vector
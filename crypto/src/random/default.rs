@@ -117,6 +117,29 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         self.counter = 0;
     }
 
+    /// Reseeds the coin with the specified integer by setting the new seed to
+    /// hash(`seed` || `value`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, DefaultRandomCoin, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let seed = &[BaseElement::new(1), BaseElement::new(2)];
+    /// let mut coin1 = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(seed);
+    /// let mut coin2 = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(seed);
+    ///
+    /// // after reseeding with different integers the coins should draw different elements
+    /// coin1.reseed_with_int(1);
+    /// coin2.reseed_with_int(2);
+    /// let e1 = coin1.draw::<BaseElement>().unwrap();
+    /// let e2 = coin2.draw::<BaseElement>().unwrap();
+    /// assert_ne!(e1, e2);
+    /// ```
+    fn reseed_with_int(&mut self, value: u64) {
+        self.seed = H::merge_with_int(self.seed, value);
+        self.counter = 0;
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -196,8 +219,7 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         assert!(num_values < domain_size, "number of values must be smaller than domain size");
 
         // reseed with nonce
-        self.seed = H::merge_with_int(self.seed, nonce);
-        self.counter = 0;
+        self.reseed_with_int(nonce);
 
         // determine how many bits are needed to represent valid values in the domain
         let v_mask = (domain_size - 1) as u64;
@@ -10,6 +10,14 @@ use utils::collections::Vec;
 mod default;
 pub use default::DefaultRandomCoin;
 
+mod scripted;
+pub use scripted::ScriptedRandomCoin;
+
+#[cfg(feature = "std")]
+mod transcript;
+#[cfg(feature = "std")]
+pub use transcript::{Transcript, TranscriptCoin, TranscriptEvent, TranscriptRecorder};
+
 // RANDOM COIN TRAIT
 // ================================================================================================
 
@@ -36,6 +44,13 @@ pub trait RandomCoin: Sync {
     /// Reseeds the coin with the specified data by setting the new seed to hash(`seed` || `data`).
     fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest);
 
+    /// Reseeds the coin with the specified integer by setting the new seed to
+    /// hash(`seed` || `value`).
+    ///
+    /// This is used to absorb proof-of-work nonces into the coin, so that values drawn after a
+    /// nonce has been found depend on the nonce.
+    fn reseed_with_int(&mut self, value: u64);
+
     /// Computes hash(`seed` || `value`) and returns the number of leading zeros in the resulting
     /// value if it is interpreted as an integer in big-endian byte order.
     fn check_leading_zeros(&self, value: u64) -> u32;
@@ -64,4 +79,61 @@ pub trait RandomCoin: Sync {
         domain_size: usize,
         nonce: u64,
     ) -> Result<Vec<usize>, RandomCoinError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a vector of distinct integers selected from the range [0, domain_size) after it
+    /// reseeds the coin with a nonce.
+    ///
+    /// Integers are drawn via [draw_integers()](RandomCoin::draw_integers) and duplicates are
+    /// rejected. If fewer than `num_values` distinct integers remain after that, the missing
+    /// integers are drawn again in the same way (which reseeds the coin with the nonce again)
+    /// until enough distinct integers are drawn. The integers are returned in the order in which
+    /// they were drawn.
+    ///
+    /// # Errors
+    /// Returns an error if the integers could not be drawn from the coin, or if the specified
+    /// number of distinct integers could not be drawn after 1000 rounds.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `domain_size` is not a power of two.
+    /// - `num_values` is greater than or equal to `domain_size`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::collections::HashSet;
+    /// # use winter_crypto::{RandomCoin, DefaultRandomCoin, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(&[BaseElement::new(1)]);
+    ///
+    /// // drawing 60 out of 64 values is almost certain to produce duplicates with replacement
+    /// let values = coin.draw_distinct_integers(60, 64, 0).unwrap();
+    /// assert_eq!(60, values.len());
+    /// assert_eq!(60, values.iter().collect::<HashSet<_>>().len());
+    /// ```
+    fn draw_distinct_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        let mut values: Vec<usize> = Vec::with_capacity(num_values);
+        for _ in 0..1000 {
+            if values.len() == num_values {
+                return Ok(values);
+            }
+            for value in self.draw_integers(num_values - values.len(), domain_size, nonce)? {
+                if !values.contains(&value) {
+                    values.push(value);
+                }
+            }
+        }
+
+        if values.len() < num_values {
+            return Err(RandomCoinError::FailedToDrawIntegers(num_values, values.len(), 1000));
+        }
+        Ok(values)
+    }
 }
@@ -0,0 +1,155 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::RandomCoinError, ElementHasher, RandomCoin};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// SCRIPTED RANDOM COIN
+// ================================================================================================
+
+/// A random coin which returns challenges supplied by the caller instead of deriving them from
+/// the hash of the protocol transcript.
+///
+/// This coin turns a STARK protocol into an interactive one in which the verifier's challenges
+/// are chosen externally: field elements are returned from a list of base field elements in the
+/// order in which they are drawn (elements in extension fields consume as many base field
+/// elements as the degree of the extension), and integers are returned from a list of integers
+/// in the same way. Data sent to the coin via [RandomCoin::reseed()] does not affect the
+/// challenges, and proof-of-work is not enforced (i.e., every nonce is accepted).
+///
+/// This coin is intended for testing soundness properties of a protocol, knowledge extractor
+/// experiments, and fuzzing of individual protocol rounds. Proofs generated with this coin are
+/// NOT sound as non-interactive proofs, since a prover who can choose the challenges can prove
+/// false statements.
+///
+/// An instance created via [RandomCoin::new()] contains no challenges; challenges should be
+/// supplied via [ScriptedRandomCoin::from_challenges()].
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{RandomCoin, ScriptedRandomCoin, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// let elements = vec![BaseElement::new(3), BaseElement::new(5)];
+/// let mut coin = ScriptedRandomCoin::<Blake3_256<BaseElement>>::from_challenges(elements, vec![7]);
+///
+/// assert_eq!(BaseElement::new(3), coin.draw::<BaseElement>().unwrap());
+/// assert_eq!(BaseElement::new(5), coin.draw::<BaseElement>().unwrap());
+/// assert!(coin.draw::<BaseElement>().is_err());
+///
+/// assert_eq!(vec![7], coin.draw_integers(1, 8, 0).unwrap());
+/// ```
+pub struct ScriptedRandomCoin<H: ElementHasher> {
+    elements: Vec<H::BaseField>,
+    integers: Vec<usize>,
+    next_element: usize,
+    next_integer: usize,
+}
+
+impl<H: ElementHasher> ScriptedRandomCoin<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new coin which returns the specified field elements and integers as challenges.
+    pub fn from_challenges(elements: Vec<H::BaseField>, integers: Vec<usize>) -> Self {
+        Self {
+            elements,
+            integers,
+            next_element: 0,
+            next_integer: 0,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of base field elements drawn from this coin so far.
+    pub fn num_drawn_elements(&self) -> usize {
+        self.next_element
+    }
+
+    /// Returns the number of integers drawn from this coin so far.
+    pub fn num_drawn_integers(&self) -> usize {
+        self.next_integer
+    }
+}
+
+impl<H: ElementHasher> Clone for ScriptedRandomCoin<H> {
+    fn clone(&self) -> Self {
+        Self {
+            elements: self.elements.clone(),
+            integers: self.integers.clone(),
+            next_element: self.next_element,
+            next_integer: self.next_integer,
+        }
+    }
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for ScriptedRandomCoin<H> {
+    type BaseField = B;
+    type Hasher = H;
+
+    /// Returns a new coin which contains no challenges; the `seed` is ignored.
+    fn new(_seed: &[Self::BaseField]) -> Self {
+        Self::from_challenges(Vec::new(), Vec::new())
+    }
+
+    /// Does nothing, since challenges returned by this coin do not depend on the transcript.
+    fn reseed(&mut self, _data: H::Digest) {}
+
+    /// Does nothing, since challenges returned by this coin do not depend on the transcript.
+    fn reseed_with_int(&mut self, _value: u64) {}
+
+    /// Returns the maximum number of leading zeros, and thus, accepts every proof-of-work nonce.
+    fn check_leading_zeros(&self, _value: u64) -> u32 {
+        u64::BITS
+    }
+
+    /// Returns the next field element built from the supplied base field elements.
+    ///
+    /// # Errors
+    /// Returns an error if fewer base field elements than the extension degree of `E` remain in
+    /// this coin.
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        let start = self.next_element;
+        let end = start + E::EXTENSION_DEGREE;
+        if end > self.elements.len() {
+            return Err(RandomCoinError::FailedToDrawFieldElement(0));
+        }
+
+        self.next_element = end;
+        Ok(E::slice_from_base_elements(&self.elements[start..end])[0])
+    }
+
+    /// Returns the next `num_values` supplied integers; the `nonce` is ignored.
+    ///
+    /// # Errors
+    /// Returns an error if fewer than `num_values` integers remain in this coin, or if any of the
+    /// returned integers is not smaller than `domain_size`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `domain_size` is not a power of two.
+    /// - `num_values` is greater than or equal to `domain_size`.
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        _nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
+        assert!(num_values < domain_size, "number of values must be smaller than domain size");
+
+        let remaining = &self.integers[self.next_integer..];
+        let num_valid = remaining.iter().take(num_values).take_while(|&&v| v < domain_size).count();
+        if num_valid < num_values {
+            return Err(RandomCoinError::FailedToDrawIntegers(num_values, num_valid, 0));
+        }
+
+        let values = remaining[..num_values].to_vec();
+        self.next_integer += num_values;
+        Ok(values)
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::RandomCoinError, Digest, RandomCoin};
+use core::fmt;
+use math::FieldElement;
+use std::sync::{Arc, Mutex};
+use utils::collections::Vec;
+
+// TRANSCRIPT
+// ================================================================================================
+
+/// A single operation performed on a public coin, as recorded by a [TranscriptCoin].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEvent {
+    /// The coin was instantiated from the seed with the specified bytes (the serialized base
+    /// field elements of the seed).
+    Seed(Vec<u8>),
+    /// The coin was reseeded with a digest; contains [Digest::as_bytes()] of the digest.
+    Reseed(Vec<u8>),
+    /// The coin was reseeded with an integer (e.g., a proof-of-work nonce).
+    ReseedWithInt(u64),
+    /// A field element was drawn from the coin; contains the bytes of the drawn element.
+    Draw(Vec<u8>),
+    /// Integers were drawn from the coin after reseeding it with the specified nonce.
+    DrawIntegers {
+        /// Proof-of-work nonce the coin was reseeded with.
+        nonce: u64,
+        /// Size of the domain the integers were drawn from.
+        domain_size: usize,
+        /// Drawn integers.
+        values: Vec<usize>,
+    },
+}
+
+impl TranscriptEvent {
+    /// Returns the name of the coin operation this event records.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Seed(_) => "seed",
+            Self::Reseed(_) => "reseed",
+            Self::ReseedWithInt(_) => "reseed_with_int",
+            Self::Draw(_) => "draw",
+            Self::DrawIntegers { .. } => "draw_integers",
+        }
+    }
+}
+
+impl fmt::Display for TranscriptEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())?;
+        match self {
+            Self::Seed(bytes) | Self::Reseed(bytes) | Self::Draw(bytes) => {
+                f.write_str(" 0x")?;
+                for byte in bytes {
+                    write!(f, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+            Self::ReseedWithInt(value) => write!(f, " {value}"),
+            Self::DrawIntegers {
+                nonce,
+                domain_size,
+                values,
+            } => {
+                write!(f, " nonce={nonce} domain_size={domain_size} values={values:?}")
+            }
+        }
+    }
+}
+
+/// A log of all operations performed on a public coin during proof generation or verification.
+///
+/// Since the prover and the verifier feed the same data to their public coins in the same order,
+/// transcripts recorded for a valid proof by the prover and by the verifier are identical. When a
+/// proof generated by one implementation of the protocol is rejected by another, comparing the
+/// transcripts via [Transcript::first_divergence()] finds the first value about which the two
+/// implementations disagree. The [Display](fmt::Display) implementation writes one event per
+/// line, and so, transcripts can also be compared with a line-based diff tool.
+///
+/// Proof-of-work checks are not recorded: the prover checks many candidate nonces while the
+/// verifier checks a single one. The accepted query nonce is recorded with the
+/// [TranscriptEvent::DrawIntegers] event, and accepted FRI layer nonces are recorded with the
+/// [TranscriptEvent::ReseedWithInt] events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    events: Vec<TranscriptEvent>,
+}
+
+impl Transcript {
+    /// Returns all events of this transcript in the order in which they were recorded.
+    pub fn events(&self) -> &[TranscriptEvent] {
+        &self.events
+    }
+
+    /// Returns the number of events in this transcript.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if no events have been recorded in this transcript.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns the index of the first event which differs between this and the `other`
+    /// transcript, or `None` if the transcripts are identical.
+    ///
+    /// If one transcript is a prefix of the other, the length of the shorter transcript is
+    /// returned.
+    pub fn first_divergence(&self, other: &Self) -> Option<usize> {
+        match self.events.iter().zip(other.events.iter()).position(|(a, b)| a != b) {
+            Some(index) => Some(index),
+            None if self.len() != other.len() => Some(self.len().min(other.len())),
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, event) in self.events.iter().enumerate() {
+            writeln!(f, "{index}: {event}")?;
+        }
+        Ok(())
+    }
+}
+
+// TRANSCRIPT RECORDER
+// ================================================================================================
+
+/// A handle to a [Transcript] shared by all coins created via [TranscriptRecorder::wrap()].
+///
+/// Public coins are usually instantiated and consumed deep inside the prover and the verifier;
+/// the recorder is kept by the caller, and so, the transcript remains accessible after the coin
+/// has been dropped.
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptRecorder {
+    transcript: Arc<Mutex<Transcript>>,
+}
+
+impl TranscriptRecorder {
+    /// Returns a new recorder with an empty transcript.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a coin which delegates all operations to `coin` and records them in the
+    /// transcript of this recorder; `seed` must be the seed `coin` was instantiated from, and is
+    /// recorded as the first event.
+    pub fn wrap<R: RandomCoin>(&self, seed: &[R::BaseField], coin: R) -> TranscriptCoin<R> {
+        self.record(TranscriptEvent::Seed(R::BaseField::elements_as_bytes(seed).to_vec()));
+        TranscriptCoin {
+            coin,
+            recorder: self.clone(),
+        }
+    }
+
+    /// Returns a copy of the transcript recorded so far.
+    pub fn transcript(&self) -> Transcript {
+        self.transcript.lock().expect("transcript lock poisoned").clone()
+    }
+
+    fn record(&self, event: TranscriptEvent) {
+        self.transcript.lock().expect("transcript lock poisoned").events.push(event);
+    }
+}
+
+// TRANSCRIPT COIN
+// ================================================================================================
+
+/// A random coin which delegates all operations to the wrapped coin `R` and records them in a
+/// [Transcript].
+///
+/// Challenges drawn from this coin are identical to the challenges drawn from the wrapped coin,
+/// and thus, proofs generated with this coin are identical to proofs generated with `R`. A coin
+/// instantiated via [RandomCoin::new()] records its transcript in a recorder of its own, which
+/// can be read via [TranscriptCoin::transcript()]; to read the transcript after the coin has been
+/// consumed, the coin should be created via [TranscriptRecorder::wrap()] instead.
+pub struct TranscriptCoin<R: RandomCoin> {
+    coin: R,
+    recorder: TranscriptRecorder,
+}
+
+impl<R: RandomCoin> TranscriptCoin<R> {
+    /// Returns a copy of the transcript recorded so far.
+    pub fn transcript(&self) -> Transcript {
+        self.recorder.transcript()
+    }
+}
+
+impl<R: RandomCoin> RandomCoin for TranscriptCoin<R> {
+    type BaseField = R::BaseField;
+    type Hasher = R::Hasher;
+
+    fn new(seed: &[Self::BaseField]) -> Self {
+        TranscriptRecorder::new().wrap(seed, R::new(seed))
+    }
+
+    fn reseed(&mut self, data: <Self::Hasher as crate::Hasher>::Digest) {
+        self.recorder.record(TranscriptEvent::Reseed(data.as_bytes().to_vec()));
+        self.coin.reseed(data);
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        self.recorder.record(TranscriptEvent::ReseedWithInt(value));
+        self.coin.reseed_with_int(value);
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        self.coin.check_leading_zeros(value)
+    }
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        let element = self.coin.draw::<E>()?;
+        self.recorder
+            .record(TranscriptEvent::Draw(E::elements_as_bytes(&[element]).to_vec()));
+        Ok(element)
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        let values = self.coin.draw_integers(num_values, domain_size, nonce)?;
+        self.recorder.record(TranscriptEvent::DrawIntegers {
+            nonce,
+            domain_size,
+            values: values.clone(),
+        });
+        Ok(values)
+    }
+}
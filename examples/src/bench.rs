@@ -0,0 +1,225 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+use winterfell::{FieldExtension, ProofOptions};
+
+use examples::{ExampleOptions, ExampleType};
+
+// BENCHMARK OPTIONS
+// ================================================================================================
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "winter-bench", about = "Winterfell proof options benchmark")]
+struct BenchOptions {
+    #[structopt(subcommand)]
+    example: ExampleType,
+
+    /// Hash functions to benchmark (comma-separated)
+    #[structopt(
+        short = "h",
+        long = "hash_fn",
+        use_delimiter = true,
+        default_value = "blake3_256"
+    )]
+    hash_fns: Vec<String>,
+
+    /// Numbers of queries to benchmark (comma-separated)
+    #[structopt(short = "q", long = "queries", use_delimiter = true, default_value = "32")]
+    num_queries: Vec<usize>,
+
+    /// Blowup factors to benchmark (comma-separated)
+    #[structopt(short = "b", long = "blowup", use_delimiter = true, default_value = "8")]
+    blowup_factors: Vec<usize>,
+
+    /// FRI folding factors to benchmark (comma-separated)
+    #[structopt(short = "f", long = "folding", use_delimiter = true, default_value = "8")]
+    folding_factors: Vec<usize>,
+
+    /// Grinding factor for query seed
+    #[structopt(short = "g", long = "grinding", default_value = "16")]
+    grinding_factor: u32,
+
+    /// Field extension for composition polynomial (none, quad, or cubic)
+    #[structopt(short = "e", long = "field_extension", default_value = "none")]
+    field_extension: String,
+
+    /// Maximum degree of the FRI remainder polynomial
+    #[structopt(short = "r", long = "remainder", default_value = "31")]
+    remainder_max_degree: usize,
+
+    /// Number of times each configuration is run; the fastest run is reported
+    #[structopt(long = "runs", default_value = "1")]
+    num_runs: usize,
+
+    /// Output format (csv or json)
+    #[structopt(long = "format", default_value = "csv")]
+    format: String,
+}
+
+// BENCHMARK RESULTS
+// ================================================================================================
+
+/// Results of benchmarking a single configuration of proof options.
+struct BenchResult {
+    example: String,
+    hash_fn: String,
+    options: ProofOptions,
+    prove_time: Duration,
+    verify_time: Duration,
+    proof_size: usize,
+    security_level: usize,
+}
+
+impl BenchResult {
+    /// Names of all fields of the result, in the order in which they are written.
+    const FIELDS: [&'static str; 12] = [
+        "example",
+        "hash_fn",
+        "num_queries",
+        "blowup_factor",
+        "folding_factor",
+        "remainder_max_degree",
+        "field_extension",
+        "grinding_factor",
+        "prove_time_ms",
+        "verify_time_ms",
+        "proof_size_bytes",
+        "security_level",
+    ];
+
+    /// Returns values of all fields of the result; string values are enclosed in double quotes.
+    fn values(&self) -> [String; 12] {
+        let string = |value: &str| format!("\"{value}\"");
+        let field_extension = match self.options.field_extension() {
+            FieldExtension::None => "none",
+            FieldExtension::Quadratic => "quad",
+            FieldExtension::Cubic => "cubic",
+        };
+        [
+            string(&self.example),
+            string(&self.hash_fn),
+            self.options.num_queries().to_string(),
+            self.options.blowup_factor().to_string(),
+            self.options.to_fri_options().folding_factor().to_string(),
+            self.options.to_fri_options().remainder_max_degree().to_string(),
+            string(field_extension),
+            self.options.grinding_factor().to_string(),
+            format!("{:.3}", self.prove_time.as_secs_f64() * 1000.0),
+            format!("{:.3}", self.verify_time.as_secs_f64() * 1000.0),
+            self.proof_size.to_string(),
+            self.security_level.to_string(),
+        ]
+    }
+
+    /// Returns this result as a line of comma-separated values.
+    fn to_csv(&self) -> String {
+        self.values().join(",")
+    }
+
+    /// Returns this result as a JSON object.
+    fn to_json(&self) -> String {
+        let entries = Self::FIELDS
+            .iter()
+            .zip(self.values())
+            .map(|(name, value)| format!("\"{name}\": {value}"))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", entries.join(", "))
+    }
+}
+
+// BENCHMARK RUNNER
+// ================================================================================================
+
+fn main() {
+    let bench = BenchOptions::from_args();
+    let json = match bench.format.as_str() {
+        "csv" => false,
+        "json" => true,
+        val => panic!("'{val}' is not a valid output format"),
+    };
+
+    if json {
+        println!("[");
+    } else {
+        println!("{}", BenchResult::FIELDS.join(","));
+    }
+
+    // run all combinations of the swept parameters; configurations which are not valid for the
+    // example are skipped
+    let mut num_results = 0;
+    for hash_fn in bench.hash_fns.iter() {
+        for &num_queries in bench.num_queries.iter() {
+            for &blowup_factor in bench.blowup_factors.iter() {
+                for &folding_factor in bench.folding_factors.iter() {
+                    let options = format!(
+                        "q={},b={},g={},ext={},fri={}/{}",
+                        num_queries,
+                        blowup_factor,
+                        bench.grinding_factor,
+                        bench.field_extension,
+                        folding_factor,
+                        bench.remainder_max_degree
+                    );
+                    match run(&bench, hash_fn, &options) {
+                        Ok(result) if json => {
+                            let separator = if num_results == 0 { "" } else { ",\n" };
+                            print!("{separator}  {}", result.to_json());
+                            num_results += 1;
+                        }
+                        Ok(result) => println!("{}", result.to_csv()),
+                        Err(err) => eprintln!("skipping '{options},hash={hash_fn}': {err}"),
+                    }
+                }
+            }
+        }
+    }
+
+    if json {
+        println!("\n]");
+    }
+}
+
+/// Proves and verifies the example with the specified hash function and proof options `num_runs`
+/// times, and returns the result of the fastest run.
+fn run(bench: &BenchOptions, hash_fn: &str, options: &str) -> Result<BenchResult, String> {
+    let proof_options = options.parse::<ProofOptions>().map_err(|err| err.to_string())?;
+    let example_options =
+        ExampleOptions::from_compact(bench.example.clone(), &format!("{options},hash={hash_fn}"));
+    let example = examples::get_example(&example_options)?;
+
+    let mut result: Option<BenchResult> = None;
+    for _ in 0..bench.num_runs.max(1) {
+        let now = Instant::now();
+        let proof = example.prove();
+        let prove_time = now.elapsed();
+
+        let proof_size = proof.to_bytes().len();
+        let security_level = example_options.get_proof_security_level(&proof, true);
+
+        let now = Instant::now();
+        example.verify(proof).map_err(|err| format!("failed to verify proof: {err}"))?;
+        let verify_time = now.elapsed();
+
+        let is_fastest = match result.as_ref() {
+            Some(best) => prove_time < best.prove_time,
+            None => true,
+        };
+        if is_fastest {
+            result = Some(BenchResult {
+                example: format!("{:?}", bench.example),
+                hash_fn: hash_fn.to_string(),
+                options: proof_options.clone(),
+                prove_time,
+                verify_time,
+                proof_size,
+                security_level,
+            });
+        }
+    }
+
+    Ok(result.expect("no runs"))
+}
@@ -0,0 +1,255 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compression::{MSG_PERMUTATION, STATE_WIDTH},
+    get_init_state, state_word, BaseElement, FieldElement, ProofOptions, A1_OFFSET, BLOCK_SIZE,
+    C1_OFFSET, CARRIES_PER_SLOT, CARRY_OFFSET, CYCLE_LENGTH, DIGEST_SIZE, MSG_OFFSET,
+    NUM_HALF_ROUNDS, NUM_SLOTS, TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of constraints which enforce that bit columns contain binary values; all columns
+/// preceding the message block are bit columns.
+const NUM_BINARY_CONSTRAINTS: usize = MSG_OFFSET;
+
+/// Index of the first constraint which enforces application of the G functions.
+const SLOT_CONSTRAINTS: usize = NUM_BINARY_CONSTRAINTS;
+
+/// Number of constraints which enforce application of a single G function.
+const CONSTRAINTS_PER_SLOT: usize = 6;
+
+/// Index of the first constraint which enforces transition of the state outside of the rounds.
+const STATE_CONSTRAINTS: usize = SLOT_CONSTRAINTS + NUM_SLOTS * CONSTRAINTS_PER_SLOT;
+
+/// Index of the first constraint which enforces transition of the message block.
+const MSG_CONSTRAINTS: usize = STATE_CONSTRAINTS + STATE_WIDTH;
+
+/// Total number of transition constraints.
+const NUM_CONSTRAINTS: usize = MSG_CONSTRAINTS + BLOCK_SIZE;
+
+/// Total number of assertions.
+const NUM_ASSERTIONS: usize = STATE_WIDTH * 32 + BLOCK_SIZE + DIGEST_SIZE;
+
+// BLAKE3 AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub seed: [u32; DIGEST_SIZE],
+    pub result: [u32; DIGEST_SIZE],
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.seed
+            .iter()
+            .chain(self.result.iter())
+            .map(|&word| BaseElement::from(word))
+            .collect()
+    }
+}
+
+pub struct Blake3Air {
+    context: AirContext<BaseElement>,
+    seed: [u32; DIGEST_SIZE],
+    result: [u32; DIGEST_SIZE],
+}
+
+impl Air for Blake3Air {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let flagged_degree =
+            |degree| TransitionConstraintDegree::with_cycles(degree, vec![CYCLE_LENGTH]);
+        let mut degrees = vec![TransitionConstraintDegree::new(2); NUM_BINARY_CONSTRAINTS];
+        for _ in 0..NUM_SLOTS {
+            degrees.extend([1, 2, 2, 3, 1, 3].map(flagged_degree));
+        }
+        degrees.resize(MSG_CONSTRAINTS, flagged_degree(1));
+        degrees.resize(MSG_CONSTRAINTS + DIGEST_SIZE, flagged_degree(2));
+        degrees.resize(NUM_CONSTRAINTS, flagged_degree(1));
+
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        Blake3Air {
+            context: AirContext::new(trace_info, degrees, NUM_ASSERTIONS, options),
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into flags of the rows which mix columns, mix diagonals,
+        // finalize the compression, and leave the state unchanged
+        let column_flag = periodic_values[0];
+        let diagonal_flag = periodic_values[1];
+        let final_flag = periodic_values[2];
+        let idle_flag = periodic_values[3];
+        let round_flag = column_flag + diagonal_flag;
+        let two_32 = E::from(1u64 << 32);
+
+        // all bit columns must contain binary values
+        for i in 0..NUM_BINARY_CONSTRAINTS {
+            result[i] = is_binary(current[i]);
+        }
+
+        // every G function mixes the words in a single column of the trace:
+        //   a1 = a + b + mx;  d1 = (d ^ a1) >>> 16;  c1 = c + d1;  b1 = (b ^ c1) >>> 12
+        //   a2 = a1 + b1 + my;  d2 = (d1 ^ a2) >>> 8;  c2 = c1 + d2;  b2 = (b1 ^ c2) >>> 7
+        // words a2, b2, c2, d2 are stored in the next row; since the layout of the next row is
+        // different from the layout of the current row, words b2 and d2 are read from different
+        // positions depending on the half of the round
+        let msg = &current[MSG_OFFSET..MSG_OFFSET + BLOCK_SIZE];
+        let word = |row: &[E], position: usize| -> [E; 32] {
+            core::array::from_fn(|bit| row[state_word(position) + bit])
+        };
+        for slot in 0..NUM_SLOTS {
+            let offset = SLOT_CONSTRAINTS + slot * CONSTRAINTS_PER_SLOT;
+            let [a, b, c, d] = [0, 1, 2, 3].map(|row| word(current, row * 4 + slot));
+            let a1 = &current[A1_OFFSET + slot * 32..A1_OFFSET + (slot + 1) * 32];
+            let c1 = &current[C1_OFFSET + slot * 32..C1_OFFSET + (slot + 1) * 32];
+            let carries = &current[CARRY_OFFSET + slot * CARRIES_PER_SLOT..];
+            let mx = column_flag * msg[slot * 2] + diagonal_flag * msg[8 + slot * 2];
+            let my = column_flag * msg[slot * 2 + 1] + diagonal_flag * msg[9 + slot * 2];
+
+            result[offset] = round_flag
+                * are_equal(
+                    compose_word(a1) + compose_word(&carries[..2]) * two_32,
+                    compose_word(&a) + compose_word(&b),
+                )
+                - mx;
+
+            let d1 = xor_rotated(&d, a1, 16);
+            result[offset + 1] = round_flag
+                * are_equal(
+                    compose_word(c1) + carries[2] * two_32,
+                    compose_word(&c) + compose_word(&d1),
+                );
+
+            let b1 = xor_rotated(&b, c1, 12);
+            let a2 = word(next, slot);
+            result[offset + 2] = round_flag
+                * are_equal(
+                    compose_word(&a2) + compose_word(&carries[3..5]) * two_32,
+                    compose_word(a1) + compose_word(&b1),
+                )
+                - my;
+
+            let d2 = column_flag * compose_word(&word(next, 12 + (slot + 1) % 4))
+                + diagonal_flag * compose_word(&word(next, 12 + (slot + 3) % 4));
+            result[offset + 3] =
+                are_equal(d2, round_flag * compose_word(&xor_rotated(&d1, &a2, 8)));
+
+            let c2 = word(next, 8 + (slot + 2) % 4);
+            result[offset + 4] = round_flag
+                * are_equal(compose_word(&c2) + carries[5] * two_32, compose_word(c1))
+                - d2;
+
+            let b2 = column_flag * compose_word(&word(next, 4 + (slot + 3) % 4))
+                + diagonal_flag * compose_word(&word(next, 4 + (slot + 1) % 4));
+            result[offset + 5] =
+                are_equal(b2, round_flag * compose_word(&xor_rotated(&b1, &c2, 7)));
+        }
+
+        // when the compression is finalized, the state is reset to its initial value; on the
+        // last row of a cycle, the state is left unchanged
+        for (i, &value) in get_init_state().iter().enumerate() {
+            let next_word = compose_word(&word(next, i));
+            result[STATE_CONSTRAINTS + i] = final_flag * are_equal(next_word, E::from(value))
+                + idle_flag * are_equal(next_word, compose_word(&word(current, i)));
+        }
+
+        // the message block is permuted after every round; when the compression is finalized,
+        // the output of the compression becomes the first half of the next message block, and
+        // the second half of the next message block is set to zeros
+        for i in 0..BLOCK_SIZE {
+            let mut next_value =
+                (column_flag + idle_flag) * msg[i] + diagonal_flag * msg[MSG_PERMUTATION[i]];
+            if i < DIGEST_SIZE {
+                let output = xor_rotated(&word(current, i), &word(current, i + DIGEST_SIZE), 0);
+                next_value += final_flag * compose_word(&output);
+            }
+            result[MSG_CONSTRAINTS + i] = are_equal(next[MSG_OFFSET + i], next_value);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the first compression starts with the initial state and the seed as the message
+        // block, and the last compression outputs the result
+        let last_step = self.trace_length() - 1;
+        let mut assertions = Vec::with_capacity(NUM_ASSERTIONS);
+        for (i, &value) in get_init_state().iter().enumerate() {
+            for bit in 0..32 {
+                let bit_value = BaseElement::from((value >> bit) & 1);
+                assertions.push(Assertion::single(state_word(i) + bit, 0, bit_value));
+            }
+        }
+        for i in 0..BLOCK_SIZE {
+            let value = self.seed.get(i).copied().unwrap_or(0);
+            assertions.push(Assertion::single(MSG_OFFSET + i, 0, value.into()));
+        }
+        for (i, &value) in self.result.iter().enumerate() {
+            assertions.push(Assertion::single(MSG_OFFSET + i, last_step, value.into()));
+        }
+        assertions
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut column_flags = vec![BaseElement::ZERO; CYCLE_LENGTH];
+        let mut diagonal_flags = vec![BaseElement::ZERO; CYCLE_LENGTH];
+        for row in 0..NUM_HALF_ROUNDS {
+            if row % 2 == 0 {
+                column_flags[row] = BaseElement::ONE;
+            } else {
+                diagonal_flags[row] = BaseElement::ONE;
+            }
+        }
+        let mut final_flags = vec![BaseElement::ZERO; CYCLE_LENGTH];
+        final_flags[NUM_HALF_ROUNDS] = BaseElement::ONE;
+        let mut idle_flags = vec![BaseElement::ZERO; CYCLE_LENGTH];
+        idle_flags[NUM_HALF_ROUNDS + 1] = BaseElement::ONE;
+        vec![column_flags, diagonal_flags, final_flags, idle_flags]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a field element composed from the provided bits, where the first bit is the least
+/// significant bit.
+fn compose_word<E: FieldElement>(bits: &[E]) -> E {
+    bits.iter().rev().fold(E::ZERO, |word, &bit| word.double() + bit)
+}
+
+/// Returns bits of (x ^ y) >>> n, where x and y are given by their bits.
+fn xor_rotated<E: FieldElement>(x: &[E], y: &[E], n: usize) -> [E; 32] {
+    core::array::from_fn(|i| {
+        let (x, y) = (x[(i + n) % 32], y[(i + n) % 32]);
+        x + y - (x * y).double()
+    })
+}
@@ -0,0 +1,136 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of 32-bit words in the state of BLAKE3 compression function.
+pub const STATE_WIDTH: usize = 16;
+
+/// Number of 32-bit words in a message block.
+pub const BLOCK_SIZE: usize = 16;
+
+/// Number of 32-bit words in a chaining value (and in a hash).
+pub const DIGEST_SIZE: usize = 8;
+
+/// Number of rounds in a single invocation of BLAKE3 compression function.
+pub const NUM_ROUNDS: usize = 7;
+
+/// Initialization vector of BLAKE3; this is the same as the initial hash value of SHA-256.
+pub const IV: [u32; DIGEST_SIZE] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Permutation applied to the words of the message block after every round.
+pub const MSG_PERMUTATION: [usize; BLOCK_SIZE] =
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+/// Domain separation flags of a compression which both starts and ends a chunk, and produces
+/// the root of the hash tree.
+pub const CHUNK_START: u32 = 1;
+pub const CHUNK_END: u32 = 2;
+pub const ROOT: u32 = 8;
+
+// BLAKE3 COMPRESSION FUNCTION
+// ================================================================================================
+
+/// Returns the BLAKE3 hash of a 32-byte input, where the input and the hash are given as
+/// little-endian 32-bit words.
+///
+/// Such inputs fit into a single block of a single chunk, and thus, the hash is the chaining
+/// value output by a single invocation of the compression function.
+pub fn hash(input: &[u32; DIGEST_SIZE]) -> [u32; DIGEST_SIZE] {
+    let mut block = [0; BLOCK_SIZE];
+    block[..DIGEST_SIZE].copy_from_slice(input);
+    let state = compress(&IV, &block, 0, 32, CHUNK_START | CHUNK_END | ROOT);
+    let mut result = [0; DIGEST_SIZE];
+    result.copy_from_slice(&state[..DIGEST_SIZE]);
+    result
+}
+
+/// Applies BLAKE3 compression function to the specified chaining value and message block, and
+/// returns the full 16-word output of the function.
+pub fn compress(
+    cv: &[u32; DIGEST_SIZE],
+    block: &[u32; BLOCK_SIZE],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; STATE_WIDTH] {
+    let mut state = init_state(cv, counter, block_len, flags);
+    let mut block = *block;
+    for round in 0..NUM_ROUNDS {
+        apply_round(&mut state, &block);
+        if round != NUM_ROUNDS - 1 {
+            block = permute(&block);
+        }
+    }
+    for i in 0..DIGEST_SIZE {
+        state[i] ^= state[i + DIGEST_SIZE];
+        state[i + DIGEST_SIZE] ^= cv[i];
+    }
+    state
+}
+
+/// Returns the initial state of the compression function.
+pub fn init_state(
+    cv: &[u32; DIGEST_SIZE],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; STATE_WIDTH] {
+    [
+        cv[0],
+        cv[1],
+        cv[2],
+        cv[3],
+        cv[4],
+        cv[5],
+        cv[6],
+        cv[7],
+        IV[0],
+        IV[1],
+        IV[2],
+        IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ]
+}
+
+/// Applies a single round of the compression function to the provided state; a round consists
+/// of mixing the columns of the state followed by mixing its diagonals.
+pub fn apply_round(state: &mut [u32; STATE_WIDTH], block: &[u32; BLOCK_SIZE]) {
+    // mix the columns
+    g(state, 0, 4, 8, 12, block[0], block[1]);
+    g(state, 1, 5, 9, 13, block[2], block[3]);
+    g(state, 2, 6, 10, 14, block[4], block[5]);
+    g(state, 3, 7, 11, 15, block[6], block[7]);
+
+    // mix the diagonals
+    g(state, 0, 5, 10, 15, block[8], block[9]);
+    g(state, 1, 6, 11, 12, block[10], block[11]);
+    g(state, 2, 7, 8, 13, block[12], block[13]);
+    g(state, 3, 4, 9, 14, block[14], block[15]);
+}
+
+/// The quarter-round function of BLAKE3, which mixes the words at the specified indexes of the
+/// state with two words of the message block.
+pub fn g(state: &mut [u32], a: usize, b: usize, c: usize, d: usize, mx: u32, my: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(mx);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(my);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+/// Returns the message block with its words permuted according to [MSG_PERMUTATION].
+pub fn permute(block: &[u32; BLOCK_SIZE]) -> [u32; BLOCK_SIZE] {
+    MSG_PERMUTATION.map(|i| block[i])
+}
@@ -0,0 +1,241 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod compression;
+use compression::{BLOCK_SIZE, CHUNK_END, CHUNK_START, DIGEST_SIZE, IV, ROOT, STATE_WIDTH};
+
+mod air;
+use air::{Blake3Air, PublicInputs};
+
+mod prover;
+use prover::Blake3Prover;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of trace rows used by a single invocation of the compression function; 14 rows execute
+/// halves of the 7 rounds of the compression function, and the remaining 2 rows finalize the
+/// output and start the next invocation.
+const CYCLE_LENGTH: usize = 16;
+
+/// Number of rows executing halves of the rounds of the compression function.
+const NUM_HALF_ROUNDS: usize = 14;
+
+/// Number of quarter-round functions applied in parallel in every half of a round.
+const NUM_SLOTS: usize = 4;
+
+/// Number of carry bits of additions performed by a single quarter-round function.
+const CARRIES_PER_SLOT: usize = 6;
+
+/// Length of the hashed values in bytes; such values fit into a single message block.
+const INPUT_LEN: u32 = 32;
+
+/// Domain separation flags of every compression in the chain.
+const FLAGS: u32 = CHUNK_START | CHUNK_END | ROOT;
+
+// TRACE LAYOUT
+// ================================================================================================
+// The example computes a hash chain x_{i+1} = BLAKE3(x_i), where every value is 32 bytes long,
+// and thus, every hash is computed by a single invocation of the compression function. Every row
+// of a cycle holds the state of the compression function, as well as the message block, and the
+// rows of the cycle are used as follows:
+//
+// - Rows 0 - 13 execute halves of the rounds: even rows mix the columns of the state, while odd
+//   rows mix its diagonals. Four quarter-round functions G are applied in parallel on every row.
+// - Row 14 finalizes the compression: the output of the compression becomes the first 8 words of
+//   the message block of the next compression, and the state is reset to its initial value.
+// - Row 15 leaves the state and the message block unchanged.
+//
+// Words of the state are decomposed into bits, which makes XORs and rotations of the words cheap
+// to express. To keep constraints the same for both halves of a round, the state is stored in
+// diagonalized form on odd rows, i.e., row r of the 4x4 matrix of state words is rotated left by
+// r positions; this way, every G function is always applied to the words in the same column of
+// the trace. Additions modulo 2^32 are verified using the bits of their results and carries, and
+// the intermediate values of every G function which are needed in XORs are decomposed into bits
+// as well. Words of the message block are range checked implicitly, as they are composed from
+// bits of the state of the previous compression (or are public).
+
+/// Index of the first column holding bits of state words.
+const STATE_OFFSET: usize = 0;
+
+/// Index of the first column holding bits of the first intermediate value of word a of every G
+/// function.
+const A1_OFFSET: usize = STATE_OFFSET + STATE_WIDTH * 32;
+
+/// Index of the first column holding bits of the first intermediate value of word c of every G
+/// function.
+const C1_OFFSET: usize = A1_OFFSET + NUM_SLOTS * 32;
+
+/// Index of the first column holding carry bits of additions of every G function; the carries
+/// of additions computing words a and c occupy 2 bits and 1 bit respectively.
+const CARRY_OFFSET: usize = C1_OFFSET + NUM_SLOTS * 32;
+
+/// Index of the first column holding words of the message block.
+const MSG_OFFSET: usize = CARRY_OFFSET + NUM_SLOTS * CARRIES_PER_SLOT;
+
+/// Total number of columns in the execution trace.
+const TRACE_WIDTH: usize = MSG_OFFSET + BLOCK_SIZE;
+
+/// Returns index of the column holding the first bit of the state word at the specified
+/// position.
+const fn state_word(position: usize) -> usize {
+    STATE_OFFSET + position * 32
+}
+
+/// Returns the position of the specified state word in the trace; on rows which mix diagonals,
+/// row r of the matrix of state words is rotated left by r positions.
+const fn get_position(word: usize, diagonal: bool) -> usize {
+    let (row, column) = (word / 4, word % 4);
+    if diagonal {
+        row * 4 + (column + 4 - row) % 4
+    } else {
+        word
+    }
+}
+
+/// Returns the initial state of every compression in the chain.
+fn get_init_state() -> [u32; STATE_WIDTH] {
+    compression::init_state(&IV, 0, INPUT_LEN, FLAGS)
+}
+
+// BLAKE3 HASH CHAIN EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    chain_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(42, 4);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(Blake3Example::<Blake3_192>::new(chain_length, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(Blake3Example::<Blake3_256>::new(chain_length, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(Blake3Example::<Sha3_256>::new(chain_length, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct Blake3Example<H: ElementHasher> {
+    options: ProofOptions,
+    chain_length: usize,
+    seed: [u32; DIGEST_SIZE],
+    result: [u32; DIGEST_SIZE],
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> Blake3Example<H> {
+    pub fn new(chain_length: usize, options: ProofOptions) -> Self {
+        assert!(chain_length.is_power_of_two(), "chain length must be a power of 2");
+        let seed = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        // compute the sequence of hashes
+        let now = Instant::now();
+        let result = compute_hash_chain(seed, chain_length);
+        debug!(
+            "Computed a chain of {} BLAKE3 hashes in {} ms",
+            chain_length,
+            now.elapsed().as_millis(),
+        );
+
+        Blake3Example {
+            options,
+            chain_length,
+            seed,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for Blake3Example<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for computing a chain of {} BLAKE3 hashes\n\
+            ---------------------",
+            self.chain_length
+        );
+
+        // create a prover
+        let prover = Blake3Prover::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.seed, self.chain_length);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            seed: self.seed,
+            result: self.result,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<Blake3Air, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut result = self.result;
+        result[0] ^= 1;
+        let pub_inputs = PublicInputs {
+            seed: self.seed,
+            result,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<Blake3Air, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the result of applying BLAKE3 hash function to the seed the specified number of times.
+fn compute_hash_chain(seed: [u32; DIGEST_SIZE], length: usize) -> [u32; DIGEST_SIZE] {
+    (0..length).fold(seed, |value, _| compression::hash(&value))
+}
@@ -0,0 +1,193 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compression::{self, STATE_WIDTH},
+    get_init_state, get_position, state_word, BaseElement, Blake3Air, DefaultRandomCoin,
+    ElementHasher, FieldElement, PhantomData, ProofOptions, Prover, PublicInputs, A1_OFFSET,
+    BLOCK_SIZE, C1_OFFSET, CARRIES_PER_SLOT, CARRY_OFFSET, CYCLE_LENGTH, DIGEST_SIZE, MSG_OFFSET,
+    NUM_HALF_ROUNDS, NUM_SLOTS, TRACE_WIDTH,
+};
+use winterfell::{
+    math::StarkField, matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// BLAKE3 PROVER
+// ================================================================================================
+
+pub struct Blake3Prover<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> Blake3Prover<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace of computing a hash chain of the specified length starting
+    /// from the specified seed.
+    pub fn build_trace(
+        &self,
+        seed: [u32; DIGEST_SIZE],
+        chain_length: usize,
+    ) -> TraceTable<BaseElement> {
+        let trace_length = chain_length * CYCLE_LENGTH;
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+        let mut state = get_init_state();
+        let mut block = [0; BLOCK_SIZE];
+        block[..DIGEST_SIZE].copy_from_slice(&seed);
+        trace.fill(
+            move |row| fill_row(row, &state, &block, 0),
+            move |step, row| {
+                // update the state and the message block as of the next row
+                let half_round = step % CYCLE_LENGTH;
+                if half_round < NUM_HALF_ROUNDS {
+                    apply_half_round(&mut state, &block, half_round % 2 == 1);
+                    if half_round % 2 == 1 {
+                        block = compression::permute(&block);
+                    }
+                } else if half_round == NUM_HALF_ROUNDS {
+                    block = [0; BLOCK_SIZE];
+                    for i in 0..DIGEST_SIZE {
+                        block[i] = state[i] ^ state[i + DIGEST_SIZE];
+                    }
+                    state = get_init_state();
+                }
+                fill_row(row, &state, &block, (step + 1) % CYCLE_LENGTH);
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for Blake3Prover<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = Blake3Air;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        let mut seed = [0; DIGEST_SIZE];
+        let mut result = [0; DIGEST_SIZE];
+        for i in 0..DIGEST_SIZE {
+            seed[i] = trace.get(MSG_OFFSET + i, 0).as_int() as u32;
+            result[i] = trace.get(MSG_OFFSET + i, last_step).as_int() as u32;
+        }
+        PublicInputs { seed, result }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the indexes of the state words mixed by the G function in the specified slot.
+fn get_slot_words(slot: usize, diagonal: bool) -> [usize; 4] {
+    [0, 1, 2, 3].map(|row| {
+        if diagonal {
+            row * 4 + (slot + row) % 4
+        } else {
+            row * 4 + slot
+        }
+    })
+}
+
+/// Applies the G functions of the specified half of a round to the provided state.
+fn apply_half_round(state: &mut [u32; STATE_WIDTH], block: &[u32; BLOCK_SIZE], diagonal: bool) {
+    for slot in 0..NUM_SLOTS {
+        let [a, b, c, d] = get_slot_words(slot, diagonal);
+        let msg = if diagonal { 8 + slot * 2 } else { slot * 2 };
+        compression::g(state, a, b, c, d, block[msg], block[msg + 1]);
+    }
+}
+
+/// Fills in the provided row with the specified state and message block, as well as with the
+/// intermediate values and carries of the G functions if the row executes a half of a round.
+fn fill_row(
+    row: &mut [BaseElement],
+    state: &[u32; STATE_WIDTH],
+    block: &[u32; BLOCK_SIZE],
+    step: usize,
+) {
+    let diagonal = step % 2 == 1 && step < NUM_HALF_ROUNDS;
+    for (i, &word) in state.iter().enumerate() {
+        write_bits(row, state_word(get_position(i, diagonal)), word as u64, 32);
+    }
+    for (i, &word) in block.iter().enumerate() {
+        row[MSG_OFFSET + i] = BaseElement::from(word);
+    }
+
+    // the intermediate values and carries are set to zeros on rows which do not execute rounds
+    for slot in 0..NUM_SLOTS {
+        let mut values = [0; 6];
+        if step < NUM_HALF_ROUNDS {
+            let [a, b, c, d] = get_slot_words(slot, diagonal).map(|i| state[i] as u64);
+            let msg = if diagonal { 8 + slot * 2 } else { slot * 2 };
+            let (mx, my) = (block[msg] as u64, block[msg + 1] as u64);
+
+            // words are kept as 64-bit values, so that the carries of additions are preserved
+            // in the upper 32 bits
+            let a1 = a + b + mx;
+            let d1 = ((d ^ a1) as u32).rotate_right(16) as u64;
+            let c1 = c + d1;
+            let b1 = ((b ^ c1) as u32).rotate_right(12) as u64;
+            let a2 = (a1 as u32) as u64 + b1 + my;
+            let d2 = ((d1 ^ a2) as u32).rotate_right(8) as u64;
+            let c2 = (c1 as u32) as u64 + d2;
+            values = [a1 as u32 as u64, c1 as u32 as u64, a1 >> 32, c1 >> 32, a2 >> 32, c2 >> 32];
+        }
+
+        write_bits(row, A1_OFFSET + slot * 32, values[0], 32);
+        write_bits(row, C1_OFFSET + slot * 32, values[1], 32);
+        let carries = CARRY_OFFSET + slot * CARRIES_PER_SLOT;
+        write_bits(row, carries, values[2], 2);
+        write_bits(row, carries + 2, values[3], 1);
+        write_bits(row, carries + 3, values[4], 2);
+        write_bits(row, carries + 5, values[5], 1);
+    }
+}
+
+fn write_bits(row: &mut [BaseElement], offset: usize, value: u64, num_bits: usize) {
+    for i in 0..num_bits {
+        row[offset + i] = BaseElement::from((value >> i) & 1);
+    }
+}
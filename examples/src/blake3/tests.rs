@@ -0,0 +1,46 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{compression, Blake3_256, DIGEST_SIZE};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn blake3_hash_matches_reference() {
+    let input: [u32; DIGEST_SIZE] =
+        core::array::from_fn(|i| (i as u32 + 1).wrapping_mul(0x9E3779B9));
+    let bytes = input.iter().flat_map(|word| word.to_le_bytes()).collect::<Vec<_>>();
+    let expected = ::blake3::hash(&bytes);
+
+    let result = compression::hash(&input);
+    let result = result.iter().flat_map(|word| word.to_le_bytes()).collect::<Vec<_>>();
+    assert_eq!(expected.as_bytes(), result.as_slice());
+}
+
+#[test]
+fn blake3_test_basic_proof_verification() {
+    let blake3_eg = Box::new(super::Blake3Example::<Blake3_256>::new(4, build_options(false)));
+    crate::tests::test_basic_proof_verification(blake3_eg);
+}
+
+#[test]
+fn blake3_test_basic_proof_verification_extension() {
+    let blake3_eg = Box::new(super::Blake3Example::<Blake3_256>::new(4, build_options(true)));
+    crate::tests::test_basic_proof_verification(blake3_eg);
+}
+
+#[test]
+fn blake3_test_basic_proof_verification_fail() {
+    let blake3_eg = Box::new(super::Blake3Example::<Blake3_256>::new(4, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(blake3_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
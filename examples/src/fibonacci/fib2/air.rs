@@ -20,6 +20,7 @@ pub struct FibAir {
 impl Air for FibAir {
     type BaseField = BaseElement;
     type PublicInputs = BaseElement;
+    const AIR_ID: u32 = 1;
 
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
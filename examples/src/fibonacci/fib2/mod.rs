@@ -15,10 +15,10 @@ use winterfell::{
 };
 
 mod air;
-use air::FibAir;
+pub use air::FibAir;
 
 mod prover;
-use prover::FibProver;
+pub use prover::FibProver;
 
 #[cfg(test)]
 mod tests;
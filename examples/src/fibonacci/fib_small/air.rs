@@ -20,6 +20,7 @@ pub struct FibSmall {
 impl Air for FibSmall {
     type BaseField = BaseElement;
     type PublicInputs = BaseElement;
+    const AIR_ID: u32 = 2;
 
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
@@ -15,10 +15,10 @@ use winterfell::{
 };
 
 mod air;
-use air::FibSmall;
+pub use air::FibSmall;
 
 mod prover;
-use prover::FibSmallProver;
+pub use prover::FibSmallProver;
 
 #[cfg(test)]
 mod tests;
@@ -23,6 +23,7 @@ pub struct MulFib2Air {
 impl Air for MulFib2Air {
     type BaseField = BaseElement;
     type PublicInputs = BaseElement;
+    const AIR_ID: u32 = 3;
 
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
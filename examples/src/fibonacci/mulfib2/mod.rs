@@ -15,10 +15,10 @@ use winterfell::{
 };
 
 mod air;
-use air::MulFib2Air;
+pub use air::MulFib2Air;
 
 mod prover;
-use prover::MulFib2Prover;
+pub use prover::MulFib2Prover;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,91 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{get_seeds, ExtElement, PublicInputs, NUM_EXTENSION_COLUMNS, TRACE_WIDTH};
+use crate::utils::are_equal;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// FIBONACCI AIR
+// ================================================================================================
+
+pub struct MulFibExtAir {
+    context: AirContext<BaseElement>,
+    result: ExtElement,
+}
+
+impl Air for MulFibExtAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+    const AIR_ID: u32 = 4;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+        ];
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let context = AirContext::new(trace_info, degrees, 7, options)
+            .set_num_main_extension_columns(NUM_EXTENSION_COLUMNS);
+        MulFibExtAir {
+            context,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // the frame contains the step counter followed by two terms of the sequence, each as a
+        // single extension field element
+        debug_assert_eq!(1 + NUM_EXTENSION_COLUMNS, current.len());
+        debug_assert_eq!(1 + NUM_EXTENSION_COLUMNS, next.len());
+
+        // the step counter is incremented by one at every step:
+        // s_{0, i+1} = s_{0, i} + 1
+        result[0] = are_equal(next[0], current[0] + E::ONE);
+
+        // constraints of multiplicative Fibonacci (with 2 registers) which state that:
+        // s_{1, i+1} = s_{1, i} * s_{2, i}
+        // s_{2, i+1} = s_{2, i} * s_{1, i+1}
+        result[1] = are_equal(next[1], current[1] * current[2]);
+        result[2] = are_equal(next[2], current[2] * next[1]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // assertions refer to columns of the committed main trace segment, and thus, constrain
+        // coordinates of extension field elements; a valid sequence should start with the seeds
+        // and terminate with the expected result
+        let last_step = self.trace_length() - 1;
+        let [seed0, seed1] = get_seeds();
+        let mut assertions = vec![Assertion::single(0, 0, BaseElement::ZERO)];
+        for (i, &coordinate) in
+            ExtElement::slice_as_base_elements(&[seed0, seed1]).iter().enumerate()
+        {
+            assertions.push(Assertion::single(1 + i, 0, coordinate));
+        }
+        for (i, &coordinate) in
+            ExtElement::slice_as_base_elements(&[self.result]).iter().enumerate()
+        {
+            assertions.push(Assertion::single(1 + i, last_step, coordinate));
+        }
+        assertions
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{
+        fields::{f128::BaseElement, QuadExtension},
+        FieldElement, ToElements,
+    },
+    FieldExtension, ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+pub use air::MulFibExtAir;
+
+mod prover;
+pub use prover::MulFibExtProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+/// Extension field in which terms of the sequence are computed.
+pub type ExtElement = QuadExtension<BaseElement>;
+
+/// Width of the main trace segment: a step counter, followed by coordinates of two terms of the
+/// sequence.
+const TRACE_WIDTH: usize = 1 + 2 * 2;
+
+/// Number of main trace columns holding extension field elements.
+const NUM_EXTENSION_COLUMNS: usize = 2;
+
+// FIBONACCI EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    sequence_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+    if options.field_extension() != FieldExtension::Quadratic {
+        return Err("This example requires quadratic field extension.".to_string());
+    }
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(MulFibExtExample::<Blake3_192>::new(sequence_length, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(MulFibExtExample::<Blake3_256>::new(sequence_length, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(MulFibExtExample::<Sha3_256>::new(sequence_length, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct MulFibExtExample<H: ElementHasher> {
+    options: ProofOptions,
+    sequence_length: usize,
+    result: ExtElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> MulFibExtExample<H> {
+    pub fn new(sequence_length: usize, options: ProofOptions) -> Self {
+        assert!(sequence_length.is_power_of_two(), "sequence length must be a power of 2");
+
+        // compute Fibonacci sequence
+        let now = Instant::now();
+        let result = compute_mulfib_ext_term(sequence_length);
+        debug!(
+            "Computed multiplicative Fibonacci sequence in extension field up to {}th term in {} ms",
+            sequence_length,
+            now.elapsed().as_millis()
+        );
+
+        MulFibExtExample {
+            options,
+            sequence_length,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for MulFibExtExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        let sequence_length = self.sequence_length;
+        debug!(
+            "Generating proof for computing multiplicative Fibonacci sequence in extension field (2 terms per step) up to {}th term\n\
+            ---------------------",
+            sequence_length
+        );
+
+        // create a prover
+        let prover = MulFibExtProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(sequence_length);
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<MulFibExtAir, H, DefaultRandomCoin<H>>(
+            proof,
+            PublicInputs {
+                result: self.result,
+            },
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<MulFibExtAir, H, DefaultRandomCoin<H>>(
+            proof,
+            PublicInputs {
+                result: self.result + ExtElement::ONE,
+            },
+            &acceptable_options,
+        )
+    }
+}
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub result: ExtElement,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        ExtElement::slice_as_base_elements(&[self.result]).to_vec()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the first two terms of the sequence.
+fn get_seeds() -> [ExtElement; 2] {
+    [
+        ExtElement::new(BaseElement::new(1), BaseElement::new(1)),
+        ExtElement::new(BaseElement::new(2), BaseElement::new(1)),
+    ]
+}
+
+/// Returns the term of the sequence which ends up in the first term column of the last row of a
+/// trace for a sequence of length `n` (i.e., the term with index `n - 2`).
+fn compute_mulfib_ext_term(n: usize) -> ExtElement {
+    let [mut t0, mut t1] = get_seeds();
+
+    for _ in 0..(n - 2) {
+        let t2 = t0 * t1;
+        t0 = t1;
+        t1 = t2;
+    }
+
+    t0
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_seeds, BaseElement, DefaultRandomCoin, ElementHasher, ExtElement, FieldElement,
+    MulFibExtAir, PhantomData, ProofOptions, Prover, PublicInputs, TRACE_WIDTH,
+};
+use winterfell::{
+    matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+pub struct MulFibExtProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> MulFibExtProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for computing a multiplicative version of a Fibonacci sequence in
+    /// the quadratic extension field such that each row advances the sequence by 2 terms.
+    ///
+    /// The first column holds the step counter; each of the following two pairs of columns holds
+    /// coordinates of a single term of the sequence.
+    pub fn build_trace(&self, length: usize) -> TraceTable<BaseElement> {
+        assert!(length.is_power_of_two(), "sequence length must be a power of 2");
+
+        let mut trace = TraceTable::new(TRACE_WIDTH, length / 2);
+        let [mut s1, mut s2] = get_seeds();
+        for step in 0..length / 2 {
+            trace.set(0, step, BaseElement::new(step as u128));
+            trace.set_extension(1, step, s1);
+            trace.set_extension(3, step, s2);
+
+            s1 *= s2;
+            s2 *= s1;
+        }
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for MulFibExtProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = MulFibExtAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            result: trace.get_extension::<ExtElement>(1, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
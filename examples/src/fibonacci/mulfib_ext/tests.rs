@@ -0,0 +1,41 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{super::utils::build_proof_options, Blake3_256, ExtElement};
+use winterfell::{math::FieldElement, ConstraintViolation, Prover};
+
+#[test]
+fn mulfib_ext_test_basic_proof_verification() {
+    let fib = Box::new(super::MulFibExtExample::<Blake3_256>::new(16, build_proof_options(true)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn mulfib_ext_test_basic_proof_verification_fail() {
+    let fib = Box::new(super::MulFibExtExample::<Blake3_256>::new(16, build_proof_options(true)));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn mulfib_ext_test_validate_trace() {
+    let prover = super::MulFibExtProver::<Blake3_256>::new(build_proof_options(true));
+    let trace = prover.build_trace(16);
+    assert!(prover.validate_trace::<ExtElement>(trace).unwrap().is_valid());
+
+    // changing a single coordinate of an extension field element should violate the transition
+    // constraint evaluated over the extension field
+    let mut trace = prover.build_trace(16);
+    let value = trace.get_extension::<ExtElement>(3, 2);
+    trace.set_extension(3, 2, value + ExtElement::ONE);
+    let report = prover.validate_trace::<ExtElement>(trace).unwrap();
+    assert!(report.violations().iter().any(|violation| matches!(
+        violation,
+        ConstraintViolation::MainExtensionTransition {
+            constraint: 2,
+            step: 1,
+            ..
+        }
+    )));
+}
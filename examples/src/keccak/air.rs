@@ -0,0 +1,239 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    a, a_prime, a_prime_prime, c, c_prime,
+    keccak::{NUM_ROUNDS, ROTATION_OFFSETS, ROUND_CONSTANTS, ROUND_CONSTANT_BITS, STATE_WIDTH},
+    BaseElement, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, not, EvaluationResult};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of constraints which enforce that bits of A' and C are binary.
+const NUM_BINARY_CONSTRAINTS: usize = STATE_WIDTH * 64 + 5 * 64;
+
+/// Index of the first constraint which enforces correct computation of C'.
+const C_PRIME_CONSTRAINTS: usize = NUM_BINARY_CONSTRAINTS;
+
+/// Index of the first constraint which enforces that C' is the column parity of A'.
+const PARITY_CONSTRAINTS: usize = C_PRIME_CONSTRAINTS + 5 * 64;
+
+/// Index of the first constraint which enforces that A' = theta(A).
+const THETA_CONSTRAINTS: usize = PARITY_CONSTRAINTS + 5 * 64;
+
+/// Index of the first constraint which enforces that A'' = iota(chi(pi(rho(A')))).
+const ROUND_CONSTRAINTS: usize = THETA_CONSTRAINTS + STATE_WIDTH;
+
+/// Index of the first constraint which enforces transition to the next row.
+const TRANSITION_CONSTRAINTS: usize = ROUND_CONSTRAINTS + STATE_WIDTH;
+
+/// Total number of transition constraints.
+const NUM_CONSTRAINTS: usize = TRANSITION_CONSTRAINTS + STATE_WIDTH;
+
+// KECCAK AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub seed: [u64; STATE_WIDTH],
+    pub result: [u64; STATE_WIDTH],
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.seed
+            .iter()
+            .chain(self.result.iter())
+            .map(|&lane| BaseElement::from(lane))
+            .collect()
+    }
+}
+
+pub struct KeccakAir {
+    context: AirContext<BaseElement>,
+    seed: [u64; STATE_WIDTH],
+    result: [u64; STATE_WIDTH],
+}
+
+impl Air for KeccakAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let mut degrees = vec![TransitionConstraintDegree::new(2); NUM_BINARY_CONSTRAINTS];
+        degrees.resize(ROUND_CONSTRAINTS, TransitionConstraintDegree::new(3));
+        // lane (0, 0) is also XORed with the round constant
+        degrees.push(TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]));
+        degrees.resize(TRANSITION_CONSTRAINTS, TransitionConstraintDegree::new(3));
+        degrees.resize(
+            NUM_CONSTRAINTS,
+            TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH]),
+        );
+
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        KeccakAir {
+            context: AirContext::new(trace_info, degrees, 2 * STATE_WIDTH, options),
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into the round flag and bits of the round constant
+        let round_flag = periodic_values[0];
+        let rc_bits = &periodic_values[1..];
+
+        // bits of A' and C must be binary; bits of C' are binary because they are computed
+        // from bits of C below
+        for i in 0..STATE_WIDTH * 64 {
+            result[i] = is_binary(current[a_prime(0, 0, 0) + i]);
+        }
+        for i in 0..5 * 64 {
+            result[STATE_WIDTH * 64 + i] = is_binary(current[c(0, 0) + i]);
+        }
+
+        // C'[x, z] = C[x, z] xor C[x - 1, z] xor C[x + 1, z - 1]
+        for x in 0..5 {
+            for z in 0..64 {
+                let value = xor(
+                    xor(current[c(x, z)], current[c((x + 4) % 5, z)]),
+                    current[c((x + 1) % 5, (z + 63) % 64)],
+                );
+                result[C_PRIME_CONSTRAINTS + x * 64 + z] = are_equal(current[c_prime(x, z)], value);
+            }
+        }
+
+        // the XOR of A'[x, y, z] over all y must be equal to C'[x, z]; this is the case when
+        // the sum of these bits differs from C'[x, z] by 0, 2, or 4
+        for x in 0..5 {
+            for z in 0..64 {
+                let sum = (0..5).fold(E::ZERO, |sum, y| sum + current[a_prime(x, y, z)]);
+                let diff = sum - current[c_prime(x, z)];
+                let two = E::from(2u32);
+                let four = E::from(4u32);
+                result[PARITY_CONSTRAINTS + x * 64 + z] = diff * (diff - two) * (diff - four);
+            }
+        }
+
+        // A[x, y, z] = A'[x, y, z] xor C[x, z] xor C'[x, z]; together with the constraints
+        // above, this implies that C are the column parities of A, and thus, A' = theta(A)
+        for x in 0..5 {
+            for y in 0..5 {
+                let lane = compose_lane(|z| {
+                    xor(xor(current[a_prime(x, y, z)], current[c(x, z)]), current[c_prime(x, z)])
+                });
+                result[THETA_CONSTRAINTS + x + 5 * y] = are_equal(current[a(x, y)], lane);
+            }
+        }
+
+        // A''[x, y, z] = B[x, y, z] xor (not(B[x + 1, y, z]) and B[x + 2, y, z]), where
+        // B = pi(rho(A')); for lane (0, 0), the bits of the round constant are XORed in as well
+        let b = |x: usize, y: usize, z: usize| {
+            let (src_x, src_y) = get_pi_source(x, y);
+            let offset = ROTATION_OFFSETS[src_x][src_y] as usize;
+            current[a_prime(src_x, src_y, (z + 64 - offset) % 64)]
+        };
+        for x in 0..5 {
+            for y in 0..5 {
+                let lane = compose_lane(|z| {
+                    let mut bit = xor(b(x, y, z), not(b((x + 1) % 5, y, z)) * b((x + 2) % 5, y, z));
+                    if x == 0 && y == 0 {
+                        if let Some(i) = ROUND_CONSTANT_BITS.iter().position(|&bit| bit == z) {
+                            bit = xor(bit, rc_bits[i]);
+                        }
+                    }
+                    bit
+                });
+                result[ROUND_CONSTRAINTS + x + 5 * y] =
+                    are_equal(current[a_prime_prime(x, y)], lane);
+            }
+        }
+
+        // during the rounds of the permutation, the next state is the state after the round;
+        // otherwise, the state is copied over to the next row
+        for i in 0..STATE_WIDTH {
+            result[TRANSITION_CONSTRAINTS + i] = are_equal(next[a(0, 0) + i], current[a(0, 0) + i]);
+            result.agg_constraint(
+                TRANSITION_CONSTRAINTS + i,
+                round_flag,
+                are_equal(current[a(0, 0) + i], current[a_prime_prime(0, 0) + i]),
+            );
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // assert starting and ending states of the permutation chain
+        let last_step = self.trace_length() - 1;
+        let mut assertions = Vec::with_capacity(2 * STATE_WIDTH);
+        for (i, &lane) in self.seed.iter().enumerate() {
+            assertions.push(Assertion::single(a(0, 0) + i, 0, BaseElement::from(lane)));
+        }
+        for (i, &lane) in self.result.iter().enumerate() {
+            assertions.push(Assertion::single(a(0, 0) + i, last_step, BaseElement::from(lane)));
+        }
+        assertions
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        // the round flag is set to one on the rows at which rounds of the permutation are executed
+        let mut round_flags = vec![BaseElement::ONE; NUM_ROUNDS];
+        round_flags.resize(CYCLE_LENGTH, BaseElement::ZERO);
+
+        let mut result = vec![round_flags];
+        for bit in ROUND_CONSTANT_BITS {
+            let mut column = ROUND_CONSTANTS
+                .iter()
+                .map(|&rc| BaseElement::from((rc >> bit) & 1))
+                .collect::<Vec<_>>();
+            column.resize(CYCLE_LENGTH, BaseElement::ZERO);
+            result.push(column);
+        }
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the lane (x, y) of A' from which lane (x, y) of B = pi(rho(A')) is computed.
+///
+/// The pi step moves lane (x, y) to (y, 2x + 3y); thus, the source lane is (x', y') such that
+/// y' = x and 2x' + 3y' = y (mod 5).
+fn get_pi_source(x: usize, y: usize) -> (usize, usize) {
+    // 3 is the inverse of 2 modulo 5
+    ((3 * (y + 15 - 3 * x)) % 5, x)
+}
+
+/// Returns a XOR of two binary values.
+fn xor<E: FieldElement>(a: E, b: E) -> E {
+    a + b - (a * b).double()
+}
+
+/// Returns a field element composed from 64 bits provided by the specified function, where bit
+/// 0 is the least significant bit.
+fn compose_lane<E: FieldElement>(bit: impl Fn(usize) -> E) -> E {
+    (0..64).rev().fold(E::ZERO, |lane, z| lane.double() + bit(z))
+}
@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of 64-bit lanes in Keccak-f[1600] state.
+pub const STATE_WIDTH: usize = 25;
+
+/// Number of rounds in a single Keccak-f[1600] permutation.
+pub const NUM_ROUNDS: usize = 24;
+
+/// Round constants added to lane (0, 0) during the iota step.
+pub const ROUND_CONSTANTS: [u64; NUM_ROUNDS] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Positions of bits which can be set in round constants; all other bits of every round constant
+/// are zeros.
+pub const ROUND_CONSTANT_BITS: [usize; 7] = [0, 1, 3, 7, 15, 31, 63];
+
+/// Rotation offsets applied to each lane during the rho step; indexed as `[x][y]`.
+pub const ROTATION_OFFSETS: [[u32; 5]; 5] = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+];
+
+// KECCAK-F[1600] PERMUTATION
+// ================================================================================================
+
+/// Applies Keccak-f[1600] permutation to the provided state; lane (x, y) of the state is stored
+/// at index x + 5 * y.
+pub fn permute(state: &mut [u64; STATE_WIDTH]) {
+    for round_constant in ROUND_CONSTANTS {
+        apply_round(state, round_constant);
+    }
+}
+
+/// Applies a single round of Keccak-f[1600] permutation with the specified round constant to the
+/// provided state.
+pub fn apply_round(state: &mut [u64; STATE_WIDTH], round_constant: u64) {
+    // theta step
+    let d = get_theta_offsets(state);
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane ^= d[i % 5];
+    }
+
+    // rho and pi steps
+    let mut b = [0; STATE_WIDTH];
+    for x in 0..5 {
+        for y in 0..5 {
+            b[y + 5 * ((2 * x + 3 * y) % 5)] = state[x + 5 * y].rotate_left(ROTATION_OFFSETS[x][y]);
+        }
+    }
+
+    // chi step
+    for x in 0..5 {
+        for y in 0..5 {
+            state[x + 5 * y] = b[x + 5 * y] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y]);
+        }
+    }
+
+    // iota step
+    state[0] ^= round_constant;
+}
+
+/// Returns column parities of the provided state.
+pub fn get_column_parities(state: &[u64; STATE_WIDTH]) -> [u64; 5] {
+    let mut c = [0; 5];
+    for (i, lane) in state.iter().enumerate() {
+        c[i % 5] ^= lane;
+    }
+    c
+}
+
+/// Returns values which are XORed into each column of the provided state during the theta step.
+pub fn get_theta_offsets(state: &[u64; STATE_WIDTH]) -> [u64; 5] {
+    let c = get_column_parities(state);
+    let mut d = [0; 5];
+    for (x, d) in d.iter_mut().enumerate() {
+        *d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+    }
+    d
+}
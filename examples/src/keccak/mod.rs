@@ -0,0 +1,216 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+#[allow(clippy::module_inception)]
+pub(crate) mod keccak;
+use keccak::STATE_WIDTH;
+
+mod air;
+use air::{KeccakAir, PublicInputs};
+
+mod prover;
+use prover::KeccakProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of trace rows used by a single permutation: the first 24 rows execute the rounds of
+/// the permutation, and the remaining 8 rows carry the result over to the next permutation.
+const CYCLE_LENGTH: usize = 32;
+
+// TRACE LAYOUT
+// ================================================================================================
+// Every row of the execution trace contains the state of the permutation at the start of a
+// round, as well as the values needed to verify a single round of the permutation:
+//
+// - 25 columns of state lanes A, each lane stored as a single field element.
+// - 1600 columns of bits of lanes A' = theta(A).
+// - 320 columns of bits of column parities C of A.
+// - 320 columns of bits of C' = C xor D, where D are the values XORed into A by theta.
+// - 25 columns of lanes A'' = iota(chi(pi(rho(A')))), i.e., the state after the round.
+
+/// Index of the first column holding state lanes.
+const A_OFFSET: usize = 0;
+
+/// Index of the first column holding bits of A' lanes.
+const A_PRIME_OFFSET: usize = A_OFFSET + STATE_WIDTH;
+
+/// Index of the first column holding bits of column parities.
+const C_OFFSET: usize = A_PRIME_OFFSET + STATE_WIDTH * 64;
+
+/// Index of the first column holding bits of C'.
+const C_PRIME_OFFSET: usize = C_OFFSET + 5 * 64;
+
+/// Index of the first column holding lanes of the state after the round.
+const A_PRIME_PRIME_OFFSET: usize = C_PRIME_OFFSET + 5 * 64;
+
+/// Total number of columns in the execution trace.
+const TRACE_WIDTH: usize = A_PRIME_PRIME_OFFSET + STATE_WIDTH;
+
+/// Returns index of the column holding lane (x, y) of the state.
+const fn a(x: usize, y: usize) -> usize {
+    A_OFFSET + x + 5 * y
+}
+
+/// Returns index of the column holding bit z of lane (x, y) of A'.
+const fn a_prime(x: usize, y: usize, z: usize) -> usize {
+    A_PRIME_OFFSET + (x + 5 * y) * 64 + z
+}
+
+/// Returns index of the column holding bit z of the parity of column x.
+const fn c(x: usize, z: usize) -> usize {
+    C_OFFSET + x * 64 + z
+}
+
+/// Returns index of the column holding bit z of C'[x].
+const fn c_prime(x: usize, z: usize) -> usize {
+    C_PRIME_OFFSET + x * 64 + z
+}
+
+/// Returns index of the column holding lane (x, y) of the state after the round.
+const fn a_prime_prime(x: usize, y: usize) -> usize {
+    A_PRIME_PRIME_OFFSET + x + 5 * y
+}
+
+// KECCAK PERMUTATION CHAIN EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_permutations: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(42, 4);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(KeccakExample::<Blake3_192>::new(num_permutations, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(KeccakExample::<Blake3_256>::new(num_permutations, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(KeccakExample::<Sha3_256>::new(num_permutations, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct KeccakExample<H: ElementHasher> {
+    options: ProofOptions,
+    num_permutations: usize,
+    seed: [u64; STATE_WIDTH],
+    result: [u64; STATE_WIDTH],
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> KeccakExample<H> {
+    pub fn new(num_permutations: usize, options: ProofOptions) -> Self {
+        assert!(
+            num_permutations.is_power_of_two(),
+            "number of permutations must be a power of 2"
+        );
+        let mut seed = [0; STATE_WIDTH];
+        for (i, lane) in seed.iter_mut().enumerate() {
+            *lane = (i as u64 + 1).wrapping_mul(0x9E3779B97F4A7C15);
+        }
+
+        // apply the permutation to the seed the specified number of times
+        let now = Instant::now();
+        let mut result = seed;
+        for _ in 0..num_permutations {
+            keccak::permute(&mut result);
+        }
+        debug!(
+            "Computed a chain of {} Keccak-f[1600] permutations in {} ms",
+            num_permutations,
+            now.elapsed().as_millis(),
+        );
+
+        KeccakExample {
+            options,
+            num_permutations,
+            seed,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for KeccakExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for computing a chain of {} Keccak-f[1600] permutations\n\
+            ---------------------",
+            self.num_permutations
+        );
+
+        // create a prover
+        let prover = KeccakProver::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.seed, self.num_permutations);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            seed: self.seed,
+            result: self.result,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<KeccakAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut result = self.result;
+        result[STATE_WIDTH - 1] ^= 1;
+        let pub_inputs = PublicInputs {
+            seed: self.seed,
+            result,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<KeccakAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    a, a_prime, a_prime_prime, c, c_prime,
+    keccak::{self, NUM_ROUNDS, ROUND_CONSTANTS, STATE_WIDTH},
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, KeccakAir, PhantomData,
+    ProofOptions, Prover, PublicInputs, CYCLE_LENGTH, TRACE_WIDTH,
+};
+use winterfell::{
+    math::StarkField, matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// KECCAK PROVER
+// ================================================================================================
+
+pub struct KeccakProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> KeccakProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn build_trace(
+        &self,
+        seed: [u64; STATE_WIDTH],
+        num_permutations: usize,
+    ) -> TraceTable<BaseElement> {
+        // allocate memory to hold the trace table
+        let trace_length = num_permutations * CYCLE_LENGTH;
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+        trace.fill(
+            |state| {
+                // initialize first state of the computation
+                for (i, &lane) in seed.iter().enumerate() {
+                    state[a(0, 0) + i] = BaseElement::from(lane);
+                }
+                fill_round(state, 0);
+            },
+            |step, state| {
+                // for the first 24 steps in every cycle, the state after the round becomes the
+                // next state; for the remaining 8 steps, the state is carried over unchanged
+                if (step % CYCLE_LENGTH) < NUM_ROUNDS {
+                    for i in 0..STATE_WIDTH {
+                        state[a(0, 0) + i] = state[a_prime_prime(0, 0) + i];
+                    }
+                }
+                fill_round(state, (step + 1) % CYCLE_LENGTH);
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for KeccakProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = KeccakAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        let mut seed = [0; STATE_WIDTH];
+        let mut result = [0; STATE_WIDTH];
+        for i in 0..STATE_WIDTH {
+            seed[i] = trace.get(a(0, 0) + i, 0).as_int() as u64;
+            result[i] = trace.get(a(0, 0) + i, last_step).as_int() as u64;
+        }
+        PublicInputs { seed, result }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Fills in the columns of the provided row which are needed to verify the specified round of the
+/// permutation applied to the state in the row; rounds past the last round of the permutation are
+/// executed with a zero round constant.
+fn fill_round(row: &mut [BaseElement], round: usize) {
+    let mut state = [0; STATE_WIDTH];
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane = row[a(0, 0) + i].as_int() as u64;
+    }
+
+    // compute values of theta step
+    let parities = keccak::get_column_parities(&state);
+    let offsets = keccak::get_theta_offsets(&state);
+    for x in 0..5 {
+        for z in 0..64 {
+            row[c(x, z)] = get_bit(parities[x], z);
+            row[c_prime(x, z)] = get_bit(parities[x] ^ offsets[x], z);
+        }
+        for y in 0..5 {
+            let lane = state[x + 5 * y] ^ offsets[x];
+            for z in 0..64 {
+                row[a_prime(x, y, z)] = get_bit(lane, z);
+            }
+        }
+    }
+
+    // compute the state after the round
+    let round_constant = ROUND_CONSTANTS.get(round).copied().unwrap_or(0);
+    keccak::apply_round(&mut state, round_constant);
+    for (i, &lane) in state.iter().enumerate() {
+        row[a_prime_prime(0, 0) + i] = BaseElement::from(lane);
+    }
+}
+
+fn get_bit(value: u64, index: usize) -> BaseElement {
+    BaseElement::from((value >> index) & 1)
+}
@@ -0,0 +1,44 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{keccak, Blake3_256, STATE_WIDTH};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn keccak_permutation_test_vector() {
+    // Keccak-f[1600] applied to the all-zero state
+    let mut state = [0; STATE_WIDTH];
+    keccak::permute(&mut state);
+    assert_eq!(0xF1258F7940E1DDE7, state[0]);
+    assert_eq!(0x84D5CCF933C0478A, state[1]);
+    assert_eq!(0xD598261EA65AA9EE, state[2]);
+}
+
+#[test]
+fn keccak_test_basic_proof_verification() {
+    let keccak_eg = Box::new(super::KeccakExample::<Blake3_256>::new(2, build_options(false)));
+    crate::tests::test_basic_proof_verification(keccak_eg);
+}
+
+#[test]
+fn keccak_test_basic_proof_verification_extension() {
+    let keccak_eg = Box::new(super::KeccakExample::<Blake3_256>::new(2, build_options(true)));
+    crate::tests::test_basic_proof_verification(keccak_eg);
+}
+
+#[test]
+fn keccak_test_basic_proof_verification_fail() {
+    let keccak_eg = Box::new(super::KeccakExample::<Blake3_256>::new(2, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(keccak_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -10,16 +10,27 @@ use winterfell::{
     FieldExtension, ProofOptions, StarkProof, VerifierError,
 };
 
+pub mod blake3;
 pub mod fibonacci;
+pub mod keccak;
 #[cfg(feature = "std")]
 pub mod lamport;
+pub mod memory;
 #[cfg(feature = "std")]
 pub mod merkle;
+pub mod nn;
+pub mod pedersen;
 pub mod rescue;
 #[cfg(feature = "std")]
 pub mod rescue_raps;
+pub mod rollup;
+pub mod schnorr;
+pub mod sha256;
+pub mod trace_queries;
+pub mod u256;
 pub mod utils;
 pub mod vdf;
+pub mod vm;
 
 #[cfg(test)]
 mod tests;
@@ -69,10 +80,45 @@ pub struct ExampleOptions {
     /// Folding factor for FRI protocol
     #[structopt(short = "f", long = "folding", default_value = "8")]
     folding_factor: usize,
+
+    /// Proof options in a compact format (e.g., "q=54,b=8,g=16,ext=quad,fri=4/255,hash=blake3_256");
+    /// when specified, all other proof options are ignored
+    #[structopt(short = "o", long = "options")]
+    options: Option<String>,
 }
 
 impl ExampleOptions {
+    /// Returns options for running the specified example with proof options and the hash
+    /// function given in the compact format (e.g., "q=54,b=8,g=16,ext=quad,fri=4/255,hash=blake3_256").
+    pub fn from_compact(example: ExampleType, options: &str) -> Self {
+        Self {
+            example,
+            hash_fn: "blake3_256".to_string(),
+            num_queries: None,
+            blowup_factor: None,
+            grinding_factor: 16,
+            field_extension: 1,
+            folding_factor: 8,
+            options: Some(options.to_string()),
+        }
+    }
+
     pub fn to_proof_options(&self, q: usize, b: usize) -> (ProofOptions, HashFunction) {
+        let hash_fn = parse_hash_fn(self.hash_fn_name());
+        if let Some(options) = &self.options {
+            // the hash function is not a part of proof options, and so it is removed from the
+            // string before parsing the options
+            let options = options
+                .split(',')
+                .filter(|entry| !entry.trim().starts_with("hash="))
+                .collect::<Vec<_>>()
+                .join(",");
+            match options.parse() {
+                Ok(options) => return (options, hash_fn),
+                Err(err) => panic!("'{options}' is not a valid proof options string: {err}"),
+            }
+        }
+
         let num_queries = self.num_queries.unwrap_or(q);
         let blowup_factor = self.blowup_factor.unwrap_or(b);
         let field_extension = match self.field_extension {
@@ -82,16 +128,6 @@ impl ExampleOptions {
             val => panic!("'{val}' is not a valid field extension option"),
         };
 
-        let hash_fn = match self.hash_fn.as_str() {
-            "blake3_192" => HashFunction::Blake3_192,
-            "blake3_256" => HashFunction::Blake3_256,
-            "sha3_256" => HashFunction::Sha3_256,
-            "rp64_256" => HashFunction::Rp64_256,
-            "rp_jive64_256" => HashFunction::RpJive64_256,
-            "griffin_jive64_256" => HashFunction::GriffinJive64_256,
-            val => panic!("'{val}' is not a valid hash function option"),
-        };
-
         (
             ProofOptions::new(
                 num_queries,
@@ -107,7 +143,7 @@ impl ExampleOptions {
 
     /// Returns security level of the input proof in bits.
     pub fn get_proof_security_level(&self, proof: &StarkProof, conjectured: bool) -> usize {
-        let security_level = match self.hash_fn.as_str() {
+        let security_level = match self.hash_fn_name() {
             "blake3_192" => proof.security_level::<Blake3_192>(conjectured),
             "blake3_256" => proof.security_level::<Blake3_256>(conjectured),
             "sha3_256" => proof.security_level::<Sha3_256>(conjectured),
@@ -119,9 +155,89 @@ impl ExampleOptions {
 
         security_level as usize
     }
+
+    /// Returns the name of the hash function; a hash function specified in the proof options
+    /// string takes precedence over the one specified via the `hash_fn` option.
+    fn hash_fn_name(&self) -> &str {
+        self.options
+            .iter()
+            .flat_map(|options| options.split(','))
+            .find_map(|entry| entry.trim().strip_prefix("hash="))
+            .map(str::trim)
+            .unwrap_or(self.hash_fn.as_str())
+    }
 }
 
-#[derive(StructOpt, Debug)]
+/// Returns the example specified by the provided options.
+///
+/// # Errors
+/// Returns an error if the example cannot be instantiated with the specified options (e.g., if
+/// the example does not support the specified hash function).
+pub fn get_example(options: &ExampleOptions) -> Result<Box<dyn Example>, String> {
+    match options.example {
+        ExampleType::Fib { sequence_length } => {
+            fibonacci::fib2::get_example(options, sequence_length)
+        }
+        ExampleType::Fib8 { sequence_length } => {
+            fibonacci::fib8::get_example(options, sequence_length)
+        }
+        ExampleType::Mulfib { sequence_length } => {
+            fibonacci::mulfib2::get_example(options, sequence_length)
+        }
+        ExampleType::Mulfib8 { sequence_length } => {
+            fibonacci::mulfib8::get_example(options, sequence_length)
+        }
+        ExampleType::FibSmall { sequence_length } => {
+            fibonacci::fib_small::get_example(options, sequence_length)
+        }
+        ExampleType::MulfibExt { sequence_length } => {
+            fibonacci::mulfib_ext::get_example(options, sequence_length)
+        }
+        ExampleType::Vdf { num_steps } => vdf::regular::get_example(options, num_steps),
+        ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(options, num_steps),
+        ExampleType::Rescue { chain_length } => rescue::get_example(options, chain_length),
+        ExampleType::Keccak { num_permutations } => keccak::get_example(options, num_permutations),
+        #[cfg(feature = "std")]
+        ExampleType::RescueRaps { chain_length } => rescue_raps::get_example(options, chain_length),
+        ExampleType::Schnorr { num_signatures } => schnorr::get_example(options, num_signatures),
+        ExampleType::Pedersen { num_hashes } => pedersen::get_example(options, num_hashes),
+        ExampleType::Sha256 { num_blocks } => sha256::get_example(options, num_blocks),
+        ExampleType::Blake3 { chain_length } => blake3::get_example(options, chain_length),
+        ExampleType::U256 { num_products } => u256::get_example(options, num_products),
+        ExampleType::Nn { num_neurons } => nn::get_example(options, num_neurons),
+        ExampleType::Vm { num_iterations } => vm::get_example(options, num_iterations),
+        ExampleType::Memory { num_accesses } => memory::get_example(options, num_accesses),
+        ExampleType::TraceQueries { sequence_length } => {
+            trace_queries::get_example(options, sequence_length)
+        }
+        ExampleType::Rollup { num_transfers } => rollup::get_example(options, num_transfers),
+        #[cfg(feature = "std")]
+        ExampleType::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
+        #[cfg(feature = "std")]
+        ExampleType::LamportA { num_signatures } => {
+            lamport::aggregate::get_example(options, num_signatures)
+        }
+        #[cfg(feature = "std")]
+        ExampleType::LamportT { num_signers } => {
+            lamport::threshold::get_example(options, num_signers)
+        }
+    }
+}
+
+/// Returns the hash function with the specified name.
+fn parse_hash_fn(name: &str) -> HashFunction {
+    match name {
+        "blake3_192" => HashFunction::Blake3_192,
+        "blake3_256" => HashFunction::Blake3_256,
+        "sha3_256" => HashFunction::Sha3_256,
+        "rp64_256" => HashFunction::Rp64_256,
+        "rp_jive64_256" => HashFunction::RpJive64_256,
+        "griffin_jive64_256" => HashFunction::GriffinJive64_256,
+        val => panic!("'{val}' is not a valid hash function option"),
+    }
+}
+
+#[derive(StructOpt, Debug, Clone)]
 //#[structopt(about = "available examples")]
 pub enum ExampleType {
     /// Compute a Fibonacci sequence using trace table with 2 registers
@@ -154,6 +270,13 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "65536")]
         sequence_length: usize,
     },
+    /// Compute a multiplicative Fibonacci sequence in the quadratic extension field using trace
+    /// columns which hold extension field elements; requires quadratic field extension
+    MulfibExt {
+        /// Length of Fibonacci sequence; must be a power of two
+        #[structopt(short = "n", default_value = "1048576")]
+        sequence_length: usize,
+    },
     /// Execute a simple VDF function
     Vdf {
         /// Number of steps in the VDF function; must be a power of two
@@ -172,6 +295,12 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1024")]
         chain_length: usize,
     },
+    /// Apply Keccak-f[1600] permutation to a state multiple times
+    Keccak {
+        /// Number of permutations to apply; must be a power of two
+        #[structopt(short = "n", default_value = "64")]
+        num_permutations: usize,
+    },
     /// Compute two hash chains absorbing sequences that are a permutation of each other
     #[cfg(feature = "std")]
     RescueRaps {
@@ -179,6 +308,67 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1024")]
         chain_length: usize,
     },
+    /// Verify a batch of Schnorr signatures over an embedded elliptic curve
+    Schnorr {
+        /// Number of signatures to verify; must be a power of two
+        #[structopt(short = "n", default_value = "8")]
+        num_signatures: usize,
+    },
+    /// Compute Pedersen hashes of messages over an embedded elliptic curve
+    Pedersen {
+        /// Number of hashes to compute; must be a power of two
+        #[structopt(short = "n", default_value = "64")]
+        num_hashes: usize,
+    },
+    /// Apply SHA-256 compression function to a sequence of message blocks
+    Sha256 {
+        /// Number of message blocks; must be one less than a power of two
+        #[structopt(short = "n", default_value = "63")]
+        num_blocks: usize,
+    },
+    /// Compute a hash chain using BLAKE3 compression function
+    Blake3 {
+        /// Length of the hash chain; must be a power of two
+        #[structopt(short = "n", default_value = "128")]
+        chain_length: usize,
+    },
+    /// Compute a sum of products of 256-bit integers
+    U256 {
+        /// Number of products; must be one less than a power of two
+        #[structopt(short = "n", default_value = "127")]
+        num_products: usize,
+    },
+    /// Evaluate a quantized neural network layer with ReLU activation
+    Nn {
+        /// Number of neurons in the layer; must be one less than a power of two and at least 15
+        #[structopt(short = "n", default_value = "63")]
+        num_neurons: usize,
+    },
+    /// Execute a program computing Fibonacci numbers on a minimal register VM
+    Vm {
+        /// Number of iterations of the program loop
+        #[structopt(short = "n", default_value = "1023")]
+        num_iterations: usize,
+    },
+    /// Check consistency of a log of reads and writes to memory
+    Memory {
+        /// Number of memory accesses; must be one less than a power of two and at least 31
+        #[structopt(short = "n", default_value = "1023")]
+        num_accesses: usize,
+    },
+    /// Authenticate trace queries of a Fibonacci proof inside of another proof
+    TraceQueries {
+        /// Length of the Fibonacci sequence of the inner proof; must be a power of two and at
+        /// least 64
+        #[structopt(short = "n", default_value = "4096")]
+        sequence_length: usize,
+    },
+    /// Apply a batch of balance transfers to a sparse Merkle tree of accounts
+    Rollup {
+        /// Number of transfers in the batch; must be a power of two
+        #[structopt(short = "n", default_value = "64")]
+        num_transfers: usize,
+    },
     /// Compute a root of a Merkle path using Rescue hash function
     #[cfg(feature = "std")]
     Merkle {
@@ -0,0 +1,185 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compress, BaseElement, ExtensionOf, FieldElement, ProofOptions, ADDR, AUX_TRACE_WIDTH, CLK,
+    IS_WRITE, NUM_CELLS, PERMUTATION, RANGE_CHECK, RANGE_MULTIPLICITY, READ_SUM, SORTED_ADDR,
+    SORTED_CLK, SORTED_IS_WRITE, SORTED_VALUE, TRACE_WIDTH, VALUE,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// MEMORY AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub read_sum: BaseElement,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.read_sum]
+    }
+}
+
+pub struct MemoryAir {
+    context: AirContext<BaseElement>,
+    read_sum: BaseElement,
+}
+
+impl Air for MemoryAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let main_degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::with_cycles(2, vec![trace_info.length()]),
+        ];
+        let aux_degrees =
+            vec![TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(4)];
+        assert_eq!(TRACE_WIDTH + AUX_TRACE_WIDTH, trace_info.width());
+
+        MemoryAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                5,
+                4,
+                options,
+            ),
+            read_sum: pub_inputs.read_sum,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // the clock cycle of the original log is incremented by one on every row
+        result[0] = are_equal(next[CLK], current[CLK] + E::ONE);
+
+        // the type of an access in the original log must be binary; this also makes the types of
+        // the accesses in the sorted copy binary, as the copy is a permutation of the log
+        result[1] = is_binary(current[IS_WRITE]);
+
+        // the values returned by reads of the original log are accumulated in the read sum
+        let read_value = not(current[IS_WRITE]) * current[VALUE];
+        result[2] = are_equal(next[READ_SUM], current[READ_SUM] + read_value);
+
+        // the address of the sorted copy either stays the same or is incremented by one
+        let addr_delta = next[SORTED_ADDR] - current[SORTED_ADDR];
+        result[3] = is_binary(addr_delta);
+
+        // a read must return the value of the previous access to the same cell, or zero if this
+        // is the first access to the cell
+        let is_read = not(next[SORTED_IS_WRITE]);
+        result[4] =
+            not(addr_delta) * is_read * are_equal(next[SORTED_VALUE], current[SORTED_VALUE]);
+        result[5] = addr_delta * is_read * next[SORTED_VALUE];
+
+        // the access on the first row of the sorted copy is the first access to its cell, and
+        // thus, it must return zero if it is a read
+        result[6] = periodic_values[0] * not(current[SORTED_IS_WRITE]) * current[SORTED_VALUE];
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let current = main_frame.current();
+        let next = main_frame.next();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        let random_elements = aux_rand_elements.get_segment_elements(0);
+        let (alpha, beta) = (random_elements[0], random_elements[1]);
+        let get = |row: &[F], column: usize| -> E { row[column].into() };
+
+        // permutation check: the running product is multiplied by the access of the original log
+        // and divided by the access of the sorted copy on the current row
+        let access = [ADDR, CLK, VALUE, IS_WRITE].map(|c| get(current, c));
+        let sorted =
+            [SORTED_ADDR, SORTED_CLK, SORTED_VALUE, SORTED_IS_WRITE].map(|c| get(current, c));
+        result[PERMUTATION] = are_equal(
+            aux_next[PERMUTATION] * (alpha - compress(beta, &sorted)),
+            aux_current[PERMUTATION] * (alpha - compress(beta, &access)),
+        );
+
+        // range check: the change of the running sum must be equal to 1 / (α - δ) - m / (α -
+        // clk), where δ is the difference between the clock cycles of the sorted copy
+        // decremented by one when the cell stays the same, and zero otherwise
+        let addr_delta = get(next, SORTED_ADDR) - get(current, SORTED_ADDR);
+        let clk_delta = get(next, SORTED_CLK) - get(current, SORTED_CLK);
+        let lookup = alpha - not(addr_delta) * (clk_delta - E::ONE);
+        let entry = alpha - get(current, CLK);
+        result[RANGE_CHECK] = are_equal(
+            (aux_next[RANGE_CHECK] - aux_current[RANGE_CHECK]) * lookup * entry,
+            entry - get(current, RANGE_MULTIPLICITY) * lookup,
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the original log starts at clock cycle zero with a zero read sum, and ends with the
+        // expected read sum; the sorted copy starts at the first cell and ends at the last one
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(CLK, 0, BaseElement::ZERO),
+            Assertion::single(READ_SUM, 0, BaseElement::ZERO),
+            Assertion::single(READ_SUM, last_step, self.read_sum),
+            Assertion::single(SORTED_ADDR, 0, BaseElement::ZERO),
+            Assertion::single(SORTED_ADDR, last_step, BaseElement::from(NUM_CELLS as u64 - 1)),
+        ]
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        // the running product of the permutation check must start and end at one, and the
+        // running sum of the range check must start and end at zero
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(PERMUTATION, 0, E::ONE),
+            Assertion::single(PERMUTATION, last_step, E::ONE),
+            Assertion::single(RANGE_CHECK, 0, E::ZERO),
+            Assertion::single(RANGE_CHECK, last_step, E::ZERO),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut first_row_flag = vec![BaseElement::ZERO; self.trace_length()];
+        first_row_flag[0] = BaseElement::ONE;
+        vec![first_row_flag]
+    }
+}
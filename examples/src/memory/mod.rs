@@ -0,0 +1,268 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod trace;
+use trace::{MemoryAuxBuilder, MemoryTraceTable};
+
+mod air;
+use air::{MemoryAir, PublicInputs};
+
+mod prover;
+use prover::MemoryProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of memory cells; addresses of the cells are 0, 1, ..., NUM_CELLS - 1.
+const NUM_CELLS: usize = 16;
+
+// TRACE LAYOUT
+// ================================================================================================
+// The execution trace holds a log of memory accesses twice: once in the order in which the
+// accesses are made (one access per row, with the clock cycle of the access equal to the index of
+// the row), and once sorted by address and then by clock cycle. The two copies are placed side by
+// side, and the argument proceeds as follows:
+//
+// - The sorted copy must be a permutation of the original log; this is enforced using a multiset
+//   check: the running product of the auxiliary column multiplies by (α - (addr + β * clk + β^2 *
+//   value + β^3 * is_write)) for every access of the original log, and divides by the same
+//   expression for every access of the sorted copy.
+// - In the sorted copy, the address either stays the same or is incremented by one from row to
+//   row. Thus, all cells are accessed at least once, and the first access to a cell is the one
+//   following a change of address (or the one on the first row).
+// - For consecutive accesses to the same cell, the clock cycle must increase; this is enforced by
+//   range-checking the difference between the clock cycles decremented by one, which is done by
+//   looking it up in the table of clock cycles of the original log using the logarithmic
+//   derivative argument. Multiplicities of all table entries are supplied by the prover.
+// - Once the sorted copy is known to be sorted, a read must return the value of the previous
+//   access to the same cell, or zero if it is the first access to the cell.
+//
+// The transition from the last row of the trace is not enforced, and thus, the last row does not
+// hold an access; it is filled in by the prover so that the constraints are satisfied. To give
+// the log a public meaning, the sum of the values returned by all reads is accumulated across the
+// original log.
+
+/// Clock cycle of the access in the original log.
+const CLK: usize = 0;
+
+/// Address, value, and type of the access in the original log.
+const ADDR: usize = 1;
+const VALUE: usize = 2;
+const IS_WRITE: usize = 3;
+
+/// Sum of the values returned by all reads in the original log preceding the current row.
+const READ_SUM: usize = 4;
+
+/// Address, clock cycle, value, and type of the access in the sorted copy of the log.
+const SORTED_ADDR: usize = 5;
+const SORTED_CLK: usize = 6;
+const SORTED_VALUE: usize = 7;
+const SORTED_IS_WRITE: usize = 8;
+
+/// Number of times the clock cycle of the current row was looked up by the range check.
+const RANGE_MULTIPLICITY: usize = 9;
+
+/// Total number of columns in the main segment of the execution trace.
+const TRACE_WIDTH: usize = RANGE_MULTIPLICITY + 1;
+
+/// Columns of the auxiliary trace segment.
+const PERMUTATION: usize = 0;
+const RANGE_CHECK: usize = 1;
+
+/// Total number of columns in the auxiliary segment of the execution trace.
+const AUX_TRACE_WIDTH: usize = 2;
+
+// MEMORY EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_accesses: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(MemoryExample::<Blake3_192>::new(num_accesses, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(MemoryExample::<Blake3_256>::new(num_accesses, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(MemoryExample::<Sha3_256>::new(num_accesses, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct MemoryExample<H: ElementHasher> {
+    options: ProofOptions,
+    accesses: Vec<MemoryAccess>,
+    read_sum: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> MemoryExample<H> {
+    pub fn new(num_accesses: usize, options: ProofOptions) -> Self {
+        assert!(
+            (num_accesses + 1).is_power_of_two(),
+            "number of accesses must be one less than a power of 2"
+        );
+        assert!(
+            num_accesses >= 2 * NUM_CELLS - 1,
+            "number of accesses must be at least {}",
+            2 * NUM_CELLS - 1
+        );
+
+        // generate the log of memory accesses
+        let now = Instant::now();
+        let accesses = generate_accesses(num_accesses);
+        debug!(
+            "Generated a log of {} memory accesses in {} ms",
+            num_accesses,
+            now.elapsed().as_millis()
+        );
+
+        MemoryExample {
+            options,
+            read_sum: get_read_sum(&accesses),
+            accesses,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for MemoryExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for checking consistency of {} memory accesses\n\
+            ---------------------",
+            self.accesses.len()
+        );
+
+        // create a prover
+        let prover = MemoryProver::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.accesses);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.main_trace_width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            read_sum: self.read_sum,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<MemoryAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            read_sum: self.read_sum + BaseElement::ONE,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<MemoryAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// MEMORY ACCESSES
+// ================================================================================================
+
+/// A single access to the memory.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryAccess {
+    pub addr: usize,
+    pub value: BaseElement,
+    pub is_write: bool,
+}
+
+/// Returns a pseudo-random log of memory accesses of the specified length.
+///
+/// The first [NUM_CELLS] accesses touch every cell in order, so that every cell is accessed at
+/// least once; all other accesses are made to random cells. Every access is a write with
+/// probability 1/2, and every read returns the value of the previous write to the same cell, or
+/// zero if the cell has not been written yet.
+fn generate_accesses(num_accesses: usize) -> Vec<MemoryAccess> {
+    let mut memory = [BaseElement::ZERO; NUM_CELLS];
+    let mut accesses = Vec::with_capacity(num_accesses);
+    for i in 0..num_accesses {
+        let seed = blake3::hash(&(i as u64).to_le_bytes());
+        let seed = u128::from_le_bytes(seed.as_bytes()[..16].try_into().unwrap());
+        let addr = if i < NUM_CELLS { i } else { seed as usize % NUM_CELLS };
+        let is_write = (seed >> 32) & 1 == 1;
+        if is_write {
+            memory[addr] = BaseElement::new(seed >> 64);
+        }
+        accesses.push(MemoryAccess {
+            addr,
+            value: memory[addr],
+            is_write,
+        });
+    }
+    accesses
+}
+
+/// Returns the sum of the values returned by all reads in the specified log.
+fn get_read_sum(accesses: &[MemoryAccess]) -> BaseElement {
+    accesses
+        .iter()
+        .filter(|access| !access.is_write)
+        .fold(BaseElement::ZERO, |sum, access| sum + access.value)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a random linear combination of the specified values, where the i-th value is
+/// multiplied by the i-th power of β.
+fn compress<E: FieldElement>(beta: E, values: &[E]) -> E {
+    let mut power = E::ONE;
+    let mut result = E::ZERO;
+    for &value in values {
+        result += power * value;
+        power *= beta;
+    }
+    result
+}
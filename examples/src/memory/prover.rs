@@ -0,0 +1,138 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, MemoryAccess, MemoryAir,
+    MemoryAuxBuilder, MemoryTraceTable, PhantomData, ProofOptions, Prover, PublicInputs, ADDR, CLK,
+    IS_WRITE, RANGE_MULTIPLICITY, READ_SUM, SORTED_ADDR, SORTED_CLK, SORTED_IS_WRITE, SORTED_VALUE,
+    TRACE_WIDTH, VALUE,
+};
+use winterfell::{
+    math::StarkField, matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable, TraceWithAux,
+};
+
+// MEMORY PROVER
+// ================================================================================================
+
+pub struct MemoryProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> MemoryProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for the specified log of memory accesses; the trace has one
+    /// more row than the log.
+    ///
+    /// # Panics
+    /// Panics if the number of accesses is not one less than a power of two.
+    pub fn build_trace(&self, accesses: &[MemoryAccess]) -> MemoryTraceTable {
+        let trace_length = accesses.len() + 1;
+        assert!(
+            trace_length.is_power_of_two(),
+            "number of accesses must be one less than a power of 2"
+        );
+        let mut columns = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+
+        // fill in the original log of accesses; the last row holds only the clock cycle and the
+        // final read sum
+        for (clk, access) in accesses.iter().enumerate() {
+            columns[CLK][clk] = BaseElement::from(clk as u64);
+            columns[ADDR][clk] = BaseElement::from(access.addr as u64);
+            columns[VALUE][clk] = access.value;
+            columns[IS_WRITE][clk] = BaseElement::from(access.is_write as u8);
+
+            let read_value = if access.is_write {
+                BaseElement::ZERO
+            } else {
+                access.value
+            };
+            columns[READ_SUM][clk + 1] = columns[READ_SUM][clk] + read_value;
+        }
+        columns[CLK][trace_length - 1] = BaseElement::from(trace_length as u64 - 1);
+
+        // fill in the copy of the log sorted by address and then by clock cycle; the last row
+        // repeats the last access as a write one clock cycle later, so that the constraints are
+        // satisfied on the transition to it
+        let mut sorted = (0..accesses.len()).collect::<Vec<_>>();
+        sorted.sort_by_key(|&clk| (accesses[clk].addr, clk));
+        sorted.push(sorted[sorted.len() - 1]);
+        for (row, &clk) in sorted.iter().enumerate() {
+            let access = accesses[clk];
+            let is_padding = row == trace_length - 1;
+            columns[SORTED_ADDR][row] = BaseElement::from(access.addr as u64);
+            columns[SORTED_CLK][row] = BaseElement::from((clk + is_padding as usize) as u64);
+            columns[SORTED_VALUE][row] = access.value;
+            columns[SORTED_IS_WRITE][row] =
+                BaseElement::from((access.is_write || is_padding) as u8);
+        }
+
+        // count the differences between the clock cycles of consecutive accesses to the same
+        // cell in the multiplicities of the corresponding clock cycles; a change of the cell
+        // counts as a lookup of zero
+        for row in 0..trace_length - 1 {
+            let delta = if columns[SORTED_ADDR][row] == columns[SORTED_ADDR][row + 1] {
+                let clk_delta = columns[SORTED_CLK][row + 1] - columns[SORTED_CLK][row];
+                clk_delta.as_int() as usize - 1
+            } else {
+                0
+            };
+            columns[RANGE_MULTIPLICITY][delta] += BaseElement::ONE;
+        }
+
+        TraceWithAux::new(TraceTable::init(columns), MemoryAuxBuilder)
+    }
+}
+
+impl<H: ElementHasher> Prover for MemoryProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = MemoryAir;
+    type Trace = MemoryTraceTable;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            read_sum: trace.main_segment().get(READ_SUM, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
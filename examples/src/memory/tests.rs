@@ -0,0 +1,76 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    generate_accesses, BaseElement, Blake3_256, MemoryAir, MemoryProver, NUM_CELLS, SORTED_VALUE,
+};
+use winterfell::{
+    math::{fields::QuadExtension, FieldElement},
+    Air, FieldExtension, ProofOptions, Prover, Trace, TraceWithAux,
+};
+
+#[test]
+fn memory_generate_accesses() {
+    let accesses = generate_accesses(63);
+    assert_eq!(63, accesses.len());
+
+    // every cell is accessed at least once, and every read returns the value of the previous
+    // write to the same cell, or zero if there was none
+    for addr in 0..NUM_CELLS {
+        let mut value = FieldElement::ZERO;
+        for access in accesses.iter().filter(|access| access.addr == addr) {
+            if access.is_write {
+                value = access.value;
+            }
+            assert_eq!(value, access.value);
+        }
+    }
+}
+
+#[test]
+fn memory_test_basic_proof_verification() {
+    let memory_eg = Box::new(super::MemoryExample::<Blake3_256>::new(63, build_options(false)));
+    crate::tests::test_basic_proof_verification(memory_eg);
+}
+
+#[test]
+fn memory_test_basic_proof_verification_extension() {
+    let memory_eg = Box::new(super::MemoryExample::<Blake3_256>::new(63, build_options(true)));
+    crate::tests::test_basic_proof_verification(memory_eg);
+}
+
+#[test]
+fn memory_test_basic_proof_verification_fail() {
+    let memory_eg = Box::new(super::MemoryExample::<Blake3_256>::new(63, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(memory_eg);
+}
+
+#[test]
+fn memory_test_check() {
+    let prover = MemoryProver::<Blake3_256>::new(build_options(true));
+    let accesses = generate_accesses(63);
+    let trace = prover.build_trace(&accesses);
+    let air = MemoryAir::new(trace.get_info(), prover.get_pub_inputs(&trace), build_options(true));
+    let report = prover.check::<QuadExtension<BaseElement>>(trace, &air).unwrap();
+    assert!(report.is_valid(), "{report}");
+
+    // changing a value in the sorted copy of the log breaks the permutation check
+    let trace = prover.build_trace(&accesses);
+    let (mut main_trace, aux_builder) = trace.into_parts();
+    let value = main_trace.get(SORTED_VALUE, 10);
+    main_trace.set(SORTED_VALUE, 10, value + BaseElement::ONE);
+    let trace = TraceWithAux::new(main_trace, aux_builder);
+    let report = prover.check::<QuadExtension<BaseElement>>(trace, &air).unwrap();
+    assert!(!report.is_valid());
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -0,0 +1,80 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compress, BaseElement, FieldElement, ADDR, AUX_TRACE_WIDTH, CLK, IS_WRITE, PERMUTATION,
+    RANGE_CHECK, RANGE_MULTIPLICITY, SORTED_ADDR, SORTED_CLK, SORTED_IS_WRITE, SORTED_VALUE, VALUE,
+};
+use winterfell::{matrix::ColMatrix, AuxTraceBuilder, MainTraceRows, TraceTable, TraceWithAux};
+
+// MEMORY TRACE TABLE
+// ================================================================================================
+
+/// An execution trace of a log of memory accesses.
+///
+/// The main segment of the trace is built by the prover directly; the auxiliary segment consists
+/// of columns for the permutation check and the range check, and is built by [MemoryAuxBuilder].
+pub type MemoryTraceTable = TraceWithAux<TraceTable<BaseElement>, MemoryAuxBuilder>;
+
+// AUXILIARY TRACE BUILDER
+// ================================================================================================
+
+/// Builds the permutation check and the range check columns of the memory trace.
+pub struct MemoryAuxBuilder;
+
+impl AuxTraceBuilder<BaseElement> for MemoryAuxBuilder {
+    fn aux_trace_width(&self) -> usize {
+        AUX_TRACE_WIDTH
+    }
+
+    fn num_rand_elements(&self) -> usize {
+        2
+    }
+
+    fn build_aux_columns<E>(
+        &mut self,
+        main_trace: MainTraceRows<BaseElement>,
+        rand_elements: &[E],
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        // the transition from the last row is not enforced, and thus, both columns are built
+        // from all rows except for the last one
+        let (alpha, beta) = (rand_elements[0], rand_elements[1]);
+        let trace_length = main_trace.num_rows();
+        let mut columns = vec![vec![E::ZERO; trace_length]; AUX_TRACE_WIDTH];
+        columns[PERMUTATION][0] = E::ONE;
+        for row in main_trace.take(trace_length - 1) {
+            let i = row.index();
+            let get = |column: usize| E::from(row.get(column));
+
+            // permutation check: multiply by the access of the original log and divide by the
+            // access of the sorted copy
+            let access = [ADDR, CLK, VALUE, IS_WRITE].map(get);
+            let sorted = [SORTED_ADDR, SORTED_CLK, SORTED_VALUE, SORTED_IS_WRITE].map(get);
+            columns[PERMUTATION][i + 1] = columns[PERMUTATION][i]
+                * (alpha - compress(beta, &access))
+                * (alpha - compress(beta, &sorted)).inv();
+
+            // range check: add 1 / (α - δ), where δ is the difference between the clock cycles
+            // of consecutive accesses to the same cell decremented by one (or zero if the cell
+            // changes), and subtract m / (α - clk) for the clock cycle of the current row looked
+            // up m times
+            let mut delta = E::ZERO;
+            if row.get(SORTED_ADDR) == row.get_next(SORTED_ADDR) {
+                delta = E::from(row.get_next(SORTED_CLK)) - get(SORTED_CLK) - E::ONE;
+            }
+            let mut change = (alpha - delta).inv();
+            let multiplicity = row.get(RANGE_MULTIPLICITY);
+            if multiplicity != BaseElement::ZERO {
+                change -= E::from(multiplicity) * (alpha - get(CLK)).inv();
+            }
+            columns[RANGE_CHECK][i + 1] = columns[RANGE_CHECK][i] + change;
+        }
+
+        ColMatrix::new(columns)
+    }
+}
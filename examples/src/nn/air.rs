@@ -0,0 +1,203 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_table_entry, to_element, BaseElement, ExtensionOf, FieldElement, ProofOptions, ACC,
+    CYCLE_LENGTH, MULTIPLICITY, NUM_INPUTS, OUTPUT, QUANTIZED, REMAINDER_BITS_OFFSET, SHIFT,
+    TABLE_SIZE, TRACE_WIDTH, WEIGHT, WEIGHT_BITS_OFFSET,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Index of the constraint which enforces accumulation of products of weights and inputs.
+const ACC_CONSTRAINT: usize = 9;
+
+/// Index of the constraint which enforces re-quantization of the accumulated value.
+const REQUANTIZE_CONSTRAINT: usize = 10;
+
+/// Index of the first constraint which enforces that remainder bits are binary.
+const REMAINDER_CONSTRAINTS: usize = 11;
+
+/// Total number of transition constraints against the main trace segment.
+const NUM_CONSTRAINTS: usize = REMAINDER_CONSTRAINTS + SHIFT;
+
+// NEURAL NETWORK LAYER AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub inputs: [i8; NUM_INPUTS],
+    pub outputs: Vec<u8>,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let inputs = self.inputs.iter().map(|&x| to_element(x as i32));
+        let outputs = self.outputs.iter().map(|&y| BaseElement::from(y));
+        inputs.chain(outputs).collect()
+    }
+}
+
+pub struct NnAir {
+    context: AirContext<BaseElement>,
+    inputs: [i8; NUM_INPUTS],
+    outputs: Vec<u8>,
+}
+
+impl Air for NnAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let flagged_degree =
+            TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH, CYCLE_LENGTH]);
+        let mut main_degrees = vec![TransitionConstraintDegree::new(1)];
+        main_degrees.resize(ACC_CONSTRAINT, TransitionConstraintDegree::new(2));
+        main_degrees.resize(REMAINDER_CONSTRAINTS, flagged_degree);
+        main_degrees.resize(NUM_CONSTRAINTS, TransitionConstraintDegree::new(2));
+        let aux_degrees = vec![TransitionConstraintDegree::with_cycles(2, vec![TABLE_SIZE])];
+        assert_eq!(TRACE_WIDTH + 1, trace_info.width());
+        assert_eq!(
+            pub_inputs.outputs.len() + 1,
+            trace_info.length() / CYCLE_LENGTH,
+            "number of outputs must be one less than the number of cycles in the trace"
+        );
+
+        NnAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                2,
+                2,
+                options,
+            ),
+            inputs: pub_inputs.inputs,
+            outputs: pub_inputs.outputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let input = periodic_values[0];
+        let last_row_flag = periodic_values[1];
+
+        // the weight must be a signed 8-bit value; this is enforced by decomposing the weight
+        // incremented by 128 into 8 bits
+        let weight_bits = &current[WEIGHT_BITS_OFFSET..ACC];
+        let weight = current[WEIGHT];
+        result[0] = are_equal(weight + E::from(128u32), compose_bits(weight_bits));
+        for (i, &bit) in weight_bits.iter().enumerate() {
+            result[1 + i] = is_binary(bit);
+        }
+
+        // the product of the weight and the input is added to the accumulator; the accumulator
+        // is reset at the start of every cycle
+        let acc = current[ACC] + weight * input;
+        result[ACC_CONSTRAINT] = are_equal(next[ACC], not(last_row_flag) * acc);
+
+        // on the last row of a cycle, the accumulated value must be equal to t * 2^SHIFT + r,
+        // where t is the re-quantized value and r is the remainder
+        let remainder_bits = &current[REMAINDER_BITS_OFFSET..MULTIPLICITY];
+        let quantized = current[QUANTIZED] * E::from(1u32 << SHIFT);
+        result[REQUANTIZE_CONSTRAINT] =
+            last_row_flag * are_equal(acc, quantized + compose_bits(remainder_bits));
+        for (i, &bit) in remainder_bits.iter().enumerate() {
+            result[REMAINDER_CONSTRAINTS + i] = is_binary(bit);
+        }
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let main_current = main_frame.current();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        let random_elements = aux_rand_elements.get_segment_elements(0);
+        let (alpha, beta) = (random_elements[0], random_elements[1]);
+
+        let last_row_flag: E = periodic_values[1].into();
+        let table_value: E = periodic_values[2].into();
+        let table_output: E = periodic_values[3].into();
+
+        // the change of the running sum must be equal to f / (α - (t + β * y)) - m / (α - (t' +
+        // β * y')), where f is the last row flag, (t, y) is the looked-up pair, and (t', y') is
+        // the table entry of the current row looked up m times
+        let quantized: E = main_current[QUANTIZED].into();
+        let output: E = main_current[OUTPUT].into();
+        let multiplicity: E = main_current[MULTIPLICITY].into();
+        let lookup = alpha - (quantized + beta * output);
+        let entry = alpha - (table_value + beta * table_output);
+        result[0] = are_equal(
+            (aux_next[0] - aux_current[0]) * lookup * entry,
+            last_row_flag * entry - multiplicity * lookup,
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the accumulator starts at zero, and outputs of all neurons (including the output of
+        // the last neuron, which has all weights set to zero) must be equal to the expected ones
+        let mut outputs = self.outputs.iter().map(|&y| BaseElement::from(y)).collect::<Vec<_>>();
+        outputs.push(BaseElement::ZERO);
+        vec![
+            Assertion::single(ACC, 0, BaseElement::ZERO),
+            Assertion::sequence(OUTPUT, CYCLE_LENGTH - 1, CYCLE_LENGTH, outputs),
+        ]
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        // the running sum of the lookup argument must start and end at zero
+        let last_step = self.trace_length() - 1;
+        vec![Assertion::single(0, 0, E::ZERO), Assertion::single(0, last_step, E::ZERO)]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let inputs = self.inputs.iter().map(|&x| to_element(x as i32)).collect();
+        let mut last_row_flags = vec![BaseElement::ZERO; CYCLE_LENGTH];
+        last_row_flags[CYCLE_LENGTH - 1] = BaseElement::ONE;
+        let (table_values, table_outputs) = (0..TABLE_SIZE).map(get_table_entry).unzip();
+        vec![inputs, last_row_flags, table_values, table_outputs]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a field element composed from the provided bits, where the first bit is the least
+/// significant bit.
+fn compose_bits<E: FieldElement>(bits: &[E]) -> E {
+    bits.iter().rev().fold(E::ZERO, |value, &bit| value.double() + bit)
+}
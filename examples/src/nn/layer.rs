@@ -0,0 +1,48 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of inputs of the layer.
+pub const NUM_INPUTS: usize = 16;
+
+/// Number of bits by which the accumulated value of a neuron is shifted right to re-quantize it
+/// into an 8-bit value.
+///
+/// The accumulated value of a neuron is a sum of 16 products of 8-bit values, and thus, is at
+/// most 2^18 in absolute value; after the shift, it is at most 64 in absolute value.
+pub const SHIFT: usize = 12;
+
+// LAYER EVALUATION
+// ================================================================================================
+
+/// Returns the output of a single neuron with the specified weights applied to the specified
+/// inputs.
+///
+/// The output is computed by accumulating products of weights and inputs, re-quantizing the
+/// accumulated value, and applying ReLU activation function to the result.
+pub fn evaluate_neuron(weights: &[i8; NUM_INPUTS], inputs: &[i8; NUM_INPUTS]) -> u8 {
+    let (quantized, _) = requantize(accumulate(weights, inputs));
+    relu(quantized)
+}
+
+/// Returns the sum of products of the specified weights and inputs.
+pub fn accumulate(weights: &[i8; NUM_INPUTS], inputs: &[i8; NUM_INPUTS]) -> i32 {
+    weights.iter().zip(inputs.iter()).map(|(&w, &x)| w as i32 * x as i32).sum()
+}
+
+/// Splits the accumulated value into its re-quantized value and the remainder, such that
+/// acc = quantized * 2^SHIFT + remainder, where 0 <= remainder < 2^SHIFT.
+pub fn requantize(acc: i32) -> (i8, u32) {
+    let quantized = acc >> SHIFT;
+    let remainder = acc - (quantized << SHIFT);
+    (quantized as i8, remainder as u32)
+}
+
+/// Returns the result of applying ReLU activation function to the specified value.
+pub fn relu(value: i8) -> u8 {
+    value.max(0) as u8
+}
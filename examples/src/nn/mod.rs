@@ -0,0 +1,233 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod layer;
+use layer::{NUM_INPUTS, SHIFT};
+
+mod trace;
+use trace::LookupTraceTable;
+
+mod air;
+use air::{NnAir, PublicInputs};
+
+mod prover;
+use prover::NnProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of trace rows used to evaluate a single neuron; each row processes a single input.
+const CYCLE_LENGTH: usize = NUM_INPUTS;
+
+/// Number of entries in the lookup table of the activation function; the table contains pairs
+/// (t, ReLU(t)) for all t in [-128, 127].
+const TABLE_SIZE: usize = 256;
+
+// TRACE LAYOUT
+// ================================================================================================
+// The layer computes y_j = ReLU((w_j0 * x_0 + ... + w_j15 * x_15) >> SHIFT) for every neuron j,
+// where inputs x and weights w are signed 8-bit values, and outputs y are unsigned 8-bit values.
+// Signed values are represented by field elements in the natural way (i.e., -1 is p - 1).
+//
+// A neuron is evaluated in a cycle of 16 rows: on every row, the product of a weight and an
+// input is added to the accumulator, and on the last row of a cycle, the accumulated value is
+// re-quantized and passed through the activation function. Inputs are public and are supplied
+// to the AIR via a periodic column, while weights are known only to the prover. The trace ends
+// with an additional neuron with all weights set to zero.
+//
+// Weights and remainders of re-quantization are range-checked by decomposing them into binary
+// columns. The activation function is applied via a lookup into a table of (t, ReLU(t)) pairs;
+// this also ensures that re-quantized values fit into 8 bits. The lookup is enforced using the
+// logarithmic derivative argument: the auxiliary trace segment contains a single column which
+// accumulates 1 / (α - (t + β * y)) for every lookup of (t, y), and subtracts m / (α - (t' + β *
+// y')) for every entry (t', y') of the table which was looked up m times. The table is supplied
+// to the AIR via periodic columns, and its multiplicities are supplied by the prover.
+
+/// Weight of the current row.
+const WEIGHT: usize = 0;
+
+/// Index of the first column holding bits of the current weight incremented by 128.
+const WEIGHT_BITS_OFFSET: usize = WEIGHT + 1;
+
+/// Sum of products accumulated in the current cycle prior to the current row.
+const ACC: usize = WEIGHT_BITS_OFFSET + 8;
+
+/// Re-quantized value of the accumulator; set only on the last row of a cycle.
+const QUANTIZED: usize = ACC + 1;
+
+/// Output of the neuron; set only on the last row of a cycle.
+const OUTPUT: usize = QUANTIZED + 1;
+
+/// Index of the first column holding bits of the remainder of re-quantization; set only on the
+/// last row of a cycle.
+const REMAINDER_BITS_OFFSET: usize = OUTPUT + 1;
+
+/// Number of times the table entry of the current row was looked up.
+const MULTIPLICITY: usize = REMAINDER_BITS_OFFSET + SHIFT;
+
+/// Total number of columns in the main segment of the execution trace.
+const TRACE_WIDTH: usize = MULTIPLICITY + 1;
+
+// NEURAL NETWORK LAYER EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_neurons: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(42, 4);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(NnExample::<Blake3_192>::new(num_neurons, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(NnExample::<Blake3_256>::new(num_neurons, options)))
+        }
+        HashFunction::Sha3_256 => Ok(Box::new(NnExample::<Sha3_256>::new(num_neurons, options))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct NnExample<H: ElementHasher> {
+    options: ProofOptions,
+    inputs: [i8; NUM_INPUTS],
+    weights: Vec<[i8; NUM_INPUTS]>,
+    outputs: Vec<u8>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> NnExample<H> {
+    pub fn new(num_neurons: usize, options: ProofOptions) -> Self {
+        assert!(
+            (num_neurons + 1).is_power_of_two(),
+            "number of neurons must be one less than a power of 2"
+        );
+        assert!(
+            (num_neurons + 1) * CYCLE_LENGTH >= TABLE_SIZE,
+            "number of neurons must be at least {}",
+            TABLE_SIZE / CYCLE_LENGTH - 1
+        );
+
+        // build the inputs and the weights
+        let inputs = hash_to_values(0);
+        let weights = (1..=num_neurons as u64).map(hash_to_values).collect::<Vec<_>>();
+
+        // evaluate the layer
+        let now = Instant::now();
+        let outputs =
+            weights.iter().map(|weights| layer::evaluate_neuron(weights, &inputs)).collect();
+        debug!(
+            "Evaluated a layer of {} neurons in {} ms",
+            num_neurons,
+            now.elapsed().as_millis(),
+        );
+
+        NnExample {
+            options,
+            inputs,
+            weights,
+            outputs,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for NnExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for evaluating a layer of {} neurons\n\
+            ---------------------",
+            self.weights.len()
+        );
+
+        // create a prover
+        let prover = NnProver::<H>::new(self.inputs, self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.weights);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.main_trace_width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            inputs: self.inputs,
+            outputs: self.outputs.clone(),
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<NnAir, H, DefaultRandomCoin<H>>(proof, pub_inputs, &acceptable_options)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut outputs = self.outputs.clone();
+        outputs[0] += 1;
+        let pub_inputs = PublicInputs {
+            inputs: self.inputs,
+            outputs,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<NnAir, H, DefaultRandomCoin<H>>(proof, pub_inputs, &acceptable_options)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a field element representing the specified signed value.
+fn to_element(value: i32) -> BaseElement {
+    if value < 0 {
+        -BaseElement::from(value.unsigned_abs())
+    } else {
+        BaseElement::from(value as u32)
+    }
+}
+
+/// Returns the entry of the activation function lookup table at the specified index.
+fn get_table_entry(index: usize) -> (BaseElement, BaseElement) {
+    let value = index as i32 - 128;
+    (to_element(value), to_element(value.max(0)))
+}
+
+/// Returns a sequence of 8-bit values derived from a hash of the specified seed.
+fn hash_to_values(seed: u64) -> [i8; NUM_INPUTS] {
+    let hash = blake3::hash(&seed.to_le_bytes());
+    let mut result = [0; NUM_INPUTS];
+    for (value, &byte) in result.iter_mut().zip(hash.as_bytes()) {
+        *value = byte as i8;
+    }
+    result
+}
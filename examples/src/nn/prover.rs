@@ -0,0 +1,128 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    layer, to_element, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement,
+    LookupTraceTable, NnAir, PhantomData, ProofOptions, Prover, PublicInputs, ACC, CYCLE_LENGTH,
+    MULTIPLICITY, NUM_INPUTS, OUTPUT, QUANTIZED, REMAINDER_BITS_OFFSET, SHIFT, TRACE_WIDTH, WEIGHT,
+    WEIGHT_BITS_OFFSET,
+};
+use winterfell::{
+    math::StarkField, matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+};
+
+// NEURAL NETWORK LAYER PROVER
+// ================================================================================================
+
+pub struct NnProver<H: ElementHasher> {
+    inputs: [i8; NUM_INPUTS],
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> NnProver<H> {
+    pub fn new(inputs: [i8; NUM_INPUTS], options: ProofOptions) -> Self {
+        Self {
+            inputs,
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace of evaluating a layer with the specified weights of neurons;
+    /// the trace is padded with a neuron with all weights set to zero.
+    pub fn build_trace(&self, weights: &[[i8; NUM_INPUTS]]) -> LookupTraceTable {
+        // allocate memory to hold the trace table
+        let trace_length = (weights.len() + 1) * CYCLE_LENGTH;
+        let mut columns = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+        let padded_weights = weights.iter().chain([[0; NUM_INPUTS]].iter());
+
+        for (neuron, weights) in padded_weights.enumerate() {
+            let mut acc = 0;
+            for (i, (&weight, &input)) in weights.iter().zip(self.inputs.iter()).enumerate() {
+                let row = neuron * CYCLE_LENGTH + i;
+                columns[WEIGHT][row] = to_element(weight as i32);
+                write_bits(&mut columns, row, WEIGHT_BITS_OFFSET, (weight as i32 + 128) as u32, 8);
+                columns[ACC][row] = to_element(acc);
+                acc += weight as i32 * input as i32;
+            }
+
+            // re-quantize the accumulated value and apply the activation function
+            let row = neuron * CYCLE_LENGTH + CYCLE_LENGTH - 1;
+            let (quantized, remainder) = layer::requantize(acc);
+            columns[QUANTIZED][row] = to_element(quantized as i32);
+            columns[OUTPUT][row] = BaseElement::from(layer::relu(quantized));
+            write_bits(&mut columns, row, REMAINDER_BITS_OFFSET, remainder, SHIFT);
+
+            // count the lookup in the multiplicity of the corresponding table entry; the lookup
+            // on the last row of the trace is not a part of the lookup argument
+            if row != trace_length - 1 {
+                columns[MULTIPLICITY][(quantized as i32 + 128) as usize] += BaseElement::ONE;
+            }
+        }
+
+        LookupTraceTable::new(columns)
+    }
+}
+
+impl<H: ElementHasher> Prover for NnProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = NnAir;
+    type Trace = LookupTraceTable;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let num_neurons = trace.length() / CYCLE_LENGTH - 1;
+        let outputs = (0..num_neurons)
+            .map(|neuron| {
+                let row = neuron * CYCLE_LENGTH + CYCLE_LENGTH - 1;
+                trace.main_segment().get(OUTPUT, row).as_int() as u8
+            })
+            .collect();
+        PublicInputs {
+            inputs: self.inputs,
+            outputs,
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn write_bits(columns: &mut [Vec<BaseElement>], row: usize, offset: usize, value: u32, n: usize) {
+    for i in 0..n {
+        columns[offset + i][row] = BaseElement::from((value >> i) & 1);
+    }
+}
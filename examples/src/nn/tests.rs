@@ -0,0 +1,54 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{layer, Blake3_256, NUM_INPUTS};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn nn_evaluate_neuron() {
+    // 16 * (-128 * -128) = 2^18, which is re-quantized into 64
+    assert_eq!(64, layer::evaluate_neuron(&[-128; NUM_INPUTS], &[-128; NUM_INPUTS]));
+
+    // 16 * (127 * -128) = -260096 = -64 * 2^12 + 2048, which is re-quantized into -64
+    assert_eq!((-64, 2048), layer::requantize(-260096));
+    assert_eq!(0, layer::evaluate_neuron(&[127; NUM_INPUTS], &[-128; NUM_INPUTS]));
+
+    // 4103 = 2^12 + 7, which is re-quantized into 1
+    let mut weights = [0; NUM_INPUTS];
+    weights[0] = 1;
+    weights[1] = 7;
+    let mut inputs = [0; NUM_INPUTS];
+    inputs[0] = 8;
+    inputs[1] = 1;
+    assert_eq!(15, layer::accumulate(&weights, &inputs));
+    assert_eq!((1, 7), layer::requantize(4103));
+}
+
+#[test]
+fn nn_test_basic_proof_verification() {
+    let nn_eg = Box::new(super::NnExample::<Blake3_256>::new(15, build_options(false)));
+    crate::tests::test_basic_proof_verification(nn_eg);
+}
+
+#[test]
+fn nn_test_basic_proof_verification_extension() {
+    let nn_eg = Box::new(super::NnExample::<Blake3_256>::new(15, build_options(true)));
+    crate::tests::test_basic_proof_verification(nn_eg);
+}
+
+#[test]
+fn nn_test_basic_proof_verification_fail() {
+    let nn_eg = Box::new(super::NnExample::<Blake3_256>::new(15, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(nn_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
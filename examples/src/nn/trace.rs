@@ -0,0 +1,104 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_table_entry, BaseElement, FieldElement, CYCLE_LENGTH, MULTIPLICITY, OUTPUT, QUANTIZED,
+    TABLE_SIZE, TRACE_WIDTH,
+};
+use winterfell::{matrix::ColMatrix, EvaluationFrame, Trace, TraceLayout};
+
+// LOOKUP TRACE TABLE
+// ================================================================================================
+
+/// An execution trace of evaluating a neural network layer.
+///
+/// The main segment of the trace is built by the prover directly; the auxiliary segment consists
+/// of a single column accumulating the terms of the lookup argument for the activation function.
+pub struct LookupTraceTable {
+    layout: TraceLayout,
+    trace: ColMatrix<BaseElement>,
+}
+
+impl LookupTraceTable {
+    /// Returns a new trace table built from the specified main trace columns.
+    ///
+    /// # Panics
+    /// Panics if the number of columns is not equal to the width of the main trace segment.
+    pub fn new(columns: Vec<Vec<BaseElement>>) -> Self {
+        assert_eq!(TRACE_WIDTH, columns.len(), "invalid number of trace columns");
+        Self {
+            layout: TraceLayout::new(TRACE_WIDTH, [1], [2]),
+            trace: ColMatrix::new(columns),
+        }
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl Trace for LookupTraceTable {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.trace.read_row_into(row_idx, frame.current_mut());
+        self.trace.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        &self.trace
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // we have only one auxiliary segment
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        // on every row, add 1 / (α - (t + β * y)) for the looked-up pair (t, y) (this happens
+        // only on the last row of every cycle), and subtract m / (α - (t' + β * y')) for the
+        // table entry (t', y') of this row; the transition from the last row is not enforced,
+        // and thus, the last row does not contribute to the sum
+        let (alpha, beta) = (rand_elements[0], rand_elements[1]);
+        let mut column = vec![E::ZERO; self.length()];
+        for i in 0..self.length() - 1 {
+            let mut delta = E::ZERO;
+            if i % CYCLE_LENGTH == CYCLE_LENGTH - 1 {
+                let quantized = E::from(self.trace.get(QUANTIZED, i));
+                let output = E::from(self.trace.get(OUTPUT, i));
+                delta += (alpha - (quantized + beta * output)).inv();
+            }
+
+            let multiplicity = self.trace.get(MULTIPLICITY, i);
+            if multiplicity != BaseElement::ZERO {
+                let (value, output) = get_table_entry(i % TABLE_SIZE);
+                let entry = E::from(value) + beta * E::from(output);
+                delta -= E::from(multiplicity) * (alpha - entry).inv();
+            }
+            column[i + 1] = column[i] + delta;
+        }
+
+        Some(ColMatrix::new(vec![column]))
+    }
+}
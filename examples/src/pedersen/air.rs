@@ -0,0 +1,168 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    hash, AffinePoint, BaseElement, FieldElement, ProofOptions, ACC_X, ACC_Y, CYCLE_LENGTH,
+    RESULT_X, RESULT_Y, SLOPE, TABLE_SIZE, TABLE_X, TABLE_Y, TRACE_WIDTH, WINDOW_BITS,
+    WINDOW_BITS_OFFSET, X_DIFF_INV,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// PEDERSEN HASH AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub hashes: Vec<AffinePoint>,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.hashes.iter().flat_map(|point| [point.x, point.y]).collect()
+    }
+}
+
+pub struct PedersenAir {
+    context: AirContext<BaseElement>,
+    results: Vec<AffinePoint>,
+}
+
+impl Air for PedersenAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let selection_degree =
+            TransitionConstraintDegree::with_cycles(WINDOW_BITS, vec![CYCLE_LENGTH]);
+        let mut degrees = vec![TransitionConstraintDegree::new(2); WINDOW_BITS];
+        degrees.extend([selection_degree.clone(), selection_degree]);
+        degrees.extend(vec![TransitionConstraintDegree::new(2); 4]);
+        degrees.extend(vec![TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH]); 2]);
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+
+        // the result of every hash computation is the hash itself shifted by the offset point
+        // and by the sum of the shifts of all table entries
+        let shift = AffinePoint::offset().add(&hash::get_hash_shift());
+        let results = pub_inputs.hashes.iter().map(|point| point.add(&shift)).collect();
+
+        PedersenAir {
+            context: AirContext::new(trace_info, degrees, 4, options),
+            results,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let last_row_flag = periodic_values[0];
+        let table_xs = &periodic_values[1..1 + TABLE_SIZE];
+        let table_ys = &periodic_values[1 + TABLE_SIZE..];
+
+        // bits of the window must be binary
+        let bits = &current[WINDOW_BITS_OFFSET..TABLE_X];
+        for (i, &bit) in bits.iter().enumerate() {
+            result[i] = is_binary(bit);
+        }
+
+        // the looked-up point must be equal to the table entry selected by the bits; the entry
+        // is selected by evaluating the multilinear extension of the table at the bits
+        let basis = get_selection_basis(bits);
+        let (tx, ty) = (current[TABLE_X], current[TABLE_Y]);
+        result[WINDOW_BITS] = are_equal(tx, dot_product(&basis, table_xs));
+        result[WINDOW_BITS + 1] = are_equal(ty, dot_product(&basis, table_ys));
+
+        // R = A + T; x coordinates of A and T must differ, which makes the slope unique
+        let (ax, ay) = (current[ACC_X], current[ACC_Y]);
+        let (rx, ry) = (current[RESULT_X], current[RESULT_Y]);
+        let slope = current[SLOPE];
+        result[WINDOW_BITS + 2] = are_equal(current[X_DIFF_INV] * (tx - ax), E::ONE);
+        result[WINDOW_BITS + 3] = are_equal(slope * (tx - ax), ty - ay);
+        result[WINDOW_BITS + 4] = are_equal(rx, slope.square() - ax - tx);
+        result[WINDOW_BITS + 5] = are_equal(ry, slope * (ax - rx) - ay);
+
+        // within a single hash, the result becomes the accumulated point of the next row
+        let not_last_row = not(last_row_flag);
+        result[WINDOW_BITS + 6] = not_last_row * are_equal(next[ACC_X], rx);
+        result[WINDOW_BITS + 7] = not_last_row * are_equal(next[ACC_Y], ry);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // computation of every hash starts at the offset point and ends with the expected result
+        let offset = AffinePoint::offset();
+        let last_step = CYCLE_LENGTH - 1;
+        vec![
+            Assertion::periodic(ACC_X, 0, CYCLE_LENGTH, offset.x),
+            Assertion::periodic(ACC_Y, 0, CYCLE_LENGTH, offset.y),
+            Assertion::sequence(
+                RESULT_X,
+                last_step,
+                CYCLE_LENGTH,
+                self.results.iter().map(|point| point.x).collect(),
+            ),
+            Assertion::sequence(
+                RESULT_Y,
+                last_step,
+                CYCLE_LENGTH,
+                self.results.iter().map(|point| point.y).collect(),
+            ),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut last_row_flags = vec![BaseElement::ZERO; CYCLE_LENGTH];
+        last_row_flags[CYCLE_LENGTH - 1] = BaseElement::ONE;
+
+        // row i of the cycle uses the table of window i
+        let tables = hash::get_window_tables();
+        let mut result = vec![last_row_flags];
+        for v in 0..TABLE_SIZE {
+            result.push(tables.iter().map(|table| table[v].x).collect());
+        }
+        for v in 0..TABLE_SIZE {
+            result.push(tables.iter().map(|table| table[v].y).collect());
+        }
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the values of the multilinear Lagrange basis at the specified bits; the v-th value is
+/// one when the bits encode v, and zero for all other binary inputs.
+fn get_selection_basis<E: FieldElement>(bits: &[E]) -> [E; TABLE_SIZE] {
+    let mut basis = [E::ZERO; TABLE_SIZE];
+    basis[0] = E::ONE;
+    for (i, &bit) in bits.iter().enumerate() {
+        let size = 1 << i;
+        for v in 0..size {
+            basis[v + size] = basis[v] * bit;
+            basis[v] *= not(bit);
+        }
+    }
+    basis
+}
+
+/// Returns the sum of products of the corresponding values.
+fn dot_product<E: FieldElement>(a: &[E], b: &[E]) -> E {
+    a.iter().zip(b).fold(E::ZERO, |acc, (&a, &b)| acc + a * b)
+}
@@ -0,0 +1,98 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::schnorr::curve::AffinePoint;
+use winterfell::math::fields::f128::BaseElement;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of 128-bit chunks in a message; every chunk is multiplied by its own generator.
+pub const NUM_CHUNKS: usize = 2;
+
+/// Number of bits in a single window of a scalar.
+pub const WINDOW_BITS: usize = 4;
+
+/// Number of points in the lookup table of a single window.
+pub const TABLE_SIZE: usize = 1 << WINDOW_BITS;
+
+/// Number of windows in a single 128-bit chunk of a message.
+pub const WINDOWS_PER_CHUNK: usize = 128 / WINDOW_BITS;
+
+/// Total number of windows in a message.
+pub const NUM_WINDOWS: usize = NUM_CHUNKS * WINDOWS_PER_CHUNK;
+
+/// A message consisting of 128-bit chunks.
+pub type Message = [u128; NUM_CHUNKS];
+
+// PEDERSEN HASH
+// ================================================================================================
+
+/// Returns the Pedersen hash of the specified message, computed as m_0 * G_0 + m_1 * G_1, where
+/// m_i are chunks of the message and G_i are fixed generators.
+///
+/// # Panics
+/// Panics if the hash is the point at infinity (e.g., if all chunks of the message are zero).
+pub fn hash(message: &Message) -> AffinePoint {
+    message
+        .iter()
+        .enumerate()
+        .filter(|(_, &chunk)| chunk != 0)
+        .map(|(i, &chunk)| get_generator(i).mul(chunk))
+        .reduce(|result, point| result.add(&point))
+        .expect("hash is the point at infinity")
+}
+
+/// Returns the generator for the specified chunk of a message.
+///
+/// Generators are derived from small y coordinates, and thus, the discrete logarithms between
+/// them are unknown but not provably so; this is sufficient for illustration purposes only.
+pub fn get_generator(chunk: usize) -> AffinePoint {
+    AffinePoint::from_y(BaseElement::new(3 + chunk as u128))
+}
+
+// WINDOWED EVALUATION
+// ================================================================================================
+
+/// Returns the value of the specified window of a message; windows of every chunk are ordered
+/// from the least significant to the most significant one.
+pub fn get_window_value(message: &Message, window: usize) -> usize {
+    let chunk = message[window / WINDOWS_PER_CHUNK];
+    let shift = (window % WINDOWS_PER_CHUNK) * WINDOW_BITS;
+    ((chunk >> shift) as usize) & (TABLE_SIZE - 1)
+}
+
+/// Returns lookup tables for all windows of a message.
+///
+/// The entry v of the table for window j of chunk i is (v + 1) * 16^j * G_i. The entries are
+/// shifted by one so that none of them is the point at infinity; thus, adding up the looked-up
+/// entries for all windows of a message yields the hash of the message plus a fixed point (see
+/// [get_hash_shift()]).
+pub fn get_window_tables() -> Vec<[AffinePoint; TABLE_SIZE]> {
+    let mut tables = Vec::with_capacity(NUM_WINDOWS);
+    for chunk in 0..NUM_CHUNKS {
+        let mut base = get_generator(chunk);
+        for _ in 0..WINDOWS_PER_CHUNK {
+            let mut table = [base; TABLE_SIZE];
+            table[1] = base.double();
+            for v in 2..TABLE_SIZE {
+                table[v] = table[v - 1].add(&base);
+            }
+            tables.push(table);
+            base = base.mul_pow2(WINDOW_BITS);
+        }
+    }
+    tables
+}
+
+/// Returns the difference between the sum of the looked-up table entries for all windows of a
+/// message and the hash of the message.
+///
+/// Since every entry is shifted by one, the difference is c * (G_0 + G_1), where c = 1 + 16 +
+/// ... + 16^31 = (2^128 - 1) / 15.
+pub fn get_hash_shift() -> AffinePoint {
+    let c = u128::MAX / (TABLE_SIZE as u128 - 1);
+    hash(&[c; NUM_CHUNKS])
+}
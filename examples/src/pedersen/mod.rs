@@ -0,0 +1,200 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    schnorr::curve::AffinePoint, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction,
+    Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod hash;
+use hash::{Message, NUM_WINDOWS, TABLE_SIZE, WINDOW_BITS};
+
+mod air;
+use air::{PedersenAir, PublicInputs};
+
+mod prover;
+use prover::PedersenProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of trace rows used to compute a single hash; each row processes a single window of the
+/// message.
+const CYCLE_LENGTH: usize = NUM_WINDOWS;
+
+// TRACE LAYOUT
+// ================================================================================================
+// A hash of a message is computed by splitting the message into 4-bit windows, looking up the
+// point corresponding to the value of every window in a table of precomputed points for that
+// window, and adding up all looked-up points. Tables are public, and thus, they are supplied to
+// the AIR via periodic columns: for every row of a cycle, there is a periodic column for each
+// coordinate of every table entry.
+//
+// On every row, the value of the window is decomposed into bits, and the looked-up point T is
+// selected from the table of the row by evaluating the multilinear extension of the table at the
+// bits. The point is then added to the accumulated point A. To avoid the point at infinity, the
+// computation starts from a fixed offset point rather than from the point at infinity. Similarly
+// to the Schnorr example, the addition is verified using the slope of the line through the
+// points, and the inverse of the difference of the x coordinates of the points is supplied to
+// rule out the exceptional cases of slope-based addition; a proof cannot be generated for the
+// (negligibly rare) messages for which such a case occurs.
+
+/// Coordinates of the accumulated point at the start of the row.
+const ACC_X: usize = 0;
+const ACC_Y: usize = 1;
+
+/// Index of the first column holding bits of the current window, starting with the least
+/// significant bit.
+const WINDOW_BITS_OFFSET: usize = 2;
+
+/// Coordinates of the point looked up for the current window.
+const TABLE_X: usize = WINDOW_BITS_OFFSET + WINDOW_BITS;
+const TABLE_Y: usize = TABLE_X + 1;
+
+/// Slope of the line through the accumulated point and the looked-up point.
+const SLOPE: usize = TABLE_Y + 1;
+
+/// Inverse of the difference of x coordinates of the looked-up point and the accumulated point.
+const X_DIFF_INV: usize = SLOPE + 1;
+
+/// Coordinates of the accumulated point at the end of the row.
+const RESULT_X: usize = X_DIFF_INV + 1;
+const RESULT_Y: usize = RESULT_X + 1;
+
+/// Total number of columns in the execution trace.
+const TRACE_WIDTH: usize = RESULT_Y + 1;
+
+// PEDERSEN HASH EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_hashes: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(PedersenExample::<Blake3_192>::new(num_hashes, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(PedersenExample::<Blake3_256>::new(num_hashes, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(PedersenExample::<Sha3_256>::new(num_hashes, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct PedersenExample<H: ElementHasher> {
+    options: ProofOptions,
+    messages: Vec<Message>,
+    hashes: Vec<AffinePoint>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> PedersenExample<H> {
+    pub fn new(num_hashes: usize, options: ProofOptions) -> Self {
+        assert!(num_hashes.is_power_of_two(), "number of hashes must be a power of 2");
+
+        // compute hashes of all messages
+        let messages = (0..num_hashes as u64).map(hash_to_message).collect::<Vec<_>>();
+        let now = Instant::now();
+        let hashes = messages.iter().map(hash::hash).collect();
+        debug!("Computed {} Pedersen hashes in {} ms", num_hashes, now.elapsed().as_millis());
+
+        PedersenExample {
+            options,
+            messages,
+            hashes,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for PedersenExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for computing {} Pedersen hashes\n\
+            ---------------------",
+            self.messages.len()
+        );
+
+        // create a prover
+        let prover = PedersenProver::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.messages);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            hashes: self.hashes.clone(),
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<PedersenAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut hashes = self.hashes.clone();
+        hashes[0] = hashes[0].neg();
+        let pub_inputs = PublicInputs { hashes };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<PedersenAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a message derived from a hash of the specified seed.
+fn hash_to_message(seed: u64) -> Message {
+    let hash = blake3::hash(&seed.to_le_bytes());
+    let mut message = [0; hash::NUM_CHUNKS];
+    for (chunk, bytes) in message.iter_mut().zip(hash.as_bytes().chunks(16)) {
+        *chunk = u128::from_le_bytes(bytes.try_into().unwrap());
+    }
+    message
+}
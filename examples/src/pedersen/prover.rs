@@ -0,0 +1,147 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    hash, AffinePoint, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, Message,
+    PedersenAir, PhantomData, ProofOptions, Prover, PublicInputs, ACC_X, ACC_Y, CYCLE_LENGTH,
+    RESULT_X, RESULT_Y, SLOPE, TABLE_SIZE, TABLE_X, TABLE_Y, TRACE_WIDTH, WINDOW_BITS,
+    WINDOW_BITS_OFFSET, X_DIFF_INV,
+};
+use winterfell::{
+    matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// PEDERSEN HASH PROVER
+// ================================================================================================
+
+pub struct PedersenProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> PedersenProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for computing hashes of all specified messages; each hash is
+    /// computed in a separate cycle of 64 steps.
+    pub fn build_trace(&self, messages: &[Message]) -> TraceTable<BaseElement> {
+        // allocate memory to hold the trace table
+        let trace_length = messages.len() * CYCLE_LENGTH;
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+        let tables = hash::get_window_tables();
+        trace.fill(
+            |row| {
+                fill_row(row, AffinePoint::offset(), &messages[0], 0, &tables[0]);
+            },
+            |step, row| {
+                // the accumulated point is either the result of the previous row or, at the
+                // start of a new hash, the offset point
+                let step = step + 1;
+                let window = step % CYCLE_LENGTH;
+                let acc = if window == 0 {
+                    AffinePoint::offset()
+                } else {
+                    AffinePoint {
+                        x: row[RESULT_X],
+                        y: row[RESULT_Y],
+                    }
+                };
+                let message = &messages[step / CYCLE_LENGTH];
+                fill_row(row, acc, message, window, &tables[window]);
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for PedersenProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = PedersenAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        // remove the shift from the results of all hash computations
+        let shift = AffinePoint::offset().add(&hash::get_hash_shift()).neg();
+        let hashes = (0..trace.length() / CYCLE_LENGTH)
+            .map(|i| {
+                let step = i * CYCLE_LENGTH + CYCLE_LENGTH - 1;
+                let result = AffinePoint {
+                    x: trace.get(RESULT_X, step),
+                    y: trace.get(RESULT_Y, step),
+                };
+                result.add(&shift)
+            })
+            .collect();
+        PublicInputs { hashes }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Fills in the specified row of the trace for processing the specified window of a message
+/// using the lookup table of that window.
+fn fill_row(
+    row: &mut [BaseElement],
+    acc: AffinePoint,
+    message: &Message,
+    window: usize,
+    table: &[AffinePoint; TABLE_SIZE],
+) {
+    let value = hash::get_window_value(message, window);
+    let point = table[value];
+    let result = acc.add(&point);
+
+    row[ACC_X] = acc.x;
+    row[ACC_Y] = acc.y;
+    for i in 0..WINDOW_BITS {
+        row[WINDOW_BITS_OFFSET + i] = BaseElement::from(((value >> i) & 1) as u8);
+    }
+    row[TABLE_X] = point.x;
+    row[TABLE_Y] = point.y;
+    row[SLOPE] = (point.y - acc.y) / (point.x - acc.x);
+    row[X_DIFF_INV] = (point.x - acc.x).inv();
+    row[RESULT_X] = result.x;
+    row[RESULT_Y] = result.y;
+}
@@ -0,0 +1,48 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{hash, AffinePoint, Blake3_256, NUM_WINDOWS};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn pedersen_windowed_hash() {
+    // adding up the looked-up table entries for all windows of a message starting from the
+    // offset point must yield the hash shifted by the offset and by the shift of the tables
+    let tables = hash::get_window_tables();
+    for message in [[1, 0], [u128::MAX, 1], super::hash_to_message(0)] {
+        let result = (0..NUM_WINDOWS).fold(AffinePoint::offset(), |acc, window| {
+            acc.add(&tables[window][hash::get_window_value(&message, window)])
+        });
+        let shift = AffinePoint::offset().add(&hash::get_hash_shift());
+        assert_eq!(hash::hash(&message).add(&shift), result);
+    }
+}
+
+#[test]
+fn pedersen_test_basic_proof_verification() {
+    let pedersen_eg = Box::new(super::PedersenExample::<Blake3_256>::new(4, build_options(false)));
+    crate::tests::test_basic_proof_verification(pedersen_eg);
+}
+
+#[test]
+fn pedersen_test_basic_proof_verification_extension() {
+    let pedersen_eg = Box::new(super::PedersenExample::<Blake3_256>::new(4, build_options(true)));
+    crate::tests::test_basic_proof_verification(pedersen_eg);
+}
+
+#[test]
+fn pedersen_test_basic_proof_verification_fail() {
+    let pedersen_eg = Box::new(super::PedersenExample::<Blake3_256>::new(4, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(pedersen_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -13,6 +13,9 @@ use winterfell::{
     DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
 };
 
+#[cfg(feature = "std")]
+use winterfell::PeriodicColumnCache;
+
 // RESCUE PROVER
 // ================================================================================================
 /// This example constructs a proof for correct execution of 2 hash chains simultaneously.
@@ -20,6 +23,8 @@ use winterfell::{
 /// permutations of each other.
 pub struct RescueRapsProver<H: ElementHasher> {
     options: ProofOptions,
+    #[cfg(feature = "std")]
+    periodic_column_cache: Option<PeriodicColumnCache<BaseElement>>,
     _hasher: PhantomData<H>,
 }
 
@@ -27,9 +32,20 @@ impl<H: ElementHasher> RescueRapsProver<H> {
     pub fn new(options: ProofOptions) -> Self {
         Self {
             options,
+            #[cfg(feature = "std")]
+            periodic_column_cache: None,
             _hasher: PhantomData,
         }
     }
+
+    /// Makes this prover reuse periodic column values extended by provers sharing the specified
+    /// cache.
+    #[cfg(all(test, feature = "std"))]
+    pub fn with_periodic_column_cache(mut self, cache: PeriodicColumnCache<BaseElement>) -> Self {
+        self.periodic_column_cache = Some(cache);
+        self
+    }
+
     /// The parameter `seeds` is the set of seeds for the first hash chain.
     /// The parameter `permuted_seeds` is the set of seeds for the second hash chain.
     pub fn build_trace(
@@ -120,6 +136,11 @@ where
         &self.options
     }
 
+    #[cfg(feature = "std")]
+    fn periodic_column_cache(&self) -> Option<&PeriodicColumnCache<Self::BaseField>> {
+        self.periodic_column_cache.as_ref()
+    }
+
     fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
         &self,
         trace_info: &TraceInfo,
@@ -4,7 +4,11 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use crate::Example;
+use winterfell::{
+    crypto::ElementHasher, Air, CoefficientHash, FieldExtension, PeriodicColumnCache, ProofOptions,
+    Prover, ProverError, ProvingConfig, ProvingKey, Trace,
+};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -26,6 +30,141 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_multiple_ood_points() {
+    let options = build_options(true).with_num_ood_points(2);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_resume_proof_from_checkpoint() {
+    let rescue_eg = super::RescueRapsExample::<Blake3_256>::new(128, build_options(true));
+    let prover = super::RescueRapsProver::<Blake3_256>::new(build_options(true));
+    let trace = prover.build_trace(&rescue_eg.seeds, &rescue_eg.permuted_seeds, rescue_eg.result);
+
+    let mut checkpoint = Vec::new();
+    let config =
+        ProvingConfig::new().with_checkpoint_handler(|bytes| checkpoint.extend_from_slice(bytes));
+    let proof = prover.prove_with_config(trace, config).unwrap();
+    assert!(!checkpoint.is_empty());
+
+    // a proof resumed from the checkpoint should be identical to the original proof
+    let pub_inputs = super::PublicInputs {
+        result: rescue_eg.result,
+    };
+    let resumed_proof = prover.resume_proof(pub_inputs, &checkpoint).unwrap();
+    assert_eq!(proof.to_bytes(), resumed_proof.to_bytes());
+    assert!(rescue_eg.verify(resumed_proof).is_ok());
+
+    // resuming with different public inputs should fail
+    let pub_inputs = super::PublicInputs {
+        result: [rescue_eg.result[1], rescue_eg.result[0]],
+    };
+    assert!(matches!(
+        prover.resume_proof(pub_inputs, &checkpoint),
+        Err(ProverError::InvalidCheckpoint(_))
+    ));
+}
+
+#[test]
+fn rescue_test_prove_with_proving_key() {
+    let rescue_eg = super::RescueRapsExample::<Blake3_256>::new(128, build_options(true));
+    let prover = super::RescueRapsProver::<Blake3_256>::new(build_options(true));
+    let build_trace =
+        || prover.build_trace(&rescue_eg.seeds, &rescue_eg.permuted_seeds, rescue_eg.result);
+
+    // build the proving key once
+    let trace = build_trace();
+    let air = super::RescueRapsAir::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        build_options(true),
+    );
+    let proving_key = ProvingKey::new(&air);
+
+    // proofs generated with the key should be identical to proofs generated without it
+    let proof = prover.prove(build_trace()).unwrap();
+    for _ in 0..2 {
+        let config = ProvingConfig::new().with_proving_key(&proving_key);
+        let keyed_proof = prover.prove_with_config(build_trace(), config).unwrap();
+        assert_eq!(proof.to_bytes(), keyed_proof.to_bytes());
+    }
+
+    // a key built for different proof options should be rejected
+    let other_prover = super::RescueRapsProver::<Blake3_256>::new(build_options(false));
+    assert!(matches!(
+        other_prover
+            .prove_with_config(build_trace(), ProvingConfig::new().with_proving_key(&proving_key)),
+        Err(ProverError::IncompatibleProvingKey(_))
+    ));
+}
+
+#[test]
+fn rescue_test_prove_with_coefficient_commitments() {
+    let rescue_eg = super::RescueRapsExample::<Blake3_256>::new(128, build_options(true));
+    let prover = super::RescueRapsProver::<Blake3_256>::new(build_options(true));
+    let build_trace =
+        || prover.build_trace(&rescue_eg.seeds, &rescue_eg.permuted_seeds, rescue_eg.result);
+
+    let trace = build_trace();
+    let main_trace_width = trace.main_trace_width();
+    let aux_trace_width = trace.aux_trace_width();
+    let main_trace_polys = trace.main_segment().interpolate_columns();
+
+    let scheme = CoefficientHash::<Blake3_256>::new();
+    let (proof, commitments) = prover.prove_with_coefficient_commitments(trace, &scheme).unwrap();
+
+    // the proof should be identical to a proof generated without coefficient commitments
+    assert_eq!(prover.prove(build_trace()).unwrap().to_bytes(), proof.to_bytes());
+    assert!(rescue_eg.verify(proof).is_ok());
+
+    // all columns of the main and auxiliary segments should be committed to, in column order
+    assert_eq!(main_trace_width + aux_trace_width, commitments.len());
+    for (poly, commitment) in main_trace_polys.columns().zip(commitments.iter()) {
+        assert_eq!(Blake3_256::hash_elements(poly), *commitment);
+    }
+}
+
+#[test]
+fn rescue_test_prove_with_periodic_column_cache() {
+    let rescue_eg = super::RescueRapsExample::<Blake3_256>::new(128, build_options(true));
+    let cache = PeriodicColumnCache::new();
+    let prover = super::RescueRapsProver::<Blake3_256>::new(build_options(true))
+        .with_periodic_column_cache(cache.clone());
+    let build_trace =
+        || prover.build_trace(&rescue_eg.seeds, &rescue_eg.permuted_seeds, rescue_eg.result);
+
+    let trace = build_trace();
+    let air = super::RescueRapsAir::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        build_options(true),
+    );
+    assert!(cache.is_empty());
+
+    // the first proof populates the cache; proofs should be identical to proofs generated
+    // without the cache
+    let uncached_prover = super::RescueRapsProver::<Blake3_256>::new(build_options(true));
+    let proof = uncached_prover.prove(build_trace()).unwrap();
+    for _ in 0..2 {
+        let cached_proof = prover.prove(build_trace()).unwrap();
+        assert_eq!(proof.to_bytes(), cached_proof.to_bytes());
+        assert!(cache.contains(&air));
+        assert_eq!(1, cache.len());
+    }
+
+    // a prover sharing the cache with different proof options should add a separate entry
+    let other_prover = super::RescueRapsProver::<Blake3_256>::new(build_options(false))
+        .with_periodic_column_cache(cache.clone());
+    let other_proof = other_prover.prove(build_trace()).unwrap();
+    assert!(rescue_eg.verify(other_proof).is_ok());
+    assert_eq!(2, cache.len());
+
+    cache.clear();
+    assert!(!cache.contains(&air));
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -0,0 +1,305 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, Digest, FieldElement, ProofOptions, RpMerklePath, Transfer, AMOUNT, BALANCE_BITS,
+    NEW_BALANCE, NEW_NONCE, NEW_PATH, NEW_ROOT, OLD_BALANCE, OLD_NONCE, OLD_PATH, OLD_ROOT,
+    RANGE_ACC, RANGE_BIT, TRACE_WIDTH, TRANSFER_LENGTH, TREE_DEPTH, UPDATE_LENGTH,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Offsets of the hasher state columns within a Merkle path component: the capacity, the first
+/// half of the rate, and the second half of the rate.
+const CAPACITY: usize = 0;
+const LEFT: usize = 4;
+const RIGHT: usize = 8;
+
+/// Offset of the index bit column within a Merkle path component.
+const INDEX_BIT: usize = 12;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Clone)]
+pub struct PublicInputs {
+    pub old_root: Digest,
+    pub new_root: Digest,
+    pub transfers: Vec<Transfer>,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.old_root.to_vec();
+        result.extend_from_slice(&self.new_root);
+        for transfer in self.transfers.iter() {
+            result.push(BaseElement::from(transfer.sender as u64));
+            result.push(BaseElement::from(transfer.receiver as u64));
+            result.push(BaseElement::from(transfer.amount));
+        }
+        result
+    }
+}
+
+// ROLLUP AIR
+// ================================================================================================
+
+pub struct RollupAir {
+    context: AirContext<BaseElement>,
+    old_root: Digest,
+    new_root: Digest,
+    transfers: Vec<Transfer>,
+}
+
+impl Air for RollupAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert_eq!(pub_inputs.transfers.len() * TRANSFER_LENGTH, trace_info.length());
+
+        // constraints of both Merkle path components are disabled on the last row of every
+        // segment
+        let path_degrees = RpMerklePath::transition_constraint_degrees()
+            .into_iter()
+            .enumerate()
+            .map(|(i, degree)| match i {
+                INDEX_BIT => degree,
+                _ => TransitionConstraintDegree::with_cycles(
+                    7,
+                    vec![RpMerklePath::CYCLE_LENGTH, UPDATE_LENGTH],
+                ),
+            })
+            .collect::<Vec<_>>();
+        let mut degrees = path_degrees.clone();
+        degrees.extend(path_degrees);
+
+        // both components use the same index bits and siblings
+        degrees.push(TransitionConstraintDegree::new(1));
+        let sibling_degree =
+            TransitionConstraintDegree::with_cycles(2, vec![RpMerklePath::CYCLE_LENGTH]);
+        degrees.extend(vec![sibling_degree; 4]);
+
+        // the components are initialized with the leaves before and after the update
+        degrees.extend(vec![TransitionConstraintDegree::with_cycles(1, vec![UPDATE_LENGTH]); 8]);
+        degrees.extend(vec![TransitionConstraintDegree::with_cycles(2, vec![UPDATE_LENGTH]); 8]);
+
+        // balances, nonces, and roots are constant within a segment, and the amount is constant
+        // within a transfer
+        degrees.extend(vec![TransitionConstraintDegree::with_cycles(1, vec![UPDATE_LENGTH]); 12]);
+        degrees.push(TransitionConstraintDegree::with_cycles(1, vec![TRANSFER_LENGTH]));
+
+        // balances and nonces are updated according to the transfer
+        degrees.push(TransitionConstraintDegree::with_cycles(1, vec![TRANSFER_LENGTH]));
+        degrees.push(TransitionConstraintDegree::new(1));
+
+        // the components resolve to the roots before and after the update, and the roots are
+        // chained across segments
+        degrees.extend(vec![TransitionConstraintDegree::with_cycles(1, vec![UPDATE_LENGTH]); 12]);
+
+        // range check of the new balance
+        degrees.extend(vec![TransitionConstraintDegree::with_cycles(1, vec![UPDATE_LENGTH]); 3]);
+        degrees.push(TransitionConstraintDegree::new(2));
+
+        // every leaf update binds the index bits of the leaf, and every transfer binds the
+        // amount; in addition, the initial and the final roots are bound
+        let num_transfers = pub_inputs.transfers.len();
+        let num_assertions = num_transfers * (2 * TREE_DEPTH + 1) + 8;
+
+        RollupAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            old_root: pub_inputs.old_root,
+            new_root: pub_inputs.new_root,
+            transfers: pub_inputs.transfers,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let (path_periodic_values, periodic_values) =
+            periodic_values.split_at(RpMerklePath::NUM_PERIODIC_COLUMNS);
+        let level_start = periodic_values[0];
+        let segment_start = periodic_values[1];
+        let range_shift = periodic_values[2];
+        let range_end = periodic_values[3];
+        let segment_before_end = periodic_values[4];
+        let segment_end = periodic_values[5];
+        let is_sender = periodic_values[6];
+        let transfer_end = periodic_values[7];
+
+        // enforce both Merkle path components, except on the last row of a segment
+        let mut offset = 0;
+        for path in [OLD_PATH, NEW_PATH] {
+            let columns = path..path + RpMerklePath::TRACE_WIDTH;
+            let result = &mut result[offset..offset + RpMerklePath::NUM_CONSTRAINTS];
+            RpMerklePath::evaluate_transition(
+                &current[columns.clone()],
+                &next[columns],
+                path_periodic_values,
+                result,
+            );
+            for value in result[..INDEX_BIT].iter_mut() {
+                *value *= not(segment_end);
+            }
+            offset += RpMerklePath::NUM_CONSTRAINTS;
+        }
+
+        // both components use the same index bits, and at the start of every level, the
+        // siblings of the two components must be the same
+        let old = &current[OLD_PATH..OLD_PATH + RpMerklePath::TRACE_WIDTH];
+        let new = &current[NEW_PATH..NEW_PATH + RpMerklePath::TRACE_WIDTH];
+        let bit = old[INDEX_BIT];
+        result[offset] = are_equal(old[INDEX_BIT], new[INDEX_BIT]);
+        offset += 1;
+        for i in 0..4 {
+            let left = are_equal(old[LEFT + i], new[LEFT + i]);
+            let right = are_equal(old[RIGHT + i], new[RIGHT + i]);
+            result[offset] = level_start * (not(bit) * right + bit * left);
+            offset += 1;
+        }
+
+        // at the start of a segment, the capacity of both components must be [8, 0, 0, 0], and
+        // the leaves before and after the update must be placed into the rate according to the
+        // first index bit
+        for path in [old, new] {
+            result[offset] = segment_start * are_equal(path[CAPACITY], E::from(8u32));
+            for i in 1..4 {
+                result[offset + i] = segment_start * path[CAPACITY + i];
+            }
+            offset += 4;
+        }
+        let old_leaf = [current[OLD_BALANCE], current[OLD_NONCE], E::ZERO, E::ZERO];
+        let new_leaf = [current[NEW_BALANCE], current[NEW_NONCE], E::ZERO, E::ZERO];
+        for (path, leaf) in [(old, old_leaf), (new, new_leaf)] {
+            for (i, &value) in leaf.iter().enumerate() {
+                let left = are_equal(path[LEFT + i], value);
+                let right = are_equal(path[RIGHT + i], value);
+                result[offset] = segment_start * (not(bit) * left + bit * right);
+                offset += 1;
+            }
+        }
+
+        // balances, nonces, and roots do not change within a segment
+        let columns = [OLD_BALANCE, NEW_BALANCE, OLD_NONCE, NEW_NONCE];
+        for column in columns.into_iter().chain(OLD_ROOT..NEW_ROOT + 4) {
+            result[offset] = not(segment_end) * are_equal(next[column], current[column]);
+            offset += 1;
+        }
+
+        // the amount does not change within a transfer
+        result[offset] = not(transfer_end) * are_equal(next[AMOUNT], current[AMOUNT]);
+        offset += 1;
+
+        // the sender loses the amount and increments its nonce; the receiver gains the amount
+        let sign = E::ONE - is_sender.double();
+        result[offset] =
+            are_equal(current[NEW_BALANCE], current[OLD_BALANCE] + sign * current[AMOUNT]);
+        result[offset + 1] = are_equal(current[NEW_NONCE], current[OLD_NONCE] + is_sender);
+        offset += 2;
+
+        // at the end of a segment, the components must resolve to the roots before and after the
+        // update; the roots are checked one row before the end of a segment since transitions
+        // from the last row of the trace are not enforced
+        for (path, root) in [(OLD_PATH, OLD_ROOT), (NEW_PATH, NEW_ROOT)] {
+            for i in 0..4 {
+                let digest = next[path + LEFT + i];
+                result[offset] = segment_before_end * are_equal(digest, current[root + i]);
+                offset += 1;
+            }
+        }
+
+        // the root after the update of a segment is the root before the update of the next one
+        for i in 0..4 {
+            result[offset] = segment_end * are_equal(next[OLD_ROOT + i], current[NEW_ROOT + i]);
+            offset += 1;
+        }
+
+        // the new balance is decomposed into bits, starting with the most significant one, over
+        // the first 32 rows of a segment
+        result[offset] = segment_start * are_equal(current[RANGE_ACC], current[RANGE_BIT]);
+        result[offset + 1] =
+            range_shift * are_equal(next[RANGE_ACC], current[RANGE_ACC].double() + next[RANGE_BIT]);
+        result[offset + 2] = range_end * are_equal(current[RANGE_ACC], current[NEW_BALANCE]);
+        result[offset + 3] = is_binary(current[RANGE_BIT]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut result = Vec::new();
+
+        // index bits of every leaf update must be the bits of the sender or of the receiver
+        for (i, transfer) in self.transfers.iter().enumerate() {
+            for (j, index) in [transfer.sender, transfer.receiver].into_iter().enumerate() {
+                let start = i * TRANSFER_LENGTH + j * UPDATE_LENGTH;
+                for level in 0..TREE_DEPTH {
+                    let bit = BaseElement::from(((index >> level) & 1) as u64);
+                    let step = start + level * RpMerklePath::CYCLE_LENGTH;
+                    result.push(Assertion::single(OLD_PATH + INDEX_BIT, step, bit));
+                }
+            }
+            let amount = BaseElement::from(transfer.amount);
+            result.push(Assertion::single(AMOUNT, i * TRANSFER_LENGTH, amount));
+        }
+
+        // the batch must start from the old root and end with the new root
+        let last_step = self.trace_length() - 1;
+        for i in 0..4 {
+            result.push(Assertion::single(OLD_ROOT + i, 0, self.old_root[i]));
+            result.push(Assertion::single(NEW_ROOT + i, last_step, self.new_root[i]));
+        }
+
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut result = RpMerklePath::get_periodic_column_values();
+
+        let mut level_start = vec![BaseElement::ZERO; RpMerklePath::CYCLE_LENGTH];
+        level_start[0] = BaseElement::ONE;
+        result.push(level_start);
+
+        let flag = |rows: core::ops::Range<usize>| {
+            (0..UPDATE_LENGTH)
+                .map(|row| BaseElement::from(rows.contains(&row) as u8))
+                .collect::<Vec<_>>()
+        };
+        result.push(flag(0..1));
+        result.push(flag(0..BALANCE_BITS - 1));
+        result.push(flag(BALANCE_BITS - 1..BALANCE_BITS));
+        result.push(flag(UPDATE_LENGTH - 2..UPDATE_LENGTH - 1));
+        result.push(flag(UPDATE_LENGTH - 1..UPDATE_LENGTH));
+
+        let mut is_sender = vec![BaseElement::ZERO; TRANSFER_LENGTH];
+        is_sender[..UPDATE_LENGTH].fill(BaseElement::ONE);
+        result.push(is_sender);
+
+        let mut transfer_end = vec![BaseElement::ZERO; TRANSFER_LENGTH];
+        transfer_end[TRANSFER_LENGTH - 1] = BaseElement::ONE;
+        result.push(transfer_end);
+
+        result
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Example, ExampleOptions, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    gadgets::RpMerklePath,
+    math::{fields::f64::BaseElement, FieldElement, StarkField},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod tree;
+use tree::SparseMerkleTree;
+
+mod air;
+use air::{PublicInputs, RollupAir};
+
+mod prover;
+use prover::RollupProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+/// Depth of the account tree; the tree has room for 2^16 accounts.
+const TREE_DEPTH: usize = 16;
+
+/// Number of trace rows used to update a single leaf of the account tree.
+const UPDATE_LENGTH: usize = TREE_DEPTH * RpMerklePath::CYCLE_LENGTH;
+
+/// Number of trace rows used to apply a single transfer; a transfer updates the leaf of the
+/// sender and then the leaf of the receiver.
+const TRANSFER_LENGTH: usize = 2 * UPDATE_LENGTH;
+
+/// Number of bits in an account balance.
+const BALANCE_BITS: usize = 32;
+
+/// A node of the account tree.
+type Digest = [BaseElement; 4];
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+type RpJive64_256 = winterfell::crypto::hashers::RpJive64_256;
+type GriffinJive64_256 = winterfell::crypto::hashers::GriffinJive64_256;
+
+// TRACE LAYOUT
+// ================================================================================================
+// The state of the rollup is a sparse Merkle tree of accounts built with Rp64_256 hash function,
+// in which the leaf of an account is [balance, nonce, 0, 0] and the leaves of non-existent
+// accounts are empty. A batch of transfers is applied to the state one by one; every transfer
+// decrements the balance and increments the nonce of the sender, and then increments the
+// balance of the receiver (which may be a new account).
+//
+// The trace is split into segments of 128 rows, each of which updates a single leaf of the tree:
+// the authentication path of the leaf is verified twice by two RpMerklePath components placed
+// side by side, once for the leaf before the update (resolving to the root before the update),
+// and once for the leaf after the update (resolving to the root after the update). Both
+// components use the same leaf index and siblings, and thus, the update changes only the leaf.
+// The constraints of the components are disabled on the last row of every segment so that the
+// next segment can start a new path.
+//
+// Every segment holds the balances and nonces of the leaf before and after the update, as well
+// as the roots before and after the update, in columns which are constant within the segment;
+// the root after the update of one segment becomes the root before the update of the next one.
+// Within a transfer, the amount is constant, and the balances are updated according to whether
+// the segment updates the leaf of the sender or of the receiver. Every new balance is range
+// checked by decomposing it into 32 bits over the first 32 rows of the segment; this rules out
+// transferring more than the balance of the sender.
+//
+// The public inputs are the roots before and after the batch, and the list of transfers (the
+// data a rollup would publish). The leaf indexes and the amounts of the transfers are bound to
+// the trace via assertions. Authorization of transfers (e.g., signatures of the senders) is out
+// of scope of this example.
+
+/// Columns of the component verifying the path of the leaf before the update.
+const OLD_PATH: usize = 0;
+
+/// Columns of the component verifying the path of the leaf after the update.
+const NEW_PATH: usize = OLD_PATH + RpMerklePath::TRACE_WIDTH;
+
+/// Balance and nonce of the leaf before and after the update.
+const OLD_BALANCE: usize = NEW_PATH + RpMerklePath::TRACE_WIDTH;
+const NEW_BALANCE: usize = OLD_BALANCE + 1;
+const OLD_NONCE: usize = NEW_BALANCE + 1;
+const NEW_NONCE: usize = OLD_NONCE + 1;
+
+/// Amount of the transfer.
+const AMOUNT: usize = NEW_NONCE + 1;
+
+/// Roots of the tree before and after the update.
+const OLD_ROOT: usize = AMOUNT + 1;
+const NEW_ROOT: usize = OLD_ROOT + 4;
+
+/// Range check of the new balance: the value accumulated from the bits so far and the current
+/// bit.
+const RANGE_ACC: usize = NEW_ROOT + 4;
+const RANGE_BIT: usize = RANGE_ACC + 1;
+
+/// Total number of columns in the execution trace.
+const TRACE_WIDTH: usize = RANGE_BIT + 1;
+
+// ROLLUP EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_transfers: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(RollupExample::<Blake3_192>::new(num_transfers, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(RollupExample::<Blake3_256>::new(num_transfers, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(RollupExample::<Sha3_256>::new(num_transfers, options)))
+        }
+        HashFunction::Rp64_256 => {
+            Ok(Box::new(RollupExample::<Rp64_256>::new(num_transfers, options)))
+        }
+        HashFunction::RpJive64_256 => {
+            Ok(Box::new(RollupExample::<RpJive64_256>::new(num_transfers, options)))
+        }
+        HashFunction::GriffinJive64_256 => {
+            Ok(Box::new(RollupExample::<GriffinJive64_256>::new(num_transfers, options)))
+        }
+    }
+}
+
+/// A transfer of funds between two accounts identified by their leaf indexes.
+#[derive(Debug, Clone, Copy)]
+pub struct Transfer {
+    pub sender: usize,
+    pub receiver: usize,
+    pub amount: u32,
+}
+
+/// A single update of a leaf of the account tree.
+pub struct LeafUpdate {
+    index: usize,
+    old_leaf: Digest,
+    new_leaf: Digest,
+    old_root: Digest,
+    new_root: Digest,
+    path: Vec<Digest>,
+}
+
+pub struct RollupExample<H: ElementHasher> {
+    options: ProofOptions,
+    pub_inputs: PublicInputs,
+    updates: Vec<LeafUpdate>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RollupExample<H> {
+    pub fn new(num_transfers: usize, options: ProofOptions) -> Self {
+        assert!(num_transfers.is_power_of_two(), "number of transfers must be a power of 2");
+
+        // build the initial state with as many funded accounts as there are transfers
+        let now = Instant::now();
+        let mut accounts = Vec::with_capacity(num_transfers);
+        let mut seed = 0;
+        while accounts.len() < num_transfers {
+            let index = get_index(seed);
+            if accounts.iter().all(|&(i, _)| i != index) {
+                let balance = 1000 + ((rand_value(seed) >> 16) % 9000) as u32;
+                let nonce = ((rand_value(seed) >> 48) % 100) as u32;
+                accounts.push((index, to_leaf(balance, nonce)));
+            }
+            seed += 1;
+        }
+        let mut tree = SparseMerkleTree::new(&accounts);
+        let old_root = tree.root();
+
+        // generate and apply the transfers; receivers are chosen at random, and so most of them
+        // are new accounts
+        let mut transfers = Vec::with_capacity(num_transfers);
+        let mut updates = Vec::with_capacity(2 * num_transfers);
+        for i in 0..num_transfers as u64 {
+            let sender = accounts[(rand_value(seed + i) % num_transfers as u64) as usize].0;
+            let receiver = get_index(seed + num_transfers as u64 + i);
+            let amount = (rand_value(seed + 2 * num_transfers as u64 + i)
+                % (get_balance(&tree.get_leaf(sender)) as u64 + 1)) as u32;
+
+            let leaf = tree.get_leaf(sender);
+            let new_leaf = to_leaf(get_balance(&leaf) - amount, get_nonce(&leaf) + 1);
+            updates.push(update_leaf(&mut tree, sender, new_leaf));
+
+            let leaf = tree.get_leaf(receiver);
+            let new_balance = get_balance(&leaf).checked_add(amount).expect("balance overflow");
+            updates.push(update_leaf(&mut tree, receiver, to_leaf(new_balance, get_nonce(&leaf))));
+
+            transfers.push(Transfer {
+                sender,
+                receiver,
+                amount,
+            });
+        }
+        debug!(
+            "Applied {} transfers to the account tree in {} ms",
+            num_transfers,
+            now.elapsed().as_millis()
+        );
+
+        let pub_inputs = PublicInputs {
+            old_root,
+            new_root: tree.root(),
+            transfers,
+        };
+
+        RollupExample {
+            options,
+            pub_inputs,
+            updates,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for RollupExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for applying {} transfers to the account tree\n\
+            ---------------------",
+            self.pub_inputs.transfers.len()
+        );
+
+        // create a prover
+        let prover = RollupProver::<H>::new(self.options.clone(), self.pub_inputs.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.updates);
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace.length().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<RollupAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.pub_inputs.clone(),
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut pub_inputs = self.pub_inputs.clone();
+        pub_inputs.transfers[0].amount += 1;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<RollupAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Sets the leaf at the specified index of the tree to the specified value and returns the
+/// record of the update.
+fn update_leaf(tree: &mut SparseMerkleTree, index: usize, new_leaf: Digest) -> LeafUpdate {
+    let old_leaf = tree.get_leaf(index);
+    let old_root = tree.root();
+    let path = tree.get_path(index);
+    tree.set_leaf(index, new_leaf);
+    LeafUpdate {
+        index,
+        old_leaf,
+        new_leaf,
+        old_root,
+        new_root: tree.root(),
+        path,
+    }
+}
+
+/// Returns the leaf of an account with the specified balance and nonce.
+fn to_leaf(balance: u32, nonce: u32) -> Digest {
+    [
+        BaseElement::from(balance),
+        BaseElement::from(nonce),
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+    ]
+}
+
+fn get_balance(leaf: &Digest) -> u32 {
+    leaf[0].as_int() as u32
+}
+
+fn get_nonce(leaf: &Digest) -> u32 {
+    leaf[1].as_int() as u32
+}
+
+/// Returns a pseudo-random leaf index derived from the specified seed.
+fn get_index(seed: u64) -> usize {
+    (rand_value(seed) % (1 << TREE_DEPTH)) as usize
+}
+
+/// Returns a pseudo-random value derived from a hash of the specified seed.
+fn rand_value(seed: u64) -> u64 {
+    let hash = blake3::hash(&seed.to_le_bytes());
+    u64::from_le_bytes(hash.as_bytes()[..8].try_into().unwrap())
+}
@@ -0,0 +1,128 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, LeafUpdate, PhantomData,
+    ProofOptions, Prover, PublicInputs, RollupAir, RpMerklePath, StarkField, AMOUNT, BALANCE_BITS,
+    NEW_BALANCE, NEW_NONCE, NEW_PATH, NEW_ROOT, OLD_BALANCE, OLD_NONCE, OLD_PATH, OLD_ROOT,
+    RANGE_ACC, RANGE_BIT, TRACE_WIDTH, UPDATE_LENGTH,
+};
+use winterfell::{
+    matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// ROLLUP PROVER
+// ================================================================================================
+
+pub struct RollupProver<H: ElementHasher> {
+    options: ProofOptions,
+    pub_inputs: PublicInputs,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RollupProver<H> {
+    pub fn new(options: ProofOptions, pub_inputs: PublicInputs) -> Self {
+        Self {
+            options,
+            pub_inputs,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for applying the specified leaf updates to the account tree;
+    /// every pair of updates corresponds to a single transfer of the public inputs.
+    pub fn build_trace(&self, updates: &[LeafUpdate]) -> TraceTable<BaseElement> {
+        let trace_length = updates.len() * UPDATE_LENGTH;
+        let mut columns =
+            (0..TRACE_WIDTH).map(|_| Vec::with_capacity(trace_length)).collect::<Vec<_>>();
+
+        for (i, update) in updates.iter().enumerate() {
+            let transfer = &self.pub_inputs.transfers[i / 2];
+
+            // both Merkle path components use the same index and path
+            let old_path = RpMerklePath::build_columns(update.old_leaf, update.index, &update.path);
+            let new_path = RpMerklePath::build_columns(update.new_leaf, update.index, &update.path);
+            for (j, column) in old_path.into_iter().enumerate() {
+                columns[OLD_PATH + j].extend(column);
+            }
+            for (j, column) in new_path.into_iter().enumerate() {
+                columns[NEW_PATH + j].extend(column);
+            }
+
+            // balances, nonces, the amount, and the roots are constant within the segment
+            let values = [
+                (OLD_BALANCE, update.old_leaf[0]),
+                (NEW_BALANCE, update.new_leaf[0]),
+                (OLD_NONCE, update.old_leaf[1]),
+                (NEW_NONCE, update.new_leaf[1]),
+                (AMOUNT, BaseElement::from(transfer.amount)),
+            ];
+            let roots = (0..4)
+                .map(|j| (OLD_ROOT + j, update.old_root[j]))
+                .chain((0..4).map(|j| (NEW_ROOT + j, update.new_root[j])));
+            for (column, value) in values.into_iter().chain(roots) {
+                columns[column].extend(core::iter::repeat(value).take(UPDATE_LENGTH));
+            }
+
+            // decompose the new balance into bits over the first rows of the segment, starting
+            // with the most significant bit
+            let balance = update.new_leaf[0].as_int();
+            for row in 0..UPDATE_LENGTH {
+                let (acc, bit) = if row < BALANCE_BITS {
+                    let acc = balance >> (BALANCE_BITS - 1 - row);
+                    (acc, acc & 1)
+                } else {
+                    (0, 0)
+                };
+                columns[RANGE_ACC].push(BaseElement::new(acc));
+                columns[RANGE_BIT].push(BaseElement::new(bit));
+            }
+        }
+
+        TraceTable::init(columns)
+    }
+}
+
+impl<H: ElementHasher> Prover for RollupProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = RollupAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        self.pub_inputs.clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Rp64_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn rollup_test_basic_proof_verification() {
+    let rollup_eg = Box::new(super::RollupExample::<Rp64_256>::new(4, build_options(false)));
+    crate::tests::test_basic_proof_verification(rollup_eg);
+}
+
+#[test]
+fn rollup_test_basic_proof_verification_extension() {
+    let rollup_eg = Box::new(super::RollupExample::<Rp64_256>::new(4, build_options(true)));
+    crate::tests::test_basic_proof_verification(rollup_eg);
+}
+
+#[test]
+fn rollup_test_basic_proof_verification_fail() {
+    let rollup_eg = Box::new(super::RollupExample::<Rp64_256>::new(4, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(rollup_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
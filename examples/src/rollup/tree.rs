@@ -0,0 +1,100 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, Digest, TREE_DEPTH};
+use std::collections::BTreeMap;
+use winterfell::{
+    crypto::{hashers::Rp64_256, Hasher},
+    math::FieldElement,
+};
+
+// SPARSE MERKLE TREE
+// ================================================================================================
+
+/// A Merkle tree of depth [TREE_DEPTH] built with [Rp64_256] hash function in which most of the
+/// leaves are empty (i.e., [0, 0, 0, 0]).
+///
+/// Only the nodes which have at least one non-empty leaf below them are stored; all other nodes
+/// are roots of empty subtrees and are taken from a table of precomputed values.
+pub struct SparseMerkleTree {
+    nodes: BTreeMap<(usize, usize), Digest>,
+    empty_nodes: Vec<Digest>,
+}
+
+impl SparseMerkleTree {
+    /// Returns a tree in which all leaves are empty except for the specified ones.
+    pub fn new(leaves: &[(usize, Digest)]) -> Self {
+        let mut empty_nodes = vec![[BaseElement::ZERO; 4]];
+        for _ in 0..TREE_DEPTH {
+            let node = *empty_nodes.last().unwrap();
+            empty_nodes.push(merge(&node, &node));
+        }
+
+        let mut tree = SparseMerkleTree {
+            nodes: BTreeMap::new(),
+            empty_nodes,
+        };
+        for &(index, leaf) in leaves {
+            tree.set_leaf(index, leaf);
+        }
+        tree
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> Digest {
+        self.get_node(TREE_DEPTH, 0)
+    }
+
+    /// Returns the leaf at the specified index.
+    pub fn get_leaf(&self, index: usize) -> Digest {
+        self.get_node(0, index)
+    }
+
+    /// Returns the authentication path of the leaf at the specified index, from the sibling of
+    /// the leaf towards the root.
+    pub fn get_path(&self, index: usize) -> Vec<Digest> {
+        (0..TREE_DEPTH)
+            .map(|level| self.get_node(level, (index >> level) ^ 1))
+            .collect()
+    }
+
+    /// Sets the leaf at the specified index to the specified value, and updates all nodes on
+    /// the path from the leaf to the root.
+    pub fn set_leaf(&mut self, index: usize, leaf: Digest) {
+        assert!(index < (1 << TREE_DEPTH), "leaf index {index} is out of bounds");
+        let mut node = leaf;
+        let mut index = index;
+        for level in 0..TREE_DEPTH {
+            self.nodes.insert((level, index), node);
+            let sibling = self.get_node(level, index ^ 1);
+            node = if index & 1 == 0 {
+                merge(&node, &sibling)
+            } else {
+                merge(&sibling, &node)
+            };
+            index >>= 1;
+        }
+        self.nodes.insert((TREE_DEPTH, 0), node);
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the node at the specified index of the specified level; leaves are at level 0.
+    fn get_node(&self, level: usize, index: usize) -> Digest {
+        match self.nodes.get(&(level, index)) {
+            Some(node) => *node,
+            None => self.empty_nodes[level],
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a hash of two nodes of the tree.
+fn merge(left: &Digest, right: &Digest) -> Digest {
+    Rp64_256::merge(&[(*left).into(), (*right).into()]).into()
+}
@@ -0,0 +1,195 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    signature::get_challenge, AffinePoint, BaseElement, FieldElement, ProofOptions, PublicKey,
+    ACC_X, ACC_Y, ADD_G_INV, ADD_G_SLOPE, ADD_G_X, ADD_G_Y, ADD_P_INV, ADD_P_SLOPE, ADD_P_X,
+    ADD_P_Y, CYCLE_LENGTH, DOUBLE_SLOPE, DOUBLE_X, DOUBLE_Y, E_ACC, E_BIT, RESULT_X, RESULT_Y,
+    S_BIT, TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// SCHNORR AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub pub_key: PublicKey,
+    pub messages: Vec<BaseElement>,
+    pub nonces: Vec<AffinePoint>,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = vec![self.pub_key.0.x, self.pub_key.0.y];
+        for (message, nonce) in self.messages.iter().zip(self.nonces.iter()) {
+            result.extend_from_slice(&[*message, nonce.x, nonce.y]);
+        }
+        result
+    }
+}
+
+pub struct SchnorrAir {
+    context: AirContext<BaseElement>,
+    generator: AffinePoint,
+    neg_pub_key: AffinePoint,
+    challenges: Vec<BaseElement>,
+    results: Vec<AffinePoint>,
+}
+
+impl Air for SchnorrAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![
+            // doubling
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            // addition of G
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            // addition of -P
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(3),
+            // selection of the result
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            // scalar bits
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            // transition to the next row
+            TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH]),
+            TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH]),
+            TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH]),
+        ];
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+
+        // compute challenges of all signatures, as well as the expected results of scalar
+        // multiplications: s * G - e * P + 2^128 * T = R + 2^128 * T
+        let shifted_offset = AffinePoint::offset().mul_pow2(CYCLE_LENGTH);
+        let challenges = pub_inputs
+            .messages
+            .iter()
+            .zip(pub_inputs.nonces.iter())
+            .map(|(&message, nonce)| {
+                BaseElement::new(get_challenge(&pub_inputs.pub_key, nonce, message))
+            })
+            .collect();
+        let results = pub_inputs.nonces.iter().map(|nonce| nonce.add(&shifted_offset)).collect();
+
+        SchnorrAir {
+            context: AirContext::new(trace_info, degrees, 6, options),
+            generator: AffinePoint::generator(),
+            neg_pub_key: pub_inputs.pub_key.0.neg(),
+            challenges,
+            results,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let last_row_flag = periodic_values[0];
+        let (x, y) = (current[ACC_X], current[ACC_Y]);
+        let (s_bit, e_bit) = (current[S_BIT], current[E_BIT]);
+
+        // D = 2 * A
+        let slope = current[DOUBLE_SLOPE];
+        let (dx, dy) = (current[DOUBLE_X], current[DOUBLE_Y]);
+        result[0] = are_equal(slope * y.double(), E::from(3u32) * x.square());
+        result[1] = are_equal(dx, slope.square() - x.double());
+        result[2] = are_equal(dy, slope * (x - dx) - y);
+
+        // D + G; x coordinates of D and G must differ, which makes the slope unique
+        let (gx, gy) = (E::from(self.generator.x), E::from(self.generator.y));
+        let slope = current[ADD_G_SLOPE];
+        let (ax, ay) = (current[ADD_G_X], current[ADD_G_Y]);
+        result[3] = are_equal(current[ADD_G_INV] * (gx - dx), E::ONE);
+        result[4] = are_equal(slope * (gx - dx), gy - dy);
+        result[5] = are_equal(ax, slope.square() - dx - gx);
+        result[6] = are_equal(ay, slope * (dx - ax) - dy);
+
+        // S - P, where S = D + G if the bit of s is one, and S = D otherwise; x coordinates of S
+        // and -P must differ, which makes the slope unique
+        let (sx, sy) = (dx + s_bit * (ax - dx), dy + s_bit * (ay - dy));
+        let (px, py) = (E::from(self.neg_pub_key.x), E::from(self.neg_pub_key.y));
+        let slope = current[ADD_P_SLOPE];
+        let (ax, ay) = (current[ADD_P_X], current[ADD_P_Y]);
+        result[7] = are_equal(current[ADD_P_INV] * (px - sx), E::ONE);
+        result[8] = are_equal(slope * (px - sx), py - sy);
+        result[9] = are_equal(ax, slope.square() - sx - px);
+        result[10] = are_equal(ay, slope * (sx - ax) - sy);
+
+        // the result is S - P if the bit of e is one, and S otherwise
+        result[11] = are_equal(current[RESULT_X], sx + e_bit * (ax - sx));
+        result[12] = are_equal(current[RESULT_Y], sy + e_bit * (ay - sy));
+
+        // bits of scalars must be binary
+        result[13] = is_binary(s_bit);
+        result[14] = is_binary(e_bit);
+
+        // within a single signature, the result becomes the accumulated point of the next row,
+        // and the next bit of e is appended to the accumulated value of e
+        let not_last_row = not(last_row_flag);
+        result[15] = not_last_row * are_equal(next[ACC_X], current[RESULT_X]);
+        result[16] = not_last_row * are_equal(next[ACC_Y], current[RESULT_Y]);
+        result[17] = are_equal(next[E_ACC], next[E_BIT] + not_last_row * current[E_ACC].double());
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // verification of every signature starts at the offset point with the most significant
+        // bit of e set to zero, and ends with the expected result and the value of e for that
+        // signature
+        let offset = AffinePoint::offset();
+        let last_step = CYCLE_LENGTH - 1;
+        vec![
+            Assertion::periodic(ACC_X, 0, CYCLE_LENGTH, offset.x),
+            Assertion::periodic(ACC_Y, 0, CYCLE_LENGTH, offset.y),
+            Assertion::periodic(E_ACC, 0, CYCLE_LENGTH, BaseElement::ZERO),
+            Assertion::sequence(E_ACC, last_step, CYCLE_LENGTH, self.challenges.clone()),
+            Assertion::sequence(
+                RESULT_X,
+                last_step,
+                CYCLE_LENGTH,
+                self.results.iter().map(|point| point.x).collect(),
+            ),
+            Assertion::sequence(
+                RESULT_Y,
+                last_step,
+                CYCLE_LENGTH,
+                self.results.iter().map(|point| point.y).collect(),
+            ),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut last_row_flags = vec![BaseElement::ZERO; CYCLE_LENGTH];
+        last_row_flags[CYCLE_LENGTH - 1] = BaseElement::ONE;
+        vec![last_row_flags]
+    }
+}
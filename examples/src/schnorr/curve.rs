@@ -0,0 +1,131 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::math::{fields::f128::BaseElement, FieldElement, StarkField};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Coefficient b of the curve equation y^2 = x^3 + b.
+pub const CURVE_B: BaseElement = BaseElement::new(7);
+
+/// Order of the curve group.
+///
+/// Since the modulus p of the base field is 2 mod 3, cubing is a bijection over the field, and
+/// thus, for every y there is exactly one x such that (x, y) is on the curve. Together with the
+/// point at infinity, the curve contains exactly p + 1 points. Such curves are supersingular, and
+/// discrete logarithms on them can be reduced to discrete logarithms in a quadratic extension of
+/// the base field; thus, this curve is suitable for illustration purposes only and should never
+/// be used to secure real signatures.
+pub const GROUP_ORDER: u128 = BaseElement::MODULUS + 1;
+
+// AFFINE POINT
+// ================================================================================================
+
+/// A point on the curve y^2 = x^3 + 7 defined over the base field, represented by its affine
+/// coordinates; the point at infinity cannot be represented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AffinePoint {
+    pub x: BaseElement,
+    pub y: BaseElement,
+}
+
+impl AffinePoint {
+    /// Returns the curve point with the specified y coordinate.
+    pub fn from_y(y: BaseElement) -> Self {
+        // since p = 2 mod 3, the cube root of v is v^((2p - 1) / 3)
+        let exp = 2 * ((BaseElement::MODULUS - 1) / 3) + 1;
+        let x = (y.square() - CURVE_B).exp(exp);
+        let point = Self { x, y };
+        debug_assert!(point.is_on_curve());
+        point
+    }
+
+    /// Returns the generator point used for signatures.
+    pub fn generator() -> Self {
+        Self::from_y(BaseElement::ONE)
+    }
+
+    /// Returns the point from which scalar multiplication starts in the AIR; starting from this
+    /// point (rather than from the point at infinity) allows using affine coordinates throughout.
+    pub fn offset() -> Self {
+        Self::from_y(BaseElement::new(2))
+    }
+
+    /// Returns true if this point is on the curve.
+    pub fn is_on_curve(&self) -> bool {
+        self.y.square() == self.x.cube() + CURVE_B
+    }
+
+    /// Returns the negation of this point.
+    pub fn neg(&self) -> Self {
+        Self {
+            x: self.x,
+            y: -self.y,
+        }
+    }
+
+    /// Returns the sum of this point and the `other` point; the points must be distinct and must
+    /// not be negations of each other.
+    pub fn add(&self, other: &Self) -> Self {
+        let slope = (other.y - self.y) / (other.x - self.x);
+        let x = slope.square() - self.x - other.x;
+        let y = slope * (self.x - x) - self.y;
+        Self { x, y }
+    }
+
+    /// Returns the double of this point.
+    pub fn double(&self) -> Self {
+        let slope = (BaseElement::new(3) * self.x.square()) / self.y.double();
+        let x = slope.square() - self.x.double();
+        let y = slope * (self.x - x) - self.y;
+        Self { x, y }
+    }
+
+    /// Returns this point multiplied by the specified scalar; the result must not be the point
+    /// at infinity.
+    pub fn mul(&self, scalar: u128) -> Self {
+        // start from the offset point to avoid the point at infinity, and subtract the doubled
+        // offset at the end
+        let mut result = Self::offset();
+        for i in (0..128).rev() {
+            result = result.double();
+            if (scalar >> i) & 1 == 1 {
+                result = result.add(self);
+            }
+        }
+        result.add(&Self::offset().mul_pow2(128).neg())
+    }
+
+    /// Returns this point multiplied by 2^`n`.
+    pub fn mul_pow2(&self, n: usize) -> Self {
+        (0..n).fold(*self, |point, _| point.double())
+    }
+}
+
+// SCALAR ARITHMETIC
+// ================================================================================================
+
+/// Returns (a + b) mod the group order; a and b must be smaller than the group order.
+pub fn add_scalars(a: u128, b: u128) -> u128 {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow || sum >= GROUP_ORDER {
+        sum.wrapping_sub(GROUP_ORDER)
+    } else {
+        sum
+    }
+}
+
+/// Returns (a * b) mod the group order; a and b must be smaller than the group order.
+pub fn mul_scalars(a: u128, b: u128) -> u128 {
+    (0..128).rev().fold(0, |result, i| {
+        let result = add_scalars(result, result);
+        if (b >> i) & 1 == 1 {
+            add_scalars(result, a)
+        } else {
+            result
+        }
+    })
+}
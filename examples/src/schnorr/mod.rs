@@ -0,0 +1,226 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+pub(crate) mod curve;
+use curve::AffinePoint;
+
+mod signature;
+use signature::{PrivateKey, PublicKey, Signature};
+
+mod air;
+use air::{PublicInputs, SchnorrAir};
+
+mod prover;
+use prover::SchnorrProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of trace rows used to verify a single signature; each row processes a single bit of
+/// the scalars, and scalars are at most 128 bits long.
+const CYCLE_LENGTH: usize = 128;
+
+// TRACE LAYOUT
+// ================================================================================================
+// A signature (R, s) over a message is verified by computing s * G - e * P and checking that the
+// result is equal to R. Both scalar multiplications are performed together, one bit per row,
+// starting from the most significant bit. To avoid the point at infinity, the computation starts
+// from a fixed offset point T rather than from the point at infinity, and thus, the result after
+// processing all 128 bits is R + 2^128 * T.
+//
+// On every row, the accumulated point A is doubled, G is added to the result if the current bit
+// of s is one, and then -P is added to the result if the current bit of e is one. Every addition
+// and doubling is verified using the slope of the line through the points, which is supplied as
+// an extra column.
+//
+// The slope-based addition formulas are incomplete: if both points have the same x coordinate,
+// the slope constraint is satisfied by any slope when the points are equal, which would let a
+// malicious prover choose the resulting point freely. To rule this out, every addition is also
+// supplied with the inverse of the difference of the x coordinates, and the constraints require
+// the difference to be non-zero; together with the slope constraint, this uniquely determines
+// the resulting point. Doubling needs no such check: a point with y = 0 has a non-zero x
+// coordinate on this curve, and thus, the doubling slope constraint cannot be satisfied for it.
+// As a consequence, a proof cannot be generated for signatures for which one of the additions
+// hits an exceptional case; for honest signatures, this occurs with negligible probability.
+
+/// Coordinates of the accumulated point at the start of the row.
+const ACC_X: usize = 0;
+const ACC_Y: usize = 1;
+
+/// Current bits of scalars s and e.
+const S_BIT: usize = 2;
+const E_BIT: usize = 3;
+
+/// Value of scalar e composed from all bits processed so far, including the current bit.
+const E_ACC: usize = 4;
+
+/// Slope and coordinates of the doubled accumulated point D = 2 * A.
+const DOUBLE_SLOPE: usize = 5;
+const DOUBLE_X: usize = 6;
+const DOUBLE_Y: usize = 7;
+
+/// Slope, inverse of the difference of x coordinates, and coordinates of the point D + G.
+const ADD_G_SLOPE: usize = 8;
+const ADD_G_INV: usize = 9;
+const ADD_G_X: usize = 10;
+const ADD_G_Y: usize = 11;
+
+/// Slope, inverse of the difference of x coordinates, and coordinates of the point S - P, where
+/// S is either D or D + G, depending on the current bit of s.
+const ADD_P_SLOPE: usize = 12;
+const ADD_P_INV: usize = 13;
+const ADD_P_X: usize = 14;
+const ADD_P_Y: usize = 15;
+
+/// Coordinates of the accumulated point at the end of the row.
+const RESULT_X: usize = 16;
+const RESULT_Y: usize = 17;
+
+/// Total number of columns in the execution trace.
+const TRACE_WIDTH: usize = 18;
+
+// SCHNORR SIGNATURE EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_signatures: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(42, 4);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(SchnorrExample::<Blake3_192>::new(num_signatures, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(SchnorrExample::<Blake3_256>::new(num_signatures, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(SchnorrExample::<Sha3_256>::new(num_signatures, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct SchnorrExample<H: ElementHasher> {
+    options: ProofOptions,
+    pub_key: PublicKey,
+    messages: Vec<BaseElement>,
+    signatures: Vec<Signature>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SchnorrExample<H> {
+    pub fn new(num_signatures: usize, options: ProofOptions) -> Self {
+        assert!(num_signatures.is_power_of_two(), "number of signatures must be a power of 2");
+
+        // sign all messages with the same private key
+        let now = Instant::now();
+        let private_key = PrivateKey::from_seed([1; 32]);
+        let messages =
+            (0..num_signatures as u128).map(|i| BaseElement::new(i + 1)).collect::<Vec<_>>();
+        let signatures =
+            messages.iter().map(|&message| private_key.sign(message)).collect::<Vec<_>>();
+        debug!("Signed {} messages in {} ms", num_signatures, now.elapsed().as_millis(),);
+
+        // make sure all signatures are valid
+        let pub_key = private_key.pub_key();
+        for (message, signature) in messages.iter().zip(signatures.iter()) {
+            assert!(pub_key.verify(*message, signature), "invalid signature");
+        }
+
+        SchnorrExample {
+            options,
+            pub_key,
+            messages,
+            signatures,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for SchnorrExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for verifying {} Schnorr signatures\n\
+            ---------------------",
+            self.signatures.len()
+        );
+
+        // create a prover
+        let prover = SchnorrProver::<H>::new(
+            self.pub_key,
+            self.messages.clone(),
+            self.signatures.clone(),
+            self.options.clone(),
+        );
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            pub_key: self.pub_key,
+            messages: self.messages.clone(),
+            nonces: self.signatures.iter().map(|signature| signature.nonce).collect(),
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<SchnorrAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut messages = self.messages.clone();
+        messages[0] += BaseElement::ONE;
+        let pub_inputs = PublicInputs {
+            pub_key: self.pub_key,
+            messages,
+            nonces: self.signatures.iter().map(|signature| signature.nonce).collect(),
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<SchnorrAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
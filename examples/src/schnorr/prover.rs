@@ -0,0 +1,192 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    signature::get_challenge, AffinePoint, BaseElement, DefaultRandomCoin, ElementHasher,
+    FieldElement, PhantomData, ProofOptions, Prover, PublicInputs, PublicKey, SchnorrAir,
+    Signature, ACC_X, ACC_Y, ADD_G_INV, ADD_G_SLOPE, ADD_G_X, ADD_G_Y, ADD_P_INV, ADD_P_SLOPE,
+    ADD_P_X, ADD_P_Y, CYCLE_LENGTH, DOUBLE_SLOPE, DOUBLE_X, DOUBLE_Y, E_ACC, E_BIT, RESULT_X,
+    RESULT_Y, S_BIT, TRACE_WIDTH,
+};
+use winterfell::{
+    matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// SCHNORR PROVER
+// ================================================================================================
+
+pub struct SchnorrProver<H: ElementHasher> {
+    pub_key: PublicKey,
+    messages: Vec<BaseElement>,
+    signatures: Vec<Signature>,
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SchnorrProver<H> {
+    pub fn new(
+        pub_key: PublicKey,
+        messages: Vec<BaseElement>,
+        signatures: Vec<Signature>,
+        options: ProofOptions,
+    ) -> Self {
+        assert_eq!(messages.len(), signatures.len(), "every message must have a signature");
+        Self {
+            pub_key,
+            messages,
+            signatures,
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for verifying all signatures provided to this prover; each
+    /// signature is verified in a separate cycle of 128 steps.
+    pub fn build_trace(&self) -> TraceTable<BaseElement> {
+        // compute scalars s and e for all signatures
+        let scalars = self
+            .messages
+            .iter()
+            .zip(self.signatures.iter())
+            .map(|(&message, signature)| {
+                (signature.s, get_challenge(&self.pub_key, &signature.nonce, message))
+            })
+            .collect::<Vec<_>>();
+
+        // allocate memory to hold the trace table
+        let trace_length = self.signatures.len() * CYCLE_LENGTH;
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+        let neg_pub_key = self.pub_key.0.neg();
+        trace.fill(
+            |row| {
+                let (s, e) = scalars[0];
+                fill_row(row, AffinePoint::offset(), s, e, 0, &neg_pub_key);
+            },
+            |step, row| {
+                // the accumulated point is either the result of the previous row or, at the
+                // start of a new signature, the offset point
+                let step = step + 1;
+                let acc = if step % CYCLE_LENGTH == 0 {
+                    AffinePoint::offset()
+                } else {
+                    AffinePoint {
+                        x: row[RESULT_X],
+                        y: row[RESULT_Y],
+                    }
+                };
+                let (s, e) = scalars[step / CYCLE_LENGTH];
+                fill_row(row, acc, s, e, step % CYCLE_LENGTH, &neg_pub_key);
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for SchnorrProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = SchnorrAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        PublicInputs {
+            pub_key: self.pub_key,
+            messages: self.messages.clone(),
+            nonces: self.signatures.iter().map(|signature| signature.nonce).collect(),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Fills in the specified row of the trace for processing bits of scalars `s` and `e` at the
+/// specified step of a signature verification cycle; bits are processed starting from the most
+/// significant one.
+///
+/// If the points of one of the additions have the same x coordinate, the row will not satisfy
+/// the constraints of the AIR.
+fn fill_row(
+    row: &mut [BaseElement],
+    acc: AffinePoint,
+    s: u128,
+    e: u128,
+    step: usize,
+    neg_pub_key: &AffinePoint,
+) {
+    let shift = CYCLE_LENGTH - 1 - step;
+    let s_bit = (s >> shift) & 1;
+    let e_bit = (e >> shift) & 1;
+
+    row[ACC_X] = acc.x;
+    row[ACC_Y] = acc.y;
+    row[S_BIT] = BaseElement::from(s_bit);
+    row[E_BIT] = BaseElement::from(e_bit);
+    row[E_ACC] = BaseElement::from(e >> shift);
+
+    // D = 2 * A
+    let double = acc.double();
+    row[DOUBLE_SLOPE] = BaseElement::new(3) * acc.x.square() / acc.y.double();
+    row[DOUBLE_X] = double.x;
+    row[DOUBLE_Y] = double.y;
+
+    // D + G
+    let generator = AffinePoint::generator();
+    let add_g = double.add(&generator);
+    row[ADD_G_SLOPE] = get_slope(&double, &generator);
+    row[ADD_G_INV] = (generator.x - double.x).inv();
+    row[ADD_G_X] = add_g.x;
+    row[ADD_G_Y] = add_g.y;
+
+    // S - P
+    let selected = if s_bit == 1 { add_g } else { double };
+    let add_p = selected.add(neg_pub_key);
+    row[ADD_P_SLOPE] = get_slope(&selected, neg_pub_key);
+    row[ADD_P_INV] = (neg_pub_key.x - selected.x).inv();
+    row[ADD_P_X] = add_p.x;
+    row[ADD_P_Y] = add_p.y;
+
+    let result = if e_bit == 1 { add_p } else { selected };
+    row[RESULT_X] = result.x;
+    row[RESULT_Y] = result.y;
+}
+
+/// Returns the slope of the line through the specified points.
+fn get_slope(a: &AffinePoint, b: &AffinePoint) -> BaseElement {
+    (b.y - a.y) / (b.x - a.x)
+}
@@ -0,0 +1,99 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::curve::{add_scalars, mul_scalars, AffinePoint, GROUP_ORDER};
+use crate::Blake3_256;
+use winterfell::{
+    crypto::{Digest, ElementHasher, Hasher},
+    math::{fields::f128::BaseElement, StarkField},
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bits in a signature challenge; challenges are smaller than the group order, and
+/// thus, do not need to be reduced.
+pub const CHALLENGE_BITS: usize = 126;
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+pub struct PrivateKey {
+    secret: u128,
+    pub_key: PublicKey,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(pub AffinePoint);
+
+/// A Schnorr signature (R, s) such that s * G = R + e * P, where P is the public key of the
+/// signer, and e = H(R, P, message) is the signature challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    pub nonce: AffinePoint,
+    pub s: u128,
+}
+
+// PRIVATE KEY IMPLEMENTATION
+// ================================================================================================
+
+impl PrivateKey {
+    /// Returns a private key generated from the specified `seed`.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let secret = hash_to_scalar(&seed);
+        let pub_key = PublicKey(AffinePoint::generator().mul(secret));
+        PrivateKey { secret, pub_key }
+    }
+
+    /// Returns a public key corresponding to this private key.
+    pub fn pub_key(&self) -> PublicKey {
+        self.pub_key
+    }
+
+    /// Signs the specified `message` with this private key; the nonce is derived
+    /// deterministically from the private key and the message.
+    pub fn sign(&self, message: BaseElement) -> Signature {
+        let mut nonce_seed = self.secret.to_le_bytes().to_vec();
+        nonce_seed.extend_from_slice(&message.as_int().to_le_bytes());
+        let k = hash_to_scalar(&nonce_seed);
+
+        let nonce = AffinePoint::generator().mul(k);
+        let e = get_challenge(&self.pub_key, &nonce, message);
+        let s = add_scalars(k, mul_scalars(e, self.secret));
+        Signature { nonce, s }
+    }
+}
+
+// PUBLIC KEY IMPLEMENTATION
+// ================================================================================================
+
+impl PublicKey {
+    /// Returns true if the provided signature is a valid signature of the specified `message`
+    /// against this public key.
+    pub fn verify(&self, message: BaseElement, signature: &Signature) -> bool {
+        let e = get_challenge(self, &signature.nonce, message);
+        let lhs = AffinePoint::generator().mul(signature.s);
+        let rhs = signature.nonce.add(&self.0.mul(e));
+        lhs == rhs
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the challenge e = H(R, P, message) for a signature with the specified nonce R
+/// against public key P.
+pub fn get_challenge(pub_key: &PublicKey, nonce: &AffinePoint, message: BaseElement) -> u128 {
+    let digest = Blake3_256::hash_elements(&[nonce.x, nonce.y, pub_key.0.x, pub_key.0.y, message]);
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&digest.as_bytes()[..16]);
+    u128::from_le_bytes(bytes) >> (128 - CHALLENGE_BITS)
+}
+
+fn hash_to_scalar(bytes: &[u8]) -> u128 {
+    let mut scalar = [0; 16];
+    scalar.copy_from_slice(&Blake3_256::hash(bytes).as_bytes()[..16]);
+    u128::from_le_bytes(scalar) % GROUP_ORDER
+}
@@ -0,0 +1,87 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    AffinePoint, BaseElement, Blake3_256, FieldElement, PrivateKey, PublicInputs, SchnorrAir,
+    ADD_G_INV, ADD_G_SLOPE, ADD_G_X, ADD_G_Y, CYCLE_LENGTH, DOUBLE_X, DOUBLE_Y, TRACE_WIDTH,
+};
+use winterfell::{Air, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo};
+
+#[test]
+fn schnorr_sign_and_verify() {
+    let generator = AffinePoint::generator();
+    assert!(generator.is_on_curve());
+    assert!(AffinePoint::offset().is_on_curve());
+
+    let private_key = PrivateKey::from_seed([1; 32]);
+    let pub_key = private_key.pub_key();
+    assert!(pub_key.0.is_on_curve());
+
+    let message = BaseElement::new(42);
+    let signature = private_key.sign(message);
+    assert!(pub_key.verify(message, &signature));
+    assert!(!pub_key.verify(message + BaseElement::ONE, &signature));
+}
+
+#[test]
+fn schnorr_test_basic_proof_verification() {
+    let schnorr_eg = Box::new(super::SchnorrExample::<Blake3_256>::new(2, build_options(false)));
+    crate::tests::test_basic_proof_verification(schnorr_eg);
+}
+
+#[test]
+fn schnorr_test_basic_proof_verification_extension() {
+    let schnorr_eg = Box::new(super::SchnorrExample::<Blake3_256>::new(2, build_options(true)));
+    crate::tests::test_basic_proof_verification(schnorr_eg);
+}
+
+#[test]
+fn schnorr_test_basic_proof_verification_fail() {
+    let schnorr_eg = Box::new(super::SchnorrExample::<Blake3_256>::new(2, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(schnorr_eg);
+}
+
+#[test]
+fn schnorr_exceptional_addition() {
+    let private_key = PrivateKey::from_seed([1; 32]);
+    let message = BaseElement::new(1);
+    let pub_inputs = PublicInputs {
+        pub_key: private_key.pub_key(),
+        messages: vec![message],
+        nonces: vec![private_key.sign(message).nonce],
+    };
+    let trace_info = TraceInfo::new(TRACE_WIDTH, CYCLE_LENGTH);
+    let air = SchnorrAir::new(trace_info, pub_inputs, build_options(false));
+
+    // when the doubled point is equal to G, any slope satisfies the slope constraint of the
+    // addition of G; the sum of the points is then determined by the chosen slope, but the
+    // constraint on the difference of x coordinates cannot be satisfied
+    let generator = AffinePoint::generator();
+    let slope = BaseElement::new(5);
+    let mut row = vec![BaseElement::ZERO; TRACE_WIDTH];
+    row[DOUBLE_X] = generator.x;
+    row[DOUBLE_Y] = generator.y;
+    row[ADD_G_SLOPE] = slope;
+    row[ADD_G_X] = slope.square() - generator.x.double();
+    row[ADD_G_Y] = slope * (generator.x - row[ADD_G_X]) - generator.y;
+
+    for inv in [BaseElement::ZERO, BaseElement::ONE, BaseElement::new(42)] {
+        row[ADD_G_INV] = inv;
+        let frame = EvaluationFrame::from_rows(row.clone(), row.clone());
+        let mut result = vec![BaseElement::ZERO; air.context().num_main_transition_constraints()];
+        air.evaluate_transition(&frame, &[BaseElement::ZERO], &mut result);
+        assert_eq!(&[BaseElement::ZERO; 3], &result[4..7]);
+        assert_ne!(BaseElement::ZERO, result[3]);
+    }
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -0,0 +1,297 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    sha256::{BLOCK_SIZE, INIT_STATE, NUM_ROUNDS, ROUND_CONSTANTS, STATE_WIDTH},
+    state_bit, BaseElement, FieldElement, ProofOptions, CARRY_A_OFFSET, CARRY_E_OFFSET,
+    CARRY_FINAL_OFFSET, CARRY_W_OFFSET, CYCLE_LENGTH, INIT_STATE_OFFSET, SCHEDULE_OFFSET,
+    STATE_OFFSET, TRACE_WIDTH, W0_BITS_OFFSET, W14_BITS_OFFSET,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of constraints which enforce that bit columns contain binary values.
+const NUM_BINARY_CONSTRAINTS: usize = STATE_WIDTH * 32 + TRACE_WIDTH - W0_BITS_OFFSET;
+
+/// Index of the first constraint which enforces bit decomposition of message schedule words.
+const DECOMPOSITION_CONSTRAINTS: usize = NUM_BINARY_CONSTRAINTS;
+
+/// Index of the first constraint which enforces shifting of the message schedule.
+const SCHEDULE_CONSTRAINTS: usize = DECOMPOSITION_CONSTRAINTS + 2;
+
+/// Index of the first constraint which enforces a single round of the compression function.
+const ROUND_CONSTRAINTS: usize = SCHEDULE_CONSTRAINTS + BLOCK_SIZE;
+
+/// Index of the first constraint which enforces transition of the initial compression state.
+const INIT_STATE_CONSTRAINTS: usize = ROUND_CONSTRAINTS + STATE_WIDTH;
+
+/// Total number of transition constraints.
+const NUM_CONSTRAINTS: usize = INIT_STATE_CONSTRAINTS + STATE_WIDTH;
+
+/// Working state words which, during a round, are set to the preceding word of the working state.
+const SHIFTED_WORDS: [usize; 6] = [1, 2, 3, 5, 6, 7];
+
+// SHA-256 AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub result: [u32; STATE_WIDTH],
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.result.iter().map(|&word| BaseElement::from(word)).collect()
+    }
+}
+
+pub struct Sha256Air {
+    context: AirContext<BaseElement>,
+    result: [u32; STATE_WIDTH],
+}
+
+impl Air for Sha256Air {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let flagged_degree = TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH]);
+        let mut degrees = vec![TransitionConstraintDegree::new(2); NUM_BINARY_CONSTRAINTS];
+        degrees.resize(SCHEDULE_CONSTRAINTS, TransitionConstraintDegree::new(1));
+        degrees.resize(ROUND_CONSTRAINTS - 1, flagged_degree.clone());
+        degrees.push(TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]));
+        for word in 0..STATE_WIDTH {
+            if SHIFTED_WORDS.contains(&word) {
+                degrees.push(flagged_degree.clone());
+            } else {
+                degrees.push(TransitionConstraintDegree::new(3));
+            }
+        }
+        degrees.resize(NUM_CONSTRAINTS, flagged_degree);
+
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        Sha256Air {
+            context: AirContext::new(trace_info, degrees, STATE_WIDTH * 34, options),
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into the flag for the last round and the round constant
+        let last_round_flag = periodic_values[0];
+        let round_constant = periodic_values[1];
+        let two_32 = E::from(1u64 << 32);
+
+        // all bit columns must contain binary values
+        for i in 0..STATE_WIDTH * 32 {
+            result[i] = is_binary(current[STATE_OFFSET + i]);
+        }
+        for i in 0..NUM_BINARY_CONSTRAINTS - STATE_WIDTH * 32 {
+            result[STATE_WIDTH * 32 + i] = is_binary(current[W0_BITS_OFFSET + i]);
+        }
+
+        // words 0 and 14 of the message schedule must be equal to the words composed from their
+        // bits; this also guarantees that these words are 32-bit values
+        let state = |word: usize| &current[state_bit(word, 0)..state_bit(word + 1, 0)];
+        let schedule = &current[SCHEDULE_OFFSET..SCHEDULE_OFFSET + BLOCK_SIZE];
+        let w0 = &current[W0_BITS_OFFSET..W0_BITS_OFFSET + 32];
+        let w14 = &current[W14_BITS_OFFSET..W14_BITS_OFFSET + 32];
+        result[DECOMPOSITION_CONSTRAINTS] = are_equal(schedule[0], compose_word(w0));
+        result[DECOMPOSITION_CONSTRAINTS + 1] = are_equal(schedule[14], compose_word(w14));
+
+        // within a compression, the message schedule is shifted by one word, and the new word is
+        // computed as W[15] = sigma1(W[14]) + W[9] + sigma0(W[1]) + W[0]; once compression is
+        // completed, the message schedule of the next message block can be arbitrary
+        let not_last_round = not(last_round_flag);
+        for i in 0..BLOCK_SIZE - 1 {
+            result[SCHEDULE_CONSTRAINTS + i] = not_last_round
+                * are_equal(next[SCHEDULE_OFFSET + i], current[SCHEDULE_OFFSET + i + 1]);
+        }
+        let next_w0 = &next[W0_BITS_OFFSET..W0_BITS_OFFSET + 32];
+        let next_word = small_sigma1(w14) + schedule[9] + small_sigma0(next_w0) + schedule[0];
+        let next_word_carry = compose_word(&current[CARRY_W_OFFSET..CARRY_W_OFFSET + 2]);
+        result[SCHEDULE_CONSTRAINTS + BLOCK_SIZE - 1] = not_last_round
+            * are_equal(
+                next[SCHEDULE_OFFSET + BLOCK_SIZE - 1] + next_word_carry * two_32,
+                next_word,
+            );
+
+        // a round of the compression function computes:
+        //   T1 = h + Sigma1(e) + Ch(e, f, g) + K + W[0]
+        //   T2 = Sigma0(a) + Maj(a, b, c)
+        //   (a, b, c, d, e, f, g, h) = (T1 + T2, a, b, c, d + T1, e, f, g)
+        // during the last round of a compression, the initial state of the compression is added
+        // to the result as well; all additions are modulo 2^32, with carries supplied in
+        // dedicated columns
+        let next_state =
+            |word: usize| compose_word(&next[state_bit(word, 0)..state_bit(word + 1, 0)]);
+        let init_state = &current[INIT_STATE_OFFSET..INIT_STATE_OFFSET + STATE_WIDTH];
+        let t1 = compose_word(state(7))
+            + big_sigma1(state(4))
+            + ch(state(4), state(5), state(6))
+            + round_constant
+            + schedule[0];
+        let t2 = big_sigma0(state(0)) + maj(state(0), state(1), state(2));
+
+        let carry = compose_word(&current[CARRY_A_OFFSET..CARRY_A_OFFSET + 3]);
+        result[ROUND_CONSTRAINTS] =
+            are_equal(next_state(0) + carry * two_32, t1 + t2 + last_round_flag * init_state[0]);
+
+        let carry = compose_word(&current[CARRY_E_OFFSET..CARRY_E_OFFSET + 3]);
+        result[ROUND_CONSTRAINTS + 4] = are_equal(
+            next_state(4) + carry * two_32,
+            compose_word(state(3)) + t1 + last_round_flag * init_state[4],
+        );
+
+        for (i, &word) in SHIFTED_WORDS.iter().enumerate() {
+            let carry = current[CARRY_FINAL_OFFSET + i];
+            result[ROUND_CONSTRAINTS + word] = are_equal(
+                next_state(word),
+                compose_word(state(word - 1))
+                    + last_round_flag * (init_state[word] - carry * two_32),
+            );
+        }
+
+        // once compression is completed, the resulting state becomes the initial state of the
+        // next compression; otherwise, the initial state is carried over to the next row
+        for (i, &value) in init_state.iter().enumerate() {
+            result[INIT_STATE_CONSTRAINTS + i] = are_equal(
+                next[INIT_STATE_OFFSET + i],
+                value + last_round_flag * (next_state(i) - value),
+            );
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the first compression starts with the initial hash value, and the initial state of the
+        // last (incomplete) compression is the result of all previous compressions
+        let last_cycle_step = self.trace_length() - CYCLE_LENGTH;
+        let mut assertions = Vec::with_capacity(STATE_WIDTH * 34);
+        for (word, &value) in INIT_STATE.iter().enumerate() {
+            for bit in 0..32 {
+                let bit_value = BaseElement::from((value >> bit) & 1);
+                assertions.push(Assertion::single(state_bit(word, bit), 0, bit_value));
+            }
+            assertions.push(Assertion::single(INIT_STATE_OFFSET + word, 0, value.into()));
+        }
+        for (word, &value) in self.result.iter().enumerate() {
+            assertions.push(Assertion::single(
+                INIT_STATE_OFFSET + word,
+                last_cycle_step,
+                value.into(),
+            ));
+        }
+        assertions
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut last_round_flags = vec![BaseElement::ZERO; NUM_ROUNDS];
+        last_round_flags[NUM_ROUNDS - 1] = BaseElement::ONE;
+        let round_constants = ROUND_CONSTANTS.iter().map(|&k| BaseElement::from(k)).collect();
+        vec![last_round_flags, round_constants]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a field element composed from the provided bits, where the first bit is the least
+/// significant bit.
+fn compose_word<E: FieldElement>(bits: &[E]) -> E {
+    bits.iter().rev().fold(E::ZERO, |word, &bit| word.double() + bit)
+}
+
+/// Returns a word composed from results of the specified function applied to every bit index.
+fn compose_bits<E: FieldElement>(bit: impl Fn(usize) -> E) -> E {
+    (0..32).rev().fold(E::ZERO, |word, i| word.double() + bit(i))
+}
+
+/// Returns a XOR of three binary values.
+fn xor3<E: FieldElement>(a: E, b: E, c: E) -> E {
+    let four = E::from(4u32);
+    a + b + c - (a * b + a * c + b * c).double() + four * a * b * c
+}
+
+/// Returns a bit of the word rotated right by `n` bits, or, if `shift` is true, shifted right
+/// by `n` bits.
+fn rotated_bit<E: FieldElement>(word: &[E], i: usize, n: usize, shift: bool) -> E {
+    if shift && i + n >= 32 {
+        E::ZERO
+    } else {
+        word[(i + n) % 32]
+    }
+}
+
+fn big_sigma0<E: FieldElement>(a: &[E]) -> E {
+    compose_bits(|i| {
+        xor3(
+            rotated_bit(a, i, 2, false),
+            rotated_bit(a, i, 13, false),
+            rotated_bit(a, i, 22, false),
+        )
+    })
+}
+
+fn big_sigma1<E: FieldElement>(e: &[E]) -> E {
+    compose_bits(|i| {
+        xor3(
+            rotated_bit(e, i, 6, false),
+            rotated_bit(e, i, 11, false),
+            rotated_bit(e, i, 25, false),
+        )
+    })
+}
+
+fn small_sigma0<E: FieldElement>(w: &[E]) -> E {
+    compose_bits(|i| {
+        xor3(
+            rotated_bit(w, i, 7, false),
+            rotated_bit(w, i, 18, false),
+            rotated_bit(w, i, 3, true),
+        )
+    })
+}
+
+fn small_sigma1<E: FieldElement>(w: &[E]) -> E {
+    compose_bits(|i| {
+        xor3(
+            rotated_bit(w, i, 17, false),
+            rotated_bit(w, i, 19, false),
+            rotated_bit(w, i, 10, true),
+        )
+    })
+}
+
+/// Computes Ch(e, f, g) = (e and f) xor (not(e) and g); since the two terms of the XOR cannot
+/// both be one, the XOR can be replaced with an addition.
+fn ch<E: FieldElement>(e: &[E], f: &[E], g: &[E]) -> E {
+    compose_bits(|i| e[i] * f[i] + not(e[i]) * g[i])
+}
+
+/// Computes Maj(a, b, c) = (a and b) xor (a and c) xor (b and c).
+fn maj<E: FieldElement>(a: &[E], b: &[E], c: &[E]) -> E {
+    compose_bits(|i| a[i] * b[i] + a[i] * c[i] + b[i] * c[i] - (a[i] * b[i] * c[i]).double())
+}
@@ -0,0 +1,213 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+#[allow(clippy::module_inception)]
+pub(crate) mod sha256;
+use sha256::{BLOCK_SIZE, STATE_WIDTH};
+
+mod air;
+use air::{PublicInputs, Sha256Air};
+
+mod prover;
+use prover::Sha256Prover;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of trace rows used by a single invocation of the compression function; each row
+/// executes a single round of the compression function.
+const CYCLE_LENGTH: usize = 64;
+
+// TRACE LAYOUT
+// ================================================================================================
+// Every row of the execution trace contains the working state at the start of a round, as well
+// as the values needed to verify a single round of the compression function:
+//
+// - 256 columns of bits of working state words a, b, c, d, e, f, g, h.
+// - 8 columns of words of the state at the start of the current compression (needed for the
+//   final addition).
+// - 16 columns of words of the message schedule; the first of these is the word used in the
+//   current round.
+// - 64 columns of bits of words 0 and 14 of the message schedule (needed to compute the next
+//   word of the message schedule).
+// - 14 columns of bits of carries resulting from 32-bit word additions.
+//
+// Since every word which is used in additions is either decomposed into bits, or is computed
+// from words decomposed into bits, all words are guaranteed to be in the range [0, 2^32).
+
+/// Index of the first column holding bits of working state words.
+const STATE_OFFSET: usize = 0;
+
+/// Index of the first column holding words of the state at the start of current compression.
+const INIT_STATE_OFFSET: usize = STATE_OFFSET + STATE_WIDTH * 32;
+
+/// Index of the first column holding words of the message schedule.
+const SCHEDULE_OFFSET: usize = INIT_STATE_OFFSET + STATE_WIDTH;
+
+/// Index of the first column holding bits of word 0 of the message schedule.
+const W0_BITS_OFFSET: usize = SCHEDULE_OFFSET + BLOCK_SIZE;
+
+/// Index of the first column holding bits of word 14 of the message schedule.
+const W14_BITS_OFFSET: usize = W0_BITS_OFFSET + 32;
+
+/// Index of the first column holding bits of the carry of the addition computing word a; the
+/// carry can be at most 7, and thus, occupies 3 bits.
+const CARRY_A_OFFSET: usize = W14_BITS_OFFSET + 32;
+
+/// Index of the first column holding bits of the carry of the addition computing word e; the
+/// carry can be at most 6, and thus, occupies 3 bits.
+const CARRY_E_OFFSET: usize = CARRY_A_OFFSET + 3;
+
+/// Index of the first column holding bits of the carry of the addition computing the next word
+/// of the message schedule; the carry can be at most 3, and thus, occupies 2 bits.
+const CARRY_W_OFFSET: usize = CARRY_E_OFFSET + 3;
+
+/// Index of the first column holding carries of the final additions for words b, c, d, f, g, h.
+const CARRY_FINAL_OFFSET: usize = CARRY_W_OFFSET + 2;
+
+/// Total number of columns in the execution trace.
+const TRACE_WIDTH: usize = CARRY_FINAL_OFFSET + 6;
+
+/// Returns index of the column holding the specified bit of the specified working state word.
+const fn state_bit(word: usize, bit: usize) -> usize {
+    STATE_OFFSET + word * 32 + bit
+}
+
+// SHA-256 COMPRESSION CHAIN EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_blocks: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(42, 4);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(Sha256Example::<Blake3_192>::new(num_blocks, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(Sha256Example::<Blake3_256>::new(num_blocks, options)))
+        }
+        HashFunction::Sha3_256 => Ok(Box::new(Sha256Example::<Sha3_256>::new(num_blocks, options))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct Sha256Example<H: ElementHasher> {
+    options: ProofOptions,
+    blocks: Vec<[u32; BLOCK_SIZE]>,
+    result: [u32; STATE_WIDTH],
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> Sha256Example<H> {
+    pub fn new(num_blocks: usize, options: ProofOptions) -> Self {
+        assert!(
+            (num_blocks + 1).is_power_of_two(),
+            "number of blocks must be one less than a power of 2"
+        );
+
+        // build the message blocks
+        let mut blocks = vec![[0; BLOCK_SIZE]; num_blocks];
+        for (i, block) in blocks.iter_mut().enumerate() {
+            for (j, word) in block.iter_mut().enumerate() {
+                *word = ((i * BLOCK_SIZE + j) as u32 + 1).wrapping_mul(0x9E3779B9);
+            }
+        }
+
+        // apply the compression function to all message blocks
+        let now = Instant::now();
+        let mut result = sha256::INIT_STATE;
+        for block in blocks.iter() {
+            sha256::compress(&mut result, block);
+        }
+        debug!(
+            "Computed a chain of {} SHA-256 compressions in {} ms",
+            num_blocks,
+            now.elapsed().as_millis(),
+        );
+
+        Sha256Example {
+            options,
+            blocks,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for Sha256Example<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for computing a chain of {} SHA-256 compressions\n\
+            ---------------------",
+            self.blocks.len()
+        );
+
+        // create a prover
+        let prover = Sha256Prover::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.blocks);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            result: self.result,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<Sha256Air, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut result = self.result;
+        result[0] ^= 1;
+        let pub_inputs = PublicInputs { result };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<Sha256Air, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    sha256::{self, BLOCK_SIZE, INIT_STATE, NUM_ROUNDS, ROUND_CONSTANTS, STATE_WIDTH},
+    state_bit, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, PhantomData,
+    ProofOptions, Prover, PublicInputs, Sha256Air, CARRY_A_OFFSET, CARRY_E_OFFSET,
+    CARRY_FINAL_OFFSET, CARRY_W_OFFSET, CYCLE_LENGTH, INIT_STATE_OFFSET, SCHEDULE_OFFSET,
+    TRACE_WIDTH, W0_BITS_OFFSET, W14_BITS_OFFSET,
+};
+use winterfell::{
+    math::StarkField, matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// SHA-256 PROVER
+// ================================================================================================
+
+pub struct Sha256Prover<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> Sha256Prover<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace of applying the compression function to all provided message
+    /// blocks; the trace is padded with an incomplete compression of an all-zero block, such
+    /// that its initial state is the result of compressing all message blocks.
+    pub fn build_trace(&self, blocks: &[[u32; BLOCK_SIZE]]) -> TraceTable<BaseElement> {
+        // allocate memory to hold the trace table
+        let trace_length = (blocks.len() + 1) * CYCLE_LENGTH;
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+        let get_block = |index: usize| blocks.get(index).copied().unwrap_or([0; BLOCK_SIZE]);
+
+        trace.fill(
+            |row| {
+                // initialize first state of the computation
+                write_state(row, INIT_STATE);
+                write_words(row, INIT_STATE_OFFSET, &INIT_STATE);
+                write_words(row, SCHEDULE_OFFSET, &get_block(0));
+                fill_round(row, 0);
+            },
+            |step, row| {
+                let round = step % CYCLE_LENGTH;
+                let mut state = read_state(row);
+                let mut init_state = read_words::<STATE_WIDTH>(row, INIT_STATE_OFFSET);
+                let mut schedule = read_words::<BLOCK_SIZE>(row, SCHEDULE_OFFSET);
+
+                // apply a round of the compression function; after the last round, add the
+                // initial state and start compressing the next message block
+                sha256::apply_round(&mut state, ROUND_CONSTANTS[round], schedule[0]);
+                if round == NUM_ROUNDS - 1 {
+                    for (word, value) in state.iter_mut().zip(init_state) {
+                        *word = word.wrapping_add(value);
+                    }
+                    init_state = state;
+                    schedule = get_block((step + 1) / CYCLE_LENGTH);
+                } else {
+                    let next_word = sha256::next_message_word(&schedule);
+                    schedule.copy_within(1.., 0);
+                    schedule[BLOCK_SIZE - 1] = next_word;
+                }
+
+                write_state(row, state);
+                write_words(row, INIT_STATE_OFFSET, &init_state);
+                write_words(row, SCHEDULE_OFFSET, &schedule);
+                fill_round(row, (step + 1) % CYCLE_LENGTH);
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for Sha256Prover<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = Sha256Air;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_cycle_step = trace.length() - CYCLE_LENGTH;
+        let mut result = [0; STATE_WIDTH];
+        for (i, word) in result.iter_mut().enumerate() {
+            *word = trace.get(INIT_STATE_OFFSET + i, last_cycle_step).as_int() as u32;
+        }
+        PublicInputs { result }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Fills in bits of message schedule words and carries needed to verify the specified round of
+/// the compression function applied to the working state in the provided row.
+///
+/// The carries of the final additions, which are needed only in the last round, are filled in
+/// for all rounds; this way, the values in these columns do not depend on the round.
+fn fill_round(row: &mut [BaseElement], round: usize) {
+    let [a, b, c, d, e, f, g, h] = read_state(row).map(u64::from);
+    let init_state = read_words::<STATE_WIDTH>(row, INIT_STATE_OFFSET).map(u64::from);
+    let schedule = read_words::<BLOCK_SIZE>(row, SCHEDULE_OFFSET);
+    let last_round = round == NUM_ROUNDS - 1;
+
+    write_bits(row, W0_BITS_OFFSET, schedule[0] as u64, 32);
+    write_bits(row, W14_BITS_OFFSET, schedule[14] as u64, 32);
+
+    // compute carries of the additions performed in the round
+    let t1 = h
+        + sha256::big_sigma1(e as u32) as u64
+        + sha256::ch(e as u32, f as u32, g as u32) as u64
+        + ROUND_CONSTANTS[round] as u64
+        + schedule[0] as u64;
+    let t2 = sha256::big_sigma0(a as u32) as u64 + sha256::maj(a as u32, b as u32, c as u32) as u64;
+    let init_a = if last_round { init_state[0] } else { 0 };
+    let init_e = if last_round { init_state[4] } else { 0 };
+    write_bits(row, CARRY_A_OFFSET, (t1 + t2 + init_a) >> 32, 3);
+    write_bits(row, CARRY_E_OFFSET, (d + t1 + init_e) >> 32, 3);
+
+    let next_word = sha256::small_sigma1(schedule[14]) as u64
+        + schedule[9] as u64
+        + sha256::small_sigma0(schedule[1]) as u64
+        + schedule[0] as u64;
+    write_bits(row, CARRY_W_OFFSET, next_word >> 32, 2);
+
+    let shifted = [a, b, c, e, f, g];
+    let init_shifted = [1, 2, 3, 5, 6, 7].map(|i| init_state[i]);
+    for (i, (word, init_word)) in shifted.into_iter().zip(init_shifted).enumerate() {
+        row[CARRY_FINAL_OFFSET + i] = BaseElement::from((word + init_word) >> 32);
+    }
+}
+
+fn read_state(row: &[BaseElement]) -> [u32; STATE_WIDTH] {
+    let mut state = [0; STATE_WIDTH];
+    for (i, word) in state.iter_mut().enumerate() {
+        *word =
+            (0..32).fold(0, |word, bit| word | ((row[state_bit(i, bit)].as_int() as u32) << bit));
+    }
+    state
+}
+
+fn write_state(row: &mut [BaseElement], state: [u32; STATE_WIDTH]) {
+    for (i, &word) in state.iter().enumerate() {
+        write_bits(row, state_bit(i, 0), word as u64, 32);
+    }
+}
+
+fn read_words<const N: usize>(row: &[BaseElement], offset: usize) -> [u32; N] {
+    let mut words = [0; N];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = row[offset + i].as_int() as u32;
+    }
+    words
+}
+
+fn write_words(row: &mut [BaseElement], offset: usize, words: &[u32]) {
+    for (i, &word) in words.iter().enumerate() {
+        row[offset + i] = BaseElement::from(word);
+    }
+}
+
+fn write_bits(row: &mut [BaseElement], offset: usize, value: u64, num_bits: usize) {
+    for i in 0..num_bits {
+        row[offset + i] = BaseElement::from((value >> i) & 1);
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of 32-bit words in SHA-256 state.
+pub const STATE_WIDTH: usize = 8;
+
+/// Number of 32-bit words in a message block.
+pub const BLOCK_SIZE: usize = 16;
+
+/// Number of rounds in a single invocation of SHA-256 compression function.
+pub const NUM_ROUNDS: usize = 64;
+
+/// Initial hash value of SHA-256.
+pub const INIT_STATE: [u32; STATE_WIDTH] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Round constants of SHA-256.
+pub const ROUND_CONSTANTS: [u32; NUM_ROUNDS] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// SHA-256 COMPRESSION FUNCTION
+// ================================================================================================
+
+/// Applies SHA-256 compression function to the provided state and message block.
+pub fn compress(state: &mut [u32; STATE_WIDTH], block: &[u32; BLOCK_SIZE]) {
+    // expand the message block into the message schedule
+    let mut schedule = [0; NUM_ROUNDS];
+    schedule[..BLOCK_SIZE].copy_from_slice(block);
+    for t in BLOCK_SIZE..NUM_ROUNDS {
+        schedule[t] = next_message_word(&schedule[t - BLOCK_SIZE..t]);
+    }
+
+    // apply the rounds and add the result to the initial state
+    let mut working = *state;
+    for (&k, &w) in ROUND_CONSTANTS.iter().zip(schedule.iter()) {
+        apply_round(&mut working, k, w);
+    }
+    for (word, value) in state.iter_mut().zip(working) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+/// Applies a single round of SHA-256 compression function with the specified round constant
+/// and message word to the provided working state.
+pub fn apply_round(state: &mut [u32; STATE_WIDTH], k: u32, w: u32) {
+    let [a, b, c, d, e, f, g, h] = *state;
+    let t1 = h
+        .wrapping_add(big_sigma1(e))
+        .wrapping_add(ch(e, f, g))
+        .wrapping_add(k)
+        .wrapping_add(w);
+    let t2 = big_sigma0(a).wrapping_add(maj(a, b, c));
+    *state = [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g];
+}
+
+/// Computes the next word of the message schedule from the preceding 16 words.
+pub fn next_message_word(window: &[u32]) -> u32 {
+    small_sigma1(window[14])
+        .wrapping_add(window[9])
+        .wrapping_add(small_sigma0(window[1]))
+        .wrapping_add(window[0])
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+pub fn ch(e: u32, f: u32, g: u32) -> u32 {
+    (e & f) ^ (!e & g)
+}
+
+pub fn maj(a: u32, b: u32, c: u32) -> u32 {
+    (a & b) ^ (a & c) ^ (b & c)
+}
+
+pub fn big_sigma0(a: u32) -> u32 {
+    a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22)
+}
+
+pub fn big_sigma1(e: u32) -> u32 {
+    e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25)
+}
+
+pub fn small_sigma0(w: u32) -> u32 {
+    w.rotate_right(7) ^ w.rotate_right(18) ^ (w >> 3)
+}
+
+pub fn small_sigma1(w: u32) -> u32 {
+    w.rotate_right(17) ^ w.rotate_right(19) ^ (w >> 10)
+}
@@ -0,0 +1,50 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{sha256, Blake3_256, BLOCK_SIZE};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn sha256_compression_test_vector() {
+    // a single padded block of the message "abc"
+    let mut block = [0; BLOCK_SIZE];
+    block[0] = 0x61626380;
+    block[15] = 0x18;
+
+    let mut state = sha256::INIT_STATE;
+    sha256::compress(&mut state, &block);
+    let expected = [
+        0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3, 0x96177a9c, 0xb410ff61,
+        0xf20015ad,
+    ];
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn sha256_test_basic_proof_verification() {
+    let sha256_eg = Box::new(super::Sha256Example::<Blake3_256>::new(3, build_options(false)));
+    crate::tests::test_basic_proof_verification(sha256_eg);
+}
+
+#[test]
+fn sha256_test_basic_proof_verification_extension() {
+    let sha256_eg = Box::new(super::Sha256Example::<Blake3_256>::new(3, build_options(true)));
+    crate::tests::test_basic_proof_verification(sha256_eg);
+}
+
+#[test]
+fn sha256_test_basic_proof_verification_fail() {
+    let sha256_eg = Box::new(super::Sha256Example::<Blake3_256>::new(3, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(sha256_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
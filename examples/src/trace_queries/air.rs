@@ -0,0 +1,136 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, FieldElement, ProofOptions, RpMerklePath, DIGEST_SIZE, INNER_TRACE_WIDTH,
+    SIBLING_OFFSET,
+};
+use winterfell::{math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Trace queries of the inner proof which are authenticated by the outer proof.
+#[derive(Clone)]
+pub struct PublicInputs {
+    /// Size of the LDE domain of the inner proof; this determines the depth of the trace
+    /// commitment tree.
+    pub lde_domain_size: usize,
+    /// Commitment to the main trace segment of the inner proof.
+    pub trace_root: [BaseElement; DIGEST_SIZE],
+    /// Positions of the LDE domain queried by the verifier of the inner proof.
+    pub positions: Vec<usize>,
+    /// Trace states of the inner proof at the queried positions.
+    pub states: Vec<[BaseElement; INNER_TRACE_WIDTH]>,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = vec![BaseElement::from(self.lde_domain_size as u64)];
+        result.extend_from_slice(&self.trace_root);
+        for (&position, state) in self.positions.iter().zip(self.states.iter()) {
+            result.push(BaseElement::from(position as u64));
+            result.extend_from_slice(state);
+        }
+        result
+    }
+}
+
+// RECURSIVE VERIFIER AIR
+// ================================================================================================
+
+pub struct TraceQueriesAir {
+    context: AirContext<BaseElement>,
+    depth: usize,
+    trace_root: [BaseElement; DIGEST_SIZE],
+    positions: Vec<usize>,
+    states: Vec<[BaseElement; INNER_TRACE_WIDTH]>,
+}
+
+impl Air for TraceQueriesAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let num_queries = pub_inputs.positions.len();
+        assert_eq!(num_queries, pub_inputs.states.len());
+        assert_eq!(num_queries * RpMerklePath::TRACE_WIDTH, trace_info.width());
+
+        // a path for every query requires its own copy of the component constraints; the depth
+        // includes the extra level for hashing the leaf
+        let depth = pub_inputs.lde_domain_size.ilog2() as usize + 1;
+        assert!(
+            RpMerklePath::trace_length(depth) <= trace_info.length(),
+            "execution trace is too short"
+        );
+        let degrees = (0..num_queries)
+            .flat_map(|_| RpMerklePath::transition_constraint_degrees())
+            .collect::<Vec<_>>();
+
+        // each path is bound to its leaf index, the trace state, and the trace root
+        let num_assertions = num_queries * (2 * INNER_TRACE_WIDTH + depth);
+        TraceQueriesAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            depth,
+            trace_root: pub_inputs.trace_root,
+            positions: pub_inputs.positions,
+            states: pub_inputs.states,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        // all paths share the same periodic columns
+        let columns = current
+            .chunks(RpMerklePath::TRACE_WIDTH)
+            .zip(next.chunks(RpMerklePath::TRACE_WIDTH));
+        for ((current, next), result) in
+            columns.zip(result.chunks_mut(RpMerklePath::NUM_CONSTRAINTS))
+        {
+            RpMerklePath::evaluate_transition(current, next, periodic_values, result);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut result = Vec::new();
+        for (i, (&position, state)) in self.positions.iter().zip(self.states.iter()).enumerate() {
+            let first_column = i * RpMerklePath::TRACE_WIDTH;
+
+            // a trace state is hashed by merging its first and its second half; the first half
+            // is the leaf of the path at index 2 * position, and the second half is its sibling
+            let (left, right) = state.split_at(DIGEST_SIZE);
+            let leaf = left.try_into().unwrap();
+            result.extend(RpMerklePath::get_assertions(
+                first_column,
+                0,
+                self.depth,
+                position << 1,
+                leaf,
+                self.trace_root,
+            ));
+            for (j, &value) in right.iter().enumerate() {
+                result.push(Assertion::single(first_column + SIBLING_OFFSET + j, 0, value));
+            }
+        }
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        RpMerklePath::get_periodic_column_values()
+    }
+}
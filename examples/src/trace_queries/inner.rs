@@ -0,0 +1,172 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, DefaultRandomCoin, FieldElement, ProofOptions, Prover, Rp64_256};
+use crate::utils::are_equal;
+use winterfell::{
+    matrix::ColMatrix, Air, AirContext, Assertion, AuxTraceRandElements,
+    ConstraintCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde,
+    EvaluationFrame, StarkDomain, Trace, TraceInfo, TracePolyTable, TraceTable,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Width of the execution trace of the inner computation.
+///
+/// With 8 columns, a row of the trace is hashed by [Rp64_256] in a single permutation starting
+/// from the capacity [8, 0, 0, 0]; this is exactly the initial state used by the Merkle path
+/// component when merging two nodes, and thus, the component hashes the leaves of the trace
+/// commitment as a part of the path.
+pub const TRACE_WIDTH: usize = 8;
+
+// INNER FIBONACCI AIR
+// ================================================================================================
+
+/// AIR of a Fibonacci sequence in which every row of the trace advances the sequence by 8 terms.
+pub struct InnerFibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for InnerFibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1); TRACE_WIDTH];
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        InnerFibAir {
+            context: AirContext::new(trace_info, degrees, TRACE_WIDTH + 1, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // the first two terms of the next row continue the sequence from the last two terms of
+        // the current row, and every other term of the next row is the sum of the two preceding
+        // terms
+        result[0] = are_equal(next[0], current[6] + current[7]);
+        result[1] = are_equal(next[1], current[7] + next[0]);
+        for i in 2..TRACE_WIDTH {
+            result[i] = are_equal(next[i], next[i - 2] + next[i - 1]);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the first row must contain the first 8 terms of the sequence, and the last term of the
+        // sequence must be equal to the expected result
+        let last_step = self.trace_length() - 1;
+        let mut result = get_first_row()
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| Assertion::single(i, 0, value))
+            .collect::<Vec<_>>();
+        result.push(Assertion::single(TRACE_WIDTH - 1, last_step, self.result));
+        result
+    }
+}
+
+// INNER FIBONACCI PROVER
+// ================================================================================================
+
+/// Prover of the inner computation; proofs are always generated using [Rp64_256] hash function
+/// so that they can be verified inside of another computation.
+pub struct InnerFibProver {
+    options: ProofOptions,
+}
+
+impl InnerFibProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    /// Builds an execution trace for computing a Fibonacci sequence of the specified length such
+    /// that each row advances the sequence by 8 terms.
+    pub fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+        assert!(sequence_length.is_power_of_two(), "sequence length must be a power of 2");
+
+        let mut trace = TraceTable::new(TRACE_WIDTH, sequence_length / TRACE_WIDTH);
+        trace.fill(
+            |state| state.copy_from_slice(&get_first_row()),
+            |_, state| {
+                state[0] = state[6] + state[7];
+                state[1] = state[7] + state[0];
+                for i in 2..TRACE_WIDTH {
+                    state[i] = state[i - 2] + state[i - 1];
+                }
+            },
+        );
+
+        trace
+    }
+}
+
+impl Prover for InnerFibProver {
+    type BaseField = BaseElement;
+    type Air = InnerFibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Rp64_256;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        let last_step = trace.length() - 1;
+        trace.get(TRACE_WIDTH - 1, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the first 8 terms of the Fibonacci sequence.
+fn get_first_row() -> [BaseElement; TRACE_WIDTH] {
+    let mut row = [BaseElement::ONE; TRACE_WIDTH];
+    for i in 2..TRACE_WIDTH {
+        row[i] = row[i - 2] + row[i - 1];
+    }
+    row
+}
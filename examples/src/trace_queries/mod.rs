@@ -0,0 +1,250 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Example, ExampleOptions, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    gadgets::RpMerklePath,
+    math::{fields::f64::BaseElement, FieldElement},
+    stages, AcceptableOptions, Air, ProofOptions, Prover, StarkProof, Trace, VerifierCostEstimate,
+    VerifierError,
+};
+
+mod inner;
+use inner::{InnerFibAir, InnerFibProver, TRACE_WIDTH as INNER_TRACE_WIDTH};
+
+mod air;
+use air::{PublicInputs, TraceQueriesAir};
+
+mod prover;
+use prover::TraceQueriesProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+/// Number of field elements in an Rp64_256 digest.
+const DIGEST_SIZE: usize = 4;
+
+/// Index of the first column of the second half of the rate in the Merkle path component; when
+/// merging two nodes, the right node is located in this and the next 3 columns.
+const SIBLING_OFFSET: usize = 2 * DIGEST_SIZE;
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+type RpJive64_256 = winterfell::crypto::hashers::RpJive64_256;
+type GriffinJive64_256 = winterfell::crypto::hashers::GriffinJive64_256;
+
+// TRACE QUERY AUTHENTICATION EXAMPLE
+// ================================================================================================
+// The inner computation is a Fibonacci sequence proved using Rp64_256 hash function. The outer
+// computation verifies a part of the inner proof: for every position queried by the verifier of
+// the inner proof, it proves that the trace state included in the inner proof at this position
+// is authenticated by the commitment to the trace of the inner proof. The outer proof can be
+// generated with any hash function supported by the 64-bit field.
+//
+// Authenticating a trace state requires hashing the state into a leaf of the trace commitment
+// tree and computing the root of the tree from the leaf and its authentication path. Since the
+// inner trace has 8 columns, hashing a state is the same as merging its two halves, and so the
+// whole computation is performed by the RpMerklePath component with one extra level below the
+// leaves of the tree. The components for different queries are placed side by side.
+//
+// This is only the first step towards recursive verification: the outer proof does not attest
+// that the inner proof is valid. The queried positions are derived outside of the outer proof by
+// replaying the transcript of the inner proof, and a recursive verifier would also need to verify
+// constraint and FRI layer queries, evaluate constraints at the out-of-domain point, and check
+// DEEP composition and FRI folding inside the outer proof. The costs of these steps are reported
+// by the example using VerifierCostEstimate.
+
+pub fn get_example(
+    options: &ExampleOptions,
+    sequence_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(TraceQueriesExample::<Blake3_192>::new(sequence_length, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(TraceQueriesExample::<Blake3_256>::new(sequence_length, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(TraceQueriesExample::<Sha3_256>::new(sequence_length, options)))
+        }
+        HashFunction::Rp64_256 => {
+            Ok(Box::new(TraceQueriesExample::<Rp64_256>::new(sequence_length, options)))
+        }
+        HashFunction::RpJive64_256 => {
+            Ok(Box::new(TraceQueriesExample::<RpJive64_256>::new(sequence_length, options)))
+        }
+        HashFunction::GriffinJive64_256 => Ok(Box::new(
+            TraceQueriesExample::<GriffinJive64_256>::new(sequence_length, options),
+        )),
+    }
+}
+
+/// A trace query of the inner proof together with its Merkle authentication path (from the
+/// sibling of the leaf towards the root).
+pub struct TraceQuery {
+    position: usize,
+    state: [BaseElement; INNER_TRACE_WIDTH],
+    path: Vec<[BaseElement; DIGEST_SIZE]>,
+}
+
+pub struct TraceQueriesExample<H: ElementHasher> {
+    options: ProofOptions,
+    pub_inputs: PublicInputs,
+    queries: Vec<TraceQuery>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> TraceQueriesExample<H> {
+    /// Generates an inner proof for computing a Fibonacci sequence of the specified length, and
+    /// extracts the trace queries of the inner proof to be verified by the outer proof.
+    ///
+    /// The inner proof is generated with the same proof options as the outer proof.
+    pub fn new(sequence_length: usize, options: ProofOptions) -> Self {
+        assert!(sequence_length.is_power_of_two(), "sequence length must be a power of 2");
+        assert!(sequence_length >= INNER_TRACE_WIDTH * 8, "sequence length must be at least 64");
+
+        // generate the inner proof
+        let now = Instant::now();
+        let inner_prover = InnerFibProver::new(options.clone());
+        let trace = inner_prover.build_trace(sequence_length);
+        let result = inner_prover.get_pub_inputs(&trace);
+        let inner_proof = inner_prover.prove(trace).unwrap();
+        debug!(
+            "Generated inner proof for computing Fibonacci sequence (8 terms per step) up to {}th \
+            term in {} ms",
+            sequence_length,
+            now.elapsed().as_millis()
+        );
+
+        // replay the transcript of the inner proof to get the queried positions, and split the
+        // trace openings into individual authentication paths
+        let lde_domain_size = inner_proof.lde_domain_size();
+        let acceptable_options = AcceptableOptions::OptionSet(vec![options.clone()]);
+        let positions = stages::get_query_positions::<
+            InnerFibAir,
+            Rp64_256,
+            DefaultRandomCoin<Rp64_256>,
+        >(inner_proof.clone(), result, &acceptable_options)
+        .expect("failed to replay inner proof transcript");
+
+        let num_fri_layers = options.to_fri_options().num_fri_layers(lde_domain_size);
+        let (trace_roots, _, _) =
+            inner_proof.commitments.clone().parse::<Rp64_256>(1, num_fri_layers).unwrap();
+        let (merkle_proof, states) = inner_proof.trace_queries[0]
+            .clone()
+            .parse::<Rp64_256, BaseElement>(lde_domain_size, positions.len(), INNER_TRACE_WIDTH)
+            .unwrap();
+        let paths = merkle_proof.into_paths(&positions).unwrap();
+
+        let queries = positions
+            .iter()
+            .zip(states.rows())
+            .zip(paths)
+            .map(|((&position, state), path)| TraceQuery {
+                position,
+                state: state.try_into().unwrap(),
+                path: path.iter().skip(1).map(to_elements).collect(),
+            })
+            .collect::<Vec<_>>();
+
+        let pub_inputs = PublicInputs {
+            lde_domain_size,
+            trace_root: to_elements(&trace_roots[0]),
+            positions,
+            states: queries.iter().map(|query| query.state).collect(),
+        };
+
+        // report the work needed to verify the entire inner proof
+        let inner_air = InnerFibAir::new(inner_proof.get_trace_info(), result, options.clone());
+        let cost = VerifierCostEstimate::new::<_, Rp64_256>(&inner_air);
+        debug!(
+            "Verifying the inner proof requires up to {} hashes ({} for Merkle paths, of which {} \
+            are for trace queries) and {} field multiplications",
+            cost.hash_invocations(),
+            cost.merkle_hashes,
+            pub_inputs.positions.len() * (lde_domain_size.ilog2() as usize + 1),
+            cost.field_mults
+        );
+
+        TraceQueriesExample {
+            options,
+            pub_inputs,
+            queries,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for TraceQueriesExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for verifying {} trace queries of the inner proof\n\
+            ---------------------",
+            self.queries.len()
+        );
+
+        // create a prover
+        let prover = TraceQueriesProver::<H>::new(self.options.clone(), self.pub_inputs.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.queries);
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace.length().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options = AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<TraceQueriesAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.pub_inputs.clone(),
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut pub_inputs = self.pub_inputs.clone();
+        pub_inputs.states[0][INNER_TRACE_WIDTH - 1] += BaseElement::ONE;
+        let acceptable_options = AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<TraceQueriesAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts an Rp64_256 digest into field elements.
+fn to_elements(digest: &<Rp64_256 as winterfell::crypto::Hasher>::Digest) -> [BaseElement; 4] {
+    digest.as_elements().try_into().unwrap()
+}
@@ -0,0 +1,102 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, PhantomData, ProofOptions, Prover,
+    PublicInputs, RpMerklePath, TraceQueriesAir, TraceQuery, DIGEST_SIZE,
+};
+use winterfell::{
+    matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// RECURSIVE VERIFIER PROVER
+// ================================================================================================
+
+pub struct TraceQueriesProver<H: ElementHasher> {
+    options: ProofOptions,
+    pub_inputs: PublicInputs,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> TraceQueriesProver<H> {
+    pub fn new(options: ProofOptions, pub_inputs: PublicInputs) -> Self {
+        Self {
+            options,
+            pub_inputs,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace which verifies Merkle authentication paths of all specified
+    /// trace queries against the trace commitment of the inner proof.
+    ///
+    /// Paths are placed side by side, and every path is extended with zero siblings until it
+    /// fills the whole trace; the extra levels are not bound by any assertions.
+    pub fn build_trace(&self, queries: &[TraceQuery]) -> TraceTable<BaseElement> {
+        let depth = self.pub_inputs.lde_domain_size.ilog2() as usize + 1;
+        let trace_length = RpMerklePath::trace_length(depth).next_power_of_two();
+        let num_levels = trace_length / RpMerklePath::CYCLE_LENGTH;
+
+        let mut columns = Vec::with_capacity(queries.len() * RpMerklePath::TRACE_WIDTH);
+        for query in queries {
+            // the second half of the trace state is the sibling of the first half at the
+            // extra level below the leaves of the trace commitment
+            let (left, right) = query.state.split_at(DIGEST_SIZE);
+            let mut path = vec![right.try_into().unwrap()];
+            path.extend_from_slice(&query.path);
+            path.resize(num_levels, [BaseElement::ZERO; DIGEST_SIZE]);
+
+            columns.extend(RpMerklePath::build_columns(
+                left.try_into().unwrap(),
+                query.position << 1,
+                &path,
+            ));
+        }
+
+        TraceTable::init(columns)
+    }
+}
+
+impl<H: ElementHasher> Prover for TraceQueriesProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = TraceQueriesAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        self.pub_inputs.clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Rp64_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn trace_queries_test_basic_proof_verification() {
+    let trace_queries_eg =
+        Box::new(super::TraceQueriesExample::<Rp64_256>::new(512, build_options(false)));
+    crate::tests::test_basic_proof_verification(trace_queries_eg);
+}
+
+#[test]
+fn trace_queries_test_basic_proof_verification_extension() {
+    let trace_queries_eg =
+        Box::new(super::TraceQueriesExample::<Rp64_256>::new(512, build_options(true)));
+    crate::tests::test_basic_proof_verification(trace_queries_eg);
+}
+
+#[test]
+fn trace_queries_test_basic_proof_verification_fail() {
+    let trace_queries_eg =
+        Box::new(super::TraceQueriesExample::<Rp64_256>::new(512, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(trace_queries_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -0,0 +1,162 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, FieldElement, ProofOptions, ACC_BITS_OFFSET, ACC_OFFSET, A_BITS_OFFSET,
+    B_BITS_OFFSET, CARRY_BITS_OFFSET, CARRY_HI_OFFSET, LIMB_BITS, NUM_LIMBS, TRACE_WIDTH, U256,
+};
+use crate::utils::{are_equal, is_binary};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of constraints which enforce that bit columns contain binary values.
+const NUM_BINARY_CONSTRAINTS: usize = CARRY_HI_OFFSET - NUM_LIMBS;
+
+/// Index of the first constraint which enforces ranges of the upper parts of carries.
+const CARRY_HI_CONSTRAINTS: usize = NUM_BINARY_CONSTRAINTS;
+
+/// Index of the first constraint which enforces bit decomposition of accumulator limbs.
+const DECOMPOSITION_CONSTRAINTS: usize = CARRY_HI_CONSTRAINTS + NUM_LIMBS - 1;
+
+/// Index of the first constraint which enforces computation of the limbs of the result.
+const MUL_ADD_CONSTRAINTS: usize = DECOMPOSITION_CONSTRAINTS + NUM_LIMBS;
+
+/// Total number of transition constraints.
+const NUM_CONSTRAINTS: usize = MUL_ADD_CONSTRAINTS + NUM_LIMBS;
+
+// U256 AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub result: U256,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.result.iter().map(|&limb| BaseElement::from(limb)).collect()
+    }
+}
+
+pub struct U256Air {
+    context: AirContext<BaseElement>,
+    result: U256,
+}
+
+impl Air for U256Air {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let mut degrees = vec![TransitionConstraintDegree::new(2); NUM_BINARY_CONSTRAINTS];
+        for limb in 1..NUM_LIMBS {
+            degrees.push(TransitionConstraintDegree::new(limb + 1));
+        }
+        degrees.resize(MUL_ADD_CONSTRAINTS, TransitionConstraintDegree::new(1));
+        degrees.resize(NUM_CONSTRAINTS, TransitionConstraintDegree::new(2));
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        U256Air {
+            context: AirContext::new(trace_info, degrees, NUM_LIMBS * 2, options),
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // all columns, except for the columns holding limbs of the accumulator and the upper
+        // parts of carries, must be binary
+        let bits = current[..ACC_OFFSET].iter().chain(&current[ACC_BITS_OFFSET..CARRY_HI_OFFSET]);
+        for (result, &bit) in result.iter_mut().zip(bits) {
+            *result = is_binary(bit);
+        }
+
+        // the upper part of the carry out of limb k must be at most k
+        for k in 1..NUM_LIMBS {
+            let carry_hi = current[carry_hi(k)];
+            result[CARRY_HI_CONSTRAINTS + k - 1] =
+                (1..=k).fold(carry_hi, |value, i| value * (carry_hi - E::from(i as u32)));
+        }
+
+        // limbs of the accumulator must be equal to the limbs composed from their bits; this
+        // also ensures that limbs of the accumulator are 32 bits long
+        for k in 0..NUM_LIMBS {
+            result[DECOMPOSITION_CONSTRAINTS + k] =
+                are_equal(current[ACC_OFFSET + k], compose_limb(&current[ACC_BITS_OFFSET..], k));
+        }
+
+        // limb k of the next accumulator together with the carry out of it must be equal to
+        // the sum of limb k of the current accumulator, products of limbs of the operands
+        // contributing to limb k, and the carry out of limb k - 1
+        let a = (0..NUM_LIMBS)
+            .map(|k| compose_limb(&current[A_BITS_OFFSET..], k))
+            .collect::<Vec<_>>();
+        let b = (0..NUM_LIMBS)
+            .map(|k| compose_limb(&current[B_BITS_OFFSET..], k))
+            .collect::<Vec<_>>();
+        let limb_base = E::from(1u64 << LIMB_BITS);
+        let mut carry = E::ZERO;
+        for k in 0..NUM_LIMBS {
+            let sum = (0..=k).fold(current[ACC_OFFSET + k] + carry, |sum, i| sum + a[i] * b[k - i]);
+            carry = compose_limb(&current[CARRY_BITS_OFFSET..], k);
+            if k > 0 {
+                carry += limb_base * current[carry_hi(k)];
+            }
+            result[MUL_ADD_CONSTRAINTS + k] =
+                are_equal(next[ACC_OFFSET + k] + limb_base * carry, sum);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the accumulator starts at zero, and ends with the expected result
+        let last_step = self.trace_length() - 1;
+        let mut assertions = Vec::with_capacity(NUM_LIMBS * 2);
+        for k in 0..NUM_LIMBS {
+            assertions.push(Assertion::single(ACC_OFFSET + k, 0, BaseElement::ZERO));
+        }
+        for (k, &limb) in self.result.iter().enumerate() {
+            assertions.push(Assertion::single(ACC_OFFSET + k, last_step, limb.into()));
+        }
+        assertions
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a field element composed from the provided bits, where the first bit is the least
+/// significant bit.
+fn compose_bits<E: FieldElement>(bits: &[E]) -> E {
+    bits.iter().rev().fold(E::ZERO, |value, &bit| value.double() + bit)
+}
+
+/// Returns the specified limb composed from the bits of a 256-bit integer starting at the
+/// beginning of the provided slice.
+fn compose_limb<E: FieldElement>(bits: &[E], limb: usize) -> E {
+    compose_bits(&bits[limb * LIMB_BITS..(limb + 1) * LIMB_BITS])
+}
+
+/// Returns index of the column holding the upper part of the carry out of the specified limb.
+fn carry_hi(limb: usize) -> usize {
+    CARRY_HI_OFFSET + limb - 1
+}
@@ -0,0 +1,201 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+#[allow(clippy::module_inception)]
+pub(crate) mod u256;
+use u256::{LIMB_BITS, NUM_LIMBS, U256};
+
+mod air;
+use air::{PublicInputs, U256Air};
+
+mod prover;
+use prover::U256Prover;
+
+#[cfg(test)]
+mod tests;
+
+// TRACE LAYOUT
+// ================================================================================================
+// Every row of the trace holds the accumulator acc and operands a and b of a single step of the
+// computation acc <- (acc + a * b) mod 2^256. All values are split into 32-bit limbs; products
+// of limbs are at most 64 bits long, and thus, the sum of all limb products contributing to a
+// single limb of the result fits into the base field without overflow. Every limb of the result
+// is computed as such a sum plus the carry from the previous limb, and the carries are supplied
+// by the prover in dedicated columns.
+//
+// For the computation to be sound, limbs of all values and all carries must be range-checked.
+// This is done by decomposing limbs and the lower 32 bits of carries into binary columns. The
+// upper part of the carry out of limb k can take only k + 1 values, and is range-checked by a
+// polynomial of degree k + 1 which vanishes on all of them.
+
+/// Index of the first column holding bits of operand a.
+const A_BITS_OFFSET: usize = 0;
+
+/// Index of the first column holding bits of operand b.
+const B_BITS_OFFSET: usize = A_BITS_OFFSET + NUM_LIMBS * LIMB_BITS;
+
+/// Index of the first column holding limbs of the accumulator.
+const ACC_OFFSET: usize = B_BITS_OFFSET + NUM_LIMBS * LIMB_BITS;
+
+/// Index of the first column holding bits of the accumulator.
+const ACC_BITS_OFFSET: usize = ACC_OFFSET + NUM_LIMBS;
+
+/// Index of the first column holding bits of the lower 32 bits of the carries out of limbs of
+/// the result.
+const CARRY_BITS_OFFSET: usize = ACC_BITS_OFFSET + NUM_LIMBS * LIMB_BITS;
+
+/// Index of the first column holding the upper parts of the carries out of limbs of the result.
+/// The carry out of limb k is smaller than (k + 1) * 2^32, and thus, its upper part is at most k;
+/// since the carry out of limb 0 has no upper part, the first column holds the upper part of the
+/// carry out of limb 1.
+const CARRY_HI_OFFSET: usize = CARRY_BITS_OFFSET + NUM_LIMBS * LIMB_BITS;
+
+/// Total number of columns in the execution trace.
+const TRACE_WIDTH: usize = CARRY_HI_OFFSET + NUM_LIMBS - 1;
+
+// U256 MULTIPLY-ACCUMULATE EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_products: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(U256Example::<Blake3_192>::new(num_products, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(U256Example::<Blake3_256>::new(num_products, options)))
+        }
+        HashFunction::Sha3_256 => Ok(Box::new(U256Example::<Sha3_256>::new(num_products, options))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct U256Example<H: ElementHasher> {
+    options: ProofOptions,
+    operands: Vec<(U256, U256)>,
+    result: U256,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> U256Example<H> {
+    pub fn new(num_products: usize, options: ProofOptions) -> Self {
+        assert!(
+            (num_products + 1).is_power_of_two(),
+            "number of products must be one less than a power of 2"
+        );
+
+        // build the operands; the first product is computed from the largest possible operands,
+        // which results in the largest possible carries, and the remaining operands are
+        // pseudo-random
+        let mut operands = vec![(u256::MAX, u256::MAX)];
+        for i in 1..num_products as u64 {
+            operands.push((hash_to_u256(2 * i), hash_to_u256(2 * i + 1)));
+        }
+
+        // compute the sum of all products
+        let now = Instant::now();
+        let result =
+            operands.iter().fold([0; NUM_LIMBS], |acc, (a, b)| u256::mul_add(&acc, a, b).0);
+        debug!(
+            "Computed a sum of {} products of 256-bit integers in {} ms",
+            num_products,
+            now.elapsed().as_millis(),
+        );
+
+        U256Example {
+            options,
+            operands,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for U256Example<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for computing a sum of {} products of 256-bit integers\n\
+            ---------------------",
+            self.operands.len()
+        );
+
+        // create a prover
+        let prover = U256Prover::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.operands);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            result: self.result,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<U256Air, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut result = self.result;
+        result[0] ^= 1;
+        let pub_inputs = PublicInputs { result };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<U256Air, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a 256-bit integer derived from a hash of the specified seed.
+fn hash_to_u256(seed: u64) -> U256 {
+    let hash = blake3::hash(&seed.to_le_bytes());
+    let mut result = [0; NUM_LIMBS];
+    for (limb, bytes) in result.iter_mut().zip(hash.as_bytes().chunks(4)) {
+        *limb = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    result
+}
@@ -0,0 +1,139 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    u256, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, PhantomData, ProofOptions,
+    Prover, PublicInputs, U256Air, ACC_BITS_OFFSET, ACC_OFFSET, A_BITS_OFFSET, B_BITS_OFFSET,
+    CARRY_BITS_OFFSET, CARRY_HI_OFFSET, LIMB_BITS, NUM_LIMBS, TRACE_WIDTH, U256,
+};
+use winterfell::{
+    math::StarkField, matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// U256 PROVER
+// ================================================================================================
+
+pub struct U256Prover<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> U256Prover<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace of adding all products of the provided operands to an
+    /// accumulator which starts at zero; the last row of the trace holds only the result.
+    pub fn build_trace(&self, operands: &[(U256, U256)]) -> TraceTable<BaseElement> {
+        // allocate memory to hold the trace table
+        let trace_length = operands.len() + 1;
+        assert!(
+            trace_length.is_power_of_two(),
+            "number of products must be one less than a power of 2"
+        );
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+        let get_operands = |step: usize| operands.get(step).copied().unwrap_or_default();
+
+        trace.fill(
+            |row| {
+                fill_row(row, &[0; NUM_LIMBS], get_operands(0));
+            },
+            |step, row| {
+                let (a, b) = get_operands(step);
+                let (acc, _) = u256::mul_add(&read_acc(row), &a, &b);
+                fill_row(row, &acc, get_operands(step + 1));
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for U256Prover<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = U256Air;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        let mut result = [0; NUM_LIMBS];
+        for (k, limb) in result.iter_mut().enumerate() {
+            *limb = trace.get(ACC_OFFSET + k, last_step).as_int() as u32;
+        }
+        PublicInputs { result }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Fills in the provided row with the accumulator, the operands, and the carries of adding the
+/// product of the operands to the accumulator.
+fn fill_row(row: &mut [BaseElement], acc: &U256, (a, b): (U256, U256)) {
+    write_bits(row, A_BITS_OFFSET, &a);
+    write_bits(row, B_BITS_OFFSET, &b);
+    write_bits(row, ACC_BITS_OFFSET, acc);
+    for (k, &limb) in acc.iter().enumerate() {
+        row[ACC_OFFSET + k] = BaseElement::from(limb);
+    }
+
+    let (_, carries) = u256::mul_add(acc, &a, &b);
+    write_bits(row, CARRY_BITS_OFFSET, &carries.map(|carry| carry as u32));
+    for (k, carry) in carries.into_iter().enumerate().skip(1) {
+        row[CARRY_HI_OFFSET + k - 1] = BaseElement::from(carry >> LIMB_BITS);
+    }
+}
+
+fn read_acc(row: &[BaseElement]) -> U256 {
+    let mut acc = [0; NUM_LIMBS];
+    for (k, limb) in acc.iter_mut().enumerate() {
+        *limb = row[ACC_OFFSET + k].as_int() as u32;
+    }
+    acc
+}
+
+fn write_bits(row: &mut [BaseElement], offset: usize, value: &U256) {
+    for (k, &limb) in value.iter().enumerate() {
+        for i in 0..LIMB_BITS {
+            row[offset + k * LIMB_BITS + i] = BaseElement::from((limb >> i) & 1);
+        }
+    }
+}
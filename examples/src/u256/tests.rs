@@ -0,0 +1,55 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{u256, Blake3_256, NUM_LIMBS};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn u256_mul_add() {
+    // (2^256 - 1) * (2^256 - 1) = 1 mod 2^256
+    let (result, _) = u256::mul_add(&[0; NUM_LIMBS], &u256::MAX, &u256::MAX);
+    assert_eq!([1, 0, 0, 0, 0, 0, 0, 0], result);
+
+    // 2^128 * 2^128 + (2^256 - 1) = 2^256 - 1 mod 2^256
+    let pow128 = [0, 0, 0, 0, 1, 0, 0, 0];
+    let (result, _) = u256::mul_add(&u256::MAX, &pow128, &pow128);
+    assert_eq!(u256::MAX, result);
+
+    // (2^32 + 1) * (2^32 - 1) + 1 = 2^64
+    let (result, carries) = u256::mul_add(
+        &[1, 0, 0, 0, 0, 0, 0, 0],
+        &[1, 1, 0, 0, 0, 0, 0, 0],
+        &[u32::MAX, 0, 0, 0, 0, 0, 0, 0],
+    );
+    assert_eq!([0, 0, 1, 0, 0, 0, 0, 0], result);
+    assert_eq!([1, 1, 0, 0, 0, 0, 0, 0], carries);
+}
+
+#[test]
+fn u256_test_basic_proof_verification() {
+    let u256_eg = Box::new(super::U256Example::<Blake3_256>::new(31, build_options(false)));
+    crate::tests::test_basic_proof_verification(u256_eg);
+}
+
+#[test]
+fn u256_test_basic_proof_verification_extension() {
+    let u256_eg = Box::new(super::U256Example::<Blake3_256>::new(31, build_options(true)));
+    crate::tests::test_basic_proof_verification(u256_eg);
+}
+
+#[test]
+fn u256_test_basic_proof_verification_fail() {
+    let u256_eg = Box::new(super::U256Example::<Blake3_256>::new(31, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(u256_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -0,0 +1,45 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of limbs in a 256-bit integer.
+pub const NUM_LIMBS: usize = 8;
+
+/// Number of bits in a single limb.
+pub const LIMB_BITS: usize = 32;
+
+/// The largest 256-bit integer, 2^256 - 1.
+pub const MAX: U256 = [u32::MAX; NUM_LIMBS];
+
+// TYPES
+// ================================================================================================
+
+/// A 256-bit unsigned integer represented by 32-bit limbs in little-endian order.
+pub type U256 = [u32; NUM_LIMBS];
+
+// ARITHMETIC
+// ================================================================================================
+
+/// Returns (acc + a * b) mod 2^256 computed using schoolbook multiplication, together with the
+/// carries out of every limb of the result.
+///
+/// Limb k of the result is computed from the sum of acc_k, all products a_i * b_j such that
+/// i + j = k, and the carry out of limb k - 1. By induction, the carry out of limb k is smaller
+/// than (k + 1) * 2^32.
+pub fn mul_add(acc: &U256, a: &U256, b: &U256) -> (U256, [u64; NUM_LIMBS]) {
+    let mut result = [0; NUM_LIMBS];
+    let mut carries = [0; NUM_LIMBS];
+    let mut carry = 0u128;
+    for k in 0..NUM_LIMBS {
+        let sum =
+            (0..=k).fold(acc[k] as u128 + carry, |sum, i| sum + a[i] as u128 * b[k - i] as u128);
+        result[k] = sum as u32;
+        carry = sum >> LIMB_BITS;
+        carries[k] = carry as u64;
+    }
+    (result, carries)
+}
@@ -3,8 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{execute_vdf, BaseElement, Blake3_256, VdfAir, VdfInputs, VdfProver};
+use winterfell::{
+    crypto::DefaultRandomCoin, math::FieldElement, AcceptableOptions, ContinuationProof,
+    FieldExtension, ProofOptions, Prover, ProverError, Trace, TraceTable, VerifierError,
+};
 
 #[test]
 fn vdf_test_basic_proof_verification() {
@@ -24,6 +27,85 @@ fn vdf_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(fib);
 }
 
+#[test]
+fn vdf_test_continuation_proof_verification() {
+    let seed = BaseElement::new(123);
+    let (proof, final_state) = build_continuation_proof(seed, 4, 64);
+    assert_eq!(4, proof.num_segments());
+
+    // the chain of segments must be equivalent to running the VDF for the total number of steps
+    assert_eq!(execute_vdf(seed, 4 * 63 + 1), final_state);
+
+    let proof = ContinuationProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert!(verify_continuation(proof, seed, final_state).is_ok());
+}
+
+#[test]
+fn vdf_test_continuation_proof_verification_fail() {
+    let seed = BaseElement::new(123);
+    let (proof, final_state) = build_continuation_proof(seed, 3, 64);
+
+    // wrong final state
+    let result = verify_continuation(proof.clone(), seed, final_state + BaseElement::ONE);
+    assert_eq!(Err(VerifierError::UnexpectedContinuationState), result);
+
+    // swapped segment proofs
+    let (boundary_states, mut segment_proofs) = proof.into_parts();
+    segment_proofs.swap(0, 1);
+    let proof = ContinuationProof::new(boundary_states, segment_proofs);
+    let result = verify_continuation(proof, seed, final_state);
+    assert!(matches!(
+        result,
+        Err(VerifierError::ContinuationSegmentVerificationFailed(0, _))
+    ));
+}
+
+#[test]
+fn vdf_test_continuation_proof_unlinked_segments() {
+    let prover = VdfProver::<Blake3_256>::new(build_options(false));
+    let segments = vec![
+        VdfProver::<Blake3_256>::build_trace(BaseElement::new(123), 64),
+        VdfProver::<Blake3_256>::build_trace(BaseElement::new(124), 64),
+    ];
+    let result = prover.prove_continuation(segments);
+    assert!(matches!(result, Err(ProverError::InvalidContinuation(_))));
+}
+
+fn build_continuation_proof(
+    seed: BaseElement,
+    num_segments: usize,
+    segment_length: usize,
+) -> (ContinuationProof<BaseElement>, BaseElement) {
+    let mut segments: Vec<TraceTable<BaseElement>> = Vec::new();
+    let mut state = seed;
+    for _ in 0..num_segments {
+        let trace = VdfProver::<Blake3_256>::build_trace(state, segment_length);
+        state = trace.get(0, trace.length() - 1);
+        segments.push(trace);
+    }
+
+    let prover = VdfProver::<Blake3_256>::new(build_options(false));
+    (prover.prove_continuation(segments).unwrap(), state)
+}
+
+fn verify_continuation(
+    proof: ContinuationProof<BaseElement>,
+    seed: BaseElement,
+    result: BaseElement,
+) -> Result<(), VerifierError> {
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options(false)]);
+    winterfell::verify_continuation::<VdfAir, Blake3_256, DefaultRandomCoin<Blake3_256>, _>(
+        proof,
+        &[seed],
+        &[result],
+        |initial_state, final_state| VdfInputs {
+            seed: initial_state[0],
+            result: final_state[0],
+        },
+        &acceptable_options,
+    )
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -0,0 +1,282 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compress, get_program_table, opcode_to_element, BaseElement, ExtensionOf, FieldElement,
+    Instruction, Opcode, ProofOptions, A, AUX_TRACE_WIDTH, A_INV, B, CHIPLET_FLAG, CLK, IMM,
+    MEMORY_BUS, MEM_ADDR, MEM_CLK, MEM_FIRST, MEM_VALUE, MEM_WRITE, NUM_OPCODES, OP_FLAGS_OFFSET,
+    PC, PROGRAM_BUS, PROGRAM_MULTIPLICITY, RANGE_BUS, RANGE_MULTIPLICITY, TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Index of the first constraint against the memory chiplet.
+const CHIPLET_CONSTRAINTS: usize = NUM_OPCODES + 6;
+
+/// Total number of transition constraints against the main trace segment.
+const NUM_CONSTRAINTS: usize = CHIPLET_CONSTRAINTS + 7;
+
+// VM AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub program: Vec<Instruction>,
+    pub output: BaseElement,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = Vec::with_capacity(self.program.len() * 2 + 1);
+        for instruction in self.program.iter() {
+            result.extend_from_slice(&[opcode_to_element(instruction), instruction.imm]);
+        }
+        result.push(self.output);
+        result
+    }
+}
+
+pub struct VmAir {
+    context: AirContext<BaseElement>,
+    program: Vec<Instruction>,
+    output: BaseElement,
+}
+
+impl Air for VmAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let mut main_degrees = vec![TransitionConstraintDegree::new(1)];
+        main_degrees.resize(NUM_OPCODES + 1, TransitionConstraintDegree::new(2));
+        main_degrees.extend([
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(4),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(2),
+        ]);
+        main_degrees.resize(CHIPLET_CONSTRAINTS + 4, TransitionConstraintDegree::new(2));
+        main_degrees.extend([
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(4),
+        ]);
+        debug_assert_eq!(NUM_CONSTRAINTS, main_degrees.len());
+        let aux_degrees = vec![
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::with_cycles(2, vec![trace_info.length()]),
+            TransitionConstraintDegree::new(4),
+        ];
+        assert_eq!(TRACE_WIDTH + AUX_TRACE_WIDTH, trace_info.width());
+        assert!(
+            pub_inputs.program.len() < trace_info.length(),
+            "program must be shorter than the execution trace"
+        );
+        assert_eq!(
+            Some(Opcode::Halt),
+            pub_inputs.program.last().map(|instruction| instruction.opcode),
+            "program must end with a HALT instruction"
+        );
+
+        VmAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                7,
+                6,
+                options,
+            ),
+            program: pub_inputs.program,
+            output: pub_inputs.output,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // the clock cycle is incremented by one on every row
+        result[0] = are_equal(next[CLK], current[CLK] + E::ONE);
+
+        // exactly one operation flag must be set on every row
+        let flags = &current[OP_FLAGS_OFFSET..CHIPLET_FLAG];
+        for (i, &flag) in flags.iter().enumerate() {
+            result[1 + i] = is_binary(flag);
+        }
+        result[NUM_OPCODES + 1] = are_equal(flags.iter().fold(E::ZERO, |acc, &f| acc + f), E::ONE);
+        let flag = |opcode: Opcode| flags[opcode as usize];
+
+        // a * a_inv must be one when a is not zero; this also makes a * a_inv equal to zero when
+        // a is zero
+        let (a, b, imm) = (current[A], current[B], current[IMM]);
+        let is_not_zero = a * current[A_INV];
+        result[NUM_OPCODES + 2] = a * not(is_not_zero);
+
+        // the program counter is incremented on every row, except for HALT, which leaves it
+        // unchanged, and a taken jump, which sets it to the immediate value
+        let pc = current[PC];
+        let jump = flag(Opcode::Jnz) * is_not_zero * (imm - pc - E::ONE);
+        result[NUM_OPCODES + 3] = are_equal(next[PC], pc + not(flag(Opcode::Halt)) + jump);
+
+        // register a is updated according to the operation; the value loaded from memory is not
+        // constrained here, as it is enforced by the memory bus
+        let unchanged = flag(Opcode::Halt) + flag(Opcode::Mov) + flag(Opcode::Jnz);
+        let new_a = (unchanged + flag(Opcode::Store)) * a
+            + flag(Opcode::Set) * imm
+            + flag(Opcode::Add) * (a + b)
+            + flag(Opcode::Mul) * a * b;
+        result[NUM_OPCODES + 4] = are_equal(not(flag(Opcode::Load)) * next[A], new_a);
+
+        // register b is updated only by MOV
+        result[NUM_OPCODES + 5] = are_equal(next[B], b + flag(Opcode::Mov) * (a - b));
+
+        // the chiplet flag must be binary, and once it is set, it must remain set until the end
+        // of the trace
+        let chiplet_flag = current[CHIPLET_FLAG];
+        let next_chiplet_flag = next[CHIPLET_FLAG];
+        result[CHIPLET_CONSTRAINTS] = is_binary(chiplet_flag);
+        result[CHIPLET_CONSTRAINTS + 1] = chiplet_flag * not(next_chiplet_flag);
+        result[CHIPLET_CONSTRAINTS + 2] = is_binary(current[MEM_FIRST]);
+        result[CHIPLET_CONSTRAINTS + 3] = is_binary(current[MEM_WRITE]);
+
+        // the first access in the chiplet must be the first access to its address, and all
+        // other accesses must be to the same address as the previous access, unless they are
+        // marked as the first ones
+        let not_first = not(next[MEM_FIRST]);
+        result[CHIPLET_CONSTRAINTS + 4] = next_chiplet_flag * not(chiplet_flag) * not_first;
+        result[CHIPLET_CONSTRAINTS + 5] =
+            next_chiplet_flag * not_first * are_equal(next[MEM_ADDR], current[MEM_ADDR]);
+
+        // a read must return the value of the previous access to the same address, or zero if
+        // this is the first access to the address
+        result[CHIPLET_CONSTRAINTS + 6] = next_chiplet_flag
+            * not(next[MEM_WRITE])
+            * are_equal(next[MEM_VALUE], not_first * current[MEM_VALUE]);
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let current = main_frame.current();
+        let next = main_frame.next();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        let random_elements = aux_rand_elements.get_segment_elements(0);
+        let (alpha, beta) = (random_elements[0], random_elements[1]);
+        let get = |row: &[F], column: usize| -> E { row[column].into() };
+        let flag = |opcode: Opcode| get(current, OP_FLAGS_OFFSET + opcode as usize);
+
+        // memory bus: the running product is multiplied by the access made by the CPU on the
+        // current row, and divided by the access in the chiplet on the next row; the value of a
+        // memory access made by the CPU is always the value of register a on the next row
+        let is_store = flag(Opcode::Store);
+        let is_memory_op = flag(Opcode::Load) + is_store;
+        let cpu_access = [get(current, IMM), get(next, A), get(current, CLK), is_store];
+        let cpu_term = E::ONE + is_memory_op * (alpha - compress(beta, &cpu_access) - E::ONE);
+        let chiplet_access = [MEM_ADDR, MEM_VALUE, MEM_CLK, MEM_WRITE].map(|c| get(next, c));
+        let chiplet_term =
+            E::ONE + get(next, CHIPLET_FLAG) * (alpha - compress(beta, &chiplet_access) - E::ONE);
+        result[MEMORY_BUS] =
+            are_equal(aux_next[MEMORY_BUS] * chiplet_term, aux_current[MEMORY_BUS] * cpu_term);
+
+        // program bus: the change of the running sum must be equal to 1 / (α - (pc + β * opcode
+        // + β^2 * imm)) - m / (α - (clk + β * opcode' + β^2 * imm')), where (opcode', imm') is
+        // the program table entry of the current row
+        let opcode = (0..NUM_OPCODES)
+            .fold(E::ZERO, |acc, op| acc + get(current, OP_FLAGS_OFFSET + op) * E::from(op as u32));
+        let instruction = [get(current, PC), opcode, get(current, IMM)];
+        let entry = [get(current, CLK), periodic_values[0].into(), periodic_values[1].into()];
+        let lookup = alpha - compress(beta, &instruction);
+        let entry = alpha - compress(beta, &entry);
+        result[PROGRAM_BUS] = are_equal(
+            (aux_next[PROGRAM_BUS] - aux_current[PROGRAM_BUS]) * lookup * entry,
+            entry - get(current, PROGRAM_MULTIPLICITY) * lookup,
+        );
+
+        // range check bus: the change of the running sum must be equal to f / (α - δ) - m / (α -
+        // clk), where f is set when both rows belong to the chiplet, and δ is the difference
+        // between the addresses or the clock cycles of the accesses, decremented by one
+        let is_first = get(next, MEM_FIRST);
+        let addr_delta = get(next, MEM_ADDR) - get(current, MEM_ADDR);
+        let clk_delta = get(next, MEM_CLK) - get(current, MEM_CLK);
+        let delta = is_first * addr_delta + not(is_first) * clk_delta - E::ONE;
+        let lookup = alpha - delta;
+        let entry = alpha - get(current, CLK);
+        let both_in_chiplet = get(current, CHIPLET_FLAG) * get(next, CHIPLET_FLAG);
+        result[RANGE_BUS] = are_equal(
+            (aux_next[RANGE_BUS] - aux_current[RANGE_BUS]) * lookup * entry,
+            both_in_chiplet * entry - get(current, RANGE_MULTIPLICITY) * lookup,
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the VM starts at the first instruction with all registers set to zero, and ends at the
+        // HALT instruction with the expected output in register a; the first row of the trace
+        // must not belong to the memory chiplet
+        let last_step = self.trace_length() - 1;
+        let halt_pc = BaseElement::from(self.program.len() as u64 - 1);
+        vec![
+            Assertion::single(CLK, 0, BaseElement::ZERO),
+            Assertion::single(PC, 0, BaseElement::ZERO),
+            Assertion::single(A, 0, BaseElement::ZERO),
+            Assertion::single(B, 0, BaseElement::ZERO),
+            Assertion::single(CHIPLET_FLAG, 0, BaseElement::ZERO),
+            Assertion::single(PC, last_step, halt_pc),
+            Assertion::single(A, last_step, self.output),
+        ]
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        // the running product of the memory bus must start and end at one, and the running sums
+        // of the lookup arguments must start and end at zero
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(MEMORY_BUS, 0, E::ONE),
+            Assertion::single(MEMORY_BUS, last_step, E::ONE),
+            Assertion::single(PROGRAM_BUS, 0, E::ZERO),
+            Assertion::single(PROGRAM_BUS, last_step, E::ZERO),
+            Assertion::single(RANGE_BUS, 0, E::ZERO),
+            Assertion::single(RANGE_BUS, last_step, E::ZERO),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let table = get_program_table(&self.program, self.trace_length());
+        let opcodes = table.iter().map(opcode_to_element).collect();
+        let imms = table.iter().map(|instruction| instruction.imm).collect();
+        vec![opcodes, imms]
+    }
+}
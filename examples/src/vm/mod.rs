@@ -0,0 +1,236 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod program;
+use program::{Instruction, Opcode, NUM_OPCODES};
+
+mod trace;
+use trace::VmTraceTable;
+
+mod air;
+use air::{PublicInputs, VmAir};
+
+mod prover;
+use prover::VmProver;
+
+#[cfg(test)]
+mod tests;
+
+// TRACE LAYOUT
+// ================================================================================================
+// The execution trace consists of two parts placed side by side: the CPU, which executes one
+// instruction per row, and the memory chiplet, which holds all memory accesses performed by the
+// CPU sorted by address and then by clock cycle. The two parts are connected via buses built in
+// the auxiliary trace segment:
+//
+// - Instructions are fetched by looking up (pc, opcode, imm) tuples in the program table. The
+//   program is public, and thus, the table is supplied to the AIR via periodic columns; the pc of
+//   a table entry is the clock cycle of the row. The lookup is enforced using the logarithmic
+//   derivative argument, and multiplicities of all table entries are supplied by the prover.
+// - Memory accesses of the CPU must be a permutation of the accesses in the memory chiplet; this
+//   is enforced using a multiset check: the running product of the auxiliary column multiplies by
+//   (α - (addr + β * value + β^2 * clk + β^3 * is_write)) for every access made by the CPU, and
+//   divides by the same expression for every access in the chiplet.
+// - Differences between consecutive addresses, and between consecutive clock cycles for the same
+//   address, are range-checked by looking them up in the table of clock cycles; this ensures that
+//   memory accesses in the chiplet are sorted, and thus, every read returns the value of the
+//   preceding write to the same address (or zero if there was none).
+//
+// Opcodes of instructions are decoded into one-hot selector columns; this keeps the degree of
+// constraints low, as the flag of every operation is a single column. The CPU keeps executing
+// the HALT instruction once it is reached. The memory chiplet occupies the bottom rows of the
+// trace, and its rows are marked by a selector column.
+
+/// Clock cycle of the current row.
+const CLK: usize = 0;
+
+/// Program counter.
+const PC: usize = 1;
+
+/// Registers of the VM.
+const A: usize = 2;
+const B: usize = 3;
+
+/// Inverse of the value in register `a`, or zero if the value is zero.
+const A_INV: usize = 4;
+
+/// Immediate value of the current instruction.
+const IMM: usize = 5;
+
+/// Index of the first of the selector columns of the current operation.
+const OP_FLAGS_OFFSET: usize = 6;
+
+/// Set to one on rows which belong to the memory chiplet.
+const CHIPLET_FLAG: usize = OP_FLAGS_OFFSET + NUM_OPCODES;
+
+/// Set to one if the memory access is the first access to its address.
+const MEM_FIRST: usize = CHIPLET_FLAG + 1;
+
+/// Set to one if the memory access is a write.
+const MEM_WRITE: usize = MEM_FIRST + 1;
+
+/// Address, clock cycle, and value of the memory access.
+const MEM_ADDR: usize = MEM_WRITE + 1;
+const MEM_CLK: usize = MEM_ADDR + 1;
+const MEM_VALUE: usize = MEM_CLK + 1;
+
+/// Number of times the program table entry of the current row was looked up.
+const PROGRAM_MULTIPLICITY: usize = MEM_VALUE + 1;
+
+/// Number of times the clock cycle of the current row was looked up by the range check.
+const RANGE_MULTIPLICITY: usize = PROGRAM_MULTIPLICITY + 1;
+
+/// Total number of columns in the main segment of the execution trace.
+const TRACE_WIDTH: usize = RANGE_MULTIPLICITY + 1;
+
+/// Columns of the auxiliary trace segment.
+const MEMORY_BUS: usize = 0;
+const PROGRAM_BUS: usize = 1;
+const RANGE_BUS: usize = 2;
+
+/// Total number of columns in the auxiliary segment of the execution trace.
+const AUX_TRACE_WIDTH: usize = 3;
+
+// VM EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_iterations: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(VmExample::<Blake3_192>::new(num_iterations, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(VmExample::<Blake3_256>::new(num_iterations, options)))
+        }
+        HashFunction::Sha3_256 => Ok(Box::new(VmExample::<Sha3_256>::new(num_iterations, options))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct VmExample<H: ElementHasher> {
+    options: ProofOptions,
+    program: Vec<Instruction>,
+    output: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VmExample<H> {
+    pub fn new(num_iterations: usize, options: ProofOptions) -> Self {
+        // execute the program to get its output
+        let program = program::fibonacci_program(num_iterations);
+        let now = Instant::now();
+        let execution = program::execute(&program);
+        debug!(
+            "Executed a program of {} instructions in {} steps in {} ms",
+            program.len(),
+            execution.states.len(),
+            now.elapsed().as_millis(),
+        );
+
+        VmExample {
+            options,
+            output: execution.output(),
+            program,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for VmExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for executing a program of {} instructions\n\
+            ---------------------",
+            self.program.len()
+        );
+
+        // create a prover
+        let prover = VmProver::<H>::new(self.program.clone(), self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.main_trace_width(),
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            program: self.program.clone(),
+            output: self.output,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<VmAir, H, DefaultRandomCoin<H>>(proof, pub_inputs, &acceptable_options)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            program: self.program.clone(),
+            output: self.output + BaseElement::ONE,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<VmAir, H, DefaultRandomCoin<H>>(proof, pub_inputs, &acceptable_options)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the opcode of the specified instruction as a field element.
+fn opcode_to_element(instruction: &Instruction) -> BaseElement {
+    BaseElement::from(instruction.opcode as u8)
+}
+
+/// Returns the program table entries for all rows of a trace of the specified length; rows past
+/// the end of the program hold [Opcode::Halt] instructions.
+fn get_program_table(program: &[Instruction], trace_length: usize) -> Vec<Instruction> {
+    let mut table = program.to_vec();
+    table.resize(trace_length, Instruction::op(Opcode::Halt));
+    table
+}
+
+/// Returns a random linear combination of the specified values, where the i-th value is
+/// multiplied by the i-th power of β.
+fn compress<E: FieldElement>(beta: E, values: &[E]) -> E {
+    let mut power = E::ONE;
+    let mut result = E::ZERO;
+    for &value in values {
+        result += power * value;
+        power *= beta;
+    }
+    result
+}
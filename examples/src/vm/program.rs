@@ -0,0 +1,203 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::collections::HashMap;
+use winterfell::math::{fields::f128::BaseElement, FieldElement, StarkField};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of distinct operations supported by the VM.
+pub const NUM_OPCODES: usize = 8;
+
+// INSTRUCTION SET
+// ================================================================================================
+
+/// Operations supported by the VM.
+///
+/// The VM has two registers, `a` and `b`, a program counter, and a memory addressed by field
+/// elements; all memory cells are initialized to zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Opcode {
+    /// Stops the execution; the state of the VM remains unchanged from this point on.
+    Halt = 0,
+    /// Sets register `a` to the immediate value.
+    Set = 1,
+    /// Copies the value of register `a` into register `b`.
+    Mov = 2,
+    /// Sets register `a` to `a + b`.
+    Add = 3,
+    /// Sets register `a` to `a * b`.
+    Mul = 4,
+    /// Jumps to the instruction at the immediate value if register `a` is not zero.
+    Jnz = 5,
+    /// Loads the value at the memory address specified by the immediate value into register `a`.
+    Load = 6,
+    /// Stores the value of register `a` at the memory address specified by the immediate value.
+    Store = 7,
+}
+
+/// A single instruction of a program consisting of an operation and an immediate value; the
+/// immediate value is ignored by operations which do not need it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub imm: BaseElement,
+}
+
+impl Instruction {
+    /// Returns a new instruction with the specified operation and immediate value.
+    pub fn new(opcode: Opcode, imm: BaseElement) -> Self {
+        Self { opcode, imm }
+    }
+
+    /// Returns a new instruction with the specified operation and the immediate value set to
+    /// zero.
+    pub fn op(opcode: Opcode) -> Self {
+        Self::new(opcode, BaseElement::ZERO)
+    }
+}
+
+// EXECUTION
+// ================================================================================================
+
+/// State of the VM prior to executing an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    pub pc: usize,
+    pub a: BaseElement,
+    pub b: BaseElement,
+}
+
+/// A single memory access performed by the VM at the specified clock cycle; for reads, the value
+/// is the value read from memory, and for writes, the value written into memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryAccess {
+    pub addr: BaseElement,
+    pub clk: usize,
+    pub value: BaseElement,
+    pub is_write: bool,
+}
+
+/// Result of executing a program.
+pub struct Execution {
+    /// States of the VM prior to executing every instruction; the last state is the state in
+    /// which the VM reached the [Opcode::Halt] instruction.
+    pub states: Vec<State>,
+    /// All memory accesses performed by the VM in the order of execution.
+    pub accesses: Vec<MemoryAccess>,
+}
+
+impl Execution {
+    /// Returns the value of register `a` at the end of the execution.
+    pub fn output(&self) -> BaseElement {
+        self.states.last().expect("no states").a
+    }
+}
+
+/// Executes the specified program starting from a state with all registers set to zero.
+///
+/// # Panics
+/// Panics if the program counter moves beyond the end of the program.
+pub fn execute(program: &[Instruction]) -> Execution {
+    let mut state = State {
+        pc: 0,
+        a: BaseElement::ZERO,
+        b: BaseElement::ZERO,
+    };
+    let mut memory = HashMap::new();
+    let mut states = Vec::new();
+    let mut accesses = Vec::new();
+
+    loop {
+        let instruction = program.get(state.pc).expect("program counter out of bounds");
+        states.push(state);
+
+        let clk = states.len() - 1;
+        let imm = instruction.imm;
+        state.pc += 1;
+        match instruction.opcode {
+            Opcode::Halt => break,
+            Opcode::Set => state.a = imm,
+            Opcode::Mov => state.b = state.a,
+            Opcode::Add => state.a += state.b,
+            Opcode::Mul => state.a *= state.b,
+            Opcode::Jnz => {
+                if state.a != BaseElement::ZERO {
+                    state.pc = imm.as_int() as usize;
+                }
+            }
+            Opcode::Load => {
+                state.a = *memory.get(&imm.as_int()).unwrap_or(&BaseElement::ZERO);
+                accesses.push(MemoryAccess {
+                    addr: imm,
+                    clk,
+                    value: state.a,
+                    is_write: false,
+                });
+            }
+            Opcode::Store => {
+                memory.insert(imm.as_int(), state.a);
+                accesses.push(MemoryAccess {
+                    addr: imm,
+                    clk,
+                    value: state.a,
+                    is_write: true,
+                });
+            }
+        }
+    }
+
+    Execution { states, accesses }
+}
+
+// PROGRAMS
+// ================================================================================================
+
+/// Returns a program which computes F(n) * F(n + 1), where F(i) is the i-th Fibonacci number and
+/// n is the specified number of iterations; this is also the sum of the squares of the first
+/// n + 1 Fibonacci numbers.
+///
+/// The program keeps the loop counter and the last two Fibonacci numbers in memory.
+///
+/// # Panics
+/// Panics if the number of iterations is zero.
+pub fn fibonacci_program(num_iterations: usize) -> Vec<Instruction> {
+    assert!(num_iterations > 0, "number of iterations must be greater than zero");
+    const COUNTER: u128 = 0;
+    const X: u128 = 1;
+    const Y: u128 = 2;
+    const LOOP_START: u128 = 4;
+
+    vec![
+        // counter = n, x = F(0) = 0, y = F(1) = 1; memory is initialized to zero, and thus,
+        // x does not need to be set explicitly
+        Instruction::new(Opcode::Set, BaseElement::new(num_iterations as u128)),
+        Instruction::new(Opcode::Store, BaseElement::new(COUNTER)),
+        Instruction::new(Opcode::Set, BaseElement::ONE),
+        Instruction::new(Opcode::Store, BaseElement::new(Y)),
+        // (x, y) = (y, x + y)
+        Instruction::new(Opcode::Load, BaseElement::new(X)),
+        Instruction::op(Opcode::Mov),
+        Instruction::new(Opcode::Load, BaseElement::new(Y)),
+        Instruction::new(Opcode::Store, BaseElement::new(X)),
+        Instruction::op(Opcode::Add),
+        Instruction::new(Opcode::Store, BaseElement::new(Y)),
+        // counter = counter - 1; repeat while the counter is not zero
+        Instruction::new(Opcode::Set, -BaseElement::ONE),
+        Instruction::op(Opcode::Mov),
+        Instruction::new(Opcode::Load, BaseElement::new(COUNTER)),
+        Instruction::op(Opcode::Add),
+        Instruction::new(Opcode::Store, BaseElement::new(COUNTER)),
+        Instruction::new(Opcode::Jnz, BaseElement::new(LOOP_START)),
+        // a = x * y
+        Instruction::new(Opcode::Load, BaseElement::new(X)),
+        Instruction::op(Opcode::Mov),
+        Instruction::new(Opcode::Load, BaseElement::new(Y)),
+        Instruction::op(Opcode::Mul),
+        Instruction::op(Opcode::Halt),
+    ]
+}
@@ -0,0 +1,147 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_program_table, program, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement,
+    Instruction, PhantomData, ProofOptions, Prover, PublicInputs, VmAir, VmTraceTable, A, A_INV, B,
+    CHIPLET_FLAG, CLK, IMM, MEM_ADDR, MEM_CLK, MEM_FIRST, MEM_VALUE, MEM_WRITE, OP_FLAGS_OFFSET,
+    PC, PROGRAM_MULTIPLICITY, RANGE_MULTIPLICITY, TRACE_WIDTH,
+};
+use winterfell::{
+    math::StarkField, matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+};
+
+// VM PROVER
+// ================================================================================================
+
+pub struct VmProver<H: ElementHasher> {
+    program: Vec<Instruction>,
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VmProver<H> {
+    pub fn new(program: Vec<Instruction>, options: ProofOptions) -> Self {
+        Self {
+            program,
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace of the program of this prover; the trace is padded by repeating
+    /// the HALT instruction.
+    pub fn build_trace(&self) -> VmTraceTable {
+        let execution = program::execute(&self.program);
+        let states = &execution.states;
+
+        // the trace must be long enough to hold all steps of the program, as well as all memory
+        // accesses in the chiplet with one extra row before them
+        let trace_length = states
+            .len()
+            .max(execution.accesses.len() + 1)
+            .max(self.program.len() + 1)
+            .next_power_of_two()
+            .max(8);
+        let mut columns = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+
+        // fill in the CPU part of the trace; the last state is the state of the VM at the
+        // HALT instruction, and it is repeated until the end of the trace
+        for row in 0..trace_length {
+            let state = states[row.min(states.len() - 1)];
+            let instruction = self.program[state.pc];
+            columns[CLK][row] = BaseElement::from(row as u64);
+            columns[PC][row] = BaseElement::from(state.pc as u64);
+            columns[A][row] = state.a;
+            columns[B][row] = state.b;
+            columns[A_INV][row] = state.a.inv();
+            columns[IMM][row] = instruction.imm;
+            columns[OP_FLAGS_OFFSET + instruction.opcode as usize][row] = BaseElement::ONE;
+
+            // count the instruction fetch in the multiplicity of the corresponding program table
+            // entry; the fetch on the last row of the trace is not a part of the lookup argument
+            if row != trace_length - 1 {
+                columns[PROGRAM_MULTIPLICITY][state.pc] += BaseElement::ONE;
+            }
+        }
+
+        // fill in the memory chiplet with all accesses sorted by address and then by clock
+        // cycle; the chiplet occupies the bottom rows of the trace
+        let mut accesses = execution.accesses.clone();
+        accesses.sort_by_key(|access| (access.addr.as_int(), access.clk));
+        let offset = trace_length - accesses.len();
+        for (i, access) in accesses.iter().enumerate() {
+            let row = offset + i;
+            columns[CHIPLET_FLAG][row] = BaseElement::ONE;
+            columns[MEM_WRITE][row] = BaseElement::from(access.is_write as u8);
+            columns[MEM_ADDR][row] = access.addr;
+            columns[MEM_CLK][row] = BaseElement::from(access.clk as u64);
+            columns[MEM_VALUE][row] = access.value;
+
+            let is_first = i == 0 || accesses[i - 1].addr != access.addr;
+            columns[MEM_FIRST][row] = BaseElement::from(is_first as u8);
+
+            // count the difference between this and the previous access in the multiplicity of
+            // the corresponding clock cycle
+            if i > 0 {
+                let prev = &accesses[i - 1];
+                let delta = if is_first {
+                    (access.addr.as_int() - prev.addr.as_int()) as usize - 1
+                } else {
+                    access.clk - prev.clk - 1
+                };
+                assert!(delta < trace_length - 1, "memory addresses are too far apart");
+                columns[RANGE_MULTIPLICITY][delta] += BaseElement::ONE;
+            }
+        }
+
+        VmTraceTable::new(columns, get_program_table(&self.program, trace_length))
+    }
+}
+
+impl<H: ElementHasher> Prover for VmProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = VmAir;
+    type Trace = VmTraceTable;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            program: self.program.clone(),
+            output: trace.main_segment().get(A, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
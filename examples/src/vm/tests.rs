@@ -0,0 +1,52 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{program, Blake3_256};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    FieldExtension, ProofOptions,
+};
+
+#[test]
+fn vm_execute_program() {
+    // F(5) * F(6) = 5 * 8 = 0^2 + 1^2 + 1^2 + 2^2 + 3^2 + 5^2
+    let program = program::fibonacci_program(5);
+    let execution = program::execute(&program);
+    assert_eq!(BaseElement::new(40), execution.output());
+    assert_eq!(4 + 12 * 5 + 5, execution.states.len());
+    assert_eq!(2 + 6 * 5 + 2, execution.accesses.len());
+
+    // the first read of x happens before x is written, and thus, it returns zero
+    let first_read = execution.accesses.iter().find(|access| !access.is_write).unwrap();
+    assert_eq!(BaseElement::ONE, first_read.addr);
+    assert_eq!(BaseElement::ZERO, first_read.value);
+}
+
+#[test]
+fn vm_test_basic_proof_verification() {
+    let vm_eg = Box::new(super::VmExample::<Blake3_256>::new(3, build_options(false)));
+    crate::tests::test_basic_proof_verification(vm_eg);
+}
+
+#[test]
+fn vm_test_basic_proof_verification_extension() {
+    let vm_eg = Box::new(super::VmExample::<Blake3_256>::new(3, build_options(true)));
+    crate::tests::test_basic_proof_verification(vm_eg);
+}
+
+#[test]
+fn vm_test_basic_proof_verification_fail() {
+    let vm_eg = Box::new(super::VmExample::<Blake3_256>::new(3, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(vm_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -0,0 +1,151 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compress, opcode_to_element, BaseElement, FieldElement, Instruction, Opcode, A,
+    AUX_TRACE_WIDTH, CHIPLET_FLAG, CLK, IMM, MEMORY_BUS, MEM_ADDR, MEM_CLK, MEM_FIRST, MEM_VALUE,
+    MEM_WRITE, NUM_OPCODES, OP_FLAGS_OFFSET, PC, PROGRAM_BUS, PROGRAM_MULTIPLICITY, RANGE_BUS,
+    RANGE_MULTIPLICITY, TRACE_WIDTH,
+};
+use winterfell::{matrix::ColMatrix, EvaluationFrame, Trace, TraceLayout};
+
+// VM TRACE TABLE
+// ================================================================================================
+
+/// An execution trace of a program executed by the VM.
+///
+/// The main segment of the trace is built by the prover directly; the auxiliary segment consists
+/// of columns for the memory bus, the program bus, and the range check bus.
+pub struct VmTraceTable {
+    layout: TraceLayout,
+    trace: ColMatrix<BaseElement>,
+    program_table: Vec<Instruction>,
+}
+
+impl VmTraceTable {
+    /// Returns a new trace table built from the specified main trace columns and the program
+    /// table with an entry for every row of the trace.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of columns is not equal to the width of the main trace segment.
+    /// * The number of program table entries is not equal to the length of the trace.
+    pub fn new(columns: Vec<Vec<BaseElement>>, program_table: Vec<Instruction>) -> Self {
+        assert_eq!(TRACE_WIDTH, columns.len(), "invalid number of trace columns");
+        assert_eq!(
+            columns[0].len(),
+            program_table.len(),
+            "invalid number of program table entries"
+        );
+        Self {
+            layout: TraceLayout::new(TRACE_WIDTH, [AUX_TRACE_WIDTH], [2]),
+            trace: ColMatrix::new(columns),
+            program_table,
+        }
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl Trace for VmTraceTable {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.trace.read_row_into(row_idx, frame.current_mut());
+        self.trace.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        &self.trace
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // we have only one auxiliary segment
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        // the transition from the last row is not enforced, and thus, the CPU side and the table
+        // sides of the buses are built from the current row, while the chiplet side of the memory
+        // bus is built from the next row
+        let (alpha, beta) = (rand_elements[0], rand_elements[1]);
+        let get = |column: usize, row: usize| E::from(self.trace.get(column, row));
+        let mut columns = vec![vec![E::ZERO; self.length()]; AUX_TRACE_WIDTH];
+        columns[MEMORY_BUS][0] = E::ONE;
+        for i in 0..self.length() - 1 {
+            // memory bus: multiply by the access made by the CPU on the current row (if any) and
+            // divide by the access in the chiplet on the next row (if any)
+            let is_load = self.trace.get(OP_FLAGS_OFFSET + Opcode::Load as usize, i);
+            let is_store = self.trace.get(OP_FLAGS_OFFSET + Opcode::Store as usize, i);
+            let mut product = columns[MEMORY_BUS][i];
+            if is_load == BaseElement::ONE || is_store == BaseElement::ONE {
+                let access = [get(IMM, i), get(A, i + 1), get(CLK, i), E::from(is_store)];
+                product *= alpha - compress(beta, &access);
+            }
+            if self.trace.get(CHIPLET_FLAG, i + 1) == BaseElement::ONE {
+                let access = [MEM_ADDR, MEM_VALUE, MEM_CLK, MEM_WRITE].map(|c| get(c, i + 1));
+                product *= (alpha - compress(beta, &access)).inv();
+            }
+            columns[MEMORY_BUS][i + 1] = product;
+
+            // program bus: add 1 / (α - (pc + β * opcode + β^2 * imm)) for the instruction
+            // executed on the current row, and subtract m / (α - (clk + β * opcode' + β^2 *
+            // imm')) for the program table entry of the current row looked up m times
+            let opcode = (0..NUM_OPCODES)
+                .fold(E::ZERO, |acc, op| acc + get(OP_FLAGS_OFFSET + op, i) * E::from(op as u32));
+            let instruction = [get(PC, i), opcode, get(IMM, i)];
+            let mut delta = (alpha - compress(beta, &instruction)).inv();
+            let multiplicity = self.trace.get(PROGRAM_MULTIPLICITY, i);
+            if multiplicity != BaseElement::ZERO {
+                let entry = &self.program_table[i];
+                let entry = [get(CLK, i), opcode_to_element(entry).into(), E::from(entry.imm)];
+                delta -= E::from(multiplicity) * (alpha - compress(beta, &entry)).inv();
+            }
+            columns[PROGRAM_BUS][i + 1] = columns[PROGRAM_BUS][i] + delta;
+
+            // range check bus: add 1 / (α - δ) for the difference δ between memory accesses on
+            // the current and the next rows when both rows belong to the chiplet, and subtract
+            // m / (α - clk) for the clock cycle of the current row looked up m times
+            let mut delta = E::ZERO;
+            let in_chiplet = self.trace.get(CHIPLET_FLAG, i) == BaseElement::ONE;
+            if in_chiplet && self.trace.get(CHIPLET_FLAG, i + 1) == BaseElement::ONE {
+                let difference = if self.trace.get(MEM_FIRST, i + 1) == BaseElement::ONE {
+                    get(MEM_ADDR, i + 1) - get(MEM_ADDR, i)
+                } else {
+                    get(MEM_CLK, i + 1) - get(MEM_CLK, i)
+                };
+                delta += (alpha - (difference - E::ONE)).inv();
+            }
+            let multiplicity = self.trace.get(RANGE_MULTIPLICITY, i);
+            if multiplicity != BaseElement::ZERO {
+                delta -= E::from(multiplicity) * (alpha - get(CLK, i)).inv();
+            }
+            columns[RANGE_BUS][i + 1] = columns[RANGE_BUS][i] + delta;
+        }
+
+        Some(ColMatrix::new(columns))
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+#ifndef WINTERFELL_H
+#define WINTERFELL_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* AIR IDENTIFIERS */
+
+/* Fibonacci sequence over a 128-bit field, proved using BLAKE3 hash function. */
+#define WINTERFELL_AIR_FIB2 1u
+/* Fibonacci sequence over a 64-bit field, proved using Rescue Prime hash function. */
+#define WINTERFELL_AIR_FIB_SMALL 2u
+/* Multiplicative Fibonacci sequence over a 128-bit field, proved using BLAKE3 hash function. */
+#define WINTERFELL_AIR_MULFIB2 3u
+
+/* STATUS CODES */
+
+typedef enum {
+    WINTERFELL_STATUS_OK = 0,
+    WINTERFELL_STATUS_INVALID_ARGUMENT = 1,
+    WINTERFELL_STATUS_INVALID_OPTIONS = 2,
+    WINTERFELL_STATUS_UNKNOWN_AIR = 3,
+    WINTERFELL_STATUS_PROVING_FAILED = 4,
+    WINTERFELL_STATUS_VERIFICATION_FAILED = 5,
+    WINTERFELL_STATUS_PANIC = 6,
+} WinterfellStatus;
+
+/* BYTE BUFFER */
+
+/*
+ * A byte buffer allocated by the library and owned by the caller; it must be released via
+ * winterfell_buffer_free(). An empty buffer has a null data pointer.
+ */
+typedef struct {
+    uint8_t *data;
+    size_t len;
+    size_t capacity;
+} WinterfellBuffer;
+
+/*
+ * Releases the memory held by the buffer and resets the buffer to an empty one. Releasing an
+ * empty buffer (or passing a null pointer) has no effect.
+ */
+void winterfell_buffer_free(WinterfellBuffer *buffer);
+
+/* PROVER */
+
+/*
+ * Generates a proof for computing a sequence of the specified length (a power of two, at least
+ * 16) using the AIR with the specified identifier.
+ *
+ * options is a null-terminated string with proof options in the compact format, e.g.,
+ * "q=28,b=8,g=16,ext=quad,fri=8/31"; if it is null, default options are used.
+ *
+ * On success, the serialized proof and the serialized public inputs are written into proof and
+ * pub_inputs; both must be released via winterfell_buffer_free(). On failure, both are empty.
+ */
+WinterfellStatus winterfell_prove(
+    uint32_t air_id,
+    size_t sequence_length,
+    const char *options,
+    WinterfellBuffer *proof,
+    WinterfellBuffer *pub_inputs);
+
+/* VERIFIER */
+
+/*
+ * Verifies the serialized proof against the serialized public inputs; the AIR is determined by
+ * the identifier recorded in the proof. The proof is accepted only if it provides at least
+ * min_security_level bits of conjectured security.
+ *
+ * Input buffers are borrowed for the duration of the call only.
+ */
+WinterfellStatus winterfell_verify(
+    const uint8_t *proof,
+    size_t proof_len,
+    const uint8_t *pub_inputs,
+    size_t pub_inputs_len,
+    uint32_t min_security_level);
+
+/* ERRORS */
+
+/*
+ * Returns a null-terminated description of the last error which occurred on the calling thread,
+ * or null if no error has occurred yet. The string is owned by the library and remains valid
+ * until the next call into the library on the same thread.
+ */
+const char *winterfell_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* WINTERFELL_H */
@@ -0,0 +1,344 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate exposes Winterfell prover and verifier via a C ABI, so that they can be used from
+//! languages which can call C functions (e.g., C, C++, Go, or Swift).
+//!
+//! Since AIRs are Rust types, the set of computations which can be proved is fixed at compile
+//! time; currently, the following computations from the examples crate are available (see
+//! [WINTERFELL_AIR_FIB2], [WINTERFELL_AIR_FIB_SMALL], and [WINTERFELL_AIR_MULFIB2]):
+//! * Fibonacci sequence over a 128-bit field, proved using BLAKE3 hash function.
+//! * Fibonacci sequence over a 64-bit field, proved using Rescue Prime hash function.
+//! * Multiplicative Fibonacci sequence over a 128-bit field, proved using BLAKE3 hash function.
+//!
+//! Proofs are generated via [winterfell_prove()] and verified via [winterfell_verify()]. The
+//! verifier dispatches a proof to the right AIR using an [AirRegistry]: the AIR is determined by
+//! the identifier recorded in the proof, and so the caller does not need to specify it.
+//!
+//! # Conventions
+//! * All functions return a [WinterfellStatus]; when it is not [WinterfellStatus::Ok], a
+//!   description of the error can be retrieved via [winterfell_last_error()].
+//! * Byte buffers passed into functions as pointer/length pairs are borrowed for the duration
+//!   of the call only.
+//! * Byte buffers returned from functions via [WinterfellBuffer] are owned by the caller and
+//!   must be released via [winterfell_buffer_free()]; they must not be released by any other
+//!   means (e.g., `free()`).
+//! * Panics never cross the ABI boundary; a panic is reported as [WinterfellStatus::Panic].
+//!
+//! A C header declaring all of the above is located in `include/winterfell.h`.
+
+use examples::fibonacci::{fib2, fib_small, mulfib2};
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    mem::ManuallyDrop,
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+use winterfell::{
+    crypto::{
+        hashers::{Blake3_256, Rp64_256},
+        DefaultRandomCoin,
+    },
+    math::fields::f128,
+    AcceptableOptions, Air, AirRegistry, ProofOptions, Prover, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// AIR IDENTIFIERS
+// ================================================================================================
+
+/// Identifier of the Fibonacci sequence AIR over a 128-bit field (2 terms per step).
+pub const WINTERFELL_AIR_FIB2: u32 = fib2::FibAir::AIR_ID;
+
+/// Identifier of the Fibonacci sequence AIR over a 64-bit field (2 terms per step).
+pub const WINTERFELL_AIR_FIB_SMALL: u32 = fib_small::FibSmall::AIR_ID;
+
+/// Identifier of the multiplicative Fibonacci sequence AIR over a 128-bit field (2 terms per
+/// step).
+pub const WINTERFELL_AIR_MULFIB2: u32 = mulfib2::MulFib2Air::AIR_ID;
+
+type Blake3 = Blake3_256<f128::BaseElement>;
+
+// STATUS CODES
+// ================================================================================================
+
+/// Result of a call to a function of this crate.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinterfellStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A pointer argument was null or an argument had an invalid value.
+    InvalidArgument = 1,
+    /// The proof options could not be parsed.
+    InvalidOptions = 2,
+    /// The AIR identifier does not refer to any of the supported AIRs.
+    UnknownAir = 3,
+    /// The prover failed to generate a proof.
+    ProvingFailed = 4,
+    /// The proof is malformed or does not attest to a correct execution of the computation.
+    VerificationFailed = 5,
+    /// The call panicked.
+    Panic = 6,
+}
+
+// BYTE BUFFER
+// ================================================================================================
+
+/// A byte buffer allocated by this crate and owned by the caller.
+///
+/// A buffer must be released via [winterfell_buffer_free()]; an empty buffer has a null `data`
+/// pointer.
+#[repr(C)]
+#[derive(Debug)]
+pub struct WinterfellBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl WinterfellBuffer {
+    /// A buffer which does not hold any data.
+    const EMPTY: Self = Self {
+        data: ptr::null_mut(),
+        len: 0,
+        capacity: 0,
+    };
+
+    /// Transfers ownership of the bytes to the caller.
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let mut bytes = ManuallyDrop::new(bytes);
+        Self {
+            data: bytes.as_mut_ptr(),
+            len: bytes.len(),
+            capacity: bytes.capacity(),
+        }
+    }
+}
+
+/// Releases the memory held by the buffer and resets the buffer to an empty one.
+///
+/// Releasing an empty buffer (or passing a null pointer) has no effect.
+///
+/// # Safety
+/// `buffer` must be null or point to a buffer returned by one of the functions of this crate
+/// which has not been modified by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn winterfell_buffer_free(buffer: *mut WinterfellBuffer) {
+    if let Some(buffer) = buffer.as_mut() {
+        if !buffer.data.is_null() {
+            drop(Vec::from_raw_parts(buffer.data, buffer.len, buffer.capacity));
+        }
+        *buffer = WinterfellBuffer::EMPTY;
+    }
+}
+
+// PROVER
+// ================================================================================================
+
+/// Generates a proof for computing a sequence of the specified length using the AIR with the
+/// specified identifier.
+///
+/// On success, the serialized proof is written into `proof` and the serialized public inputs
+/// (i.e., the last term of the sequence) are written into `pub_inputs`; both buffers must be
+/// released by the caller via [winterfell_buffer_free()]. On failure, both buffers are empty.
+///
+/// `options` is a null-terminated string with proof options in the compact format (e.g.,
+/// `q=28,b=8,g=16,ext=quad,fri=8/31`); if `options` is null, default options are used.
+///
+/// # Safety
+/// `options` must be null or point to a null-terminated string, and `proof` and `pub_inputs`
+/// must point to writable [WinterfellBuffer] values.
+#[no_mangle]
+pub unsafe extern "C" fn winterfell_prove(
+    air_id: u32,
+    sequence_length: usize,
+    options: *const c_char,
+    proof: *mut WinterfellBuffer,
+    pub_inputs: *mut WinterfellBuffer,
+) -> WinterfellStatus {
+    if proof.is_null() || pub_inputs.is_null() {
+        return set_error(WinterfellStatus::InvalidArgument, "output buffer is null".into());
+    }
+    proof.write(WinterfellBuffer::EMPTY);
+    pub_inputs.write(WinterfellBuffer::EMPTY);
+
+    let options = if options.is_null() {
+        ""
+    } else {
+        match CStr::from_ptr(options).to_str() {
+            Ok(options) => options,
+            Err(err) => return set_error(WinterfellStatus::InvalidOptions, err.to_string()),
+        }
+    };
+
+    run(|| {
+        let options = options
+            .parse::<ProofOptions>()
+            .map_err(|err| (WinterfellStatus::InvalidOptions, err.to_string()))?;
+        if !sequence_length.is_power_of_two() || sequence_length < 16 {
+            let message = format!(
+                "sequence length must be a power of two and at least 16, but was {sequence_length}"
+            );
+            return Err((WinterfellStatus::InvalidArgument, message));
+        }
+
+        let (proof_bytes, pub_inputs_bytes) = match air_id {
+            WINTERFELL_AIR_FIB2 => {
+                let prover = fib2::FibProver::<Blake3>::new(options);
+                let trace = prover.build_trace(sequence_length);
+                prove(prover, trace)?
+            }
+            WINTERFELL_AIR_FIB_SMALL => {
+                let prover = fib_small::FibSmallProver::<Rp64_256>::new(options);
+                let trace = prover.build_trace(sequence_length);
+                prove(prover, trace)?
+            }
+            WINTERFELL_AIR_MULFIB2 => {
+                let prover = mulfib2::MulFib2Prover::<Blake3>::new(options);
+                let trace = prover.build_trace(sequence_length);
+                prove(prover, trace)?
+            }
+            _ => {
+                let message = format!("AIR with identifier {air_id} is not supported");
+                return Err((WinterfellStatus::UnknownAir, message));
+            }
+        };
+
+        proof.write(WinterfellBuffer::from_vec(proof_bytes));
+        pub_inputs.write(WinterfellBuffer::from_vec(pub_inputs_bytes));
+        Ok(())
+    })
+}
+
+/// Generates a proof for the specified trace and returns the serialized proof and public inputs.
+fn prove<P: Prover>(
+    prover: P,
+    trace: P::Trace,
+) -> Result<(Vec<u8>, Vec<u8>), (WinterfellStatus, String)>
+where
+    <P::Air as Air>::PublicInputs: Serializable,
+{
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover
+        .prove(trace)
+        .map_err(|err| (WinterfellStatus::ProvingFailed, err.to_string()))?;
+    Ok((proof.to_bytes(), pub_inputs.to_bytes()))
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifies the serialized proof against the serialized public inputs.
+///
+/// The proof is dispatched to one of the supported AIRs according to the AIR identifier recorded
+/// in the proof, and it is accepted only if it provides at least `min_security_level` bits of
+/// conjectured security.
+///
+/// # Safety
+/// `proof` and `pub_inputs` must point to readable memory regions of `proof_len` and
+/// `pub_inputs_len` bytes respectively; either of the pointers may be null if the corresponding
+/// length is zero.
+#[no_mangle]
+pub unsafe extern "C" fn winterfell_verify(
+    proof: *const u8,
+    proof_len: usize,
+    pub_inputs: *const u8,
+    pub_inputs_len: usize,
+    min_security_level: u32,
+) -> WinterfellStatus {
+    let (proof, pub_inputs) =
+        match (as_slice(proof, proof_len), as_slice(pub_inputs, pub_inputs_len)) {
+            (Some(proof), Some(pub_inputs)) => (proof, pub_inputs),
+            _ => {
+                return set_error(WinterfellStatus::InvalidArgument, "input buffer is null".into())
+            }
+        };
+
+    run(|| {
+        build_registry(min_security_level)
+            .verify_any(proof, pub_inputs)
+            .map_err(|err| (WinterfellStatus::VerificationFailed, err.to_string()))
+    })
+}
+
+/// Returns a registry of all supported AIRs which accepts proofs with the specified minimum
+/// conjectured security level.
+fn build_registry(min_security_level: u32) -> AirRegistry {
+    let acceptable_options = || AcceptableOptions::MinConjecturedSecurity(min_security_level);
+    let mut registry = AirRegistry::new();
+    registry.register::<fib2::FibAir, Blake3, DefaultRandomCoin<Blake3>>(acceptable_options());
+    registry.register::<fib_small::FibSmall, Rp64_256, DefaultRandomCoin<Rp64_256>>(
+        acceptable_options(),
+    );
+    registry
+        .register::<mulfib2::MulFib2Air, Blake3, DefaultRandomCoin<Blake3>>(acceptable_options());
+    registry
+}
+
+// ERRORS
+// ================================================================================================
+
+thread_local! {
+    /// Description of the last error which occurred on the current thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Returns a null-terminated description of the last error which occurred on the calling thread,
+/// or null if no error has occurred yet.
+///
+/// The returned string is owned by this crate and remains valid until the next call to a
+/// function of this crate on the same thread.
+#[no_mangle]
+pub extern "C" fn winterfell_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| match error.borrow().as_ref() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+/// Records the specified error message for the calling thread and returns the status.
+fn set_error(status: WinterfellStatus, message: String) -> WinterfellStatus {
+    // interior null bytes cannot be represented in a C string
+    let message = CString::new(message.replace('\0', " ")).expect("message contains null bytes");
+    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+    status
+}
+
+/// Executes the specified closure converting errors and panics into status codes.
+fn run<F>(f: F) -> WinterfellStatus
+where
+    F: FnOnce() -> Result<(), (WinterfellStatus, String)>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => WinterfellStatus::Ok,
+        Ok(Err((status, message))) => set_error(status, message),
+        Err(payload) => {
+            let message = match payload.downcast_ref::<&str>() {
+                Some(message) => message.to_string(),
+                None => match payload.downcast_ref::<String>() {
+                    Some(message) => message.clone(),
+                    None => "unknown panic".to_string(),
+                },
+            };
+            set_error(WinterfellStatus::Panic, message)
+        }
+    }
+}
+
+/// Returns the memory region as a slice, or None if the pointer is null and the length is not
+/// zero.
+unsafe fn as_slice<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, len))
+    }
+}
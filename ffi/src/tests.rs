@@ -0,0 +1,102 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+const OPTIONS: &[u8] = b"q=28,b=8,g=0,ext=none,fri=4/31\0";
+
+#[test]
+fn prove_verify_all_airs() {
+    for air_id in [WINTERFELL_AIR_FIB2, WINTERFELL_AIR_FIB_SMALL, WINTERFELL_AIR_MULFIB2] {
+        let (mut proof, mut pub_inputs) = prove_sequence(air_id, 64);
+        assert_eq!(WinterfellStatus::Ok, verify(&proof, &pub_inputs, 0));
+
+        // the proof does not provide the requested security level
+        assert_eq!(WinterfellStatus::VerificationFailed, verify(&proof, &pub_inputs, 100));
+
+        unsafe {
+            winterfell_buffer_free(&mut proof);
+            winterfell_buffer_free(&mut pub_inputs);
+        }
+        assert!(proof.data.is_null());
+        assert!(pub_inputs.data.is_null());
+    }
+}
+
+#[test]
+fn verify_fail_wrong_inputs() {
+    let (mut proof, mut pub_inputs) = prove_sequence(WINTERFELL_AIR_FIB_SMALL, 64);
+    unsafe { *pub_inputs.data ^= 1 };
+    assert_eq!(WinterfellStatus::VerificationFailed, verify(&proof, &pub_inputs, 0));
+    assert!(!winterfell_last_error().is_null());
+
+    // a truncated proof cannot be deserialized
+    proof.len /= 2;
+    assert_eq!(WinterfellStatus::VerificationFailed, verify(&proof, &pub_inputs, 0));
+
+    unsafe {
+        winterfell_buffer_free(&mut proof);
+        winterfell_buffer_free(&mut pub_inputs);
+    }
+}
+
+#[test]
+fn prove_fail_invalid_arguments() {
+    let mut proof = WinterfellBuffer::EMPTY;
+    let mut pub_inputs = WinterfellBuffer::EMPTY;
+    let options = OPTIONS.as_ptr().cast();
+
+    let status = unsafe { winterfell_prove(0, 64, options, &mut proof, &mut pub_inputs) };
+    assert_eq!(WinterfellStatus::UnknownAir, status);
+
+    let status =
+        unsafe { winterfell_prove(WINTERFELL_AIR_FIB2, 63, options, &mut proof, &mut pub_inputs) };
+    assert_eq!(WinterfellStatus::InvalidArgument, status);
+
+    let options = b"q=28,fri=3/31\0".as_ptr().cast();
+    let status =
+        unsafe { winterfell_prove(WINTERFELL_AIR_FIB2, 64, options, &mut proof, &mut pub_inputs) };
+    assert_eq!(WinterfellStatus::InvalidOptions, status);
+
+    let status = unsafe {
+        winterfell_prove(WINTERFELL_AIR_FIB2, 64, ptr::null(), ptr::null_mut(), &mut pub_inputs)
+    };
+    assert_eq!(WinterfellStatus::InvalidArgument, status);
+
+    assert!(proof.data.is_null());
+    assert!(pub_inputs.data.is_null());
+    assert_eq!(WinterfellStatus::InvalidArgument, unsafe {
+        winterfell_verify(ptr::null(), 1, ptr::null(), 0, 0)
+    });
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn prove_sequence(air_id: u32, sequence_length: usize) -> (WinterfellBuffer, WinterfellBuffer) {
+    let mut proof = WinterfellBuffer::EMPTY;
+    let mut pub_inputs = WinterfellBuffer::EMPTY;
+    let status = unsafe {
+        winterfell_prove(
+            air_id,
+            sequence_length,
+            OPTIONS.as_ptr().cast(),
+            &mut proof,
+            &mut pub_inputs,
+        )
+    };
+    assert_eq!(WinterfellStatus::Ok, status);
+    (proof, pub_inputs)
+}
+
+fn verify(
+    proof: &WinterfellBuffer,
+    pub_inputs: &WinterfellBuffer,
+    min_security: u32,
+) -> WinterfellStatus {
+    unsafe {
+        winterfell_verify(proof.data, proof.len, pub_inputs.data, pub_inputs.len, min_security)
+    }
+}
@@ -11,6 +11,9 @@ use crypto::RandomCoinError;
 // ================================================================================================
 
 /// Defines errors which can occur during FRI proof verification.
+///
+/// By default, errors are displayed tersely; use the alternate format (`{:#}`) to also display
+/// details of the failure, such as the index of the layer which did not match its commitment.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VerifierError {
     /// Attempt to draw a random value from a public coin failed.
@@ -20,8 +23,9 @@ pub enum VerifierError {
     UnsupportedFoldingFactor(usize),
     /// Number of query positions does not match the number of provided evaluations.
     NumPositionEvaluationMismatch(usize, usize),
-    /// Evaluations at queried positions did not match layer commitment made by the prover.
-    LayerCommitmentMismatch,
+    /// Evaluations at queried positions did not match layer commitment made by the prover at the
+    /// specified layer.
+    LayerCommitmentMismatch(usize),
     /// Degree-respecting projection was not performed correctly at one of the layers.
     InvalidLayerFolding(usize),
     /// FRI remainder did not match the commitment.
@@ -34,6 +38,10 @@ pub enum VerifierError {
     RemainderDegreeMismatch(usize),
     /// Polynomial degree at one of the FRI layers could not be divided evenly by the folding factor.
     DegreeTruncation(usize, usize, usize),
+    /// Number of FRI layer proof-of-work nonces is not consistent with the number of layers.
+    NumLayerNoncesMismatch(usize, usize),
+    /// Proof-of-work nonce for the specified FRI layer does not have enough leading zeros.
+    LayerProofOfWorkNotValid(usize),
 }
 
 impl fmt::Display for VerifierError {
@@ -49,8 +57,12 @@ impl fmt::Display for VerifierError {
             Self::NumPositionEvaluationMismatch(num_positions, num_evaluations) => write!(f,
                 "the number of query positions must be the same as the number of polynomial evaluations, but {num_positions} and {num_evaluations} were provided"
             ),
-            Self::LayerCommitmentMismatch => {
-                write!(f, "FRI queries did not match layer commitment made by the prover")
+            Self::LayerCommitmentMismatch(layer) => {
+                write!(f, "FRI queries did not match layer commitment made by the prover")?;
+                if f.alternate() {
+                    write!(f, " at layer {layer}")?;
+                }
+                Ok(())
             }
             Self::InvalidLayerFolding(layer) => {
                 write!(f, "degree-respecting projection is not consistent at layer {layer}")
@@ -70,6 +82,44 @@ impl fmt::Display for VerifierError {
             Self::DegreeTruncation(degree, folding, layer) => {
                 write!(f, "degree reduction from {degree} by {folding} at layer {layer} results in degree truncation")
             }
+            Self::NumLayerNoncesMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI layer proof-of-work nonces, but {actual} were provided")
+            }
+            Self::LayerProofOfWorkNotValid(layer) => {
+                write!(f, "FRI layer proof-of-work verification failed at layer {layer}")
+            }
+        }
+    }
+}
+
+impl VerifierError {
+    /// Returns a stable code identifying the kind of this error.
+    ///
+    /// Codes have the form `fri.<kind>` and are stable across releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::RandomCoinError(_) => "fri.random_coin",
+            Self::UnsupportedFoldingFactor(_) => "fri.unsupported_folding_factor",
+            Self::NumPositionEvaluationMismatch(_, _) => "fri.num_position_evaluation_mismatch",
+            Self::LayerCommitmentMismatch(_) => "fri.layer_commitment_mismatch",
+            Self::InvalidLayerFolding(_) => "fri.invalid_layer_folding",
+            Self::RemainderCommitmentMismatch => "fri.remainder_commitment_mismatch",
+            Self::InvalidRemainderFolding => "fri.invalid_remainder_folding",
+            Self::RemainderDegreeNotValid => "fri.remainder_degree_not_valid",
+            Self::RemainderDegreeMismatch(_) => "fri.remainder_degree_mismatch",
+            Self::DegreeTruncation(_, _, _) => "fri.degree_truncation",
+            Self::NumLayerNoncesMismatch(_, _) => "fri.num_layer_nonces_mismatch",
+            Self::LayerProofOfWorkNotValid(_) => "fri.layer_proof_of_work_not_valid",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::RandomCoinError(err) => Some(err),
+            _ => None,
         }
     }
 }
@@ -44,6 +44,23 @@
 //!    are provided to the [verify()](FriVerifier::verify()) function directly. The values at
 //!    remaining layers, the verifier reads from the specified verifier channel.
 //!
+//! # Embedding FRI into other protocols
+//! FRI prover and verifier do not depend on the rest of the STARK protocol: all interaction
+//! between them goes through the [ProverChannel] and [VerifierChannel] traits, and random values
+//! on the verifier side are drawn from a [RandomCoin](crypto::RandomCoin). Thus, a protocol which
+//! maintains its own transcript can use FRI as follows:
+//!
+//! * On the prover side, implement [ProverChannel] so that layer commitments are absorbed into
+//!   the transcript of the protocol, and α values are drawn from it. The prover invokes the
+//!   channel in a fixed order described in the documentation of the trait.
+//! * On the verifier side, implement [VerifierChannel] to return the data sent by the prover
+//!   (e.g., parsed from a [FriProof] embedded into a larger proof, or received over a network),
+//!   and pass to [FriVerifier::new()] a coin which is in the same state as the transcript of the
+//!   prover was before the first FRI layer was committed to.
+//!
+//! The verifier checks all values read from the channel against the layer commitments, and
+//! thus, a channel implementation does not need to be trusted to validate the data it returns.
+//!
 //! # Protocol parameters
 //! The current implementation supports executing FRI protocol with dynamically configurable
 //! parameters including:
@@ -53,7 +70,8 @@
 //! * Domain blowup factor,
 //! * Hash function (used for Merkle tree commitments),
 //! * Folding factor (used for degree reduction for each FRI layer),
-//! * Maximum size of the last FRI layer.
+//! * Maximum size of the last FRI layer,
+//! * Layer grinding factor (proof-of-work performed after committing to each FRI layer).
 //!
 //! # References
 //! * StarkWare's blog post on [Low Degree Testing](https://medium.com/starkware/low-degree-testing-f7614f5172db)
@@ -85,3 +103,6 @@ mod errors;
 pub use errors::VerifierError;
 
 pub mod utils;
+
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -9,20 +9,35 @@ use math::StarkField;
 // ================================================================================================
 
 /// FRI protocol config options for proof generation and verification.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FriOptions {
     folding_factor: usize,
     remainder_max_degree: usize,
     blowup_factor: usize,
+    layer_grinding_factor: u32,
+    domain_offset: Option<u64>,
 }
 
 impl FriOptions {
+    /// Smallest folding factor supported by the FRI protocol.
+    pub const MIN_FOLDING_FACTOR: usize = 2;
+
+    /// Largest folding factor supported by the FRI protocol.
+    pub const MAX_FOLDING_FACTOR: usize = 32;
+
+    /// Largest number of bits of proof-of-work which can be required for each FRI layer.
+    pub const MAX_LAYER_GRINDING_FACTOR: u32 = 32;
+
     /// Returns a new [FriOptions] struct instantiated with the specified parameters.
     ///
+    /// Larger folding factors reduce the number of FRI layers (and thus, the number of Merkle
+    /// authentication paths in a proof) at the expense of more values per query in every layer
+    /// and more work for the verifier to fold these values.
+    ///
     /// # Panics
     /// Panics if:
     /// - `blowup_factor` is not a power of two.
-    /// - `folding_factor` is not 2, 4, 8, or 16.
+    /// - `folding_factor` is not a power of two between 2 and 32.
     pub fn new(blowup_factor: usize, folding_factor: usize, remainder_max_degree: usize) -> Self {
         // TODO: change panics to errors
         assert!(
@@ -30,27 +45,67 @@ impl FriOptions {
             "blowup factor must be a power of two, but was {blowup_factor}"
         );
         assert!(
-            folding_factor == 2
-                || folding_factor == 4
-                || folding_factor == 8
-                || folding_factor == 16,
+            folding_factor.is_power_of_two()
+                && (Self::MIN_FOLDING_FACTOR..=Self::MAX_FOLDING_FACTOR).contains(&folding_factor),
             "folding factor {folding_factor} is not supported"
         );
         FriOptions {
             folding_factor,
             remainder_max_degree,
             blowup_factor,
+            layer_grinding_factor: 0,
+            domain_offset: None,
         }
     }
 
-    /// Returns the offset by which the evaluation domain is shifted.
+    /// Returns a new [FriOptions] struct with the layer grinding factor set to the specified
+    /// number of bits.
+    ///
+    /// When the grinding factor is greater than zero, after committing to each FRI layer (except
+    /// for the remainder), the prover must find a proof-of-work nonce such that the seed of the
+    /// public coin (which determines the next α) together with the nonce hashes to a value with
+    /// at least `grinding_factor` leading zeros. A dishonest prover attempting to resample α by
+    /// changing a layer commitment must thus redo the work for every attempt, which reduces the
+    /// soundness error of the commit phase of the protocol by `grinding_factor` bits per layer
+    /// at the cost of one extra nonce per layer in the proof.
     ///
-    /// The domain is shifted by multiplying every element in the domain by this offset.
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 32.
+    pub fn with_layer_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        assert!(
+            grinding_factor <= Self::MAX_LAYER_GRINDING_FACTOR,
+            "layer grinding factor cannot be greater than {}, but was {grinding_factor}",
+            Self::MAX_LAYER_GRINDING_FACTOR
+        );
+        self.layer_grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Returns a new [FriOptions] struct with the evaluation domain shifted by the specified
+    /// offset instead of the primitive element of the field.
     ///
-    /// Currently, the offset is hard-coded to be the primitive element in the field specified by
-    /// type parameter `B`.
+    /// The offset must be an element of the field in which the protocol is executed, and must not
+    /// be in the (multiplicative) subgroup spanned by the evaluation domain; this is not checked
+    /// here as it depends on the field and on the size of the domain.
+    ///
+    /// # Panics
+    /// Panics if `offset` is zero.
+    pub fn with_domain_offset(mut self, offset: u64) -> Self {
+        assert!(offset != 0, "domain offset cannot be zero");
+        self.domain_offset = Some(offset);
+        self
+    }
+
+    /// Returns the offset by which the evaluation domain is shifted.
+    ///
+    /// The domain is shifted by multiplying every element in the domain by this offset. Unless
+    /// set via [with_domain_offset()](FriOptions::with_domain_offset), the offset is the
+    /// primitive element in the field specified by type parameter `B`.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
     }
 
     /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
@@ -64,7 +119,11 @@ impl FriOptions {
     /// Returns maximum allowed remainder polynomial degree.
     ///
     /// In combination with `folding_factor` this property defines how many FRI layers are needed
-    /// for an evaluation domain of a given size.
+    /// for an evaluation domain of a given size. A larger remainder results in fewer FRI layers
+    /// (and thus, fewer Merkle authentication paths in a proof), but the verifier needs to read
+    /// and evaluate the entire remainder polynomial at every query position. This trade-off is
+    /// particularly important for recursive verification, where the cost of evaluating the
+    /// remainder and the cost of verifying authentication paths can differ significantly.
     pub fn remainder_max_degree(&self) -> usize {
         self.remainder_max_degree
     }
@@ -78,14 +137,26 @@ impl FriOptions {
         self.blowup_factor
     }
 
+    /// Returns the number of leading zeros required from the proof-of-work performed after
+    /// committing to each FRI layer; zero means that FRI layers are not ground.
+    pub fn layer_grinding_factor(&self) -> u32 {
+        self.layer_grinding_factor
+    }
+
     /// Computes and return the number of FRI layers required for a domain of the specified size.
     ///
     /// The number of layers for a given domain size is defined by the `folding_factor` and
     /// `remainder_max_degree` and `blowup_factor` settings.
+    ///
+    /// A layer is folded only if the degree of the folded polynomial does not drop below zero
+    /// (i.e., the folded domain is not smaller than the blowup factor). Thus, for large folding
+    /// factors, the degree of the remainder polynomial may exceed `remainder_max_degree`, but it
+    /// is always smaller than `folding_factor`.
     pub fn num_fri_layers(&self, mut domain_size: usize) -> usize {
         let mut result = 0;
         let max_remainder_size = (self.remainder_max_degree + 1) * self.blowup_factor;
-        while domain_size > max_remainder_size {
+        let min_folded_size = self.blowup_factor * self.folding_factor;
+        while domain_size > max_remainder_size && domain_size >= min_folded_size {
             domain_size /= self.folding_factor;
             result += 1;
         }
@@ -23,7 +23,8 @@ use utils::{
 /// A proof consists of zero or more layers and a remainder polynomial. Each layer contains a set of
 /// polynomial evaluations at positions queried by the verifier as well as Merkle authentication
 /// paths for these evaluations (the Merkle paths are compressed into a batch Merkle proof). The
-/// remainder polynomial is given by its list of coefficients i.e. field elements.
+/// remainder polynomial is given by its list of coefficients i.e. field elements. If FRI layer
+/// grinding is enabled, the proof also contains a proof-of-work nonce for each layer.
 ///
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
 /// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
@@ -33,21 +34,25 @@ pub struct FriProof {
     layers: Vec<FriProofLayer>,
     remainder: Vec<u8>,
     num_partitions: u8, // stored as power of 2
+    layer_nonces: Vec<u64>,
 }
 
 impl FriProof {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new FRI proof from the provided layers and remainder polynomial.
+    /// Creates a new FRI proof from the provided layers, remainder polynomial, and layer
+    /// proof-of-work nonces.
     ///
     /// # Panics
     /// Panics if:
     /// * Number of remainder elements zero or is not a power of two.
     /// * `num_partitions` is zero or is not a power of two.
+    /// * Number of layer nonces is greater than 255.
     pub(crate) fn new<E: FieldElement>(
         layers: Vec<FriProofLayer>,
         remainder: Vec<E>,
         num_partitions: usize,
+        layer_nonces: Vec<u64>,
     ) -> Self {
         assert!(!remainder.is_empty(), "number of remainder elements must be greater than zero");
         assert!(
@@ -60,10 +65,12 @@ impl FriProof {
             num_partitions.is_power_of_two(),
             "number of partitions must be a power of two, but was {num_partitions}"
         );
+        assert!(layer_nonces.len() <= u8::MAX as usize, "too many layer nonces");
         FriProof {
             layers,
             remainder: remainder.to_bytes(),
             num_partitions: num_partitions.trailing_zeros() as u8,
+            layer_nonces,
         }
     }
 
@@ -73,6 +80,7 @@ impl FriProof {
             layers: Vec::new(),
             remainder: Vec::new(),
             num_partitions: 0,
+            layer_nonces: Vec::new(),
         }
     }
 
@@ -97,12 +105,42 @@ impl FriProof {
         2usize.pow(self.num_partitions as u32)
     }
 
+    /// Returns proof-of-work nonces for the FRI layers of this proof.
+    ///
+    /// The list is empty if FRI layer grinding was not enabled during proof generation.
+    pub fn layer_nonces(&self) -> &[u64] {
+        &self.layer_nonces
+    }
+
+    /// Returns serialized query values and serialized Merkle authentication paths of the layer
+    /// at the specified index.
+    ///
+    /// The values and the paths can be parsed via [parse_layers()](FriProof::parse_layers());
+    /// this accessor is meant for tooling which inspects proofs without knowing the field and
+    /// the hash function used to generate them.
+    ///
+    /// # Panics
+    /// Panics if `layer_idx` is not smaller than the number of layers in this proof.
+    pub fn layer_bytes(&self, layer_idx: usize) -> (&[u8], &[u8]) {
+        let layer = &self.layers[layer_idx];
+        (&layer.values, &layer.paths)
+    }
+
+    /// Returns serialized coefficients of the remainder polynomial.
+    ///
+    /// The coefficients can be parsed via [parse_remainder()](FriProof::parse_remainder).
+    pub fn remainder_bytes(&self) -> &[u8] {
+        &self.remainder
+    }
+
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
+        // +1 for number of layers, +4 for remainder length, +1 for number of partitions,
+        // +1 for number of layer nonces
+        let nonces_size = self.layer_nonces.len() * 8;
         self.layers
             .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+            .fold(self.remainder.len() + nonces_size + 7, |acc, layer| acc + layer.size())
     }
 
     // PARSING
@@ -189,11 +227,17 @@ impl Serializable for FriProof {
         }
 
         // write remainder
-        target.write_u16(self.remainder.len() as u16);
+        target.write_u32(self.remainder.len() as u32);
         target.write_bytes(&self.remainder);
 
         // write number of partitions
         target.write_u8(self.num_partitions);
+
+        // write layer proof-of-work nonces
+        target.write_u8(self.layer_nonces.len() as u8);
+        for &nonce in self.layer_nonces.iter() {
+            target.write_u64(nonce);
+        }
     }
 }
 
@@ -208,16 +252,23 @@ impl Deserializable for FriProof {
         let layers = FriProofLayer::read_batch_from(source, num_layers)?;
 
         // read remainder
-        let num_remainder_bytes = source.read_u16()? as usize;
+        let num_remainder_bytes = source.read_u32()? as usize;
         let remainder = source.read_vec(num_remainder_bytes)?;
 
         // read number of partitions
         let num_partitions = source.read_u8()?;
 
+        // read layer proof-of-work nonces
+        let num_layer_nonces = source.read_u8()? as usize;
+        let layer_nonces = (0..num_layer_nonces)
+            .map(|_| source.read_u64())
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(FriProof {
             layers,
             remainder,
             num_partitions,
+            layer_nonces,
         })
     }
 }
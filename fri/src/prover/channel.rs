@@ -20,6 +20,27 @@ use utils::collections::Vec;
 /// In the interactive version of the protocol, the verifier chooses α uniformly at random from
 /// the entire field. In the non-interactive version, the α is drawn pseudo-randomly based on the
 /// commitments the prover has written into the channel up to this point.
+///
+/// # Implementing the trait
+/// This trait can be implemented by protocols which maintain their own transcript, to execute
+/// FRI as a part of a larger protocol. During execution of the commit phase via
+/// [FriProver::build_layers()](crate::FriProver::build_layers()), the prover invokes the methods
+/// of the channel in the following order:
+///
+/// 1. For every FRI layer except the last one: [commit_fri_layer()](ProverChannel::commit_fri_layer()),
+///    then [grind_fri_layer()](ProverChannel::grind_fri_layer()) if layer grinding is enabled,
+///    and then [draw_fri_alpha()](ProverChannel::draw_fri_alpha()).
+/// 2. For the remainder: [commit_fri_layer()](ProverChannel::commit_fri_layer()) with a hash of
+///    the remainder polynomial coefficients.
+///
+/// When the proof is built via [FriProver::build_batched_layers()](crate::FriProver::build_batched_layers()),
+/// the batching challenge is drawn via [draw_fri_alpha()](ProverChannel::draw_fri_alpha())
+/// before the first layer is committed to.
+///
+/// For the resulting proof to be verifiable by a [FriVerifier](crate::FriVerifier), every α must
+/// be derived from the commitments in the same way as the random coin passed to the verifier
+/// derives it, i.e., the channel must reseed its coin with every commitment and draw α from the
+/// coin immediately afterwards.
 pub trait ProverChannel<E: FieldElement> {
     /// Hash function used by the prover to commit to polynomial evaluations.
     type Hasher: Hasher;
@@ -45,6 +66,18 @@ pub trait ProverChannel<E: FieldElement> {
     /// prover, in the non-interactive version, the α is pseudo-randomly generated based on the
     /// values the prover previously wrote into the channel.
     fn draw_fri_alpha(&mut self) -> E;
+
+    /// Returns a proof-of-work nonce for the most recently committed FRI layer, and absorbs the
+    /// nonce into the channel.
+    ///
+    /// The nonce must be such that, when hashed with the current state of the channel, the result
+    /// has at least `grinding_factor` leading zeros. Once the nonce is found, the channel must be
+    /// reseeded with it so that the next α depends on the nonce; otherwise, α could be computed
+    /// for many candidate layer commitments without doing any work, and the proof-of-work would
+    /// be performed only for a favorable one. This method is invoked only if FRI layer grinding is
+    /// enabled, after [commit_fri_layer()](ProverChannel::commit_fri_layer()) and before
+    /// [draw_fri_alpha()](ProverChannel::draw_fri_alpha()) for every layer except the remainder.
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64;
 }
 
 // DEFAULT PROVER CHANNEL IMPLEMENTATION
@@ -133,4 +166,12 @@ where
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
 }
@@ -6,7 +6,7 @@
 use crate::{
     folding::{apply_drp, fold_positions},
     proof::{FriProof, FriProofLayer},
-    utils::hash_values,
+    utils::{combine_batch, hash_values},
     FriOptions,
 };
 use core::marker::PhantomData;
@@ -64,6 +64,10 @@ mod tests;
 /// sends it to the prover. In the non-interactive version, α is pseudo-randomly generated based
 /// on the values the prover has written into the channel up to that point.
 ///
+/// If layer grinding is enabled in the protocol options, the prover also performs a proof-of-work
+/// after committing to each layer (except for the remainder) and before drawing α; the resulting
+/// nonces are included in the proof.
+///
 /// The prover keeps all FRI layers (consisting of evaluations and corresponding Merkle trees) in
 /// its internal state.
 ///
@@ -95,6 +99,7 @@ where
 {
     options: FriOptions,
     layers: Vec<FriLayer<B, E, H>>,
+    layer_nonces: Vec<u64>,
     remainder_poly: FriRemainder<E>,
     _channel: PhantomData<C>,
 }
@@ -124,6 +129,7 @@ where
         FriProver {
             options,
             layers: Vec::new(),
+            layer_nonces: Vec::new(),
             remainder_poly: FriRemainder(vec![]),
             _channel: PhantomData,
         }
@@ -151,6 +157,7 @@ where
     /// Clears a vector of internally stored layers.
     pub fn reset(&mut self) {
         self.layers.clear();
+        self.layer_nonces.clear();
         self.remainder_poly.0.clear();
     }
 
@@ -177,12 +184,17 @@ where
 
         // reduce the degree by folding_factor at each iteration until the remaining polynomial
         // has small enough degree
-        for _ in 0..self.options.num_fri_layers(evaluations.len()) {
+        for _layer in 0..self.options.num_fri_layers(evaluations.len()) {
+            #[cfg(feature = "tracing")]
+            let _span =
+                tracing::info_span!("fri_layer", layer = _layer, domain_size = evaluations.len())
+                    .entered();
             match self.folding_factor() {
                 2 => self.build_layer::<2>(channel, &mut evaluations),
                 4 => self.build_layer::<4>(channel, &mut evaluations),
                 8 => self.build_layer::<8>(channel, &mut evaluations),
                 16 => self.build_layer::<16>(channel, &mut evaluations),
+                32 => self.build_layer::<32>(channel, &mut evaluations),
                 _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
             }
         }
@@ -190,6 +202,28 @@ where
         self.set_remainder(channel, &mut evaluations);
     }
 
+    /// Executes the commit phase of the FRI protocol for a batch of polynomials.
+    ///
+    /// Each vector in `evaluations` contains evaluations of a single polynomial over the same
+    /// domain *D*. Before the commit phase, the prover draws a batching challenge γ from the
+    /// channel (in the same way as α values are drawn), and then executes the commit phase for
+    /// evaluations of the random linear combination `f_0 + γ·f_1 + γ^2·f_2 + ...` of these
+    /// polynomials as described for [build_layers()](FriProver::build_layers()). Thus, a single
+    /// FRI proof attests to the degree of all polynomials in the batch; the polynomials should be
+    /// committed to via the channel before this method is invoked.
+    ///
+    /// The proof must be verified by a verifier instantiated via
+    /// [FriVerifier::new_batched()](crate::FriVerifier::new_batched()).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The prover state is dirty (the vector of layers is not empty).
+    /// * `evaluations` is empty or the polynomials are not evaluated over domains of the same size.
+    pub fn build_batched_layers(&mut self, channel: &mut C, evaluations: Vec<Vec<E>>) {
+        let batching_challenge = channel.draw_fri_alpha();
+        self.build_layers(channel, combine_batch(&evaluations, batching_challenge));
+    }
+
     /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
     /// alpha from the channel and use it to perform degree-respecting projection.
     fn build_layer<const N: usize>(&mut self, channel: &mut C, evaluations: &mut Vec<E>) {
@@ -203,6 +237,12 @@ where
             MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
         channel.commit_fri_layer(*evaluation_tree.root());
 
+        // perform proof-of-work for this layer, if required, so that α cannot be re-drawn cheaply
+        let grinding_factor = self.options.layer_grinding_factor();
+        if grinding_factor > 0 {
+            self.layer_nonces.push(channel.grind_fri_layer(grinding_factor));
+        }
+
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
         // projection to reduce the degree of evaluations by N
         let alpha = channel.draw_fri_alpha();
@@ -257,6 +297,7 @@ where
                     4 => query_layer::<B, E, H, 4>(&self.layers[i], &positions),
                     8 => query_layer::<B, E, H, 8>(&self.layers[i], &positions),
                     16 => query_layer::<B, E, H, 16>(&self.layers[i], &positions),
+                    32 => query_layer::<B, E, H, 32>(&self.layers[i], &positions),
                     _ => unimplemented!("folding factor {} is not supported", folding_factor),
                 };
 
@@ -267,11 +308,12 @@ where
 
         // use the remaining polynomial values directly as proof
         let remainder = self.remainder_poly.0.clone();
+        let layer_nonces = self.layer_nonces.clone();
 
         // clear layers so that another proof can be generated
         self.reset();
 
-        FriProof::new(layers, remainder, 1)
+        FriProof::new(layers, remainder, 1, layer_nonces)
     }
 }
 
@@ -3,12 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{DefaultProverChannel, FriProver};
+use super::{DefaultProverChannel, FriProver, ProverChannel};
 use crate::{
     verifier::{DefaultVerifierChannel, FriVerifier},
-    FriOptions, FriProof, VerifierError,
+    FriOptions, FriProof, VerifierChannel, VerifierError,
 };
-use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, RandomCoin};
+use crypto::{hashers::Blake3_256, BatchMerkleProof, DefaultRandomCoin, Hasher, RandomCoin};
 use math::{fft, fields::f128::BaseElement, FieldElement};
 use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
 
@@ -35,6 +35,277 @@ fn fri_folding_4() {
     fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
 }
 
+#[test]
+fn fri_folding_32() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 5;
+    let max_remainder_degree = 7;
+    fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
+}
+
+#[test]
+fn fri_folding_32_large_remainder() {
+    // after a single layer the polynomial has degree 15 which cannot be folded by 32; thus, the
+    // remainder is larger than the specified max remainder degree
+    let trace_length_e = 9;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 5;
+    let max_remainder_degree = 7;
+    let options = FriOptions::new(1 << lde_blowup_e, 1 << folding_factor_e, max_remainder_degree);
+    assert_eq!(1, options.num_fri_layers(1 << (trace_length_e + lde_blowup_e)));
+    fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
+}
+
+#[test]
+fn fri_batched_prove_verify() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = (0..3)
+        .map(|i| build_evaluations_with_offset(trace_length, lde_blowup, i * 1000))
+        .collect::<Vec<_>>();
+
+    // instantiate the prover and generate a single proof for all polynomials
+    let mut prover = FriProver::new(options.clone());
+    prover.build_batched_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    let verify_batched = |evaluations: &[Vec<BaseElement>]| {
+        let mut channel = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+            proof.clone(),
+            commitments.clone(),
+            trace_length * lde_blowup,
+            options.folding_factor(),
+        )
+        .unwrap();
+        let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+        let verifier =
+            FriVerifier::new_batched(&mut channel, &mut coin, options.clone(), trace_length - 1)?;
+        let queried_evaluations = evaluations
+            .iter()
+            .map(|column| positions.iter().map(|&p| column[p]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        verifier.verify_batched(&mut channel, &queried_evaluations, &positions)
+    };
+
+    let result = verify_batched(&evaluations);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // the proof does not verify if any of the polynomials is different
+    let mut other_evaluations = evaluations.clone();
+    other_evaluations[2] = build_evaluations_with_offset(trace_length, lde_blowup, 7);
+    assert!(verify_batched(&other_evaluations).is_err());
+
+    // or if the polynomials are batched in a different order
+    let mut other_evaluations = evaluations;
+    other_evaluations.swap(0, 1);
+    assert!(verify_batched(&other_evaluations).is_err());
+}
+
+#[test]
+fn fri_layer_grinding_prove_verify() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 7).with_layer_grinding_factor(8);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // a proof-of-work nonce is included for every layer except the remainder
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    assert_eq!(proof.num_layers(), proof.layer_nonces().len());
+    assert!(proof.num_layers() > 0);
+
+    let commitments = channel.layer_commitments().to_vec();
+    let domain_size = trace_length * lde_blowup;
+    let verify = |proof: FriProof, options: &FriOptions| {
+        verify_proof(
+            proof,
+            commitments.clone(),
+            &evaluations,
+            trace_length - 1,
+            domain_size,
+            &positions,
+            options,
+        )
+    };
+    let result = verify(proof.clone(), &options);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // the proof is rejected if the verifier does not expect layer nonces
+    let plain_options = FriOptions::new(lde_blowup, 4, 7);
+    let num_layers = proof.num_layers();
+    assert_eq!(
+        Err(VerifierError::NumLayerNoncesMismatch(0, num_layers)),
+        verify(proof.clone(), &plain_options)
+    );
+
+    // the proof is rejected if the nonce of the last layer is modified; the nonce is serialized
+    // at the end of the proof
+    let mut proof_bytes = proof.to_bytes();
+    let nonce_start = proof_bytes.len() - 8;
+    proof_bytes[nonce_start] ^= 1;
+    let proof = FriProof::read_from_bytes(&proof_bytes).unwrap();
+    assert_eq!(
+        Err(VerifierError::LayerProofOfWorkNotValid(num_layers - 1)),
+        verify(proof, &options)
+    );
+}
+
+#[test]
+fn fri_layer_grinding_nonce_affects_alpha() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 7).with_layer_grinding_factor(2);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+    let domain_size = trace_length * lde_blowup;
+
+    // find another nonce which satisfies proof-of-work for the first layer; the verifier's coin
+    // is in the same state as the prover's coin was when the first layer was ground
+    let num_layers = proof.num_layers();
+    let nonce = proof.layer_nonces()[0];
+    let new_coin = || {
+        let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+        coin.reseed(commitments[0]);
+        coin
+    };
+    let mut coin = new_coin();
+    let other_nonce = (nonce + 1..u64::MAX)
+        .find(|&nonce| coin.check_leading_zeros(nonce) >= options.layer_grinding_factor())
+        .unwrap();
+
+    // α drawn after absorbing the nonce depends on the nonce
+    let mut other_coin = new_coin();
+    coin.reseed_with_int(nonce);
+    other_coin.reseed_with_int(other_nonce);
+    assert_ne!(coin.draw::<BaseElement>().unwrap(), other_coin.draw::<BaseElement>().unwrap());
+
+    // thus, replacing the nonce with another valid nonce for the first layer changes α and the
+    // state of the coin for all subsequent layers, and the proof is rejected even though
+    // proof-of-work for the first layer is valid
+    let mut proof_bytes = proof.to_bytes();
+    let nonce_start = proof_bytes.len() - 8 * num_layers;
+    proof_bytes[nonce_start..nonce_start + 8].copy_from_slice(&other_nonce.to_le_bytes());
+    let proof = FriProof::read_from_bytes(&proof_bytes).unwrap();
+    assert_eq!(other_nonce, proof.layer_nonces()[0]);
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(
+        matches!(
+            result,
+            Err(VerifierError::LayerProofOfWorkNotValid(1) | VerifierError::InvalidLayerFolding(_))
+        ),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn fri_external_transcript() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // the transcript of an outer protocol which has already absorbed some data before FRI
+    let new_transcript = || {
+        let mut transcript = DefaultRandomCoin::<Blake3>::new(&[BaseElement::new(42)]);
+        transcript.reseed(Blake3::hash(b"outer protocol commitment"));
+        transcript
+    };
+
+    // run FRI prover over the transcript of the outer protocol
+    let mut channel = TranscriptProverChannel {
+        transcript: new_transcript(),
+        commitments: Vec::new(),
+    };
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.transcript.draw_integers(32, domain_size, 0).unwrap();
+    let positions = dedup_positions(positions);
+    let proof = prover.build_proof(&positions);
+
+    // verify the proof against the same transcript
+    let verify = |channel: &mut TranscriptVerifierChannel| {
+        let mut transcript = new_transcript();
+        let verifier =
+            FriVerifier::new(channel, &mut transcript, options.clone(), trace_length - 1)?;
+        let queried_evaluations = positions.iter().map(|&p| evaluations[p]).collect::<Vec<_>>();
+        verifier.verify(channel, &queried_evaluations, &positions)
+    };
+
+    let mut verifier_channel =
+        TranscriptVerifierChannel::new(proof.clone(), channel.commitments.clone(), domain_size);
+    let result = verify(&mut verifier_channel);
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // values returned by a channel are checked against the layer commitments
+    let mut verifier_channel =
+        TranscriptVerifierChannel::new(proof, channel.commitments.clone(), domain_size);
+    verifier_channel.layer_queries[0][0] += BaseElement::ONE;
+    assert_eq!(Err(VerifierError::LayerCommitmentMismatch(0)), verify(&mut verifier_channel));
+}
+
+// RANDOM PROOF TESTS
+// ================================================================================================
+
+#[cfg(feature = "testing")]
+proptest::proptest! {
+    #![proptest_config(proptest::test_runner::Config::with_cases(32))]
+
+    #[test]
+    fn fri_random_proof_rejected(
+        options in proptest::arbitrary::any::<FriOptions>(),
+        trace_length_e in 3..=8usize,
+        seed: u64,
+    ) {
+        let trace_length = 1 << trace_length_e;
+        let domain_size = trace_length * options.blowup_factor();
+        let mut coin = DefaultRandomCoin::<Blake3>::new(&[BaseElement::from(seed)]);
+        let positions = dedup_positions(coin.draw_integers(8, domain_size, 0).unwrap());
+        let (proof, commitments) = crate::testing::build_random_proof::<BaseElement, Blake3, _>(
+            &options,
+            domain_size,
+            &positions,
+            &mut coin,
+        );
+        proptest::prop_assert_eq!(options.num_fri_layers(domain_size), proof.num_layers());
+
+        // the proof is well-formed, and thus, it must be rejected without panicking
+        let evaluations = build_evaluations(trace_length, options.blowup_factor());
+        let result = verify_proof(
+            proof,
+            commitments,
+            &evaluations,
+            trace_length - 1,
+            domain_size,
+            &positions,
+            &options,
+        );
+        proptest::prop_assert!(result.is_err());
+    }
+}
+
 // TEST UTILS
 // ================================================================================================
 
@@ -46,7 +317,18 @@ pub fn build_prover_channel(
 }
 
 pub fn build_evaluations(trace_length: usize, lde_blowup: usize) -> Vec<BaseElement> {
-    let mut p = (0..trace_length as u128).map(BaseElement::new).collect::<Vec<_>>();
+    build_evaluations_with_offset(trace_length, lde_blowup, 0)
+}
+
+/// Builds evaluations of a polynomial whose coefficients are `offset`, `offset + 1`, etc.
+pub fn build_evaluations_with_offset(
+    trace_length: usize,
+    lde_blowup: usize,
+    offset: u128,
+) -> Vec<BaseElement> {
+    let mut p = (offset..offset + trace_length as u128)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>();
     let domain_size = trace_length * lde_blowup;
     p.resize(domain_size, BaseElement::ZERO);
 
@@ -132,3 +414,85 @@ fn fri_prove_verify(
     );
     assert!(result.is_err());
 }
+
+fn dedup_positions(mut positions: Vec<usize>) -> Vec<usize> {
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+/// A prover channel which executes FRI over the transcript of an outer protocol.
+struct TranscriptProverChannel {
+    transcript: DefaultRandomCoin<Blake3>,
+    commitments: Vec<<Blake3 as Hasher>::Digest>,
+}
+
+impl ProverChannel<BaseElement> for TranscriptProverChannel {
+    type Hasher = Blake3;
+
+    fn commit_fri_layer(&mut self, layer_root: <Blake3 as Hasher>::Digest) {
+        self.commitments.push(layer_root);
+        self.transcript.reseed(layer_root);
+    }
+
+    fn draw_fri_alpha(&mut self) -> BaseElement {
+        self.transcript.draw().unwrap()
+    }
+
+    fn grind_fri_layer(&mut self, _grinding_factor: u32) -> u64 {
+        unimplemented!("layer grinding is not used by the outer protocol")
+    }
+}
+
+/// A verifier channel which returns FRI data embedded into a proof of an outer protocol.
+struct TranscriptVerifierChannel {
+    commitments: Vec<<Blake3 as Hasher>::Digest>,
+    layer_proofs: Vec<BatchMerkleProof<Blake3>>,
+    layer_queries: Vec<Vec<BaseElement>>,
+    remainder: Vec<BaseElement>,
+}
+
+impl TranscriptVerifierChannel {
+    fn new(
+        proof: FriProof,
+        commitments: Vec<<Blake3 as Hasher>::Digest>,
+        domain_size: usize,
+    ) -> Self {
+        let remainder = proof.parse_remainder().unwrap();
+        let (layer_queries, layer_proofs) = proof.parse_layers(domain_size, 4).unwrap();
+        Self {
+            commitments,
+            layer_proofs,
+            layer_queries,
+            remainder,
+        }
+    }
+}
+
+impl VerifierChannel<BaseElement> for TranscriptVerifierChannel {
+    type Hasher = Blake3;
+
+    fn read_fri_num_partitions(&self) -> usize {
+        1
+    }
+
+    fn read_fri_layer_commitments(&mut self) -> Vec<<Blake3 as Hasher>::Digest> {
+        core::mem::take(&mut self.commitments)
+    }
+
+    fn take_next_fri_layer_queries(&mut self) -> Vec<BaseElement> {
+        self.layer_queries.remove(0)
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<Blake3> {
+        self.layer_proofs.remove(0)
+    }
+
+    fn take_fri_remainder(&mut self) -> Vec<BaseElement> {
+        core::mem::take(&mut self.remainder)
+    }
+
+    fn read_fri_layer_nonces(&mut self) -> Vec<u64> {
+        Vec::new()
+    }
+}
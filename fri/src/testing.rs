@@ -0,0 +1,167 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Utilities for property-testing code which consumes FRI proofs.
+//!
+//! This module provides an [Arbitrary] implementation for [FriOptions], and the
+//! [build_random_proof()] function which generates FRI proofs which are structurally valid (i.e.,
+//! can be parsed and have the shape expected by a verifier) but contain random values.
+
+use crate::{
+    folding::fold_positions,
+    proof::{FriProof, FriProofLayer},
+    FriOptions,
+};
+use crypto::{ElementHasher, MerkleTree, RandomCoin};
+use math::FieldElement;
+use proptest::prelude::*;
+use utils::collections::Vec;
+
+// FRI OPTIONS
+// ================================================================================================
+
+impl Arbitrary for FriOptions {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates options with the blowup factor between 2 and 128, any supported folding factor,
+    /// the remainder degree bound up to 255, and the layer grinding factor of either 0 or up to 8
+    /// bits (so that proofs for these options can be generated quickly).
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (1..=7u32, 1..=5u32, 0..=8u32, prop_oneof![Just(0u32), 1..=8u32])
+            .prop_map(|(blowup, folding, remainder, grinding)| {
+                FriOptions::new(1 << blowup, 1 << folding, (1 << remainder) - 1)
+                    .with_layer_grinding_factor(grinding)
+            })
+            .boxed()
+    }
+}
+
+// FRI PROOF
+// ================================================================================================
+
+/// Returns a FRI proof with random values for the specified query `positions` in a domain of
+/// size `domain_size`, together with commitments to all FRI layers (including the remainder).
+///
+/// The returned proof is structurally valid: it contains the number of layers implied by
+/// `options`, the values queried at each layer hash into the leaves of the Merkle authentication
+/// paths of that layer, and these paths resolve to the returned layer commitments. However,
+/// since the values are random, the proof is not expected to pass verification. Leaves of layer
+/// commitment trees which are not queried are set to default digests, and layer proof-of-work
+/// nonces (if any) are random.
+///
+/// All randomness is drawn from the specified `coin`, and thus, the same proof is returned for
+/// the same coin state.
+///
+/// # Panics
+/// Panics if:
+/// * `domain_size` is not a power of two or is smaller than the blowup factor.
+/// * `positions` is empty or contains a position outside of the domain.
+pub fn build_random_proof<E, H, R>(
+    options: &FriOptions,
+    domain_size: usize,
+    positions: &[usize],
+    coin: &mut R,
+) -> (FriProof, Vec<H::Digest>)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
+    assert!(
+        domain_size >= options.blowup_factor(),
+        "domain size is smaller than blowup factor"
+    );
+    assert!(!positions.is_empty(), "at least one query position must be provided");
+    assert!(
+        positions.iter().all(|&p| p < domain_size),
+        "query positions must be smaller than the domain size"
+    );
+
+    let num_layers = options.num_fri_layers(domain_size);
+    let folding_factor = options.folding_factor();
+    let mut layers = Vec::with_capacity(num_layers);
+    let mut commitments = Vec::with_capacity(num_layers + 1);
+
+    // build random layers and query them at the positions folded in the same way as the FRI
+    // prover would fold them
+    let mut positions = positions.to_vec();
+    let mut domain_size = domain_size;
+    for _ in 0..num_layers {
+        positions = fold_positions(&positions, domain_size, folding_factor);
+
+        // sort of a static dispatch for folding_factor parameter
+        let (layer, commitment) = match folding_factor {
+            2 => build_random_layer::<E, H, R, 2>(domain_size, &positions, coin),
+            4 => build_random_layer::<E, H, R, 4>(domain_size, &positions, coin),
+            8 => build_random_layer::<E, H, R, 8>(domain_size, &positions, coin),
+            16 => build_random_layer::<E, H, R, 16>(domain_size, &positions, coin),
+            32 => build_random_layer::<E, H, R, 32>(domain_size, &positions, coin),
+            _ => unimplemented!("folding factor {} is not supported", folding_factor),
+        };
+        layers.push(layer);
+        commitments.push(commitment);
+        domain_size /= folding_factor;
+    }
+
+    // the remainder is committed to by hashing all of its coefficients
+    let remainder: Vec<E> =
+        (0..domain_size / options.blowup_factor()).map(|_| draw_element(coin)).collect();
+    commitments.push(<H as ElementHasher>::hash_elements(&remainder));
+
+    let layer_nonces = if options.layer_grinding_factor() > 0 && num_layers > 0 {
+        coin.draw_integers(num_layers, 1 << 32, 0)
+            .expect("failed to draw layer nonces")
+            .into_iter()
+            .map(|nonce| nonce as u64)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    (FriProof::new(layers, remainder, 1, layer_nonces), commitments)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a layer with random values over a domain of the specified size, commits to it, and
+/// queries it at the specified positions.
+fn build_random_layer<E, H, R, const N: usize>(
+    domain_size: usize,
+    positions: &[usize],
+    coin: &mut R,
+) -> (FriProofLayer, H::Digest)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    // only the queried leaves need to be backed by values; all other leaves are left empty
+    let mut leaves = vec![H::Digest::default(); domain_size / N];
+    let mut queried_values: Vec<[E; N]> = Vec::with_capacity(positions.len());
+    for &position in positions {
+        let values = core::array::from_fn(|_| draw_element(coin));
+        leaves[position] = H::hash_elements(&values);
+        queried_values.push(values);
+    }
+
+    let tree = MerkleTree::<H>::new(leaves).expect("failed to build a Merkle tree for a FRI layer");
+    let proof = tree
+        .prove_batch(positions)
+        .expect("failed to generate a Merkle proof for FRI layer queries");
+
+    (FriProofLayer::new(queried_values, proof), *tree.root())
+}
+
+/// Draws a random field element from the specified coin.
+fn draw_element<E, R>(coin: &mut R) -> E
+where
+    E: FieldElement,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    coin.draw().expect("failed to draw a random element")
+}
@@ -49,3 +49,28 @@ where
     });
     result
 }
+
+/// Returns evaluations of a random linear combination `f_0 + γ·f_1 + γ^2·f_2 + ...` of
+/// polynomials `f_i` whose evaluations are specified by `evaluations`, where `γ` is the
+/// `batching_challenge`.
+///
+/// # Panics
+/// Panics if `evaluations` is empty or if the evaluation vectors are not all of the same length.
+pub fn combine_batch<E: FieldElement>(evaluations: &[Vec<E>], batching_challenge: E) -> Vec<E> {
+    assert!(!evaluations.is_empty(), "at least one polynomial must be provided");
+    let num_evaluations = evaluations[0].len();
+    assert!(
+        evaluations.iter().all(|column| column.len() == num_evaluations),
+        "all polynomials must be evaluated at the same number of points"
+    );
+
+    let mut result = evaluations[0].clone();
+    let mut coefficient = E::ONE;
+    for column in evaluations.iter().skip(1) {
+        coefficient *= batching_challenge;
+        iter_mut!(result, 1024).zip(column).for_each(|(r, &v)| {
+            *r += coefficient * v;
+        });
+    }
+    result
+}
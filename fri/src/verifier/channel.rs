@@ -19,6 +19,18 @@ use utils::{collections::Vec, group_vector_elements, DeserializationError};
 ///
 /// Note: that reading removes the data from the channel. Thus, reading duplicated values from
 /// the channel should not be possible.
+///
+/// # Implementing the trait
+/// This trait can be implemented by protocols which embed FRI proofs into their own proofs, or
+/// which receive FRI data from the prover in some other way. The verifier reads the number of
+/// partitions, layer commitments, and layer nonces when it is instantiated, and then, during the
+/// query phase, it reads the proof and queries for every FRI layer (in order, starting with the
+/// first layer) followed by the remainder.
+///
+/// Implementations do not need to validate the data they return: layer queries are checked
+/// against the corresponding Merkle authentication paths, and the paths are checked against the
+/// layer commitments by the [read_layer_queries()](VerifierChannel::read_layer_queries())
+/// method; the remainder is checked against its commitment by the verifier.
 pub trait VerifierChannel<E: FieldElement> {
     /// Hash function used by the prover to commit to polynomial evaluations.
     type Hasher: ElementHasher<BaseField = E::BaseField>;
@@ -65,29 +77,47 @@ pub trait VerifierChannel<E: FieldElement> {
     /// Reads and removes the remainder polynomial from the channel.
     fn take_fri_remainder(&mut self) -> Vec<E>;
 
+    /// Reads and removes from the channel proof-of-work nonces for all FRI layers.
+    ///
+    /// The returned vector is empty if FRI layer grinding was not used by the prover.
+    fn read_fri_layer_nonces(&mut self) -> Vec<u64>;
+
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Returns FRI query values at the specified positions from the current FRI layer and advances
     /// layer pointer by one.
     ///
-    /// This also checks if the values are valid against the provided FRI layer commitment.
+    /// This also checks if the values are valid against the provided FRI layer commitment; `depth`
+    /// is the index of the current FRI layer and is used only to report errors. Specifically, the
+    /// values at every position must hash into the corresponding leaf of the layer proof, and the
+    /// layer proof must be valid against the commitment.
     ///
     /// # Errors
     /// Returns an error if query values did not match layer commitment.
     fn read_layer_queries<const N: usize>(
         &mut self,
+        depth: usize,
         positions: &[usize],
         commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
     ) -> Result<Vec<[E; N]>, VerifierError> {
         let layer_proof = self.take_next_fri_layer_proof();
         MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
-            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
-
-        // TODO: make sure layer queries hash into leaves of layer proof
+            .map_err(|_| VerifierError::LayerCommitmentMismatch(depth))?;
 
+        // make sure layer queries hash into leaves of layer proof
         let layer_queries = self.take_next_fri_layer_queries();
-        Ok(group_vector_elements(layer_queries))
+        if layer_queries.len() != layer_proof.leaves.len() * N {
+            return Err(VerifierError::LayerCommitmentMismatch(depth));
+        }
+        let layer_queries: Vec<[E; N]> = group_vector_elements(layer_queries);
+        for (values, leaf) in layer_queries.iter().zip(layer_proof.leaves.iter()) {
+            if Self::Hasher::hash_elements(values) != *leaf {
+                return Err(VerifierError::LayerCommitmentMismatch(depth));
+            }
+        }
+
+        Ok(layer_queries)
     }
 
     /// Returns FRI remainder polynomial read from this channel.
@@ -113,6 +143,7 @@ pub struct DefaultVerifierChannel<E: FieldElement, H: ElementHasher<BaseField =
     layer_queries: Vec<Vec<E>>,
     remainder: Vec<E>,
     num_partitions: usize,
+    layer_nonces: Vec<u64>,
 }
 
 impl<E, H> DefaultVerifierChannel<E, H>
@@ -131,6 +162,7 @@ where
         folding_factor: usize,
     ) -> Result<Self, DeserializationError> {
         let num_partitions = proof.num_partitions();
+        let layer_nonces = proof.layer_nonces().to_vec();
 
         let remainder = proof.parse_remainder()?;
         let (layer_queries, layer_proofs) =
@@ -142,6 +174,7 @@ where
             layer_queries,
             remainder,
             num_partitions,
+            layer_nonces,
         })
     }
 }
@@ -172,4 +205,8 @@ where
     fn take_fri_remainder(&mut self) -> Vec<E> {
         self.remainder.clone()
     }
+
+    fn read_fri_layer_nonces(&mut self) -> Vec<u64> {
+        self.layer_nonces.drain(..).collect()
+    }
 }
@@ -5,7 +5,11 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
+use crate::{
+    folding::fold_positions,
+    utils::{combine_batch, map_positions_to_indexes},
+    FriOptions, VerifierError,
+};
 use core::{convert::TryInto, marker::PhantomData, mem};
 use crypto::{ElementHasher, RandomCoin};
 use math::{polynom, FieldElement, StarkField};
@@ -67,6 +71,7 @@ where
     domain_generator: E::BaseField,
     layer_commitments: Vec<H::Digest>,
     layer_alphas: Vec<E>,
+    batching_challenge: Option<E>,
     options: FriOptions,
     num_partitions: usize,
     _channel: PhantomData<C>,
@@ -99,6 +104,8 @@ where
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
+    /// * FRI layer grinding is enabled in the `options` but proof-of-work nonces read from the
+    ///   channel are missing or not valid.
     pub fn new(
         channel: &mut C,
         public_coin: &mut R,
@@ -111,20 +118,44 @@ where
 
         let num_partitions = channel.read_fri_num_partitions();
 
-        // read layer commitments from the channel and use them to build a list of alphas
+        // read layer commitments from the channel and use them to build a list of alphas for all
+        // layers except the remainder (the prover does not draw α after committing to the
+        // remainder); if layer grinding is enabled, also check proof-of-work for these layers and
+        // absorb the nonces into the coin so that α depends on them
         let layer_commitments = channel.read_fri_layer_commitments();
+        let layer_nonces = channel.read_fri_layer_nonces();
+        let grinding_factor = options.layer_grinding_factor();
+        let num_ground_layers = if grinding_factor > 0 {
+            layer_commitments.len().saturating_sub(1)
+        } else {
+            0
+        };
+        if layer_nonces.len() != num_ground_layers {
+            return Err(VerifierError::NumLayerNoncesMismatch(
+                num_ground_layers,
+                layer_nonces.len(),
+            ));
+        }
+
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
             public_coin.reseed(*commitment);
+            if let Some(&nonce) = layer_nonces.get(depth) {
+                if public_coin.check_leading_zeros(nonce) < grinding_factor {
+                    return Err(VerifierError::LayerProofOfWorkNotValid(depth));
+                }
+                public_coin.reseed_with_int(nonce);
+            }
+            if depth == layer_commitments.len() - 1 {
+                break;
+            }
             let alpha = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
             layer_alphas.push(alpha);
 
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
-            if depth != layer_commitments.len() - 1
-                && max_degree_plus_1 % options.folding_factor() != 0
-            {
+            if max_degree_plus_1 % options.folding_factor() != 0 {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
                     options.folding_factor(),
@@ -140,6 +171,7 @@ where
             domain_generator,
             layer_commitments,
             layer_alphas,
+            batching_challenge: None,
             options,
             num_partitions,
             _channel: PhantomData,
@@ -147,9 +179,37 @@ where
         })
     }
 
+    /// Returns a new instance of FRI verifier for a batch of polynomials.
+    ///
+    /// This is equivalent to [new()](FriVerifier::new()) except that, before reading FRI layer
+    /// commitments from the `channel`, the verifier draws a batching challenge γ from the
+    /// `public_coin`. The query phase must then be executed via
+    /// [verify_batched()](FriVerifier::verify_batched()).
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as [new()](FriVerifier::new()), or if the batching
+    /// challenge could not be drawn from the coin.
+    pub fn new_batched(
+        channel: &mut C,
+        public_coin: &mut R,
+        options: FriOptions,
+        max_poly_degree: usize,
+    ) -> Result<Self, VerifierError> {
+        let batching_challenge = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+        let mut verifier = Self::new(channel, public_coin, options, max_poly_degree)?;
+        verifier.batching_challenge = Some(batching_challenge);
+        Ok(verifier)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the batching challenge drawn by this verifier, or `None` if the verifier was not
+    /// instantiated for a batch of polynomials.
+    pub fn batching_challenge(&self) -> Option<E> {
+        self.batching_challenge
+    }
+
     /// Returns maximum degree of a polynomial accepted by this verifier.
     pub fn max_poly_degree(&self) -> usize {
         self.max_poly_degree
@@ -221,10 +281,49 @@ where
             4 => self.verify_generic::<4>(channel, evaluations, positions),
             8 => self.verify_generic::<8>(channel, evaluations, positions),
             16 => self.verify_generic::<16>(channel, evaluations, positions),
+            32 => self.verify_generic::<32>(channel, evaluations, positions),
             _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
         }
     }
 
+    /// Executes the query phase of the FRI protocol for a batch of polynomials.
+    ///
+    /// Each vector in `evaluations` contains evaluations of a single polynomial of the batch at x
+    /// coordinates specified by the `positions` slice, in the same order in which the
+    /// polynomials were batched by the prover. Returns `Ok(())` if the random linear combination
+    /// of these polynomials defined by the batching challenge is a polynomial with degree <=
+    /// `max_poly_degree` (see [verify()](FriVerifier::verify())).
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as [verify()](FriVerifier::verify()); in addition,
+    /// an error is returned if `evaluations` is empty or the length of any of its vectors is not
+    /// equal to the length of `positions`.
+    ///
+    /// # Panics
+    /// Panics if this verifier was not instantiated via
+    /// [new_batched()](FriVerifier::new_batched()).
+    pub fn verify_batched(
+        &self,
+        channel: &mut C,
+        evaluations: &[Vec<E>],
+        positions: &[usize],
+    ) -> Result<(), VerifierError> {
+        let batching_challenge =
+            self.batching_challenge.expect("FRI verifier was not instantiated for a batch");
+        if let Some(column) = evaluations.iter().find(|column| column.len() != positions.len()) {
+            return Err(VerifierError::NumPositionEvaluationMismatch(
+                positions.len(),
+                column.len(),
+            ));
+        }
+        if evaluations.is_empty() {
+            return Err(VerifierError::NumPositionEvaluationMismatch(positions.len(), 0));
+        }
+
+        let evaluations = combine_batch(evaluations, batching_challenge);
+        self.verify(channel, &evaluations, positions)
+    }
+
     /// This is the actual implementation of the verification procedure described above, but it
     /// also takes folding factor as a generic parameter N.
     fn verify_generic<const N: usize>(
@@ -258,8 +357,8 @@ where
             );
             // read query values from the specified indexes in the Merkle tree
             let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
+            let layer_values =
+                channel.read_layer_queries(depth, &position_indexes, &layer_commitment)?;
             let query_values =
                 get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
             if evaluations != query_values {
@@ -292,7 +391,7 @@ where
             }
 
             // update variables for the next iteration of the loop
-            domain_generator = domain_generator.exp_vartime((N as u32).into());
+            domain_generator = domain_generator.exp_vartime((N as u64).into());
             max_degree_plus_1 /= N;
             domain_size /= N;
             mem::swap(&mut positions, &mut folded_positions);
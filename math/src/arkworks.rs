@@ -0,0 +1,194 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Conversions between fields of this crate and fields of the [arkworks](https://arkworks.rs)
+//! ecosystem.
+//!
+//! arkworks does not provide implementations of STARK-friendly fields, and thus, this module
+//! defines arkworks fields [F62], [F64], and [F128] with the same moduli and multiplicative
+//! generators as [f62](crate::fields::f62), [f64](crate::fields::f64), and
+//! [f128](crate::fields::f128) fields respectively. Elements can be converted between the
+//! corresponding fields via [From] and [Into], or generically via [ArkField] trait.
+//!
+//! Conversions go through the canonical integer representation of an element, and thus, do not
+//! depend on internal representations used by either library.
+//!
+//! Two-adic roots of unity of arkworks fields are derived from their generators. For [F64] this
+//! root differs from the one used by [f64](crate::fields::f64) field, and thus, evaluation
+//! domains of the same size contain the same elements, but in a different order.
+
+// the configs are derived via arkworks macros which implement traits in non-local scopes
+#![allow(unknown_lints, non_local_definitions)]
+
+use crate::{
+    fields::{f128, f62, f64},
+    StarkField,
+};
+use ark_ff::{
+    fields::{Fp128, Fp64, MontBackend, MontConfig},
+    BigInt, PrimeField,
+};
+
+// ARKWORKS FIELDS
+// ================================================================================================
+
+/// Parameters of the arkworks field with modulus 2<sup>62</sup> - 111 * 2<sup>39</sup> + 1.
+#[derive(MontConfig)]
+#[modulus = "4611624995532046337"]
+#[generator = "3"]
+pub struct F62Config;
+
+/// arkworks field with modulus 2<sup>62</sup> - 111 * 2<sup>39</sup> + 1.
+pub type F62 = Fp64<MontBackend<F62Config, 1>>;
+
+/// Parameters of the arkworks field with modulus 2<sup>64</sup> - 2<sup>32</sup> + 1.
+#[derive(MontConfig)]
+#[modulus = "18446744069414584321"]
+#[generator = "7"]
+pub struct F64Config;
+
+/// arkworks field with modulus 2<sup>64</sup> - 2<sup>32</sup> + 1.
+pub type F64 = Fp64<MontBackend<F64Config, 1>>;
+
+/// Parameters of the arkworks field with modulus 2<sup>128</sup> - 45 * 2<sup>40</sup> + 1.
+#[derive(MontConfig)]
+#[modulus = "340282366920938463463374557953744961537"]
+#[generator = "3"]
+pub struct F128Config;
+
+/// arkworks field with modulus 2<sup>128</sup> - 45 * 2<sup>40</sup> + 1.
+pub type F128 = Fp128<MontBackend<F128Config, 2>>;
+
+// ARK FIELD TRAIT
+// ================================================================================================
+
+/// Defines a correspondence between a STARK field and an arkworks prime field with the same
+/// modulus.
+pub trait ArkField: StarkField {
+    /// The arkworks field with the same modulus as this field.
+    type Ark: PrimeField;
+
+    /// Converts this element into an element of the corresponding arkworks field.
+    fn to_ark(self) -> Self::Ark;
+
+    /// Converts an element of the corresponding arkworks field into an element of this field.
+    fn from_ark(value: Self::Ark) -> Self;
+}
+
+impl ArkField for f62::BaseElement {
+    type Ark = F62;
+
+    fn to_ark(self) -> Self::Ark {
+        F62::from(self.as_int())
+    }
+
+    fn from_ark(value: Self::Ark) -> Self {
+        Self::new(value.into_bigint().0[0])
+    }
+}
+
+impl ArkField for f64::BaseElement {
+    type Ark = F64;
+
+    fn to_ark(self) -> Self::Ark {
+        F64::from(self.as_int())
+    }
+
+    fn from_ark(value: Self::Ark) -> Self {
+        Self::new(value.into_bigint().0[0])
+    }
+}
+
+impl ArkField for f128::BaseElement {
+    type Ark = F128;
+
+    fn to_ark(self) -> Self::Ark {
+        let value = self.as_int();
+        F128::from_bigint(BigInt([value as u64, (value >> 64) as u64]))
+            .expect("canonical value must be smaller than the modulus")
+    }
+
+    fn from_ark(value: Self::Ark) -> Self {
+        let limbs = value.into_bigint().0;
+        Self::new(((limbs[1] as u128) << 64) | limbs[0] as u128)
+    }
+}
+
+// CONVERSIONS
+// ================================================================================================
+
+macro_rules! impl_conversions {
+    ($element:ty, $ark:ty) => {
+        impl From<$element> for $ark {
+            fn from(value: $element) -> Self {
+                value.to_ark()
+            }
+        }
+
+        impl From<$ark> for $element {
+            fn from(value: $ark) -> Self {
+                <$element>::from_ark(value)
+            }
+        }
+    };
+}
+
+impl_conversions!(f62::BaseElement, F62);
+impl_conversions!(f64::BaseElement, F64);
+impl_conversions!(f128::BaseElement, F128);
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ArkField;
+    use crate::fields::{f128, f62, f64};
+    use ark_ff::{FftField, Field, PrimeField};
+    use rand_utils::rand_vector;
+
+    #[test]
+    fn ark_conversions_f62() {
+        check_conversions::<f62::BaseElement>();
+    }
+
+    #[test]
+    fn ark_conversions_f64() {
+        check_conversions::<f64::BaseElement>();
+    }
+
+    #[test]
+    fn ark_conversions_f128() {
+        check_conversions::<f128::BaseElement>();
+    }
+
+    fn check_conversions<B: ArkField>() {
+        // both fields have the same parameters
+        assert_eq!(B::MODULUS_BITS, <B::Ark as PrimeField>::MODULUS_BIT_SIZE);
+        assert_eq!(B::TWO_ADICITY, <B::Ark as FftField>::TWO_ADICITY);
+        assert_eq!(B::GENERATOR.to_ark(), <B::Ark as FftField>::GENERATOR);
+
+        // two-adic roots of unity of both fields have the same order
+        let root = B::TWO_ADIC_ROOT_OF_UNITY.to_ark();
+        let exp = [1u64 << (B::TWO_ADICITY - 1)];
+        assert_eq!(-<B::Ark as Field>::ONE, root.pow(exp));
+        assert_eq!(-<B::Ark as Field>::ONE, <B::Ark as FftField>::TWO_ADIC_ROOT_OF_UNITY.pow(exp));
+
+        // edge cases
+        assert_eq!(B::ZERO.to_ark(), <B::Ark as Field>::ZERO);
+        assert_eq!(B::ONE.to_ark(), <B::Ark as Field>::ONE);
+        assert_eq!(B::from_ark(-<B::Ark as Field>::ONE), -B::ONE);
+
+        // conversions round-trip and agree with field operations
+        let a = rand_vector::<B>(64);
+        let b = rand_vector::<B>(64);
+        for (&a, &b) in a.iter().zip(b.iter()) {
+            assert_eq!(a, B::from_ark(a.to_ark()));
+            assert_eq!(a + b, B::from_ark(a.to_ark() + b.to_ark()));
+            assert_eq!(a * b, B::from_ark(a.to_ark() * b.to_ark()));
+            assert_eq!(a.inv(), B::from_ark(a.to_ark().inverse().unwrap_or_default()));
+        }
+    }
+}
@@ -500,7 +500,7 @@ where
         "multiplicative subgroup of size {domain_size} does not exist in the specified base field"
     );
     let root = B::get_root_of_unity(domain_size.ilog2());
-    let inv_root = root.exp((domain_size as u32 - 1).into());
+    let inv_root = root.exp((domain_size as u64 - 1).into());
     let mut inv_twiddles = get_power_series(inv_root, domain_size / 2);
     permute(&mut inv_twiddles);
     inv_twiddles
@@ -99,6 +99,81 @@ impl BaseElement {
     }
 }
 
+// CANONICAL ENCODING
+// ------------------------------------------------------------------------------------------------
+
+/// Conversions between elements and the encoding used by other implementations of this field
+/// (e.g., Plonky2, Plonky3, Miden).
+///
+/// In this encoding, an element is represented by its canonical value (i.e., a value in the range
+/// [0, M)) in little-endian byte order. Element serialization and hashing (e.g., via BLAKE3 or
+/// SHA3 hashers) in this library use the same encoding. However, elements are stored in Montgomery
+/// form internally (see [BaseElement::inner()] and [BaseElement::from_mont()]), and thus, bytes
+/// returned by [FieldElement::elements_as_bytes()] do not match this encoding.
+///
+/// Only the base field encoding is shared across libraries: for example, Plonky2 defines the
+/// quadratic extension of this field via polynomial x<sup>2</sup> - 7 rather than
+/// x<sup>2</sup> - x + 2 used here.
+impl BaseElement {
+    /// Returns an element with the specified canonical value.
+    ///
+    /// # Errors
+    /// Returns an error if `value` is greater than or equal to the field modulus.
+    pub fn from_canonical_u64(value: u64) -> Result<Self, DeserializationError> {
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(Self::new(value))
+    }
+
+    /// Returns an element for the specified value which may be greater than or equal to the
+    /// field modulus; such values are reduced. This is how, for example, Plonky2 represents
+    /// elements in memory.
+    pub const fn from_noncanonical_u64(value: u64) -> Self {
+        Self::new(value)
+    }
+
+    /// Returns the canonical little-endian encoding of this element.
+    pub fn to_canonical_bytes(&self) -> [u8; ELEMENT_BYTES] {
+        self.as_int().to_le_bytes()
+    }
+
+    /// Returns an element decoded from the specified canonical little-endian encoding.
+    ///
+    /// # Errors
+    /// Returns an error if the encoded value is greater than or equal to the field modulus.
+    pub fn from_canonical_bytes(bytes: [u8; ELEMENT_BYTES]) -> Result<Self, DeserializationError> {
+        Self::from_canonical_u64(u64::from_le_bytes(bytes))
+    }
+
+    /// Returns canonical little-endian encodings of the specified elements concatenated together.
+    pub fn elements_to_canonical_bytes(elements: &[Self]) -> Vec<u8> {
+        elements.iter().flat_map(|e| e.to_canonical_bytes()).collect()
+    }
+
+    /// Returns elements decoded from the specified concatenation of canonical little-endian
+    /// encodings.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of bytes does not divide into a whole number of elements.
+    /// * Any of the encoded values is greater than or equal to the field modulus.
+    pub fn elements_from_canonical_bytes(bytes: &[u8]) -> Result<Vec<Self>, DeserializationError> {
+        if bytes.len() % ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+        bytes
+            .chunks_exact(ELEMENT_BYTES)
+            .map(|chunk| Self::from_canonical_bytes(chunk.try_into().expect("invalid chunk size")))
+            .collect()
+    }
+}
+
 impl FieldElement for BaseElement {
     type PositiveInteger = u64;
     type BaseField = Self;
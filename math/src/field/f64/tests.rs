@@ -221,6 +221,57 @@ fn bytes_as_elements() {
     assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
 }
 
+// CANONICAL ENCODING
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn canonical_encoding_test_vectors() {
+    // (canonical value, Montgomery form)
+    let vectors = [
+        (0u64, 0u64),
+        (1, 0xffffffff),
+        (7, 0x6fffffff9),
+        (M - 1, 0xfffffffe00000002),
+        (0x123456789abcdef0, 0x9abcdeef530eca98),
+        (0xfedcba9876543210, 0x7654320e8acf1358),
+    ];
+    for (value, mont) in vectors {
+        let element = BaseElement::from_canonical_u64(value).unwrap();
+        assert_eq!(mont, element.inner());
+        assert_eq!(element, BaseElement::from_mont(mont));
+        assert_eq!(value.to_le_bytes(), element.to_canonical_bytes());
+        assert_eq!(element, BaseElement::from_canonical_bytes(value.to_le_bytes()).unwrap());
+    }
+
+    // arithmetic agrees with other implementations
+    let a = BaseElement::from_canonical_u64(0x123456789abcdef0).unwrap();
+    let b = BaseElement::from_canonical_u64(0xfedcba9876543210).unwrap();
+    assert_eq!(0xfaeafd1f6c7bbad4, (a * b).as_int());
+    assert_eq!(0xcc82422076a04151, a.inv().as_int());
+
+    // non-canonical values are rejected when decoding but are reduced when explicitly allowed
+    assert!(BaseElement::from_canonical_u64(M).is_err());
+    assert!(BaseElement::from_canonical_bytes(u64::MAX.to_le_bytes()).is_err());
+    assert_eq!(BaseElement::ZERO, BaseElement::from_noncanonical_u64(M));
+    assert_eq!(0xfffffffe, BaseElement::from_noncanonical_u64(u64::MAX).as_int());
+}
+
+#[test]
+fn canonical_encoding_of_elements() {
+    let elements = [BaseElement::new(1), BaseElement::new(M - 1), BaseElement::new(42)];
+    let bytes = BaseElement::elements_to_canonical_bytes(&elements);
+
+    // canonical encoding matches serialization but not the in-memory Montgomery representation
+    assert_eq!(elements.to_bytes(), bytes);
+    assert_ne!(BaseElement::elements_as_bytes(&elements), bytes.as_slice());
+    assert_eq!(elements.to_vec(), BaseElement::elements_from_canonical_bytes(&bytes).unwrap());
+
+    assert!(BaseElement::elements_from_canonical_bytes(&bytes[..23]).is_err());
+    let mut bytes = bytes;
+    bytes[8..16].copy_from_slice(&M.to_le_bytes());
+    assert!(BaseElement::elements_from_canonical_bytes(&bytes).is_err());
+}
+
 // INITIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -65,6 +65,12 @@
 //! This can be used to interpolate and evaluate polynomials in *O(n log n)* time as long as
 //! the domain of the polynomial is a multiplicative subgroup with size which is a power of 2.
 //!
+//! # Matrices
+//! [Matrix](matrix) module contains column-major and row-major matrices of field elements which
+//! are used by the prover to represent execution traces, polynomials, and their evaluations. The
+//! matrices support conversions between the two layouts, low-degree extension of every column,
+//! and hashing of every row.
+//!
 //! # Concurrent execution
 //!
 //! When the crate is compiled with `concurrent` feature enabled, some operations will be
@@ -84,6 +90,12 @@
 //!   - [interpolate_poly_with_offset()][fft::interpolate_poly_with_offset()]
 //!   - [get_twiddles()](fft::get_twiddles())
 //!   - [get_inv_twiddles()](fft::get_twiddles())
+//! * `matrix` module:
+//!   - [ColMatrix::interpolate_columns()](matrix::ColMatrix::interpolate_columns())
+//!   - [ColMatrix::evaluate_columns_with_offset()](matrix::ColMatrix::evaluate_columns_with_offset())
+//!   - [RowMatrix::evaluate_polys_with_offset()](matrix::RowMatrix::evaluate_polys_with_offset())
+//!   - [ColMatrix::to_row_matrix()](matrix::ColMatrix::to_row_matrix()) and
+//!     [RowMatrix::to_col_matrix()](matrix::RowMatrix::to_col_matrix())
 //!
 //! Number of threads can be configured via `RAYON_NUM_THREADS` environment variable
 
@@ -94,6 +106,7 @@
 extern crate alloc;
 
 pub mod fft;
+pub mod matrix;
 pub mod polynom;
 
 mod field;
@@ -111,6 +124,15 @@ pub mod fields {
     pub use super::field::QuadExtension;
 }
 
+#[cfg(feature = "std")]
+pub mod symbolic;
+
+#[cfg(feature = "arkworks")]
+pub mod arkworks;
+
+#[cfg(feature = "testing")]
+mod testing;
+
 mod utils;
 pub use crate::utils::{
     add_in_place, batch_inversion, get_power_series, get_power_series_with_offset, log2, mul_acc,
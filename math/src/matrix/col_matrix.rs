@@ -3,11 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::StarkDomain;
+use super::RowMatrix;
+use crate::{fft, polynom, FieldElement};
 use core::{iter::FusedIterator, slice};
-use crypto::{ElementHasher, MerkleTree};
-use math::{fft, polynom, FieldElement};
-use utils::{batch_iter_mut, collections::Vec, iter, iter_mut, uninit_vector};
+use utils::{
+    batch_iter_mut, collections::Vec, iter, iter_mut, string::ToString, uninit_vector, ByteReader,
+    ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -17,11 +19,12 @@ use utils::iterators::*;
 
 /// A two-dimensional matrix of field elements arranged in column-major order.
 ///
-/// This struct is used as a backing type for many objects manipulated by the prover. The matrix
-/// itself does not assign any contextual meaning to the values stored in it. For example, columns
-/// may contain evaluations of polynomials, or polynomial coefficients, or really anything else.
-/// However, the matrix does expose a number of methods which make assumptions about the underlying
-/// data.
+/// This struct is used as a backing type for many objects manipulated by the prover (e.g., the
+/// execution trace and trace polynomials), and can be used by trace generators and external tools
+/// to build data in the same layout. The matrix itself does not assign any contextual meaning to
+/// the values stored in it. For example, columns may contain evaluations of polynomials, or
+/// polynomial coefficients, or really anything else. However, the matrix does expose a number of
+/// methods which make assumptions about the underlying data.
 ///
 /// A matrix imposes the following restrictions on its content:
 /// - A matrix must consist of at least 1 column and at least 2 rows.
@@ -35,7 +38,7 @@ pub struct ColMatrix<E: FieldElement> {
 impl<E: FieldElement> ColMatrix<E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new [Matrix] instantiated with the data from the specified columns.
+    /// Returns a new [ColMatrix] instantiated with the data from the specified columns.
     ///
     /// # Panics
     /// Panics if:
@@ -156,15 +159,31 @@ impl<E: FieldElement> ColMatrix<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns an iterator over the columns of this matrix.
-    pub fn columns(&self) -> ColumnIter<E> {
+    pub fn columns(&self) -> ColumnIter<'_, E> {
         ColumnIter::new(self)
     }
 
     /// Returns a mutable iterator over the columns of this matrix.
-    pub fn columns_mut(&mut self) -> ColumnIterMut<E> {
+    pub fn columns_mut(&mut self) -> ColumnIterMut<'_, E> {
         ColumnIterMut::new(self)
     }
 
+    /// Returns a parallel iterator over the columns of this matrix.
+    ///
+    /// This method is available only when the crate is compiled with `concurrent` feature.
+    #[cfg(feature = "concurrent")]
+    pub fn par_columns(&self) -> impl IndexedParallelIterator<Item = &[E]> {
+        self.columns.par_iter().map(|column| column.as_slice())
+    }
+
+    /// Returns a parallel iterator over the mutable columns of this matrix.
+    ///
+    /// This method is available only when the crate is compiled with `concurrent` feature.
+    #[cfg(feature = "concurrent")]
+    pub fn par_columns_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [E]> {
+        self.columns.par_iter_mut().map(|column| column.as_mut_slice())
+    }
+
     // POLYNOMIAL METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -206,25 +225,30 @@ impl<E: FieldElement> ColMatrix<E> {
         self
     }
 
-    /// Evaluates polynomials contained in the columns of this matrix over the specified domain
-    /// and returns the result.
+    /// Evaluates polynomials contained in the columns of this matrix over a coset of a larger
+    /// domain and returns the result (i.e., computes a low-degree extension of every column).
     ///
     /// The evaluation is done as follows:
     /// * Each column of the matrix is interpreted as coefficients of degree `num_rows - 1`
     ///   polynomial.
-    /// * These polynomials are evaluated over the LDE domain defined by the specified
-    ///   [StarkDomain] using FFT algorithm. The domain specification includes the size of the
-    ///   subgroup as well as the domain offset (to define a coset).
-    /// * The resulting evaluations are returned in a new Matrix.
-    pub fn evaluate_columns_over(&self, domain: &StarkDomain<E::BaseField>) -> Self {
+    /// * These polynomials are evaluated using FFT algorithm over the domain of size
+    ///   `num_rows * blowup_factor` shifted by `domain_offset`; `twiddles` must be computed for
+    ///   the polynomial size via [fft::get_twiddles()].
+    /// * The resulting evaluations are returned in a new matrix; the evaluations are in natural
+    ///   order.
+    ///
+    /// # Panics
+    /// Panics if `blowup_factor` is not a power of two, or if the number of twiddles is not half
+    /// the number of rows in this matrix.
+    pub fn evaluate_columns_with_offset(
+        &self,
+        twiddles: &[E::BaseField],
+        domain_offset: E::BaseField,
+        blowup_factor: usize,
+    ) -> Self {
         let columns = iter!(self.columns)
             .map(|poly| {
-                fft::evaluate_poly_with_offset(
-                    poly,
-                    domain.trace_twiddles(),
-                    domain.offset(),
-                    domain.trace_to_lde_blowup(),
-                )
+                fft::evaluate_poly_with_offset(poly, twiddles, domain_offset, blowup_factor)
             })
             .collect();
         Self { columns }
@@ -238,23 +262,22 @@ impl<E: FieldElement> ColMatrix<E> {
         iter!(self.columns).map(|p| polynom::eval(p, x)).collect()
     }
 
-    // COMMITMENTS
+    // ROW HASHING
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a commitment to this matrix.
+    /// Hashes each row of this matrix with the specified hash function and returns the resulting
+    /// digests, one per row.
     ///
-    /// The commitment is built as follows:
-    /// * Each row of the matrix is hashed into a single digest of the specified hash function.
-    /// * The resulting values are used to built a binary Merkle tree such that each row digest
-    ///   becomes a leaf in the tree. Thus, the number of leaves in the tree is equal to the
-    ///   number of rows in the matrix.
-    /// * The resulting Merkle tree is return as the commitment to the entire matrix.
-    pub fn commit_to_rows<H>(&self) -> MerkleTree<H>
+    /// A row is copied into a buffer before it is hashed to avoid heap allocations. The digests
+    /// can be used to build a commitment to the matrix (e.g., as leaves of a Merkle tree). When
+    /// the crate is compiled with `concurrent` feature, rows are hashed in multiple threads.
+    pub fn hash_rows<D, F>(&self, hash_fn: F) -> Vec<D>
     where
-        H: ElementHasher<BaseField = E::BaseField>,
+        D: Copy + Send,
+        F: Fn(&[E]) -> D + Sync,
     {
         // allocate vector to store row hashes
-        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
+        let mut row_hashes = unsafe { uninit_vector::<D>(self.num_rows()) };
 
         // iterate though matrix rows, hashing each row; the hashing is done by first copying a
         // row into row_buf to avoid heap allocations, and then by applying the hash function to
@@ -262,22 +285,30 @@ impl<E: FieldElement> ColMatrix<E> {
         batch_iter_mut!(
             &mut row_hashes,
             128, // min batch size
-            |batch: &mut [H::Digest], batch_offset: usize| {
+            |batch: &mut [D], batch_offset: usize| {
                 let mut row_buf = vec![E::ZERO; self.num_cols()];
                 for (i, row_hash) in batch.iter_mut().enumerate() {
                     self.read_row_into(i + batch_offset, &mut row_buf);
-                    *row_hash = H::hash_elements(&row_buf);
+                    *row_hash = hash_fn(&row_buf);
                 }
             }
         );
 
-        // build Merkle tree out of hashed rows
-        MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
+        row_hashes
     }
 
     // CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a row-major matrix with the same content as this matrix.
+    ///
+    /// Extension field elements are decomposed into base field elements, and the rows of the
+    /// returned matrix are not padded. When the crate is compiled with `concurrent` feature, the
+    /// data is transposed in multiple threads.
+    pub fn to_row_matrix(&self) -> RowMatrix<E> {
+        RowMatrix::from_col_matrix(self)
+    }
+
     /// Returns the columns of this matrix as a list of vectors.
     ///
     /// TODO: replace this with an iterator.
@@ -286,6 +317,50 @@ impl<E: FieldElement> ColMatrix<E> {
     }
 }
 
+impl<E: FieldElement> Serializable for ColMatrix<E> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.num_cols() as u32);
+        target.write_u8(self.num_rows().ilog2() as u8); // store as power of two
+        for column in self.columns.iter() {
+            E::write_batch_into(column, target);
+        }
+    }
+}
+
+impl<E: FieldElement> Deserializable for ColMatrix<E> {
+    /// Reads a column-major matrix from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid matrix could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_cols = source.read_u32()? as usize;
+        if num_cols == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a matrix must contain at least one column".to_string(),
+            ));
+        }
+
+        // read and validate number of rows (which was stored as a power of two)
+        let num_rows = source.read_u8()? as u32;
+        if num_rows == 0 || num_rows >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of rows in a matrix must be between 2^1 and 2^{}, but was 2^{}",
+                usize::BITS - 1,
+                num_rows
+            )));
+        }
+        let num_rows = 2_usize.pow(num_rows);
+
+        let mut columns = Vec::with_capacity(num_cols);
+        for _ in 0..num_cols {
+            columns.push(E::read_batch_from(source, num_rows)?);
+        }
+
+        Ok(Self { columns })
+    }
+}
+
 // COLUMN ITERATOR
 // ================================================================================================
 
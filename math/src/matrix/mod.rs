@@ -0,0 +1,30 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Two-dimensional data structures used to represent polynomials and polynomial evaluations.
+//!
+//! A [ColMatrix] stores data column by column, which is convenient for building execution traces
+//! and for operations applied to each column independently (e.g., interpolating columns into
+//! polynomials, or evaluating polynomials over a larger domain). A [RowMatrix] stores data row by
+//! row, which is convenient for operations applied to each row (e.g., hashing rows to build a
+//! commitment). Matrices can be converted into each other via [ColMatrix::to_row_matrix()] and
+//! [RowMatrix::to_col_matrix()], and [RowMatrix::evaluate_polys_with_offset()] evaluates columns of
+//! a [ColMatrix] directly into the row-major layout.
+//!
+//! When the crate is compiled with `concurrent` feature enabled, operations over entire matrices
+//! are executed in multiple threads, and matrices expose parallel iterators over their columns and
+//! rows.
+
+mod row_matrix;
+pub use row_matrix::{build_segments, get_evaluation_offsets, RowMatrix};
+
+mod col_matrix;
+pub use col_matrix::{ColMatrix, ColumnIter, ColumnIterMut, MultiColumnIter};
+
+mod segments;
+pub use segments::Segment;
+
+#[cfg(test)]
+mod tests;
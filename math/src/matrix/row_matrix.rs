@@ -4,9 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{ColMatrix, Segment};
-use crate::StarkDomain;
-use crypto::{ElementHasher, MerkleTree};
-use math::{fft, FieldElement, StarkField};
+use crate::{fft, FieldElement, StarkField};
 use utils::collections::Vec;
 use utils::{batch_iter_mut, flatten_vector_elements, uninit_vector};
 
@@ -71,27 +69,30 @@ impl<E: FieldElement> RowMatrix<E> {
     }
 
     /// Returns a new [RowMatrix] constructed by evaluating the provided polynomials over the
-    /// specified [StarkDomain].
+    /// domain defined by the specified blowup factor and shifted by `domain_offset`.
     ///
     /// The provided `polys` matrix is assumed to contain polynomials in coefficient form (one
     /// polynomial per column). Columns in the returned matrix will contain evaluations of the
-    /// corresponding polynomials over the LDE domain defined by the provided [StarkDomain].
+    /// corresponding polynomials over the domain of size `num_rows * blowup_factor` shifted by
+    /// `domain_offset`; `twiddles` must be computed for the polynomial size via
+    /// [fft::get_twiddles()].
     ///
     /// To improve performance, polynomials are evaluated in batches specified by the `N` type
     /// parameter. Minimum batch size is 1.
-    pub fn evaluate_polys_over<const N: usize>(
+    pub fn evaluate_polys_with_offset<const N: usize>(
         polys: &ColMatrix<E>,
-        domain: &StarkDomain<E::BaseField>,
+        twiddles: &[E::BaseField],
+        domain_offset: E::BaseField,
+        blowup_factor: usize,
     ) -> Self {
         assert!(N > 0, "batch size N must be greater than zero");
 
         // pre-compute offsets for each row
         let poly_size = polys.num_rows();
-        let offsets =
-            get_evaluation_offsets::<E>(poly_size, domain.trace_to_lde_blowup(), domain.offset());
+        let offsets = get_evaluation_offsets::<E>(poly_size, blowup_factor, domain_offset);
 
         // build matrix segments by evaluating all polynomials
-        let segments = build_segments::<E, N>(polys, domain.trace_twiddles(), &offsets);
+        let segments = build_segments::<E, N>(polys, twiddles, &offsets);
 
         // transpose data in individual segments into a single row-major matrix
         Self::from_segments(segments, polys.num_base_cols())
@@ -132,6 +133,36 @@ impl<E: FieldElement> RowMatrix<E> {
         }
     }
 
+    /// Returns a new [RowMatrix] with the same content as the specified column-major matrix.
+    ///
+    /// Extension field elements are decomposed into base field elements, and rows of the returned
+    /// matrix are not padded. When the crate is compiled with `concurrent` feature, the data is
+    /// transposed in multiple threads.
+    pub fn from_col_matrix(matrix: &ColMatrix<E>) -> Self {
+        let row_width = matrix.num_base_cols();
+        let mut data = unsafe { uninit_vector::<E::BaseField>(matrix.num_rows() * row_width) };
+
+        // define a closure which copies a single row of the matrix; the row is read column by
+        // column, and each element is written as a sequence of its base field elements
+        let copy_row = |(row_idx, row): (usize, &mut [E::BaseField])| {
+            for (column, chunk) in matrix.columns().zip(row.chunks_mut(E::EXTENSION_DEGREE)) {
+                chunk.copy_from_slice(E::slice_as_base_elements(&column[row_idx..row_idx + 1]));
+            }
+        };
+
+        #[cfg(not(feature = "concurrent"))]
+        data.chunks_mut(row_width).enumerate().for_each(copy_row);
+
+        #[cfg(feature = "concurrent")]
+        data.par_chunks_mut(row_width).enumerate().for_each(copy_row);
+
+        RowMatrix {
+            data,
+            row_width,
+            elements_per_row: row_width,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -168,37 +199,73 @@ impl<E: FieldElement> RowMatrix<E> {
         &self.data
     }
 
-    // COMMITMENTS
+    // ITERATION
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a commitment to this matrix.
+    /// Returns an iterator over the rows of this matrix.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[E]> {
+        (0..self.num_rows()).map(|row_idx| self.row(row_idx))
+    }
+
+    /// Returns a parallel iterator over the rows of this matrix.
     ///
-    /// The commitment is built as follows:
-    /// * Each row of the matrix is hashed into a single digest of the specified hash function.
-    /// * The resulting values are used to build a binary Merkle tree such that each row digest
-    ///   becomes a leaf in the tree. Thus, the number of leaves in the tree is equal to the
-    ///   number of rows in the matrix.
-    /// * The resulting Merkle tree is returned as the commitment to the entire matrix.
-    pub fn commit_to_rows<H>(&self) -> MerkleTree<H>
+    /// This method is available only when the crate is compiled with `concurrent` feature.
+    #[cfg(feature = "concurrent")]
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[E]> {
+        (0..self.num_rows()).into_par_iter().map(|row_idx| self.row(row_idx))
+    }
+
+    // ROW HASHING
+    // --------------------------------------------------------------------------------------------
+
+    /// Hashes each row of this matrix with the specified hash function and returns the resulting
+    /// digests, one per row.
+    ///
+    /// The digests can be used to build a commitment to the matrix (e.g., as leaves of a Merkle
+    /// tree). When the crate is compiled with `concurrent` feature, rows are hashed in multiple
+    /// threads.
+    pub fn hash_rows<D, F>(&self, hash_fn: F) -> Vec<D>
     where
-        H: ElementHasher<BaseField = E::BaseField>,
+        D: Copy + Send,
+        F: Fn(&[E]) -> D + Sync,
     {
         // allocate vector to store row hashes
-        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
+        let mut row_hashes = unsafe { uninit_vector::<D>(self.num_rows()) };
 
         // iterate though matrix rows, hashing each row
         batch_iter_mut!(
             &mut row_hashes,
             128, // min batch size
-            |batch: &mut [H::Digest], batch_offset: usize| {
+            |batch: &mut [D], batch_offset: usize| {
                 for (i, row_hash) in batch.iter_mut().enumerate() {
-                    *row_hash = H::hash_elements(self.row(batch_offset + i));
+                    *row_hash = hash_fn(self.row(batch_offset + i));
                 }
             }
         );
 
-        // build Merkle tree out of hashed rows
-        MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
+        row_hashes
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a column-major matrix with the same content as this matrix.
+    ///
+    /// Padding of the rows (if any) is not included in the returned matrix. When the crate is
+    /// compiled with `concurrent` feature, columns are assembled in multiple threads.
+    ///
+    /// # Panics
+    /// Panics if the number of rows in this matrix is not a power of two greater than one.
+    pub fn to_col_matrix(&self) -> ColMatrix<E> {
+        let read_column = |col_idx: usize| self.rows().map(|row| row[col_idx]).collect();
+
+        #[cfg(not(feature = "concurrent"))]
+        let columns = (0..self.num_cols()).map(read_column).collect();
+
+        #[cfg(feature = "concurrent")]
+        let columns = (0..self.num_cols()).into_par_iter().map(read_column).collect();
+
+        ColMatrix::new(columns)
     }
 }
 
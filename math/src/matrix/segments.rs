@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::ColMatrix;
+use crate::{fft::fft_inputs::FftInputs, FieldElement, StarkField};
 use core::ops::Deref;
-use math::{fft::fft_inputs::FftInputs, FieldElement, StarkField};
 use utils::{collections::Vec, group_vector_elements, uninit_vector};
 
 #[cfg(feature = "concurrent")]
@@ -23,7 +23,7 @@ const MIN_CONCURRENT_SIZE: usize = 1024;
 /// A set of columns of a matrix stored in row-major form.
 ///
 /// The rows are stored in a single vector where each element is an array of size `N`. A segment
-/// can store [StarkField] elements only, but can be instantiated from a [Matrix] of any extension
+/// can store [StarkField] elements only, but can be instantiated from a [ColMatrix] of any extension
 /// of the specified [StarkField]. In such a case, extension field elements are decomposed into
 /// base field elements and then added to the segment.
 #[derive(Clone, Debug)]
@@ -40,7 +40,7 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
     ///
     /// The offset is assumed to be an offset into the view of the matrix where extension field
     /// elements are decomposed into base field elements. This offset must be compatible with the
-    /// values supplied into [Matrix::get_base_element()] method.
+    /// values supplied into [ColMatrix::get_base_element()] method.
     ///
     /// Evaluation is performed over the domain specified by the provided twiddles and offsets.
     ///
@@ -79,7 +79,7 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
     ///
     /// The offset is assumed to be an offset into the view of the matrix where extension field
     /// elements are decomposed into base field elements. This offset must be compatible with the
-    /// values supplied into [Matrix::get_base_element()] method.
+    /// values supplied into [ColMatrix::get_base_element()] method.
     ///
     /// Evaluation is performed over the domain specified by the provided twiddles and offsets.
     ///
@@ -178,10 +178,9 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         poly_offset: usize,
         offsets: &[B],
     ) {
-        for row_idx in 0..dest.len() {
-            for i in 0..N {
-                let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+        for (row_idx, (row, &offset)) in dest.iter_mut().zip(offsets).enumerate() {
+            for (i, value) in row.iter_mut().take(N).enumerate() {
+                *value = polys.get_base_element(poly_offset + i, row_idx) * offset;
             }
         }
     }
@@ -198,10 +197,9 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         offsets: &[B],
     ) {
         debug_assert!(num_polys < N);
-        for row_idx in 0..dest.len() {
-            for i in 0..num_polys {
-                let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+        for (row_idx, (row, &offset)) in dest.iter_mut().zip(offsets).enumerate() {
+            for (i, value) in row.iter_mut().take(num_polys).enumerate() {
+                *value = polys.get_base_element(poly_offset + i, row_idx) * offset;
             }
         }
     }
@@ -224,7 +222,7 @@ impl<B: StarkField, const N: usize> Deref for Segment<B, N> {
 #[cfg(feature = "concurrent")]
 mod concurrent {
     use super::{FftInputs, StarkField};
-    use math::fft::permute_index;
+    use crate::fft::permute_index;
     use utils::{iterators::*, rayon};
 
     /// In-place recursive FFT with permuted output.
@@ -233,7 +231,7 @@ mod concurrent {
         // generator of the domain should be in the middle of twiddles
         let n = data.len();
         let g = twiddles[twiddles.len() / 2];
-        debug_assert_eq!(g.exp((n as u32).into()), B::ONE);
+        debug_assert_eq!(g.exp((n as u64).into()), B::ONE);
 
         let inner_len = 1_usize << (n.ilog2() / 2);
         let outer_len = n / inner_len;
@@ -255,7 +253,7 @@ mod concurrent {
         data.par_chunks_mut(outer_len).enumerate().for_each(|(i, row)| {
             if i > 0 {
                 let i = permute_index(inner_len, i);
-                let inner_twiddle = g.exp_vartime((i as u32).into());
+                let inner_twiddle = g.exp_vartime((i as u64).into());
                 let mut outer_twiddle = inner_twiddle;
                 for element in row.iter_mut().skip(1) {
                     for col_idx in 0..N {
@@ -0,0 +1,123 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColMatrix, RowMatrix};
+use crate::{fft, fields::f64::BaseElement, get_power_series, polynom, FieldElement, StarkField};
+use rand_utils::rand_vector;
+use utils::collections::Vec;
+
+#[test]
+fn test_eval_poly_with_offset_matrix() {
+    let n = 256;
+    let num_polys = 64;
+    let blowup_factor = 8;
+
+    // generate random columns. Each column is a polynomial of degree n - 1.
+    let mut columns: Vec<Vec<BaseElement>> = (0..num_polys).map(|_| rand_vector(n)).collect();
+
+    // evaluate columns using the row matrix implementation.
+    let row_matrix =
+        RowMatrix::evaluate_polys::<8>(&ColMatrix::new(columns.clone()), blowup_factor);
+
+    // evaluate columns using the using the polynomial evaluation implementation.
+    let offset = BaseElement::GENERATOR;
+    let domain = build_domain(n * blowup_factor);
+    let shifted_domain = domain.iter().map(|&x| x * offset).collect::<Vec<_>>();
+    for p in columns.iter_mut() {
+        *p = polynom::eval_many(p, &shifted_domain);
+    }
+
+    // compare the results of the two implementations row by row.
+    for row in 0..n * blowup_factor {
+        let row_matrix_row = row_matrix.row(row);
+        let eval_col_row = get_row(&columns, row);
+        assert_eq!(row_matrix_row, eval_col_row);
+    }
+}
+
+#[test]
+fn test_eval_columns_with_offset_matrix() {
+    let n = 128;
+    let num_polys = 7;
+    let blowup_factor = 4;
+    let offset = BaseElement::new(7);
+
+    let columns: Vec<Vec<BaseElement>> = (0..num_polys).map(|_| rand_vector(n)).collect();
+    let polys = ColMatrix::new(columns);
+
+    // evaluate columns using column-major and row-major implementations
+    let twiddles = fft::get_twiddles::<BaseElement>(n);
+    let col_matrix = polys.evaluate_columns_with_offset(&twiddles, offset, blowup_factor);
+    let row_matrix =
+        RowMatrix::evaluate_polys_with_offset::<4>(&polys, &twiddles, offset, blowup_factor);
+
+    assert_eq!(n * blowup_factor, col_matrix.num_rows());
+    assert_eq!(num_polys, col_matrix.num_cols());
+    for (expected, actual) in col_matrix.to_row_matrix().rows().zip(row_matrix.rows()) {
+        assert_eq!(expected, actual);
+    }
+
+    // compare the results against direct polynomial evaluation
+    let domain = build_domain(n * blowup_factor);
+    for (i, column) in col_matrix.columns().enumerate() {
+        for (&x, &y) in domain.iter().zip(column) {
+            assert_eq!(polynom::eval(polys.get_column(i), x * offset), y);
+        }
+    }
+}
+
+#[test]
+fn test_matrix_transpose() {
+    let n = 64;
+    let num_cols = 5;
+
+    let columns: Vec<Vec<BaseElement>> = (0..num_cols).map(|_| rand_vector(n)).collect();
+    let col_matrix = ColMatrix::new(columns.clone());
+
+    let row_matrix = col_matrix.to_row_matrix();
+    assert_eq!(n, row_matrix.num_rows());
+    assert_eq!(num_cols, row_matrix.num_cols());
+    for (i, row) in row_matrix.rows().enumerate() {
+        assert_eq!(get_row(&columns, i), row);
+    }
+
+    let transposed = RowMatrix::from_col_matrix(&col_matrix);
+    assert_eq!(row_matrix.data(), transposed.data());
+
+    assert_eq!(columns, row_matrix.to_col_matrix().into_columns());
+}
+
+#[test]
+fn test_hash_rows() {
+    let n = 256;
+    let num_cols = 3;
+
+    let columns: Vec<Vec<BaseElement>> = (0..num_cols).map(|_| rand_vector(n)).collect();
+    let col_matrix = ColMatrix::new(columns.clone());
+    let row_matrix = col_matrix.to_row_matrix();
+
+    let hash_fn = |row: &[BaseElement]| {
+        row.iter()
+            .fold(BaseElement::ZERO, |acc, &value| acc * BaseElement::new(31) + value)
+    };
+    let expected = (0..n).map(|i| hash_fn(&get_row(&columns, i))).collect::<Vec<_>>();
+
+    assert_eq!(expected, col_matrix.hash_rows(hash_fn));
+    assert_eq!(expected, row_matrix.hash_rows(hash_fn));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a domain of size `size` using the primitive element of the field.
+fn build_domain(size: usize) -> Vec<BaseElement> {
+    let g = BaseElement::get_root_of_unity(size.ilog2());
+    get_power_series(g, size)
+}
+
+/// Returns a row of the column major matrix.
+fn get_row(columns: &[Vec<BaseElement>], row_id: usize) -> Vec<BaseElement> {
+    columns.iter().map(|col| col[row_id]).collect()
+}
@@ -0,0 +1,530 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Symbolic evaluation of computations which are generic over field elements.
+//!
+//! [Symbolic] implements [FieldElement] trait, but instead of computing values, every arithmetic
+//! operation on symbolic elements records a node in an expression graph. Thus, evaluating a
+//! function which is generic over field elements (e.g., AIR constraints) with symbolic inputs
+//! yields the structure of the computation performed by the function as a list of [Node]s.
+//!
+//! Nodes are recorded as follows:
+//! * Every expression is recorded once, and so, two symbolic elements are equal if and only if
+//!   their expressions are the same. Arguments of additions and multiplications are ordered, and
+//!   thus, `a + b` and `b + a` are the same expression.
+//! * Operations on constants are evaluated, and additions of zero, multiplications by zero or one,
+//!   and exponentiations by zero or one are simplified away.
+//! * Divisions are recorded as multiplications by inverses.
+//!
+//! The graph is kept in thread-local storage; symbolic elements can be created and operated on
+//! only within a closure executed via [record()] function, and only on the thread which executes
+//! the closure. Operations which cannot be performed on symbolic elements (e.g., conversions into
+//! base field elements) panic.
+//!
+//! This module is available only when the crate is compiled with `std` feature.
+
+use crate::{ExtensionOf, FieldElement, StarkField};
+use core::{
+    cell::RefCell,
+    convert::TryFrom,
+    fmt,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+use std::collections::HashMap;
+use utils::{
+    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Randomizable, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// EXPRESSION NODE
+// ================================================================================================
+
+/// A node in an expression graph recorded via [record()].
+///
+/// Arguments of operations are indexes of nodes in the graph; arguments always precede the nodes
+/// which refer to them. The first two nodes of every graph are constants ZERO and ONE.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node<B: StarkField> {
+    /// A constant value.
+    Constant(B),
+    /// An input of the computation with the specified index (see [Symbolic::variable()]).
+    Variable(usize),
+    /// Sum of two nodes.
+    Add(usize, usize),
+    /// Difference of two nodes.
+    Sub(usize, usize),
+    /// Product of two nodes.
+    Mul(usize, usize),
+    /// Additive inverse of a node.
+    Neg(usize),
+    /// Multiplicative inverse of a node.
+    Inv(usize),
+    /// A node raised to the specified power.
+    Exp(usize, u128),
+}
+
+/// Returns the result of executing `f` together with the graph of expressions recorded for the
+/// operations on symbolic elements performed by `f`.
+///
+/// # Panics
+/// Panics if invoked from within a closure executed via this function.
+pub fn record<B: StarkField, R>(f: impl FnOnce() -> R) -> (R, Vec<Node<B>>) {
+    GRAPH.with(|graph| {
+        let mut graph = graph.borrow_mut();
+        assert!(graph.is_none(), "symbolic expressions are already being recorded");
+        *graph = Some(Graph::new::<B>());
+    });
+
+    // make sure the graph is discarded even if `f` panics
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            GRAPH.with(|graph| graph.borrow_mut().take());
+        }
+    }
+    let reset = Reset;
+
+    let result = f();
+    let graph = GRAPH.with(|graph| graph.borrow_mut().take()).expect("graph is not empty");
+    drop(reset);
+
+    let nodes = graph.nodes.into_iter().map(RawNode::into_node).collect();
+    (result, nodes)
+}
+
+// SYMBOLIC ELEMENT
+// ================================================================================================
+
+/// A field element which records operations performed on it as nodes of an expression graph.
+///
+/// See [module documentation](self) for details.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Symbolic<B: StarkField> {
+    index: u32,
+    _base: PhantomData<B>,
+}
+
+impl<B: StarkField> Symbolic<B> {
+    /// Returns a symbolic element for the input of the computation with the specified index.
+    ///
+    /// # Panics
+    /// Panics if invoked outside of a closure executed via [record()].
+    pub fn variable(index: usize) -> Self {
+        Self::push(RawNode::Variable(index))
+    }
+
+    /// Returns a symbolic element for the specified constant.
+    ///
+    /// # Panics
+    /// Panics if invoked outside of a closure executed via [record()].
+    pub fn constant(value: B) -> Self {
+        if value == B::ZERO {
+            Self::ZERO
+        } else if value == B::ONE {
+            Self::ONE
+        } else {
+            Self::push(RawNode::Constant(value.to_bytes()))
+        }
+    }
+
+    /// Returns the index of the node for this element in the recorded graph.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    const fn from_index(index: u32) -> Self {
+        Self {
+            index,
+            _base: PhantomData,
+        }
+    }
+
+    fn push(node: RawNode) -> Self {
+        Self::from_index(with_graph(|graph| graph.push(node)))
+    }
+
+    /// Returns the value of this element if it is a constant.
+    fn as_constant(&self) -> Option<B> {
+        match self.index {
+            0 => Some(B::ZERO),
+            1 => Some(B::ONE),
+            index => with_graph(|graph| match &graph.nodes[index as usize] {
+                RawNode::Constant(bytes) => {
+                    Some(B::read_from_bytes(bytes).expect("invalid constant"))
+                }
+                _ => None,
+            }),
+        }
+    }
+
+    /// Returns the constants of both elements if both of them are constants.
+    fn as_constants(self, other: Self) -> Option<(B, B)> {
+        Some((self.as_constant()?, other.as_constant()?))
+    }
+}
+
+impl<B: StarkField> FieldElement for Symbolic<B> {
+    type PositiveInteger = u128;
+    type BaseField = B;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ELEMENT_BYTES: usize = 4;
+    const IS_CANONICAL: bool = false;
+    const ZERO: Self = Self::from_index(0);
+    const ONE: Self = Self::from_index(1);
+
+    fn exp(self, power: Self::PositiveInteger) -> Self {
+        self.exp_vartime(power)
+    }
+
+    fn exp_vartime(self, power: Self::PositiveInteger) -> Self {
+        match power {
+            0 => Self::ONE,
+            1 => self,
+            _ => match self.as_constant() {
+                Some(value) => Self::constant(exp_constant(value, power)),
+                None => Self::push(RawNode::Exp(self.index, power)),
+            },
+        }
+    }
+
+    fn inv(self) -> Self {
+        match self.as_constant() {
+            Some(value) => Self::constant(value.inv()),
+            None => Self::push(RawNode::Inv(self.index)),
+        }
+    }
+
+    fn conjugate(&self) -> Self {
+        *self
+    }
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        assert_eq!(i, 0, "symbolic elements have only one base element");
+        self.as_constant()
+            .expect("symbolic variables cannot be converted into base elements")
+    }
+
+    fn slice_as_base_elements(_elements: &[Self]) -> &[Self::BaseField] {
+        panic!("symbolic elements cannot be converted into base elements")
+    }
+
+    fn slice_from_base_elements(_elements: &[Self::BaseField]) -> &[Self] {
+        panic!("base elements cannot be converted into symbolic elements")
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        // symbolic elements are represented by the indexes of their nodes
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(_bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        Err(DeserializationError::InvalidValue(
+            "bytes cannot be converted into symbolic elements".to_string(),
+        ))
+    }
+}
+
+impl<B: StarkField> ExtensionOf<B> for Symbolic<B> {
+    fn mul_base(self, other: B) -> Self {
+        self * Self::constant(other)
+    }
+}
+
+impl<B: StarkField> Default for Symbolic<B> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<B: StarkField> Randomizable for Symbolic<B> {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(_bytes: &[u8]) -> Option<Self> {
+        None
+    }
+}
+
+impl<B: StarkField> fmt::Display for Symbolic<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.index)
+    }
+}
+
+// OVERLOADED OPERATORS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: StarkField> Add for Symbolic<B> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        if let Some((lhs, rhs)) = self.as_constants(rhs) {
+            Self::constant(lhs + rhs)
+        } else if self == Self::ZERO {
+            rhs
+        } else if rhs == Self::ZERO {
+            self
+        } else {
+            Self::push(RawNode::Add(self.index.min(rhs.index), self.index.max(rhs.index)))
+        }
+    }
+}
+
+impl<B: StarkField> AddAssign for Symbolic<B> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl<B: StarkField> Sub for Symbolic<B> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        if let Some((lhs, rhs)) = self.as_constants(rhs) {
+            Self::constant(lhs - rhs)
+        } else if rhs == Self::ZERO {
+            self
+        } else if self == rhs {
+            Self::ZERO
+        } else if self == Self::ZERO {
+            -rhs
+        } else {
+            Self::push(RawNode::Sub(self.index, rhs.index))
+        }
+    }
+}
+
+impl<B: StarkField> SubAssign for Symbolic<B> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs
+    }
+}
+
+impl<B: StarkField> Mul for Symbolic<B> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if let Some((lhs, rhs)) = self.as_constants(rhs) {
+            Self::constant(lhs * rhs)
+        } else if self == Self::ZERO || rhs == Self::ZERO {
+            Self::ZERO
+        } else if self == Self::ONE {
+            rhs
+        } else if rhs == Self::ONE {
+            self
+        } else {
+            Self::push(RawNode::Mul(self.index.min(rhs.index), self.index.max(rhs.index)))
+        }
+    }
+}
+
+impl<B: StarkField> MulAssign for Symbolic<B> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl<B: StarkField> Div for Symbolic<B> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<B: StarkField> DivAssign for Symbolic<B> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl<B: StarkField> Neg for Symbolic<B> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self.as_constant() {
+            Some(value) => Self::constant(-value),
+            None => Self::push(RawNode::Neg(self.index)),
+        }
+    }
+}
+
+// TYPE CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: StarkField> From<B> for Symbolic<B> {
+    fn from(value: B) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl<B: StarkField> From<u128> for Symbolic<B> {
+    fn from(value: u128) -> Self {
+        Self::constant(B::from(value))
+    }
+}
+
+impl<B: StarkField> From<u64> for Symbolic<B> {
+    fn from(value: u64) -> Self {
+        Self::constant(B::from(value))
+    }
+}
+
+impl<B: StarkField> From<u32> for Symbolic<B> {
+    fn from(value: u32) -> Self {
+        Self::constant(B::from(value))
+    }
+}
+
+impl<B: StarkField> From<u16> for Symbolic<B> {
+    fn from(value: u16) -> Self {
+        Self::constant(B::from(value))
+    }
+}
+
+impl<B: StarkField> From<u8> for Symbolic<B> {
+    fn from(value: u8) -> Self {
+        Self::constant(B::from(value))
+    }
+}
+
+impl<B: StarkField> TryFrom<&[u8]> for Symbolic<B> {
+    type Error = DeserializationError;
+
+    fn try_from(_bytes: &[u8]) -> Result<Self, Self::Error> {
+        Err(DeserializationError::InvalidValue(
+            "bytes cannot be converted into symbolic elements".to_string(),
+        ))
+    }
+}
+
+impl<B: StarkField> AsBytes for Symbolic<B> {
+    fn as_bytes(&self) -> &[u8] {
+        let self_ptr: *const Self = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, Self::ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<B: StarkField> Serializable for Symbolic<B> {
+    /// Writes the index of the node of this element into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.index);
+    }
+}
+
+impl<B: StarkField> Deserializable for Symbolic<B> {
+    /// Returns an error since symbolic elements cannot be deserialized.
+    fn read_from<R: ByteReader>(_source: &mut R) -> Result<Self, DeserializationError> {
+        Err(DeserializationError::InvalidValue(
+            "symbolic elements cannot be deserialized".to_string(),
+        ))
+    }
+}
+
+// EXPRESSION GRAPH
+// ================================================================================================
+
+thread_local! {
+    static GRAPH: RefCell<Option<Graph>> = const { RefCell::new(None) };
+}
+
+/// A node of a graph in which constants are stored in serialized form; this way, the graph does
+/// not depend on the field of the recorded expressions.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum RawNode {
+    Constant(Vec<u8>),
+    Variable(usize),
+    Add(u32, u32),
+    Sub(u32, u32),
+    Mul(u32, u32),
+    Neg(u32),
+    Inv(u32),
+    Exp(u32, u128),
+}
+
+impl RawNode {
+    fn into_node<B: StarkField>(self) -> Node<B> {
+        match self {
+            Self::Constant(bytes) => {
+                Node::Constant(B::read_from_bytes(&bytes).expect("invalid constant"))
+            }
+            Self::Variable(index) => Node::Variable(index),
+            Self::Add(lhs, rhs) => Node::Add(lhs as usize, rhs as usize),
+            Self::Sub(lhs, rhs) => Node::Sub(lhs as usize, rhs as usize),
+            Self::Mul(lhs, rhs) => Node::Mul(lhs as usize, rhs as usize),
+            Self::Neg(arg) => Node::Neg(arg as usize),
+            Self::Inv(arg) => Node::Inv(arg as usize),
+            Self::Exp(arg, power) => Node::Exp(arg as usize, power),
+        }
+    }
+}
+
+struct Graph {
+    nodes: Vec<RawNode>,
+    indexes: HashMap<RawNode, u32>,
+}
+
+impl Graph {
+    /// Returns a new graph containing constants ZERO and ONE.
+    fn new<B: StarkField>() -> Self {
+        let mut graph = Self {
+            nodes: Vec::new(),
+            indexes: HashMap::new(),
+        };
+        graph.push(RawNode::Constant(B::ZERO.to_bytes()));
+        graph.push(RawNode::Constant(B::ONE.to_bytes()));
+        graph
+    }
+
+    /// Returns the index of the specified node, adding the node to the graph if needed.
+    fn push(&mut self, node: RawNode) -> u32 {
+        if let Some(&index) = self.indexes.get(&node) {
+            return index;
+        }
+        let index = u32::try_from(self.nodes.len()).expect("too many symbolic expressions");
+        self.nodes.push(node.clone());
+        self.indexes.insert(node, index);
+        index
+    }
+}
+
+/// Executes `f` against the graph being recorded on the current thread.
+fn with_graph<R>(f: impl FnOnce(&mut Graph) -> R) -> R {
+    GRAPH.with(|graph| {
+        let mut graph = graph.borrow_mut();
+        let graph = graph
+            .as_mut()
+            .expect("symbolic elements can be used only within symbolic::record()");
+        f(graph)
+    })
+}
+
+/// Raises the specified constant to the specified power.
+fn exp_constant<B: StarkField>(value: B, power: u128) -> B {
+    let mut result = B::ONE;
+    for i in (0..u128::BITS - power.leading_zeros()).rev() {
+        result = result.square();
+        if (power >> i) & 1 == 1 {
+            result *= value;
+        }
+    }
+    result
+}
@@ -0,0 +1,79 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{record, Node, Symbolic};
+use crate::{fields::f64::BaseElement, FieldElement};
+
+type S = Symbolic<BaseElement>;
+
+#[test]
+fn record_expression() {
+    let (result, nodes) = record::<BaseElement, _>(|| {
+        let x = S::variable(0);
+        let y = S::variable(1);
+        (x * y + S::from(3u32) - x.exp(5)).index()
+    });
+
+    assert_eq!(Node::Constant(BaseElement::ZERO), nodes[0]);
+    assert_eq!(Node::Constant(BaseElement::ONE), nodes[1]);
+    assert_eq!(
+        vec![
+            Node::Variable(0),
+            Node::Variable(1),
+            Node::Mul(2, 3),
+            Node::Constant(BaseElement::new(3)),
+            Node::Add(4, 5),
+            Node::Exp(2, 5),
+            Node::Sub(6, 7),
+        ],
+        nodes[2..]
+    );
+    assert_eq!(8, result);
+}
+
+#[test]
+fn record_simplifications() {
+    let ((), nodes) = record::<BaseElement, _>(|| {
+        let x = S::variable(0);
+        let y = S::variable(1);
+
+        // expressions are recorded once, and arguments of commutative operations are ordered
+        assert_eq!(x, S::variable(0));
+        assert_eq!(x + y, y + x);
+        assert_eq!(x * y, y * x);
+        assert_ne!(x - y, y - x);
+
+        // identities are simplified away
+        assert_eq!(x, x + S::ZERO);
+        assert_eq!(x, S::ONE * x);
+        assert_eq!(S::ZERO, x * S::ZERO);
+        assert_eq!(S::ZERO, x - x);
+        assert_eq!(S::ONE, x.exp(0));
+        assert_eq!(x, x.exp(1));
+
+        // operations on constants are evaluated
+        let two = S::from(2u8);
+        assert_eq!(S::from(6u8), two + S::from(4u8));
+        assert_eq!(S::from(BaseElement::new(2).inv()), two.inv());
+        assert_eq!(S::from(32u8), two.exp(5));
+        assert_eq!(S::from(-BaseElement::new(2)), -two);
+    });
+
+    // x, y, x + y, x * y, x - y, y - x, and constants 2, 4, 6, 1/2, 32, and -2
+    assert_eq!(2 + 12, nodes.len());
+}
+
+#[test]
+fn record_division() {
+    let (result, nodes) = record::<BaseElement, _>(|| (S::variable(0) / S::variable(1)).index());
+    assert_eq!(Node::Inv(3), nodes[4]);
+    assert_eq!(Node::Mul(2, 4), nodes[result]);
+}
+
+#[test]
+#[should_panic(expected = "symbolic elements can be used only within symbolic::record()")]
+fn symbolic_outside_of_record() {
+    S::variable(0);
+}
@@ -0,0 +1,95 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! [Arbitrary] implementations for field elements.
+//!
+//! Random elements are drawn uniformly from the entire range of the underlying integer type, and
+//! are reduced into the field on construction. Additionally, zero, one, and negative one are
+//! generated more often than they would be otherwise since these are frequent edge cases.
+
+use crate::{
+    fields::{f128, f62, f64, CubeExtension, QuadExtension},
+    ExtensibleField, FieldElement,
+};
+use proptest::prelude::*;
+
+// BASE FIELDS
+// ================================================================================================
+
+impl Arbitrary for f62::BaseElement {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        with_edge_cases(any::<u64>().prop_map(Self::new))
+    }
+}
+
+impl Arbitrary for f64::BaseElement {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        with_edge_cases(any::<u64>().prop_map(Self::new))
+    }
+}
+
+impl Arbitrary for f128::BaseElement {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        with_edge_cases(any::<u128>().prop_map(Self::new))
+    }
+}
+
+// EXTENSION FIELDS
+// ================================================================================================
+
+impl<B> Arbitrary for QuadExtension<B>
+where
+    B: ExtensibleField<2> + Arbitrary,
+    B::Strategy: 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (any::<B>(), any::<B>()).prop_map(|(a, b)| Self::new(a, b)).boxed()
+    }
+}
+
+impl<B> Arbitrary for CubeExtension<B>
+where
+    B: ExtensibleField<3> + Arbitrary,
+    B::Strategy: 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (any::<B>(), any::<B>(), any::<B>())
+            .prop_map(|(a, b, c)| Self::new(a, b, c))
+            .boxed()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Mixes zero, one, and negative one into the elements generated by the specified strategy.
+fn with_edge_cases<E, S>(elements: S) -> BoxedStrategy<E>
+where
+    E: FieldElement + 'static,
+    S: Strategy<Value = E> + 'static,
+{
+    prop_oneof![
+        1 => Just(E::ZERO),
+        1 => Just(E::ONE),
+        1 => Just(E::ZERO - E::ONE),
+        7 => elements,
+    ]
+    .boxed()
+}
@@ -37,7 +37,11 @@ fn evaluate_columns(c: &mut Criterion) {
                     let twiddles = fft::get_twiddles::<BaseElement>(SIZE);
                     let stark_domain =
                         StarkDomain::from_twiddles(twiddles, blowup_factor, BaseElement::GENERATOR);
-                    column_matrix.evaluate_columns_over(&stark_domain)
+                    column_matrix.evaluate_columns_with_offset(
+                        stark_domain.trace_twiddles(),
+                        stark_domain.offset(),
+                        stark_domain.trace_to_lde_blowup(),
+                    )
                 });
             });
         }
@@ -0,0 +1,206 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    ColMatrix, CompositionPoly, CompositionPolyTrace, ConstraintCommitment, RowMatrix, StarkDomain,
+};
+use crate::DEFAULT_SEGMENT_WIDTH;
+use crypto::{ElementHasher, MerkleTree};
+use math::FieldElement;
+
+#[cfg(feature = "std")]
+use crate::matrix::DiskMatrix;
+#[cfg(feature = "std")]
+use crate::time::Instant;
+#[cfg(feature = "std")]
+use log::debug;
+#[cfg(feature = "std")]
+use std::{io, path::Path};
+
+// PROVER BACKEND
+// ================================================================================================
+
+/// Defines a set of computationally-intensive operations performed during proof generation.
+///
+/// The operations covered by this trait are the ones which dominate proof generation time:
+/// interpolation and low-degree extension of polynomials, and hashing of matrix rows into Merkle
+/// tree commitments. Thus, these are the operations which benefit the most from being delegated
+/// to specialized hardware (e.g., GPUs or FPGAs). The default CPU implementation of this trait is
+/// provided by [CpuBackend].
+///
+/// A backend is plugged into the prover as follows:
+/// * Extension of and commitment to the execution trace are performed by the
+///   [DefaultTraceLde](crate::DefaultTraceLde) instantiated with the backend via
+///   [DefaultTraceLde::with_backend()](crate::DefaultTraceLde::with_backend) function.
+/// * Extension of and commitment to the constraint composition polynomial are performed via
+///   [build_constraint_commitment()](crate::build_constraint_commitment) function, which can be
+///   invoked with the backend from [Prover::build_constraint_commitment()](crate::Prover) method.
+///
+/// Evaluation of constraints over the extended execution trace can be accelerated by providing a
+/// custom [ConstraintEvaluator](crate::ConstraintEvaluator).
+pub trait ProverBackend: Send + Sync {
+    /// Interpolates each column of the provided matrix into a polynomial (in coefficient form)
+    /// and returns the result as a new matrix.
+    fn interpolate_columns<E: FieldElement>(&self, columns: &ColMatrix<E>) -> ColMatrix<E>;
+
+    /// Evaluates polynomials contained in the columns of the provided matrix over the LDE domain
+    /// defined by the specified [StarkDomain], and returns the result as a row-major matrix.
+    fn evaluate_polys_over<E: FieldElement>(
+        &self,
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> RowMatrix<E>;
+
+    /// Hashes each row of the provided matrix, and builds a Merkle tree from the resulting
+    /// hashes.
+    fn commit_to_rows<E, H>(&self, matrix: &RowMatrix<E>) -> MerkleTree<H>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>;
+}
+
+// CPU BACKEND
+// ================================================================================================
+
+/// Default implementation of the [ProverBackend] trait which executes all operations on CPU.
+///
+/// When the crate is compiled with `concurrent` feature enabled, operations are executed in
+/// multiple threads.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CpuBackend;
+
+impl ProverBackend for CpuBackend {
+    fn interpolate_columns<E: FieldElement>(&self, columns: &ColMatrix<E>) -> ColMatrix<E> {
+        columns.interpolate_columns()
+    }
+
+    fn evaluate_polys_over<E: FieldElement>(
+        &self,
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> RowMatrix<E> {
+        RowMatrix::evaluate_polys_with_offset::<DEFAULT_SEGMENT_WIDTH>(
+            polys,
+            domain.trace_twiddles(),
+            domain.offset(),
+            domain.trace_to_lde_blowup(),
+        )
+    }
+
+    fn commit_to_rows<E, H>(&self, matrix: &RowMatrix<E>) -> MerkleTree<H>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        MerkleTree::new(matrix.hash_rows(H::hash_elements))
+            .expect("failed to construct trace Merkle tree")
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Extends constraint composition polynomial over the LDE domain and builds a commitment to
+/// its evaluations using the specified backend.
+///
+/// The extension is done by first interpolating the evaluations of the polynomial so that we
+/// get the composition polynomial in coefficient form; then breaking the polynomial into
+/// columns each of size equal to trace length, and finally evaluating each composition
+/// polynomial column over the LDE domain.
+///
+/// The commitment is computed by hashing each row in the evaluation matrix, and then building
+/// a Merkle tree from the resulting hashes.
+pub fn build_constraint_commitment<E, H, B>(
+    backend: &B,
+    composition_poly_trace: CompositionPolyTrace<E>,
+    num_trace_poly_columns: usize,
+    domain: &StarkDomain<E::BaseField>,
+) -> (ConstraintCommitment<E, H>, CompositionPoly<E>)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    B: ProverBackend,
+{
+    // first, build constraint composition polynomial from its trace as follows:
+    // - interpolate the trace into a polynomial in coefficient form
+    // - "break" the polynomial into a set of column polynomials each of degree equal to
+    //   trace_length - 1
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+    let composition_poly =
+        CompositionPoly::new(composition_poly_trace, domain, num_trace_poly_columns);
+    #[cfg(feature = "std")]
+    debug!(
+        "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
+        composition_poly.num_columns(),
+        composition_poly.column_degree(),
+        now.elapsed().as_millis()
+    );
+
+    // then, evaluate composition polynomial columns over the LDE domain
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+    let composed_evaluations = backend.evaluate_polys_over(composition_poly.data(), domain);
+    #[cfg(feature = "std")]
+    debug!(
+        "Evaluated {} composition polynomial columns over LDE domain (2^{} elements) in {} ms",
+        composed_evaluations.num_cols(),
+        composed_evaluations.num_rows().ilog2(),
+        now.elapsed().as_millis()
+    );
+
+    // finally, build constraint evaluation commitment
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+    let commitment = backend.commit_to_rows(&composed_evaluations);
+    let constraint_commitment = ConstraintCommitment::new(composed_evaluations, commitment);
+    #[cfg(feature = "std")]
+    debug!(
+        "Computed constraint evaluation commitment (Merkle tree of depth {}) in {} ms",
+        constraint_commitment.tree_depth(),
+        now.elapsed().as_millis()
+    );
+
+    (constraint_commitment, composition_poly)
+}
+
+/// Extends constraint composition polynomial over the LDE domain, stores its evaluations in a
+/// temporary file in the specified directory, and builds a commitment to these evaluations.
+///
+/// This is the same as [build_constraint_commitment()] executed with [CpuBackend], except that
+/// the evaluations are never held in memory all at once.
+#[cfg(feature = "std")]
+pub(crate) fn build_constraint_commitment_on_disk<E, H>(
+    composition_poly_trace: CompositionPolyTrace<E>,
+    num_trace_poly_columns: usize,
+    domain: &StarkDomain<E::BaseField>,
+    directory: &Path,
+) -> io::Result<(ConstraintCommitment<E, H>, CompositionPoly<E>)>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let now = Instant::now();
+    let composition_poly =
+        CompositionPoly::new(composition_poly_trace, domain, num_trace_poly_columns);
+    debug!(
+        "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
+        composition_poly.num_columns(),
+        composition_poly.column_degree(),
+        now.elapsed().as_millis()
+    );
+
+    let now = Instant::now();
+    let (evaluations, commitment) =
+        DiskMatrix::evaluate_polys_over::<H>(composition_poly.data(), domain, directory)?;
+    let constraint_commitment = ConstraintCommitment::new_on_disk(evaluations, commitment);
+    debug!(
+        "Evaluated composition polynomial columns over LDE domain and committed to them on disk (Merkle tree of depth {}) in {} ms",
+        constraint_commitment.tree_depth(),
+        now.elapsed().as_millis()
+    );
+
+    Ok((constraint_commitment, composition_poly))
+}
@@ -0,0 +1,47 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+// CANCELLATION TOKEN
+// ================================================================================================
+
+/// A token which can be used to cancel in-flight proof generation.
+///
+/// A prover observes the token returned from
+/// [Prover::cancellation_token()](crate::Prover::cancellation_token). Once
+/// [CancellationToken::cancel()] is invoked on the token (or on any of its clones, e.g., from a
+/// different thread), the prover stops at the start of the next phase of proof generation (and
+/// during proof-of-work grinding), drops all data allocated for the proof, and returns
+/// [ProverError::Cancelled](crate::ProverError::Cancelled).
+///
+/// Cancellation is permanent: once a token is cancelled, all proofs generated by provers
+/// observing this token fail. Thus, a new token should be created for every proving request.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Returns a new token which has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of proof generation for all provers observing this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if cancellation has been requested via this token or any of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
@@ -3,15 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::{CancellationToken, ProverError, ProvingPhase};
 use air::{
-    proof::{Commitments, Context, OodFrame, Queries, StarkProof},
+    proof::{
+        stream::ProofSection, Commitments, Context, OodFrame, ProofMetadata, Queries, StarkProof,
+    },
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
 };
 use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, RandomCoin};
 use fri::{self, FriProof};
 use math::{FieldElement, ToElements};
-use utils::collections::Vec;
+use utils::{collections::Vec, Serializable};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -19,6 +22,9 @@ use utils::iterators::*;
 // TYPES AND INTERFACES
 // ================================================================================================
 
+/// Callback to which the sections of a streamed proof are passed.
+type OnSection<'a> = &'a mut dyn FnMut(ProofSection, &[u8]);
+
 pub struct ProverChannel<'a, A, E, H, R>
 where
     A: Air,
@@ -32,6 +38,8 @@ where
     commitments: Commitments,
     ood_frame: OodFrame,
     pow_nonce: u64,
+    on_section: Option<OnSection<'a>>,
+    cancellation: Option<CancellationToken>,
     _field_element: PhantomData<E>,
 }
 
@@ -48,8 +56,25 @@ where
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new prover channel for the specified `air` and public inputs.
-    pub fn new(air: &'a A, mut pub_inputs_elements: Vec<A::BaseField>) -> Self {
-        let context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone());
+    ///
+    /// The public coin of the channel is instantiated via `new_public_coin` from the seed built
+    /// from the proof context and the public inputs; if `salt` is provided, it is included in the
+    /// proof context.
+    pub fn new<F>(
+        air: &'a A,
+        salt: Option<[u8; 32]>,
+        mut pub_inputs_elements: Vec<A::BaseField>,
+        new_public_coin: F,
+    ) -> Self
+    where
+        F: FnOnce(&[A::BaseField]) -> R,
+    {
+        let mut context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone())
+            .set_air_id(A::AIR_ID)
+            .set_air_fingerprint(air.fingerprint());
+        if let Some(salt) = salt {
+            context = context.set_salt(salt);
+        }
 
         // build a seed for the public coin; the initial seed is a hash of the proof context and
         // the public inputs, but as the protocol progresses, the coin will be reseeded with the
@@ -59,27 +84,51 @@ where
 
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&coin_seed_elements),
+            public_coin: new_public_coin(&coin_seed_elements),
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
             pow_nonce: 0,
+            on_section: None,
+            cancellation: None,
             _field_element: PhantomData,
         }
     }
 
+    /// Makes the channel pass every section of the proof to `on_section` as soon as the section
+    /// is finalized; the context section is passed immediately.
+    ///
+    /// This must be called before the prover commits to the execution trace.
+    pub fn stream_sections(&mut self, on_section: OnSection<'a>) {
+        on_section(ProofSection::Context, &self.context.to_bytes());
+        self.on_section = Some(on_section);
+    }
+
+    /// Makes proof-of-work grinding performed via this channel stop as soon as the specified
+    /// token is cancelled.
+    pub fn observe_cancellation(&mut self, token: CancellationToken) {
+        self.cancellation = Some(token);
+    }
+
+    /// Returns true if the token observed by this channel (if any) has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.as_ref().is_some_and(|token| token.is_cancelled())
+    }
+
     // COMMITMENT METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Commits the prover the extended execution trace.
     pub fn commit_trace(&mut self, trace_root: H::Digest) {
         self.commitments.add::<H>(&trace_root);
+        self.emit_digest(ProofSection::TraceCommitment, &trace_root);
         self.public_coin.reseed(trace_root);
     }
 
     /// Commits the prover to the evaluations of the constraint composition polynomial.
     pub fn commit_constraints(&mut self, constraint_root: H::Digest) {
         self.commitments.add::<H>(&constraint_root);
+        self.emit_digest(ProofSection::ConstraintCommitment, &constraint_root);
         self.public_coin.reseed(constraint_root);
     }
 
@@ -97,6 +146,14 @@ where
         self.public_coin.reseed(H::hash_elements(evaluations));
     }
 
+    /// Passes the out-of-domain frame to the section callback (if any); this must be called once
+    /// evaluations at all out-of-domain points have been sent.
+    pub fn finish_ood_frame(&mut self) {
+        if let Some(on_section) = self.on_section.as_mut() {
+            on_section(ProofSection::OodFrame, &self.ood_frame.to_bytes());
+        }
+    }
+
     // PUBLIC COIN METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -136,15 +193,20 @@ where
     /// Returns a set of positions in the LDE domain against which the evaluations of trace and
     /// constraint composition polynomials should be queried.
     ///
-    /// The positions are drawn from the public coin uniformly at random. Duplicate positions
-    /// are removed from the returned vector.
+    /// The positions are drawn from the public coin uniformly at random; if the proof options
+    /// require distinct queries, duplicate positions are re-drawn. Duplicate positions are
+    /// removed from the returned vector.
     pub fn get_query_positions(&mut self) -> Vec<usize> {
-        let num_queries = self.context.options().num_queries();
+        let options = self.context.options();
+        let num_queries = options.num_queries();
         let lde_domain_size = self.context.lde_domain_size();
-        let mut positions = self
-            .public_coin
-            .draw_integers(num_queries, lde_domain_size, self.pow_nonce)
-            .expect("failed to draw query position");
+        let mut positions = if options.distinct_queries() {
+            self.public_coin
+                .draw_distinct_integers(num_queries, lde_domain_size, self.pow_nonce)
+        } else {
+            self.public_coin.draw_integers(num_queries, lde_domain_size, self.pow_nonce)
+        }
+        .expect("failed to draw query position");
 
         // remove any duplicate positions from the list
         positions.sort_unstable();
@@ -156,21 +218,34 @@ where
     /// Determines a nonce, which when hashed with the current seed of the public coin results
     /// in a new seed with the number of leading zeros equal to the grinding_factor specified
     /// in the proof options.
-    pub fn grind_query_seed(&mut self) {
+    ///
+    /// If the token observed by this channel is cancelled while the search for the nonce is in
+    /// progress, the search is aborted and an error is returned.
+    pub fn grind_query_seed(&mut self) -> Result<(), ProverError> {
         let grinding_factor = self.context.options().grinding_factor();
+        let cancellation = self.cancellation.as_ref();
+        let is_cancelled = || cancellation.is_some_and(|token| token.is_cancelled());
 
         #[cfg(not(feature = "concurrent"))]
         let nonce = (1..u64::MAX)
-            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .find(|&nonce| {
+                is_cancelled() || self.public_coin.check_leading_zeros(nonce) >= grinding_factor
+            })
             .expect("nonce not found");
 
         #[cfg(feature = "concurrent")]
         let nonce = (1..u64::MAX)
             .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .find_any(|&nonce| {
+                is_cancelled() || self.public_coin.check_leading_zeros(nonce) >= grinding_factor
+            })
             .expect("nonce not found");
 
+        if is_cancelled() {
+            return Err(ProverError::Cancelled(ProvingPhase::Grinding));
+        }
         self.pow_nonce = nonce;
+        Ok(())
     }
 
     // PROOF BUILDER
@@ -184,9 +259,9 @@ where
         fri_proof: FriProof,
         num_query_positions: usize,
     ) -> StarkProof {
-        assert!(num_query_positions <= u8::MAX as usize, "num_query_positions too big");
+        assert!(num_query_positions <= u16::MAX as usize, "num_query_positions too big");
 
-        StarkProof {
+        let proof = StarkProof {
             context: self.context,
             commitments: self.commitments,
             ood_frame: self.ood_frame,
@@ -194,7 +269,27 @@ where
             constraint_queries,
             fri_proof,
             pow_nonce: self.pow_nonce,
-            num_unique_queries: num_query_positions as u8,
+            num_unique_queries: num_query_positions as u16,
+            metadata: ProofMetadata::new(),
+        };
+
+        // all other sections have already been emitted; the queries section is the last one
+        if let Some(on_section) = self.on_section {
+            let sections = proof.to_stream_sections::<H>().expect("invalid proof commitments");
+            let (section, payload) = sections.last().expect("no proof sections");
+            on_section(*section, payload);
+        }
+
+        proof
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Passes the specified digest to the section callback (if any).
+    fn emit_digest(&mut self, section: ProofSection, digest: &<H as Hasher>::Digest) {
+        if let Some(on_section) = self.on_section.as_mut() {
+            on_section(section, &digest.to_bytes());
         }
     }
 }
@@ -214,6 +309,7 @@ where
     /// Commits the prover to a FRI layer.
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.add::<H>(&layer_root);
+        self.emit_digest(ProofSection::FriCommitment, &layer_root);
         self.public_coin.reseed(layer_root);
     }
 
@@ -221,4 +317,34 @@ where
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    /// Determines a nonce, which when hashed with the current seed of the public coin results
+    /// in a value with the number of leading zeros equal to the specified grinding factor, and
+    /// reseeds the public coin with this nonce.
+    ///
+    /// If the token observed by this channel is cancelled while the search for the nonce is in
+    /// progress, the search is aborted and an arbitrary nonce is returned; the prover must check
+    /// for cancellation via [ProverChannel::is_cancelled()] once FRI layers have been built.
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let cancellation = self.cancellation.as_ref();
+        let is_cancelled = || cancellation.is_some_and(|token| token.is_cancelled());
+
+        #[cfg(not(feature = "concurrent"))]
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| {
+                is_cancelled() || self.public_coin.check_leading_zeros(nonce) >= grinding_factor
+            })
+            .expect("nonce not found");
+
+        #[cfg(feature = "concurrent")]
+        let nonce = (1..u64::MAX)
+            .into_par_iter()
+            .find_any(|&nonce| {
+                is_cancelled() || self.public_coin.check_leading_zeros(nonce) >= grinding_factor
+            })
+            .expect("nonce not found");
+
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
 }
@@ -0,0 +1,234 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    ColMatrix, CompositionPolyTrace, CpuBackend, EvaluationFrame, ProverBackend, ProverError,
+    RowMatrix, StarkDomain, TraceInfo, TraceLayout, TraceLde, ValidationReport,
+};
+use air::proof::Queries;
+use core::{fmt, marker::PhantomData};
+use crypto::ElementHasher;
+use math::{fft, polynom, FieldElement};
+use utils::collections::Vec;
+
+// CHECK REPORT
+// ================================================================================================
+/// A report describing the results of checking an execution trace against an AIR via
+/// [Prover::check()](crate::Prover::check).
+///
+/// The report combines two kinds of diagnostics:
+/// * Constraint violations: every assertion and transition constraint which is not satisfied by
+///   the trace, together with the step and the values of the trace at this step.
+/// * Degree of the constraint composition polynomial: if the trace is valid and degrees of
+///   transition constraints are declared correctly, the constraint composition polynomial
+///   computed over the low-degree extension of the trace has degree smaller than
+///   [CheckReport::max_composition_degree()]. Otherwise, the verifier would reject a proof for
+///   the trace, even though no constraint violations are found. The degree is computed only for
+///   traces which do not violate any constraints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckReport<E: FieldElement> {
+    validation: ValidationReport<E>,
+    composition_degree: Option<usize>,
+    max_composition_degree: usize,
+}
+
+impl<E: FieldElement> CheckReport<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new report instantiated from the specified diagnostics.
+    pub fn new(
+        validation: ValidationReport<E>,
+        composition_degree: Option<usize>,
+        max_composition_degree: usize,
+    ) -> Self {
+        Self {
+            validation,
+            composition_degree,
+            max_composition_degree,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the trace satisfies all constraints of the AIR and the constraint
+    /// composition polynomial has the expected degree; in this case, a valid proof can be
+    /// generated for the trace.
+    pub fn is_valid(&self) -> bool {
+        self.validation.is_valid() && self.is_composition_degree_valid()
+    }
+
+    /// Returns a report describing all constraints which are not satisfied by the trace.
+    pub fn validation(&self) -> &ValidationReport<E> {
+        &self.validation
+    }
+
+    /// Returns the degree of the constraint composition polynomial computed over the low-degree
+    /// extension of the trace, or None if constraints were not evaluated over the low-degree
+    /// extension because the trace violates some constraints.
+    pub fn composition_degree(&self) -> Option<usize> {
+        self.composition_degree
+    }
+
+    /// Returns the maximum degree of the constraint composition polynomial supported by the AIR
+    /// (exclusive).
+    pub fn max_composition_degree(&self) -> usize {
+        self.max_composition_degree
+    }
+
+    /// Returns true if the constraint composition polynomial was computed and its degree is
+    /// smaller than the maximum degree supported by the AIR.
+    pub fn is_composition_degree_valid(&self) -> bool {
+        self.composition_degree
+            .is_some_and(|degree| degree < self.max_composition_degree)
+    }
+}
+
+impl<E: FieldElement> fmt::Display for CheckReport<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.validation)?;
+        match self.composition_degree {
+            Some(degree) if degree < self.max_composition_degree => write!(
+                f,
+                "\nconstraint composition polynomial has degree {degree} (must be smaller than {})",
+                self.max_composition_degree
+            ),
+            Some(degree) => write!(
+                f,
+                "\nconstraint composition polynomial has degree {degree}, but must be smaller than {}",
+                self.max_composition_degree
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+// UNCOMMITTED TRACE LDE
+// ================================================================================================
+/// Low-degree extension of an execution trace which is not committed to.
+///
+/// This is used by [Prover::check()](crate::Prover::check) to evaluate constraints over the
+/// extended trace without building Merkle trees. Commitments returned by this struct are default
+/// digests, and querying rows of the extended trace returns no queries.
+pub(crate) struct UncommittedTraceLde<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    main_segment_lde: RowMatrix<E::BaseField>,
+    aux_segment_ldes: Vec<RowMatrix<E>>,
+    blowup: usize,
+    trace_info: TraceInfo,
+    _digest: PhantomData<H::Digest>,
+}
+
+impl<E, H> UncommittedTraceLde<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Returns a new low-degree extension of the main segment of the specified trace over the
+    /// specified domain.
+    pub fn new(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> Self {
+        let main_trace_polys = CpuBackend.interpolate_columns(main_trace);
+        Self {
+            main_segment_lde: CpuBackend.evaluate_polys_over(&main_trace_polys, domain),
+            aux_segment_ldes: Vec::new(),
+            blowup: domain.trace_to_lde_blowup(),
+            trace_info: trace_info.clone(),
+            _digest: PhantomData,
+        }
+    }
+}
+
+impl<E, H> TraceLde<E> for UncommittedTraceLde<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    type HashFn = H;
+
+    fn get_main_trace_commitment(&self) -> H::Digest {
+        H::Digest::default()
+    }
+
+    fn add_aux_segment(
+        &mut self,
+        aux_trace: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> Result<(ColMatrix<E>, H::Digest), ProverError> {
+        let aux_trace_polys = CpuBackend.interpolate_columns(aux_trace);
+        self.aux_segment_ldes
+            .push(CpuBackend.evaluate_polys_over(&aux_trace_polys, domain));
+        Ok((aux_trace_polys, H::Digest::default()))
+    }
+
+    fn read_main_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E::BaseField>,
+    ) -> Result<(), ProverError> {
+        // at the end of the trace, next state wraps around and we read the first step again
+        let next_lde_step = (lde_step + self.blowup) % self.trace_len();
+
+        frame.current_mut().copy_from_slice(self.main_segment_lde.row(lde_step));
+        frame.next_mut().copy_from_slice(self.main_segment_lde.row(next_lde_step));
+        Ok(())
+    }
+
+    fn read_aux_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E>,
+    ) -> Result<(), ProverError> {
+        // at the end of the trace, next state wraps around and we read the first step again
+        let next_lde_step = (lde_step + self.blowup) % self.trace_len();
+
+        // copy values of each auxiliary trace segment into the corresponding columns of the frame
+        let mut offset = 0;
+        for segment_lde in self.aux_segment_ldes.iter() {
+            let width = segment_lde.num_cols();
+            let columns = offset..offset + width;
+            frame.current_mut()[columns.clone()].copy_from_slice(segment_lde.row(lde_step));
+            frame.next_mut()[columns].copy_from_slice(segment_lde.row(next_lde_step));
+            offset += width;
+        }
+        Ok(())
+    }
+
+    /// Returns no queries, since rows of an uncommitted trace cannot be authenticated;
+    /// [Prover::check()](crate::Prover::check) never queries the trace.
+    fn query(&self, positions: &[usize]) -> Result<Vec<Queries>, ProverError> {
+        debug_assert!(positions.is_empty(), "uncommitted trace LDE cannot be queried");
+        Ok(Vec::new())
+    }
+
+    fn trace_len(&self) -> usize {
+        self.main_segment_lde.num_rows()
+    }
+
+    fn blowup(&self) -> usize {
+        self.blowup
+    }
+
+    fn trace_layout(&self) -> &TraceLayout {
+        self.trace_info.layout()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Interpolates the constraint composition polynomial from its evaluations over the constraint
+/// evaluation domain and returns its degree.
+pub(crate) fn get_composition_degree<E: FieldElement>(
+    composition_poly_trace: CompositionPolyTrace<E>,
+    domain: &StarkDomain<E::BaseField>,
+) -> usize {
+    let mut evaluations = composition_poly_trace.into_inner();
+    let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(evaluations.len());
+    fft::interpolate_poly_with_offset(&mut evaluations, &inv_twiddles, domain.offset());
+    polynom::degree_of(&evaluations)
+}
@@ -0,0 +1,254 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColMatrix, TraceInfo};
+use crypto::Hasher;
+use math::FieldElement;
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the checkpoint serialization format; checkpoints serialized in other versions of
+/// the format cannot be read.
+const CHECKPOINT_VERSION: u8 = 1;
+
+// TRACE CHECKPOINT
+// ================================================================================================
+/// A snapshot of the prover state taken right after the prover has committed to all segments of
+/// the execution trace.
+///
+/// A checkpoint contains the main trace segment, all auxiliary trace segments, the public inputs
+/// of the computation, the transcript salt (if any), and the roots of trace commitments written
+/// into the prover channel. This is sufficient to resume proof generation without re-executing
+/// the computation and without re-building auxiliary trace segments: the prover channel is
+/// restored by replaying trace commitments, and the LDEs of trace segments are re-computed from
+/// the stored segments (the re-computed commitments are checked against the stored ones).
+///
+/// A checkpoint does not contain the LDEs of trace segments, the state of the prover channel, or
+/// any data produced after the trace was committed to. Thus, resuming from a checkpoint saves
+/// only the work of building the execution trace: the LDEs and commitments of all trace segments
+/// are computed again, and all subsequent steps of the protocol (constraint evaluation, DEEP
+/// composition, and FRI) are executed from scratch.
+///
+/// Checkpoints are produced by [Prover::prove_with_config()] and consumed by
+/// [Prover::resume_proof()]. Since a checkpoint contains the entire execution trace, it reveals
+/// all secret inputs of the computation, and thus, should be stored with the same care as the
+/// inputs themselves.
+///
+/// [Prover::prove_with_config()]: crate::Prover::prove_with_config
+/// [Prover::resume_proof()]: crate::Prover::resume_proof
+#[derive(Debug, Clone)]
+pub struct TraceCheckpoint<E: FieldElement, H: Hasher> {
+    trace_info: TraceInfo,
+    pub_inputs: Vec<E::BaseField>,
+    salt: Option<[u8; 32]>,
+    main_segment: ColMatrix<E::BaseField>,
+    aux_segments: Vec<ColMatrix<E>>,
+    trace_roots: Vec<H::Digest>,
+}
+
+impl<E: FieldElement, H: Hasher> TraceCheckpoint<E, H> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns information about the execution trace contained in this checkpoint.
+    pub fn trace_info(&self) -> &TraceInfo {
+        &self.trace_info
+    }
+
+    /// Returns public inputs of the computation serialized into field elements.
+    pub fn pub_inputs(&self) -> &[E::BaseField] {
+        &self.pub_inputs
+    }
+
+    /// Returns the salt of the proof transcript, or `None` if the transcript is not salted.
+    pub fn salt(&self) -> Option<&[u8; 32]> {
+        self.salt.as_ref()
+    }
+
+    /// Returns the main segment of the execution trace.
+    pub fn main_segment(&self) -> &ColMatrix<E::BaseField> {
+        &self.main_segment
+    }
+
+    /// Returns auxiliary segments of the execution trace.
+    pub fn aux_segments(&self) -> &[ColMatrix<E>] {
+        &self.aux_segments
+    }
+
+    /// Returns roots of the commitments to all trace segments, starting with the main segment.
+    pub fn trace_roots(&self) -> &[H::Digest] {
+        &self.trace_roots
+    }
+
+    // PUBLIC METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Destructures this checkpoint into trace info, public inputs, transcript salt, main trace
+    /// segment, auxiliary trace segments, and trace commitment roots.
+    #[allow(clippy::type_complexity)]
+    pub fn into_parts(
+        self,
+    ) -> (
+        TraceInfo,
+        Vec<E::BaseField>,
+        Option<[u8; 32]>,
+        ColMatrix<E::BaseField>,
+        Vec<ColMatrix<E>>,
+        Vec<H::Digest>,
+    ) {
+        (
+            self.trace_info,
+            self.pub_inputs,
+            self.salt,
+            self.main_segment,
+            self.aux_segments,
+            self.trace_roots,
+        )
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement, H: Hasher> Serializable for TraceCheckpoint<E, H> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_checkpoint::<E, H, W>(
+            target,
+            &self.trace_info,
+            &self.pub_inputs,
+            self.salt.as_ref(),
+            &self.main_segment,
+            &self.aux_segments,
+            &self.trace_roots,
+        );
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Deserializable for TraceCheckpoint<E, H> {
+    /// Reads a trace checkpoint from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid checkpoint could not be read from the specified `source`, or
+    /// if the checkpoint was created for a field extension of a different degree.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        if version != CHECKPOINT_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "checkpoint version {version} is not supported; expected version {CHECKPOINT_VERSION}"
+            )));
+        }
+
+        let extension_degree = source.read_u8()? as usize;
+        if extension_degree != E::EXTENSION_DEGREE {
+            return Err(DeserializationError::InvalidValue(format!(
+                "checkpoint was created for field extension of degree {}, but expected degree {}",
+                extension_degree,
+                E::EXTENSION_DEGREE
+            )));
+        }
+
+        let trace_info = TraceInfo::read_from(source)?;
+
+        let num_pub_inputs = source.read_u32()? as usize;
+        let pub_inputs = E::BaseField::read_batch_from(source, num_pub_inputs)?;
+        let salt = if source.read_bool()? {
+            Some(source.read_array()?)
+        } else {
+            None
+        };
+
+        // read and validate the main trace segment
+        let main_segment = ColMatrix::<E::BaseField>::read_from(source)?;
+        validate_segment_shape(&main_segment, trace_info.layout().main_trace_width(), &trace_info)?;
+
+        // read and validate auxiliary trace segments
+        let layout = trace_info.layout();
+        let mut aux_segments = Vec::with_capacity(layout.num_aux_segments());
+        for i in 0..layout.num_aux_segments() {
+            let segment = ColMatrix::<E>::read_from(source)?;
+            validate_segment_shape(&segment, layout.get_aux_segment_width(i), &trace_info)?;
+            aux_segments.push(segment);
+        }
+
+        let trace_roots = H::Digest::read_batch_from(source, layout.num_segments())?;
+
+        Ok(Self {
+            trace_info,
+            pub_inputs,
+            salt,
+            main_segment,
+            aux_segments,
+            trace_roots,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes a trace checkpoint assembled from the provided parts into the `target`.
+///
+/// This allows the prover to write a checkpoint without taking ownership of (or copying) the
+/// execution trace.
+pub(crate) fn write_checkpoint<E, H, W>(
+    target: &mut W,
+    trace_info: &TraceInfo,
+    pub_inputs: &[E::BaseField],
+    salt: Option<&[u8; 32]>,
+    main_segment: &ColMatrix<E::BaseField>,
+    aux_segments: &[ColMatrix<E>],
+    trace_roots: &[H::Digest],
+) where
+    E: FieldElement,
+    H: Hasher,
+    W: ByteWriter,
+{
+    debug_assert_eq!(aux_segments.len(), trace_info.layout().num_aux_segments());
+    debug_assert_eq!(trace_roots.len(), trace_info.layout().num_segments());
+
+    target.write_u8(CHECKPOINT_VERSION);
+    target.write_u8(E::EXTENSION_DEGREE as u8);
+    trace_info.write_into(target);
+    target.write_u32(pub_inputs.len() as u32);
+    E::BaseField::write_batch_into(pub_inputs, target);
+    target.write_bool(salt.is_some());
+    if let Some(salt) = salt {
+        target.write_bytes(salt);
+    }
+    main_segment.write_into(target);
+    for segment in aux_segments {
+        segment.write_into(target);
+    }
+    H::Digest::write_batch_into(trace_roots, target);
+}
+
+/// Returns an error if the specified trace segment does not have the expected number of columns
+/// or if its length is different from the length of the trace described by `trace_info`.
+fn validate_segment_shape<E: FieldElement>(
+    segment: &ColMatrix<E>,
+    expected_width: usize,
+    trace_info: &TraceInfo,
+) -> Result<(), DeserializationError> {
+    if segment.num_cols() != expected_width {
+        return Err(DeserializationError::InvalidValue(format!(
+            "trace segment must contain {} columns, but contained {}",
+            expected_width,
+            segment.num_cols()
+        )));
+    }
+    if segment.num_rows() != trace_info.length() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "trace segment must contain {} rows, but contained {}",
+            trace_info.length(),
+            segment.num_rows()
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,297 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    matrix::ColMatrix, AuxTraceRandElements, CancellationToken, CompositionPoly,
+    CompositionPolyTrace, ConstraintCommitment, ConstraintCompositionCoefficients, EvaluationFrame,
+    PeriodicColumnCache, ProofOptions, Prover, ProverError, ProvingPhase, StarkDomain, TraceInfo,
+    TraceLde, TracePolyTable,
+};
+use air::{proof::Queries, Air, TraceLayout};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, Hasher};
+use math::{FieldElement, StarkField};
+use std::sync::Mutex;
+use utils::collections::Vec;
+
+// COEFFICIENT COMMITMENT
+// ================================================================================================
+
+/// A polynomial commitment scheme which commits to polynomials in coefficient form.
+///
+/// The prover commits to the LDEs of trace polynomials via Merkle trees. Other proof systems
+/// (e.g., systems based on KZG or IPA commitments) consume polynomials committed to under their
+/// own schemes. Implementing this trait for such a scheme and generating proofs via
+/// [Prover::prove_with_coefficient_commitments()] commits to the same trace polynomials under both
+/// schemes in a single pass; the polynomials are passed to the scheme exactly as they are
+/// interpolated by the prover.
+///
+/// Main trace segment polynomials are passed as polynomials over the base field `B`, and
+/// auxiliary trace segment polynomials are passed as polynomials over the extension field used by
+/// the prover (or over `B` when no extension is used). The scheme is responsible for mapping
+/// these polynomials into the field it operates over.
+pub trait CoefficientCommitment<B: StarkField>: Sync {
+    /// Commitment to a single polynomial.
+    type Commitment: Send;
+
+    /// Returns a commitment to the polynomial defined by the specified coefficients; the
+    /// coefficients are ordered from the constant term to the coefficient of the highest degree
+    /// term.
+    fn commit<E: FieldElement<BaseField = B>>(&self, coefficients: &[E]) -> Self::Commitment;
+}
+
+/// A [CoefficientCommitment] which commits to a polynomial by hashing its coefficients with hash
+/// function `H`.
+///
+/// The commitment is binding but does not support openings; it is meant for settings in which
+/// the consumer of the commitment receives the coefficients as well (e.g., to check that the
+/// polynomials committed to by the prover are the polynomials passed to another system).
+pub struct CoefficientHash<H: ElementHasher> {
+    _hasher: PhantomData<fn() -> H>,
+}
+
+impl<H: ElementHasher> CoefficientHash<H> {
+    /// Returns a new instance of this commitment scheme.
+    pub fn new() -> Self {
+        Self {
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<H: ElementHasher> Default for CoefficientHash<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: ElementHasher> CoefficientCommitment<H::BaseField> for CoefficientHash<H> {
+    type Commitment = H::Digest;
+
+    fn commit<E: FieldElement<BaseField = H::BaseField>>(&self, coefficients: &[E]) -> H::Digest {
+        H::hash_elements(coefficients)
+    }
+}
+
+// COMMITTING PROVER
+// ================================================================================================
+
+/// A prover which delegates all work to the wrapped prover while committing to all trace
+/// polynomials interpolated by the wrapped prover via scheme `C`.
+///
+/// Commitments are appended to `commitments` in the order in which trace polynomials are
+/// interpolated, i.e., main trace segment columns first, followed by columns of the auxiliary
+/// segments.
+pub(crate) struct CommittingProver<'p, P: Prover, C: CoefficientCommitment<P::BaseField>> {
+    prover: &'p P,
+    scheme: &'p C,
+    commitments: &'p Mutex<Vec<C::Commitment>>,
+}
+
+impl<'p, P, C> CommittingProver<'p, P, C>
+where
+    P: Prover,
+    C: CoefficientCommitment<P::BaseField>,
+{
+    pub fn new(prover: &'p P, scheme: &'p C, commitments: &'p Mutex<Vec<C::Commitment>>) -> Self {
+        Self {
+            prover,
+            scheme,
+            commitments,
+        }
+    }
+}
+
+impl<'p, P, C> Prover for CommittingProver<'p, P, C>
+where
+    P: Prover,
+    C: CoefficientCommitment<P::BaseField>,
+{
+    type BaseField = P::BaseField;
+    type Air = P::Air;
+    type Trace = P::Trace;
+    type HashFn = P::HashFn;
+    type RandomCoin = P::RandomCoin;
+    type TraceLde<E>
+        = CommittingTraceLde<'p, E, P::TraceLde<E>, C>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+    type ConstraintEvaluator<'a, E>
+        = P::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> <Self::Air as Air>::PublicInputs {
+        self.prover.get_pub_inputs(trace)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.prover.options()
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let (trace_lde, trace_polys) = self.prover.new_trace_lde(trace_info, main_trace, domain);
+        let mut commitments = self.commitments.lock().expect("commitments lock poisoned");
+        commitments.extend(trace_polys.main_trace_polys().map(|poly| self.scheme.commit(poly)));
+
+        let trace_lde = CommittingTraceLde {
+            trace_lde,
+            scheme: self.scheme,
+            commitments: self.commitments,
+            _field_element: PhantomData,
+        };
+        (trace_lde, trace_polys)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.prover.new_evaluator(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn on_phase_start(&self, phase: ProvingPhase) {
+        self.prover.on_phase_start(phase);
+    }
+
+    fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.prover.cancellation_token()
+    }
+
+    fn low_memory_mode(&self) -> bool {
+        self.prover.low_memory_mode()
+    }
+
+    fn lde_directory(&self) -> Option<std::path::PathBuf> {
+        self.prover.lde_directory()
+    }
+
+    fn rng_seed(&self) -> Option<[u8; 32]> {
+        self.prover.rng_seed()
+    }
+
+    fn salt_transcript(&self) -> bool {
+        self.prover.salt_transcript()
+    }
+
+    fn periodic_column_cache(&self) -> Option<&PeriodicColumnCache<Self::BaseField>> {
+        self.prover.periodic_column_cache()
+    }
+
+    fn new_public_coin(&self, seed: &[Self::BaseField]) -> Self::RandomCoin {
+        self.prover.new_public_coin(seed)
+    }
+
+    fn build_constraint_commitment<E>(
+        &self,
+        composition_poly_trace: CompositionPolyTrace<E>,
+        num_trace_poly_columns: usize,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Result<(ConstraintCommitment<E, Self::HashFn>, CompositionPoly<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.prover.build_constraint_commitment(
+            composition_poly_trace,
+            num_trace_poly_columns,
+            domain,
+        )
+    }
+}
+
+// COMMITTING TRACE LDE
+// ================================================================================================
+
+/// A trace LDE which delegates all work to the wrapped trace LDE while committing to the
+/// polynomials of auxiliary trace segments via scheme `C`.
+pub(crate) struct CommittingTraceLde<'p, E, L, C>
+where
+    E: FieldElement,
+    L: TraceLde<E>,
+    C: CoefficientCommitment<E::BaseField>,
+{
+    trace_lde: L,
+    scheme: &'p C,
+    commitments: &'p Mutex<Vec<C::Commitment>>,
+    _field_element: PhantomData<E>,
+}
+
+impl<'p, E, L, C> TraceLde<E> for CommittingTraceLde<'p, E, L, C>
+where
+    E: FieldElement,
+    L: TraceLde<E>,
+    C: CoefficientCommitment<E::BaseField>,
+{
+    type HashFn = L::HashFn;
+
+    fn get_main_trace_commitment(&self) -> <Self::HashFn as Hasher>::Digest {
+        self.trace_lde.get_main_trace_commitment()
+    }
+
+    fn add_aux_segment(
+        &mut self,
+        aux_trace: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> Result<(ColMatrix<E>, <Self::HashFn as Hasher>::Digest), ProverError> {
+        let (aux_segment_polys, aux_segment_root) =
+            self.trace_lde.add_aux_segment(aux_trace, domain)?;
+        let mut commitments = self.commitments.lock().expect("commitments lock poisoned");
+        commitments.extend(aux_segment_polys.columns().map(|poly| self.scheme.commit(poly)));
+        Ok((aux_segment_polys, aux_segment_root))
+    }
+
+    fn read_main_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E::BaseField>,
+    ) -> Result<(), ProverError> {
+        self.trace_lde.read_main_trace_frame_into(lde_step, frame)
+    }
+
+    fn read_aux_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E>,
+    ) -> Result<(), ProverError> {
+        self.trace_lde.read_aux_trace_frame_into(lde_step, frame)
+    }
+
+    fn query(&self, positions: &[usize]) -> Result<Vec<Queries>, ProverError> {
+        self.trace_lde.query(positions)
+    }
+
+    fn discard_evaluations(
+        &mut self,
+        trace_polys: &TracePolyTable<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) {
+        self.trace_lde.discard_evaluations(trace_polys, domain)
+    }
+
+    fn trace_len(&self) -> usize {
+        self.trace_lde.trace_len()
+    }
+
+    fn blowup(&self) -> usize {
+        self.trace_lde.blowup()
+    }
+
+    fn trace_layout(&self) -> &TraceLayout {
+        self.trace_lde.trace_layout()
+    }
+}
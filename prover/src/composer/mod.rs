@@ -5,27 +5,37 @@
 
 use super::{constraints::CompositionPoly, StarkDomain, TracePolyTable};
 use air::DeepCompositionCoefficients;
-use math::{add_in_place, fft, mul_acc, polynom, ExtensionOf, FieldElement, StarkField};
-use utils::{collections::Vec, iter_mut};
+use math::{add_in_place, fft, polynom, FieldElement, StarkField};
+use utils::{batch_iter_mut, collections::Vec, iter_mut};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Number of coefficients updated from all polynomials at once when combining polynomials.
+const TILE_SIZE: usize = 256;
+
 // DEEP COMPOSITION POLYNOMIAL
 // ================================================================================================
 pub struct DeepCompositionPoly<E: FieldElement> {
     coefficients: Vec<E>,
     cc: DeepCompositionCoefficients<E>,
-    z: E,
+    z: Vec<E>,
 }
 
 impl<E: FieldElement> DeepCompositionPoly<E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new DEEP composition polynomial. Initially, this polynomial will be empty, and
-    /// the intent is to populate the coefficients via add_trace_polys() and add_constraint_polys()
-    /// methods.
-    pub fn new(z: E, cc: DeepCompositionCoefficients<E>) -> Self {
+    /// Returns a new DEEP composition polynomial for the specified out-of-domain points.
+    /// Initially, this polynomial will be empty, and the intent is to populate the coefficients
+    /// via add_trace_polys() and add_constraint_polys() methods.
+    ///
+    /// # Panics
+    /// Panics if `z` is empty.
+    pub fn new(z: Vec<E>, cc: DeepCompositionCoefficients<E>) -> Self {
+        assert!(!z.is_empty(), "at least one out-of-domain point is required");
         DeepCompositionPoly {
             coefficients: vec![],
             cc,
@@ -58,81 +68,58 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///   combination as T(x) = sum((T'_i(x) + T''_i(x)) * cc_i) for all i, where cc_i is
     ///   the coefficient for the random linear combination drawn from the public coin.
     ///
-    /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_trace_state`
-    /// parameter.
+    /// Since division by (x - z) and (x - z * g) is linear, the trace polynomials are first
+    /// combined into a single polynomial S(x) = sum(T_i(x) * cc_i) in one pass over the
+    /// coefficients of all trace polynomials, and then T(x) is computed as
+    /// (S(x) - S(z)) / (x - z) + (S(x) - S(z * g)) / (x - z * g). Thus, no intermediate vectors
+    /// are allocated for individual trace polynomials.
+    ///
+    /// When there are several out-of-domain points, the quotients for all points are computed
+    /// from the same S(x) and added together.
+    ///
+    /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_trace_states`
+    /// parameter; this parameter contains one frame per out-of-domain point.
     pub fn add_trace_polys(
         &mut self,
         trace_polys: TracePolyTable<E>,
-        ood_trace_states: Vec<Vec<E>>,
+        ood_trace_states: Vec<Vec<Vec<E>>>,
     ) {
         assert!(self.coefficients.is_empty());
+        assert_eq!(self.z.len(), ood_trace_states.len());
 
-        // compute a second out-of-domain point offset from z by exactly trace generator; this
-        // point defines the "next" computation state in relation to point z
+        // the "next" computation state in relation to point z is defined by point z * g, where
+        // g is the generator of the trace domain
         let trace_length = trace_polys.poly_size();
         let g = E::from(E::BaseField::get_root_of_unity(trace_length.ilog2()));
-        let next_z = self.z * g;
-
-        // combine trace polynomials into 2 composition polynomials T'(x) and T''(x)
-        let mut t1_composition = E::zeroed_vector(trace_length);
-        let mut t2_composition = E::zeroed_vector(trace_length);
-
-        // index of a trace polynomial; we declare it here so that we can maintain index continuity
-        // across all trace segments
-        let mut i = 0;
-
-        // --- merge polynomials of the main trace segment ----------------------------------------
-        for poly in trace_polys.main_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states[0][i],
-                self.cc.trace[i],
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states[1][i],
-                self.cc.trace[i],
-            );
-
-            i += 1;
-        }
 
-        // --- merge polynomials of the auxiliary trace segments ----------------------------------
-        for poly in trace_polys.aux_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states[0][i],
-                self.cc.trace[i],
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states[1][i],
-                self.cc.trace[i],
-            );
-
-            i += 1;
+        // combine polynomials of all trace segments into S(x); polynomials of the main trace
+        // segment are over the base field, while polynomials of the auxiliary trace segments
+        // are over the extension field
+        let main_polys = trace_polys.main_trace_polys().collect::<Vec<_>>();
+        let aux_polys = trace_polys.aux_trace_polys().collect::<Vec<_>>();
+        let mut combined_poly = E::zeroed_vector(trace_length);
+        combine_polys(&mut combined_poly, &main_polys, &aux_polys, &self.cc.trace);
+
+        let mut trace_poly = E::zeroed_vector(trace_length);
+        for (&z, ood_trace_state) in self.z.iter().zip(ood_trace_states.iter()) {
+            // compute S(z) and S(z * g) from the out-of-domain evaluations of trace polynomials
+            let s_at_z = linear_combination(&ood_trace_state[0], &self.cc.trace);
+            let s_at_next_z = linear_combination(&ood_trace_state[1], &self.cc.trace);
+
+            // compute S(x) - S(z) and S(x) - S(z * g)
+            let mut t1_composition = combined_poly.clone();
+            let mut t2_composition = combined_poly.clone();
+            t1_composition[0] -= s_at_z;
+            t2_composition[0] -= s_at_next_z;
+
+            // divide the composition polynomials by (x - z) and (x - z * g), respectively,
+            // and add the resulting polynomials together; the output of this step
+            // is a single trace polynomial T(x) and deg(T(x)) = trace_length - 2.
+            let quotient =
+                merge_trace_compositions(vec![t1_composition, t2_composition], vec![z, z * g]);
+            add_in_place(&mut trace_poly, &quotient);
         }
 
-        // divide the composition polynomials by (x - z) and (x - z * g), respectively,
-        // and add the resulting polynomials together; the output of this step
-        // is a single trace polynomial T(x) and deg(T(x)) = trace_length - 2.
-        let trace_poly =
-            merge_trace_compositions(vec![t1_composition, t2_composition], vec![self.z, next_z]);
-
         // set the coefficients of the DEEP composition polynomial
         self.coefficients = trace_poly;
         assert_eq!(self.poly_size() - 2, self.degree());
@@ -150,28 +137,34 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///   all i, where cc_i is the coefficient for the random linear combination drawn from the
     ///   public coin.
     ///
-    /// Note that evaluations of H_i(x) at z are passed in via the `ood_evaluations` parameter.
+    /// Similarly to trace polynomials, the columns are first combined into a single polynomial
+    /// in one pass over their coefficients, and then each OOD point is divided out of a copy of
+    /// the combined polynomial.
+    ///
+    /// Note that evaluations of H_i(x) at z are passed in via the `ood_evaluations` parameter;
+    /// this parameter contains one vector of evaluations per out-of-domain point.
     pub fn add_composition_poly(
         &mut self,
         composition_poly: CompositionPoly<E>,
-        ood_evaluations: Vec<E>,
+        ood_evaluations: Vec<Vec<E>>,
     ) {
         assert!(!self.coefficients.is_empty());
+        assert_eq!(self.z.len(), ood_evaluations.len());
 
-        let z = self.z;
+        let column_polys = composition_poly.into_columns();
+        let column_polys = column_polys.iter().map(|poly| poly.as_slice()).collect::<Vec<_>>();
 
-        let mut column_polys = composition_poly.into_columns();
+        let mut combined_poly = E::zeroed_vector(self.poly_size());
+        combine_polys(&mut combined_poly, &[], &column_polys, &self.cc.constraints);
 
-        // Divide out the OOD point z from column polynomials
-        iter_mut!(column_polys).zip(ood_evaluations).for_each(|(poly, value_at_z)| {
-            // compute H'_i(x) = (H_i(x) - H_i(z)) / (x - z)
-            poly[0] -= value_at_z;
-            polynom::syn_div_in_place(poly, 1, z);
-        });
+        for (&z, evaluations) in self.z.iter().zip(ood_evaluations.iter()) {
+            // compute H(x) = (sum(H_i(x) * cc_i) - sum(H_i(z) * cc_i)) / (x - z)
+            let mut composition = combined_poly.clone();
+            composition[0] -= linear_combination(evaluations, &self.cc.constraints);
+            polynom::syn_div_in_place(&mut composition, 1, z);
 
-        // add H'_i(x) * cc_i for all i into the DEEP composition polynomial
-        for (i, poly) in column_polys.into_iter().enumerate() {
-            mul_acc::<E, E>(&mut self.coefficients, &poly, self.cc.constraints[i]);
+            // add H(x) into the DEEP composition polynomial
+            add_in_place(&mut self.coefficients, &composition);
         }
         assert_eq!(self.poly_size() - 2, self.degree());
     }
@@ -209,13 +202,40 @@ fn merge_trace_compositions<E: FieldElement>(mut polys: Vec<Vec<E>>, divisors: V
     result
 }
 
-/// Computes (P(x) - value) * k and saves the result into the accumulator.
-fn acc_trace_poly<F, E>(accumulator: &mut [E], poly: &[F], value: E, k: E)
-where
-    F: FieldElement,
-    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
-{
-    mul_acc(accumulator, poly, k);
-    let adjusted_tz = value * k;
-    accumulator[0] -= adjusted_tz;
+/// Computes sum(P_i(x) * k_i) for all polynomials P_i in `base_polys` followed by `ext_polys`,
+/// and adds the result into `result`.
+///
+/// The coefficients are processed in a single pass: the result is split into batches (one per
+/// thread when `concurrent` feature is enabled), and every tile of coefficients in a batch is
+/// updated from all polynomials before moving on to the next tile.
+fn combine_polys<E: FieldElement>(
+    result: &mut [E],
+    base_polys: &[&[E::BaseField]],
+    ext_polys: &[&[E]],
+    coefficients: &[E],
+) {
+    debug_assert_eq!(base_polys.len() + ext_polys.len(), coefficients.len());
+    let (base_coefficients, ext_coefficients) = coefficients.split_at(base_polys.len());
+
+    batch_iter_mut!(result, 1024, |batch: &mut [E], batch_offset: usize| {
+        for (i, tile) in batch.chunks_mut(TILE_SIZE).enumerate() {
+            let start = batch_offset + i * TILE_SIZE;
+            let end = start + tile.len();
+            for (poly, &k) in base_polys.iter().zip(base_coefficients) {
+                for (acc, &c) in tile.iter_mut().zip(&poly[start..end]) {
+                    *acc += k.mul_base(c);
+                }
+            }
+            for (poly, &k) in ext_polys.iter().zip(ext_coefficients) {
+                for (acc, &c) in tile.iter_mut().zip(&poly[start..end]) {
+                    *acc += k * c;
+                }
+            }
+        }
+    });
+}
+
+/// Returns sum(values_i * k_i) for all i.
+fn linear_combination<E: FieldElement>(values: &[E], coefficients: &[E]) -> E {
+    values.iter().zip(coefficients).fold(E::ZERO, |acc, (&v, &k)| acc + v * k)
 }
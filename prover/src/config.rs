@@ -0,0 +1,123 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ProvingKey;
+use air::proof::stream::ProofSection;
+use math::StarkField;
+use utils::{Box, ByteWriter};
+
+// TYPE ALIASES
+// ================================================================================================
+
+type CheckpointHandler<'a> = Box<dyn FnMut(&[u8]) + 'a>;
+type SectionHandler<'a> = Box<dyn FnMut(ProofSection, &[u8]) + 'a>;
+
+// PROVING CONFIG
+// ================================================================================================
+
+/// Optional inputs and callbacks for a single invocation of
+/// [Prover::prove_with_config()](crate::Prover::prove_with_config).
+///
+/// A default config does not set any of the options below; in this case, proof generation is
+/// identical to [Prover::prove()](crate::Prover::prove). Options can be combined freely, and
+/// setting any of them does not change the generated proof:
+/// * A [ProvingKey] supplies data precomputed for the computation and its trace length, which
+///   otherwise would be re-computed for every proof.
+/// * A checkpoint handler receives a serialized [TraceCheckpoint](crate::TraceCheckpoint) once
+///   the prover has committed to the execution trace.
+/// * A proof stream receives every section of the proof as soon as the prover has finalized it.
+pub struct ProvingConfig<'a, B: StarkField> {
+    proving_key: Option<&'a ProvingKey<B>>,
+    on_checkpoint: Option<CheckpointHandler<'a>>,
+    on_section: Option<SectionHandler<'a>>,
+}
+
+impl<'a, B: StarkField> ProvingConfig<'a, B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new config with none of the options set.
+    pub fn new() -> Self {
+        Self {
+            proving_key: None,
+            on_checkpoint: None,
+            on_section: None,
+        }
+    }
+
+    // BUILDER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the proving key from which the domain twiddles, periodic column values, and
+    /// constraint divisor evaluations are taken, rather than being re-computed for the proof.
+    ///
+    /// Proof generation fails if the key was built for a different trace length, trace layout,
+    /// or proof options than the ones defined by the trace and the prover.
+    pub fn with_proving_key(mut self, proving_key: &'a ProvingKey<B>) -> Self {
+        self.proving_key = Some(proving_key);
+        self
+    }
+
+    /// Sets the closure to which a checkpoint of the prover state is passed once the prover has
+    /// committed to the execution trace.
+    ///
+    /// The checkpoint is a serialized [TraceCheckpoint](crate::TraceCheckpoint). If proof
+    /// generation is interrupted after the checkpoint has been persisted (e.g., written to disk),
+    /// it can be resumed from the checkpoint via
+    /// [Prover::resume_proof()](crate::Prover::resume_proof), possibly on a different machine.
+    /// The checkpoint contains the entire execution trace, and thus, reveals all secret inputs of
+    /// the computation.
+    pub fn with_checkpoint_handler<F>(mut self, on_checkpoint: F) -> Self
+    where
+        F: FnMut(&[u8]) + 'a,
+    {
+        self.on_checkpoint = Some(Box::new(on_checkpoint));
+        self
+    }
+
+    /// Sets the writer into which the proof is written in the streamed encoding.
+    ///
+    /// Every section of the proof (i.e., the context, the commitments, the out-of-domain frame,
+    /// and the queries) is written into `target` as soon as the prover has finalized it (see
+    /// [proof::stream](air::proof::stream) module for the layout); thus, a writer which forwards
+    /// the written bytes to a remote party can upload the proof while the later phases of proof
+    /// generation are still running. The written stream is identical to the stream returned from
+    /// [StarkProof::to_stream_bytes()](air::proof::StarkProof::to_stream_bytes) for the
+    /// generated proof. If proof generation fails, sections written before the error occurred
+    /// do not form a complete proof and should be discarded.
+    pub fn with_proof_stream<W: ByteWriter>(mut self, target: &'a mut W) -> Self {
+        self.on_section = Some(Box::new(move |section: ProofSection, payload: &[u8]| {
+            section.write_section(target, payload)
+        }));
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the proving key set for this config, if any.
+    pub fn proving_key(&self) -> Option<&'a ProvingKey<B>> {
+        self.proving_key
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Splits this config into the proving key, the checkpoint handler, and the section handler.
+    pub(crate) fn into_parts(
+        self,
+    ) -> (
+        Option<&'a ProvingKey<B>>,
+        Option<CheckpointHandler<'a>>,
+        Option<SectionHandler<'a>>,
+    ) {
+        (self.proving_key, self.on_checkpoint, self.on_section)
+    }
+}
+
+impl<B: StarkField> Default for ProvingConfig<'_, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,11 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::RowMatrix;
+use super::{ColMatrix, RowMatrix, StarkDomain};
+use crate::{matrix::LdeMatrix, ProverError};
 use air::proof::Queries;
 use crypto::{ElementHasher, MerkleTree};
 use math::FieldElement;
-use utils::collections::Vec;
+
+#[cfg(feature = "std")]
+use crate::matrix::DiskMatrix;
 
 // CONSTRAINT COMMITMENT
 // ================================================================================================
@@ -18,8 +21,12 @@ use utils::collections::Vec;
 /// * Evaluations of composition polynomial columns over the LDE domain.
 /// * Merkle tree where each leaf in the tree corresponds to a row in the composition polynomial
 ///   evaluation matrix.
+///
+/// In the low-memory mode, the evaluations can be discarded once the commitment is built; in
+/// this case, the evaluations at queried positions are re-computed from the composition
+/// polynomial columns.
 pub struct ConstraintCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    evaluations: RowMatrix<E>,
+    evaluations: LdeMatrix<E>,
     commitment: MerkleTree<H>,
 }
 
@@ -33,7 +40,22 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
             "number of rows in constraint evaluation matrix must be the same as number of leaves in constraint commitment"
         );
         ConstraintCommitment {
-            evaluations,
+            evaluations: LdeMatrix::Evaluations(evaluations),
+            commitment,
+        }
+    }
+
+    /// Creates a new constraint evaluation commitment from the provided composition polynomial
+    /// evaluations stored on disk and the corresponding Merkle tree commitment.
+    #[cfg(feature = "std")]
+    pub(crate) fn new_on_disk(evaluations: DiskMatrix<E>, commitment: MerkleTree<H>) -> Self {
+        assert_eq!(
+            evaluations.num_rows(),
+            commitment.leaves().len(),
+            "number of rows in constraint evaluation matrix must be the same as number of leaves in constraint commitment"
+        );
+        ConstraintCommitment {
+            evaluations: LdeMatrix::Disk(evaluations),
             commitment,
         }
     }
@@ -49,9 +71,19 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
         self.commitment.depth()
     }
 
+    /// Discards composition polynomial evaluations, keeping only the specified columns of the
+    /// composition polynomial (in coefficient form) from which evaluations at queried positions
+    /// can be re-computed.
+    pub fn discard_evaluations(&mut self, polys: ColMatrix<E>, domain: &StarkDomain<E::BaseField>) {
+        self.evaluations.discard_evaluations(polys, domain);
+    }
+
     /// Returns constraint evaluations at the specified positions along with Merkle authentication
     /// paths from the root of the commitment to these evaluations.
-    pub fn query(self, positions: &[usize]) -> Queries {
+    ///
+    /// # Errors
+    /// Returns an error if the evaluations are stored on disk and could not be read.
+    pub fn query(self, positions: &[usize]) -> Result<Queries, ProverError> {
         // build Merkle authentication paths to the leaves specified by positions
         let merkle_proof = self
             .commitment
@@ -59,12 +91,8 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
             .expect("failed to generate a Merkle proof for constraint queries");
 
         // determine a set of evaluations corresponding to each position
-        let mut evaluations = Vec::new();
-        for &position in positions {
-            let row = self.evaluations.row(position).to_vec();
-            evaluations.push(row);
-        }
+        let evaluations = self.evaluations.read_rows(positions)?;
 
-        Queries::new(merkle_proof, evaluations)
+        Ok(Queries::new(merkle_proof, evaluations))
     }
 }
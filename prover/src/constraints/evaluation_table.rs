@@ -19,7 +19,7 @@ use utils::iterators::*;
 // CONSTANTS
 // ================================================================================================
 
-const MIN_FRAGMENT_SIZE: usize = 16;
+pub(crate) const MIN_FRAGMENT_SIZE: usize = 16;
 
 // CONSTRAINT EVALUATION TABLE
 // ================================================================================================
@@ -30,7 +30,7 @@ pub struct ConstraintEvaluationTable<'a, E: FieldElement> {
     domain: &'a StarkDomain<E::BaseField>,
 
     #[cfg(debug_assertions)]
-    main_transition_evaluations: Vec<Vec<E::BaseField>>,
+    main_transition_evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
     aux_transition_evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
@@ -238,7 +238,7 @@ pub struct EvaluationTableFragment<'a, E: FieldElement> {
     evaluations: Vec<&'a mut [E]>,
 
     #[cfg(debug_assertions)]
-    tm_evaluations: Vec<&'a mut [E::BaseField]>,
+    tm_evaluations: Vec<&'a mut [E]>,
     #[cfg(debug_assertions)]
     ta_evaluations: Vec<&'a mut [E]>,
 }
@@ -268,14 +268,17 @@ impl<'a, E: FieldElement> EvaluationTableFragment<'a, E> {
 
     /// Updates transition evaluations row with the provided data; available only in debug mode.
     #[cfg(debug_assertions)]
-    pub fn update_transition_evaluations(
+    pub fn update_transition_evaluations<F>(
         &mut self,
         row_idx: usize,
-        main_evaluations: &[E::BaseField],
+        main_evaluations: &[F],
         aux_evaluations: &[E],
-    ) {
+    ) where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: From<F>,
+    {
         for (column, &value) in self.tm_evaluations.iter_mut().zip(main_evaluations) {
-            column[row_idx] = value;
+            column[row_idx] = E::from(value);
         }
         for (column, &value) in self.ta_evaluations.iter_mut().zip(aux_evaluations) {
             column[row_idx] = value;
@@ -323,8 +326,16 @@ fn acc_column<E: FieldElement>(
     let numerator = divisor.numerator();
     assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
 
-    // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b)
-    let z = get_inv_evaluation(divisor, domain);
+    // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b),
+    // unless these evaluations have already been precomputed for the domain
+    let inv_evaluations;
+    let z = match domain.get_divisor_inv_evaluations(divisor) {
+        Some(z) => z,
+        None => {
+            inv_evaluations = get_inv_evaluation(divisor, domain);
+            &inv_evaluations
+        }
+    };
 
     // divide column values by the divisor; for boundary constraints this computed simply as
     // multiplication of column value by the inverse of divisor numerator; for transition
@@ -367,7 +378,7 @@ fn acc_column<E: FieldElement>(
 }
 
 /// Computes evaluations of the divisor's numerator over the domain of the specified size and offset.
-fn get_inv_evaluation<B: StarkField>(
+pub(crate) fn get_inv_evaluation<B: StarkField>(
     divisor: &ConstraintDivisor<B>,
     domain: &StarkDomain<B>,
 ) -> Vec<B> {
@@ -375,16 +386,6 @@ fn get_inv_evaluation<B: StarkField>(
     let a = numerator[0].0 as u64; // numerator degree
     let b = numerator[0].1;
 
-    // this guarantees that we can use get_ce_x_power_at() below but limits execution trace length
-    // to be at most 2^32. in the future, we should revisit this to allow execution traces of
-    // greater length.
-    assert!(
-        a <= u32::MAX as u64,
-        "constraint divisor numerator degree cannot exceed {}, but was {}",
-        u32::MAX,
-        a
-    );
-
     let n = domain.ce_domain_size() / a as usize;
     let domain_offset_exp = domain.offset().exp(a.into());
 
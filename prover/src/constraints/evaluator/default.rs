@@ -5,15 +5,18 @@
 
 use super::{
     super::EvaluationTableFragment, BoundaryConstraints, CompositionPolyTrace,
-    ConstraintEvaluationTable, ConstraintEvaluator, PeriodicValueTable, StarkDomain, TraceLde,
+    ConstraintEvaluationTable, ConstraintEvaluator, PeriodicValueTable, ProverError, StarkDomain,
+    TraceLde,
 };
 use air::{
     Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame,
     TransitionConstraints,
 };
-use math::FieldElement;
+use math::{ExtensionOf, FieldElement};
 use utils::iter_mut;
 
+#[cfg(feature = "concurrent")]
+use super::super::evaluation_table::MIN_FRAGMENT_SIZE;
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
 
@@ -34,13 +37,15 @@ const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 ///
 /// When `concurrent` feature is enabled, the extended execution trace is split into sets of
 /// sequential evaluation frames (called fragments), and frames in each fragment are evaluated
-/// in separate threads.
+/// in separate threads. By default, one fragment is created per thread of the current thread
+/// pool; the size of fragments can be set via
+/// [with_fragment_size()](DefaultConstraintEvaluator::with_fragment_size).
 pub struct DefaultConstraintEvaluator<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> {
     air: &'a A,
     boundary_constraints: BoundaryConstraints<E>,
     transition_constraints: TransitionConstraints<E>,
     aux_rand_elements: AuxTraceRandElements<E>,
-    periodic_values: PeriodicValueTable<E::BaseField>,
+    fragment_size: Option<usize>,
 }
 
 impl<'a, A, E> ConstraintEvaluator<E> for DefaultConstraintEvaluator<'a, A, E>
@@ -54,13 +59,24 @@ where
         self,
         trace: &T,
         domain: &StarkDomain<<E as FieldElement>::BaseField>,
-    ) -> CompositionPolyTrace<E> {
+    ) -> Result<CompositionPolyTrace<E>, ProverError> {
         assert_eq!(
             trace.trace_len(),
             domain.lde_domain_size(),
             "extended trace length is not consistent with evaluation domain"
         );
 
+        // use periodic column values precomputed for the domain (e.g., as a part of a proving key);
+        // if there are none, build periodic value table now
+        let periodic_table;
+        let periodic_values = match domain.periodic_values() {
+            Some(periodic_values) => periodic_values,
+            None => {
+                periodic_table = PeriodicValueTable::new(self.air);
+                &periodic_table
+            }
+        };
+
         // build a list of constraint divisors; currently, all transition constraints have the same
         // divisor which we put at the front of the list; boundary constraint divisors are appended
         // after that
@@ -77,30 +93,39 @@ where
             ConstraintEvaluationTable::<E>::new(domain, divisors, &self.transition_constraints);
 
         // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
-        // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
-        // then don't bother with concurrent evaluation
+        // to evaluate them into multiple threads; if the size of fragments was not specified, use
+        // one fragment per thread unless the constraint evaluation domain is small, then don't
+        // bother with concurrent evaluation
 
         #[cfg(not(feature = "concurrent"))]
         let num_fragments = 1;
 
         #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
-            rayon::current_num_threads().next_power_of_two()
-        } else {
-            1
+        let num_fragments = match self.fragment_size {
+            Some(fragment_size) => {
+                let fragment_size = fragment_size.next_power_of_two().max(MIN_FRAGMENT_SIZE);
+                (domain.ce_domain_size() / fragment_size).max(1)
+            }
+            None if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE => {
+                rayon::current_num_threads().next_power_of_two()
+            }
+            None => 1,
         };
 
         // evaluate constraints for each fragment; if the trace consist of multiple segments
         // we evaluate constraints for all segments. otherwise, we evaluate constraints only
-        // for the main segment.
+        // for the main segment. if some columns of the main segment hold extension field
+        // elements, main transition constraints are evaluated over the extension field.
         let mut fragments = evaluation_table.fragments(num_fragments);
-        iter_mut!(fragments).for_each(|fragment| {
-            if self.air.trace_info().is_multi_segment() {
-                self.evaluate_fragment_full(trace, domain, fragment);
+        iter_mut!(fragments).try_for_each(|fragment| {
+            if self.air.context().num_main_extension_columns() > 0 {
+                self.evaluate_fragment_ext(trace, domain, periodic_values, fragment)
+            } else if self.air.trace_info().is_multi_segment() {
+                self.evaluate_fragment_full(trace, domain, periodic_values, fragment)
             } else {
-                self.evaluate_fragment_main(trace, domain, fragment);
+                self.evaluate_fragment_main(trace, domain, periodic_values, fragment)
             }
-        });
+        })?;
 
         // when in debug mode, make sure expected transition constraint degrees align with
         // actual degrees we got during constraint evaluation
@@ -108,7 +133,7 @@ where
         evaluation_table.validate_transition_degrees();
 
         // combine all evaluations into a single column and return
-        evaluation_table.combine()
+        Ok(evaluation_table.combine())
     }
 }
 
@@ -131,9 +156,6 @@ where
         let transition_constraints =
             air.get_transition_constraints(&composition_coefficients.transition);
 
-        // build periodic value table
-        let periodic_values = PeriodicValueTable::new(air);
-
         // build boundary constraint groups; these will be used to evaluate and compose boundary
         // constraint evaluations.
         let boundary_constraints =
@@ -144,10 +166,23 @@ where
             boundary_constraints,
             transition_constraints,
             aux_rand_elements,
-            periodic_values,
+            fragment_size: None,
         }
     }
 
+    /// Returns a new evaluator which splits the constraint evaluation domain into fragments of
+    /// the specified number of rows when constraints are evaluated concurrently.
+    ///
+    /// Smaller fragments balance the work between threads better, while larger fragments reduce
+    /// scheduling overhead; this can be used together with `Prover::prove_in_pool()` to tune CPU
+    /// usage of the prover. The size is rounded up to a power of two, and is at least 16 rows.
+    /// This has an effect only when `concurrent` feature is enabled, and does not affect the
+    /// result of constraint evaluation.
+    pub fn with_fragment_size(mut self, fragment_size: usize) -> Self {
+        self.fragment_size = Some(fragment_size);
+        self
+    }
+
     // EVALUATION HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -158,8 +193,9 @@ where
         &self,
         trace: &T,
         domain: &StarkDomain<A::BaseField>,
+        periodic_values: &PeriodicValueTable<E::BaseField>,
         fragment: &mut EvaluationTableFragment<E>,
-    ) {
+    ) -> Result<(), ProverError> {
         // initialize buffers to hold trace values and evaluation results at each step;
         let mut main_frame = EvaluationFrame::new(trace.trace_layout().main_trace_width());
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
@@ -176,11 +212,13 @@ where
             // read current and next rows from the trace into the buffer; data in the trace
             // table is extended over the LDE domain, so, we need to convert step in constraint
             // evaluation domain, into a step in LDE domain, in case these domains are different
-            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
+            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame)?;
 
             // evaluate transition constraints and save the merged result the first slot of the
             // evaluations buffer
-            evaluations[0] = self.evaluate_main_transition(&main_frame, step, &mut t_evaluations);
+            let periodic_row = periodic_values.get_row(step);
+            evaluations[0] =
+                self.evaluate_main_transition(&main_frame, periodic_row, &mut t_evaluations);
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
@@ -199,6 +237,8 @@ where
             // record the result in the evaluation table
             fragment.update_row(i, &evaluations);
         }
+
+        Ok(())
     }
 
     /// Evaluates constraints for a single fragment of the evaluation table.
@@ -209,8 +249,9 @@ where
         &self,
         trace: &T,
         domain: &StarkDomain<A::BaseField>,
+        periodic_values: &PeriodicValueTable<E::BaseField>,
         fragment: &mut EvaluationTableFragment<E>,
-    ) {
+    ) -> Result<(), ProverError> {
         // initialize buffers to hold trace values and evaluation results at each step
         let mut main_frame = EvaluationFrame::new(trace.trace_layout().main_trace_width());
         let mut aux_frame = EvaluationFrame::new(trace.trace_layout().aux_trace_width());
@@ -226,15 +267,21 @@ where
             let step = i + fragment.offset();
 
             // read both the main and the auxiliary evaluation frames from the trace
-            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
-            trace.read_aux_trace_frame_into(step << lde_shift, &mut aux_frame);
+            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame)?;
+            trace.read_aux_trace_frame_into(step << lde_shift, &mut aux_frame)?;
 
             // evaluate transition constraints and save the merged result the first slot of the
             // evaluations buffer; we evaluate and compose constraints in the same function, we
             // can just add up the results of evaluating main and auxiliary constraints.
-            evaluations[0] = self.evaluate_main_transition(&main_frame, step, &mut tm_evaluations);
-            evaluations[0] +=
-                self.evaluate_aux_transition(&main_frame, &aux_frame, step, &mut ta_evaluations);
+            let periodic_row = periodic_values.get_row(step);
+            evaluations[0] =
+                self.evaluate_main_transition(&main_frame, periodic_row, &mut tm_evaluations);
+            evaluations[0] += self.evaluate_aux_transition(
+                &main_frame,
+                &aux_frame,
+                periodic_row,
+                &mut ta_evaluations,
+            );
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
@@ -255,28 +302,118 @@ where
             // record the result in the evaluation table
             fragment.update_row(i, &evaluations);
         }
+
+        Ok(())
+    }
+
+    /// Evaluates constraints for a single fragment of the evaluation table.
+    ///
+    /// This is used when some columns of the main segment of the execution trace hold extension
+    /// field elements: coordinates of these elements are combined into extension field elements,
+    /// and transition constraints are evaluated over the resulting frames. Constraints are
+    /// evaluated over all segments of the execution trace.
+    fn evaluate_fragment_ext<T: TraceLde<E>>(
+        &self,
+        trace: &T,
+        domain: &StarkDomain<A::BaseField>,
+        periodic_values: &PeriodicValueTable<E::BaseField>,
+        fragment: &mut EvaluationTableFragment<E>,
+    ) -> Result<(), ProverError> {
+        let context = self.air.context();
+        let is_multi_segment = self.air.trace_info().is_multi_segment();
+
+        // initialize buffers to hold trace values and evaluation results at each step
+        let mut main_frame = EvaluationFrame::new(trace.trace_layout().main_trace_width());
+        let mut ext_main_frame = EvaluationFrame::<E>::new(context.main_frame_width());
+        let mut aux_frame = if is_multi_segment {
+            Some(EvaluationFrame::new(trace.trace_layout().aux_trace_width()))
+        } else {
+            None
+        };
+        let mut ext_periodic_row = vec![E::ZERO; self.air.get_periodic_column_polys().len()];
+        let mut tm_evaluations = vec![E::ZERO; self.num_main_transition_constraints()];
+        let mut ta_evaluations = vec![E::ZERO; self.num_aux_transition_constraints()];
+        let mut evaluations = vec![E::ZERO; fragment.num_columns()];
+
+        // this will be used to convert steps in constraint evaluation domain to steps in
+        // LDE domain
+        let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
+
+        for i in 0..fragment.num_rows() {
+            let step = i + fragment.offset();
+
+            // read the main evaluation frame from the trace and combine coordinates of extension
+            // field columns into extension field elements
+            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame)?;
+            ext_main_frame.read_extension_columns(&main_frame, context.num_main_base_columns());
+
+            // evaluate transition constraints and save the merged result the first slot of the
+            // evaluations buffer
+            let periodic_row = periodic_values.get_row(step);
+            for (ext_value, &value) in ext_periodic_row.iter_mut().zip(periodic_row) {
+                *ext_value = E::from(value);
+            }
+            evaluations[0] = self.evaluate_main_transition_ext(
+                &ext_main_frame,
+                &ext_periodic_row,
+                &mut tm_evaluations,
+            );
+            if let Some(aux_frame) = aux_frame.as_mut() {
+                trace.read_aux_trace_frame_into(step << lde_shift, aux_frame)?;
+                evaluations[0] += self.evaluate_aux_transition::<E>(
+                    &ext_main_frame,
+                    aux_frame,
+                    &ext_periodic_row,
+                    &mut ta_evaluations,
+                );
+            }
+
+            // when in debug mode, save transition constraint evaluations
+            #[cfg(debug_assertions)]
+            fragment.update_transition_evaluations(i, &tm_evaluations, &ta_evaluations);
+
+            // evaluate boundary constraints against the committed main segment; the results go
+            // into remaining slots of the evaluations buffer
+            let main_state = main_frame.current();
+            match aux_frame.as_ref() {
+                Some(aux_frame) => self.boundary_constraints.evaluate_all(
+                    main_state,
+                    aux_frame.current(),
+                    domain,
+                    step,
+                    &mut evaluations[1..],
+                ),
+                None => self.boundary_constraints.evaluate_main(
+                    main_state,
+                    domain,
+                    step,
+                    &mut evaluations[1..],
+                ),
+            }
+
+            // record the result in the evaluation table
+            fragment.update_row(i, &evaluations);
+        }
+
+        Ok(())
     }
 
     // TRANSITION CONSTRAINT EVALUATORS
     // --------------------------------------------------------------------------------------------
 
-    /// Evaluates transition constraints of the main execution trace at the specified step of the
-    /// constraint evaluation domain.
+    /// Evaluates transition constraints of the main execution trace at a step of the constraint
+    /// evaluation domain.
     ///
-    /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
-    /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
+    /// `periodic_values` are the values of periodic columns at this step.
     fn evaluate_main_transition(
         &self,
         main_frame: &EvaluationFrame<E::BaseField>,
-        step: usize,
+        periodic_values: &[E::BaseField],
         evaluations: &mut [E::BaseField],
     ) -> E {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::BaseField::ZERO);
 
-        // get periodic values at the evaluation step
-        let periodic_values = self.periodic_values.get_row(step);
-
         // evaluate transition constraints over the main segment of the execution trace and save
         // the results into evaluations buffer
         self.air.evaluate_transition(main_frame, periodic_values, evaluations);
@@ -289,23 +426,48 @@ where
             .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef.mul_base(const_eval))
     }
 
-    /// Evaluates all transition constraints (i.e., for main and auxiliary trace segments) at the
-    /// specified step of the constraint evaluation domain.
+    /// Evaluates transition constraints of the main execution trace at a step of the constraint
+    /// evaluation domain over the extension field.
     ///
-    /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
-    /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
-    fn evaluate_aux_transition(
+    /// This is used when some columns of the main execution trace hold extension field elements;
+    /// `periodic_values` are the values of periodic columns at this step.
+    fn evaluate_main_transition_ext(
         &self,
-        main_frame: &EvaluationFrame<E::BaseField>,
-        aux_frame: &EvaluationFrame<E>,
-        step: usize,
+        main_frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
         evaluations: &mut [E],
     ) -> E {
-        // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::ZERO);
 
-        // get periodic values at the evaluation step
-        let periodic_values = self.periodic_values.get_row(step);
+        // evaluate transition constraints over the main segment of the execution trace and save
+        // the results into evaluations buffer
+        self.air.evaluate_transition(main_frame, periodic_values, evaluations);
+
+        // merge transition constraint evaluations into a single value and return it;
+        // we can do this here because all transition constraints have the same divisor.
+        evaluations
+            .iter()
+            .zip(self.transition_constraints.main_constraint_coef().iter())
+            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef * const_eval)
+    }
+
+    /// Evaluates all transition constraints (i.e., for main and auxiliary trace segments) at a
+    /// step of the constraint evaluation domain.
+    ///
+    /// `periodic_values` are the values of periodic columns at this step.
+    fn evaluate_aux_transition<F>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        evaluations: &mut [E],
+    ) -> E
+    where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: ExtensionOf<F>,
+    {
+        // TODO: use a more efficient way to zero out memory
+        evaluations.fill(E::ZERO);
 
         // evaluate transition constraints over auxiliary trace segments and save the results into
         // evaluations buffer
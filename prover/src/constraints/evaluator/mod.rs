@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::TraceLde, CompositionPolyTrace, ConstraintEvaluationTable, StarkDomain};
+use super::{
+    super::{ProverError, TraceLde},
+    CompositionPolyTrace, ConstraintEvaluationTable, StarkDomain,
+};
 use air::Air;
 use math::FieldElement;
 
@@ -14,7 +17,7 @@ mod boundary;
 use boundary::BoundaryConstraints;
 
 mod periodic_table;
-use periodic_table::PeriodicValueTable;
+pub(crate) use periodic_table::PeriodicValueTable;
 
 // CONSTRAINT EVALUATOR TRAIT
 // ================================================================================================
@@ -33,9 +36,12 @@ pub trait ConstraintEvaluator<E: FieldElement> {
     ///
     /// Constraints are evaluated over a constraint evaluation domain. This is an optimization
     /// because constraint evaluation domain can be many times smaller than the full LDE domain.
+    ///
+    /// # Errors
+    /// Returns an error if evaluation frames could not be read from the extended execution trace.
     fn evaluate<T: TraceLde<E>>(
         self,
         trace: &T,
         domain: &StarkDomain<E::BaseField>,
-    ) -> CompositionPolyTrace<E>;
+    ) -> Result<CompositionPolyTrace<E>, ProverError>;
 }
@@ -128,7 +128,7 @@ mod tests {
                 domain
                     .iter()
                     .map(|&x| {
-                        let x = x.exp((num_cycles as u32).into());
+                        let x = x.exp((num_cycles as u64).into());
                         polynom::eval(poly, x)
                     })
                     .collect::<Vec<_>>()
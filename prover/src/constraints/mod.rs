@@ -6,12 +6,14 @@
 use super::{ColMatrix, ConstraintDivisor, RowMatrix, StarkDomain};
 
 mod evaluator;
+pub(crate) use evaluator::PeriodicValueTable;
 pub use evaluator::{ConstraintEvaluator, DefaultConstraintEvaluator};
 
 mod composition_poly;
 pub use composition_poly::{CompositionPoly, CompositionPolyTrace};
 
 mod evaluation_table;
+pub(crate) use evaluation_table::get_inv_evaluation;
 pub use evaluation_table::{ConstraintEvaluationTable, EvaluationTableFragment};
 
 mod commitment;
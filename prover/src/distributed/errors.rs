@@ -0,0 +1,52 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+// WORKER RESPONSE ERROR
+// ================================================================================================
+/// Represents an error returned by the coordinator when responses received from LDE workers are
+/// inconsistent with the [WorkerConfig](super::WorkerConfig) or with the requested positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerResponseError {
+    /// This error occurs when responses were received from a number of workers different from
+    /// the number of workers specified by the config. The values hold the expected and the
+    /// actual number of workers.
+    UnexpectedNumWorkers(usize, usize),
+    /// This error occurs when a worker returned a number of row hashes different from the number
+    /// of LDE rows assigned to it. The values hold the index of the worker, and the expected and
+    /// the actual number of hashes.
+    UnexpectedNumRowHashes(usize, usize, usize),
+    /// This error occurs when a worker returned a number of rows different from the number of
+    /// positions requested from it. The values hold the index of the worker, and the expected and
+    /// the actual number of rows.
+    UnexpectedNumRows(usize, usize, usize),
+    /// This error occurs when a row returned by a worker does not hash to the leaf committed to
+    /// at the requested position. The values hold the index of the worker and the position.
+    RowDoesNotMatchCommitment(usize, usize),
+}
+
+impl fmt::Display for WorkerResponseError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedNumWorkers(expected, actual) => {
+                write!(f, "expected responses from {expected} workers, but received from {actual}")
+            }
+            Self::UnexpectedNumRowHashes(worker, expected, actual) => {
+                write!(f, "expected {expected} row hashes from worker {worker}, but received {actual}")
+            }
+            Self::UnexpectedNumRows(worker, expected, actual) => {
+                write!(f, "expected {expected} rows from worker {worker}, but received {actual}")
+            }
+            Self::RowDoesNotMatchCommitment(worker, position) => {
+                write!(f, "row at position {position} received from worker {worker} does not match the commitment")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WorkerResponseError {}
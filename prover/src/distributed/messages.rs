@@ -0,0 +1,307 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ColMatrix;
+use crypto::Digest;
+use math::{FieldElement, StarkField};
+use utils::{
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable,
+};
+
+// WORKER CONFIG
+// ================================================================================================
+
+/// Parameters of a distributed trace commitment shared by the coordinator and all workers.
+///
+/// The LDE domain of size `trace_length * blowup_factor` is split among `num_workers` workers
+/// such that worker `i` is responsible for all LDE rows at positions `p` where
+/// `p % num_workers == i`. These rows form a coset of a multiplicative subgroup, and thus, a
+/// worker can compute its part of the LDE using FFT without access to the rest of the domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerConfig<B: StarkField> {
+    num_workers: usize,
+    trace_length: usize,
+    blowup_factor: usize,
+    domain_offset: B,
+}
+
+impl<B: StarkField> WorkerConfig<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new worker config for the specified parameters.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_workers`, `trace_length`, or `blowup_factor` is not a power of two.
+    /// * `num_workers` is greater than `blowup_factor`.
+    /// * `trace_length` is smaller than 2.
+    pub fn new(
+        num_workers: usize,
+        trace_length: usize,
+        blowup_factor: usize,
+        domain_offset: B,
+    ) -> Self {
+        assert!(num_workers.is_power_of_two(), "number of workers must be a power of 2");
+        assert!(trace_length.is_power_of_two(), "trace length must be a power of 2");
+        assert!(trace_length > 1, "trace length must be greater than 1");
+        assert!(blowup_factor.is_power_of_two(), "blowup factor must be a power of 2");
+        assert!(
+            num_workers <= blowup_factor,
+            "number of workers cannot be greater than blowup factor {blowup_factor}, but was {num_workers}"
+        );
+        Self {
+            num_workers,
+            trace_length,
+            blowup_factor,
+            domain_offset,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of workers among which the LDE domain is split.
+    pub fn num_workers(&self) -> usize {
+        self.num_workers
+    }
+
+    /// Returns the number of rows in the execution trace.
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    /// Returns the blowup factor used to extend the execution trace.
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+
+    /// Returns the offset of the LDE domain.
+    pub fn domain_offset(&self) -> B {
+        self.domain_offset
+    }
+
+    /// Returns the size of the LDE domain.
+    pub fn lde_domain_size(&self) -> usize {
+        self.trace_length * self.blowup_factor
+    }
+
+    /// Returns the number of LDE rows each worker is responsible for.
+    pub fn rows_per_worker(&self) -> usize {
+        self.lde_domain_size() / self.num_workers
+    }
+
+    /// Returns the index of the worker responsible for the LDE row at the specified position.
+    pub fn worker_for_position(&self, position: usize) -> usize {
+        position % self.num_workers
+    }
+}
+
+impl<B: StarkField> Serializable for WorkerConfig<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.num_workers.ilog2() as u8);
+        target.write_u8(self.trace_length.ilog2() as u8);
+        target.write_u8(self.blowup_factor.ilog2() as u8);
+        self.domain_offset.write_into(target);
+    }
+}
+
+impl<B: StarkField> Deserializable for WorkerConfig<B> {
+    /// Reads a worker config from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid worker config could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_workers = source.read_u8()? as u32;
+        let trace_length = source.read_u8()? as u32;
+        let blowup_factor = source.read_u8()? as u32;
+        if trace_length == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "trace length must be greater than 1".to_string(),
+            ));
+        }
+        if num_workers > blowup_factor {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of workers cannot be greater than blowup factor 2^{blowup_factor}, but was 2^{num_workers}"
+            )));
+        }
+        if trace_length + blowup_factor > B::TWO_ADICITY {
+            return Err(DeserializationError::InvalidValue(format!(
+                "LDE domain cannot be larger than 2^{}, but was 2^{}",
+                B::TWO_ADICITY,
+                trace_length + blowup_factor
+            )));
+        }
+        let domain_offset = B::read_from(source)?;
+
+        Ok(Self {
+            num_workers: 1 << num_workers,
+            trace_length: 1 << trace_length,
+            blowup_factor: 1 << blowup_factor,
+            domain_offset,
+        })
+    }
+}
+
+// WORKER REQUEST
+// ================================================================================================
+
+const EXTEND_SEGMENT_TAG: u8 = 1;
+const READ_ROWS_TAG: u8 = 2;
+const SEGMENT_HASHES_TAG: u8 = 1;
+const ROWS_TAG: u8 = 2;
+
+/// A request sent by the coordinator to a worker.
+///
+/// Requests carry elements of a single field `E`; thus, requests for the main trace segment
+/// (defined over the base field) and for auxiliary trace segments (which may be defined over an
+/// extension field) are handled by separate [LdeWorker](super::LdeWorker) instances.
+#[derive(Debug, Clone)]
+pub enum WorkerRequest<E: FieldElement> {
+    /// Evaluate the provided trace segment polynomials (in coefficient form) over the part of
+    /// the LDE domain assigned to the worker, hash the resulting rows, and keep the rows for
+    /// subsequent reads.
+    ExtendSegment { segment: usize, polys: ColMatrix<E> },
+    /// Read rows of a previously extended trace segment at the specified LDE positions; all
+    /// positions must be assigned to the worker.
+    ReadRows {
+        segment: usize,
+        positions: Vec<usize>,
+    },
+}
+
+impl<E: FieldElement> Serializable for WorkerRequest<E> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::ExtendSegment { segment, polys } => {
+                target.write_u8(EXTEND_SEGMENT_TAG);
+                target.write_u8(*segment as u8);
+                polys.write_into(target);
+            }
+            Self::ReadRows { segment, positions } => {
+                target.write_u8(READ_ROWS_TAG);
+                target.write_u8(*segment as u8);
+                target.write_u32(positions.len() as u32);
+                for &position in positions.iter() {
+                    target.write_u64(position as u64);
+                }
+            }
+        }
+    }
+}
+
+impl<E: FieldElement> Deserializable for WorkerRequest<E> {
+    /// Reads a worker request from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid worker request could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            EXTEND_SEGMENT_TAG => {
+                let segment = source.read_u8()? as usize;
+                let polys = ColMatrix::read_from(source)?;
+                Ok(Self::ExtendSegment { segment, polys })
+            }
+            READ_ROWS_TAG => {
+                let segment = source.read_u8()? as usize;
+                let positions = read_positions(source)?;
+                Ok(Self::ReadRows { segment, positions })
+            }
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid worker request tag: {tag}"
+            ))),
+        }
+    }
+}
+
+// WORKER RESPONSE
+// ================================================================================================
+
+/// A response sent by a worker back to the coordinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerResponse<E: FieldElement, D: Digest> {
+    /// Hashes of all LDE rows of a trace segment assigned to the worker, in the order of their
+    /// positions in the LDE domain.
+    SegmentHashes { segment: usize, row_hashes: Vec<D> },
+    /// Rows of a trace segment read at the requested positions.
+    Rows { segment: usize, rows: Vec<Vec<E>> },
+}
+
+impl<E: FieldElement, D: Digest> Serializable for WorkerResponse<E, D> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::SegmentHashes {
+                segment,
+                row_hashes,
+            } => {
+                target.write_u8(SEGMENT_HASHES_TAG);
+                target.write_u8(*segment as u8);
+                target.write_u32(row_hashes.len() as u32);
+                D::write_batch_into(row_hashes, target);
+            }
+            Self::Rows { segment, rows } => {
+                target.write_u8(ROWS_TAG);
+                target.write_u8(*segment as u8);
+                target.write_u32(rows.len() as u32);
+                target.write_u32(rows.first().map_or(0, |row| row.len()) as u32);
+                for row in rows.iter() {
+                    E::write_batch_into(row, target);
+                }
+            }
+        }
+    }
+}
+
+impl<E: FieldElement, D: Digest> Deserializable for WorkerResponse<E, D> {
+    /// Reads a worker response from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid worker response could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            SEGMENT_HASHES_TAG => {
+                let segment = source.read_u8()? as usize;
+                let num_hashes = source.read_u32()? as usize;
+                let row_hashes = D::read_batch_from(source, num_hashes)?;
+                Ok(Self::SegmentHashes {
+                    segment,
+                    row_hashes,
+                })
+            }
+            ROWS_TAG => {
+                let segment = source.read_u8()? as usize;
+                let num_rows = source.read_u32()? as usize;
+                let row_width = source.read_u32()? as usize;
+                let mut rows = Vec::with_capacity(num_rows);
+                for _ in 0..num_rows {
+                    rows.push(E::read_batch_from(source, row_width)?);
+                }
+                Ok(Self::Rows { segment, rows })
+            }
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid worker response tag: {tag}"
+            ))),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn read_positions<R: ByteReader>(source: &mut R) -> Result<Vec<usize>, DeserializationError> {
+    let num_positions = source.read_u32()? as usize;
+    let mut positions = Vec::with_capacity(num_positions);
+    for _ in 0..num_positions {
+        let position = source.read_u64()?;
+        let position = usize::try_from(position).map_err(|_| {
+            DeserializationError::InvalidValue(format!("position {position} is too large"))
+        })?;
+        positions.push(position);
+    }
+    Ok(positions)
+}
@@ -0,0 +1,193 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Building blocks for distributing trace LDE and commitment computation across multiple workers.
+//!
+//! For very large execution traces, the low-degree extension of the trace may not fit into the
+//! memory of a single machine. This module makes it possible to split the LDE domain among
+//! several workers as follows:
+//!
+//! 1. The coordinator interpolates a trace segment into polynomials (this requires memory
+//!    proportional to the size of the trace, rather than the size of the LDE), and sends the
+//!    polynomials to every worker via [WorkerRequest::ExtendSegment] message.
+//! 2. Each [LdeWorker] evaluates the polynomials over its part of the LDE domain, hashes the
+//!    resulting rows, and responds with [WorkerResponse::SegmentHashes] message.
+//! 3. The coordinator merges row hashes received from all workers into a Merkle tree via
+//!    [build_segment_commitment()] function, and writes the root of the tree into the
+//!    transcript. The resulting commitment is identical to the one which would have been
+//!    computed on a single machine, and thus, the proof can be verified by a regular verifier.
+//! 4. When query positions are known, the coordinator groups them by worker via
+//!    [group_positions_by_worker()] function, requests the rows via [WorkerRequest::ReadRows]
+//!    messages, and assembles trace queries via [build_segment_queries()] function.
+//!
+//! All messages implement [Serializable](utils::Serializable) and
+//! [Deserializable](utils::Deserializable) traits, and thus, can be sent over
+//! any transport. This module does not make any assumptions about how messages are delivered.
+//!
+//! Responses received from workers are checked by the coordinator: [build_segment_commitment()]
+//! and [build_segment_queries()] return a [WorkerResponseError] if a response does not match
+//! the config or the requested positions, or if a returned row does not match the commitment.
+//!
+//! Note that a worker can keep only the rows at LDE positions `p` where
+//! `p % num_workers == worker_idx`. Since the number of workers cannot exceed the blowup factor,
+//! the rows at positions `p` and `p + blowup_factor` (i.e., the current and the next rows of an
+//! evaluation frame) are always held by the same worker.
+//!
+//! Currently, only the trace commitment and the trace queries are distributed. The remaining
+//! phases of proof generation (i.e., constraint evaluation and DEEP composition) are not
+//! distributed, and the coordinator still needs the full LDE of the trace to perform them; thus,
+//! this module reduces the time spent on trace commitments, but not the peak memory of the
+//! coordinator.
+
+use super::ColMatrix;
+use air::proof::Queries;
+use crypto::{ElementHasher, MerkleTree};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+mod messages;
+pub use messages::{WorkerConfig, WorkerRequest, WorkerResponse};
+
+mod worker;
+pub use worker::LdeWorker;
+
+mod errors;
+pub use errors::WorkerResponseError;
+
+#[cfg(test)]
+mod tests;
+
+// COORDINATOR FUNCTIONS
+// ================================================================================================
+
+/// Builds a Merkle tree commitment to a trace segment LDE from row hashes computed by workers.
+///
+/// `worker_hashes` must contain row hashes from all workers ordered by worker index.
+///
+/// # Errors
+/// Returns an error if the number of workers or the number of hashes from any of the workers is
+/// different from the one specified by `config`.
+pub fn build_segment_commitment<B, H>(
+    config: &WorkerConfig<B>,
+    worker_hashes: Vec<Vec<H::Digest>>,
+) -> Result<MerkleTree<H>, WorkerResponseError>
+where
+    B: StarkField,
+    H: ElementHasher<BaseField = B>,
+{
+    if worker_hashes.len() != config.num_workers() {
+        return Err(WorkerResponseError::UnexpectedNumWorkers(
+            config.num_workers(),
+            worker_hashes.len(),
+        ));
+    }
+    for (i, hashes) in worker_hashes.iter().enumerate() {
+        if hashes.len() != config.rows_per_worker() {
+            return Err(WorkerResponseError::UnexpectedNumRowHashes(
+                i,
+                config.rows_per_worker(),
+                hashes.len(),
+            ));
+        }
+    }
+
+    // interleave row hashes so that the hash of the row at position p is at index p
+    let num_workers = config.num_workers();
+    let leaves = (0..config.lde_domain_size())
+        .map(|position| worker_hashes[position % num_workers][position / num_workers])
+        .collect();
+
+    Ok(MerkleTree::new(leaves).expect("failed to construct trace Merkle tree"))
+}
+
+/// Splits the specified LDE positions into groups such that the group at index `i` contains all
+/// positions assigned to worker `i`.
+pub fn group_positions_by_worker<B: StarkField>(
+    config: &WorkerConfig<B>,
+    positions: &[usize],
+) -> Vec<Vec<usize>> {
+    let mut result = vec![Vec::new(); config.num_workers()];
+    for &position in positions {
+        result[config.worker_for_position(position)].push(position);
+    }
+    result
+}
+
+/// Builds trace queries for a segment from rows read from workers and the Merkle tree built via
+/// [build_segment_commitment()] function.
+///
+/// `worker_rows` must contain rows read from all workers ordered by worker index, where rows from
+/// each worker are requested for the positions returned by [group_positions_by_worker()].
+///
+/// # Errors
+/// Returns an error if the number of workers is different from the one specified by `config`,
+/// if the number of rows received from any of the workers does not match the number of
+/// positions assigned to the worker, or if any of the rows does not hash to the leaf of
+/// `segment_tree` at the corresponding position.
+pub fn build_segment_queries<E, H>(
+    config: &WorkerConfig<E::BaseField>,
+    segment_tree: &MerkleTree<H>,
+    positions: &[usize],
+    worker_rows: Vec<Vec<Vec<E>>>,
+) -> Result<Queries, WorkerResponseError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let worker_positions = group_positions_by_worker(config, positions);
+    validate_worker_rows(&worker_positions, &worker_rows, segment_tree)?;
+
+    // put rows back into the order of the requested positions
+    let mut next_row = vec![0; config.num_workers()];
+    let trace_states = positions
+        .iter()
+        .map(|&position| {
+            let worker = config.worker_for_position(position);
+            let row = worker_rows[worker][next_row[worker]].clone();
+            next_row[worker] += 1;
+            row
+        })
+        .collect::<Vec<_>>();
+
+    // build Merkle authentication paths to the leaves specified by positions
+    let trace_proof = segment_tree
+        .prove_batch(positions)
+        .expect("failed to generate a Merkle proof for trace queries");
+
+    Ok(Queries::new(trace_proof, trace_states))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure rows received from workers correspond to the positions requested from them and
+/// to the leaves of the segment tree at these positions.
+fn validate_worker_rows<E, H>(
+    worker_positions: &[Vec<usize>],
+    worker_rows: &[Vec<Vec<E>>],
+    segment_tree: &MerkleTree<H>,
+) -> Result<(), WorkerResponseError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    if worker_rows.len() != worker_positions.len() {
+        return Err(WorkerResponseError::UnexpectedNumWorkers(
+            worker_positions.len(),
+            worker_rows.len(),
+        ));
+    }
+    for (i, (positions, rows)) in worker_positions.iter().zip(worker_rows).enumerate() {
+        if rows.len() != positions.len() {
+            return Err(WorkerResponseError::UnexpectedNumRows(i, positions.len(), rows.len()));
+        }
+        for (&position, row) in positions.iter().zip(rows) {
+            if H::hash_elements(row) != segment_tree.leaves()[position] {
+                return Err(WorkerResponseError::RowDoesNotMatchCommitment(i, position));
+            }
+        }
+    }
+    Ok(())
+}
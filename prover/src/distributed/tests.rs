@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_segment_commitment, build_segment_queries, group_positions_by_worker, LdeWorker,
+    WorkerConfig, WorkerRequest, WorkerResponse, WorkerResponseError,
+};
+use crate::{
+    tests::{build_fib_trace, MockAir},
+    DefaultTraceLde, StarkDomain, Trace, TraceLde,
+};
+use crypto::{hashers::Blake3_256, Hasher};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::{collections::Vec, Deserializable, Serializable};
+
+type Blake3 = Blake3_256<BaseElement>;
+type Digest = <Blake3 as Hasher>::Digest;
+
+#[test]
+fn distributed_trace_commitment() {
+    let trace_length = 16;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+
+    // build the commitment on a single machine
+    let (trace_lde, trace_polys) = DefaultTraceLde::<BaseElement, Blake3>::new(
+        &trace.get_info(),
+        trace.main_segment(),
+        &domain,
+    );
+
+    // build the same commitment using 4 workers; all messages are sent in serialized form
+    let config = WorkerConfig::new(4, trace_length, domain.trace_to_lde_blowup(), domain.offset());
+    let config = WorkerConfig::read_from_bytes(&config.to_bytes()).unwrap();
+    let mut workers = (0..config.num_workers())
+        .map(|i| LdeWorker::<BaseElement, Blake3>::new(config, i))
+        .collect::<Vec<_>>();
+
+    let polys = trace_polys.main_trace_polys().map(|poly| poly.to_vec()).collect();
+    let request = WorkerRequest::ExtendSegment {
+        segment: 0,
+        polys: crate::ColMatrix::new(polys),
+    };
+    let worker_hashes = workers
+        .iter_mut()
+        .map(|worker| {
+            let request = WorkerRequest::read_from_bytes(&request.to_bytes()).unwrap();
+            let response = worker.handle(request).to_bytes();
+            match WorkerResponse::<BaseElement, Digest>::read_from_bytes(&response).unwrap() {
+                WorkerResponse::SegmentHashes { row_hashes, .. } => row_hashes,
+                _ => panic!("unexpected worker response"),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let tree =
+        build_segment_commitment::<BaseElement, Blake3>(&config, worker_hashes.clone()).unwrap();
+    assert_eq!(trace_lde.get_main_trace_commitment(), *tree.root());
+
+    // queries assembled from worker rows should be the same as queries from the trace LDE
+    let positions = vec![3, 17, 18, 40, 63, 100];
+    let worker_rows = group_positions_by_worker(&config, &positions)
+        .into_iter()
+        .zip(workers.iter_mut())
+        .map(|(positions, worker)| {
+            match worker.handle(WorkerRequest::ReadRows {
+                segment: 0,
+                positions,
+            }) {
+                WorkerResponse::Rows { rows, .. } => rows,
+                _ => panic!("unexpected worker response"),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let queries = build_segment_queries(&config, &tree, &positions, worker_rows.clone()).unwrap();
+    assert_eq!(trace_lde.query(&positions).unwrap()[0], queries);
+
+    // responses inconsistent with the config or with the commitment are rejected
+    let mut missing_hashes = worker_hashes.clone();
+    missing_hashes[2].pop();
+    assert_eq!(
+        Err(WorkerResponseError::UnexpectedNumRowHashes(
+            2,
+            config.rows_per_worker(),
+            config.rows_per_worker() - 1
+        )),
+        build_segment_commitment::<BaseElement, Blake3>(&config, missing_hashes).map(|_| ())
+    );
+    assert_eq!(
+        Err(WorkerResponseError::UnexpectedNumWorkers(4, 3)),
+        build_segment_commitment::<BaseElement, Blake3>(&config, worker_hashes[1..].to_vec())
+            .map(|_| ())
+    );
+
+    let mut missing_rows = worker_rows.clone();
+    missing_rows[0].pop();
+    assert_eq!(
+        Err(WorkerResponseError::UnexpectedNumRows(0, 2, 1)),
+        build_segment_queries(&config, &tree, &positions, missing_rows)
+    );
+
+    let mut invalid_rows = worker_rows;
+    invalid_rows[1][0][0] += BaseElement::ONE;
+    assert_eq!(
+        Err(WorkerResponseError::RowDoesNotMatchCommitment(1, 17)),
+        build_segment_queries(&config, &tree, &positions, invalid_rows)
+    );
+}
@@ -0,0 +1,169 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColMatrix, WorkerConfig, WorkerRequest, WorkerResponse};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, Hasher};
+use math::{fft, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// LDE WORKER
+// ================================================================================================
+
+/// A worker which extends and hashes its part of the LDE domain for trace segments distributed
+/// by a coordinator.
+///
+/// A worker responsible for the LDE positions `p` where `p % num_workers == worker_idx` evaluates
+/// trace polynomials over the coset `offset * g^worker_idx * <g^num_workers>` (where `g` is the
+/// generator of the LDE domain). Thus, each worker performs `1 / num_workers` of the work needed
+/// to extend a trace segment and keeps only `1 / num_workers` of the segment LDE in memory.
+///
+/// Extended segments are kept by the worker until it is dropped, so that rows at queried
+/// positions can be read at the end of the protocol.
+pub struct LdeWorker<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    config: WorkerConfig<E::BaseField>,
+    worker_idx: usize,
+    twiddles: Vec<E::BaseField>,
+    segments: Vec<Option<ColMatrix<E>>>,
+    _hasher: PhantomData<H>,
+}
+
+impl<E, H> LdeWorker<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new worker with the specified index, using the specified distribution config.
+    ///
+    /// # Panics
+    /// Panics if `worker_idx` is greater than or equal to the number of workers in the config.
+    pub fn new(config: WorkerConfig<E::BaseField>, worker_idx: usize) -> Self {
+        assert!(
+            worker_idx < config.num_workers(),
+            "worker index must be smaller than {}, but was {}",
+            config.num_workers(),
+            worker_idx
+        );
+        Self {
+            config,
+            worker_idx,
+            twiddles: fft::get_twiddles(config.trace_length()),
+            segments: Vec::new(),
+            _hasher: PhantomData,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of this worker.
+    pub fn worker_idx(&self) -> usize {
+        self.worker_idx
+    }
+
+    /// Returns the distribution config of this worker.
+    pub fn config(&self) -> &WorkerConfig<E::BaseField> {
+        &self.config
+    }
+
+    // REQUEST HANDLING
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the specified request and returns a response to be sent to the coordinator.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * For [WorkerRequest::ExtendSegment], the number of polynomial coefficients is different
+    ///   from the trace length.
+    /// * For [WorkerRequest::ReadRows], the segment has not been extended yet, or any of the
+    ///   positions is not assigned to this worker.
+    pub fn handle(
+        &mut self,
+        request: WorkerRequest<E>,
+    ) -> WorkerResponse<E, <H as Hasher>::Digest> {
+        match request {
+            WorkerRequest::ExtendSegment { segment, polys } => {
+                let row_hashes = self.extend_segment(segment, polys);
+                WorkerResponse::SegmentHashes {
+                    segment,
+                    row_hashes,
+                }
+            }
+            WorkerRequest::ReadRows { segment, positions } => {
+                let rows = self.read_rows(segment, &positions);
+                WorkerResponse::Rows { segment, rows }
+            }
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates segment polynomials over the coset assigned to this worker, and returns hashes
+    /// of the resulting rows.
+    fn extend_segment(&mut self, segment: usize, polys: ColMatrix<E>) -> Vec<H::Digest> {
+        assert_eq!(
+            polys.num_rows(),
+            self.config.trace_length(),
+            "segment polynomials must have {} coefficients, but had {}",
+            self.config.trace_length(),
+            polys.num_rows()
+        );
+
+        // the coset assigned to this worker consists of LDE domain points at positions
+        // worker_idx + i * num_workers
+        let lde_generator = E::BaseField::get_root_of_unity(self.config.lde_domain_size().ilog2());
+        let offset =
+            self.config.domain_offset() * lde_generator.exp((self.worker_idx as u64).into());
+        let blowup_factor = self.config.blowup_factor() / self.config.num_workers();
+
+        let columns = polys
+            .columns()
+            .map(|poly| fft::evaluate_poly_with_offset(poly, &self.twiddles, offset, blowup_factor))
+            .collect::<Vec<_>>();
+        let evaluations = ColMatrix::new(columns);
+
+        let mut row = vec![E::ZERO; evaluations.num_cols()];
+        let row_hashes = (0..evaluations.num_rows())
+            .map(|i| {
+                evaluations.read_row_into(i, &mut row);
+                H::hash_elements(&row)
+            })
+            .collect();
+
+        if self.segments.len() <= segment {
+            self.segments.resize(segment + 1, None);
+        }
+        self.segments[segment] = Some(evaluations);
+
+        row_hashes
+    }
+
+    /// Returns rows of the specified segment at the specified LDE positions.
+    fn read_rows(&self, segment: usize, positions: &[usize]) -> Vec<Vec<E>> {
+        let evaluations = self
+            .segments
+            .get(segment)
+            .and_then(|segment| segment.as_ref())
+            .unwrap_or_else(|| panic!("trace segment {segment} has not been extended"));
+
+        positions
+            .iter()
+            .map(|&position| {
+                assert_eq!(
+                    self.config.worker_for_position(position),
+                    self.worker_idx,
+                    "position {position} is not assigned to worker {}",
+                    self.worker_idx
+                );
+                let mut row = vec![E::ZERO; evaluations.num_cols()];
+                evaluations.read_row_into(position / self.config.num_workers(), &mut row);
+                row
+            })
+            .collect()
+    }
+}
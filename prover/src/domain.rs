@@ -3,10 +3,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::Air;
+use crate::constraints::{get_inv_evaluation, PeriodicValueTable};
+use air::{Air, ConstraintDivisor};
 use math::{fft, get_power_series, StarkField};
 use utils::collections::Vec;
 
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -29,6 +35,16 @@ pub struct StarkDomain<B: StarkField> {
 
     /// Offset of the low-degree extension domain.
     domain_offset: B,
+
+    /// Values of periodic columns over the constraint evaluation domain; these are computed in
+    /// advance only for domains of a [ProvingKey](crate::ProvingKey), or taken from a
+    /// [PeriodicColumnCache](crate::PeriodicColumnCache).
+    periodic_values: Option<Arc<PeriodicValueTable<B>>>,
+
+    /// Inverse evaluations of constraint divisor numerators over the constraint evaluation
+    /// domain; these are computed in advance only for domains of a
+    /// [ProvingKey](crate::ProvingKey).
+    divisor_inv_evaluations: Vec<(ConstraintDivisor<B>, Vec<B>)>,
 }
 
 // STARK DOMAIN IMPLEMENTATION
@@ -49,6 +65,8 @@ impl<B: StarkField> StarkDomain<B> {
             ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
             ce_domain_mod_mask: air.ce_domain_size() - 1,
             domain_offset: air.domain_offset(),
+            periodic_values: None,
+            divisor_inv_evaluations: Vec::new(),
         }
     }
 
@@ -71,6 +89,8 @@ impl<B: StarkField> StarkDomain<B> {
             ce_to_lde_blowup: 1,
             ce_domain_mod_mask: ce_domain_size - 1,
             domain_offset,
+            periodic_values: None,
+            divisor_inv_evaluations: Vec::new(),
         }
     }
 
@@ -151,4 +171,58 @@ impl<B: StarkField> StarkDomain<B> {
     pub fn offset(&self) -> B {
         self.domain_offset
     }
+
+    // PRECOMPUTED CONSTRAINT DATA
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes values of periodic columns and inverse evaluations of transition and main
+    /// boundary constraint divisors for the specified AIR over the constraint evaluation domain,
+    /// and caches them in this domain.
+    pub(crate) fn precompute_constraint_data<A: Air<BaseField = B>>(&mut self, air: &A) {
+        self.periodic_values = Some(Arc::new(PeriodicValueTable::new(air)));
+
+        let context = air.context();
+        let mut divisors = vec![ConstraintDivisor::from_transition(
+            context.trace_len(),
+            context.num_transition_exemptions(),
+        )];
+        for assertion in air.get_assertions() {
+            let divisor = ConstraintDivisor::from_assertion(&assertion, context.trace_len());
+            if !divisors.contains(&divisor) {
+                divisors.push(divisor);
+            }
+        }
+
+        self.divisor_inv_evaluations = divisors
+            .into_iter()
+            .map(|divisor| {
+                let inv_evaluations = get_inv_evaluation(&divisor, self);
+                (divisor, inv_evaluations)
+            })
+            .collect();
+    }
+
+    /// Sets values of periodic columns over the constraint evaluation domain to the specified
+    /// precomputed values.
+    #[cfg(feature = "std")]
+    pub(crate) fn set_periodic_values(&mut self, periodic_values: Arc<PeriodicValueTable<B>>) {
+        self.periodic_values = Some(periodic_values);
+    }
+
+    /// Returns precomputed values of periodic columns, if any.
+    pub(crate) fn periodic_values(&self) -> Option<&PeriodicValueTable<B>> {
+        self.periodic_values.as_deref()
+    }
+
+    /// Returns precomputed inverse evaluations of the specified divisor's numerator over the
+    /// constraint evaluation domain, if any.
+    pub(crate) fn get_divisor_inv_evaluations(
+        &self,
+        divisor: &ConstraintDivisor<B>,
+    ) -> Option<&[B]> {
+        self.divisor_inv_evaluations
+            .iter()
+            .find(|(cached, _)| cached == divisor)
+            .map(|(_, inv_evaluations)| inv_evaluations.as_slice())
+    }
 }
@@ -5,7 +5,10 @@
 
 //! Contains common error types for prover and verifier.
 
+use crate::ProvingPhase;
 use core::fmt;
+use crypto::RandomCoinError;
+use utils::string::String;
 
 // PROVER ERROR
 // ================================================================================================
@@ -21,6 +24,54 @@ pub enum ProverError {
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when proof generation cannot be resumed from a checkpoint because the
+    /// checkpoint is malformed or does not match the provided public inputs or prover options.
+    InvalidCheckpoint(String),
+    /// This error occurs when a proving key cannot be used to generate a proof because it was
+    /// built for a different computation, trace shape, or proof options.
+    IncompatibleProvingKey(String),
+    /// This error occurs when a chain of execution trace segments cannot be proven as a
+    /// continuation because it is empty or because the boundary rows of adjacent segments do
+    /// not match.
+    InvalidContinuation(String),
+    /// This error occurs when an execution trace does not satisfy constraints of the AIR; this
+    /// is checked only when the prover is compiled in debug mode. The error contains a
+    /// description of all violated constraints.
+    InvalidTrace(String),
+    /// This error occurs when proof generation is cancelled via a
+    /// [CancellationToken](crate::CancellationToken). The error contains the phase of proof
+    /// generation which was about to start (or was in progress) when cancellation was detected.
+    Cancelled(ProvingPhase),
+    /// This error occurs when the dimensions of an execution trace (or of one of its auxiliary
+    /// segments) are not consistent with the trace layout or are not supported by the base field.
+    InvalidTraceShape(String),
+    /// This error occurs when proof options cannot be used to generate a proof for a given
+    /// execution trace.
+    InvalidProofOptions(String),
+    /// This error occurs when data structures required to generate a proof are too large to be
+    /// allocated on the target platform.
+    InsufficientMemory(String),
+    /// This error occurs when an execution trace fails to build one of its auxiliary segments.
+    /// The error contains the index of the segment.
+    MissingAuxTraceSegment(usize),
+    /// This error occurs when the degree of the DEEP composition polynomial is different from
+    /// the expected degree; this usually means that the execution trace does not satisfy
+    /// constraints of the AIR, or that the AIR specifies wrong constraint degrees.
+    UnexpectedDeepCompositionDegree(usize, usize),
+    /// This error occurs when a proof could not be generated by a remote worker, or when the
+    /// proof returned by the worker does not match the request.
+    RemoteProvingFailed(String),
+    /// This error occurs when an opening of a trace column cannot be generated because the column
+    /// or the opening point is invalid, or because the execution trace is not the one committed
+    /// to in the proof.
+    InvalidTraceOpening(String),
+    /// This error occurs when a prover fails to draw a random value from a random coin within a
+    /// specified number of tries.
+    RandomCoinError(RandomCoinError),
+    /// This error occurs when low-degree extensions stored on disk (see
+    /// [Prover::lde_directory()](crate::Prover::lde_directory)) cannot be written to or read from
+    /// disk; for example, because the disk is full or because a temporary file was removed.
+    LdeStorageError(String),
 }
 
 impl fmt::Display for ProverError {
@@ -36,6 +87,86 @@ impl fmt::Display for ProverError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the specified base field")
             }
+            Self::InvalidCheckpoint(err) => {
+                write!(f, "failed to resume proof generation from checkpoint: {err}")
+            }
+            Self::IncompatibleProvingKey(err) => {
+                write!(f, "proving key is not compatible with the computation: {err}")
+            }
+            Self::InvalidContinuation(err) => {
+                write!(f, "invalid continuation: {err}")
+            }
+            Self::InvalidTrace(report) => {
+                write!(f, "execution trace does not satisfy constraints of the AIR: {report}")
+            }
+            Self::Cancelled(phase) => {
+                write!(f, "proof generation was cancelled while {phase}")
+            }
+            Self::InvalidTraceShape(err) => {
+                write!(f, "invalid execution trace shape: {err}")
+            }
+            Self::InvalidProofOptions(err) => {
+                write!(f, "invalid proof options: {err}")
+            }
+            Self::InsufficientMemory(err) => {
+                write!(f, "not enough memory to generate the proof: {err}")
+            }
+            Self::MissingAuxTraceSegment(idx) => {
+                write!(f, "failed to build auxiliary trace segment {idx}")
+            }
+            Self::UnexpectedDeepCompositionDegree(expected, actual) => {
+                write!(f, "the DEEP composition polynomial has unexpected degree; expected {expected}, but was {actual}")
+            }
+            Self::RemoteProvingFailed(err) => {
+                write!(f, "remote proof generation failed: {err}")
+            }
+            Self::InvalidTraceOpening(err) => {
+                write!(f, "failed to open trace column: {err}")
+            }
+            Self::RandomCoinError(err) => {
+                write!(f, "failed to draw a random value from a random coin: {err}")
+            }
+            Self::LdeStorageError(err) => {
+                write!(f, "failed to access low-degree extension stored on disk: {err}")
+            }
         }
     }
 }
+
+impl ProverError {
+    /// Returns a stable code identifying the kind of this error.
+    ///
+    /// Codes have the form `prover.<kind>`; they do not change across releases, and are never
+    /// reused for a different kind of error. Thus, they can be used to match and report errors
+    /// (e.g., in logs and metrics) independently of their messages.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnsatisfiedTransitionConstraintError(_) => {
+                "prover.unsatisfied_transition_constraint"
+            }
+            Self::MismatchedConstraintPolynomialDegree(_, _) => {
+                "prover.mismatched_constraint_polynomial_degree"
+            }
+            Self::UnsupportedFieldExtension(_) => "prover.unsupported_field_extension",
+            Self::InvalidCheckpoint(_) => "prover.invalid_checkpoint",
+            Self::IncompatibleProvingKey(_) => "prover.incompatible_proving_key",
+            Self::InvalidContinuation(_) => "prover.invalid_continuation",
+            Self::InvalidTrace(_) => "prover.invalid_trace",
+            Self::Cancelled(_) => "prover.cancelled",
+            Self::InvalidTraceShape(_) => "prover.invalid_trace_shape",
+            Self::InvalidProofOptions(_) => "prover.invalid_proof_options",
+            Self::InsufficientMemory(_) => "prover.insufficient_memory",
+            Self::MissingAuxTraceSegment(_) => "prover.missing_aux_trace_segment",
+            Self::UnexpectedDeepCompositionDegree(_, _) => {
+                "prover.unexpected_deep_composition_degree"
+            }
+            Self::RemoteProvingFailed(_) => "prover.remote_proving_failed",
+            Self::InvalidTraceOpening(_) => "prover.invalid_trace_opening",
+            Self::RandomCoinError(_) => "prover.random_coin",
+            Self::LdeStorageError(_) => "prover.lde_storage",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProverError {}
@@ -12,7 +12,8 @@
 //! When the crate is compiled with `concurrent` feature enabled, proof generation will be
 //! performed in multiple threads (usually, as many threads as there are logical cores on the
 //! machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment
-//! variable.
+//! variable, or on a per-proof basis by generating the proof via `Prover::prove_in_pool()`
+//! method.
 //!
 //! # Usage
 //! To generate a proof that a computation was executed correctly, you'll need to do the
@@ -43,11 +44,15 @@
 #[macro_use]
 extern crate alloc;
 
+#[cfg(all(feature = "export", feature = "std"))]
+pub use air::ir;
 pub use air::{
-    proof, proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TraceLayout, TransitionConstraintDegree,
+    gadgets, metrics, proof,
+    proof::{ContinuationProof, ProofMetadata, StarkProof, TraceOpening},
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
+    EvaluationFrame, FieldExtension, ProofOptions, ProofOptionsBuilder, ProofOptionsError,
+    TraceInfo, TraceLayout, TransitionConstraintDegree, TuningObjective,
 };
 pub use utils::{
     iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
@@ -55,7 +60,7 @@ pub use utils::{
 };
 
 use fri::FriProver;
-use utils::collections::Vec;
+use utils::{collections::Vec, string::ToString};
 
 pub use math;
 use math::{
@@ -65,12 +70,12 @@ use math::{
 };
 
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, RandomCoin};
 
 #[cfg(feature = "std")]
 use log::debug;
 #[cfg(feature = "std")]
-use std::time::Instant;
+use time::Instant;
 
 mod domain;
 pub use domain::StarkDomain;
@@ -84,15 +89,91 @@ pub use constraints::{
     DefaultConstraintEvaluator,
 };
 
+mod backend;
+pub use backend::{build_constraint_commitment, CpuBackend, ProverBackend};
+
 mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
-pub use trace::{DefaultTraceLde, Trace, TraceLde, TracePolyTable, TraceTable, TraceTableFragment};
+pub use trace::{
+    AuxTraceBuilder, CellDiff, ColumnDiff, ColumnDomain, ConstraintViolation, DefaultTraceLde,
+    IncrementalTrace, MainTraceRow, MainTraceRows, PackedTraceTable, Trace, TraceBuilder,
+    TraceDiff, TraceLde, TracePolyTable, TraceRow, TraceStorage, TraceTable, TraceTableFragment,
+    TraceWithAux, ValidationReport,
+};
+
+#[cfg(feature = "std")]
+pub use trace::FileTraceTable;
 
 mod channel;
 use channel::ProverChannel;
 
+mod progress;
+pub use progress::ProvingPhase;
+
+mod recorder;
+use recorder::MetricsRecorder;
+
+mod cancellation;
+pub use cancellation::CancellationToken;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub mod distributed;
+
+pub mod remote;
+
+mod proving_key;
+pub use proving_key::ProvingKey;
+
+mod config;
+pub use config::ProvingConfig;
+
+mod rng;
+pub use rng::ProverRng;
+
+mod memory;
+pub use memory::MemoryEstimate;
+
+#[cfg(feature = "std")]
+mod time;
+
+#[cfg(feature = "std")]
+mod periodic_cache;
+#[cfg(feature = "std")]
+pub use periodic_cache::PeriodicColumnCache;
+
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "std")]
+use stats::InstrumentedProver;
+#[cfg(feature = "std")]
+pub use stats::{PhaseStats, ProofStats};
+
+#[cfg(feature = "std")]
+mod transcript;
+#[cfg(feature = "std")]
+use transcript::TranscriptProver;
+
+#[cfg(feature = "std")]
+mod coefficients;
+#[cfg(feature = "std")]
+use coefficients::CommittingProver;
+#[cfg(feature = "std")]
+pub use coefficients::{CoefficientCommitment, CoefficientHash};
+
+mod opening;
+
+mod checkpoint;
+use checkpoint::write_checkpoint;
+pub use checkpoint::TraceCheckpoint;
+
+mod check;
+pub use check::CheckReport;
+use check::{get_composition_degree, UncommittedTraceLde};
+
 mod errors;
 pub use errors::ProverError;
 
@@ -197,6 +278,283 @@ pub trait Prover {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Invoked at the start of each phase of proof generation.
+    ///
+    /// This can be used to report progress of long-running proof generation jobs (e.g., to
+    /// display a progress bar or to implement health checks); [ProvingPhase::progress()] returns
+    /// an estimate of the percentage of work completed by the time a given phase starts. Once the
+    /// proof is generated, this method is invoked with [ProvingPhase::Done].
+    ///
+    /// This method is invoked from the thread executing [Prover::prove()] and thus should return
+    /// quickly. The default implementation does nothing.
+    fn on_phase_start(&self, _phase: ProvingPhase) {}
+
+    /// Returns a token via which proof generation by this prover can be cancelled, or `None` if
+    /// proof generation cannot be cancelled.
+    ///
+    /// The token is checked at the start of every phase of proof generation (before
+    /// [Prover::on_phase_start()] is invoked) and periodically during proof-of-work grinding.
+    /// Once the token is cancelled, proof generation stops at the next check, all data allocated
+    /// for the proof is dropped, and [ProverError::Cancelled] is returned. This can be used by
+    /// proving services to abort abandoned requests promptly.
+    ///
+    /// The default implementation returns `None`.
+    fn cancellation_token(&self) -> Option<&CancellationToken> {
+        None
+    }
+
+    /// Returns a cache of extended periodic column values which this prover should use, or
+    /// `None` if periodic columns should be extended for every proof.
+    ///
+    /// When a cache is returned, periodic columns of the AIR are extended over the constraint
+    /// evaluation domain only if the cache does not contain them yet; otherwise, the cached values
+    /// are re-used. Since clones of a [PeriodicColumnCache] share the same entries, provers which
+    /// return clones of the same cache share the extended values. Periodic columns precomputed
+    /// in a [ProvingKey] take precedence over the cache.
+    ///
+    /// The default implementation returns `None`.
+    #[cfg(feature = "std")]
+    fn periodic_column_cache(&self) -> Option<&PeriodicColumnCache<Self::BaseField>> {
+        None
+    }
+
+    /// Returns true if this prover should minimize the amount of memory used during proof
+    /// generation.
+    ///
+    /// In the low-memory mode, the extended execution trace and constraint composition
+    /// polynomial evaluations are discarded as soon as they are no longer needed to build the
+    /// remaining commitments, and the rows at queried positions are re-computed from the trace
+    /// and composition polynomials during the query phase. This reduces peak memory usage at the
+    /// expense of evaluating all polynomials at each of the query positions. The generated proofs
+    /// are identical to the ones generated in the regular mode.
+    ///
+    /// The default implementation returns false.
+    fn low_memory_mode(&self) -> bool {
+        false
+    }
+
+    /// Returns a directory in which this prover should store low-degree extensions of the
+    /// constraint composition polynomial, or `None` if the extensions should be kept in memory.
+    ///
+    /// When a directory is returned, evaluations of composition polynomial columns over the LDE
+    /// domain are written into a temporary file in this directory by the default implementation
+    /// of [Prover::build_constraint_commitment()], and are read back sequentially when the
+    /// commitment is built and when queried rows are extracted. To store the extended execution
+    /// trace on disk as well, [Prover::new_trace_lde()] should instantiate the trace LDE via
+    /// [DefaultTraceLde::new_on_disk()]. Together, this enables proving traces whose LDE does not
+    /// fit in RAM; the generated proofs are identical to the ones generated in memory.
+    ///
+    /// The default implementation returns `None`.
+    #[cfg(feature = "std")]
+    fn lde_directory(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    /// Returns a seed for the generator of all values chosen by the prover at random.
+    ///
+    /// When a seed is returned, all randomness used by the prover is derived from it, and thus,
+    /// proofs generated for identical inputs are byte-identical. This is useful for tests and for
+    /// reproducible proof generation pipelines, but a fixed seed must not be used when values
+    /// chosen by the prover need to remain secret.
+    ///
+    /// The default implementation returns `None`, in which case the seed is drawn from the
+    /// randomness source of the operating system (see [Prover::new_rng()]).
+    fn rng_seed(&self) -> Option<[u8; 32]> {
+        None
+    }
+
+    /// Returns true if this prover should salt the transcript of the generated proofs.
+    ///
+    /// When the transcript is salted, a 32-byte salt is drawn from the generator returned from
+    /// [Prover::new_rng()] and is included in the proof context (see
+    /// [Context::set_salt()](proof::Context::set_salt)). The context is absorbed into the public
+    /// coin before anything else, and thus, all values drawn from the coin depend on the salt;
+    /// the verifier reads the salt from the proof and requires no changes. This way, two proofs of
+    /// the same statement do not share query positions, out-of-domain evaluations, or commitments,
+    /// with the exception of the commitment to the main trace segment, which depends only on the
+    /// execution trace. Deployments which need this commitment to be unlinkable as well should
+    /// include a column of random values (e.g., drawn from [Prover::new_rng()]) in the trace.
+    ///
+    /// The default implementation returns false.
+    fn salt_transcript(&self) -> bool {
+        false
+    }
+
+    /// Returns a new generator for values chosen by the prover at random.
+    ///
+    /// The generator is instantiated with the seed returned from [Prover::rng_seed()] or, if no
+    /// seed is specified, with a seed drawn from the randomness source of the operating system.
+    ///
+    /// # Panics
+    /// Panics if [Prover::rng_seed()] returns `None` and either the randomness source of the
+    /// operating system is not available, or the crate is compiled without `std` feature.
+    fn new_rng(&self) -> ProverRng<Self::HashFn> {
+        match self.rng_seed() {
+            Some(seed) => ProverRng::new(seed),
+            #[cfg(feature = "std")]
+            None => ProverRng::from_entropy(),
+            #[cfg(not(feature = "std"))]
+            None => panic!("a randomness seed must be provided in no-std environments"),
+        }
+    }
+
+    /// Returns a new public coin instantiated with the provided `seed`.
+    ///
+    /// The public coin is used to draw all values which would be sent by the verifier in an
+    /// interactive version of the protocol; the `seed` consists of the proof context and the
+    /// public inputs. The default implementation instantiates [Prover::RandomCoin] from the
+    /// `seed`, making the protocol non-interactive via the Fiat-Shamir transform. To supply
+    /// these values externally (e.g., for testing soundness properties of the protocol), this
+    /// method can return a [ScriptedRandomCoin](crypto::ScriptedRandomCoin) prefilled with the
+    /// values; proofs generated this way must be verified via `verify_with_public_coin()`
+    /// against a coin supplying the same values.
+    fn new_public_coin(&self, seed: &[Self::BaseField]) -> Self::RandomCoin {
+        Self::RandomCoin::new(seed)
+    }
+
+    /// Returns an estimate of the amount of memory needed to generate a proof for the specified
+    /// instance of the computation with this prover.
+    ///
+    /// The estimate is computed from the shape of the execution trace, the constraints, and the
+    /// proof options of `air`, and takes into account whether this prover runs in the low-memory
+    /// mode (see [Prover::low_memory_mode()]). Thus, it can be used to schedule proof generation
+    /// jobs onto machines with enough memory without building the execution trace first.
+    fn estimate_memory(&self, air: &Self::Air) -> MemoryEstimate {
+        MemoryEstimate::new::<_, Self::HashFn>(air, self.low_memory_mode())
+    }
+
+    /// Checks the provided trace against all assertions and transition constraints of the
+    /// computation without generating a proof, and returns a report describing every constraint
+    /// which is not satisfied by the trace.
+    ///
+    /// Auxiliary trace segments (if any) are built over the field `E` using random elements drawn
+    /// from a public coin seeded with public inputs only; thus, these elements are different from
+    /// the ones used during proof generation.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for debugging of traces and AIRs.
+    ///
+    /// # Errors
+    /// Returns an error if random elements for an auxiliary trace segment could not be drawn, or
+    /// if the trace failed to build an auxiliary segment of the shape expected by the AIR.
+    fn validate_trace<E>(&self, mut trace: Self::Trace) -> Result<ValidationReport<E>, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let pub_inputs = self.get_pub_inputs(&trace);
+        let mut public_coin = self.new_public_coin(&pub_inputs.to_elements());
+        let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
+
+        let mut aux_segments = Vec::new();
+        let mut aux_rand_elements = AuxTraceRandElements::new();
+        for i in 0..trace.layout().num_aux_segments() {
+            let rand_elements = air
+                .get_aux_trace_segment_random_elements(i, &mut public_coin)
+                .map_err(ProverError::RandomCoinError)?;
+            let aux_segment = trace
+                .build_aux_segment(&aux_segments, &rand_elements)
+                .ok_or(ProverError::MissingAuxTraceSegment(i))?;
+            check_aux_segment(&air, i, &aux_segment)?;
+            aux_rand_elements.add_segment_elements(rand_elements);
+            aux_segments.push(aux_segment);
+        }
+
+        Ok(trace.check_constraints(&air, &aux_segments, &aux_rand_elements))
+    }
+
+    /// Checks the provided trace against the specified AIR by running the parts of proof
+    /// generation which can detect errors in the trace or in the AIR, and returns a report
+    /// describing the results.
+    ///
+    /// Specifically, auxiliary trace segments (if any) are built, all trace segments are extended
+    /// over the LDE domain, and constraints of the AIR are evaluated over the extended trace and
+    /// merged into the constraint composition polynomial, just as during proof generation. Random
+    /// elements are drawn from a public coin seeded with public inputs and a seed drawn from
+    /// [Prover::new_rng()]. Building Merkle trees, FRI, and proof-of-work grinding are skipped;
+    /// thus, checking a trace takes a fraction of the time needed to generate a proof.
+    ///
+    /// The returned [CheckReport] lists all constraints not satisfied by the trace and, if there
+    /// are none, the degree of the constraint composition polynomial. The latter reveals errors
+    /// which constraint violations alone do not. In debug mode, the default constraint evaluator
+    /// also panics if degrees of transition constraints do not match the degrees declared by the
+    /// AIR.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The base field does not support the field extension specified by the proof options, or
+    ///   the LDE domain is too large for the base field.
+    /// * The trace fails to build an auxiliary segment, or builds a segment of wrong dimensions.
+    /// * The trace is inconsistent with the AIR.
+    /// * Random elements for auxiliary trace segments or constraint composition coefficients
+    ///   could not be drawn from the public coin.
+    fn check<E>(
+        &self,
+        mut trace: Self::Trace,
+        air: &Self::Air,
+    ) -> Result<CheckReport<E>, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        check_proof_inputs::<_, E>(air)?;
+        if trace.get_info() != *air.trace_info() {
+            return Err(ProverError::InvalidTraceShape(
+                "trace info of the trace does not match trace info of the AIR".to_string(),
+            ));
+        }
+
+        // seed the public coin with public inputs and fresh randomness; since nothing is
+        // committed to, the coin cannot be reseeded with trace commitments
+        let pub_inputs = self.get_pub_inputs(&trace);
+        let mut public_coin = self.new_public_coin(&pub_inputs.to_elements());
+        let mut seed = [0; 32];
+        self.new_rng().fill_bytes(&mut seed);
+        public_coin.reseed(Self::HashFn::hash(&seed));
+
+        // extend the main trace segment
+        let domain = build_domain(self, air);
+        let trace_info = trace.get_info();
+        let mut trace_lde = trace.with_main_segment(|main_segment| {
+            UncommittedTraceLde::<E, Self::HashFn>::new(&trace_info, main_segment, &domain)
+        });
+
+        // build and extend auxiliary trace segments (if any)
+        let mut aux_segments = Vec::new();
+        let mut aux_rand_elements = AuxTraceRandElements::new();
+        for i in 0..trace.layout().num_aux_segments() {
+            let rand_elements = air
+                .get_aux_trace_segment_random_elements(i, &mut public_coin)
+                .map_err(ProverError::RandomCoinError)?;
+            let aux_segment = trace
+                .build_aux_segment(&aux_segments, &rand_elements)
+                .ok_or(ProverError::MissingAuxTraceSegment(i))?;
+            check_aux_segment(air, i, &aux_segment)?;
+            trace_lde.add_aux_segment(&aux_segment, &domain)?;
+            aux_rand_elements.add_segment_elements(rand_elements);
+            aux_segments.push(aux_segment);
+        }
+
+        // check the trace against all constraints row by row; if some constraints are violated,
+        // the constraint composition polynomial is not a polynomial of bounded degree, and thus,
+        // evaluating constraints over the extended trace reveals nothing more
+        let validation = trace.check_constraints(air, &aux_segments, &aux_rand_elements);
+        let max_composition_degree =
+            air.trace_length() * air.context().num_constraint_composition_columns();
+        if !validation.is_valid() {
+            return Ok(CheckReport::new(validation, None, max_composition_degree));
+        }
+        drop(aux_segments);
+
+        // evaluate constraints over the extended trace and compute the degree of the resulting
+        // constraint composition polynomial
+        let constraint_coeffs = air
+            .get_constraint_composition_coefficients(&mut public_coin)
+            .map_err(ProverError::RandomCoinError)?;
+        let evaluator = self.new_evaluator(air, aux_rand_elements, constraint_coeffs);
+        let composition_poly_trace = evaluator.evaluate(&trace_lde, &domain)?;
+        let composition_degree = get_composition_degree(composition_poly_trace, &domain);
+
+        Ok(CheckReport::new(validation, Some(composition_degree), max_composition_degree))
+    }
+
     /// Returns a STARK proof attesting to a correct execution of a computation defined by the
     /// provided trace.
     ///
@@ -204,62 +562,430 @@ pub trait Prover {
     /// the computation described by [Self::Air](Prover::Air) and generated using some set of
     /// secret and public inputs. Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
+    ///
+    /// # Errors
+    /// Returns an error if a proof could not be generated; in particular, this happens if:
+    /// * The base field does not support the field extension specified by the proof options.
+    /// * The LDE domain is too large for the base field, or the number of queries specified by
+    ///   the proof options is not smaller than the size of the LDE domain.
+    /// * The blowup factor is too small for the degrees of the transition constraints, or the
+    ///   domain offset is not valid for the LDE domain.
+    /// * The extended execution trace is too large to be allocated on the target platform.
+    /// * The trace fails to build an auxiliary segment, or builds a segment of wrong dimensions.
+    /// * The trace does not satisfy constraints of the AIR.
+    /// * Proof generation is cancelled via [Prover::cancellation_token()].
     fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        self.prove_with_config(trace, ProvingConfig::new())
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, using the proving key and the callbacks set in the specified config.
+    ///
+    /// The generated proof is identical to the proof returned from [Prover::prove()] for the same
+    /// trace; the [ProvingConfig] only determines which precomputed data is used and which
+    /// intermediate results (i.e., a checkpoint of the prover state and sections of the streamed
+    /// proof) are passed to the caller during proof generation.
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as [Prover::prove()], or if the proving key set in
+    /// the config was built for a different trace length, trace layout, or proof options than the
+    /// ones defined by the provided trace and this prover.
+    fn prove_with_config(
+        &self,
+        trace: Self::Trace,
+        config: ProvingConfig<'_, Self::BaseField>,
+    ) -> Result<StarkProof, ProverError> {
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash function.
         match self.options().field_extension() {
-            FieldExtension::None => self.generate_proof::<Self::BaseField>(trace),
+            FieldExtension::None => self.generate_proof::<Self::BaseField>(trace, config),
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(2));
+                }
+                self.generate_proof::<QuadExtension<Self::BaseField>>(trace, config)
+            }
+            FieldExtension::Cubic => {
+                if !<CubeExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(3));
+                }
+                self.generate_proof::<CubeExtension<Self::BaseField>>(trace, config)
+            }
+        }
+    }
+
+    /// Returns STARK proofs attesting to correct executions of a computation defined by the
+    /// provided traces, in the order in which the traces are provided.
+    ///
+    /// All traces are proven by this prover against instances of the same AIR, and the data which
+    /// depends only on the computation and the length of its execution trace (i.e., domain
+    /// twiddles, periodic column values, and constraint divisor evaluations) is computed once for
+    /// every distinct trace length via [ProvingKey], and is shared between all proofs for traces
+    /// of this length. Thread pool warm-up also happens only once for the entire batch. Every
+    /// returned proof is identical to the proof returned from [Prover::prove()] for the same
+    /// trace, and is verified independently of other proofs in the batch.
+    ///
+    /// Since proving keys are shared, all instances of a given trace length must define the same
+    /// periodic columns and place assertions on the same steps of the execution trace (see
+    /// [ProvingKey] for details).
+    ///
+    /// Currently, traces are proven one after another, and every proof is generated using all
+    /// threads of the current thread pool. A single combined proof with commitments shared
+    /// between the traces is not supported: every trace gets its own proof, and thus, the size of
+    /// the batch and the work of the verifier grow linearly with the number of traces.
+    ///
+    /// # Errors
+    /// Returns an error if a proof for any of the traces could not be generated; in this case,
+    /// proofs are not generated for the remaining traces.
+    fn prove_batch<I>(&self, traces: I) -> Result<Vec<StarkProof>, ProverError>
+    where
+        I: IntoIterator<Item = Self::Trace>,
+    {
+        let mut proving_keys: Vec<ProvingKey<Self::BaseField>> = Vec::new();
+        let mut proofs = Vec::new();
+        for trace in traces {
+            let pub_inputs = self.get_pub_inputs(&trace);
+            let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
+            air.context()
+                .validate_options()
+                .map_err(|err| ProverError::InvalidProofOptions(err.to_string()))?;
+
+            // re-use a proving key built for an earlier trace of the same shape, if any
+            let key_idx = match proving_keys.iter().position(|key| key.validate(&air).is_ok()) {
+                Some(key_idx) => key_idx,
+                None => {
+                    proving_keys.push(ProvingKey::new(&air));
+                    proving_keys.len() - 1
+                }
+            };
+
+            let config = ProvingConfig::new().with_proving_key(&proving_keys[key_idx]);
+            proofs.push(self.prove_with_config(trace, config)?);
+        }
+        Ok(proofs)
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, executing all parallelizable work in the specified thread pool.
+    ///
+    /// This method is identical to [Prover::prove()], except that the work is not scheduled on
+    /// the global rayon thread pool. Thus, the number of threads used to generate the proof (e.g.,
+    /// for building LDEs, evaluating constraints, and hashing Merkle trees) is limited by the
+    /// number of threads in the `pool`. This can be used to cap CPU usage of the prover when many
+    /// proofs are generated on the same machine concurrently. A pool can be built using
+    /// [ThreadPoolBuilder](utils::rayon::ThreadPoolBuilder).
+    ///
+    /// Work for building LDEs and hashing Merkle trees is split into chunks based on the number of
+    /// threads in the pool. The size of chunks into which constraint evaluation is split can be
+    /// set via [DefaultConstraintEvaluator::with_fragment_size()] in [Prover::new_evaluator()].
+    ///
+    /// This method is available only when the crate is compiled with `concurrent` feature.
+    #[cfg(feature = "concurrent")]
+    fn prove_in_pool(
+        &self,
+        trace: Self::Trace,
+        pool: &utils::rayon::ThreadPool,
+    ) -> Result<StarkProof, ProverError>
+    where
+        Self: Sync,
+        Self::Trace: Send,
+    {
+        pool.install(|| self.prove(trace))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, together with performance metrics collected during proof generation.
+    ///
+    /// This method is identical to [Prover::prove()], except that the wall-clock and CPU time of
+    /// every phase of proof generation is measured, and the resulting [ProofStats] are returned
+    /// alongside the proof. This can be used to track performance of proof generation across
+    /// versions of the prover and choices of proof options.
+    ///
+    /// This method is available only when the crate is compiled with `std` feature.
+    #[cfg(feature = "std")]
+    fn prove_with_stats(&self, trace: Self::Trace) -> Result<(StarkProof, ProofStats), ProverError>
+    where
+        Self: Sized,
+    {
+        let pub_inputs = self.get_pub_inputs(&trace);
+        let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
+
+        let prover = InstrumentedProver::new(self);
+        let proof = prover.prove(trace)?;
+        Ok((proof, prover.into_stats(&air)))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, together with the transcript of all operations performed on the public
+    /// coin during proof generation.
+    ///
+    /// This method is identical to [Prover::prove()], except that the public coin returned from
+    /// [Prover::new_public_coin()] is wrapped into a [TranscriptCoin](crypto::TranscriptCoin)
+    /// which records every value absorbed into the coin and every challenge drawn from it. The
+    /// generated proof is the same as the proof generated by [Prover::prove()]. A verifier can
+    /// record its transcript via `verify_with_transcript()` function of the verifier crate; for
+    /// a valid proof the two transcripts are identical, and otherwise,
+    /// [Transcript::first_divergence()](crypto::Transcript::first_divergence) points to the first
+    /// value on which the prover and the verifier disagree.
+    ///
+    /// This method is available only when the crate is compiled with `std` feature.
+    #[cfg(feature = "std")]
+    fn prove_with_transcript(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(StarkProof, crypto::Transcript), ProverError>
+    where
+        Self: Sized,
+    {
+        let prover = TranscriptProver::new(self);
+        let proof = prover.prove(trace)?;
+        Ok((proof, prover.recorder().transcript()))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, together with commitments to all trace polynomials under the specified
+    /// coefficient-form commitment `scheme`.
+    ///
+    /// The proof is identical to the proof generated by [Prover::prove()]: the trace is still
+    /// committed to via Merkle trees over the LDEs of trace polynomials. In addition, every trace
+    /// polynomial is passed to `scheme` in coefficient form as soon as it is interpolated. This
+    /// allows systems based on other polynomial commitment schemes (e.g., KZG or IPA) to consume
+    /// the same polynomials without interpolating the trace again.
+    ///
+    /// The returned commitments are ordered by trace column: columns of the main trace segment
+    /// come first, followed by columns of the auxiliary trace segments. To show that a commitment
+    /// under `scheme` and the commitment included in the proof are commitments to the same
+    /// polynomial, both can be opened at a common random point; for the proof, this can be done
+    /// via [Prover::open_trace_column()].
+    ///
+    /// This method is available only when the crate is compiled with `std` feature.
+    #[cfg(feature = "std")]
+    #[allow(clippy::type_complexity)]
+    fn prove_with_coefficient_commitments<C>(
+        &self,
+        trace: Self::Trace,
+        scheme: &C,
+    ) -> Result<(StarkProof, Vec<C::Commitment>), ProverError>
+    where
+        C: CoefficientCommitment<Self::BaseField>,
+        Self: Sized,
+    {
+        let commitments = std::sync::Mutex::new(Vec::new());
+        let proof = CommittingProver::new(self, scheme, &commitments).prove(trace)?;
+        let commitments = commitments.into_inner().expect("commitments lock poisoned");
+        Ok((proof, commitments))
+    }
+
+    /// Resumes proof generation from a checkpoint passed to the checkpoint handler of a
+    /// [ProvingConfig] by [Prover::prove_with_config()], and returns the resulting STARK proof.
+    ///
+    /// The prover channel is restored by replaying trace commitments recorded in the checkpoint,
+    /// and the LDEs of all trace segments are re-computed from the execution trace stored in the
+    /// checkpoint; thus, neither the execution trace nor auxiliary trace segments need to be
+    /// re-built. All steps of the protocol which follow the commitment to the execution trace are
+    /// executed from scratch.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The checkpoint could not be deserialized, or was created for a different field extension.
+    /// * `pub_inputs` are different from the public inputs recorded in the checkpoint.
+    /// * Re-computed trace commitments are different from the commitments recorded in the
+    ///   checkpoint (e.g., because the checkpoint was created using different proof options).
+    fn resume_proof(
+        &self,
+        pub_inputs: <<Self as Prover>::Air as Air>::PublicInputs,
+        checkpoint: &[u8],
+    ) -> Result<StarkProof, ProverError> {
+        match self.options().field_extension() {
+            FieldExtension::None => {
+                self.generate_proof_from_checkpoint::<Self::BaseField>(pub_inputs, checkpoint)
+            }
             FieldExtension::Quadratic => {
                 if !<QuadExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(2));
                 }
-                self.generate_proof::<QuadExtension<Self::BaseField>>(trace)
+                self.generate_proof_from_checkpoint::<QuadExtension<Self::BaseField>>(
+                    pub_inputs, checkpoint,
+                )
             }
             FieldExtension::Cubic => {
                 if !<CubeExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(3));
                 }
-                self.generate_proof::<CubeExtension<Self::BaseField>>(trace)
+                self.generate_proof_from_checkpoint::<CubeExtension<Self::BaseField>>(
+                    pub_inputs, checkpoint,
+                )
             }
         }
     }
 
+    /// Returns a continuation proof attesting to a correct execution of a computation split into
+    /// the specified chain of segments.
+    ///
+    /// A STARK proof is generated for every segment via [Prover::prove()]. The boundary states of
+    /// the returned [ContinuationProof] are the first row of the main trace segment of the first
+    /// segment, followed by the last row of the main trace segment of every segment. For the
+    /// proof to be verifiable, [Self::Air](Prover::Air) must accept the first and the last row of
+    /// a segment as public inputs and assert that the execution trace starts and ends with them;
+    /// this way, segment proofs are bound to their boundary states.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `segments` is empty.
+    /// * The first row of some segment is different from the last row of the preceding segment.
+    /// * Proof generation for any of the segments fails.
+    fn prove_continuation(
+        &self,
+        segments: Vec<Self::Trace>,
+    ) -> Result<ContinuationProof<Self::BaseField>, ProverError> {
+        if segments.is_empty() {
+            return Err(ProverError::InvalidContinuation(
+                "at least one segment must be provided".to_string(),
+            ));
+        }
+
+        let mut boundary_states: Vec<Vec<Self::BaseField>> = Vec::with_capacity(segments.len() + 1);
+        let mut segment_proofs = Vec::with_capacity(segments.len());
+        for (i, trace) in segments.into_iter().enumerate() {
+            let main_segment = trace.main_segment();
+            let mut first_row = vec![Self::BaseField::ZERO; main_segment.num_cols()];
+            main_segment.read_row_into(0, &mut first_row);
+            let mut last_row = vec![Self::BaseField::ZERO; main_segment.num_cols()];
+            main_segment.read_row_into(main_segment.num_rows() - 1, &mut last_row);
+
+            // the segment must start in the state in which the previous segment ended
+            match boundary_states.last() {
+                Some(state) if state != &first_row => {
+                    return Err(ProverError::InvalidContinuation(format!(
+                        "first row of segment {i} is different from the last row of segment {}",
+                        i - 1
+                    )));
+                }
+                Some(_) => (),
+                None => boundary_states.push(first_row),
+            }
+            boundary_states.push(last_row);
+
+            segment_proofs.push(self.prove(trace)?);
+        }
+
+        Ok(ContinuationProof::new(boundary_states, segment_proofs))
+    }
+
+    /// Returns an opening of the specified column of the main trace segment at `point`, for the
+    /// provided trace committed to in `proof`.
+    ///
+    /// The opening attests that the polynomial interpolated from the column evaluates to
+    /// [TraceOpening::value()] at `point`, and can be checked against `proof` via
+    /// `verify_trace_opening()` function of the verifier crate. The trace is not re-proven: only
+    /// the main trace segment is extended again (to recompute the commitment included in the
+    /// proof), and the quotient of the column polynomial by `x - point` is proven to be of low
+    /// degree via FRI with the proof options of this prover.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `column` is not a column of the main trace segment.
+    /// * `point` is in the LDE domain of the proof.
+    /// * The main trace segment is not the one committed to in `proof` (e.g., because the proof
+    ///   was generated for a different trace or with different proof options).
+    fn open_trace_column<E>(
+        &self,
+        trace: &Self::Trace,
+        proof: &StarkProof,
+        column: usize,
+        point: E,
+    ) -> Result<TraceOpening<E, Self::HashFn>, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        Self: Sized,
+    {
+        opening::open_trace_column(self, trace, proof, column, point)
+    }
+
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Performs the actual proof generation procedure, generating the proof that the provided
     /// execution `trace` is valid against this prover's AIR.
+    ///
+    /// If a proving key is set in `config`, the domain and data precomputed for constraint
+    /// evaluation are taken from the key. If a checkpoint handler is set, it is invoked with a
+    /// serialized [TraceCheckpoint] once the prover has committed to all segments of the execution
+    /// trace. If a proof stream is set, every section of the proof is written into it as soon as
+    /// the section is finalized.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E>(&self, mut trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E>(
+        &self,
+        mut trace: Self::Trace,
+        config: ProvingConfig<'_, Self::BaseField>,
+    ) -> Result<StarkProof, ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
+        let (proving_key, mut on_checkpoint, mut on_section) = config.into_parts();
+
         // 0 ----- instantiate AIR and prover channel ---------------------------------------------
+        let recorder = MetricsRecorder::new();
 
         // serialize public inputs; these will be included in the seed for the public coin
         let pub_inputs = self.get_pub_inputs(&trace);
         let pub_inputs_elements = pub_inputs.to_elements();
+        let salt = self.salt_transcript().then(|| {
+            let mut salt = [0; 32];
+            self.new_rng().fill_bytes(&mut salt);
+            salt
+        });
 
         // create an instance of AIR for the provided parameters. this takes a generic description
         // of the computation (provided via AIR type), and creates a description of a specific
         // execution of the computation for the provided public inputs.
         let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
+        check_proof_inputs::<_, E>(&air)?;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "prove",
+            trace_width = air.trace_info().width(),
+            trace_length = air.trace_length(),
+            lde_domain_size = air.lde_domain_size()
+        )
+        .entered();
 
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
         let mut channel = ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new(
             &air,
-            pub_inputs_elements,
+            salt,
+            pub_inputs_elements.clone(),
+            |seed| self.new_public_coin(seed),
         );
+        if let Some(on_section) = on_section.as_deref_mut() {
+            channel.stream_sections(on_section);
+        }
+        if let Some(token) = self.cancellation_token() {
+            channel.observe_cancellation(token.clone());
+        }
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
+        start_phase(self, &recorder, ProvingPhase::TraceCommitment)?;
 
-        // build computation domain; this is used later for polynomial evaluations
+        // build computation domain; this is used later for polynomial evaluations. if a proving
+        // key was provided, the domain (along with data precomputed for constraint evaluation) is
+        // taken from the key instead
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let domain = StarkDomain::new(&air);
+        let new_domain;
+        let domain = match proving_key {
+            Some(proving_key) => {
+                proving_key.validate(&air)?;
+                proving_key.domain()
+            }
+            None => {
+                new_domain = build_domain(self, &air);
+                &new_domain
+            }
+        };
         #[cfg(feature = "std")]
         debug!(
             "Built domain of 2^{} elements in {} ms",
@@ -268,209 +994,180 @@ pub trait Prover {
         );
 
         // extend the main execution trace and build a Merkle tree from the extended trace
-        let (mut trace_lde, mut trace_polys): (Self::TraceLde<E>, TracePolyTable<E>) =
-            self.new_trace_lde(&trace.get_info(), trace.main_segment(), &domain);
-
-        // get the commitment to the main trace segment LDE
-        let main_trace_root = trace_lde.get_main_trace_commitment();
-
-        // commit to the LDE of the main trace by writing the root of its Merkle tree into
-        // the channel
-        channel.commit_trace(main_trace_root);
-
-        // build auxiliary trace segments (if any), and append the resulting segments to trace
-        // commitment and trace polynomial table structs
-        let mut aux_trace_segments = Vec::new();
-        let mut aux_trace_rand_elements = AuxTraceRandElements::new();
-        for i in 0..trace.layout().num_aux_segments() {
-            #[cfg(feature = "std")]
-            let now = Instant::now();
-
-            // draw a set of random elements required to build an auxiliary trace segment
-            let rand_elements = channel.get_aux_trace_segment_rand_elements(i);
-
-            // build the trace segment
-            let aux_segment = trace
-                .build_aux_segment(&aux_trace_segments, &rand_elements)
-                .expect("failed build auxiliary trace segment");
-            #[cfg(feature = "std")]
-            debug!(
-                "Built auxiliary trace segment of {} columns and 2^{} steps in {} ms",
-                aux_segment.num_cols(),
-                aux_segment.num_rows().ilog2(),
-                now.elapsed().as_millis()
+        let trace_info = trace.get_info();
+        let (trace_lde, trace_polys): (Self::TraceLde<E>, TracePolyTable<E>) = trace
+            .with_main_segment(|main_segment| {
+                self.new_trace_lde(&trace_info, main_segment, domain)
+            });
+
+        // commit to the main trace segment, and build, extend, and commit to auxiliary trace
+        // segments (if any)
+        let committed_trace = commit_to_trace(
+            self,
+            &recorder,
+            &air,
+            &mut channel,
+            domain,
+            trace_lde,
+            trace_polys,
+            |i, aux_segments, rand_elements| {
+                #[cfg(feature = "std")]
+                let now = Instant::now();
+                let aux_segment = trace
+                    .build_aux_segment(aux_segments, rand_elements)
+                    .ok_or(ProverError::MissingAuxTraceSegment(i))?;
+                #[cfg(feature = "std")]
+                debug!(
+                    "Built auxiliary trace segment of {} columns and 2^{} steps in {} ms",
+                    aux_segment.num_cols(),
+                    aux_segment.num_rows().ilog2(),
+                    now.elapsed().as_millis()
+                );
+                Ok(aux_segment)
+            },
+            |_, _| Ok(()),
+        )?;
+        let CommittedTrace {
+            trace_lde,
+            trace_polys,
+            aux_segments: aux_trace_segments,
+            aux_rand_elements: aux_trace_rand_elements,
+            trace_roots,
+        } = committed_trace;
+
+        // at this point, the prover has committed to all trace segments; if requested, save a
+        // checkpoint from which proof generation can be resumed
+        if let Some(on_checkpoint) = on_checkpoint.as_deref_mut() {
+            let mut checkpoint = Vec::new();
+            write_checkpoint::<E, Self::HashFn, _>(
+                &mut checkpoint,
+                &trace.get_info(),
+                &pub_inputs_elements,
+                salt.as_ref(),
+                trace.main_segment(),
+                &aux_trace_segments,
+                &trace_roots,
             );
-
-            // extend the auxiliary trace segment and build a Merkle tree from the extended trace
-            let (aux_segment_polys, aux_segment_root) =
-                trace_lde.add_aux_segment(&aux_segment, &domain);
-
-            // commit to the LDE of the extended auxiliary trace segment by writing the root of
-            // its Merkle tree into the channel
-            channel.commit_trace(aux_segment_root);
-
-            trace_polys.add_aux_segment(aux_segment_polys);
-            aux_trace_rand_elements.add_segment_elements(rand_elements);
-            aux_trace_segments.push(aux_segment);
+            on_checkpoint(&checkpoint);
         }
 
         // make sure the specified trace (including auxiliary segments) is valid against the AIR.
         // This checks validity of both, assertions and state transitions. We do this in debug
         // mode only because this is a very expensive operation.
         #[cfg(debug_assertions)]
-        trace.validate(&air, &aux_trace_segments, &aux_trace_rand_elements);
+        {
+            let report =
+                trace.check_constraints(&air, &aux_trace_segments, &aux_trace_rand_elements);
+            if !report.is_valid() {
+                return Err(ProverError::InvalidTrace(report.to_string()));
+            }
+        }
 
         // drop the main trace and aux trace segments as they are no longer needed
         drop(trace);
         drop(aux_trace_segments);
 
-        // 2 ----- evaluate constraints -----------------------------------------------------------
-        // evaluate constraints specified by the AIR over the constraint evaluation domain, and
-        // compute random linear combinations of these evaluations using coefficients drawn from
-        // the channel
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-        let constraint_coeffs = channel.get_constraint_composition_coeffs();
-        let evaluator = self.new_evaluator(&air, aux_trace_rand_elements, constraint_coeffs);
-        let composition_poly_trace = evaluator.evaluate(&trace_lde, &domain);
-        #[cfg(feature = "std")]
-        debug!(
-            "Evaluated constraints over domain of 2^{} elements in {} ms",
-            composition_poly_trace.num_rows().ilog2(),
-            now.elapsed().as_millis()
-        );
-
-        // 3 ----- commit to constraint evaluations -----------------------------------------------
-
-        // first, build a commitment to the evaluations of the constraint composition polynomial
-        // columns
-        let (constraint_commitment, composition_poly) = self.build_constraint_commitment::<E>(
-            composition_poly_trace,
-            air.context().num_constraint_composition_columns(),
-            &domain,
-        );
-
-        // then, commit to the evaluations of constraints by writing the root of the constraint
-        // Merkle tree into the channel
-        channel.commit_constraints(constraint_commitment.root());
-
-        // 4 ----- build DEEP composition polynomial ----------------------------------------------
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-
-        // draw an out-of-domain point z. Depending on the type of E, the point is drawn either
-        // from the base field or from an extension field defined by E.
-        //
-        // The purpose of sampling from the extension field here (instead of the base field) is to
-        // increase security. Soundness is limited by the size of the field that the random point
-        // is drawn from, and we can potentially save on performance by only drawing this point
-        // from an extension field, rather than increasing the size of the field overall.
-        let z = channel.get_ood_point();
-
-        // evaluate trace and constraint polynomials at the OOD point z, and send the results to
-        // the verifier. the trace polynomials are actually evaluated over two points: z and z * g,
-        // where g is the generator of the trace domain.
-        let ood_trace_states = trace_polys.get_ood_frame(z);
-        channel.send_ood_trace_states(&ood_trace_states);
-
-        let ood_evaluations = composition_poly.evaluate_at(z);
-        channel.send_ood_constraint_evaluations(&ood_evaluations);
-
-        // draw random coefficients to use during DEEP polynomial composition, and use them to
-        // initialize the DEEP composition polynomial
-        let deep_coefficients = channel.get_deep_composition_coeffs();
-        let mut deep_composition_poly = DeepCompositionPoly::new(z, deep_coefficients);
-
-        // combine all trace polynomials together and merge them into the DEEP composition
-        // polynomial
-        deep_composition_poly.add_trace_polys(trace_polys, ood_trace_states);
-
-        // merge columns of constraint composition polynomial into the DEEP composition polynomial;
-        deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
-
-        #[cfg(feature = "std")]
-        debug!(
-            "Built DEEP composition polynomial of degree {} in {} ms",
-            deep_composition_poly.degree(),
-            now.elapsed().as_millis()
-        );
-
-        // make sure the degree of the DEEP composition polynomial is equal to trace polynomial
-        // degree minus 1.
-        assert_eq!(domain.trace_length() - 2, deep_composition_poly.degree());
-
-        // 5 ----- evaluate DEEP composition polynomial over LDE domain ---------------------------
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-        let deep_evaluations = deep_composition_poly.evaluate(&domain);
-        // we check the following condition in debug mode only because infer_degree is an expensive
-        // operation
-        debug_assert_eq!(
-            domain.trace_length() - 2,
-            infer_degree(&deep_evaluations, domain.offset())
-        );
-        #[cfg(feature = "std")]
-        debug!(
-            "Evaluated DEEP composition polynomial over LDE domain (2^{} elements) in {} ms",
-            domain.lde_domain_size().ilog2(),
-            now.elapsed().as_millis()
-        );
-
-        // 6 ----- compute FRI layers for the composition polynomial ------------------------------
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-        let mut fri_prover = FriProver::new(air.options().to_fri_options());
-        fri_prover.build_layers(&mut channel, deep_evaluations);
-        #[cfg(feature = "std")]
-        debug!(
-            "Computed {} FRI layers from composition polynomial evaluations in {} ms",
-            fri_prover.num_layers(),
-            now.elapsed().as_millis()
-        );
-
-        // 7 ----- determine query positions ------------------------------------------------------
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-
-        // apply proof-of-work to the query seed
-        channel.grind_query_seed();
-
-        // generate pseudo-random query positions
-        let query_positions = channel.get_query_positions();
-        #[cfg(feature = "std")]
-        debug!(
-            "Determined {} unique query positions in {} ms",
-            query_positions.len(),
-            now.elapsed().as_millis()
-        );
-
-        // 8 ----- build proof object -------------------------------------------------------------
-        #[cfg(feature = "std")]
-        let now = Instant::now();
+        complete_proof(
+            self,
+            recorder,
+            &air,
+            channel,
+            domain,
+            trace_lde,
+            trace_polys,
+            aux_trace_rand_elements,
+        )
+    }
 
-        // generate FRI proof
-        let fri_proof = fri_prover.build_proof(&query_positions);
+    /// Resumes proof generation from the specified serialized [TraceCheckpoint].
+    #[doc(hidden)]
+    fn generate_proof_from_checkpoint<E>(
+        &self,
+        pub_inputs: <<Self as Prover>::Air as Air>::PublicInputs,
+        checkpoint: &[u8],
+    ) -> Result<StarkProof, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let recorder = MetricsRecorder::new();
+        let checkpoint = TraceCheckpoint::<E, Self::HashFn>::read_from_bytes(checkpoint)
+            .map_err(|err| ProverError::InvalidCheckpoint(err.to_string()))?;
+        let (trace_info, checkpoint_pub_inputs, salt, main_segment, aux_segments, trace_roots) =
+            checkpoint.into_parts();
 
-        // query the execution trace at the selected position; for each query, we need the
-        // state of the trace at that position + Merkle authentication path
-        let trace_queries = trace_lde.query(&query_positions);
+        // 0 ----- instantiate AIR and prover channel ---------------------------------------------
 
-        // query the constraint commitment at the selected positions; for each query, we need just
-        // a Merkle authentication path. this is because constraint evaluations for each step are
-        // merged into a single value and Merkle authentication paths contain these values already
-        let constraint_queries = constraint_commitment.query(&query_positions);
+        let pub_inputs_elements = pub_inputs.to_elements();
+        if pub_inputs_elements != checkpoint_pub_inputs {
+            return Err(ProverError::InvalidCheckpoint(
+                "public inputs are different from the public inputs in the checkpoint".to_string(),
+            ));
+        }
 
-        // build the proof object
-        let proof = channel.build_proof(
-            trace_queries,
-            constraint_queries,
-            fri_proof,
-            query_positions.len(),
+        let air = Self::Air::new(trace_info.clone(), pub_inputs, self.options().clone());
+        check_proof_inputs::<_, E>(&air)?;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "prove",
+            trace_width = air.trace_info().width(),
+            trace_length = air.trace_length(),
+            lde_domain_size = air.lde_domain_size()
+        )
+        .entered();
+        let mut channel = ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new(
+            &air,
+            salt,
+            pub_inputs_elements,
+            |seed| self.new_public_coin(seed),
         );
-        #[cfg(feature = "std")]
-        debug!("Built proof object in {} ms", now.elapsed().as_millis());
+        if let Some(token) = self.cancellation_token() {
+            channel.observe_cancellation(token.clone());
+        }
 
-        Ok(proof)
+        // 1 ----- replay commitments to the execution trace --------------------------------------
+        start_phase(self, &recorder, ProvingPhase::TraceCommitment)?;
+        let domain = build_domain(self, &air);
+
+        // re-build the LDEs of all trace segments and make sure their commitments match the ones
+        // recorded in the checkpoint; random elements drawn from the channel are the same as the
+        // ones used to build auxiliary segments, as long as the commitments match
+        let (trace_lde, trace_polys): (Self::TraceLde<E>, TracePolyTable<E>) =
+            self.new_trace_lde(&trace_info, &main_segment, &domain);
+        drop(main_segment);
+
+        let mut aux_segments = aux_segments.into_iter();
+        let committed_trace = commit_to_trace(
+            self,
+            &recorder,
+            &air,
+            &mut channel,
+            &domain,
+            trace_lde,
+            trace_polys,
+            |i, _, _| aux_segments.next().ok_or(ProverError::MissingAuxTraceSegment(i)),
+            |i, root| match i {
+                _ if *root == trace_roots[i] => Ok(()),
+                0 => Err(ProverError::InvalidCheckpoint(
+                    "commitment to the main trace segment does not match the checkpoint"
+                        .to_string(),
+                )),
+                _ => Err(ProverError::InvalidCheckpoint(format!(
+                    "commitment to auxiliary trace segment {} does not match the checkpoint",
+                    i - 1
+                ))),
+            },
+        )?;
+
+        complete_proof(
+            self,
+            recorder,
+            &air,
+            channel,
+            &domain,
+            committed_trace.trace_lde,
+            committed_trace.trace_polys,
+            committed_trace.aux_rand_elements,
+        )
     }
 
     /// Extends constraint composition polynomial over the LDE domain and builds a commitment to
@@ -483,58 +1180,474 @@ pub trait Prover {
     ///
     /// The commitment is computed by hashing each row in the evaluation matrix, and then building
     /// a Merkle tree from the resulting hashes.
+    ///
+    /// By default, the extension and the commitment are computed using [CpuBackend], and the
+    /// evaluations are stored on disk if [Prover::lde_directory()] returns a directory. Provers
+    /// which use a different [ProverBackend] can override this method to invoke
+    /// [build_constraint_commitment()] function with their backend.
+    ///
+    /// # Errors
+    /// Returns an error if the evaluations are stored on disk and could not be written to or
+    /// read from disk.
+    #[allow(clippy::type_complexity)]
     fn build_constraint_commitment<E>(
         &self,
         composition_poly_trace: CompositionPolyTrace<E>,
         num_trace_poly_columns: usize,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> (ConstraintCommitment<E, Self::HashFn>, CompositionPoly<E>)
+    ) -> Result<(ConstraintCommitment<E, Self::HashFn>, CompositionPoly<E>), ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
-        // first, build constraint composition polynomial from its trace as follows:
-        // - interpolate the trace into a polynomial in coefficient form
-        // - "break" the polynomial into a set of column polynomials each of degree equal to
-        //   trace_length - 1
         #[cfg(feature = "std")]
-        let now = Instant::now();
-        let composition_poly =
-            CompositionPoly::new(composition_poly_trace, domain, num_trace_poly_columns);
-        #[cfg(feature = "std")]
-        debug!(
-            "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
-            composition_poly.num_columns(),
-            composition_poly.column_degree(),
-            now.elapsed().as_millis()
-        );
+        if let Some(directory) = self.lde_directory() {
+            return backend::build_constraint_commitment_on_disk(
+                composition_poly_trace,
+                num_trace_poly_columns,
+                domain,
+                &directory,
+            )
+            .map_err(matrix::storage_error);
+        }
 
-        // then, evaluate composition polynomial columns over the LDE domain
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-        let composed_evaluations = RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(
-            composition_poly.data(),
+        Ok(build_constraint_commitment(
+            &CpuBackend,
+            composition_poly_trace,
+            num_trace_poly_columns,
             domain,
-        );
-        #[cfg(feature = "std")]
-        debug!(
-            "Evaluated {} composition polynomial columns over LDE domain (2^{} elements) in {} ms",
-            composed_evaluations.num_cols(),
-            composed_evaluations.num_rows().ilog2(),
-            now.elapsed().as_millis()
-        );
+        ))
+    }
+}
 
-        // finally, build constraint evaluation commitment
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-        let commitment = composed_evaluations.commit_to_rows();
-        let constraint_commitment = ConstraintCommitment::new(composed_evaluations, commitment);
-        #[cfg(feature = "std")]
-        debug!(
-            "Computed constraint evaluation commitment (Merkle tree of depth {}) in {} ms",
-            constraint_commitment.tree_depth(),
-            now.elapsed().as_millis()
-        );
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure a proof can be generated for the specified AIR instance with the proof options of
+/// the instance, and that the data structures built during proof generation can be allocated.
+///
+/// This is checked before any expensive work is done so that invalid inputs result in an error
+/// rather than in a panic deep inside of the proof generation procedure.
+fn check_proof_inputs<A, E>(air: &A) -> Result<(), ProverError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+{
+    air.context()
+        .validate_options()
+        .map_err(|err| ProverError::InvalidProofOptions(err.to_string()))?;
+
+    let lde_domain_size = air.lde_domain_size();
+    if lde_domain_size.ilog2() > A::BaseField::TWO_ADICITY {
+        return Err(ProverError::InvalidTraceShape(format!(
+            "LDE domain of 2^{} elements is too large for the base field; at most 2^{} elements are supported",
+            lde_domain_size.ilog2(),
+            A::BaseField::TWO_ADICITY
+        )));
+    }
+
+    let num_queries = air.options().num_queries();
+    if num_queries >= lde_domain_size {
+        return Err(ProverError::InvalidProofOptions(format!(
+            "number of queries must be smaller than the LDE domain size of {lde_domain_size}, but was {num_queries}"
+        )));
+    }
+
+    // make sure the largest matrices built by the prover do not exceed the maximum size of an
+    // allocation; this can happen on platforms with 32-bit pointers
+    let layout = air.trace_layout();
+    let num_composition_columns = air.context().num_constraint_composition_columns();
+    let widest_segment = (0..layout.num_aux_segments())
+        .map(|i| layout.get_aux_segment_width(i) * E::ELEMENT_BYTES)
+        .chain([
+            layout.main_trace_width() * E::BaseField::ELEMENT_BYTES,
+            num_composition_columns * E::ELEMENT_BYTES,
+        ])
+        .max()
+        .unwrap_or(0);
+    let matrix_bytes = lde_domain_size as u128 * widest_segment as u128;
+    if matrix_bytes > isize::MAX as u128 {
+        return Err(ProverError::InsufficientMemory(format!(
+            "extended trace requires an allocation of {matrix_bytes} bytes, but at most {} bytes can be allocated",
+            isize::MAX
+        )));
+    }
+
+    Ok(())
+}
+
+/// Makes sure the dimensions of the specified auxiliary trace segment match the trace layout of
+/// the AIR instance.
+fn check_aux_segment<A, E>(
+    air: &A,
+    segment_idx: usize,
+    aux_segment: &ColMatrix<E>,
+) -> Result<(), ProverError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+{
+    let expected_width = air.trace_layout().get_aux_segment_width(segment_idx);
+    if aux_segment.num_cols() != expected_width {
+        return Err(ProverError::InvalidTraceShape(format!(
+            "auxiliary trace segment {segment_idx} must have {expected_width} columns, but had {}",
+            aux_segment.num_cols()
+        )));
+    }
+    if aux_segment.num_rows() != air.trace_length() {
+        return Err(ProverError::InvalidTraceShape(format!(
+            "auxiliary trace segment {segment_idx} must have {} rows, but had {}",
+            air.trace_length(),
+            aux_segment.num_rows()
+        )));
+    }
+    Ok(())
+}
+
+/// Builds a STARK domain for the specified AIR instance; if the prover has a periodic column
+/// cache, values of periodic columns are taken from (or added to) the cache.
+#[cfg_attr(not(feature = "std"), allow(unused_mut, unused_variables))]
+fn build_domain<P: Prover + ?Sized>(prover: &P, air: &P::Air) -> StarkDomain<P::BaseField> {
+    let mut domain = StarkDomain::new(air);
+    #[cfg(feature = "std")]
+    if let Some(cache) = prover.periodic_column_cache() {
+        domain.set_periodic_values(cache.get_or_build(air));
+    }
+    domain
+}
+
+/// Makes sure proof generation by the specified prover has not been cancelled, and notifies the
+/// prover and the metrics recorder about the start of the specified phase.
+fn start_phase<P: Prover + ?Sized>(
+    prover: &P,
+    recorder: &MetricsRecorder,
+    phase: ProvingPhase,
+) -> Result<(), ProverError> {
+    if prover.cancellation_token().is_some_and(|token| token.is_cancelled()) {
+        return Err(ProverError::Cancelled(phase));
+    }
+    recorder.start_phase(phase);
+    prover.on_phase_start(phase);
+    Ok(())
+}
+
+/// Segments of an execution trace committed to by the prover, along with their low-degree
+/// extensions and polynomials.
+struct CommittedTrace<E: FieldElement, L, D> {
+    trace_lde: L,
+    trace_polys: TracePolyTable<E>,
+    aux_segments: Vec<ColMatrix<E>>,
+    aux_rand_elements: AuxTraceRandElements<E>,
+    trace_roots: Vec<D>,
+}
+
+/// Commits to all segments of the execution trace, starting with the already extended main
+/// segment.
+///
+/// Auxiliary trace segments are obtained from `get_aux_segment`, which is invoked with the index
+/// of the segment, all previously obtained segments, and the random elements drawn for the
+/// segment; the segments are then extended via `trace_lde`. Before the root of a segment
+/// commitment is written into the channel, it is passed to `check_root` together with the index
+/// of the segment (starting with 0 for the main segment).
+///
+/// This is shared between regular proof generation and proof generation resumed from a
+/// checkpoint.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn commit_to_trace<P, E>(
+    prover: &P,
+    recorder: &MetricsRecorder,
+    air: &P::Air,
+    channel: &mut ProverChannel<'_, P::Air, E, P::HashFn, P::RandomCoin>,
+    domain: &StarkDomain<P::BaseField>,
+    mut trace_lde: P::TraceLde<E>,
+    mut trace_polys: TracePolyTable<E>,
+    mut get_aux_segment: impl FnMut(usize, &[ColMatrix<E>], &[E]) -> Result<ColMatrix<E>, ProverError>,
+    check_root: impl Fn(usize, &<P::HashFn as Hasher>::Digest) -> Result<(), ProverError>,
+) -> Result<CommittedTrace<E, P::TraceLde<E>, <P::HashFn as Hasher>::Digest>, ProverError>
+where
+    P: Prover + ?Sized,
+    E: FieldElement<BaseField = P::BaseField>,
+{
+    // commit to the LDE of the main trace by writing the root of its Merkle tree into the channel
+    let main_trace_root = trace_lde.get_main_trace_commitment();
+    check_root(0, &main_trace_root)?;
+    channel.commit_trace(main_trace_root);
+    let mut trace_roots = vec![main_trace_root];
+
+    // build auxiliary trace segments (if any), and append the resulting segments to trace
+    // commitment and trace polynomial table structs
+    let mut aux_segments = Vec::new();
+    let mut aux_rand_elements = AuxTraceRandElements::new();
+    for i in 0..air.trace_layout().num_aux_segments() {
+        start_phase(prover, recorder, ProvingPhase::AuxTraceCommitment(i))?;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("aux_trace_segment", segment = i).entered();
+
+        // draw a set of random elements required to build an auxiliary trace segment, and build
+        // the segment
+        let rand_elements = channel.get_aux_trace_segment_rand_elements(i);
+        let aux_segment = get_aux_segment(i, &aux_segments, &rand_elements)?;
+        check_aux_segment(air, i, &aux_segment)?;
+
+        // extend the auxiliary trace segment and build a Merkle tree from the extended trace
+        let (aux_segment_polys, aux_segment_root) =
+            trace_lde.add_aux_segment(&aux_segment, domain)?;
+
+        // commit to the LDE of the extended auxiliary trace segment by writing the root of its
+        // Merkle tree into the channel
+        check_root(i + 1, &aux_segment_root)?;
+        channel.commit_trace(aux_segment_root);
+        trace_roots.push(aux_segment_root);
+
+        trace_polys.add_aux_segment(aux_segment_polys);
+        aux_rand_elements.add_segment_elements(rand_elements);
+        aux_segments.push(aux_segment);
+    }
+
+    Ok(CommittedTrace {
+        trace_lde,
+        trace_polys,
+        aux_segments,
+        aux_rand_elements,
+        trace_roots,
+    })
+}
+
+/// Completes proof generation for a prover which has already committed to all segments of the
+/// execution trace.
+///
+/// This executes all steps of the protocol starting with constraint evaluation, and is shared
+/// between regular proof generation and proof generation resumed from a checkpoint.
+#[allow(clippy::too_many_arguments)]
+fn complete_proof<P, E>(
+    prover: &P,
+    recorder: MetricsRecorder,
+    air: &P::Air,
+    mut channel: ProverChannel<'_, P::Air, E, P::HashFn, P::RandomCoin>,
+    domain: &StarkDomain<P::BaseField>,
+    mut trace_lde: P::TraceLde<E>,
+    trace_polys: TracePolyTable<E>,
+    aux_trace_rand_elements: AuxTraceRandElements<E>,
+) -> Result<StarkProof, ProverError>
+where
+    P: Prover + ?Sized,
+    E: FieldElement<BaseField = P::BaseField>,
+{
+    // 2 ----- evaluate constraints -----------------------------------------------------------
+    // evaluate constraints specified by the AIR over the constraint evaluation domain, and
+    // compute random linear combinations of these evaluations using coefficients drawn from
+    // the channel
+    start_phase(prover, &recorder, ProvingPhase::ConstraintEvaluation)?;
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!(
+        "constraint_evaluation",
+        ce_domain_size = domain.ce_domain_size(),
+        num_constraints = air.context().num_transition_constraints()
+    )
+    .entered();
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+    let constraint_coeffs = channel.get_constraint_composition_coeffs();
+    let evaluator = prover.new_evaluator(air, aux_trace_rand_elements, constraint_coeffs);
+    let composition_poly_trace = evaluator.evaluate(&trace_lde, domain)?;
+    #[cfg(feature = "tracing")]
+    drop(span);
+    #[cfg(feature = "std")]
+    debug!(
+        "Evaluated constraints over domain of 2^{} elements in {} ms",
+        composition_poly_trace.num_rows().ilog2(),
+        now.elapsed().as_millis()
+    );
+
+    // in the low-memory mode, the extended trace is no longer needed; the rows at queried
+    // positions will be re-computed from trace polynomials
+    if prover.low_memory_mode() {
+        trace_lde.discard_evaluations(&trace_polys, domain);
+    }
+
+    // 3 ----- commit to constraint evaluations -----------------------------------------------
+    start_phase(prover, &recorder, ProvingPhase::ConstraintCommitment)?;
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!(
+        "constraint_commitment",
+        lde_domain_size = domain.lde_domain_size(),
+        num_columns = air.context().num_constraint_composition_columns()
+    )
+    .entered();
+
+    // first, build a commitment to the evaluations of the constraint composition polynomial
+    // columns
+    let (mut constraint_commitment, composition_poly) = prover.build_constraint_commitment::<E>(
+        composition_poly_trace,
+        air.context().num_constraint_composition_columns(),
+        domain,
+    )?;
+    if prover.low_memory_mode() {
+        constraint_commitment.discard_evaluations(composition_poly.data().clone(), domain);
+    }
+
+    // then, commit to the evaluations of constraints by writing the root of the constraint
+    // Merkle tree into the channel
+    channel.commit_constraints(constraint_commitment.root());
+    #[cfg(feature = "tracing")]
+    drop(span);
+
+    // 4 ----- build DEEP composition polynomial ----------------------------------------------
+    start_phase(prover, &recorder, ProvingPhase::DeepComposition)?;
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!("deep_composition", lde_domain_size = domain.lde_domain_size())
+        .entered();
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+
+    // draw out-of-domain points z. Depending on the type of E, the points are drawn either
+    // from the base field or from an extension field defined by E.
+    //
+    // The purpose of sampling from the extension field here (instead of the base field) is to
+    // increase security. Soundness is limited by the size of the field that the random point
+    // is drawn from, and we can potentially save on performance by only drawing this point
+    // from an extension field, rather than increasing the size of the field overall. Drawing
+    // more than one point improves soundness further without increasing the extension degree.
+    let num_ood_points = air.options().num_ood_points();
+    let mut ood_points = Vec::with_capacity(num_ood_points);
+    let mut ood_trace_states = Vec::with_capacity(num_ood_points);
+    let mut ood_evaluations = Vec::with_capacity(num_ood_points);
+    for _ in 0..num_ood_points {
+        let z = channel.get_ood_point();
+
+        // evaluate trace and constraint polynomials at the OOD point z, and send the results to
+        // the verifier. the trace polynomials are actually evaluated over two points: z and
+        // z * g, where g is the generator of the trace domain. the next point is drawn only
+        // after the public coin has been reseeded with these evaluations.
+        let trace_states = trace_polys.get_ood_frame(z);
+        channel.send_ood_trace_states(&trace_states);
+
+        let evaluations = composition_poly.evaluate_at(z);
+        channel.send_ood_constraint_evaluations(&evaluations);
+
+        ood_points.push(z);
+        ood_trace_states.push(trace_states);
+        ood_evaluations.push(evaluations);
+    }
+    channel.finish_ood_frame();
+
+    // draw random coefficients to use during DEEP polynomial composition, and use them to
+    // initialize the DEEP composition polynomial
+    let deep_coefficients = channel.get_deep_composition_coeffs();
+    let mut deep_composition_poly = DeepCompositionPoly::new(ood_points, deep_coefficients);
+
+    // combine all trace polynomials together and merge them into the DEEP composition
+    // polynomial
+    deep_composition_poly.add_trace_polys(trace_polys, ood_trace_states);
+
+    // merge columns of constraint composition polynomial into the DEEP composition polynomial;
+    deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
+
+    #[cfg(feature = "std")]
+    debug!(
+        "Built DEEP composition polynomial of degree {} in {} ms",
+        deep_composition_poly.degree(),
+        now.elapsed().as_millis()
+    );
+
+    // make sure the degree of the DEEP composition polynomial is equal to trace polynomial
+    // degree minus 1; this may not be the case if the trace does not satisfy the constraints or
+    // if the AIR specifies wrong constraint degrees
+    if deep_composition_poly.degree() != domain.trace_length() - 2 {
+        return Err(ProverError::UnexpectedDeepCompositionDegree(
+            domain.trace_length() - 2,
+            deep_composition_poly.degree(),
+        ));
+    }
 
-        (constraint_commitment, composition_poly)
+    // 5 ----- evaluate DEEP composition polynomial over LDE domain ---------------------------
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+    let deep_evaluations = deep_composition_poly.evaluate(domain);
+    // we check the following condition in debug mode only because infer_degree is an expensive
+    // operation
+    debug_assert_eq!(domain.trace_length() - 2, infer_degree(&deep_evaluations, domain.offset()));
+    #[cfg(feature = "std")]
+    debug!(
+        "Evaluated DEEP composition polynomial over LDE domain (2^{} elements) in {} ms",
+        domain.lde_domain_size().ilog2(),
+        now.elapsed().as_millis()
+    );
+    #[cfg(feature = "tracing")]
+    drop(span);
+
+    // 6 ----- compute FRI layers for the composition polynomial ------------------------------
+    start_phase(prover, &recorder, ProvingPhase::FriLayers)?;
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!(
+        "fri_layers",
+        domain_size = deep_evaluations.len(),
+        folding_factor = air.options().to_fri_options().folding_factor()
+    )
+    .entered();
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+    let mut fri_prover = FriProver::new(air.options().to_fri_options());
+    fri_prover.build_layers(&mut channel, deep_evaluations);
+    if channel.is_cancelled() {
+        return Err(ProverError::Cancelled(ProvingPhase::FriLayers));
     }
+    #[cfg(feature = "tracing")]
+    drop(span);
+    #[cfg(feature = "std")]
+    debug!(
+        "Computed {} FRI layers from composition polynomial evaluations in {} ms",
+        fri_prover.num_layers(),
+        now.elapsed().as_millis()
+    );
+
+    // 7 ----- determine query positions ------------------------------------------------------
+    start_phase(prover, &recorder, ProvingPhase::Grinding)?;
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!("grinding", grinding_factor = air.options().grinding_factor())
+        .entered();
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+
+    // apply proof-of-work to the query seed
+    channel.grind_query_seed()?;
+    #[cfg(feature = "tracing")]
+    drop(span);
+
+    // generate pseudo-random query positions
+    let query_positions = channel.get_query_positions();
+    #[cfg(feature = "std")]
+    debug!(
+        "Determined {} unique query positions in {} ms",
+        query_positions.len(),
+        now.elapsed().as_millis()
+    );
+
+    // 8 ----- build proof object -------------------------------------------------------------
+    start_phase(prover, &recorder, ProvingPhase::ProofAssembly)?;
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("queries", num_queries = query_positions.len()).entered();
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+
+    // generate FRI proof
+    let fri_proof = fri_prover.build_proof(&query_positions);
+
+    // query the execution trace at the selected position; for each query, we need the
+    // state of the trace at that position + Merkle authentication path
+    let trace_queries = trace_lde.query(&query_positions)?;
+
+    // query the constraint commitment at the selected positions; for each query, we need just
+    // a Merkle authentication path. this is because constraint evaluations for each step are
+    // merged into a single value and Merkle authentication paths contain these values already
+    let constraint_queries = constraint_commitment.query(&query_positions)?;
+
+    // build the proof object
+    let proof =
+        channel.build_proof(trace_queries, constraint_queries, fri_proof, query_positions.len());
+    #[cfg(feature = "std")]
+    debug!("Built proof object in {} ms", now.elapsed().as_millis());
+
+    recorder.finish(&proof);
+    prover.on_phase_start(ProvingPhase::Done);
+    Ok(proof)
 }
@@ -0,0 +1,232 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{get_evaluation_offsets, ColMatrix, Segment};
+use crate::{StarkDomain, DEFAULT_SEGMENT_WIDTH};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, MerkleTree};
+use math::FieldElement;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+use utils::{
+    batch_iter_mut, collections::Vec, uninit_vector, Deserializable, Serializable, SliceReader,
+};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of base field columns in a single segment of the matrix.
+const N: usize = DEFAULT_SEGMENT_WIDTH;
+
+/// Number of rows read from disk at once when rows of the matrix are hashed.
+const ROWS_PER_CHUNK: usize = 1 << 12;
+
+/// Counter used to generate unique names of files created by this process.
+static NEXT_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+
+// DISK MATRIX
+// ================================================================================================
+
+/// Low-degree extension of a set of polynomials stored in a temporary file on disk.
+///
+/// The columns of the matrix are split into segments of [DEFAULT_SEGMENT_WIDTH] base field
+/// columns each. Each segment is evaluated over the LDE domain in memory, and is then written
+/// into the file as a contiguous block of rows; thus, at most one segment of the matrix is held in
+/// memory at any time. Values are stored in their canonical serialized form.
+///
+/// The file is deleted when the matrix is dropped.
+#[derive(Debug)]
+pub(crate) struct DiskMatrix<E: FieldElement> {
+    file: Mutex<File>,
+    path: PathBuf,
+    num_rows: usize,
+    num_base_cols: usize,
+    num_segments: usize,
+    _element: PhantomData<E>,
+}
+
+impl<E: FieldElement> DiskMatrix<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the provided polynomials over the LDE domain, writes the evaluations into a new
+    /// file in the specified directory, and builds a Merkle tree from the hashes of all rows of
+    /// the resulting matrix.
+    ///
+    /// The Merkle tree is the same as the one which would be built from the rows of an in-memory
+    /// [RowMatrix](super::RowMatrix) containing the same evaluations. Rows are read back from disk
+    /// for hashing in sequential order.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be created, written, or read.
+    pub fn evaluate_polys_over<H>(
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+        directory: &Path,
+    ) -> io::Result<(Self, MerkleTree<H>)>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        let file_id = NEXT_FILE_ID.fetch_add(1, Ordering::Relaxed);
+        let path = directory.join(format!("winter_lde_{}_{file_id}.bin", process::id()));
+        let file = OpenOptions::new().read(true).write(true).create_new(true).open(&path)?;
+
+        let offsets = get_evaluation_offsets::<E>(
+            polys.num_rows(),
+            domain.trace_to_lde_blowup(),
+            domain.offset(),
+        );
+        let num_base_cols = polys.num_base_cols();
+        let mut matrix = DiskMatrix {
+            file: Mutex::new(file),
+            path,
+            num_rows: offsets.len(),
+            num_base_cols,
+            num_segments: num_base_cols.div_ceil(N),
+            _element: PhantomData,
+        };
+
+        // evaluate the polynomials one segment at a time, and write each segment to disk
+        {
+            let file = matrix.file.get_mut().expect("file lock poisoned");
+            let mut writer = BufWriter::new(&mut *file);
+            for i in 0..matrix.num_segments {
+                let segment = Segment::<E::BaseField, N>::new(
+                    polys,
+                    i * N,
+                    &offsets,
+                    domain.trace_twiddles(),
+                );
+                let mut bytes = Vec::with_capacity(segment.len() * N * E::BaseField::ELEMENT_BYTES);
+                for row in segment.iter() {
+                    row.write_into(&mut bytes);
+                }
+                writer.write_all(&bytes)?;
+            }
+            writer.flush()?;
+        }
+
+        let tree = matrix.commit_to_rows()?;
+        Ok((matrix, tree))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in this matrix.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of columns in this matrix.
+    pub fn num_cols(&self) -> usize {
+        self.num_base_cols / E::EXTENSION_DEGREE
+    }
+
+    /// Reads the row at the specified position into the provided target.
+    ///
+    /// # Errors
+    /// Returns an error if the row could not be read from disk.
+    pub fn read_row_into(&self, position: usize, target: &mut [E]) -> io::Result<()> {
+        debug_assert_eq!(self.num_cols(), target.len());
+        let row = self.read_base_rows(position, 1)?;
+        target.copy_from_slice(E::slice_from_base_elements(&row[..self.num_base_cols]));
+        Ok(())
+    }
+
+    /// Returns rows at the specified positions.
+    ///
+    /// The rows are read from disk in the order of their positions (rather than in the order in
+    /// which the positions are specified) to keep disk access as sequential as possible.
+    ///
+    /// # Errors
+    /// Returns an error if the rows could not be read from disk.
+    pub fn read_rows(&self, positions: &[usize]) -> io::Result<Vec<Vec<E>>> {
+        let mut order = (0..positions.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&i| positions[i]);
+
+        let mut result = vec![Vec::new(); positions.len()];
+        for i in order {
+            let mut row = vec![E::ZERO; self.num_cols()];
+            self.read_row_into(positions[i], &mut row)?;
+            result[i] = row;
+        }
+        Ok(result)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Hashes all rows of this matrix reading them from disk sequentially in chunks, and builds a
+    /// Merkle tree from the resulting hashes.
+    fn commit_to_rows<H>(&self) -> io::Result<MerkleTree<H>>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows) };
+        let row_width = self.num_segments * N;
+        for (chunk_idx, hashes) in row_hashes.chunks_mut(ROWS_PER_CHUNK).enumerate() {
+            let rows = self.read_base_rows(chunk_idx * ROWS_PER_CHUNK, hashes.len())?;
+            batch_iter_mut!(
+                hashes,
+                128, // min batch size
+                |batch: &mut [H::Digest], batch_offset: usize| {
+                    for (i, row_hash) in batch.iter_mut().enumerate() {
+                        let row_start = (batch_offset + i) * row_width;
+                        let row = &rows[row_start..row_start + self.num_base_cols];
+                        *row_hash = H::hash_elements(row);
+                    }
+                }
+            );
+        }
+
+        Ok(MerkleTree::new(row_hashes).expect("failed to construct LDE Merkle tree"))
+    }
+
+    /// Reads `num_rows` consecutive rows starting at the specified position, and returns them as
+    /// a single vector of base field elements in row-major order; each row contains values of all
+    /// segments (including the padding of the last segment).
+    fn read_base_rows(&self, position: usize, num_rows: usize) -> io::Result<Vec<E::BaseField>> {
+        let element_bytes = E::BaseField::ELEMENT_BYTES;
+        let segment_row_bytes = N * element_bytes;
+        let row_width = self.num_segments * N;
+
+        let mut file = self.file.lock().expect("file lock poisoned");
+        let mut bytes = vec![0u8; num_rows * segment_row_bytes];
+        let mut result = vec![E::BaseField::ZERO; num_rows * row_width];
+        for segment_idx in 0..self.num_segments {
+            let offset = (segment_idx * self.num_rows + position) * segment_row_bytes;
+            file.seek(SeekFrom::Start(offset as u64))?;
+            file.read_exact(&mut bytes)?;
+
+            let mut reader = SliceReader::new(&bytes);
+            for row in result.chunks_mut(row_width) {
+                let values = E::BaseField::read_batch_from(&mut reader, N)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+                row[segment_idx * N..(segment_idx + 1) * N].copy_from_slice(&values);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<E: FieldElement> Drop for DiskMatrix<E> {
+    fn drop(&mut self) {
+        // the file is temporary, and thus, failure to remove it is not critical
+        let _ = fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColMatrix, RowMatrix};
+
+#[cfg(feature = "std")]
+use super::DiskMatrix;
+use crate::{ProverError, StarkDomain};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+#[cfg(feature = "std")]
+use utils::string::ToString;
+
+// LDE MATRIX
+// ================================================================================================
+
+/// Low-degree extension of a set of polynomials which is either kept in memory, stored on disk,
+/// or is re-computed from the polynomials on demand.
+///
+/// The latter is used by the prover in the low-memory mode: once the full extension is no longer
+/// needed, the evaluations are discarded, and only rows at the queried positions are re-computed.
+#[derive(Debug)]
+pub(crate) enum LdeMatrix<E: FieldElement> {
+    /// Evaluations of the polynomials over the LDE domain.
+    Evaluations(RowMatrix<E>),
+    /// Evaluations of the polynomials over the LDE domain stored in a file on disk.
+    #[cfg(feature = "std")]
+    Disk(DiskMatrix<E>),
+    /// Polynomials in coefficient form, along with the parameters of the LDE domain.
+    Polys {
+        polys: ColMatrix<E>,
+        num_rows: usize,
+        domain_generator: E::BaseField,
+        domain_offset: E::BaseField,
+    },
+}
+
+impl<E: FieldElement> LdeMatrix<E> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in this matrix (i.e., the size of the LDE domain).
+    pub fn num_rows(&self) -> usize {
+        match self {
+            Self::Evaluations(evaluations) => evaluations.num_rows(),
+            #[cfg(feature = "std")]
+            Self::Disk(evaluations) => evaluations.num_rows(),
+            Self::Polys { num_rows, .. } => *num_rows,
+        }
+    }
+
+    /// Returns evaluations of the polynomials over the LDE domain.
+    ///
+    /// # Panics
+    /// Panics if the evaluations are not stored in memory.
+    #[cfg(test)]
+    pub fn evaluations(&self) -> &RowMatrix<E> {
+        match self {
+            Self::Evaluations(evaluations) => evaluations,
+            _ => panic!("low-degree extension evaluations are not stored in memory"),
+        }
+    }
+
+    /// Reads a row of this matrix at the specified position in the LDE domain into the provided
+    /// target.
+    ///
+    /// If the evaluations have been discarded, the row is re-computed by evaluating all
+    /// polynomials at the corresponding point of the LDE domain.
+    ///
+    /// # Errors
+    /// Returns an error if the evaluations are stored on disk and the row could not be read.
+    pub fn read_row_into(&self, position: usize, target: &mut [E]) -> Result<(), ProverError> {
+        match self {
+            Self::Evaluations(evaluations) => target.copy_from_slice(evaluations.row(position)),
+            #[cfg(feature = "std")]
+            Self::Disk(evaluations) => {
+                evaluations.read_row_into(position, target).map_err(storage_error)?
+            }
+            Self::Polys { .. } => target.copy_from_slice(&self.read_row(position)?),
+        }
+        Ok(())
+    }
+
+    /// Returns rows of this matrix at the specified positions in the LDE domain.
+    ///
+    /// If the evaluations have been discarded, the rows are re-computed by evaluating all
+    /// polynomials at the corresponding points of the LDE domain.
+    ///
+    /// # Errors
+    /// Returns an error if the evaluations are stored on disk and the rows could not be read.
+    pub fn read_rows(&self, positions: &[usize]) -> Result<Vec<Vec<E>>, ProverError> {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Disk(evaluations) => evaluations.read_rows(positions).map_err(storage_error),
+            _ => positions.iter().map(|&position| self.read_row(position)).collect(),
+        }
+    }
+
+    /// Returns a row of this matrix at the specified position in the LDE domain.
+    ///
+    /// If the evaluations have been discarded, the row is re-computed by evaluating all
+    /// polynomials at the corresponding point of the LDE domain.
+    ///
+    /// # Errors
+    /// Returns an error if the evaluations are stored on disk and the row could not be read.
+    pub fn read_row(&self, position: usize) -> Result<Vec<E>, ProverError> {
+        match self {
+            Self::Evaluations(evaluations) => Ok(evaluations.row(position).to_vec()),
+            #[cfg(feature = "std")]
+            Self::Disk(evaluations) => {
+                let mut row = vec![E::ZERO; evaluations.num_cols()];
+                evaluations.read_row_into(position, &mut row).map_err(storage_error)?;
+                Ok(row)
+            }
+            Self::Polys {
+                polys,
+                domain_generator,
+                domain_offset,
+                ..
+            } => {
+                let x = *domain_offset * domain_generator.exp((position as u64).into());
+                Ok(polys.evaluate_columns_at(E::from(x)))
+            }
+        }
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Discards the evaluations stored in this matrix, and replaces them with the specified
+    /// polynomials from which the evaluations can be re-computed on demand.
+    ///
+    /// Does nothing if the evaluations have already been discarded.
+    pub fn discard_evaluations(&mut self, polys: ColMatrix<E>, domain: &StarkDomain<E::BaseField>) {
+        if !matches!(self, Self::Polys { .. }) {
+            let num_rows = self.num_rows();
+            *self = Self::Polys {
+                polys,
+                num_rows,
+                domain_generator: E::BaseField::get_root_of_unity(num_rows.ilog2()),
+                domain_offset: domain.offset(),
+            };
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts an error which occurred while accessing evaluations stored on disk into a
+/// [ProverError].
+#[cfg(feature = "std")]
+pub(crate) fn storage_error(err: std::io::Error) -> ProverError {
+    ProverError::LdeStorageError(err.to_string())
+}
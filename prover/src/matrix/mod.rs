@@ -5,14 +5,17 @@
 
 //! Two-dimensional data structures used to represent polynomials and polynomial evaluations.
 
-mod row_matrix;
-pub use row_matrix::{build_segments, get_evaluation_offsets, RowMatrix};
+pub use math::matrix::{
+    build_segments, get_evaluation_offsets, ColMatrix, ColumnIter, ColumnIterMut, MultiColumnIter,
+    RowMatrix, Segment,
+};
 
-mod col_matrix;
-pub use col_matrix::{ColMatrix, ColumnIter, MultiColumnIter};
+mod lde_matrix;
+#[cfg(feature = "std")]
+pub(crate) use lde_matrix::storage_error;
+pub(crate) use lde_matrix::LdeMatrix;
 
-mod segments;
-pub use segments::Segment;
-
-#[cfg(test)]
-mod tests;
+#[cfg(feature = "std")]
+mod disk_matrix;
+#[cfg(feature = "std")]
+pub(crate) use disk_matrix::DiskMatrix;
@@ -0,0 +1,118 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::Air;
+use core::{cmp, mem};
+use crypto::Hasher;
+use math::FieldElement;
+
+// MEMORY ESTIMATE
+// ================================================================================================
+/// An estimate of the amount of memory (in bytes) allocated by the prover to generate a proof
+/// for a given instance of a computation.
+///
+/// An estimate is computed via [Prover::estimate_memory()](crate::Prover::estimate_memory) from
+/// the shape of the execution trace, the constraints of the computation, and the proof options
+/// alone, without building the trace. Only the large data structures built by the prover are
+/// included; the actual amount of allocated memory will be somewhat higher due to auxiliary
+/// allocations (e.g., twiddles, intermediate buffers, and the execution trace itself).
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct MemoryEstimate {
+    /// Trace polynomials of all trace segments in coefficient form.
+    pub trace_polys: usize,
+    /// Evaluations of all trace polynomials over the LDE domain.
+    pub trace_lde: usize,
+    /// Merkle trees built for the commitments to all trace segments.
+    pub trace_trees: usize,
+    /// Evaluations of the combined constraints over the constraint evaluation domain.
+    pub constraint_evaluations: usize,
+    /// Columns of the constraint composition polynomial in coefficient form.
+    pub composition_poly: usize,
+    /// Evaluations of the constraint composition polynomial columns over the LDE domain.
+    pub composition_lde: usize,
+    /// Merkle tree built for the commitment to the constraint composition polynomial.
+    pub composition_tree: usize,
+    /// DEEP composition polynomial in coefficient form.
+    pub deep_composition_poly: usize,
+    /// Evaluations and Merkle trees of all FRI layers, including evaluations of the DEEP
+    /// composition polynomial over the LDE domain.
+    pub fri_layers: usize,
+    /// Expected peak amount of memory held by the above data structures at the same time.
+    pub peak: usize,
+}
+
+impl MemoryEstimate {
+    /// Returns a memory estimate for generating a proof for the specified AIR using the hash
+    /// function `H`.
+    ///
+    /// When `low_memory_mode` is true, the trace and the composition polynomial LDEs are assumed
+    /// to be discarded once they are no longer needed for building the commitments.
+    pub(crate) fn new<A: Air, H: Hasher>(air: &A, low_memory_mode: bool) -> Self {
+        let base_bytes = <A::BaseField as FieldElement>::ELEMENT_BYTES;
+        let ext_bytes = base_bytes * air.options().field_extension().degree() as usize;
+        let digest_bytes = mem::size_of::<H::Digest>();
+
+        let trace_length = air.trace_length();
+        let lde_domain_size = air.lde_domain_size();
+        let main_width = air.trace_layout().main_trace_width();
+        let aux_width = air.trace_layout().aux_trace_width();
+        let num_trace_segments = air.trace_layout().num_segments();
+
+        // a Merkle tree stores leaves and internal nodes for every position in the LDE domain
+        let tree_bytes = |num_leaves: usize| 2 * num_leaves * digest_bytes;
+
+        let trace_polys = trace_length * (main_width * base_bytes + aux_width * ext_bytes);
+        let trace_lde = lde_domain_size * (main_width * base_bytes + aux_width * ext_bytes);
+        let trace_trees = num_trace_segments * tree_bytes(lde_domain_size);
+
+        let constraint_evaluations = air.ce_domain_size() * ext_bytes;
+        let num_composition_columns = air.context().num_constraint_composition_columns();
+        let composition_poly = num_composition_columns * trace_length * ext_bytes;
+        let composition_lde = num_composition_columns * lde_domain_size * ext_bytes;
+        let composition_tree = tree_bytes(lde_domain_size);
+
+        let deep_composition_poly = trace_length * ext_bytes;
+        let fri_options = air.options().to_fri_options();
+        let mut fri_layers = 0;
+        let mut domain_size = lde_domain_size;
+        for _ in 0..fri_options.num_fri_layers(lde_domain_size) {
+            fri_layers += domain_size * ext_bytes;
+            domain_size /= fri_options.folding_factor();
+            fri_layers += tree_bytes(domain_size);
+        }
+        fri_layers += domain_size * ext_bytes;
+
+        // memory held at the end of each phase of proof generation: first, the trace is committed
+        // to and the constraints are evaluated; then, the composition polynomial is committed to;
+        // finally, the DEEP composition polynomial is built and FRI layers are computed.
+        let (trace_held, composition_held) = if low_memory_mode {
+            (trace_polys + trace_trees, composition_poly + composition_tree)
+        } else {
+            (
+                trace_polys + trace_lde + trace_trees,
+                composition_poly + composition_lde + composition_tree,
+            )
+        };
+        let trace_phase = trace_polys + trace_lde + trace_trees + constraint_evaluations;
+        let composition_phase = trace_held
+            + cmp::max(constraint_evaluations, composition_poly)
+            + composition_lde
+            + composition_tree;
+        let fri_phase = trace_held + composition_held + deep_composition_poly + fri_layers;
+
+        Self {
+            trace_polys,
+            trace_lde,
+            trace_trees,
+            constraint_evaluations,
+            composition_poly,
+            composition_lde,
+            composition_tree,
+            deep_composition_poly,
+            fri_layers,
+            peak: cmp::max(trace_phase, cmp::max(composition_phase, fri_phase)),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Prover, ProverError, StarkDomain, Trace, TraceLde, TracePolyTable};
+use air::{
+    proof::{StarkProof, TraceOpening},
+    Air,
+};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, RandomCoin};
+use fri::FriProver;
+use math::{fft, polynom, FieldElement};
+use utils::{collections::Vec, string::ToString};
+
+// TRACE OPENING
+// ================================================================================================
+
+/// Returns an opening of the specified main trace column at `point` for the trace committed to
+/// in `proof`; see [Prover::open_trace_column()] for details.
+pub fn open_trace_column<P, E>(
+    prover: &P,
+    trace: &P::Trace,
+    proof: &StarkProof,
+    column: usize,
+    point: E,
+) -> Result<TraceOpening<E, P::HashFn>, ProverError>
+where
+    P: Prover,
+    E: FieldElement<BaseField = P::BaseField>,
+{
+    let trace_info = trace.get_info();
+    if column >= trace.main_trace_width() {
+        return Err(ProverError::InvalidTraceOpening(format!(
+            "column {column} is out of bounds for the main trace segment of {} columns",
+            trace.main_trace_width()
+        )));
+    }
+
+    let pub_inputs = prover.get_pub_inputs(trace);
+    let air = P::Air::new(trace_info.clone(), pub_inputs, prover.options().clone());
+    let domain = StarkDomain::new(&air);
+
+    // the quotient is not defined over the LDE domain if the opening point is in the domain; the
+    // point is in the domain iff point^n = offset^n, where n is the size of the domain
+    let n = domain.lde_domain_size() as u64;
+    if point.exp(n.into()) == E::from(domain.offset().exp(n.into())) {
+        return Err(ProverError::InvalidTraceOpening(
+            "opening point must not be in the LDE domain".to_string(),
+        ));
+    }
+
+    // re-build the LDE of the main trace segment, and make sure it is the one committed to in the
+    // proof
+    let (trace_lde, trace_polys): (P::TraceLde<E>, TracePolyTable<E>) = trace
+        .with_main_segment(|main_segment| prover.new_trace_lde(&trace_info, main_segment, &domain));
+    let trace_root = trace_lde.get_main_trace_commitment();
+    let num_fri_layers = air.options().to_fri_options().num_fri_layers(air.lde_domain_size());
+    let proof_trace_root = proof
+        .commitments
+        .clone()
+        .parse::<P::HashFn>(air.trace_layout().num_segments(), num_fri_layers)
+        .map(|(trace_roots, ..)| trace_roots[0])
+        .map_err(|err| ProverError::InvalidTraceOpening(err.to_string()))?;
+    if trace_root != proof_trace_root {
+        return Err(ProverError::InvalidTraceOpening(
+            "commitment to the main trace segment does not match the proof".to_string(),
+        ));
+    }
+
+    // evaluate the column polynomial at the opening point, and compute the quotient
+    // (T(x) - T(z)) / (x - z) over the LDE domain
+    let column_poly = trace_polys
+        .main_trace_polys()
+        .nth(column)
+        .expect("column index was checked")
+        .iter()
+        .map(|&coefficient| E::from(coefficient))
+        .collect::<Vec<_>>();
+    let value = polynom::eval(&column_poly, point);
+    let mut quotient_poly = column_poly;
+    quotient_poly[0] -= value;
+    polynom::syn_div_in_place(&mut quotient_poly, 1, point);
+    let quotient_evaluations = fft::evaluate_poly_with_offset(
+        &quotient_poly,
+        domain.trace_twiddles(),
+        domain.offset(),
+        domain.trace_to_lde_blowup(),
+    );
+
+    // commit to the quotient via FRI; the public coin is bound to the proof, the opened claim,
+    // and the trace commitment
+    let seed = TraceOpening::<E, P::HashFn>::coin_seed(&proof.context, column, point, value);
+    let mut channel = OpeningChannel::<E, P::HashFn, P::RandomCoin>::new(&seed, trace_root);
+    let mut fri_prover = FriProver::new(air.options().to_fri_options());
+    fri_prover.build_layers(&mut channel, quotient_evaluations);
+
+    // apply proof-of-work to the query seed and draw query positions in the same way as the
+    // positions of the proof are drawn
+    let options = air.options();
+    let grinding_factor = options.grinding_factor();
+    let pow_nonce = (1..u64::MAX)
+        .find(|&nonce| channel.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+        .expect("nonce not found");
+    let num_queries = options.num_queries();
+    let mut positions = if options.distinct_queries() {
+        channel
+            .public_coin
+            .draw_distinct_integers(num_queries, air.lde_domain_size(), pow_nonce)
+    } else {
+        channel.public_coin.draw_integers(num_queries, air.lde_domain_size(), pow_nonce)
+    }
+    .expect("failed to draw query position");
+    positions.sort_unstable();
+    positions.dedup();
+
+    let fri_proof = fri_prover.build_proof(&positions);
+    let trace_queries = trace_lde.query(&positions)?.remove(0);
+
+    Ok(TraceOpening::new(
+        column,
+        point,
+        value,
+        channel.commitments,
+        pow_nonce,
+        trace_queries,
+        fri_proof,
+    ))
+}
+
+// OPENING CHANNEL
+// ================================================================================================
+
+/// A FRI prover channel which draws FRI challenges from a public coin seeded for an opening.
+struct OpeningChannel<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    public_coin: R,
+    commitments: Vec<H::Digest>,
+    _field_element: PhantomData<E>,
+}
+
+impl<E, H, R> OpeningChannel<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    fn new(seed: &[E::BaseField], trace_root: H::Digest) -> Self {
+        let mut public_coin = R::new(seed);
+        public_coin.reseed(trace_root);
+        Self {
+            public_coin,
+            commitments: Vec::new(),
+            _field_element: PhantomData,
+        }
+    }
+}
+
+impl<E, H, R> fri::ProverChannel<E> for OpeningChannel<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    type Hasher = H;
+
+    fn commit_fri_layer(&mut self, layer_root: H::Digest) {
+        self.commitments.push(layer_root);
+        self.public_coin.reseed(layer_root);
+    }
+
+    fn draw_fri_alpha(&mut self) -> E {
+        self.public_coin.draw().expect("failed to draw FRI alpha")
+    }
+
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::constraints::PeriodicValueTable;
+use air::Air;
+use math::StarkField;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+// PERIODIC COLUMN CACHE
+// ================================================================================================
+
+/// Identifies extended values of periodic columns: AIR fingerprint, trace length, and constraint
+/// evaluation domain blowup factor.
+type CacheKey = ([u8; 32], usize, usize);
+
+/// A cache of periodic column values extended over constraint evaluation domains.
+///
+/// Extending periodic columns of an AIR over the constraint evaluation domain is repeated for
+/// every proof, even though the extended values depend only on the computation, the length of
+/// the execution trace, and the blowup factor. A cache keeps the extended values once they are
+/// computed so that subsequent proofs for the same computation skip this work. Entries are keyed
+/// by [Air::fingerprint()] (which commits to the AIR identifier, proof options, and values of all
+/// periodic columns), by the trace length, and by the constraint evaluation blowup factor.
+///
+/// A cache is a handle to shared storage: clones of a cache refer to the same entries, and thus,
+/// a single cache can be shared by many prover instances (including provers running in different
+/// threads). A prover uses a cache returned from
+/// [Prover::periodic_column_cache()](crate::Prover::periodic_column_cache).
+///
+/// Unlike a [ProvingKey](crate::ProvingKey), a cache is not bound to a single AIR instance, and
+/// it can be populated lazily by provers for many different computations and trace lengths.
+#[derive(Clone)]
+pub struct PeriodicColumnCache<B: StarkField> {
+    entries: Arc<Mutex<BTreeMap<CacheKey, Arc<PeriodicValueTable<B>>>>>,
+}
+
+impl<B: StarkField> PeriodicColumnCache<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty cache.
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of entries in this cache.
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("periodic column cache lock poisoned").len()
+    }
+
+    /// Returns true if this cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if this cache contains extended periodic columns for the specified AIR
+    /// instance.
+    pub fn contains<A: Air<BaseField = B>>(&self, air: &A) -> bool {
+        let key = build_key(air);
+        self.entries
+            .lock()
+            .expect("periodic column cache lock poisoned")
+            .contains_key(&key)
+    }
+
+    // MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Removes all entries from this cache.
+    pub fn clear(&self) {
+        self.entries.lock().expect("periodic column cache lock poisoned").clear();
+    }
+
+    /// Returns periodic columns of the specified AIR instance extended over its constraint
+    /// evaluation domain, extending the columns and adding them to this cache if they are not
+    /// cached yet.
+    pub(crate) fn get_or_build<A: Air<BaseField = B>>(
+        &self,
+        air: &A,
+    ) -> Arc<PeriodicValueTable<B>> {
+        let key = build_key(air);
+        if let Some(table) =
+            self.entries.lock().expect("periodic column cache lock poisoned").get(&key)
+        {
+            return table.clone();
+        }
+
+        // extend periodic columns without holding the lock so that provers for other
+        // computations are not blocked; if another prover has built the same table in the
+        // meantime, its table is kept
+        let table = Arc::new(PeriodicValueTable::new(air));
+        self.entries
+            .lock()
+            .expect("periodic column cache lock poisoned")
+            .entry(key)
+            .or_insert(table)
+            .clone()
+    }
+}
+
+impl<B: StarkField> Default for PeriodicColumnCache<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_key<A: Air>(air: &A) -> CacheKey {
+    (air.fingerprint(), air.trace_length(), air.ce_blowup_factor())
+}
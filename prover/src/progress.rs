@@ -0,0 +1,95 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+// PROVING PHASE
+// ================================================================================================
+
+/// Defines a phase of the proof generation procedure.
+///
+/// Phases are reported via [Prover::on_phase_start()](crate::Prover::on_phase_start) method in the
+/// order in which they are listed below. Phases which are not applicable to a given computation
+/// (e.g., [ProvingPhase::AuxTraceCommitment] for computations without auxiliary trace segments)
+/// are skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingPhase {
+    /// Low-degree extension of and commitment to the main segment of the execution trace.
+    TraceCommitment,
+    /// Building of, low-degree extension of, and commitment to the auxiliary trace segment with
+    /// the specified index.
+    AuxTraceCommitment(usize),
+    /// Evaluation of AIR constraints over the constraint evaluation domain.
+    ConstraintEvaluation,
+    /// Low-degree extension of and commitment to the constraint composition polynomial.
+    ConstraintCommitment,
+    /// Building and evaluation of the DEEP composition polynomial.
+    DeepComposition,
+    /// Building of FRI layers for the DEEP composition polynomial.
+    FriLayers,
+    /// Proof-of-work grinding on the query seed and drawing of query positions.
+    Grinding,
+    /// Querying of all committed values and building of the proof object.
+    ProofAssembly,
+    /// Proof generation is complete.
+    Done,
+}
+
+impl ProvingPhase {
+    /// Returns an estimate of the percentage of proof generation work completed by the time
+    /// this phase starts.
+    ///
+    /// The estimates are based on typical relative costs of each phase and are intended only for
+    /// displaying progress to users.
+    pub fn progress(&self) -> u8 {
+        match self {
+            Self::TraceCommitment => 0,
+            Self::AuxTraceCommitment(_) => 20,
+            Self::ConstraintEvaluation => 35,
+            Self::ConstraintCommitment => 55,
+            Self::DeepComposition => 70,
+            Self::FriLayers => 80,
+            Self::Grinding => 90,
+            Self::ProofAssembly => 95,
+            Self::Done => 100,
+        }
+    }
+
+    /// Returns the name of the histogram in which the time of this phase (in seconds) is recorded
+    /// via [Metrics](air::metrics::Metrics), or `None` for [ProvingPhase::Done].
+    ///
+    /// Time of all auxiliary trace segment commitments is recorded in the same histogram.
+    pub fn duration_metric(&self) -> Option<&'static str> {
+        match self {
+            Self::TraceCommitment => Some("winterfell_phase_trace_commitment_seconds"),
+            Self::AuxTraceCommitment(_) => Some("winterfell_phase_aux_trace_commitment_seconds"),
+            Self::ConstraintEvaluation => Some("winterfell_phase_constraint_evaluation_seconds"),
+            Self::ConstraintCommitment => Some("winterfell_phase_constraint_commitment_seconds"),
+            Self::DeepComposition => Some("winterfell_phase_deep_composition_seconds"),
+            Self::FriLayers => Some("winterfell_phase_fri_layers_seconds"),
+            Self::Grinding => Some("winterfell_phase_grinding_seconds"),
+            Self::ProofAssembly => Some("winterfell_phase_proof_assembly_seconds"),
+            Self::Done => None,
+        }
+    }
+}
+
+impl fmt::Display for ProvingPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TraceCommitment => write!(f, "committing to the main trace segment"),
+            Self::AuxTraceCommitment(idx) => {
+                write!(f, "committing to auxiliary trace segment {idx}")
+            }
+            Self::ConstraintEvaluation => write!(f, "evaluating constraints"),
+            Self::ConstraintCommitment => write!(f, "committing to constraint evaluations"),
+            Self::DeepComposition => write!(f, "building DEEP composition polynomial"),
+            Self::FriLayers => write!(f, "building FRI layers"),
+            Self::Grinding => write!(f, "grinding query seed"),
+            Self::ProofAssembly => write!(f, "assembling proof"),
+            Self::Done => write!(f, "done"),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Air, ProofOptions, ProverError, StarkDomain, TraceInfo};
+use math::StarkField;
+use utils::string::ToString;
+
+// PROVING KEY
+// ================================================================================================
+
+/// Data which depends only on the computation and the length of its execution trace, and thus,
+/// can be computed once and re-used when generating many proofs for the same computation.
+///
+/// A proving key contains:
+/// * A [StarkDomain] with twiddles for the trace domain and the constraint evaluation domain.
+/// * Values of periodic columns over the constraint evaluation domain.
+/// * Inverse evaluations of transition constraint divisor and of boundary constraint divisors
+///   for the main trace segment over the constraint evaluation domain.
+/// * Trace shape and proof options, which determine constraint evaluation and LDE domain sizes.
+///
+/// A proving key is built from an instance of an AIR via [ProvingKey::new()], and can be set in a
+/// [ProvingConfig](crate::ProvingConfig) to skip re-computing the above data for every proof. The key is valid only for instances of the same AIR which define the
+/// same periodic columns and place assertions on the same steps of the execution trace.
+pub struct ProvingKey<B: StarkField> {
+    trace_info: TraceInfo,
+    options: ProofOptions,
+    domain: StarkDomain<B>,
+}
+
+impl<B: StarkField> ProvingKey<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new proving key for the computation described by the specified AIR instance.
+    pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
+        let mut domain = StarkDomain::new(air);
+        domain.precompute_constraint_data(air);
+
+        Self {
+            trace_info: air.trace_info().clone(),
+            options: air.options().clone(),
+            domain,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns info about the execution trace for which this key was built.
+    pub fn trace_info(&self) -> &TraceInfo {
+        &self.trace_info
+    }
+
+    /// Returns proof options for which this key was built.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    /// Returns the domain of this key.
+    pub fn domain(&self) -> &StarkDomain<B> {
+        &self.domain
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes sure this key can be used to generate a proof for the specified AIR instance.
+    pub(crate) fn validate<A: Air<BaseField = B>>(&self, air: &A) -> Result<(), ProverError> {
+        if air.trace_info().length() != self.trace_info.length() {
+            return Err(ProverError::IncompatibleProvingKey(format!(
+                "trace length must be {}, but was {}",
+                self.trace_info.length(),
+                air.trace_info().length()
+            )));
+        }
+        if air.trace_layout() != self.trace_info.layout() {
+            return Err(ProverError::IncompatibleProvingKey(
+                "trace layout is different from the layout of the key".to_string(),
+            ));
+        }
+        if air.options() != &self.options {
+            return Err(ProverError::IncompatibleProvingKey(
+                "proof options are different from the options of the key".to_string(),
+            ));
+        }
+        if air.ce_domain_size() != self.domain.ce_domain_size() {
+            return Err(ProverError::IncompatibleProvingKey(format!(
+                "constraint evaluation domain size must be {}, but was {}",
+                self.domain.ce_domain_size(),
+                air.ce_domain_size()
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ProvingPhase;
+#[cfg(feature = "std")]
+use crate::time::Instant;
+use air::{
+    metrics::{self, Metrics, PROOFS_GENERATED, PROOF_SIZE, PROVING_FAILURES},
+    proof::StarkProof,
+};
+#[cfg(feature = "std")]
+use core::cell::Cell;
+
+// METRICS RECORDER
+// ================================================================================================
+
+/// Reports metrics of generating a single proof to the installed [Metrics] backend.
+///
+/// A recorder is created once proof generation starts, is notified about the start of every
+/// phase, and is finished once the proof is generated. If the recorder is dropped before it is
+/// finished, proof generation is assumed to have failed.
+pub(crate) struct MetricsRecorder {
+    metrics: &'static dyn Metrics,
+    #[cfg(feature = "std")]
+    proof_start: Instant,
+    #[cfg(feature = "std")]
+    phase_start: Cell<Option<(ProvingPhase, Instant)>>,
+    finished: bool,
+}
+
+impl MetricsRecorder {
+    /// Returns a new recorder reporting to the installed metrics backend.
+    pub fn new() -> Self {
+        Self {
+            metrics: metrics::metrics(),
+            #[cfg(feature = "std")]
+            proof_start: Instant::now(),
+            #[cfg(feature = "std")]
+            phase_start: Cell::new(None),
+            finished: false,
+        }
+    }
+
+    /// Records the start of the specified phase, and the time of the previous phase (if any).
+    pub fn start_phase(&self, _phase: ProvingPhase) {
+        #[cfg(feature = "std")]
+        {
+            let now = Instant::now();
+            self.record_phase_time(now);
+            self.phase_start.set(Some((_phase, now)));
+        }
+    }
+
+    /// Records the time of the last phase and of the entire proof generation, together with the
+    /// size of the generated proof.
+    pub fn finish(mut self, proof: &StarkProof) {
+        self.finished = true;
+        #[cfg(feature = "std")]
+        {
+            let now = Instant::now();
+            self.record_phase_time(now);
+            self.metrics
+                .record_histogram(metrics::PROVING_TIME, (now - self.proof_start).as_secs_f64());
+        }
+        self.metrics.increment_counter(PROOFS_GENERATED, 1);
+        // serializing the proof is relatively cheap, but is not needed if metrics are discarded
+        if metrics::is_enabled() {
+            self.metrics.record_histogram(PROOF_SIZE, proof.to_bytes().len() as f64);
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    #[cfg(feature = "std")]
+    fn record_phase_time(&self, now: Instant) {
+        if let Some((phase, start)) = self.phase_start.take() {
+            if let Some(name) = phase.duration_metric() {
+                self.metrics.record_histogram(name, (now - start).as_secs_f64());
+            }
+        }
+    }
+}
+
+impl Drop for MetricsRecorder {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.metrics.increment_counter(PROVING_FAILURES, 1);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{matrix::ColMatrix, ProverError};
+use air::{proof::StarkProof, Air, ProofOptions, TraceInfo};
+use math::StarkField;
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    Box, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+const TRACE_TAG: u8 = 1;
+const INPUTS_TAG: u8 = 2;
+const PROOF_TAG: u8 = 1;
+const ERROR_TAG: u8 = 2;
+
+// PROOF REQUEST
+// ================================================================================================
+
+/// A request sent to a remote worker to generate a proof.
+///
+/// A request identifies the AIR via its [Air::AIR_ID], specifies the proof options, and carries
+/// either the main segment of an execution trace or application-defined inputs from which the
+/// worker builds the trace itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofRequest {
+    air_id: u32,
+    options: ProofOptions,
+    payload: Payload,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Payload {
+    Trace {
+        air_fingerprint: [u8; 32],
+        trace_info: TraceInfo,
+        main_segment: Vec<u8>,
+    },
+    Inputs(Vec<u8>),
+}
+
+impl ProofRequest {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a request to prove the execution trace with the specified main segment against the
+    /// specified AIR; the options and trace info are taken from the AIR.
+    pub fn for_trace<A: Air>(air: &A, main_segment: &ColMatrix<A::BaseField>) -> Self {
+        Self {
+            air_id: A::AIR_ID,
+            options: air.options().clone(),
+            payload: Payload::Trace {
+                air_fingerprint: air.fingerprint(),
+                trace_info: air.trace_info().clone(),
+                main_segment: main_segment.to_bytes(),
+            },
+        }
+    }
+
+    /// Returns a request to prove an execution of the computation described by AIR `A` for the
+    /// specified application-defined inputs.
+    pub fn for_inputs<A: Air>(options: ProofOptions, inputs: Vec<u8>) -> Self {
+        Self {
+            air_id: A::AIR_ID,
+            options,
+            payload: Payload::Inputs(inputs),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the identifier of the AIR for which the proof is requested.
+    pub fn air_id(&self) -> u32 {
+        self.air_id
+    }
+
+    /// Returns the options with which the proof should be generated.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    /// Returns the fingerprint of the AIR for which the proof is requested, or `None` if the
+    /// proof is requested for application-defined inputs.
+    pub fn air_fingerprint(&self) -> Option<&[u8; 32]> {
+        match &self.payload {
+            Payload::Trace {
+                air_fingerprint, ..
+            } => Some(air_fingerprint),
+            Payload::Inputs(_) => None,
+        }
+    }
+
+    /// Returns the info of the execution trace to be proved, or `None` if the proof is requested
+    /// for application-defined inputs.
+    pub fn trace_info(&self) -> Option<&TraceInfo> {
+        match &self.payload {
+            Payload::Trace { trace_info, .. } => Some(trace_info),
+            Payload::Inputs(_) => None,
+        }
+    }
+
+    /// Returns the application-defined inputs of this request, or `None` if the proof is
+    /// requested for an execution trace.
+    pub fn inputs(&self) -> Option<&[u8]> {
+        match &self.payload {
+            Payload::Trace { .. } => None,
+            Payload::Inputs(inputs) => Some(inputs),
+        }
+    }
+
+    /// Returns the main segment of the execution trace to be proved.
+    ///
+    /// # Errors
+    /// Returns an error if this request does not carry an execution trace, if the main segment
+    /// could not be deserialized, or if its shape does not match the trace info of this request.
+    pub fn read_main_segment<B: StarkField>(&self) -> Result<ColMatrix<B>, DeserializationError> {
+        let (trace_info, main_segment) = match &self.payload {
+            Payload::Trace {
+                trace_info,
+                main_segment,
+                ..
+            } => (trace_info, main_segment),
+            Payload::Inputs(_) => {
+                return Err(DeserializationError::InvalidValue(
+                    "proof request does not contain an execution trace".to_string(),
+                ))
+            }
+        };
+
+        let main_segment = ColMatrix::<B>::read_from_bytes(main_segment)?;
+        if main_segment.num_cols() != trace_info.layout().main_trace_width()
+            || main_segment.num_rows() != trace_info.length()
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "main trace segment of {}x{} does not match trace of {}x{}",
+                main_segment.num_cols(),
+                main_segment.num_rows(),
+                trace_info.layout().main_trace_width(),
+                trace_info.length()
+            )));
+        }
+        Ok(main_segment)
+    }
+}
+
+impl Serializable for ProofRequest {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.air_id);
+        self.options.write_into(target);
+        match &self.payload {
+            Payload::Trace {
+                air_fingerprint,
+                trace_info,
+                main_segment,
+            } => {
+                target.write_u8(TRACE_TAG);
+                target.write_bytes(air_fingerprint);
+                trace_info.write_into(target);
+                write_bytes_with_len(main_segment, target);
+            }
+            Payload::Inputs(inputs) => {
+                target.write_u8(INPUTS_TAG);
+                write_bytes_with_len(inputs, target);
+            }
+        }
+    }
+}
+
+impl Deserializable for ProofRequest {
+    /// Reads a proof request from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof request could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let air_id = source.read_u32()?;
+        let options = ProofOptions::read_from(source)?;
+        let payload = match source.read_u8()? {
+            TRACE_TAG => {
+                let air_fingerprint = source.read_array()?;
+                let trace_info = TraceInfo::read_from(source)?;
+                let main_segment = read_bytes_with_len(source)?;
+                Payload::Trace {
+                    air_fingerprint,
+                    trace_info,
+                    main_segment,
+                }
+            }
+            INPUTS_TAG => Payload::Inputs(read_bytes_with_len(source)?),
+            tag => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "invalid proof request tag: {tag}"
+                )))
+            }
+        };
+        Ok(Self {
+            air_id,
+            options,
+            payload,
+        })
+    }
+}
+
+// PROOF RESPONSE
+// ================================================================================================
+
+/// A response sent by a remote worker back to the application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofResponse {
+    /// The proof generated for the request.
+    Proof(Box<StarkProof>),
+    /// A description of the error which prevented the worker from generating the proof.
+    Error(String),
+}
+
+impl From<Result<StarkProof, ProverError>> for ProofResponse {
+    fn from(result: Result<StarkProof, ProverError>) -> Self {
+        match result {
+            Ok(proof) => Self::Proof(Box::new(proof)),
+            Err(err) => Self::Error(err.to_string()),
+        }
+    }
+}
+
+impl Serializable for ProofResponse {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Proof(proof) => {
+                target.write_u8(PROOF_TAG);
+                proof.write_into(target);
+            }
+            Self::Error(err) => {
+                target.write_u8(ERROR_TAG);
+                write_bytes_with_len(err.as_bytes(), target);
+            }
+        }
+    }
+}
+
+impl Deserializable for ProofResponse {
+    /// Reads a proof response from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof response could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            PROOF_TAG => Ok(Self::Proof(Box::new(StarkProof::read_from(source)?))),
+            ERROR_TAG => {
+                let num_bytes = source.read_u32()? as usize;
+                Ok(Self::Error(source.read_string(num_bytes)?))
+            }
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid proof response tag: {tag}"
+            ))),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn write_bytes_with_len<W: ByteWriter>(bytes: &[u8], target: &mut W) {
+    target.write_u32(bytes.len() as u32);
+    target.write_bytes(bytes);
+}
+
+fn read_bytes_with_len<R: ByteReader>(source: &mut R) -> Result<Vec<u8>, DeserializationError> {
+    let num_bytes = source.read_u32()? as usize;
+    source.read_vec(num_bytes)
+}
@@ -0,0 +1,150 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Building blocks for offloading proof generation to a remote worker (e.g., a GPU server).
+//!
+//! An AIR is defined in code, and thus, cannot be sent to a worker; instead, the worker is set up
+//! with the same AIRs as the application, and an AIR is identified by its [Air::AIR_ID] and
+//! [fingerprint](Air::fingerprint). A proof is generated remotely as follows:
+//!
+//! 1. The application implements [RemoteProver] trait for its computation. The trait specifies
+//!    the AIR, the proof options, and how serialized requests are delivered to the worker.
+//! 2. [RemoteProver::prove()] serializes the main segment of the execution trace, the proof
+//!    options, and the identity of the AIR into a [ProofRequest], and sends it to the worker.
+//!    Alternatively, [RemoteProver::prove_from_inputs()] sends application-defined inputs from
+//!    which the worker builds the trace itself (this is usually much smaller than the trace).
+//! 3. The worker passes the request to a [ProofWorker], which dispatches it to the prover
+//!    registered for the AIR, and sends the serialized [ProofResponse] back.
+//! 4. The proof is deserialized and checked against the request (i.e., that it was generated for
+//!    the requested AIR, options, and trace), and returned to the application.
+//!
+//! The worker is not trusted: a returned proof should be verified as any other proof. Auxiliary
+//! trace segments are not sent to the worker, since they are built by the prover from the main
+//! segment and the randomness drawn during proof generation.
+//!
+//! All messages implement [Serializable] and [Deserializable] traits, and thus, can be sent over
+//! any transport. This module does not make any assumptions about how messages are delivered.
+
+use crate::{ProverError, Trace};
+use air::{proof::StarkProof, Air, ProofOptions};
+use utils::{collections::Vec, string::ToString, Deserializable, Serializable};
+
+mod messages;
+pub use messages::{ProofRequest, ProofResponse};
+
+mod worker;
+pub use worker::ProofWorker;
+
+#[cfg(test)]
+mod tests;
+
+// REMOTE PROVER
+// ================================================================================================
+
+/// Defines a prover which delegates proof generation to a remote worker.
+///
+/// To use the trait, only the AIR, the trace type, the proof options, the public inputs, and the
+/// transport need to be specified; [RemoteProver::prove()] then has the same signature as
+/// [Prover::prove()](crate::Prover::prove).
+pub trait RemoteProver {
+    /// AIR describing the computation being proved.
+    type Air: Air;
+
+    /// Execution trace of the computation described by this prover.
+    type Trace: Trace<BaseField = <Self::Air as Air>::BaseField>;
+
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a set of public inputs for an instance of the computation defined by the provided
+    /// trace.
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> <Self::Air as Air>::PublicInputs;
+
+    /// Returns [ProofOptions] which the worker should use to generate STARK proofs.
+    fn options(&self) -> &ProofOptions;
+
+    /// Sends the serialized proof request to the worker and returns the serialized response.
+    ///
+    /// # Errors
+    /// Returns an error if the request could not be delivered or the response could not be
+    /// received; errors should be reported via [ProverError::RemoteProvingFailed].
+    fn send_request(&self, request: Vec<u8>) -> Result<Vec<u8>, ProverError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, generated by the remote worker.
+    ///
+    /// The main segment of the trace is sent to the worker together with the fingerprint of the
+    /// AIR instantiated for the trace, and the returned proof must have been generated for the
+    /// same AIR, options, and trace info.
+    ///
+    /// # Errors
+    /// Returns an error if the request could not be sent, if the worker failed to generate the
+    /// proof, or if the returned proof does not match the request.
+    fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        let pub_inputs = self.get_pub_inputs(&trace);
+        let trace_info = trace.get_info();
+        let air = Self::Air::new(trace_info.clone(), pub_inputs, self.options().clone());
+        let request = ProofRequest::for_trace(&air, trace.main_segment());
+
+        let proof = self.submit(&request)?;
+        if proof.context.air_fingerprint() != &air.fingerprint() {
+            return Err(mismatch("AIR fingerprint of the proof does not match the AIR"));
+        }
+        if proof.get_trace_info() != trace_info {
+            return Err(mismatch("trace info of the proof does not match the trace"));
+        }
+        Ok(proof)
+    }
+
+    /// Returns a STARK proof for a computation whose execution trace is built by the remote
+    /// worker from the specified inputs.
+    ///
+    /// The inputs are application-defined; they are passed to the handler registered for the AIR
+    /// via [ProofWorker::register()].
+    ///
+    /// # Errors
+    /// Returns an error if the request could not be sent, if the worker failed to generate the
+    /// proof, or if the returned proof was not generated for the AIR and options of this prover.
+    fn prove_from_inputs(&self, inputs: Vec<u8>) -> Result<StarkProof, ProverError> {
+        let request = ProofRequest::for_inputs::<Self::Air>(self.options().clone(), inputs);
+        self.submit(&request)
+    }
+
+    /// Sends the specified request to the worker and returns the proof from the response.
+    ///
+    /// The proof is checked to be generated for the AIR identifier and options of the request.
+    ///
+    /// # Errors
+    /// Returns an error if the request could not be sent, if the worker failed to generate the
+    /// proof, or if the returned proof does not match the request.
+    fn submit(&self, request: &ProofRequest) -> Result<StarkProof, ProverError> {
+        let response = self.send_request(request.to_bytes())?;
+        let response = ProofResponse::read_from_bytes(&response).map_err(|err| {
+            ProverError::RemoteProvingFailed(format!("failed to read worker response: {err}"))
+        })?;
+        let proof = match response {
+            ProofResponse::Proof(proof) => *proof,
+            ProofResponse::Error(err) => return Err(ProverError::RemoteProvingFailed(err)),
+        };
+
+        if proof.context.air_id() != request.air_id() {
+            return Err(mismatch("AIR identifier of the proof does not match the request"));
+        }
+        if proof.options() != request.options() {
+            return Err(mismatch("options of the proof do not match the request"));
+        }
+        Ok(proof)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn mismatch(reason: &str) -> ProverError {
+    ProverError::RemoteProvingFailed(reason.to_string())
+}
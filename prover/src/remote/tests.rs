@@ -0,0 +1,139 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ProofRequest, ProofResponse, ProofWorker};
+use crate::{
+    tests::{build_fib_trace, MockAir},
+    ProverError, Trace,
+};
+use air::Air;
+use math::fields::f128::BaseElement;
+use utils::{Deserializable, Serializable};
+
+#[test]
+fn proof_request_serialization() {
+    let trace = build_fib_trace(32);
+    let options = MockAir::with_trace_length(trace.length()).options().clone();
+    let air = MockAir::new(trace.get_info(), (), options);
+
+    // requests for traces carry the main trace segment and the identity of the AIR
+    let request = ProofRequest::for_trace(&air, trace.main_segment());
+    let request = ProofRequest::read_from_bytes(&request.to_bytes()).unwrap();
+    assert_eq!(MockAir::AIR_ID, request.air_id());
+    assert_eq!(air.options(), request.options());
+    assert_eq!(Some(&air.fingerprint()), request.air_fingerprint());
+    assert_eq!(Some(air.trace_info()), request.trace_info());
+    assert_eq!(None, request.inputs());
+    let main_segment = request.read_main_segment::<BaseElement>().unwrap();
+    assert_eq!(trace.main_segment().clone().into_columns(), main_segment.into_columns());
+
+    // requests for inputs carry only the inputs
+    let request = ProofRequest::for_inputs::<MockAir>(air.options().clone(), vec![1, 2, 3]);
+    let request = ProofRequest::read_from_bytes(&request.to_bytes()).unwrap();
+    assert_eq!(Some([1_u8, 2, 3].as_slice()), request.inputs());
+    assert_eq!(None, request.air_fingerprint());
+    assert!(request.read_main_segment::<BaseElement>().is_err());
+}
+
+#[test]
+fn proof_response_serialization() {
+    let response = ProofResponse::from(Err(ProverError::InvalidTrace("bad trace".into())));
+    let expected = "execution trace does not satisfy constraints of the AIR: bad trace";
+    assert_eq!(ProofResponse::Error(expected.into()), response);
+    assert_eq!(response, ProofResponse::read_from_bytes(&response.to_bytes()).unwrap());
+}
+
+#[test]
+fn proof_worker_dispatch() {
+    let air = MockAir::with_trace_length(16);
+    let mut worker = ProofWorker::new();
+    worker.register(MockAir::AIR_ID, |request| match request.inputs() {
+        Some(&[42]) => Err(ProverError::InvalidTrace("handled".into())),
+        _ => Err(ProverError::InvalidTrace("unexpected inputs".into())),
+    });
+    assert!(worker.contains(MockAir::AIR_ID));
+    assert!(!worker.contains(MockAir::AIR_ID + 1));
+
+    // requests are dispatched to the handler registered for their AIR
+    let request = ProofRequest::for_inputs::<MockAir>(air.options().clone(), vec![42]);
+    let response = ProofResponse::read_from_bytes(&worker.handle(&request.to_bytes())).unwrap();
+    assert!(matches!(response, ProofResponse::Error(err) if err.ends_with("handled")));
+
+    // malformed requests and requests for unknown AIRs are reported in responses
+    let response = ProofResponse::read_from_bytes(&worker.handle(&[1, 2, 3])).unwrap();
+    assert!(matches!(response, ProofResponse::Error(_)));
+
+    let mut request =
+        ProofRequest::for_inputs::<MockAir>(air.options().clone(), vec![42]).to_bytes();
+    request[0] ^= 1;
+    let response = ProofResponse::read_from_bytes(&worker.handle(&request)).unwrap();
+    assert!(matches!(response, ProofResponse::Error(err) if err.contains("no prover")));
+}
+
+#[test]
+#[cfg(feature = "testing")]
+fn remote_prover_round_trip() {
+    use super::RemoteProver;
+    use crate::{
+        testing::{self, build_mock_trace, Blake3, MockProver},
+        FieldExtension, ProofOptions, Prover, StarkProof,
+    };
+    use utils::{ByteReader, SliceReader};
+
+    struct LocalProver {
+        worker: ProofWorker,
+        options: ProofOptions,
+    }
+
+    impl RemoteProver for LocalProver {
+        type Air = testing::MockAir;
+        type Trace = crate::TraceTable<testing::BaseElement>;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> testing::BaseElement {
+            MockProver::<Blake3>::new(self.options.clone()).get_pub_inputs(trace)
+        }
+
+        fn options(&self) -> &ProofOptions {
+            &self.options
+        }
+
+        fn send_request(&self, request: Vec<u8>) -> Result<Vec<u8>, ProverError> {
+            Ok(self.worker.handle(&request))
+        }
+    }
+
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7);
+    let prover = MockProver::<Blake3>::new(options.clone());
+    let expected = prover.prove(build_mock_trace(2, 16)).unwrap();
+    let assert_expected = |proof: StarkProof| assert_eq!(expected.to_bytes(), proof.to_bytes());
+
+    // the trace is sent to the worker, and the returned proof is the one generated locally
+    let mut worker = ProofWorker::new();
+    worker.register_prover(MockProver::<Blake3>::new);
+    let remote = LocalProver {
+        worker,
+        options: options.clone(),
+    };
+    assert_expected(remote.prove(build_mock_trace(2, 16)).unwrap());
+
+    // the worker can also build the trace from inputs sent by the application
+    let mut worker = ProofWorker::new();
+    worker.register(testing::MockAir::AIR_ID, |request| {
+        let inputs = request.inputs().unwrap_or_default();
+        let trace_length = SliceReader::new(inputs)
+            .read_u32()
+            .map_err(|err| ProverError::RemoteProvingFailed(err.to_string()))?;
+        let prover = MockProver::<Blake3>::new(request.options().clone());
+        prover.prove(build_mock_trace(2, trace_length as usize))
+    });
+    let remote = LocalProver { worker, options };
+    assert_expected(remote.prove_from_inputs(16_u32.to_le_bytes().to_vec()).unwrap());
+
+    // errors of the worker are returned to the application
+    assert!(matches!(
+        remote.prove_from_inputs(vec![]),
+        Err(ProverError::RemoteProvingFailed(_))
+    ));
+}
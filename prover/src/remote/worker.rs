@@ -0,0 +1,146 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ProofRequest, ProofResponse};
+use crate::{Prover, ProverError, TraceTable};
+use air::{proof::StarkProof, Air, ProofOptions};
+use math::StarkField;
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::ToString,
+    Box, Deserializable, Serializable,
+};
+
+// TYPE ALIASES
+// ================================================================================================
+
+/// Signature of a function generating a proof for a request.
+type ProveFn = Box<dyn Fn(&ProofRequest) -> Result<StarkProof, ProverError> + Send + Sync>;
+
+// PROOF WORKER
+// ================================================================================================
+
+/// A worker which generates proofs requested by [RemoteProver](super::RemoteProver)s.
+///
+/// A prover is registered for every AIR the worker supports, and requests are dispatched to the
+/// prover registered under the AIR identifier of the request. Requests can be handled in the
+/// serialized form via [ProofWorker::handle()], and thus, a worker can be exposed over any
+/// transport.
+#[derive(Default)]
+pub struct ProofWorker {
+    provers: BTreeMap<u32, ProveFn>,
+}
+
+impl ProofWorker {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new worker with no registered provers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // REGISTRATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers a prover for requests carrying execution traces of the AIR of `P`.
+    ///
+    /// For every request, a prover is built for the options of the request via `build_prover`,
+    /// and the trace is re-assembled from the main segment and the trace info of the request.
+    ///
+    /// # Panics
+    /// Panics if a prover has already been registered for the AIR identifier of `P`.
+    pub fn register_prover<P, F>(&mut self, build_prover: F)
+    where
+        P: Prover<Trace = TraceTable<<P as Prover>::BaseField>>,
+        F: Fn(ProofOptions) -> P + Send + Sync + 'static,
+    {
+        self.register(P::Air::AIR_ID, move |request| {
+            let trace = read_trace_table::<P::BaseField>(request)?;
+            let proof = build_prover(request.options().clone()).prove(trace)?;
+            if Some(proof.context.air_fingerprint()) != request.air_fingerprint() {
+                return Err(ProverError::RemoteProvingFailed(
+                    "AIR fingerprint of the worker does not match the request".into(),
+                ));
+            }
+            Ok(proof)
+        });
+    }
+
+    /// Registers a function generating proofs for requests with the specified AIR identifier.
+    ///
+    /// This can be used for requests carrying application-defined inputs (see
+    /// [ProofRequest::inputs()]), and for provers whose traces are not [TraceTable]s.
+    ///
+    /// # Panics
+    /// Panics if a prover has already been registered for the specified AIR identifier.
+    pub fn register<F>(&mut self, air_id: u32, prove: F)
+    where
+        F: Fn(&ProofRequest) -> Result<StarkProof, ProverError> + Send + Sync + 'static,
+    {
+        let previous = self.provers.insert(air_id, Box::new(prove));
+        assert!(previous.is_none(), "prover for AIR identifier {air_id} is already registered");
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if a prover is registered for the specified AIR identifier.
+    pub fn contains(&self, air_id: u32) -> bool {
+        self.provers.contains_key(&air_id)
+    }
+
+    // PROOF GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a proof for the specified request using the prover registered under the AIR
+    /// identifier of the request.
+    ///
+    /// # Errors
+    /// Returns an error if no prover is registered for the AIR identifier of the request, or if
+    /// the registered prover fails to generate the proof.
+    pub fn prove(&self, request: &ProofRequest) -> Result<StarkProof, ProverError> {
+        let prove = self.provers.get(&request.air_id()).ok_or_else(|| {
+            ProverError::RemoteProvingFailed(format!(
+                "no prover is registered for AIR identifier {}",
+                request.air_id()
+            ))
+        })?;
+        prove(request)
+    }
+
+    /// Generates a proof for the serialized request and returns the serialized [ProofResponse].
+    ///
+    /// Errors (including deserialization errors) are reported via [ProofResponse::Error].
+    pub fn handle(&self, request: &[u8]) -> Vec<u8> {
+        let result = ProofRequest::read_from_bytes(request)
+            .map_err(|err| {
+                ProverError::RemoteProvingFailed(format!("failed to read proof request: {err}"))
+            })
+            .and_then(|request| self.prove(&request));
+        ProofResponse::from(result).to_bytes()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Re-assembles a trace table from the main trace segment and trace info of the request.
+fn read_trace_table<B: StarkField>(request: &ProofRequest) -> Result<TraceTable<B>, ProverError> {
+    let invalid_trace = |reason: &str| ProverError::InvalidTraceShape(reason.into());
+    let main_segment = request
+        .read_main_segment::<B>()
+        .map_err(|err| invalid_trace(&err.to_string()))?;
+    let trace_info = request.trace_info().expect("request contains an execution trace");
+    if trace_info.is_multi_segment() {
+        return Err(invalid_trace("trace tables cannot have auxiliary segments"));
+    }
+    if trace_info.length().ilog2() > B::TWO_ADICITY {
+        return Err(invalid_trace("trace length is not supported by the base field"));
+    }
+
+    let mut trace = TraceTable::init(main_segment.into_columns());
+    trace.set_meta(trace_info.meta().to_vec());
+    Ok(trace)
+}
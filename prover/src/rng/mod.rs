@@ -0,0 +1,104 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crypto::{Digest, Hasher};
+use math::FieldElement;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const MAX_ELEMENT_BYTES: usize = 64;
+
+// PROVER RNG
+// ================================================================================================
+/// A deterministic random number generator for values chosen by the prover.
+///
+/// Unlike the public coin, which is used to derive verifier challenges and is fully determined
+/// by the proof transcript, this generator produces values which are chosen by the prover and are
+/// not known to the verifier in advance (e.g., salts or blinding factors). All such values must be
+/// drawn from a single instance of this generator built via
+/// [Prover::new_rng()](crate::Prover::new_rng); thus, when the prover is instantiated with a fixed
+/// seed (see [Prover::rng_seed()](crate::Prover::rng_seed)), proof generation is fully
+/// deterministic, and identical inputs result in byte-identical proofs.
+///
+/// Internally, the generator works similarly to [DefaultRandomCoin](crypto::DefaultRandomCoin):
+/// its state consists of a hash of the seed and a counter, and each output is computed as
+/// hash(`seed` || `counter`) after incrementing the counter.
+#[derive(Debug, Clone)]
+pub struct ProverRng<H: Hasher> {
+    seed: H::Digest,
+    counter: u64,
+}
+
+impl<H: Hasher> ProverRng<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new generator instantiated with the specified seed.
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self {
+            seed: H::hash(&seed),
+            counter: 0,
+        }
+    }
+
+    /// Returns a new generator instantiated with a seed drawn from the randomness source of the
+    /// operating system.
+    ///
+    /// This function is available only when the crate is compiled with `std` feature. On
+    /// `wasm32-unknown-unknown` target, the seed is read via the Web Crypto API.
+    ///
+    /// # Panics
+    /// Panics if the randomness source of the operating system is not available.
+    #[cfg(feature = "std")]
+    pub fn from_entropy() -> Self {
+        let mut seed = [0u8; 32];
+        getrandom::getrandom(&mut seed).expect("failed to read randomness from the OS");
+        Self::new(seed)
+    }
+
+    // DRAW METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Fills the specified buffer with pseudo-random bytes.
+    pub fn fill_bytes(&mut self, buffer: &mut [u8]) {
+        // digests of hash functions with collision resistance below 128 bits are shorter than
+        // 32 bytes, and the remaining bytes returned from Digest::as_bytes() are set to zeros
+        let digest_bytes = (H::COLLISION_RESISTANCE as usize / 4).min(32);
+        for chunk in buffer.chunks_mut(digest_bytes) {
+            let value = self.next().as_bytes();
+            chunk.copy_from_slice(&value[..chunk.len()]);
+        }
+    }
+
+    /// Returns the next pseudo-random field element.
+    ///
+    /// # Panics
+    /// Panics if an element of field `E` is serialized into more than 64 bytes.
+    pub fn draw<E: FieldElement>(&mut self) -> E {
+        assert!(E::ELEMENT_BYTES <= MAX_ELEMENT_BYTES, "field elements cannot exceed 64 bytes");
+        let mut buffer = [0u8; MAX_ELEMENT_BYTES];
+        let bytes = &mut buffer[..E::ELEMENT_BYTES];
+        loop {
+            // if the bytes do not encode a valid field element, try again
+            self.fill_bytes(bytes);
+            if let Some(element) = E::from_random_bytes(bytes) {
+                return element;
+            }
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Updates the state by incrementing the counter and returns hash(seed || counter).
+    fn next(&mut self) -> H::Digest {
+        self.counter += 1;
+        H::merge_with_int(self.seed, self.counter)
+    }
+}
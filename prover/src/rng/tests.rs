@@ -0,0 +1,54 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ProverRng;
+use crypto::hashers::{Blake3_192, Blake3_256};
+use math::fields::{f128::BaseElement, CubeExtension};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+#[test]
+fn prover_rng_is_deterministic() {
+    let mut rng1 = ProverRng::<Blake3>::new([1; 32]);
+    let mut rng2 = ProverRng::<Blake3>::new([1; 32]);
+
+    let e1 = rng1.draw::<BaseElement>();
+    assert_eq!(e1, rng2.draw::<BaseElement>());
+    let e2 = rng1.draw::<BaseElement>();
+    assert_eq!(e2, rng2.draw::<BaseElement>());
+    assert_ne!(e1, e2);
+
+    let mut bytes1 = [0u8; 100];
+    let mut bytes2 = [0u8; 100];
+    rng1.fill_bytes(&mut bytes1);
+    rng2.fill_bytes(&mut bytes2);
+    assert_eq!(bytes1, bytes2);
+
+    // generators with different seeds should produce different values
+    let mut rng3 = ProverRng::<Blake3>::new([2; 32]);
+    assert_ne!(e1, rng3.draw::<BaseElement>());
+}
+
+#[test]
+fn prover_rng_short_digests() {
+    // output of BLAKE3 truncated to 24 bytes must not contain zero padding
+    let mut rng = ProverRng::<Blake3_192<BaseElement>>::new([1; 32]);
+    let mut bytes = [0u8; 64];
+    rng.fill_bytes(&mut bytes);
+    assert_ne!([0u8; 8], bytes[24..32]);
+
+    // elements larger than the digest can be drawn
+    let e1 = rng.draw::<CubeExtension<BaseElement>>();
+    let e2 = rng.draw::<CubeExtension<BaseElement>>();
+    assert_ne!(e1, e2);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn prover_rng_from_entropy() {
+    let mut rng1 = ProverRng::<Blake3>::from_entropy();
+    let mut rng2 = ProverRng::<Blake3>::from_entropy();
+    assert_ne!(rng1.draw::<BaseElement>(), rng2.draw::<BaseElement>());
+}
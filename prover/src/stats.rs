@@ -0,0 +1,299 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    matrix::ColMatrix, AuxTraceRandElements, CancellationToken, CompositionPoly,
+    CompositionPolyTrace, ConstraintCommitment, ConstraintCompositionCoefficients,
+    PeriodicColumnCache, ProofOptions, Prover, ProverError, ProvingPhase, StarkDomain, TraceInfo,
+    TracePolyTable,
+};
+use crate::time::Instant;
+use air::Air;
+use core::mem;
+use crypto::Hasher;
+use math::FieldElement;
+use std::{sync::Mutex, time::Duration};
+
+// PROOF STATS
+// ================================================================================================
+/// Performance metrics collected during generation of a single proof via
+/// [Prover::prove_with_stats()](crate::Prover::prove_with_stats).
+///
+/// Timings are measured for every phase of proof generation reported via
+/// [ProvingPhase]. CPU time and peak resident set size are read from the operating system, and
+/// thus, are available only on Unix-like systems; CPU time is the time spent by all threads of
+/// the process and so, when proofs are generated concurrently by multiple threads, includes work
+/// done for all of these proofs.
+///
+/// The number of hashed bytes and the number of FFTs are computed from the dimensions of the
+/// data extended and committed to by the prover, assuming that the default
+/// [CpuBackend](crate::CpuBackend) pipeline is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofStats {
+    /// Metrics of all executed phases of proof generation, in the order of their execution.
+    pub phases: Vec<PhaseStats>,
+    /// Wall-clock time of the entire proof generation.
+    pub wall_time: Duration,
+    /// CPU time (user and system) of the entire proof generation.
+    pub cpu_time: Option<Duration>,
+    /// Number of bytes hashed to build commitments to the trace segments, the constraint
+    /// composition polynomial, and all FRI layers. Bytes hashed during proof-of-work grinding
+    /// and by the public coin are not included.
+    pub hashed_bytes: usize,
+    /// Number of FFTs and inverse FFTs performed to interpolate and extend the trace segments,
+    /// the constraint composition polynomial, and the DEEP composition polynomial.
+    pub num_ffts: usize,
+    /// Peak resident set size (in bytes) of the process at the end of proof generation.
+    pub peak_rss: Option<usize>,
+}
+
+impl ProofStats {
+    /// Returns metrics of the specified phase, or `None` if the phase was not executed.
+    pub fn phase(&self, phase: ProvingPhase) -> Option<&PhaseStats> {
+        self.phases.iter().find(|stats| stats.phase == phase)
+    }
+}
+
+/// Performance metrics collected for a single phase of proof generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseStats {
+    /// Phase of proof generation the metrics are collected for.
+    pub phase: ProvingPhase,
+    /// Wall-clock time of the phase.
+    pub wall_time: Duration,
+    /// CPU time (user and system) of the phase.
+    pub cpu_time: Option<Duration>,
+}
+
+// INSTRUMENTED PROVER
+// ================================================================================================
+
+/// A prover which delegates all work to the wrapped prover while recording the start of every
+/// phase of proof generation.
+pub(crate) struct InstrumentedProver<'p, P: Prover> {
+    prover: &'p P,
+    phase_starts: Mutex<Vec<(ProvingPhase, Instant, Option<Duration>)>>,
+}
+
+impl<'p, P: Prover> InstrumentedProver<'p, P> {
+    pub fn new(prover: &'p P) -> Self {
+        Self {
+            prover,
+            phase_starts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Consumes this prover and returns stats for the proof generated for the specified AIR.
+    pub fn into_stats(self, air: &P::Air) -> ProofStats {
+        let phase_starts = self.phase_starts.into_inner().expect("phase lock poisoned");
+        let (hashed_bytes, num_ffts) = count_work::<P::Air, P::HashFn>(air);
+        let (_, end_cpu_time, peak_rss) = resource_usage();
+
+        let mut phases = Vec::with_capacity(phase_starts.len());
+        for (i, &(phase, start, start_cpu_time)) in phase_starts.iter().enumerate() {
+            if phase == ProvingPhase::Done {
+                break;
+            }
+            let (end, end_cpu_time) = match phase_starts.get(i + 1) {
+                Some(&(_, end, end_cpu_time)) => (end, end_cpu_time),
+                None => (Instant::now(), end_cpu_time),
+            };
+            phases.push(PhaseStats {
+                phase,
+                wall_time: end - start,
+                cpu_time: elapsed(start_cpu_time, end_cpu_time),
+            });
+        }
+
+        ProofStats {
+            wall_time: phases.iter().map(|stats| stats.wall_time).sum(),
+            cpu_time: phases.iter().map(|stats| stats.cpu_time).sum(),
+            phases,
+            hashed_bytes,
+            num_ffts,
+            peak_rss,
+        }
+    }
+}
+
+impl<'p, P: Prover> Prover for InstrumentedProver<'p, P> {
+    type BaseField = P::BaseField;
+    type Air = P::Air;
+    type Trace = P::Trace;
+    type HashFn = P::HashFn;
+    type RandomCoin = P::RandomCoin;
+    type TraceLde<E>
+        = P::TraceLde<E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+    type ConstraintEvaluator<'a, E>
+        = P::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> <Self::Air as Air>::PublicInputs {
+        self.prover.get_pub_inputs(trace)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.prover.options()
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.prover.new_trace_lde(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.prover.new_evaluator(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn on_phase_start(&self, phase: ProvingPhase) {
+        let (now, cpu_time, _) = resource_usage();
+        self.phase_starts
+            .lock()
+            .expect("phase lock poisoned")
+            .push((phase, now, cpu_time));
+        self.prover.on_phase_start(phase);
+    }
+
+    fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.prover.cancellation_token()
+    }
+
+    fn low_memory_mode(&self) -> bool {
+        self.prover.low_memory_mode()
+    }
+
+    fn lde_directory(&self) -> Option<std::path::PathBuf> {
+        self.prover.lde_directory()
+    }
+
+    fn rng_seed(&self) -> Option<[u8; 32]> {
+        self.prover.rng_seed()
+    }
+
+    fn salt_transcript(&self) -> bool {
+        self.prover.salt_transcript()
+    }
+
+    fn periodic_column_cache(&self) -> Option<&PeriodicColumnCache<Self::BaseField>> {
+        self.prover.periodic_column_cache()
+    }
+
+    fn new_public_coin(&self, seed: &[Self::BaseField]) -> Self::RandomCoin {
+        self.prover.new_public_coin(seed)
+    }
+
+    fn build_constraint_commitment<E>(
+        &self,
+        composition_poly_trace: CompositionPolyTrace<E>,
+        num_trace_poly_columns: usize,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Result<(ConstraintCommitment<E, Self::HashFn>, CompositionPoly<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.prover.build_constraint_commitment(
+            composition_poly_trace,
+            num_trace_poly_columns,
+            domain,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of bytes hashed and the number of FFTs performed to generate a proof for
+/// the specified AIR using hash function `H`.
+fn count_work<A: Air, H: Hasher>(air: &A) -> (usize, usize) {
+    let base_bytes = <A::BaseField as FieldElement>::ELEMENT_BYTES;
+    let ext_bytes = base_bytes * air.options().field_extension().degree() as usize;
+    let digest_bytes = mem::size_of::<H::Digest>();
+
+    let lde_domain_size = air.lde_domain_size();
+    let layout = air.trace_layout();
+    let num_composition_columns = air.context().num_constraint_composition_columns();
+
+    // every leaf of a Merkle tree is hashed, and every internal node hashes two digests
+    let tree_bytes = |num_leaves: usize, leaf_bytes: usize| {
+        num_leaves * leaf_bytes + (num_leaves - 1) * 2 * digest_bytes
+    };
+
+    let mut hashed_bytes = tree_bytes(lde_domain_size, layout.main_trace_width() * base_bytes);
+    for i in 0..layout.num_aux_segments() {
+        hashed_bytes += tree_bytes(lde_domain_size, layout.get_aux_segment_width(i) * ext_bytes);
+    }
+    hashed_bytes += tree_bytes(lde_domain_size, num_composition_columns * ext_bytes);
+
+    let fri_options = air.options().to_fri_options();
+    let folding_factor = fri_options.folding_factor();
+    let mut domain_size = lde_domain_size;
+    for _ in 0..fri_options.num_fri_layers(lde_domain_size) {
+        hashed_bytes += tree_bytes(domain_size / folding_factor, folding_factor * ext_bytes);
+        domain_size /= folding_factor;
+    }
+
+    // every trace column is interpolated and then extended; the composition polynomial is
+    // interpolated once and its columns are extended; the DEEP composition polynomial is
+    // extended once
+    let num_trace_columns = layout.main_trace_width() + layout.aux_trace_width();
+    let num_ffts = 2 * num_trace_columns + 1 + num_composition_columns + 1;
+
+    (hashed_bytes, num_ffts)
+}
+
+/// Returns the current instant together with the CPU time consumed by the process and the peak
+/// resident set size of the process (in bytes), if these are available.
+fn resource_usage() -> (Instant, Option<Duration>, Option<usize>) {
+    let now = Instant::now();
+
+    #[cfg(unix)]
+    {
+        let mut usage = mem::MaybeUninit::<libc::rusage>::uninit();
+        // SAFETY: getrusage() fully initializes the struct when it returns 0
+        if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } == 0 {
+            let usage = unsafe { usage.assume_init() };
+            let to_duration = |time: libc::timeval| {
+                Duration::from_secs(time.tv_sec as u64) + Duration::from_micros(time.tv_usec as u64)
+            };
+            let cpu_time = to_duration(usage.ru_utime) + to_duration(usage.ru_stime);
+
+            // ru_maxrss is reported in bytes on macOS, and in kilobytes on other systems
+            #[cfg(target_os = "macos")]
+            let peak_rss = usage.ru_maxrss as usize;
+            #[cfg(not(target_os = "macos"))]
+            let peak_rss = usage.ru_maxrss as usize * 1024;
+
+            return (now, Some(cpu_time), Some(peak_rss));
+        }
+    }
+
+    (now, None, None)
+}
+
+/// Returns time elapsed between `start` and `end` CPU times, if both are available.
+fn elapsed(start: Option<Duration>, end: Option<Duration>) -> Option<Duration> {
+    match (start, end) {
+        (Some(start), Some(end)) => Some(end.saturating_sub(start)),
+        _ => None,
+    }
+}
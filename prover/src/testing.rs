@@ -0,0 +1,253 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Utilities for testing code which generates and consumes STARK proofs.
+//!
+//! In addition to the proptest strategies and proof mutations of the `testing` module of the AIR
+//! crate (which are re-exported from this module), this module provides a small computation which
+//! can be proven and verified end-to-end. Thus, downstream crates can write integration tests
+//! against the prover and the verifier without defining AIRs of their own:
+//!
+//! * [MockAir] describes a Fibonacci-like computation over an execution trace of any width and
+//!   length; the shape of the computation is configured via the [TraceInfo] of the trace.
+//! * [build_mock_trace()] and [build_invalid_mock_trace()] build execution traces which satisfy
+//!   and which violate constraints of [MockAir] respectively.
+//! * [MockProver] generates proofs for [MockAir] using the specified hash function.
+//! * [mutate_proof()] invalidates specific components of generated proofs.
+//!
+//! ```
+//! # use winter_prover::{testing::*, FieldExtension, ProofOptions, Prover};
+//! let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+//! let prover = MockProver::<Blake3>::new(options);
+//!
+//! let proof = prover.prove(build_mock_trace(3, 64)).unwrap();
+//! let invalid_proof = mutate_proof(&proof, ProofMutation::OodTraceState);
+//! # assert_ne!(proof, invalid_proof);
+//! ```
+
+pub use air::testing::*;
+
+use crate::{
+    matrix::ColMatrix, Air, AirContext, Assertion, AuxTraceRandElements, CancellationToken,
+    ConstraintCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde,
+    EvaluationFrame, ProofOptions, Prover, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable, TransitionConstraintDegree,
+};
+use core::marker::PhantomData;
+use crypto::{DefaultRandomCoin, ElementHasher};
+use math::FieldElement;
+use std::path::PathBuf;
+use utils::collections::Vec;
+
+pub use math::fields::f64::BaseElement;
+
+/// BLAKE3 hash function over the base field of [MockAir].
+pub type Blake3 = crypto::hashers::Blake3_256<BaseElement>;
+
+// MOCK AIR
+// ================================================================================================
+
+/// AIR of a Fibonacci-like computation over an execution trace with `w` columns.
+///
+/// The first row of the trace consists of ones; every next row is computed from the current row
+/// as follows:
+///
+/// ```text
+/// next[i] = current[i + 1]            for i < w - 1
+/// next[w - 1] = current[0] + ... + current[w - 1]
+/// ```
+///
+/// Thus, for a trace of two columns, the columns contain terms of the Fibonacci sequence. The
+/// public input is the value of the last column in the last row of the trace.
+///
+/// All transition constraints have degree one, and so, any blowup factor and number of
+/// transition exemptions supported by the prover can be used.
+pub struct MockAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for MockAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    /// Returns the AIR for an execution trace of the specified width and length.
+    ///
+    /// # Panics
+    /// Panics if the trace consists of fewer than two columns or contains auxiliary segments.
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        assert!(trace_info.width() >= 2, "mock trace must consist of at least two columns");
+        assert!(!trace_info.is_multi_segment(), "mock trace must consist of a single segment");
+        let degrees = vec![TransitionConstraintDegree::new(1); trace_info.width()];
+        let num_assertions = trace_info.width() + 1;
+        MockAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        let width = current.len();
+
+        for i in 0..width - 1 {
+            result[i] = next[i] - current[i + 1];
+        }
+        result[width - 1] = next[width - 1] - current.iter().fold(E::ZERO, |acc, &x| acc + x);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let width = self.trace_info().width();
+        let last_step = self.trace_length() - 1;
+        let mut result = (0..width)
+            .map(|column| Assertion::single(column, 0, BaseElement::ONE))
+            .collect::<Vec<_>>();
+        result.push(Assertion::single(width - 1, last_step, self.result));
+        result
+    }
+}
+
+// MOCK TRACES
+// ================================================================================================
+
+/// Returns an execution trace of the computation described by [MockAir] with the specified
+/// number of columns and rows.
+///
+/// # Panics
+/// Panics if `width` is smaller than two, or `length` is not a power of two or is smaller than 8.
+pub fn build_mock_trace(width: usize, length: usize) -> TraceTable<BaseElement> {
+    assert!(width >= 2, "mock trace must consist of at least two columns");
+    let mut trace = TraceTable::new(width, length);
+    trace.fill(
+        |state| state.fill(BaseElement::ONE),
+        |_, state| {
+            let sum = state.iter().fold(BaseElement::ZERO, |acc, &x| acc + x);
+            state.rotate_left(1);
+            state[width - 1] = sum;
+        },
+    );
+    trace
+}
+
+/// Returns an execution trace with the specified number of columns and rows which violates a
+/// transition constraint of [MockAir] in the middle of the trace.
+///
+/// The public input derived from the trace (i.e., the value in the last row) is consistent with
+/// the rest of the trace, and thus, the violated transition constraint is the only reason for
+/// which the trace is invalid. When compiled in debug mode, the prover rejects such traces with
+/// [ProverError::InvalidTrace](crate::ProverError::InvalidTrace); in release mode, the prover
+/// generates a proof which is rejected by the verifier.
+///
+/// # Panics
+/// Panics if `width` is smaller than two, or `length` is not a power of two or is smaller than 8.
+pub fn build_invalid_mock_trace(width: usize, length: usize) -> TraceTable<BaseElement> {
+    let mut trace = build_mock_trace(width, length);
+    let value = trace.get(0, length / 2);
+    trace.set(0, length / 2, value + BaseElement::ONE);
+    trace
+}
+
+// MOCK PROVER
+// ================================================================================================
+
+/// Prover of the computation described by [MockAir] using hash function `H`.
+pub struct MockProver<H: ElementHasher<BaseField = BaseElement> = Blake3> {
+    options: ProofOptions,
+    cancellation_token: Option<CancellationToken>,
+    salt_transcript: bool,
+    lde_directory: Option<PathBuf>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> MockProver<H> {
+    /// Returns a new prover which generates proofs with the specified options.
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            cancellation_token: None,
+            salt_transcript: false,
+            lde_directory: None,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Makes proof generation by this prover cancellable via the specified token.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Makes this prover salt the transcript of generated proofs.
+    pub fn with_salted_transcript(mut self) -> Self {
+        self.salt_transcript = true;
+        self
+    }
+
+    /// Makes this prover store low-degree extensions of the constraint composition polynomial
+    /// in the specified directory.
+    pub fn with_lde_directory(mut self, directory: PathBuf) -> Self {
+        self.lde_directory = Some(directory);
+        self
+    }
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> Prover for MockProver<H> {
+    type BaseField = BaseElement;
+    type Air = MockAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(trace.width() - 1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    fn salt_transcript(&self) -> bool {
+        self.salt_transcript
+    }
+
+    fn lde_directory(&self) -> Option<PathBuf> {
+        self.lde_directory.clone()
+    }
+}
@@ -11,6 +11,9 @@ use air::{
 use math::{fields::f128::BaseElement, FieldElement, StarkField};
 use utils::collections::Vec;
 
+#[cfg(feature = "testing")]
+mod proving;
+
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
 
@@ -0,0 +1,488 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    metrics::{self, Metrics},
+    proof::stream::ProofSection,
+    testing::{
+        build_invalid_mock_trace, build_mock_trace, BaseElement, Blake3, MockAir, MockProver,
+    },
+    Air, ByteWriter, CancellationToken, ConstraintViolation, DeserializationError, FieldExtension,
+    ProofOptions, Prover, ProverError, ProvingConfig, ProvingPhase, StarkProof, Trace,
+};
+use math::FieldElement;
+use std::env::temp_dir;
+use utils::collections::Vec;
+
+#[test]
+fn prove_batch() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let lengths = [16, 32, 16];
+    let proofs = prover
+        .prove_batch(lengths.iter().map(|&length| build_mock_trace(2, length)))
+        .unwrap();
+    assert_eq!(lengths.len(), proofs.len());
+
+    // every proof in the batch should be identical to a proof generated on its own
+    for (&length, proof) in lengths.iter().zip(proofs) {
+        let expected = prover.prove(build_mock_trace(2, length)).unwrap();
+        assert_eq!(expected.to_bytes(), proof.to_bytes());
+    }
+}
+
+#[test]
+fn prove_with_many_queries() {
+    let options = ProofOptions::new(400, 8, 0, FieldExtension::None, 4, 31);
+    let prover = MockProver::<Blake3>::new(options);
+    let proof = prover.prove(build_mock_trace(2, 512)).unwrap();
+    assert!(proof.num_unique_queries > 255);
+
+    let parsed_proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(proof, parsed_proof);
+}
+
+#[test]
+fn prove_with_distinct_queries() {
+    let options = build_options();
+
+    // in a small domain, some of the queries drawn with replacement are duplicates
+    let prover = MockProver::<Blake3>::new(options.clone());
+    let proof = prover.prove(build_mock_trace(2, 8)).unwrap();
+    assert!((proof.num_unique_queries as usize) < options.num_queries());
+
+    // while with distinct queries, every query is opened
+    let prover = MockProver::<Blake3>::new(options.clone().with_distinct_queries(true));
+    let proof = prover.prove(build_mock_trace(2, 8)).unwrap();
+    assert_eq!(options.num_queries(), proof.num_unique_queries as usize);
+}
+
+#[test]
+fn prove_with_salted_transcript() {
+    let prover = MockProver::<Blake3>::new(build_options()).with_salted_transcript();
+    let trace = build_mock_trace(2, 16);
+    let result = prover.get_pub_inputs(&trace);
+    let proof1 = prover.prove(trace.clone()).unwrap();
+    let proof2 = prover.prove(trace.clone()).unwrap();
+
+    // proofs of the same statement are salted differently, and differ in everything except the
+    // commitment to the main trace segment
+    let salt1 = *proof1.context.salt().unwrap();
+    assert_ne!(Some(&salt1), proof2.context.salt());
+    let num_fri_layers = proof1
+        .options()
+        .to_fri_options()
+        .num_fri_layers(proof1.context.lde_domain_size());
+    let parse =
+        |proof: &StarkProof| proof.commitments.clone().parse::<Blake3>(1, num_fri_layers).unwrap();
+    let (trace_roots1, constraint_root1, _) = parse(&proof1);
+    let (trace_roots2, constraint_root2, _) = parse(&proof2);
+    assert_eq!(trace_roots1, trace_roots2);
+    assert_ne!(constraint_root1, constraint_root2);
+    assert_ne!(proof1.ood_frame, proof2.ood_frame);
+
+    // a proof resumed from a checkpoint re-uses the salt of the checkpoint
+    let mut checkpoint = Vec::new();
+    let config =
+        ProvingConfig::new().with_checkpoint_handler(|bytes| checkpoint.extend_from_slice(bytes));
+    let proof = prover.prove_with_config(trace, config).unwrap();
+    let resumed_proof = prover.resume_proof(result, &checkpoint).unwrap();
+    assert_eq!(proof.to_bytes(), resumed_proof.to_bytes());
+}
+
+#[test]
+fn prove_with_proof_stream() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let proof = prover.prove(build_mock_trace(2, 16)).unwrap();
+    let mut stream = Vec::new();
+    let config = ProvingConfig::new().with_proof_stream(&mut stream);
+    prover.prove_with_config(build_mock_trace(2, 16), config).unwrap();
+
+    // the streamed proof is the same proof as the one generated in memory
+    assert_eq!(proof.to_stream_bytes::<Blake3>().unwrap(), stream);
+    let streamed_proof = StarkProof::from_stream_bytes(&stream).unwrap();
+    assert_eq!(proof.to_bytes(), streamed_proof.to_bytes());
+
+    // sections are written as soon as they are finalized: stopping the prover once the
+    // out-of-domain frame has been written leaves all preceding sections in the writer
+    struct CancellingWriter {
+        tags: Vec<u8>,
+        token: CancellationToken,
+    }
+    impl ByteWriter for CancellingWriter {
+        fn write_u8(&mut self, value: u8) {
+            // single bytes are written only for section tags
+            self.tags.push(value);
+            if value == ProofSection::OodFrame as u8 {
+                self.token.cancel();
+            }
+        }
+        fn write_bytes(&mut self, _values: &[u8]) {}
+    }
+    let token = CancellationToken::new();
+    let prover = prover.with_cancellation_token(token.clone());
+    let mut writer = CancellingWriter {
+        tags: Vec::new(),
+        token,
+    };
+    let config = ProvingConfig::new().with_proof_stream(&mut writer);
+    assert!(matches!(
+        prover.prove_with_config(build_mock_trace(2, 16), config),
+        Err(ProverError::Cancelled(ProvingPhase::FriLayers))
+    ));
+    let expected = [
+        ProofSection::Context,
+        ProofSection::TraceCommitment,
+        ProofSection::ConstraintCommitment,
+        ProofSection::OodFrame,
+    ];
+    assert_eq!(expected.map(|section| section as u8).to_vec(), writer.tags);
+
+    // sections must be complete and in the expected order
+    let mut longer_stream = stream.clone();
+    longer_stream.push(0);
+    assert_eq!(
+        Err(DeserializationError::UnconsumedBytes),
+        StarkProof::from_stream_bytes(&longer_stream)
+    );
+    assert!(StarkProof::from_stream_bytes(&stream[..stream.len() - 1]).is_err());
+    let sections = proof.to_stream_sections::<Blake3>().unwrap();
+    let mut reordered = Vec::new();
+    for (section, payload) in sections.iter().rev() {
+        section.write_section(&mut reordered, payload);
+    }
+    assert!(matches!(
+        StarkProof::from_stream_bytes(&reordered),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn prove_with_cancellation_token() {
+    let token = CancellationToken::new();
+    let prover = MockProver::<Blake3>::new(build_options()).with_cancellation_token(token.clone());
+    assert!(prover.prove(build_mock_trace(2, 16)).is_ok());
+
+    // once the token is cancelled, proof generation stops before the first phase starts
+    token.cancel();
+    assert_eq!(
+        Err(ProverError::Cancelled(ProvingPhase::TraceCommitment)),
+        prover.prove(build_mock_trace(2, 16))
+    );
+}
+
+#[test]
+fn prove_with_cancellation_during_fri_grinding() {
+    // cancel proof generation as soon as the first FRI layer is committed to; with the maximum
+    // layer grinding factor, the search for the layer nonce would not complete otherwise
+    struct CancellingWriter {
+        token: CancellationToken,
+    }
+    impl ByteWriter for CancellingWriter {
+        fn write_u8(&mut self, value: u8) {
+            if value == ProofSection::FriCommitment as u8 {
+                self.token.cancel();
+            }
+        }
+        fn write_bytes(&mut self, _values: &[u8]) {}
+    }
+
+    let token = CancellationToken::new();
+    let options = build_options().with_fri_layer_grinding_factor(32);
+    let prover = MockProver::<Blake3>::new(options).with_cancellation_token(token.clone());
+    let mut writer = CancellingWriter { token };
+    let config = ProvingConfig::new().with_proof_stream(&mut writer);
+    assert_eq!(
+        Err(ProverError::Cancelled(ProvingPhase::FriLayers)),
+        prover.prove_with_config(build_mock_trace(2, 16), config)
+    );
+}
+
+#[test]
+fn prove_with_lde_directory() {
+    let trace = build_mock_trace(2, 16);
+    let expected = MockProver::<Blake3>::new(build_options()).prove(trace.clone()).unwrap();
+
+    // a proof generated with constraint evaluations stored on disk is the same as the one
+    // generated in memory
+    let prover = MockProver::<Blake3>::new(build_options()).with_lde_directory(temp_dir());
+    assert_eq!(expected, prover.prove(trace.clone()).unwrap());
+
+    // failure to store the evaluations is reported as an error
+    let prover = MockProver::<Blake3>::new(build_options())
+        .with_lde_directory(temp_dir().join("winter_missing_lde_directory"));
+    assert!(matches!(prover.prove(trace), Err(ProverError::LdeStorageError(_))));
+}
+
+#[test]
+fn prove_with_invalid_options() {
+    // the LDE domain of a trace of 8 rows extended with 8x blowup has 64 elements, and thus,
+    // cannot be queried at 100 positions
+    let options = ProofOptions::new(100, 8, 0, FieldExtension::None, 4, 7);
+    let prover = MockProver::<Blake3>::new(options);
+    assert!(matches!(
+        prover.prove(build_mock_trace(2, 8)),
+        Err(ProverError::InvalidProofOptions(_))
+    ));
+}
+
+#[test]
+fn prove_with_stats() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let (proof, stats) = prover.prove_with_stats(build_mock_trace(2, 32)).unwrap();
+    assert_eq!(prover.prove(build_mock_trace(2, 32)).unwrap().to_bytes(), proof.to_bytes());
+
+    // the trace has no auxiliary segments, and so all other phases are executed exactly once
+    assert_eq!(7, stats.phases.len());
+    assert_eq!(ProvingPhase::TraceCommitment, stats.phases[0].phase);
+    assert!(stats.phase(ProvingPhase::FriLayers).is_some());
+    assert!(stats.phase(ProvingPhase::AuxTraceCommitment(0)).is_none());
+    assert_eq!(stats.wall_time, stats.phases.iter().map(|phase| phase.wall_time).sum());
+
+    // 2 trace columns are interpolated and extended, the composition polynomial is interpolated
+    // and its single column is extended, and the DEEP composition polynomial is extended
+    assert_eq!(2 * 2 + 1 + 1 + 1, stats.num_ffts);
+    assert!(stats.hashed_bytes > 256 * 2 * 8);
+}
+
+#[test]
+fn estimate_memory() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let trace = build_mock_trace(2, 32);
+    let air = MockAir::new(trace.get_info(), prover.get_pub_inputs(&trace), build_options());
+    let estimate = prover.estimate_memory(&air);
+
+    // 2 columns of 32 elements of 8 bytes each, extended to the domain of 256 elements
+    assert_eq!(2 * 32 * 8, estimate.trace_polys);
+    assert_eq!(2 * 256 * 8, estimate.trace_lde);
+    assert_eq!(2 * 256 * 32, estimate.trace_trees);
+    assert!(estimate.fri_layers > 256 * 8);
+    assert!(estimate.peak >= estimate.trace_lde + estimate.composition_lde + estimate.fri_layers);
+}
+
+#[test]
+fn estimate_proof_size() {
+    let base = build_options();
+    let candidates = [
+        (512, base.clone()),
+        (512, ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 7)),
+        (2048, base.clone().with_distinct_queries(true)),
+        (
+            512,
+            ProofOptions::new(42, 8, 0, FieldExtension::None, 8, 15)
+                .with_fri_layer_grinding_factor(4),
+        ),
+    ];
+    for (trace_length, options) in candidates {
+        let prover = MockProver::<Blake3>::new(options.clone());
+        let trace = build_mock_trace(2, trace_length);
+        let air = MockAir::new(trace.get_info(), prover.get_pub_inputs(&trace), options);
+        let proof = prover.prove(trace).unwrap();
+
+        // the estimate is computed without generating the proof
+        let estimate = StarkProof::estimate_size::<_, Blake3>(&air) as f64;
+        let actual = proof.to_bytes().len() as f64;
+        assert!(
+            (estimate - actual).abs() / actual < 0.05,
+            "estimate {estimate}, actual {actual}"
+        );
+    }
+}
+
+#[test]
+fn estimate_security() {
+    let options = build_options();
+    let prover = MockProver::<Blake3>::new(options.clone());
+    let proof = prover.prove(build_mock_trace(2, 512)).unwrap();
+
+    // security can be estimated from proof options and trace dimensions alone
+    let trace_length = proof.get_trace_info().length();
+    let trace_width = proof.get_trace_info().width();
+    for conjectured in [true, false] {
+        assert_eq!(
+            proof.security_level::<Blake3>(conjectured),
+            options.security_level::<Blake3>(64, trace_length, trace_width, conjectured)
+        );
+    }
+
+    // the report breaks down both security levels
+    let report = proof.security_report::<Blake3>();
+    assert_eq!(report, options.security_report::<Blake3>(64, trace_length, trace_width));
+    assert_eq!(proof.security_level::<Blake3>(true), report.conjectured.security_level);
+    assert_eq!(proof.security_level::<Blake3>(false), report.proven.security_level);
+    assert!(report.to_string().contains("conjectured security"));
+}
+
+#[test]
+fn validate_trace() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let report = prover.validate_trace::<BaseElement>(build_mock_trace(2, 16)).unwrap();
+    assert!(report.is_valid());
+
+    // corrupt a single cell of the trace; this breaks transitions into and out of the row
+    let mut trace = build_mock_trace(2, 16);
+    trace.set(0, 3, BaseElement::ZERO);
+    let report = prover.validate_trace::<BaseElement>(trace).unwrap();
+    assert!(!report.is_valid());
+
+    match &report.violations()[0] {
+        ConstraintViolation::MainTransition {
+            step,
+            current,
+            next,
+            ..
+        } => {
+            assert_eq!(2, *step);
+            assert_eq!(BaseElement::ZERO, next[0]);
+            assert_eq!(2, current.len());
+        }
+        violation => panic!("unexpected violation: {violation}"),
+    }
+    assert!(report.violations().iter().all(|violation| violation.step() <= 3));
+    // invalid mock traces are rejected as well
+    let report = prover.validate_trace::<BaseElement>(build_invalid_mock_trace(2, 16)).unwrap();
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn check_trace() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let trace = build_mock_trace(2, 8);
+    let air = MockAir::new(trace.get_info(), prover.get_pub_inputs(&trace), build_options());
+    let report = prover.check::<BaseElement>(trace, &air).unwrap();
+    assert!(report.is_valid());
+    assert_eq!(8, report.max_composition_degree());
+    assert!(report.composition_degree().is_some_and(|degree| degree < 8));
+
+    // corrupting a cell of the trace yields constraint violations; the constraint composition
+    // polynomial is not computed for such a trace
+    let mut trace = build_mock_trace(2, 8);
+    trace.set(0, 3, BaseElement::ZERO);
+    let report = prover.check::<BaseElement>(trace, &air).unwrap();
+    assert!(!report.is_valid());
+    assert!(!report.validation().is_valid());
+    assert_eq!(None, report.composition_degree());
+
+    // a trace which does not match the AIR is rejected
+    assert!(matches!(
+        prover.check::<BaseElement>(build_mock_trace(2, 16), &air),
+        Err(ProverError::InvalidTraceShape(_))
+    ));
+}
+
+#[test]
+fn open_trace_column() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let trace = build_mock_trace(2, 64);
+    let proof = prover.prove(trace.clone()).unwrap();
+
+    // at points of the trace domain, column polynomials evaluate to the values in the trace
+    let g = <BaseElement as math::StarkField>::get_root_of_unity(trace.length().ilog2());
+    for (column, step) in [(0, 0), (1, 5), (0, trace.length() - 1)] {
+        let point = g.exp(step as u64);
+        let opening = prover.open_trace_column(&trace, &proof, column, point).unwrap();
+        assert_eq!(trace.get(column, step), opening.value());
+    }
+
+    // openings cannot be generated at points of the LDE domain or for a different trace
+    let offset = build_options().domain_offset::<BaseElement>();
+    let err = prover.open_trace_column(&trace, &proof, 0, offset).unwrap_err();
+    assert_eq!("prover.invalid_trace_opening", err.code());
+    let other_trace = build_mock_trace(2, 128);
+    assert!(prover.open_trace_column(&other_trace, &proof, 0, BaseElement::new(11)).is_err());
+}
+
+#[test]
+#[cfg(feature = "tracing")]
+fn prove_with_tracing_spans() {
+    use std::sync::{Arc, Mutex};
+    use tracing::{span, subscriber, Event, Metadata, Subscriber};
+
+    /// Records names of all spans created while it is the default subscriber.
+    #[derive(Default)]
+    struct SpanRecorder(Mutex<Vec<&'static str>>);
+
+    impl Subscriber for SpanRecorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            span::Id::from_u64(names.len() as u64)
+        }
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+        fn event(&self, _event: &Event<'_>) {}
+        fn enter(&self, _span: &span::Id) {}
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    let prover = MockProver::<Blake3>::new(build_options());
+    let recorder = Arc::new(SpanRecorder::default());
+    subscriber::with_default(recorder.clone(), || prover.prove(build_mock_trace(2, 64))).unwrap();
+    let spans = recorder.0.lock().unwrap().clone();
+
+    for name in ["prove", "trace_lde", "trace_commitment", "constraint_evaluation", "fri_layer"] {
+        assert!(spans.contains(&name), "missing prover span {name}");
+    }
+    assert_eq!(Some(&"prove"), spans.first());
+    assert_eq!(Some(&"queries"), spans.last());
+}
+
+#[test]
+fn prove_with_metrics() {
+    use std::{collections::BTreeMap, sync::Mutex};
+
+    struct CountingMetrics(Mutex<BTreeMap<&'static str, u64>>);
+
+    impl Metrics for CountingMetrics {
+        fn increment_counter(&self, name: &'static str, value: u64) {
+            *self.0.lock().unwrap().entry(name).or_default() += value;
+        }
+
+        fn record_histogram(&self, name: &'static str, _value: f64) {
+            *self.0.lock().unwrap().entry(name).or_default() += 1;
+        }
+    }
+
+    impl CountingMetrics {
+        fn get(&self, name: &'static str) -> u64 {
+            self.0.lock().unwrap().get(name).copied().unwrap_or_default()
+        }
+    }
+
+    // metrics are global and other tests may report them concurrently, so only lower bounds on
+    // the reported values can be checked
+    static METRICS: CountingMetrics = CountingMetrics(Mutex::new(BTreeMap::new()));
+    assert!(metrics::set_metrics(&METRICS).is_ok(), "metrics backend already installed");
+    assert!(metrics::is_enabled());
+
+    let prover = MockProver::<Blake3>::new(build_options());
+    assert!(prover.prove(build_mock_trace(2, 16)).is_ok());
+    assert!(METRICS.get(metrics::PROOFS_GENERATED) >= 1);
+    assert!(METRICS.get(metrics::PROOF_SIZE) >= 1);
+    assert!(METRICS.get(metrics::PROVING_TIME) >= 1);
+    for phase in [
+        ProvingPhase::TraceCommitment,
+        ProvingPhase::FriLayers,
+        ProvingPhase::ProofAssembly,
+    ] {
+        assert!(METRICS.get(phase.duration_metric().unwrap()) >= 1);
+    }
+
+    // cancelled proof generation is reported as a failure
+    let token = CancellationToken::new();
+    token.cancel();
+    let prover = MockProver::<Blake3>::new(build_options()).with_cancellation_token(token);
+    assert!(prover.prove(build_mock_trace(2, 16)).is_err());
+    assert!(METRICS.get(metrics::PROVING_FAILURES) >= 1);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7)
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Time measurements used for reporting durations of proof generation phases.
+//!
+//! The system clock is not available on `wasm32-unknown-unknown` target (i.e., calling
+//! `std::time::Instant::now()` panics), and thus, on this target, time is not measured and all
+//! durations are reported as zero.
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use std::time::Instant;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub use unmeasured::Instant;
+
+// UNMEASURED INSTANT
+// ================================================================================================
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod unmeasured {
+    use core::{ops::Sub, time::Duration};
+
+    /// A placeholder for [std::time::Instant] on targets without a system clock; all instants are
+    /// equal, and thus, time elapsed between any two instants is zero.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Instant;
+
+    impl Instant {
+        /// Returns an instant corresponding to "now".
+        pub fn now() -> Self {
+            Self
+        }
+
+        /// Returns the amount of time elapsed since this instant, which is always zero.
+        pub fn elapsed(&self) -> Duration {
+            Duration::ZERO
+        }
+    }
+
+    impl Sub for Instant {
+        type Output = Duration;
+
+        fn sub(self, _other: Self) -> Duration {
+            Duration::ZERO
+        }
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColMatrix, Trace};
+use air::{EvaluationFrame, TraceLayout};
+use core::iter::FusedIterator;
+use math::{FieldElement, StarkField};
+
+// AUXILIARY TRACE BUILDER
+// ================================================================================================
+/// Defines how the auxiliary segment of an execution trace is built from the main segment.
+///
+/// Auxiliary columns (e.g., running products for permutation checks, or running sums for lookup
+/// arguments) depend on random elements drawn by the prover after the main segment has been
+/// committed to. A builder receives these random elements together with an iterator over the rows
+/// of the main segment, and thus, does not need to retain its own copy of the main segment.
+///
+/// A builder is attached to an execution trace via [TraceWithAux], which implements the [Trace]
+/// trait by delegating everything except building of the auxiliary segment to the wrapped trace.
+pub trait AuxTraceBuilder<B: StarkField> {
+    /// Returns the number of columns in the auxiliary segment built by this builder.
+    fn aux_trace_width(&self) -> usize;
+
+    /// Returns the number of random elements required to build the auxiliary segment.
+    fn num_rand_elements(&self) -> usize;
+
+    /// Builds and returns columns of the auxiliary segment.
+    ///
+    /// `main_trace` iterates over all rows of the main segment, and `rand_elements` contains
+    /// [AuxTraceBuilder::num_rand_elements()] random elements drawn by the prover. The returned
+    /// matrix must contain [AuxTraceBuilder::aux_trace_width()] columns, each as long as the main
+    /// segment.
+    fn build_aux_columns<E>(
+        &mut self,
+        main_trace: MainTraceRows<B>,
+        rand_elements: &[E],
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = B>;
+}
+
+// TRACE WITH AUXILIARY SEGMENT
+// ================================================================================================
+/// An execution trace whose auxiliary segment is built by an [AuxTraceBuilder].
+///
+/// The main segment is provided by the wrapped trace, which must not have auxiliary segments of
+/// its own. When the prover requests the auxiliary segment, the builder is invoked with the rows
+/// of the main segment as returned by [Trace::with_main_segment()]; thus, traces which store
+/// columns in packed form expand them only for the duration of the build.
+pub struct TraceWithAux<T: Trace, A: AuxTraceBuilder<T::BaseField>> {
+    trace: T,
+    builder: A,
+    layout: TraceLayout,
+}
+
+impl<T: Trace, A: AuxTraceBuilder<T::BaseField>> TraceWithAux<T, A> {
+    /// Returns a new execution trace with the main segment of the specified trace and the
+    /// auxiliary segment built by the specified builder.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `trace` already has auxiliary segments.
+    /// * Width of the auxiliary segment or the number of random elements specified by the builder
+    ///   are zero, or otherwise describe an invalid trace layout.
+    pub fn new(trace: T, builder: A) -> Self {
+        assert_eq!(
+            0,
+            trace.layout().num_aux_segments(),
+            "wrapped trace must not have auxiliary segments"
+        );
+        assert!(
+            builder.aux_trace_width() > 0,
+            "auxiliary segment must consist of at least one column"
+        );
+        let layout = TraceLayout::new(
+            trace.main_trace_width(),
+            [builder.aux_trace_width()],
+            [builder.num_rand_elements()],
+        );
+        Self {
+            trace,
+            builder,
+            layout,
+        }
+    }
+
+    /// Returns a reference to the wrapped trace.
+    pub fn trace(&self) -> &T {
+        &self.trace
+    }
+
+    /// Returns a reference to the auxiliary trace builder.
+    pub fn builder(&self) -> &A {
+        &self.builder
+    }
+
+    /// Returns the wrapped trace and the auxiliary trace builder.
+    pub fn into_parts(self) -> (T, A) {
+        (self.trace, self.builder)
+    }
+}
+
+impl<T: Trace, A: AuxTraceBuilder<T::BaseField>> Trace for TraceWithAux<T, A> {
+    type BaseField = T::BaseField;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace.length()
+    }
+
+    fn meta(&self) -> &[u8] {
+        self.trace.meta()
+    }
+
+    fn main_segment(&self) -> &ColMatrix<Self::BaseField> {
+        self.trace.main_segment()
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        self.trace.read_main_frame(row_idx, frame)
+    }
+
+    fn with_main_segment<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&ColMatrix<Self::BaseField>) -> R,
+    {
+        self.trace.with_main_segment(f)
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // the builder defines a single auxiliary segment
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        let Self { trace, builder, .. } = self;
+        let columns = trace.with_main_segment(|main_segment| {
+            builder.build_aux_columns(MainTraceRows::new(main_segment), rand_elements)
+        });
+
+        assert_eq!(
+            builder.aux_trace_width(),
+            columns.num_cols(),
+            "expected the auxiliary segment to consist of {} columns, but was {}",
+            builder.aux_trace_width(),
+            columns.num_cols()
+        );
+        assert_eq!(
+            trace.length(),
+            columns.num_rows(),
+            "expected the auxiliary segment to consist of {} rows, but was {}",
+            trace.length(),
+            columns.num_rows()
+        );
+
+        Some(columns)
+    }
+}
+
+// MAIN TRACE ROWS
+// ================================================================================================
+/// An iterator over the rows of the main segment of an execution trace.
+///
+/// Rows are returned as lightweight [MainTraceRow] views which read values directly from the main
+/// segment; no data is copied while iterating.
+#[derive(Clone)]
+pub struct MainTraceRows<'a, B: StarkField> {
+    matrix: &'a ColMatrix<B>,
+    row_idx: usize,
+}
+
+impl<'a, B: StarkField> MainTraceRows<'a, B> {
+    /// Returns a new iterator over the rows of the specified matrix.
+    pub fn new(matrix: &'a ColMatrix<B>) -> Self {
+        Self { matrix, row_idx: 0 }
+    }
+
+    /// Returns the number of columns in the main segment.
+    pub fn num_cols(&self) -> usize {
+        self.matrix.num_cols()
+    }
+
+    /// Returns the number of rows in the main segment.
+    pub fn num_rows(&self) -> usize {
+        self.matrix.num_rows()
+    }
+}
+
+impl<'a, B: StarkField> Iterator for MainTraceRows<'a, B> {
+    type Item = MainTraceRow<'a, B>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row_idx == self.matrix.num_rows() {
+            return None;
+        }
+        let row = MainTraceRow {
+            matrix: self.matrix,
+            row_idx: self.row_idx,
+        };
+        self.row_idx += 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.matrix.num_rows() - self.row_idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, B: StarkField> ExactSizeIterator for MainTraceRows<'a, B> {}
+impl<'a, B: StarkField> FusedIterator for MainTraceRows<'a, B> {}
+
+/// A view into a single row of the main segment of an execution trace.
+#[derive(Clone, Copy)]
+pub struct MainTraceRow<'a, B: StarkField> {
+    matrix: &'a ColMatrix<B>,
+    row_idx: usize,
+}
+
+impl<'a, B: StarkField> MainTraceRow<'a, B> {
+    /// Returns the index of this row in the main segment.
+    pub fn index(&self) -> usize {
+        self.row_idx
+    }
+
+    /// Returns the value of the specified column in this row.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is out of bounds.
+    pub fn get(&self, col_idx: usize) -> B {
+        self.matrix.get(col_idx, self.row_idx)
+    }
+
+    /// Returns the value of the specified column in the row following this row.
+    ///
+    /// Just like the next row of an [EvaluationFrame], the row following the last row of the main
+    /// segment is the first row.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is out of bounds.
+    pub fn get_next(&self, col_idx: usize) -> B {
+        let next_row_idx = (self.row_idx + 1) % self.matrix.num_rows();
+        self.matrix.get(col_idx, next_row_idx)
+    }
+
+    /// Copies values of all columns in this row into the provided slice.
+    ///
+    /// If the slice is shorter than the row, only the leading columns are copied.
+    pub fn read_into(&self, row: &mut [B]) {
+        self.matrix.read_row_into(self.row_idx, row)
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::TraceTable;
+use air::TraceInfo;
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// TRACE ROW
+// ================================================================================================
+/// Defines a typed row of an execution trace in which every column has a name and a fixed index.
+///
+/// This trait is usually implemented via the [trace_row!](crate::trace_row) macro, which also
+/// generates associated constants holding indexes of all columns. These constants can be used to
+/// access columns of an [EvaluationFrame](air::EvaluationFrame) in
+/// [Air::evaluate_transition()](air::Air::evaluate_transition) and when defining assertions, and
+/// thus, the trace builder and the AIR cannot disagree on the layout of the trace.
+pub trait TraceRow: Sized {
+    /// Base field for the columns of this row.
+    type BaseField: StarkField;
+
+    /// Names of the columns in this row, in the order of their indexes.
+    const COLUMN_NAMES: &'static [&'static str];
+
+    /// Number of columns in this row.
+    const WIDTH: usize = Self::COLUMN_NAMES.len();
+
+    /// Writes values of all columns of this row into the specified slice.
+    ///
+    /// The slice must contain exactly [TraceRow::WIDTH] elements.
+    fn write_into(&self, row: &mut [Self::BaseField]);
+
+    /// Reads a row from the specified slice of column values.
+    ///
+    /// The slice must contain exactly [TraceRow::WIDTH] elements.
+    fn read_from(row: &[Self::BaseField]) -> Self;
+}
+
+/// Defines a struct describing a row of an execution trace and implements [TraceRow] for it.
+///
+/// Every column is specified by a field name and a name of the associated constant which holds
+/// the index of the column in the execution trace. Columns are indexed in the order in which they
+/// are listed. All columns contain elements of the field specified in angle brackets.
+///
+/// For example, the following defines a row with two columns, `a` and `b`, located at indexes
+/// `FibRow::A = 0` and `FibRow::B = 1` respectively:
+///
+/// ```
+/// use winter_prover::{math::fields::f128::BaseElement, trace_row, TraceRow};
+///
+/// trace_row! {
+///     /// A row of a trace which computes Fibonacci numbers two terms at a time.
+///     #[derive(Debug, Clone, Copy)]
+///     pub struct FibRow<BaseElement> {
+///         a => A,
+///         b => B,
+///     }
+/// }
+///
+/// assert_eq!(0, FibRow::A);
+/// assert_eq!(1, FibRow::B);
+/// assert_eq!(2, FibRow::WIDTH);
+/// assert_eq!(&["a", "b"], FibRow::COLUMN_NAMES);
+/// ```
+#[macro_export]
+macro_rules! trace_row {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident<$base_field:ty> {
+            $($(#[$column_meta:meta])* $column:ident => $index:ident),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($(#[$column_meta])* pub $column: $base_field,)+
+        }
+
+        const _: () = {
+            #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+            #[repr(usize)]
+            enum Column {
+                $($index,)+
+            }
+
+            impl $name {
+                $(
+                    #[doc = concat!("Index of the `", stringify!($column), "` column.")]
+                    pub const $index: usize = Column::$index as usize;
+                )+
+            }
+
+            impl $crate::TraceRow for $name {
+                type BaseField = $base_field;
+
+                const COLUMN_NAMES: &'static [&'static str] = &[$(stringify!($column),)+];
+
+                fn write_into(&self, row: &mut [Self::BaseField]) {
+                    debug_assert_eq!(<Self as $crate::TraceRow>::WIDTH, row.len());
+                    $(row[Self::$index] = self.$column;)+
+                }
+
+                fn read_from(row: &[Self::BaseField]) -> Self {
+                    debug_assert_eq!(<Self as $crate::TraceRow>::WIDTH, row.len());
+                    Self {
+                        $($column: row[Self::$index],)+
+                    }
+                }
+            }
+        };
+    };
+}
+
+// TRACE BUILDER
+// ================================================================================================
+/// A builder of execution traces which are populated one typed row at a time.
+///
+/// Rows of the trace are described by a type implementing [TraceRow] trait (usually defined via
+/// the [trace_row!](crate::trace_row) macro). Since the width of the trace is determined by the
+/// row type, rows of a wrong width cannot be appended to the trace, and the width of the resulting
+/// [TraceTable] always matches the number of columns in the row type.
+///
+/// Rows can be appended via the [TraceBuilder::push_row()] method, or generated by executing a
+/// state transition function via the [TraceBuilder::fill()] method. Once all rows have been
+/// appended, a [TraceTable] can be built via the [TraceBuilder::build()] method.
+#[derive(Debug, Clone)]
+pub struct TraceBuilder<R: TraceRow> {
+    columns: Vec<Vec<R::BaseField>>,
+    row: Vec<R::BaseField>,
+    meta: Vec<u8>,
+}
+
+impl<R: TraceRow> TraceBuilder<R> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates a new empty trace builder with enough capacity for `num_rows` rows.
+    ///
+    /// # Panics
+    /// Panics if the row type has no columns or more than 65535 columns.
+    pub fn new(num_rows: usize) -> Self {
+        assert!(R::WIDTH > 0, "execution trace must consist of at least one column");
+        assert!(
+            R::WIDTH <= TraceInfo::MAX_TRACE_WIDTH,
+            "execution trace width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            R::WIDTH
+        );
+
+        Self {
+            columns: (0..R::WIDTH).map(|_| Vec::with_capacity(num_rows)).collect(),
+            row: vec![R::BaseField::ZERO; R::WIDTH],
+            meta: Vec::new(),
+        }
+    }
+
+    /// Sets metadata of the execution trace built by this builder.
+    ///
+    /// # Panics
+    /// Panics if the length of `meta` is greater than 65535.
+    pub fn with_meta(mut self, meta: Vec<u8>) -> Self {
+        assert!(
+            meta.len() <= TraceInfo::MAX_META_LENGTH,
+            "number of metadata bytes cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            meta.len()
+        );
+        self.meta = meta;
+        self
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the provided row to the end of the trace.
+    pub fn push_row(&mut self, row: &R) {
+        row.write_into(&mut self.row);
+        for (column, &value) in self.columns.iter_mut().zip(self.row.iter()) {
+            column.push(value);
+        }
+    }
+
+    /// Appends `num_rows` rows to the end of the trace by executing the provided closures.
+    ///
+    /// The rows are generated as follows:
+    /// - `init` closure returns the first row.
+    /// - `update` closure is used to generate all subsequent rows; it receives the index of the
+    ///   last generated row (starting with 0) and a mutable reference to this row, and must update
+    ///   it to the next row.
+    pub fn fill<I, U>(&mut self, num_rows: usize, init: I, mut update: U)
+    where
+        I: FnOnce() -> R,
+        U: FnMut(usize, &mut R),
+    {
+        if num_rows == 0 {
+            return;
+        }
+
+        let mut state = init();
+        self.push_row(&state);
+        for i in 0..num_rows - 1 {
+            update(i, &mut state);
+            self.push_row(&state);
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows appended to the trace so far.
+    pub fn num_rows(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    /// Returns the row at the specified index.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn get_row(&self, index: usize) -> R {
+        let row = self.columns.iter().map(|column| column[index]).collect::<Vec<_>>();
+        R::read_from(&row)
+    }
+
+    // TRACE CONSTRUCTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Consumes this builder and returns an execution trace consisting of all appended rows.
+    ///
+    /// # Panics
+    /// Panics if the number of appended rows is smaller than 8, greater than the biggest
+    /// multiplicative subgroup in the field `R::BaseField`, or is not a power of two.
+    pub fn build(self) -> TraceTable<R::BaseField> {
+        let mut trace = TraceTable::init(self.columns);
+        trace.set_meta(self.meta);
+        trace
+    }
+}
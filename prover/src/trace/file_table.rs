@@ -0,0 +1,392 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColMatrix, Trace};
+use air::{EvaluationFrame, TraceInfo, TraceLayout};
+use math::{FieldElement, StarkField};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+use utils::SliceReader;
+
+// FILE-BACKED TRACE TABLE
+// ================================================================================================
+/// An implementation of the [Trace] trait backed by a file on disk.
+///
+/// Columns of the trace are stored in the file in column-major order: the file starts with all
+/// values of the first column, followed by all values of the second column etc. Each value is
+/// stored in its canonical serialized form (i.e., `B::ELEMENT_BYTES` bytes per value).
+///
+/// This table is intended for cases when the execution trace is too large to be built in memory
+/// by the host application. The host can create the table via [FileTraceTable::create()] function
+/// and then write the trace into it chunk by chunk via [FileTraceTable::write_column_chunk()] or
+/// [FileTraceTable::write_fragment()] methods. Alternatively, the table can be instantiated from
+/// a file produced by some other process via [FileTraceTable::open()] function.
+///
+/// Reading values via [FileTraceTable::get()] and [FileTraceTable::read_column_chunk()] methods
+/// (as well as reading evaluation frames) loads only the requested data from disk. When the
+/// prover computes the low-degree extension of the trace (via [Trace::with_main_segment()]), the
+/// columns are read from disk into a temporary buffer which is released as soon as the extension
+/// is computed; thus, the trace is not kept in memory for the rest of proof generation. Note that
+/// trace interpolation requires complete columns, and thus, the full trace is held in memory
+/// while the extension is being computed. To keep the extension itself out of memory, the trace
+/// LDE can be instantiated via [DefaultTraceLde::new_on_disk()].
+///
+/// If the main segment is requested explicitly via [Trace::main_segment()], the entire trace is
+/// loaded into memory and cached for the lifetime of the table.
+///
+/// This type is available only when the crate is compiled with `std` feature.
+///
+/// [DefaultTraceLde::new_on_disk()]: crate::DefaultTraceLde::new_on_disk
+#[derive(Debug)]
+pub struct FileTraceTable<B: StarkField> {
+    layout: TraceLayout,
+    length: usize,
+    meta: Vec<u8>,
+    path: PathBuf,
+    file: Mutex<File>,
+    trace: OnceLock<ColMatrix<B>>,
+}
+
+impl<B: StarkField> FileTraceTable<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates a new file at the specified path large enough to hold an execution trace of the
+    /// specified width and length, and returns a trace table backed by this file.
+    ///
+    /// If the file already exists, it will be truncated. Contents of the trace are initialized to
+    /// all zeros.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be created.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 65535.
+    /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
+    ///   field `B`, or is not a power of two.
+    pub fn create<P: AsRef<Path>>(path: P, width: usize, length: usize) -> io::Result<Self> {
+        validate_dimensions::<B>(width, length);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path.as_ref())?;
+        file.set_len((width * length * B::ELEMENT_BYTES) as u64)?;
+        Ok(Self::from_file(path.as_ref().to_path_buf(), file, width, length))
+    }
+
+    /// Returns a trace table backed by an existing file at the specified path.
+    ///
+    /// The file is expected to contain an execution trace of the specified width and length laid
+    /// out as described in the documentation of [FileTraceTable].
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be opened, or if the size of the file is not
+    /// consistent with the specified trace dimensions.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 65535.
+    /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
+    ///   field `B`, or is not a power of two.
+    pub fn open<P: AsRef<Path>>(path: P, width: usize, length: usize) -> io::Result<Self> {
+        validate_dimensions::<B>(width, length);
+        let file = OpenOptions::new().read(true).write(true).open(path.as_ref())?;
+        let expected_len = (width * length * B::ELEMENT_BYTES) as u64;
+        let actual_len = file.metadata()?.len();
+        if actual_len != expected_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected trace file to be {expected_len} bytes, but was {actual_len}"),
+            ));
+        }
+        Ok(Self::from_file(path.as_ref().to_path_buf(), file, width, length))
+    }
+
+    fn from_file(path: PathBuf, file: File, width: usize, length: usize) -> Self {
+        Self {
+            layout: TraceLayout::new(width, [0], [0]),
+            length,
+            meta: Vec::new(),
+            path,
+            file: Mutex::new(file),
+            trace: OnceLock::new(),
+        }
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Updates metadata for this execution trace to the specified vector of bytes.
+    ///
+    /// # Panics
+    /// Panics if the length of `meta` is greater than 65535;
+    pub fn set_meta(&mut self, meta: Vec<u8>) {
+        assert!(
+            meta.len() <= TraceInfo::MAX_META_LENGTH,
+            "number of metadata bytes cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            meta.len()
+        );
+        self.meta = meta
+    }
+
+    /// Writes the provided values into the specified column starting at the specified step.
+    ///
+    /// # Errors
+    /// Returns an error if writing to the underlying file failed.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `column` is out of bounds for this trace.
+    /// * The chunk extends beyond the end of the trace.
+    /// * The trace has already been loaded into memory.
+    pub fn write_column_chunk(
+        &mut self,
+        column: usize,
+        offset: usize,
+        values: &[B],
+    ) -> io::Result<()> {
+        assert!(self.trace.get().is_none(), "cannot update a trace which has been loaded");
+        self.validate_chunk(column, offset, values.len());
+
+        let mut bytes = Vec::with_capacity(values.len() * B::ELEMENT_BYTES);
+        B::write_batch_into(values, &mut bytes);
+
+        let mut file = self.file.lock().expect("trace file lock poisoned");
+        file.seek(SeekFrom::Start(self.position(column, offset)))?;
+        file.write_all(&bytes)
+    }
+
+    /// Writes a fragment of the execution trace starting at the specified step.
+    ///
+    /// The fragment is defined by a list of columns; the number of columns must be equal to the
+    /// width of the trace, and all columns must have the same length.
+    ///
+    /// # Errors
+    /// Returns an error if writing to the underlying file failed.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of columns in the fragment is not equal to the width of this trace.
+    /// * Columns in the fragment have different lengths.
+    /// * The fragment extends beyond the end of the trace.
+    /// * The trace has already been loaded into memory.
+    pub fn write_fragment(&mut self, offset: usize, columns: &[Vec<B>]) -> io::Result<()> {
+        assert_eq!(
+            columns.len(),
+            self.width(),
+            "fragment must contain {} columns, but contained {}",
+            self.width(),
+            columns.len()
+        );
+        for (i, column) in columns.iter().enumerate() {
+            assert_eq!(
+                column.len(),
+                columns[0].len(),
+                "all fragment columns must have the same length"
+            );
+            self.write_column_chunk(i, offset, column)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes all pending writes to the underlying file.
+    ///
+    /// # Errors
+    /// Returns an error if flushing the underlying file failed.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.lock().expect("trace file lock poisoned").sync_data()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in this execution trace.
+    pub fn width(&self) -> usize {
+        self.main_trace_width()
+    }
+
+    /// Returns the path to the file backing this trace.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns true if the entire trace has been loaded into memory.
+    pub fn is_loaded(&self) -> bool {
+        self.trace.get().is_some()
+    }
+
+    /// Reads `len` values of the specified column starting at the specified step.
+    ///
+    /// If the trace has been loaded into memory, the values are copied from memory; otherwise,
+    /// only the requested chunk is read from disk.
+    ///
+    /// # Errors
+    /// Returns an error if reading from the underlying file failed or if the file contains
+    /// invalid field elements.
+    ///
+    /// # Panics
+    /// Panics if `column` is out of bounds for this trace or if the chunk extends beyond the
+    /// end of the trace.
+    pub fn read_column_chunk(
+        &self,
+        column: usize,
+        offset: usize,
+        len: usize,
+    ) -> io::Result<Vec<B>> {
+        self.validate_chunk(column, offset, len);
+        if let Some(trace) = self.trace.get() {
+            return Ok(trace.get_column(column)[offset..offset + len].to_vec());
+        }
+
+        let mut bytes = vec![0u8; len * B::ELEMENT_BYTES];
+        {
+            let mut file = self.file.lock().expect("trace file lock poisoned");
+            file.seek(SeekFrom::Start(self.position(column, offset)))?;
+            file.read_exact(&mut bytes)?;
+        }
+        B::read_batch_from(&mut SliceReader::new(&bytes), len)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+    }
+
+    /// Returns value of the cell in the specified column at the specified row of this trace.
+    ///
+    /// # Panics
+    /// Panics if either `column` or `step` are out of bounds for this execution trace, or if
+    /// the value could not be read from the underlying file.
+    pub fn get(&self, column: usize, step: usize) -> B {
+        self.read_column_chunk(column, step, 1).expect("failed to read execution trace")[0]
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the byte offset in the underlying file of the value in the specified column and
+    /// at the specified step.
+    fn position(&self, column: usize, step: usize) -> u64 {
+        ((column * self.length + step) * B::ELEMENT_BYTES) as u64
+    }
+
+    fn validate_chunk(&self, column: usize, offset: usize, len: usize) {
+        assert!(
+            column < self.width(),
+            "column index must be smaller than {}, but was {}",
+            self.width(),
+            column
+        );
+        assert!(
+            offset + len <= self.length,
+            "chunk cannot extend beyond step {}, but extended to step {}",
+            self.length,
+            offset + len
+        );
+    }
+
+    /// Reads the entire trace from disk into a new matrix.
+    fn read_columns(&self) -> ColMatrix<B> {
+        let columns = (0..self.width())
+            .map(|i| self.read_column_chunk(i, 0, self.length))
+            .collect::<io::Result<Vec<_>>>()
+            .expect("failed to read execution trace");
+        ColMatrix::new(columns)
+    }
+
+    /// Copies the row at the specified step into the provided slice; the row is read from disk
+    /// unless the trace has been loaded into memory.
+    fn read_row_into(&self, step: usize, row: &mut [B]) {
+        if let Some(trace) = self.trace.get() {
+            trace.read_row_into(step, row);
+            return;
+        }
+        for (column, value) in row.iter_mut().enumerate() {
+            *value = self.get(column, step);
+        }
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl<B: StarkField> Trace for FileTraceTable<B> {
+    type BaseField = B;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.read_row_into(row_idx, frame.current_mut());
+        self.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<B> {
+        self.trace.get_or_init(|| self.read_columns())
+    }
+
+    /// Reads the main segment from disk into a temporary buffer which is released once `f`
+    /// returns; if the trace has already been loaded into memory, the loaded trace is used.
+    fn with_main_segment<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&ColMatrix<B>) -> R,
+    {
+        match self.trace.get() {
+            Some(trace) => f(trace),
+            None => f(&self.read_columns()),
+        }
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        _aux_segments: &[ColMatrix<E>],
+        _rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        None
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn validate_dimensions<B: StarkField>(width: usize, length: usize) {
+    assert!(width > 0, "execution trace must consist of at least one column");
+    assert!(
+        width <= TraceInfo::MAX_TRACE_WIDTH,
+        "execution trace width cannot be greater than {}, but was {}",
+        TraceInfo::MAX_TRACE_WIDTH,
+        width
+    );
+    assert!(
+        length >= TraceInfo::MIN_TRACE_LENGTH,
+        "execution trace must be at least {} steps long, but was {}",
+        TraceInfo::MIN_TRACE_LENGTH,
+        length
+    );
+    assert!(length.is_power_of_two(), "execution trace length must be a power of 2");
+    assert!(
+        length.ilog2() <= B::TWO_ADICITY,
+        "execution trace length cannot exceed 2^{} steps, but was 2^{}",
+        B::TWO_ADICITY,
+        length.ilog2()
+    );
+}
@@ -0,0 +1,255 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColMatrix, Trace};
+use air::{EvaluationFrame, TraceInfo, TraceLayout};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// INCREMENTAL TRACE
+// ================================================================================================
+/// An implementation of the [Trace] trait which is populated incrementally from a sequence of
+/// rows.
+///
+/// Unlike [TraceTable](super::TraceTable), an incremental trace does not require the caller to
+/// know the final length of the execution trace up front or to keep the computation state around
+/// until the trace is filled. Instead, rows are appended one by one as the computation executes,
+/// either via the [IncrementalTrace::push_row()] method, or by passing an iterator over rows to
+/// the [IncrementalTrace::from_rows()] function. Rows are written directly into column-major
+/// storage, and thus, no intermediate row-major copy of the trace is materialized.
+///
+/// Once all rows have been appended, the trace must be finalized via the
+/// [IncrementalTrace::finalize()] method. Finalization checks that the trace length satisfies all
+/// the requirements imposed on execution traces (e.g., the length must be a power of two). After
+/// that, the trace can be passed to the [Prover::prove()](crate::Prover::prove) method.
+///
+/// Note that all appended rows are kept in memory: trace interpolation requires complete columns,
+/// and thus, the low degree extension of the trace and the commitment to it can be computed only
+/// after the last row has been appended. Thus, this trace does not reduce the amount of memory
+/// required to generate a proof as compared to [TraceTable](super::TraceTable).
+#[derive(Debug, Clone)]
+pub struct IncrementalTrace<B: StarkField> {
+    layout: TraceLayout,
+    columns: Vec<Vec<B>>,
+    meta: Vec<u8>,
+    trace: Option<ColMatrix<B>>,
+}
+
+impl<B: StarkField> IncrementalTrace<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates a new empty incremental trace of the specified width.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero or greater than 65535.
+    pub fn new(width: usize) -> Self {
+        Self::with_meta(width, Vec::new())
+    }
+
+    /// Creates a new empty incremental trace of the specified width and with the specified
+    /// metadata.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 65535.
+    /// * Length of `meta` is greater than 65535.
+    pub fn with_meta(width: usize, meta: Vec<u8>) -> Self {
+        assert!(width > 0, "execution trace must consist of at least one column");
+        assert!(
+            width <= TraceInfo::MAX_TRACE_WIDTH,
+            "execution trace width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            width
+        );
+        assert!(
+            meta.len() <= TraceInfo::MAX_META_LENGTH,
+            "number of metadata bytes cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            meta.len()
+        );
+
+        Self {
+            layout: TraceLayout::new(width, [0], [0]),
+            columns: (0..width).map(|_| Vec::new()).collect(),
+            meta,
+            trace: None,
+        }
+    }
+
+    /// Creates a new finalized execution trace from the provided iterator over rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 65535.
+    /// * Any of the rows has a number of elements different from `width`.
+    /// * The number of rows is smaller than 8, greater than the biggest multiplicative subgroup
+    ///   in the field `B`, or is not a power of two.
+    pub fn from_rows<I, R>(width: usize, rows: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: AsRef<[B]>,
+    {
+        let mut trace = Self::new(width);
+        let rows = rows.into_iter();
+        trace.reserve(rows.size_hint().0);
+        for row in rows {
+            trace.push_row(row.as_ref());
+        }
+        trace.finalize();
+        trace
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reserves capacity for at least `additional` more rows in this trace.
+    pub fn reserve(&mut self, additional: usize) {
+        self.columns.iter_mut().for_each(|column| column.reserve(additional));
+    }
+
+    /// Appends the provided row to the end of this execution trace.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The trace has already been finalized.
+    /// * The number of elements in `row` is different from the width of this trace.
+    pub fn push_row(&mut self, row: &[B]) {
+        assert!(self.trace.is_none(), "cannot append rows to a finalized execution trace");
+        assert_eq!(
+            row.len(),
+            self.columns.len(),
+            "row must contain {} elements, but contained {}",
+            self.columns.len(),
+            row.len()
+        );
+        for (column, &value) in self.columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+
+    /// Appends rows produced by the `next` closure until the closure returns false.
+    ///
+    /// The closure receives the index of the row being built and a mutable reference to the
+    /// state of the computation; after each invocation which returns true, the contents of the
+    /// state are appended to the trace as a new row. On the first invocation, the state is
+    /// initialized to the last row of the trace (or to all zeros if the trace is empty).
+    pub fn extend_with<F>(&mut self, mut next: F)
+    where
+        F: FnMut(usize, &mut [B]) -> bool,
+    {
+        let mut state = match self.num_rows() {
+            0 => vec![B::ZERO; self.columns.len()],
+            n => self.columns.iter().map(|column| column[n - 1]).collect(),
+        };
+        while next(self.num_rows(), &mut state) {
+            self.push_row(&state);
+        }
+    }
+
+    /// Marks this trace as complete; no new rows can be appended after the trace is finalized.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The trace has already been finalized.
+    /// * The number of rows in the trace is smaller than 8, greater than the biggest
+    ///   multiplicative subgroup in the field `B`, or is not a power of two.
+    pub fn finalize(&mut self) {
+        assert!(self.trace.is_none(), "execution trace has already been finalized");
+        let trace_length = self.num_rows();
+        assert!(
+            trace_length >= TraceInfo::MIN_TRACE_LENGTH,
+            "execution trace must be at least {} steps long, but was {}",
+            TraceInfo::MIN_TRACE_LENGTH,
+            trace_length
+        );
+        assert!(trace_length.is_power_of_two(), "execution trace length must be a power of 2");
+        assert!(
+            trace_length.ilog2() <= B::TWO_ADICITY,
+            "execution trace length cannot exceed 2^{} steps, but was 2^{}",
+            B::TWO_ADICITY,
+            trace_length.ilog2()
+        );
+
+        let columns = core::mem::take(&mut self.columns);
+        self.trace = Some(ColMatrix::new(columns));
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in this execution trace.
+    pub fn width(&self) -> usize {
+        self.main_trace_width()
+    }
+
+    /// Returns the number of rows appended to this trace so far.
+    pub fn num_rows(&self) -> usize {
+        match &self.trace {
+            Some(trace) => trace.num_rows(),
+            None => self.columns[0].len(),
+        }
+    }
+
+    /// Returns true if this trace has been finalized.
+    pub fn is_finalized(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    /// Returns value of the cell in the specified column at the specified row of this trace.
+    pub fn get(&self, column: usize, step: usize) -> B {
+        match &self.trace {
+            Some(trace) => trace.get(column, step),
+            None => self.columns[column][step],
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn trace(&self) -> &ColMatrix<B> {
+        self.trace.as_ref().expect("execution trace has not been finalized")
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl<B: StarkField> Trace for IncrementalTrace<B> {
+    type BaseField = B;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace().num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.trace().read_row_into(row_idx, frame.current_mut());
+        self.trace().read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<B> {
+        self.trace()
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        _aux_segments: &[ColMatrix<E>],
+        _rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        None
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ColMatrix;
+use core::{fmt, ops::Range};
+use math::StarkField;
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+};
+
+// CELL DIFFERENCE
+// ================================================================================================
+/// A cell of the main trace segment in which two execution traces contain different values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellDiff<B: StarkField> {
+    /// Step (row index) of the cell.
+    pub step: usize,
+    /// Column index of the cell.
+    pub column: usize,
+    /// Value contained in the first trace.
+    pub left: B,
+    /// Value contained in the second trace.
+    pub right: B,
+}
+
+impl<B: StarkField> fmt::Display for CellDiff<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {}, column {}: {} != {}", self.step, self.column, self.left, self.right)
+    }
+}
+
+// COLUMN DIFFERENCE
+// ================================================================================================
+/// Summary of the differences between two execution traces in a single column of the main trace
+/// segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDiff<B: StarkField> {
+    /// Column index.
+    pub column: usize,
+    /// Number of steps at which the traces contain different values in this column.
+    pub num_differences: usize,
+    /// The first (lowest step) cell of this column in which the traces differ.
+    pub first: CellDiff<B>,
+    /// The last (highest step) cell of this column in which the traces differ.
+    pub last: CellDiff<B>,
+}
+
+impl<B: StarkField> fmt::Display for ColumnDiff<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "column {} differs at {} step(s) between steps {} and {}; first: {} != {}, last: {} != {}",
+            self.column,
+            self.num_differences,
+            self.first.step,
+            self.last.step,
+            self.first.left,
+            self.first.right,
+            self.last.left,
+            self.last.right
+        )
+    }
+}
+
+// TRACE DIFFERENCE
+// ================================================================================================
+/// A summary of the differences between main trace segments of two execution traces.
+///
+/// A diff is built via [Trace::diff()](crate::Trace::diff). If the traces have different shapes,
+/// only the cells contained in both traces (i.e., the common columns over the common steps) are
+/// compared, and the diff records the shapes of both traces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceDiff<B: StarkField> {
+    left_shape: (usize, usize),
+    right_shape: (usize, usize),
+    columns: Vec<ColumnDiff<B>>,
+}
+
+impl<B: StarkField> TraceDiff<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a diff between the specified main trace segments.
+    pub(crate) fn new(left: &ColMatrix<B>, right: &ColMatrix<B>) -> Self {
+        let num_rows = left.num_rows().min(right.num_rows());
+        let columns = left
+            .columns()
+            .zip(right.columns())
+            .enumerate()
+            .filter_map(|(column, (left, right))| {
+                let mut steps = (0..num_rows).filter(|&step| left[step] != right[step]);
+                let first = steps.next()?;
+                let (num_differences, last) =
+                    steps.fold((1, first), |(count, _), step| (count + 1, step));
+                let cell = |step| CellDiff {
+                    step,
+                    column,
+                    left: left[step],
+                    right: right[step],
+                };
+                Some(ColumnDiff {
+                    column,
+                    num_differences,
+                    first: cell(first),
+                    last: cell(last),
+                })
+            })
+            .collect();
+
+        Self {
+            left_shape: (left.num_cols(), left.num_rows()),
+            right_shape: (right.num_cols(), right.num_rows()),
+            columns,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if both traces have the same shape and contain the same values.
+    pub fn is_identical(&self) -> bool {
+        self.shapes_match() && self.columns.is_empty()
+    }
+
+    /// Returns true if both traces have the same number of columns and the same number of rows.
+    pub fn shapes_match(&self) -> bool {
+        self.left_shape == self.right_shape
+    }
+
+    /// Returns the number of columns and the number of rows of the first trace.
+    pub fn left_shape(&self) -> (usize, usize) {
+        self.left_shape
+    }
+
+    /// Returns the number of columns and the number of rows of the second trace.
+    pub fn right_shape(&self) -> (usize, usize) {
+        self.right_shape
+    }
+
+    /// Returns the total number of compared cells in which the traces differ.
+    pub fn num_differences(&self) -> usize {
+        self.columns.iter().map(|column| column.num_differences).sum()
+    }
+
+    /// Returns the first cell in which the traces differ, or `None` if all compared cells are
+    /// identical.
+    ///
+    /// Cells are ordered by step first, and then by column; thus, this is the earliest point of
+    /// the computation at which the two traces disagree.
+    pub fn first_difference(&self) -> Option<CellDiff<B>> {
+        self.columns
+            .iter()
+            .map(|column| column.first)
+            .min_by_key(|cell| (cell.step, cell.column))
+    }
+
+    /// Returns summaries of the differences in all columns in which the traces differ, ordered
+    /// by column index.
+    pub fn columns(&self) -> &[ColumnDiff<B>] {
+        &self.columns
+    }
+}
+
+impl<B: StarkField> fmt::Display for TraceDiff<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_identical() {
+            return write!(f, "traces are identical");
+        }
+        if !self.shapes_match() {
+            let (left_width, left_length) = self.left_shape;
+            let (right_width, right_length) = self.right_shape;
+            writeln!(
+                f,
+                "traces have different shapes ({left_width} columns by {left_length} rows vs. \
+                {right_width} columns by {right_length} rows); only common cells are compared"
+            )?;
+        }
+        match self.first_difference() {
+            None => write!(f, "all common cells are identical"),
+            Some(first) => {
+                write!(
+                    f,
+                    "traces differ in {} cell(s) of {} column(s); first difference at {first}",
+                    self.num_differences(),
+                    self.columns.len()
+                )?;
+                for column in self.columns.iter() {
+                    write!(f, "\n- {column}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// TABLE FORMATTING
+// ================================================================================================
+
+/// Returns the specified columns of the main trace segment over the specified steps formatted as
+/// a table with one row per step.
+///
+/// # Panics
+/// Panics if the ranges are out of bounds of the trace segment.
+pub(crate) fn format_columns<B: StarkField>(
+    segment: &ColMatrix<B>,
+    columns: Range<usize>,
+    steps: Range<usize>,
+) -> String {
+    assert!(columns.end <= segment.num_cols(), "column range {columns:?} is out of bounds");
+    assert!(steps.end <= segment.num_rows(), "step range {steps:?} is out of bounds");
+
+    // all cells of a table column are right-aligned to the width of the widest cell
+    let header = columns.clone().map(|column| format!("col {column}")).collect::<Vec<_>>();
+    let cells = steps
+        .clone()
+        .map(|step| columns.clone().map(|column| segment.get(column, step).to_string()).collect())
+        .collect::<Vec<Vec<_>>>();
+    let step_width = steps.end.saturating_sub(1).to_string().len().max("step".len());
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(i, title)| cells.iter().map(|row| row[i].len()).fold(title.len(), usize::max))
+        .collect::<Vec<_>>();
+
+    let mut result = format!("{:>step_width$}", "step");
+    for (title, &width) in header.iter().zip(widths.iter()) {
+        result.push_str(&format!(" | {title:>width$}"));
+    }
+    result.push('\n');
+    result.push_str(&"-".repeat(step_width));
+    for &width in widths.iter() {
+        result.push_str(&format!("-+-{}", "-".repeat(width)));
+    }
+    for (step, row) in steps.zip(cells.iter()) {
+        result.push_str(&format!("\n{step:>step_width$}"));
+        for (value, &width) in row.iter().zip(widths.iter()) {
+            result.push_str(&format!(" | {value:>width$}"));
+        }
+    }
+    result
+}
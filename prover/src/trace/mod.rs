@@ -5,7 +5,9 @@
 
 use super::{matrix::MultiColumnIter, ColMatrix};
 use air::{Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout};
+use core::ops::Range;
 use math::{polynom, FieldElement, StarkField};
+use utils::{collections::Vec, string::String};
 
 mod trace_lde;
 pub use trace_lde::{DefaultTraceLde, TraceLde};
@@ -14,7 +16,30 @@ mod poly_table;
 pub use poly_table::TracePolyTable;
 
 mod trace_table;
-pub use trace_table::{TraceTable, TraceTableFragment};
+pub use trace_table::{TraceStorage, TraceTable, TraceTableFragment};
+
+mod packed_table;
+pub use packed_table::{ColumnDomain, PackedTraceTable};
+
+mod incremental_trace;
+pub use incremental_trace::IncrementalTrace;
+
+mod builder;
+pub use builder::{TraceBuilder, TraceRow};
+
+mod aux_builder;
+pub use aux_builder::{AuxTraceBuilder, MainTraceRow, MainTraceRows, TraceWithAux};
+
+mod validation;
+pub use validation::{ConstraintViolation, ValidationReport};
+
+mod inspection;
+pub use inspection::{CellDiff, ColumnDiff, TraceDiff};
+
+#[cfg(feature = "std")]
+mod file_table;
+#[cfg(feature = "std")]
+pub use file_table::FileTraceTable;
 
 #[cfg(test)]
 mod tests;
@@ -31,9 +56,10 @@ mod tests;
 /// instance of a computation must be supplied to [Prover::prove()](super::Prover::prove) method
 /// to generate a STARK proof.
 ///
-/// This crate exposes one concrete implementation of the [Trace] trait: [TraceTable]. This
-/// implementation supports concurrent trace generation and should be sufficient in most
-/// situations. However, if functionality provided by [TraceTable] is not sufficient, uses can
+/// This crate exposes two concrete implementations of the [Trace] trait: [TraceTable] and
+/// [IncrementalTrace]. [TraceTable] supports concurrent trace generation and should be sufficient
+/// in most situations, while [IncrementalTrace] can be populated row by row as the computation
+/// executes. However, if functionality provided by [TraceTable] is not sufficient, uses can
 /// provide custom implementations of the [Trace] trait which better suit their needs.
 pub trait Trace: Sized {
     /// Base field for this execution trace.
@@ -90,10 +116,58 @@ pub trait Trace: Sized {
         self.layout().aux_trace_width()
     }
 
+    /// Invokes the provided closure with the main segment of this trace in which all values are
+    /// represented by field elements, and returns the result of the closure.
+    ///
+    /// The prover uses this method to get the main segment for computing its low-degree
+    /// extension. The default implementation passes [Trace::main_segment()] to the closure. Traces
+    /// which store columns in packed form (e.g., as small integers) can override this method to
+    /// convert the columns into field elements only for the duration of the closure; thus, the
+    /// converted columns do not need to be kept in memory for the rest of proof generation.
+    fn with_main_segment<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&ColMatrix<Self::BaseField>) -> R,
+    {
+        f(self.main_segment())
+    }
+
+    // INSPECTION
+    // --------------------------------------------------------------------------------------------
+    /// Compares the main segment of this trace with the main segment of the `other` trace, and
+    /// returns a summary of the differences.
+    ///
+    /// This is intended for debugging witness generation: when two implementations of a
+    /// computation should produce identical traces, [TraceDiff::first_difference()] points to
+    /// the earliest step at which they diverge, and [TraceDiff::columns()] summarizes the
+    /// differences in every column. Traces of different shapes are compared over their common
+    /// cells only.
+    fn diff<T>(&self, other: &T) -> TraceDiff<Self::BaseField>
+    where
+        T: Trace<BaseField = Self::BaseField>,
+    {
+        self.with_main_segment(|left| other.with_main_segment(|right| TraceDiff::new(left, right)))
+    }
+
+    /// Returns the specified range of columns of the main segment of this trace over the
+    /// specified range of steps formatted as a table.
+    ///
+    /// The table has a header row followed by one row per step; the first column of the table
+    /// contains the step, and the remaining columns contain the right-aligned values of the
+    /// selected trace columns.
+    ///
+    /// # Panics
+    /// Panics if `columns` or `steps` is out of bounds of the main trace segment.
+    fn format_columns(&self, columns: Range<usize>, steps: Range<usize>) -> String {
+        self.with_main_segment(|segment| inspection::format_columns(segment, columns, steps))
+    }
+
     // VALIDATION
     // --------------------------------------------------------------------------------------------
     /// Checks if this trace is valid against the specified AIR, and panics if not.
     ///
+    /// The panic message describes all constraints which are not satisfied by this trace; to
+    /// inspect these constraints programmatically, use [Trace::check_constraints()] instead.
+    ///
     /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
     fn validate<A, E>(
         &self,
@@ -103,6 +177,33 @@ pub trait Trace: Sized {
     ) where
         A: Air<BaseField = Self::BaseField>,
         E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let report = self.check_constraints(air, aux_segments, aux_rand_elements);
+        assert!(report.is_valid(), "{report}");
+    }
+
+    /// Checks this trace against all assertions and transition constraints of the specified AIR,
+    /// and returns a report describing every constraint which is not satisfied.
+    ///
+    /// For each violated assertion, the report contains the column, the step, and the expected
+    /// and actual values; for each violated transition constraint, the report contains the index
+    /// of the constraint, the step, the value to which the constraint evaluated, and the values
+    /// of all columns in the current and the next rows.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
+    ///
+    /// # Panics
+    /// Panics if the width of this trace is inconsistent with the trace width specified by the
+    /// AIR.
+    fn check_constraints<A, E>(
+        &self,
+        air: &A,
+        aux_segments: &[ColMatrix<E>],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> ValidationReport<E>
+    where
+        A: Air<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField>,
     {
         // make sure the width align; if they don't something went terribly wrong
         assert_eq!(
@@ -113,18 +214,22 @@ pub trait Trace: Sized {
             air.trace_layout().main_trace_width(),
         );
 
+        let mut violations = Vec::new();
+
         // --- 1. make sure the assertions are valid ----------------------------------------------
 
         // first, check assertions against the main segment of the execution trace
         for assertion in air.get_assertions() {
             assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == self.main_segment().get(assertion.column(), step),
-                    "trace does not satisfy assertion main_trace({}, {}) == {}",
-                    assertion.column(),
-                    step,
-                    value
-                );
+                let actual = self.main_segment().get(assertion.column(), step);
+                if actual != value {
+                    violations.push(ConstraintViolation::MainAssertion {
+                        column: assertion.column(),
+                        step,
+                        expected: value,
+                        actual,
+                    });
+                }
             });
         }
 
@@ -145,13 +250,15 @@ pub trait Trace: Sized {
 
             // get the matrix and verify the assertion against it
             assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == aux_segments[segment_idx].get(column_idx, step),
-                    "trace does not satisfy assertion aux_trace({}, {}) == {}",
-                    assertion.column(),
-                    step,
-                    value
-                );
+                let actual = aux_segments[segment_idx].get(column_idx, step);
+                if actual != value {
+                    violations.push(ConstraintViolation::AuxAssertion {
+                        column: assertion.column(),
+                        step,
+                        expected: value,
+                        actual,
+                    });
+                }
             });
         }
 
@@ -174,49 +281,106 @@ pub trait Trace: Sized {
             vec![Self::BaseField::ZERO; air.context().num_main_transition_constraints()];
         let mut aux_evaluations = vec![E::ZERO; air.context().num_aux_transition_constraints()];
 
+        // if some columns of the main segment hold extension field elements, main transition
+        // constraints are evaluated over frames with coordinates of these elements combined
+        let context = air.context();
+        let mut ext_main_frame = if context.num_main_extension_columns() > 0 {
+            Some(EvaluationFrame::<E>::new(context.main_frame_width()))
+        } else {
+            None
+        };
+        let mut ext_periodic_values = vec![E::ZERO; periodic_values_polys.len()];
+        let mut ext_main_evaluations = vec![E::ZERO; context.num_main_transition_constraints()];
+
         // we check transition constraints on all steps except the last k steps, where k is the
         // number of steps exempt from transition constraints (guaranteed to be at least 1)
         for step in 0..self.length() - air.context().num_transition_exemptions() {
             // build periodic values
             for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
                 let num_cycles = air.trace_length() / p.len();
-                let x = x.exp((num_cycles as u32).into());
+                let x = x.exp((num_cycles as u64).into());
                 *v = polynom::eval(p, x);
             }
 
             // evaluate transition constraints for the main trace segment and make sure they all
             // evaluate to zeros
             self.read_main_frame(step, &mut main_frame);
-            air.evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
-            for (i, &evaluation) in main_evaluations.iter().enumerate() {
-                assert!(
-                    evaluation == Self::BaseField::ZERO,
-                    "main transition constraint {i} did not evaluate to ZERO at step {step}"
+            if let Some(ref mut ext_main_frame) = ext_main_frame {
+                ext_main_frame.read_extension_columns(&main_frame, context.num_main_base_columns());
+                for (ext_value, &value) in ext_periodic_values.iter_mut().zip(&periodic_values) {
+                    *ext_value = E::from(value);
+                }
+                air.evaluate_transition(
+                    ext_main_frame,
+                    &ext_periodic_values,
+                    &mut ext_main_evaluations,
                 );
+                for (i, &evaluation) in ext_main_evaluations.iter().enumerate() {
+                    if evaluation != E::ZERO {
+                        violations.push(ConstraintViolation::MainExtensionTransition {
+                            constraint: i,
+                            step,
+                            evaluation,
+                            current: ext_main_frame.current().to_vec(),
+                            next: ext_main_frame.next().to_vec(),
+                        });
+                    }
+                }
+            } else {
+                air.evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
+                for (i, &evaluation) in main_evaluations.iter().enumerate() {
+                    if evaluation != Self::BaseField::ZERO {
+                        violations.push(ConstraintViolation::MainTransition {
+                            constraint: i,
+                            step,
+                            evaluation,
+                            current: main_frame.current().to_vec(),
+                            next: main_frame.next().to_vec(),
+                        });
+                    }
+                }
             }
 
             // evaluate transition constraints for auxiliary trace segments (if any) and make
             // sure they all evaluate to zeros
             if let Some(ref mut aux_frame) = aux_frame {
                 read_aux_frame(aux_segments, step, aux_frame);
-                air.evaluate_aux_transition(
-                    &main_frame,
-                    aux_frame,
-                    &periodic_values,
-                    aux_rand_elements,
-                    &mut aux_evaluations,
-                );
+                match ext_main_frame {
+                    Some(ref ext_main_frame) => air.evaluate_aux_transition(
+                        ext_main_frame,
+                        aux_frame,
+                        &ext_periodic_values,
+                        aux_rand_elements,
+                        &mut aux_evaluations,
+                    ),
+                    None => air.evaluate_aux_transition(
+                        &main_frame,
+                        aux_frame,
+                        &periodic_values,
+                        aux_rand_elements,
+                        &mut aux_evaluations,
+                    ),
+                }
                 for (i, &evaluation) in aux_evaluations.iter().enumerate() {
-                    assert!(
-                        evaluation == E::ZERO,
-                        "auxiliary transition constraint {i} did not evaluate to ZERO at step {step}"
-                    );
+                    if evaluation != E::ZERO {
+                        violations.push(ConstraintViolation::AuxTransition {
+                            constraint: i,
+                            step,
+                            evaluation,
+                            main_current: main_frame.current().to_vec(),
+                            main_next: main_frame.next().to_vec(),
+                            aux_current: aux_frame.current().to_vec(),
+                            aux_next: aux_frame.next().to_vec(),
+                        });
+                    }
                 }
             }
 
             // update x coordinate of the domain
             x *= g;
         }
+
+        ValidationReport::new(violations)
     }
 }
 
@@ -0,0 +1,425 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ColMatrix, Trace};
+use air::{EvaluationFrame, TraceInfo, TraceLayout};
+use core::cell::OnceCell;
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// COLUMN DOMAIN
+// ================================================================================================
+
+/// Defines the set of values which a column of a [PackedTraceTable] can hold.
+///
+/// Columns with narrow domains are stored in packed form, and are expanded into field elements
+/// only when the trace is handed over to the prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnDomain {
+    /// Column values are either 0 or 1; values are stored using a single bit per value.
+    Bit,
+    /// Column values are in the range [0, 2^8); values are stored using one byte per value.
+    Byte,
+    /// Column values are in the range [0, 2^16); values are stored using two bytes per value.
+    U16,
+    /// Column values are in the range [0, 2^32); values are stored using four bytes per value.
+    U32,
+    /// Column values can be arbitrary field elements.
+    Field,
+}
+
+impl ColumnDomain {
+    /// Returns the number of bits needed to represent the values in this domain, or None if the
+    /// values can be arbitrary field elements.
+    pub fn num_bits(&self) -> Option<u32> {
+        match self {
+            Self::Bit => Some(1),
+            Self::Byte => Some(8),
+            Self::U16 => Some(16),
+            Self::U32 => Some(32),
+            Self::Field => None,
+        }
+    }
+}
+
+// PACKED TRACE TABLE
+// ================================================================================================
+/// An implementation of the [Trace] trait in which columns can be stored in packed form.
+///
+/// Each column of the table is assigned a [ColumnDomain] when the table is created. Columns with
+/// narrow domains (e.g., [ColumnDomain::Bit] or [ColumnDomain::Byte]) are stored using only as
+/// many bits per value as needed, while [ColumnDomain::Field] columns are stored as regular field
+/// elements. For traces dominated by small values (e.g., traces of VMs with many flag and limb
+/// columns) this reduces the memory needed to hold the trace several-fold.
+///
+/// The table is filled similarly to [TraceTable](super::TraceTable): values can be written one by
+/// one via [PackedTraceTable::set()] and [PackedTraceTable::set_int()] methods, row by row via
+/// [PackedTraceTable::update_row()] method, or via the [PackedTraceTable::fill()] method. Writing
+/// a value which does not fit into the domain of its column results in a panic.
+///
+/// Columns are expanded into field elements only when the prover computes the low-degree
+/// extension of the trace (via [Trace::with_main_segment()]), and the expanded columns are
+/// released as soon as the extension is computed. If the main segment is requested explicitly via
+/// [Trace::main_segment()], the expanded columns are cached for the lifetime of the table.
+/// Evaluation frames can be read directly from the packed representation.
+#[derive(Debug, Clone)]
+pub struct PackedTraceTable<B: StarkField> {
+    layout: TraceLayout,
+    length: usize,
+    domains: Vec<ColumnDomain>,
+    columns: Vec<PackedColumn<B>>,
+    meta: Vec<u8>,
+    trace: OnceCell<ColMatrix<B>>,
+}
+
+impl<B: StarkField> PackedTraceTable<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates a new execution trace of the specified length with columns in the specified
+    /// domains; all values in the trace are initialized to zeros.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domains` is empty or contains more than 65535 entries.
+    /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
+    ///   field `B`, or is not a power of two.
+    pub fn new(domains: Vec<ColumnDomain>, length: usize) -> Self {
+        Self::with_meta(domains, length, Vec::new())
+    }
+
+    /// Creates a new execution trace of the specified length with columns in the specified
+    /// domains, and with the specified metadata; all values in the trace are initialized to
+    /// zeros.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domains` is empty or contains more than 65535 entries.
+    /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
+    ///   field `B`, or is not a power of two.
+    /// * Length of `meta` is greater than 65535.
+    pub fn with_meta(domains: Vec<ColumnDomain>, length: usize, meta: Vec<u8>) -> Self {
+        assert!(!domains.is_empty(), "execution trace must consist of at least one column");
+        assert!(
+            domains.len() <= TraceInfo::MAX_TRACE_WIDTH,
+            "execution trace width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            domains.len()
+        );
+        assert!(
+            length >= TraceInfo::MIN_TRACE_LENGTH,
+            "execution trace must be at least {} steps long, but was {}",
+            TraceInfo::MIN_TRACE_LENGTH,
+            length
+        );
+        assert!(length.is_power_of_two(), "execution trace length must be a power of 2");
+        assert!(
+            length.ilog2() <= B::TWO_ADICITY,
+            "execution trace length cannot exceed 2^{} steps, but was 2^{}",
+            B::TWO_ADICITY,
+            length.ilog2()
+        );
+        assert!(
+            meta.len() <= TraceInfo::MAX_META_LENGTH,
+            "number of metadata bytes cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            meta.len()
+        );
+
+        let columns = domains.iter().map(|&domain| PackedColumn::new(domain, length)).collect();
+        Self {
+            layout: TraceLayout::new(domains.len(), [0], [0]),
+            length,
+            domains,
+            columns,
+            meta,
+            trace: OnceCell::new(),
+        }
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Updates the value in the specified `column` and the specified `step` to the provided
+    /// field element.
+    ///
+    /// # Panics
+    /// Panics if either `column` or `step` are out of bounds for this execution trace, or if
+    /// `value` is outside of the domain of the specified column.
+    pub fn set(&mut self, column: usize, step: usize, value: B) {
+        self.trace.take();
+        let domain = self.domains[column];
+        match domain.num_bits() {
+            None => self.columns[column].set_element(step, value),
+            Some(num_bits) => {
+                let value = to_small_int(value, num_bits).unwrap_or_else(|| {
+                    panic!(
+                        "value {value} at step {step} does not fit into {domain:?} column {column}"
+                    )
+                });
+                self.columns[column].set_int(step, value)
+            }
+        }
+    }
+
+    /// Updates the value in the specified `column` and the specified `step` to the provided
+    /// integer.
+    ///
+    /// # Panics
+    /// Panics if either `column` or `step` are out of bounds for this execution trace, or if
+    /// `value` is outside of the domain of the specified column.
+    pub fn set_int(&mut self, column: usize, step: usize, value: u32) {
+        self.trace.take();
+        let domain = self.domains[column];
+        if let Some(num_bits) = domain.num_bits() {
+            assert!(
+                num_bits == 32 || value >> num_bits == 0,
+                "value {value} at step {step} does not fit into {domain:?} column {column}"
+            );
+            self.columns[column].set_int(step, value);
+        } else {
+            self.columns[column].set_element(step, B::from(value));
+        }
+    }
+
+    /// Updates metadata for this execution trace to the specified vector of bytes.
+    ///
+    /// # Panics
+    /// Panics if the length of `meta` is greater than 65535;
+    pub fn set_meta(&mut self, meta: Vec<u8>) {
+        assert!(
+            meta.len() <= TraceInfo::MAX_META_LENGTH,
+            "number of metadata bytes cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            meta.len()
+        );
+        self.meta = meta
+    }
+
+    /// Fill all rows in the execution trace.
+    ///
+    /// The semantics of this method are identical to the semantics of the
+    /// [TraceTable::fill()](super::TraceTable::fill) method.
+    ///
+    /// # Panics
+    /// Panics if any of the values in the state is outside of the domain of its column.
+    pub fn fill<I, U>(&mut self, init: I, mut update: U)
+    where
+        I: FnOnce(&mut [B]),
+        U: FnMut(usize, &mut [B]),
+    {
+        let mut state = vec![B::ZERO; self.width()];
+        init(&mut state);
+        self.update_row(0, &state);
+
+        for i in 0..self.length() - 1 {
+            update(i, &mut state);
+            self.update_row(i + 1, &state);
+        }
+    }
+
+    /// Updates a single row in the execution trace with provided data.
+    ///
+    /// # Panics
+    /// Panics if any of the values in `state` is outside of the domain of its column.
+    pub fn update_row(&mut self, step: usize, state: &[B]) {
+        for (column, &value) in state.iter().enumerate() {
+            self.set(column, step, value);
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in this execution trace.
+    pub fn width(&self) -> usize {
+        self.main_trace_width()
+    }
+
+    /// Returns the domain of the column at the specified index.
+    pub fn column_domain(&self, column: usize) -> ColumnDomain {
+        self.domains[column]
+    }
+
+    /// Returns value of the cell in the specified column at the specified row of this trace.
+    pub fn get(&self, column: usize, step: usize) -> B {
+        self.columns[column].get(step)
+    }
+
+    /// Reads a single row from this execution trace into the provided target.
+    pub fn read_row_into(&self, step: usize, target: &mut [B]) {
+        for (column, value) in self.columns.iter().zip(target.iter_mut()) {
+            *value = column.get(step);
+        }
+    }
+
+    /// Returns the number of bytes used to store the packed representation of this trace.
+    pub fn packed_size(&self) -> usize {
+        self.columns.iter().map(|column| column.size()).sum()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Expands all columns into field elements (if they haven't been expanded yet) and returns
+    /// the resulting matrix.
+    fn trace(&self) -> &ColMatrix<B> {
+        self.trace.get_or_init(|| self.expand_columns())
+    }
+
+    /// Expands all columns into field elements and returns the resulting matrix.
+    fn expand_columns(&self) -> ColMatrix<B> {
+        let columns = self.columns.iter().map(|column| column.expand(self.length)).collect();
+        ColMatrix::new(columns)
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl<B: StarkField> Trace for PackedTraceTable<B> {
+    type BaseField = B;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.read_row_into(row_idx, frame.current_mut());
+        self.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<B> {
+        self.trace()
+    }
+
+    /// Converts all columns into field elements and passes the resulting matrix to the provided
+    /// closure; the converted columns are dropped once the closure returns, unless they have
+    /// already been expanded via [Trace::main_segment()].
+    fn with_main_segment<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&ColMatrix<B>) -> R,
+    {
+        match self.trace.get() {
+            Some(trace) => f(trace),
+            None => f(&self.expand_columns()),
+        }
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        _aux_segments: &[ColMatrix<E>],
+        _rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        None
+    }
+}
+
+// PACKED COLUMN
+// ================================================================================================
+
+/// Storage for values of a single column of a [PackedTraceTable].
+#[derive(Debug, Clone)]
+enum PackedColumn<B: StarkField> {
+    Bit(Vec<u64>),
+    Byte(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    Field(Vec<B>),
+}
+
+impl<B: StarkField> PackedColumn<B> {
+    fn new(domain: ColumnDomain, length: usize) -> Self {
+        match domain {
+            ColumnDomain::Bit => Self::Bit(vec![0; length.div_ceil(64)]),
+            ColumnDomain::Byte => Self::Byte(vec![0; length]),
+            ColumnDomain::U16 => Self::U16(vec![0; length]),
+            ColumnDomain::U32 => Self::U32(vec![0; length]),
+            ColumnDomain::Field => Self::Field(vec![B::ZERO; length]),
+        }
+    }
+
+    fn get(&self, step: usize) -> B {
+        match self {
+            Self::Bit(words) => B::from(((words[step / 64] >> (step % 64)) & 1) as u32),
+            Self::Byte(values) => B::from(values[step]),
+            Self::U16(values) => B::from(values[step]),
+            Self::U32(values) => B::from(values[step]),
+            Self::Field(values) => values[step],
+        }
+    }
+
+    /// Sets the value at the specified step; the value is assumed to fit into the column domain.
+    fn set_int(&mut self, step: usize, value: u32) {
+        match self {
+            Self::Bit(words) => {
+                let mask = 1u64 << (step % 64);
+                if value == 0 {
+                    words[step / 64] &= !mask;
+                } else {
+                    words[step / 64] |= mask;
+                }
+            }
+            Self::Byte(values) => values[step] = value as u8,
+            Self::U16(values) => values[step] = value as u16,
+            Self::U32(values) => values[step] = value,
+            Self::Field(values) => values[step] = B::from(value),
+        }
+    }
+
+    fn set_element(&mut self, step: usize, value: B) {
+        match self {
+            Self::Field(values) => values[step] = value,
+            _ => unreachable!("field element can be written only into a field column"),
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::Bit(words) => words.len() * 8,
+            Self::Byte(values) => values.len(),
+            Self::U16(values) => values.len() * 2,
+            Self::U32(values) => values.len() * 4,
+            Self::Field(values) => values.len() * B::ELEMENT_BYTES,
+        }
+    }
+
+    fn expand(&self, length: usize) -> Vec<B> {
+        match self {
+            Self::Field(values) => values.clone(),
+            _ => (0..length).map(|step| self.get(step)).collect(),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the integer representation of the provided field element if it can be represented
+/// with the specified number of bits; otherwise returns None.
+fn to_small_int<B: StarkField>(value: B, num_bits: u32) -> Option<u32> {
+    let value = value.as_int();
+    if value >= B::PositiveInteger::from(1u64 << num_bits) {
+        return None;
+    }
+
+    let one = B::PositiveInteger::from(1u32);
+    let result = (0..num_bits)
+        .filter(|&i| (value >> i) & one == one)
+        .fold(0u32, |result, i| result | (1 << i));
+    Some(result)
+}
@@ -3,10 +3,22 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{tests::build_fib_trace, Trace};
+#[cfg(feature = "std")]
+use crate::FileTraceTable;
+use crate::{
+    matrix::ColMatrix,
+    tests::{build_fib_trace, MockAir},
+    trace_row, Assertion, AuxTraceBuilder, AuxTraceRandElements, CellDiff, ColumnDomain,
+    ConstraintViolation, EvaluationFrame, IncrementalTrace, MainTraceRows, PackedTraceTable, Trace,
+    TraceBuilder, TraceRow, TraceStorage, TraceTable, TraceWithAux,
+};
 use math::fields::f128::BaseElement;
+use math::FieldElement;
 use utils::collections::Vec;
 
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
 #[test]
 fn new_trace_table() {
     let trace_length = 8;
@@ -27,3 +39,464 @@ fn new_trace_table() {
         .collect();
     assert_eq!(expected, trace.get_column(1));
 }
+
+#[test]
+fn row_major_trace_table() {
+    let trace_length = 8;
+    let expected = build_fib_trace(trace_length * 2);
+
+    let fib_init = |state: &mut [BaseElement]| {
+        state[0] = BaseElement::ONE;
+        state[1] = BaseElement::ONE;
+    };
+    let fib_update = |_: usize, state: &mut [BaseElement]| {
+        state[0] += state[1];
+        state[1] += state[0];
+    };
+
+    // fill the trace row by row
+    let mut trace = TraceTable::with_storage(2, trace_length, vec![], TraceStorage::RowMajor);
+    trace.fill(fib_init, fib_update);
+    assert_eq!(TraceStorage::RowMajor, trace.storage());
+    assert_eq!(trace_length, trace.length());
+    assert_eq!(expected.get(1, 5), trace.get(1, 5));
+    assert_eq!(expected.get_column(0), trace.main_segment().get_column(0));
+    assert_eq!(expected.get_column(1), trace.main_segment().get_column(1));
+
+    // updating the trace should invalidate the transposed columns
+    trace.set(0, 3, BaseElement::ZERO);
+    assert_eq!(BaseElement::ZERO, trace.main_segment().get(0, 3));
+
+    // fill the trace via fragments
+    let mut trace = TraceTable::with_storage(2, trace_length, vec![], TraceStorage::RowMajor);
+    trace.fragments(trace_length / 2).for_each(|mut fragment| {
+        let start = fragment.offset();
+        fragment.fill(
+            |state| {
+                state[0] = expected.get(0, start);
+                state[1] = expected.get(1, start);
+            },
+            fib_update,
+        );
+    });
+    assert_eq!(expected.get_column(0), trace.get_column(0));
+    assert_eq!(expected.get_column(1), trace.get_column(1));
+}
+
+#[test]
+fn fill_par_trace_table() {
+    let trace_length = 64;
+
+    // the state at step i is [i, i^2]
+    let init = |step: usize, state: &mut [BaseElement]| {
+        state[0] = BaseElement::new(step as u128);
+        state[1] = state[0].square();
+    };
+    let update = |_: usize, state: &mut [BaseElement]| {
+        state[0] += BaseElement::ONE;
+        state[1] = state[0].square();
+    };
+
+    let mut expected = TraceTable::new(2, trace_length);
+    expected.fill(|state| init(0, state), update);
+
+    for storage in [TraceStorage::ColumnMajor, TraceStorage::RowMajor] {
+        let mut trace = TraceTable::with_storage(2, trace_length, vec![], storage);
+        trace.fill_par(8, init, update);
+        assert_eq!(expected.get_column(0), trace.get_column(0));
+        assert_eq!(expected.get_column(1), trace.get_column(1));
+    }
+}
+
+#[test]
+fn incremental_trace() {
+    let trace_length = 8;
+    let expected = build_fib_trace(trace_length * 2);
+
+    // build the same trace by appending rows to it
+    let rows = (0..trace_length).map(|i| [expected.get(0, i), expected.get(1, i)]);
+    let trace = IncrementalTrace::from_rows(2, rows);
+    assert_eq!(2, trace.main_trace_width());
+    assert_eq!(trace_length, trace.length());
+    assert_eq!(expected.get_column(0), trace.main_segment().get_column(0));
+    assert_eq!(expected.get_column(1), trace.main_segment().get_column(1));
+
+    // build the same trace via a closure
+    let mut trace = IncrementalTrace::new(2);
+    trace.push_row(&[BaseElement::ONE, BaseElement::ONE]);
+    trace.extend_with(|step, state| {
+        if step == trace_length {
+            return false;
+        }
+        state[0] += state[1];
+        state[1] += state[0];
+        true
+    });
+    assert!(!trace.is_finalized());
+    trace.finalize();
+    assert_eq!(expected.get_column(0), trace.main_segment().get_column(0));
+    assert_eq!(expected.get_column(1), trace.main_segment().get_column(1));
+}
+
+#[test]
+#[should_panic(expected = "execution trace length must be a power of 2")]
+fn incremental_trace_invalid_length() {
+    let rows = (0..9u32).map(|i| [BaseElement::from(i)]);
+    IncrementalTrace::from_rows(1, rows);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn file_trace_table() {
+    let trace_length = 8;
+    let expected = build_fib_trace(trace_length * 2);
+
+    let path = std::env::temp_dir().join(format!("winter_trace_{}.bin", std::process::id()));
+    let mut trace = FileTraceTable::<BaseElement>::create(&path, 2, trace_length).unwrap();
+
+    // write the trace in two fragments
+    let fragment = |offset: usize| -> Vec<Vec<BaseElement>> {
+        (0..2).map(|i| expected.get_column(i)[offset..offset + 4].to_vec()).collect()
+    };
+    trace.write_fragment(0, &fragment(0)).unwrap();
+    trace.write_fragment(4, &fragment(4)).unwrap();
+    trace.flush().unwrap();
+
+    // values can be read back without loading the entire trace
+    assert_eq!(expected.get(1, 5), trace.get(1, 5));
+    assert_eq!(&expected.get_column(0)[2..6], trace.read_column_chunk(0, 2, 4).unwrap());
+    let mut frame = EvaluationFrame::new(2);
+    trace.read_main_frame(7, &mut frame);
+    assert_eq!(&[expected.get(0, 7), expected.get(1, 7)], frame.current());
+    assert_eq!(&[expected.get(0, 0), expected.get(1, 0)], frame.next());
+
+    // the trace is not retained after being passed to the prover for extension
+    trace.with_main_segment(|main_segment| {
+        assert_eq!(expected.get_column(0)[..trace_length], main_segment.get_column(0)[..]);
+    });
+    assert!(!trace.is_loaded());
+
+    // a table opened from the same file should contain the same trace
+    let trace = FileTraceTable::<BaseElement>::open(&path, 2, trace_length).unwrap();
+    assert_eq!(trace_length, trace.length());
+    assert_eq!(expected.get_column(0), trace.main_segment().get_column(0));
+    assert_eq!(expected.get_column(1), trace.main_segment().get_column(1));
+    assert!(trace.is_loaded());
+
+    // opening the file with inconsistent dimensions should fail
+    assert!(FileTraceTable::<BaseElement>::open(&path, 3, trace_length).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn packed_trace_table() {
+    let trace_length = 16;
+    let domains =
+        vec![ColumnDomain::Bit, ColumnDomain::Byte, ColumnDomain::U32, ColumnDomain::Field];
+    let mut trace = PackedTraceTable::<BaseElement>::new(domains, trace_length);
+    trace.fill(
+        |state| state[3] = -BaseElement::ONE,
+        |step, state| {
+            state[0] = BaseElement::from((step % 2) as u32);
+            state[1] = BaseElement::from((step * 3) as u32);
+            state[2] = BaseElement::from(u32::MAX - step as u32);
+            state[3] *= BaseElement::from(3u32);
+        },
+    );
+    assert_eq!(4, trace.main_trace_width());
+    assert_eq!(trace_length, trace.length());
+    assert_eq!(8 + trace_length + trace_length * 4 + trace_length * 16, trace.packed_size());
+
+    // columns converted for the prover should contain the same values as the packed columns
+    trace.with_main_segment(|main_segment| {
+        for column in 0..trace.width() {
+            for step in 0..trace_length {
+                assert_eq!(trace.get(column, step), main_segment.get(column, step));
+            }
+        }
+    });
+
+    // values read from the packed representation should match the expanded trace
+    let mut expected = -BaseElement::ONE;
+    for step in 1..trace_length {
+        expected *= BaseElement::from(3u32);
+        assert_eq!(BaseElement::from(((step - 1) % 2) as u32), trace.get(0, step));
+        assert_eq!(BaseElement::from(((step - 1) * 3) as u32), trace.main_segment().get(1, step));
+        assert_eq!(BaseElement::from(u32::MAX - (step - 1) as u32), trace.get(2, step));
+        assert_eq!(expected, trace.main_segment().get(3, step));
+    }
+
+    // updating the trace should invalidate the expanded trace
+    trace.set_int(1, 0, 255);
+    assert_eq!(BaseElement::from(255u32), trace.main_segment().get(1, 0));
+}
+
+#[test]
+#[should_panic(expected = "does not fit into Bit column 0")]
+fn packed_trace_table_invalid_value() {
+    let mut trace = PackedTraceTable::<BaseElement>::new(vec![ColumnDomain::Bit], 8);
+    trace.set(0, 3, BaseElement::from(2u32));
+}
+
+trace_row! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct FibRow<BaseElement> {
+        a => A,
+        b => B,
+    }
+}
+
+#[test]
+fn trace_builder() {
+    assert_eq!(0, FibRow::A);
+    assert_eq!(1, FibRow::B);
+    assert_eq!(2, FibRow::WIDTH);
+    assert_eq!(&["a", "b"], FibRow::COLUMN_NAMES);
+
+    let trace_length = 8;
+    let expected = build_fib_trace(trace_length * 2);
+
+    // fill the trace from a state transition function
+    let mut builder = TraceBuilder::<FibRow>::new(trace_length);
+    builder.fill(
+        trace_length,
+        || FibRow {
+            a: BaseElement::ONE,
+            b: BaseElement::ONE,
+        },
+        |_, row| {
+            row.a += row.b;
+            row.b += row.a;
+        },
+    );
+    assert_eq!(trace_length, builder.num_rows());
+    assert_eq!(
+        FibRow {
+            a: expected.get(0, 3),
+            b: expected.get(1, 3)
+        },
+        builder.get_row(3)
+    );
+
+    let trace = builder.build();
+    assert_eq!(FibRow::WIDTH, trace.main_trace_width());
+    assert_eq!(expected.get_column(FibRow::A), trace.get_column(FibRow::A));
+    assert_eq!(expected.get_column(FibRow::B), trace.get_column(FibRow::B));
+
+    // append rows one by one
+    let mut builder = TraceBuilder::<FibRow>::new(trace_length).with_meta(vec![1, 2, 3]);
+    for step in 0..trace_length {
+        builder.push_row(&FibRow {
+            a: expected.get(0, step),
+            b: expected.get(1, step),
+        });
+    }
+    let trace = builder.build();
+    assert_eq!(&[1, 2, 3], trace.meta());
+    assert_eq!(expected.get_column(FibRow::B), trace.get_column(FibRow::B));
+}
+
+#[test]
+fn check_trace_constraints() {
+    let trace = TraceTable::init((0..4u32).map(|i| vec![BaseElement::from(i); 8]).collect());
+    let aux_rand_elements = AuxTraceRandElements::<BaseElement>::new();
+
+    // assertions satisfied by the trace
+    let air = MockAir::with_assertions(
+        vec![
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::periodic(3, 0, 2, 3u32.into()),
+        ],
+        8,
+    );
+    assert!(trace.check_constraints(&air, &[], &aux_rand_elements).is_valid());
+
+    // an assertion violated at a single step is reported with the expected and actual values
+    let air = MockAir::with_assertions(vec![Assertion::single(2, 5, BaseElement::ONE)], 8);
+    let report = trace.check_constraints(&air, &[], &aux_rand_elements);
+    assert_eq!(
+        &[ConstraintViolation::MainAssertion {
+            column: 2,
+            step: 5,
+            expected: BaseElement::ONE,
+            actual: BaseElement::from(2u32),
+        }],
+        report.violations()
+    );
+
+    // a periodic assertion is checked at every step of its period
+    let air = MockAir::with_assertions(vec![Assertion::periodic(0, 1, 4, BaseElement::ONE)], 8);
+    let report = trace.check_constraints(&air, &[], &aux_rand_elements);
+    assert_eq!(
+        vec![1, 5],
+        report.violations().iter().map(|violation| violation.step()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn trace_diff() {
+    let trace = build_fib_trace(32);
+    assert!(trace.diff(&build_fib_trace(32)).is_identical());
+    assert_eq!("traces are identical", trace.diff(&trace).to_string());
+
+    // a witness bug which corrupts one column from some step onwards
+    let mut columns = (0..trace.main_trace_width())
+        .map(|column| trace.get_column(column).to_vec())
+        .collect::<Vec<_>>();
+    for value in columns[1][5..].iter_mut() {
+        *value += BaseElement::ONE;
+    }
+    columns[0][9] = BaseElement::ZERO;
+    let other = TraceTable::init(columns);
+
+    let diff = trace.diff(&other);
+    assert!(diff.shapes_match());
+    assert!(!diff.is_identical());
+    assert_eq!(12, diff.num_differences());
+    assert_eq!(
+        Some(CellDiff {
+            step: 5,
+            column: 1,
+            left: trace.get(1, 5),
+            right: trace.get(1, 5) + BaseElement::ONE,
+        }),
+        diff.first_difference()
+    );
+    let summaries = diff
+        .columns()
+        .iter()
+        .map(|column| (column.column, column.num_differences, column.first.step, column.last.step))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(0, 1, 9, 9), (1, 11, 5, 15)], summaries);
+    assert!(diff.to_string().starts_with("traces differ in 12 cell(s) of 2 column(s)"));
+
+    // traces of different lengths are compared over their common steps
+    let diff = trace.diff(&build_fib_trace(64));
+    assert!(!diff.shapes_match());
+    assert_eq!((2, 16), diff.left_shape());
+    assert_eq!((2, 32), diff.right_shape());
+    assert_eq!(None, diff.first_difference());
+}
+
+#[test]
+fn trace_format_columns() {
+    let trace = TraceTable::init(vec![
+        (0..8u32).map(BaseElement::from).collect(),
+        (0..8u32).map(|i| BaseElement::from(i * 1000)).collect(),
+        vec![BaseElement::ONE; 8],
+    ]);
+
+    let expected = "\
+step | col 1 | col 2
+-----+-------+------
+   2 |  2000 |     1
+   3 |  3000 |     1";
+    assert_eq!(expected, trace.format_columns(1..3, 2..4));
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn trace_format_columns_out_of_bounds() {
+    let trace = build_fib_trace(16);
+    trace.format_columns(0..3, 0..4);
+}
+
+/// Builds a running sum of `alpha * a + b` over all rows of a two-column main trace.
+struct RunningSumBuilder;
+
+impl AuxTraceBuilder<BaseElement> for RunningSumBuilder {
+    fn aux_trace_width(&self) -> usize {
+        1
+    }
+
+    fn num_rand_elements(&self) -> usize {
+        1
+    }
+
+    fn build_aux_columns<E>(
+        &mut self,
+        main_trace: MainTraceRows<BaseElement>,
+        rand_elements: &[E],
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        let mut sum = E::ZERO;
+        let column = main_trace
+            .map(|row| {
+                sum += rand_elements[0].mul_base(row.get(0)) + E::from(row.get(1));
+                sum
+            })
+            .collect();
+        ColMatrix::new(vec![column])
+    }
+}
+
+#[test]
+fn trace_with_aux() {
+    let trace_length = 8;
+    let mut trace = TraceWithAux::new(build_fib_trace(trace_length * 2), RunningSumBuilder);
+    assert_eq!(2, trace.main_trace_width());
+    assert_eq!(1, trace.aux_trace_width());
+    assert_eq!(1, trace.layout().get_aux_segment_rand_elements(0));
+    assert_eq!(trace_length, trace.length());
+
+    let alpha = BaseElement::from(7u32);
+    let aux_segment = trace.build_aux_segment(&[], &[alpha]).unwrap();
+    let mut expected = BaseElement::ZERO;
+    for step in 0..trace_length {
+        let main_segment = trace.main_segment();
+        expected += alpha * main_segment.get(0, step) + main_segment.get(1, step);
+        assert_eq!(expected, aux_segment.get(0, step));
+    }
+
+    // the builder defines a single auxiliary segment
+    assert!(trace.build_aux_segment(&[aux_segment], &[alpha]).is_none());
+}
+
+#[test]
+fn main_trace_rows() {
+    let trace = build_fib_trace(16);
+    let rows = MainTraceRows::new(trace.main_segment());
+    assert_eq!(8, rows.len());
+    assert_eq!(2, rows.num_cols());
+
+    let mut buffer = vec![BaseElement::ZERO; 2];
+    for row in rows {
+        let step = row.index();
+        row.read_into(&mut buffer);
+        assert_eq!(trace.get_column(0)[step], buffer[0]);
+        assert_eq!(trace.get_column(1)[step], row.get(1));
+        assert_eq!(trace.get_column(0)[(step + 1) % 8], row.get_next(0));
+    }
+}
+
+#[test]
+#[should_panic(expected = "expected the auxiliary segment to consist of 2 columns, but was 1")]
+fn trace_with_aux_invalid_width() {
+    struct WideBuilder;
+    impl AuxTraceBuilder<BaseElement> for WideBuilder {
+        fn aux_trace_width(&self) -> usize {
+            2
+        }
+
+        fn num_rand_elements(&self) -> usize {
+            1
+        }
+
+        fn build_aux_columns<E>(
+            &mut self,
+            main_trace: MainTraceRows<BaseElement>,
+            _: &[E],
+        ) -> ColMatrix<E>
+        where
+            E: FieldElement<BaseField = BaseElement>,
+        {
+            ColMatrix::new(vec![vec![E::ZERO; main_trace.num_rows()]])
+        }
+    }
+
+    let mut trace = TraceWithAux::new(build_fib_trace(16), WideBuilder);
+    trace.build_aux_segment::<BaseElement>(&[], &[BaseElement::ONE]);
+}
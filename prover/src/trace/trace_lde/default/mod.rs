@@ -7,13 +7,20 @@ use super::{
     ColMatrix, ElementHasher, EvaluationFrame, FieldElement, Hasher, Queries, StarkDomain,
     TraceInfo, TraceLayout, TraceLde, TracePolyTable, Vec,
 };
-use crate::{RowMatrix, DEFAULT_SEGMENT_WIDTH};
+use crate::{matrix::LdeMatrix, CpuBackend, ProverBackend, ProverError, RowMatrix};
 use crypto::MerkleTree;
 
+#[cfg(feature = "std")]
+use crate::matrix::{storage_error, DiskMatrix};
+#[cfg(feature = "std")]
+use crate::time::Instant;
 #[cfg(feature = "std")]
 use log::debug;
 #[cfg(feature = "std")]
-use std::time::Instant;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 
 #[cfg(test)]
 mod tests;
@@ -28,17 +35,41 @@ mod tests;
 ///   will always be elements in the base field (even when an extension field is used).
 /// - Auxiliary segments: a list of 0 or more segments for traces generated after the prover
 ///   commits to the first trace segment. Currently, at most 1 auxiliary segment is possible.
-pub struct DefaultTraceLde<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+///
+/// Low-degree extensions of trace segments and commitments to them are computed using the
+/// [ProverBackend] specified by the `B` type parameter ([CpuBackend] by default).
+///
+/// Every segment is committed to via a separate Merkle tree. Rows of the main and auxiliary
+/// segments cannot be interleaved into a single tree: random elements used to build an auxiliary
+/// segment are drawn from the public coin only after the commitment to all preceding segments
+/// has been written into the channel, and thus, committing to all segments at once would let the
+/// prover choose the main segment after learning these elements.
+///
+/// Evaluations of all segments can be discarded via [TraceLde::discard_evaluations()] once
+/// constraint evaluation is complete. After this, the rows at queried positions are re-computed
+/// from the trace polynomials.
+///
+/// When the LDE is instantiated via [DefaultTraceLde::new_on_disk()], evaluations of all segments
+/// are stored in temporary files on disk rather than in memory.
+pub struct DefaultTraceLde<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    B: ProverBackend = CpuBackend,
+> {
     // low-degree extension of the main segment of the trace
-    main_segment_lde: RowMatrix<E::BaseField>,
+    main_segment_lde: LdeMatrix<E::BaseField>,
     // commitment to the main segment of the trace
     main_segment_tree: MerkleTree<H>,
     // low-degree extensions of the auxiliary segments of the trace
-    aux_segment_ldes: Vec<RowMatrix<E>>,
+    aux_segment_ldes: Vec<LdeMatrix<E>>,
     // commitment to the auxiliary segments of the trace
     aux_segment_trees: Vec<MerkleTree<H>>,
     blowup: usize,
     trace_info: TraceInfo,
+    backend: B,
+    // directory in which evaluations of the trace segments are stored, if any
+    #[cfg(feature = "std")]
+    lde_directory: Option<PathBuf>,
 }
 
 impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> DefaultTraceLde<E, H> {
@@ -53,52 +84,149 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> DefaultTraceLd
         trace_info: &TraceInfo,
         main_trace: &ColMatrix<E::BaseField>,
         domain: &StarkDomain<E::BaseField>,
+    ) -> (Self, TracePolyTable<E>) {
+        Self::with_backend(trace_info, main_trace, domain, CpuBackend)
+    }
+
+    /// Takes the main trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
+    /// polynomial evaluations, and creates a new [DefaultTraceLde] with the LDE of the main trace
+    /// segment and the commitment.
+    ///
+    /// Unlike [DefaultTraceLde::new()], the evaluations are written into a temporary file in the
+    /// specified directory one segment of columns at a time, and are read back from the file
+    /// sequentially to build the commitment. The same applies to auxiliary trace segments added
+    /// later on. Thus, the full LDE of the trace is never held in memory, which makes it
+    /// possible to prove traces whose LDE does not fit in RAM. The commitments are identical to
+    /// the ones built by [DefaultTraceLde::new()]. The files are deleted when the LDE is dropped.
+    ///
+    /// Returns a tuple containing a [TracePolyTable] with the trace polynomials for the main trace
+    /// segment and the new [DefaultTraceLde].
+    ///
+    /// # Errors
+    /// Returns an error if the evaluations could not be written to or read from disk.
+    #[cfg(feature = "std")]
+    pub fn new_on_disk(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+        directory: &Path,
+    ) -> io::Result<(Self, TracePolyTable<E>)> {
+        let (main_segment_lde, main_segment_tree, main_segment_polys) =
+            build_trace_commitment_on_disk::<E, E::BaseField, H>(main_trace, domain, directory)?;
+
+        let trace_poly_table = TracePolyTable::new(main_segment_polys);
+        let trace_lde = DefaultTraceLde {
+            main_segment_lde: LdeMatrix::Disk(main_segment_lde),
+            main_segment_tree,
+            aux_segment_ldes: Vec::new(),
+            aux_segment_trees: Vec::new(),
+            blowup: domain.trace_to_lde_blowup(),
+            trace_info: trace_info.clone(),
+            backend: CpuBackend,
+            lde_directory: Some(directory.to_path_buf()),
+        };
+
+        Ok((trace_lde, trace_poly_table))
+    }
+}
+
+impl<E, H, B> DefaultTraceLde<E, H, B>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    B: ProverBackend,
+{
+    /// Takes the main trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
+    /// polynomial evaluations, and creates a new [DefaultTraceLde] with the LDE of the main trace
+    /// segment and the commitment.
+    ///
+    /// All of the above operations (as well as the same operations for auxiliary trace segments)
+    /// are performed using the specified `backend`.
+    ///
+    /// Returns a tuple containing a [TracePolyTable] with the trace polynomials for the main trace
+    /// segment and the new [DefaultTraceLde].
+    pub fn with_backend(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+        backend: B,
     ) -> (Self, TracePolyTable<E>) {
         // extend the main execution trace and build a Merkle tree from the extended trace
         let (main_segment_lde, main_segment_tree, main_segment_polys) =
-            build_trace_commitment::<E, E::BaseField, H>(main_trace, domain);
+            build_trace_commitment::<E, E::BaseField, H, B>(&backend, main_trace, domain);
 
         let trace_poly_table = TracePolyTable::new(main_segment_polys);
         let trace_lde = DefaultTraceLde {
-            main_segment_lde,
+            main_segment_lde: LdeMatrix::Evaluations(main_segment_lde),
             main_segment_tree,
             aux_segment_ldes: Vec::new(),
             aux_segment_trees: Vec::new(),
             blowup: domain.trace_to_lde_blowup(),
             trace_info: trace_info.clone(),
+            backend,
+            #[cfg(feature = "std")]
+            lde_directory: None,
         };
 
         (trace_lde, trace_poly_table)
     }
 
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Extends the provided auxiliary trace segment and builds a commitment to it; the extension
+    /// is stored on disk if the main trace segment is stored on disk as well.
+    #[allow(clippy::type_complexity)]
+    fn extend_aux_segment(
+        &self,
+        aux_trace: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> Result<(LdeMatrix<E>, MerkleTree<H>, ColMatrix<E>), ProverError> {
+        #[cfg(feature = "std")]
+        if let Some(directory) = self.lde_directory.as_deref() {
+            let (segment_lde, segment_tree, segment_polys) =
+                build_trace_commitment_on_disk::<E, E, H>(aux_trace, domain, directory)
+                    .map_err(storage_error)?;
+            return Ok((LdeMatrix::Disk(segment_lde), segment_tree, segment_polys));
+        }
+
+        let (segment_lde, segment_tree, segment_polys) =
+            build_trace_commitment::<E, E, H, B>(&self.backend, aux_trace, domain);
+        Ok((LdeMatrix::Evaluations(segment_lde), segment_tree, segment_polys))
+    }
+
     // TEST HELPERS
     // --------------------------------------------------------------------------------------------
 
     /// Returns number of columns in the main segment of the execution trace.
     #[cfg(test)]
     pub fn main_segment_width(&self) -> usize {
-        self.main_segment_lde.num_cols()
+        self.main_segment_lde.evaluations().num_cols()
     }
 
     /// Returns a reference to [Matrix] representing the main trace segment.
     #[cfg(test)]
     pub fn get_main_segment(&self) -> &RowMatrix<E::BaseField> {
-        &self.main_segment_lde
+        self.main_segment_lde.evaluations()
     }
 
     /// Returns the entire trace for the column at the specified index.
     #[cfg(test)]
     pub fn get_main_segment_column(&self, col_idx: usize) -> Vec<E::BaseField> {
-        (0..self.main_segment_lde.num_rows())
-            .map(|row_idx| self.main_segment_lde.get(col_idx, row_idx))
+        let main_segment_lde = self.main_segment_lde.evaluations();
+        (0..main_segment_lde.num_rows())
+            .map(|row_idx| main_segment_lde.get(col_idx, row_idx))
             .collect()
     }
 }
 
-impl<E, H> TraceLde<E> for DefaultTraceLde<E, H>
+impl<E, H, B> TraceLde<E> for DefaultTraceLde<E, H, B>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    B: ProverBackend,
 {
     type HashFn = H;
 
@@ -115,6 +243,10 @@ where
     /// Returns a tuple containing the column polynomials in coefficient from and the commitment
     /// to the polynomial evaluations over the LDE domain.
     ///
+    /// # Errors
+    /// Returns an error if the LDE is stored on disk, and the evaluations could not be written to
+    /// or read from disk.
+    ///
     /// # Panics
     ///
     /// This function will panic if any of the following are true:
@@ -124,10 +256,10 @@ where
         &mut self,
         aux_trace: &ColMatrix<E>,
         domain: &StarkDomain<E::BaseField>,
-    ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest) {
+    ) -> Result<(ColMatrix<E>, <Self::HashFn as Hasher>::Digest), ProverError> {
         // extend the auxiliary trace segment and build a Merkle tree from the extended trace
         let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
-            build_trace_commitment::<E, E, H>(aux_trace, domain);
+            self.extend_aux_segment(aux_trace, domain)?;
 
         // check errors
         assert!(
@@ -145,7 +277,7 @@ where
         let root_hash = *aux_segment_tree.root();
         self.aux_segment_trees.push(aux_segment_tree);
 
-        (aux_segment_polys, root_hash)
+        Ok((aux_segment_polys, root_hash))
     }
 
     /// Reads current and next rows from the main trace segment into the specified frame.
@@ -153,13 +285,13 @@ where
         &self,
         lde_step: usize,
         frame: &mut EvaluationFrame<E::BaseField>,
-    ) {
+    ) -> Result<(), ProverError> {
         // at the end of the trace, next state wraps around and we read the first step again
         let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
 
         // copy main trace segment values into the frame
-        frame.current_mut().copy_from_slice(self.main_segment_lde.row(lde_step));
-        frame.next_mut().copy_from_slice(self.main_segment_lde.row(next_lde_step));
+        self.main_segment_lde.read_row_into(lde_step, frame.current_mut())?;
+        self.main_segment_lde.read_row_into(next_lde_step, frame.next_mut())
     }
 
     /// Reads current and next rows from the auxiliary trace segment into the specified frame.
@@ -167,33 +299,54 @@ where
     /// # Panics
     /// This currently assumes that there is exactly one auxiliary trace segment, and will panic
     /// otherwise.
-    fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
+    fn read_aux_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E>,
+    ) -> Result<(), ProverError> {
         // at the end of the trace, next state wraps around and we read the first step again
         let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
 
         // copy auxiliary trace segment values into the frame
-        let segment = &self.aux_segment_ldes[0];
-        frame.current_mut().copy_from_slice(segment.row(lde_step));
-        frame.next_mut().copy_from_slice(segment.row(next_lde_step));
+        self.aux_segment_ldes[0].read_row_into(lde_step, frame.current_mut())?;
+        self.aux_segment_ldes[0].read_row_into(next_lde_step, frame.next_mut())
     }
 
     /// Returns trace table rows at the specified positions along with Merkle authentication paths
     /// from the commitment root to these rows.
-    fn query(&self, positions: &[usize]) -> Vec<Queries> {
+    fn query(&self, positions: &[usize]) -> Result<Vec<Queries>, ProverError> {
         // build queries for the main trace segment
         let mut result = vec![build_segment_queries(
             &self.main_segment_lde,
             &self.main_segment_tree,
             positions,
-        )];
+        )?];
 
         // build queries for auxiliary trace segments
         for (i, segment_tree) in self.aux_segment_trees.iter().enumerate() {
             let segment_lde = &self.aux_segment_ldes[i];
-            result.push(build_segment_queries(segment_lde, segment_tree, positions));
+            result.push(build_segment_queries(segment_lde, segment_tree, positions)?);
         }
 
-        result
+        Ok(result)
+    }
+
+    /// Discards low-degree extensions of all trace segments, keeping only the trace polynomials
+    /// from which rows at queried positions can be re-computed.
+    fn discard_evaluations(
+        &mut self,
+        trace_polys: &TracePolyTable<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) {
+        let main_polys = trace_polys.main_trace_polys().map(|poly| poly.to_vec()).collect();
+        self.main_segment_lde.discard_evaluations(ColMatrix::new(main_polys), domain);
+
+        let mut aux_polys = trace_polys.aux_trace_polys();
+        for (i, segment_lde) in self.aux_segment_ldes.iter_mut().enumerate() {
+            let segment_width = self.trace_info.layout().get_aux_segment_width(i);
+            let polys = aux_polys.by_ref().take(segment_width).map(|poly| poly.to_vec()).collect();
+            segment_lde.discard_evaluations(ColMatrix::new(polys), domain);
+        }
     }
 
     /// Returns the number of rows in the execution trace.
@@ -224,7 +377,8 @@ where
 ///
 /// The trace commitment is computed by hashing each row of the extended execution trace, then
 /// building a Merkle tree from the resulting hashes.
-fn build_trace_commitment<E, F, H>(
+fn build_trace_commitment<E, F, H, B>(
+    backend: &B,
     trace: &ColMatrix<F>,
     domain: &StarkDomain<E::BaseField>,
 ) -> (RowMatrix<F>, MerkleTree<H>, ColMatrix<F>)
@@ -232,12 +386,23 @@ where
     E: FieldElement,
     F: FieldElement<BaseField = E::BaseField>,
     H: ElementHasher<BaseField = E::BaseField>,
+    B: ProverBackend,
 {
     // extend the execution trace
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!(
+        "trace_lde",
+        width = trace.num_cols(),
+        trace_length = trace.num_rows(),
+        lde_domain_size = domain.lde_domain_size()
+    )
+    .entered();
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let trace_polys = trace.interpolate_columns();
-    let trace_lde = RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&trace_polys, domain);
+    let trace_polys = backend.interpolate_columns(trace);
+    let trace_lde = backend.evaluate_polys_over(&trace_polys, domain);
+    #[cfg(feature = "tracing")]
+    drop(span);
     #[cfg(feature = "std")]
     debug!(
         "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) in {} ms",
@@ -249,9 +414,16 @@ where
     );
 
     // build trace commitment
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!(
+        "trace_commitment",
+        width = trace_lde.num_cols(),
+        lde_domain_size = trace_lde.num_rows()
+    )
+    .entered();
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let trace_tree = trace_lde.commit_to_rows();
+    let trace_tree = backend.commit_to_rows(&trace_lde);
     #[cfg(feature = "std")]
     debug!(
         "Computed execution trace commitment (Merkle tree of depth {}) in {} ms",
@@ -262,24 +434,65 @@ where
     (trace_lde, trace_tree, trace_polys)
 }
 
+/// Computes a low-degree extension (LDE) of the provided execution trace over the specified
+/// domain, stores it in a temporary file in the specified directory, and builds a commitment to
+/// the extended trace.
+///
+/// This is the same as [build_trace_commitment()] executed with [CpuBackend], except that the
+/// entire LDE is never held in memory.
+#[cfg(feature = "std")]
+fn build_trace_commitment_on_disk<E, F, H>(
+    trace: &ColMatrix<F>,
+    domain: &StarkDomain<E::BaseField>,
+    directory: &Path,
+) -> io::Result<(DiskMatrix<F>, MerkleTree<H>, ColMatrix<F>)>
+where
+    E: FieldElement,
+    F: FieldElement<BaseField = E::BaseField>,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!(
+        "trace_lde",
+        width = trace.num_cols(),
+        trace_length = trace.num_rows(),
+        lde_domain_size = domain.lde_domain_size(),
+        on_disk = true
+    )
+    .entered();
+    let now = Instant::now();
+    let trace_polys = CpuBackend.interpolate_columns(trace);
+    let (trace_lde, trace_tree) =
+        DiskMatrix::evaluate_polys_over::<H>(&trace_polys, domain, directory)?;
+    debug!(
+        "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) and committed to it on disk in {} ms",
+        trace_lde.num_cols(),
+        trace_polys.num_rows().ilog2(),
+        trace_lde.num_rows().ilog2(),
+        domain.trace_to_lde_blowup(),
+        now.elapsed().as_millis()
+    );
+
+    Ok((trace_lde, trace_tree, trace_polys))
+}
+
 fn build_segment_queries<E, H>(
-    segment_lde: &RowMatrix<E>,
+    segment_lde: &LdeMatrix<E>,
     segment_tree: &MerkleTree<H>,
     positions: &[usize],
-) -> Queries
+) -> Result<Queries, ProverError>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
 {
     // for each position, get the corresponding row from the trace segment LDE and put all these
     // rows into a single vector
-    let trace_states =
-        positions.iter().map(|&pos| segment_lde.row(pos).to_vec()).collect::<Vec<_>>();
+    let trace_states = segment_lde.read_rows(positions)?;
 
     // build Merkle authentication paths to the leaves specified by positions
     let trace_proof = segment_tree
         .prove_batch(positions)
         .expect("failed to generate a Merkle proof for trace queries");
 
-    Queries::new(trace_proof, trace_states)
+    Ok(Queries::new(trace_proof, trace_states))
 }
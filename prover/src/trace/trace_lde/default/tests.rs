@@ -5,8 +5,11 @@
 
 use crate::{
     tests::{build_fib_trace, MockAir},
-    DefaultTraceLde, StarkDomain, Trace, TraceLde,
+    ColMatrix, CpuBackend, DefaultTraceLde, ProverBackend, RowMatrix, StarkDomain, Trace, TraceLde,
 };
+#[cfg(feature = "std")]
+use crate::{EvaluationFrame, ProverError, TraceInfo};
+use core::sync::atomic::{AtomicUsize, Ordering};
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
     fields::f128::BaseElement, get_power_series, get_power_series_with_offset, polynom,
@@ -98,6 +101,135 @@ fn commit_trace_table() {
     assert_eq!(*expected_tree.root(), trace_lde.get_main_trace_commitment())
 }
 
+#[test]
+fn extend_trace_table_with_backend() {
+    // build the trace and the domain
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+
+    // extend the trace using the default and the counting backends
+    let (expected_lde, _) = DefaultTraceLde::<BaseElement, Blake3>::new(
+        &trace.get_info(),
+        trace.main_segment(),
+        &domain,
+    );
+    let backend = CountingBackend::default();
+    let (trace_lde, _) = DefaultTraceLde::<BaseElement, Blake3, _>::with_backend(
+        &trace.get_info(),
+        trace.main_segment(),
+        &domain,
+        backend,
+    );
+
+    // make sure all operations were delegated to the backend
+    assert_eq!(1, trace_lde.backend.num_interpolations.load(Ordering::Relaxed));
+    assert_eq!(1, trace_lde.backend.num_evaluations.load(Ordering::Relaxed));
+    assert_eq!(1, trace_lde.backend.num_commitments.load(Ordering::Relaxed));
+    assert_eq!(expected_lde.get_main_trace_commitment(), trace_lde.get_main_trace_commitment());
+}
+
+#[test]
+fn query_trace_table_after_discarding_evaluations() {
+    // build the trace and the domain
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+
+    let (mut trace_lde, trace_polys) = DefaultTraceLde::<BaseElement, Blake3>::new(
+        &trace.get_info(),
+        trace.main_segment(),
+        &domain,
+    );
+    let positions = vec![0, 5, 17, 42, 63];
+    let expected_queries = trace_lde.query(&positions);
+
+    // rows re-computed from trace polynomials should be the same as the rows of the extended
+    // trace
+    trace_lde.discard_evaluations(&trace_polys, &domain);
+    assert_eq!(64, trace_lde.trace_len());
+    assert_eq!(expected_queries, trace_lde.query(&positions));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn extend_trace_table_on_disk() {
+    // build a trace which is wider than a single segment of the disk matrix
+    let trace_length = 16;
+    let trace_width = 10;
+    let air = MockAir::with_trace_length(trace_length);
+    let domain = StarkDomain::new(&air);
+    let columns = (0..trace_width)
+        .map(|i| (0..trace_length).map(|j| BaseElement::from((i * j + 1) as u32)).collect())
+        .collect::<Vec<_>>();
+    let main_trace = ColMatrix::new(columns);
+    let trace_info = TraceInfo::new(trace_width, trace_length);
+
+    let (expected_lde, expected_polys) =
+        DefaultTraceLde::<BaseElement, Blake3>::new(&trace_info, &main_trace, &domain);
+    let (trace_lde, trace_polys) = DefaultTraceLde::<BaseElement, Blake3>::new_on_disk(
+        &trace_info,
+        &main_trace,
+        &domain,
+        &std::env::temp_dir(),
+    )
+    .unwrap();
+
+    // the commitment and the trace polynomials must be the same as for the in-memory LDE
+    assert_eq!(expected_lde.get_main_trace_commitment(), trace_lde.get_main_trace_commitment());
+    assert_eq!(expected_polys.get_main_trace_poly(9), trace_polys.get_main_trace_poly(9));
+    assert_eq!(expected_lde.trace_len(), trace_lde.trace_len());
+
+    // frames read from disk must be the same as frames read from memory
+    let mut expected_frame = EvaluationFrame::new(trace_width);
+    let mut frame = EvaluationFrame::new(trace_width);
+    for lde_step in [0, 7, trace_lde.trace_len() - 1] {
+        expected_lde.read_main_trace_frame_into(lde_step, &mut expected_frame).unwrap();
+        trace_lde.read_main_trace_frame_into(lde_step, &mut frame).unwrap();
+        assert_eq!(expected_frame.current(), frame.current());
+        assert_eq!(expected_frame.next(), frame.next());
+    }
+
+    // queries must be the same as well
+    let positions = vec![42, 0, 127, 5, 17];
+    assert_eq!(expected_lde.query(&positions), trace_lde.query(&positions));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn extend_trace_table_on_disk_read_error() {
+    let trace_length = 16;
+    let air = MockAir::with_trace_length(trace_length);
+    let domain = StarkDomain::new(&air);
+    let trace = build_fib_trace(trace_length * 2);
+    let directory = std::env::temp_dir().join(format!("winter_lde_test_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let (trace_lde, _) = DefaultTraceLde::<BaseElement, Blake3>::new_on_disk(
+        &trace.get_info(),
+        trace.main_segment(),
+        &domain,
+        &directory,
+    )
+    .unwrap();
+
+    // truncate the file holding the evaluations; reading rows from it should fail with an error
+    for entry in std::fs::read_dir(&directory).unwrap() {
+        std::fs::File::create(entry.unwrap().path()).unwrap();
+    }
+    let mut frame = EvaluationFrame::new(2);
+    assert!(matches!(
+        trace_lde.read_main_trace_frame_into(0, &mut frame),
+        Err(ProverError::LdeStorageError(_))
+    ));
+    assert!(matches!(trace_lde.query(&[0, 5]), Err(ProverError::LdeStorageError(_))));
+
+    drop(trace_lde);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -105,3 +237,37 @@ fn build_lde_domain<B: StarkField>(domain_size: usize) -> Vec<B> {
     let g = B::get_root_of_unity(domain_size.ilog2());
     get_power_series_with_offset(g, B::GENERATOR, domain_size)
 }
+
+/// A backend which delegates all operations to [CpuBackend] and counts the number of times each
+/// operation was invoked.
+#[derive(Default)]
+struct CountingBackend {
+    num_interpolations: AtomicUsize,
+    num_evaluations: AtomicUsize,
+    num_commitments: AtomicUsize,
+}
+
+impl ProverBackend for CountingBackend {
+    fn interpolate_columns<E: FieldElement>(&self, columns: &ColMatrix<E>) -> ColMatrix<E> {
+        self.num_interpolations.fetch_add(1, Ordering::Relaxed);
+        CpuBackend.interpolate_columns(columns)
+    }
+
+    fn evaluate_polys_over<E: FieldElement>(
+        &self,
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> RowMatrix<E> {
+        self.num_evaluations.fetch_add(1, Ordering::Relaxed);
+        CpuBackend.evaluate_polys_over(polys, domain)
+    }
+
+    fn commit_to_rows<E, H>(&self, matrix: &RowMatrix<E>) -> MerkleTree<H>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.num_commitments.fetch_add(1, Ordering::Relaxed);
+        CpuBackend.commit_to_rows(matrix)
+    }
+}
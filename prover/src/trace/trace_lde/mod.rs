@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{ColMatrix, EvaluationFrame, FieldElement, TracePolyTable};
-use crate::StarkDomain;
+use crate::{ProverError, StarkDomain};
 use air::{proof::Queries, TraceInfo, TraceLayout};
 use crypto::{ElementHasher, Hasher};
 use utils::collections::Vec;
@@ -35,6 +35,10 @@ pub trait TraceLde<E: FieldElement>: Sync {
     /// Returns a tuple containing the column polynomials in coefficient form and the commitment
     /// to the polynomial evaluations over the LDE domain.
     ///
+    /// # Errors
+    /// Returns an error if the polynomial evaluations could not be stored (e.g., when they are
+    /// stored on disk, and the disk is full).
+    ///
     /// # Panics
     ///
     /// This function is expected to panic if any of the following are true:
@@ -44,21 +48,50 @@ pub trait TraceLde<E: FieldElement>: Sync {
         &mut self,
         aux_trace: &ColMatrix<E>,
         domain: &StarkDomain<E::BaseField>,
-    ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest);
+    ) -> Result<(ColMatrix<E>, <Self::HashFn as Hasher>::Digest), ProverError>;
 
     /// Reads current and next rows from the main trace segment into the specified frame.
+    ///
+    /// # Errors
+    /// Returns an error if the rows could not be read from where the extended trace is stored.
     fn read_main_trace_frame_into(
         &self,
         lde_step: usize,
         frame: &mut EvaluationFrame<E::BaseField>,
-    );
+    ) -> Result<(), ProverError>;
 
     /// Reads current and next rows from the auxiliary trace segment into the specified frame.
-    fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>);
+    ///
+    /// # Errors
+    /// Returns an error if the rows could not be read from where the extended trace is stored.
+    fn read_aux_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E>,
+    ) -> Result<(), ProverError>;
 
     /// Returns trace table rows at the specified positions along with Merkle authentication paths
     /// from the commitment root to these rows.
-    fn query(&self, positions: &[usize]) -> Vec<Queries>;
+    ///
+    /// # Errors
+    /// Returns an error if the rows could not be read from where the extended trace is stored.
+    fn query(&self, positions: &[usize]) -> Result<Vec<Queries>, ProverError>;
+
+    /// Discards low-degree extensions of all trace segments which have been computed so far.
+    ///
+    /// This is invoked by the prover in the low-memory mode (see
+    /// [Prover::low_memory_mode()](crate::Prover::low_memory_mode)) once constraint evaluation
+    /// is complete. After this method is invoked, trace frames can no longer be read, but
+    /// [TraceLde::query()] must still return rows at the queried positions; these rows could be
+    /// re-computed from the provided `trace_polys`.
+    ///
+    /// The default implementation does nothing, and thus, keeps the extended trace in memory.
+    fn discard_evaluations(
+        &mut self,
+        _trace_polys: &TracePolyTable<E>,
+        _domain: &StarkDomain<E::BaseField>,
+    ) {
+    }
 
     /// Returns the number of rows in the execution trace.
     fn trace_len(&self) -> usize;
@@ -6,7 +6,12 @@
 use super::{ColMatrix, Trace};
 use air::{EvaluationFrame, TraceInfo, TraceLayout};
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::{collections::Vec, iter_mut, uninit_vector};
+
+#[cfg(not(feature = "std"))]
+use core::cell::OnceCell;
+#[cfg(feature = "std")]
+use std::sync::OnceLock as OnceCell;
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
@@ -48,6 +53,14 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// This function work just like [TraceTable::new()] function, but also takes a metadata
 /// parameter which can be an arbitrary sequence of bytes up to 64KB in size.
 ///
+/// # Trace storage
+/// By default, data in the trace table is stored in column-major order. This is the order in which
+/// the prover consumes the trace, but filling such a trace row by row means that every row update
+/// writes into `width` different memory regions. For traces which are built by filling rows
+/// sequentially, a row-major storage can be selected via [TraceTable::with_storage()] function
+/// (see [TraceStorage]). A row-major trace is transposed into column-major form transparently when
+/// the prover first requests the main trace segment.
+///
 /// # Concurrent trace generation
 /// For computations which consist of many small independent computations, we can generate the
 /// execution trace of the entire computation by building fragments of the trace in parallel,
@@ -59,13 +72,37 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](TraceTableFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [TraceTableFragment::fill()] method are identical to the
 /// semantics of the [TraceTable::fill()] method.
+///
+/// When the state of a computation at any step can be derived from the step index alone, the
+/// [fill_par()](TraceTable::fill_par) method can be used instead. This method breaks the
+/// execution trace into fragments and fills them in parallel using the same pair of closures.
 #[derive(Debug, Clone)]
 pub struct TraceTable<B: StarkField> {
     layout: TraceLayout,
-    trace: ColMatrix<B>,
+    trace: TraceData<B>,
     meta: Vec<u8>,
 }
 
+/// Memory layout of the data stored in a [TraceTable].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceStorage {
+    /// Values of each column are stored contiguously. This is the default layout.
+    ColumnMajor,
+    /// Values of each row are stored contiguously; the trace is transposed into column-major form
+    /// before the prover builds its low-degree extension.
+    RowMajor,
+}
+
+#[derive(Debug, Clone)]
+enum TraceData<B: StarkField> {
+    Columns(ColMatrix<B>),
+    Rows {
+        width: usize,
+        data: Vec<B>,
+        columns: OnceCell<ColMatrix<B>>,
+    },
+}
+
 impl<B: StarkField> TraceTable<B> {
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
@@ -77,7 +114,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
     ///   `B`, or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
@@ -92,11 +129,27 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
     ///   field `B`, or is not a power of two.
     /// * Length of `meta` is greater than 65535;
     pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
+        Self::with_storage(width, length, meta, TraceStorage::ColumnMajor)
+    }
+
+    /// Creates a new execution trace of the specified width and length, with the specified
+    /// metadata, and with data stored in the specified layout.
+    ///
+    /// This allocates all the required memory for the trace, but does not initialize it. It is
+    /// expected that the trace will be filled using one of the data mutator methods.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 65535.
+    /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
+    ///   field `B`, or is not a power of two.
+    /// * Length of `meta` is greater than 65535;
+    pub fn with_storage(width: usize, length: usize, meta: Vec<u8>, storage: TraceStorage) -> Self {
         assert!(width > 0, "execution trace must consist of at least one column");
         assert!(
             width <= TraceInfo::MAX_TRACE_WIDTH,
@@ -124,10 +177,21 @@ impl<B: StarkField> TraceTable<B> {
             meta.len()
         );
 
-        let columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+        let trace = match storage {
+            TraceStorage::ColumnMajor => {
+                let columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+                TraceData::Columns(ColMatrix::new(columns))
+            }
+            TraceStorage::RowMajor => TraceData::Rows {
+                width,
+                data: unsafe { uninit_vector(width * length) },
+                columns: OnceCell::new(),
+            },
+        };
+
         Self {
             layout: TraceLayout::new(width, [0], [0]),
-            trace: ColMatrix::new(columns),
+            trace,
             meta,
         }
     }
@@ -136,7 +200,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * The `columns` vector is empty or has over 255 columns.
+    /// * The `columns` vector is empty or has over 65535 columns.
     /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all columns.
@@ -168,7 +232,7 @@ impl<B: StarkField> TraceTable<B> {
 
         Self {
             layout: TraceLayout::new(columns.len(), [0], [0]),
-            trace: ColMatrix::new(columns),
+            trace: TraceData::Columns(ColMatrix::new(columns)),
             meta: vec![],
         }
     }
@@ -184,7 +248,37 @@ impl<B: StarkField> TraceTable<B> {
     /// # Panics
     /// Panics if either `column` or `step` are out of bounds for this execution trace.
     pub fn set(&mut self, column: usize, step: usize, value: B) {
-        self.trace.set(column, step, value)
+        match &mut self.trace {
+            TraceData::Columns(trace) => trace.set(column, step, value),
+            TraceData::Rows {
+                width,
+                data,
+                columns,
+            } => {
+                assert!(column < *width, "column index {column} is out of bounds");
+                columns.take();
+                data[step * *width + column] = value;
+            }
+        }
+    }
+
+    /// Updates values of an extension field element in a single row of the execution trace.
+    ///
+    /// Coordinates of the `value` (as returned by [FieldElement::slice_as_base_elements()]) are
+    /// written into `E::EXTENSION_DEGREE` consecutive columns starting with the specified
+    /// `column`. This is the layout expected for columns of the main trace segment which hold
+    /// extension field elements (see
+    /// [AirContext::set_num_main_extension_columns()](air::AirContext::set_num_main_extension_columns)).
+    ///
+    /// # Panics
+    /// Panics if any of the columns or `step` are out of bounds for this execution trace.
+    pub fn set_extension<E>(&mut self, column: usize, step: usize, value: E)
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        for (i, &coordinate) in E::slice_as_base_elements(&[value]).iter().enumerate() {
+            self.set(column + i, step, coordinate);
+        }
     }
 
     /// Updates metadata for this execution trace to the specified vector of bytes.
@@ -229,7 +323,17 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Updates a single row in the execution trace with provided data.
     pub fn update_row(&mut self, step: usize, state: &[B]) {
-        self.trace.update_row(step, state);
+        match &mut self.trace {
+            TraceData::Columns(trace) => trace.update_row(step, state),
+            TraceData::Rows {
+                width,
+                data,
+                columns,
+            } => {
+                columns.take();
+                data[step * *width..(step + 1) * *width].copy_from_slice(state);
+            }
+        }
     }
 
     // FRAGMENTS
@@ -264,6 +368,39 @@ impl<B: StarkField> TraceTable<B> {
         self.build_fragments(fragment_length).into_par_iter()
     }
 
+    /// Fills all rows in the execution trace by filling fragments of the trace in parallel.
+    ///
+    /// The trace is broken into fragments of `fragment_length` rows each (see
+    /// [TraceTable::fragments()]), and every fragment is filled independently by executing the
+    /// provided closures as follows:
+    /// - `init` closure is used to initialize the first row of a fragment; it receives the step
+    ///   at which the fragment starts and a mutable reference to the state initialized to all
+    ///   zeros. The contents of the state are copied into the first row of the fragment after the
+    ///   closure returns.
+    /// - `update` closure is used to populate all subsequent rows of a fragment; it receives the
+    ///   step of the last updated row in the context of the entire trace and a mutable reference
+    ///   to the last updated state. The contents of the state are copied into the next row of the
+    ///   trace after the closure returns.
+    ///
+    /// Thus, when `init` produces the state of the computation at the specified step, the
+    /// resulting trace is identical to the trace filled via [TraceTable::fill()] method. When the
+    /// crate is compiled with `concurrent` feature, fragments are filled in multiple threads;
+    /// otherwise, fragments are filled one after another.
+    ///
+    /// # Panics
+    /// Panics if `fragment_length` is smaller than 2, greater than the length of the trace,
+    /// or is not a power of two.
+    pub fn fill_par<I, U>(&mut self, fragment_length: usize, init: I, update: U)
+    where
+        I: Fn(usize, &mut [B]) + Send + Sync,
+        U: Fn(usize, &mut [B]) + Send + Sync,
+    {
+        self.fragments(fragment_length).for_each(|mut fragment| {
+            let offset = fragment.offset();
+            fragment.fill(|state| init(offset, state), |i, state| update(offset + i, state));
+        });
+    }
+
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
     fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
@@ -280,22 +417,42 @@ impl<B: StarkField> TraceTable<B> {
         assert!(fragment_length.is_power_of_two(), "fragment length must be a power of 2");
         let num_fragments = self.length() / fragment_length;
 
-        let mut fragment_data = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
-        self.trace.columns_mut().for_each(|column| {
-            for (i, fragment) in column.chunks_mut(fragment_length).enumerate() {
-                fragment_data[i].push(fragment);
+        match &mut self.trace {
+            TraceData::Columns(trace) => {
+                let mut fragment_data = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
+                trace.columns_mut().for_each(|column| {
+                    for (i, fragment) in column.chunks_mut(fragment_length).enumerate() {
+                        fragment_data[i].push(fragment);
+                    }
+                });
+
+                fragment_data
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, data)| TraceTableFragment {
+                        index: i,
+                        offset: i * fragment_length,
+                        data: FragmentData::Columns(data),
+                    })
+                    .collect()
             }
-        });
-
-        fragment_data
-            .into_iter()
-            .enumerate()
-            .map(|(i, data)| TraceTableFragment {
-                index: i,
-                offset: i * fragment_length,
+            TraceData::Rows {
+                width,
                 data,
-            })
-            .collect()
+                columns,
+            } => {
+                columns.take();
+                let width = *width;
+                data.chunks_mut(fragment_length * width)
+                    .enumerate()
+                    .map(|(i, data)| TraceTableFragment {
+                        index: i,
+                        offset: i * fragment_length,
+                        data: FragmentData::Rows { width, data },
+                    })
+                    .collect()
+            }
+        }
     }
 
     // PUBLIC ACCESSORS
@@ -306,19 +463,69 @@ impl<B: StarkField> TraceTable<B> {
         self.main_trace_width()
     }
 
+    /// Returns the layout in which data of this execution trace is stored.
+    pub fn storage(&self) -> TraceStorage {
+        match self.trace {
+            TraceData::Columns(_) => TraceStorage::ColumnMajor,
+            TraceData::Rows { .. } => TraceStorage::RowMajor,
+        }
+    }
+
     /// Returns the entire trace column at the specified index.
+    ///
+    /// For a trace stored in row-major order, this transposes the entire trace.
     pub fn get_column(&self, col_idx: usize) -> &[B] {
-        self.trace.get_column(col_idx)
+        self.columns().get_column(col_idx)
     }
 
     /// Returns value of the cell in the specified column at the specified row of this trace.
     pub fn get(&self, column: usize, step: usize) -> B {
-        self.trace.get(column, step)
+        match &self.trace {
+            TraceData::Columns(trace) => trace.get(column, step),
+            TraceData::Rows { width, data, .. } => {
+                assert!(column < *width, "column index {column} is out of bounds");
+                data[step * width + column]
+            }
+        }
+    }
+
+    /// Returns the extension field element whose coordinates are stored in `E::EXTENSION_DEGREE`
+    /// consecutive columns starting with the specified `column` at the specified row of this
+    /// trace.
+    pub fn get_extension<E>(&self, column: usize, step: usize) -> E
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        let mut coordinates = Vec::with_capacity(E::EXTENSION_DEGREE);
+        for i in 0..E::EXTENSION_DEGREE {
+            coordinates.push(self.get(column + i, step));
+        }
+        E::slice_from_base_elements(&coordinates)[0]
     }
 
     /// Reads a single row from this execution trace into the provided target.
     pub fn read_row_into(&self, step: usize, target: &mut [B]) {
-        self.trace.read_row_into(step, target);
+        match &self.trace {
+            TraceData::Columns(trace) => trace.read_row_into(step, target),
+            TraceData::Rows { width, data, .. } => {
+                target.copy_from_slice(&data[step * width..(step + 1) * width]);
+            }
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the data of this trace in column-major form, transposing the trace if needed.
+    fn columns(&self) -> &ColMatrix<B> {
+        match &self.trace {
+            TraceData::Columns(trace) => trace,
+            TraceData::Rows {
+                width,
+                data,
+                columns,
+            } => columns.get_or_init(|| transpose(data, *width)),
+        }
     }
 }
 
@@ -333,7 +540,10 @@ impl<B: StarkField> Trace for TraceTable<B> {
     }
 
     fn length(&self) -> usize {
-        self.trace.num_rows()
+        match &self.trace {
+            TraceData::Columns(trace) => trace.num_rows(),
+            TraceData::Rows { width, data, .. } => data.len() / width,
+        }
     }
 
     fn meta(&self) -> &[u8] {
@@ -342,12 +552,12 @@ impl<B: StarkField> Trace for TraceTable<B> {
 
     fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
         let next_row_idx = (row_idx + 1) % self.length();
-        self.trace.read_row_into(row_idx, frame.current_mut());
-        self.trace.read_row_into(next_row_idx, frame.next_mut());
+        self.read_row_into(row_idx, frame.current_mut());
+        self.read_row_into(next_row_idx, frame.next_mut());
     }
 
     fn main_segment(&self) -> &ColMatrix<B> {
-        &self.trace
+        self.columns()
     }
 
     fn build_aux_segment<E>(
@@ -377,7 +587,12 @@ impl<B: StarkField> Trace for TraceTable<B> {
 pub struct TraceTableFragment<'a, B: StarkField> {
     index: usize,
     offset: usize,
-    data: Vec<&'a mut [B]>,
+    data: FragmentData<'a, B>,
+}
+
+enum FragmentData<'a, B: StarkField> {
+    Columns(Vec<&'a mut [B]>),
+    Rows { width: usize, data: &'a mut [B] },
 }
 
 impl<'a, B: StarkField> TraceTableFragment<'a, B> {
@@ -397,12 +612,18 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
 
     /// Returns the number of rows in this execution trace fragment.
     pub fn length(&self) -> usize {
-        self.data[0].len()
+        match &self.data {
+            FragmentData::Columns(data) => data[0].len(),
+            FragmentData::Rows { width, data } => data.len() / width,
+        }
     }
 
     /// Returns the width of the fragment (same as the width of the underlying execution trace).
     pub fn width(&self) -> usize {
-        self.data.len()
+        match &self.data {
+            FragmentData::Columns(data) => data.len(),
+            FragmentData::Rows { width, .. } => *width,
+        }
     }
 
     // DATA MUTATORS
@@ -436,8 +657,30 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
 
     /// Updates a single row in the fragment with provided data.
     pub fn update_row(&mut self, row_idx: usize, row_data: &[B]) {
-        for (column, &value) in self.data.iter_mut().zip(row_data) {
-            column[row_idx] = value;
+        match &mut self.data {
+            FragmentData::Columns(data) => {
+                for (column, &value) in data.iter_mut().zip(row_data) {
+                    column[row_idx] = value;
+                }
+            }
+            FragmentData::Rows { width, data } => {
+                data[row_idx * *width..(row_idx + 1) * *width].copy_from_slice(row_data);
+            }
         }
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Transposes trace data stored in row-major order into a column-major matrix.
+fn transpose<B: StarkField>(data: &[B], width: usize) -> ColMatrix<B> {
+    let length = data.len() / width;
+    let mut columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect::<Vec<_>>() };
+    iter_mut!(columns).enumerate().for_each(|(col_idx, column)| {
+        for (value, row) in column.iter_mut().zip(data.chunks_exact(width)) {
+            *value = row[col_idx];
+        }
+    });
+    ColMatrix::new(columns)
+}
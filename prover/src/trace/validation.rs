@@ -0,0 +1,180 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+use math::FieldElement;
+use utils::collections::Vec;
+
+// CONSTRAINT VIOLATION
+// ================================================================================================
+/// Describes a constraint of an AIR which is not satisfied by an execution trace.
+///
+/// Values of the main trace segment are elements of the base field, while values of auxiliary
+/// trace segments are elements of the extension field `E`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintViolation<E: FieldElement> {
+    /// An assertion against a column of the main trace segment is not satisfied.
+    MainAssertion {
+        /// Index of the column in the main trace segment.
+        column: usize,
+        /// Step at which the assertion is not satisfied.
+        step: usize,
+        /// Value required by the assertion.
+        expected: E::BaseField,
+        /// Value contained in the trace.
+        actual: E::BaseField,
+    },
+    /// An assertion against a column of an auxiliary trace segment is not satisfied.
+    AuxAssertion {
+        /// Index of the column among the columns of all auxiliary trace segments.
+        column: usize,
+        /// Step at which the assertion is not satisfied.
+        step: usize,
+        /// Value required by the assertion.
+        expected: E,
+        /// Value contained in the trace.
+        actual: E,
+    },
+    /// A transition constraint over the main trace segment does not evaluate to ZERO.
+    MainTransition {
+        /// Index of the constraint among main transition constraints of the AIR.
+        constraint: usize,
+        /// Step at which the constraint does not evaluate to ZERO.
+        step: usize,
+        /// Value to which the constraint evaluates.
+        evaluation: E::BaseField,
+        /// Values of the main trace segment columns at the step.
+        current: Vec<E::BaseField>,
+        /// Values of the main trace segment columns at the next step.
+        next: Vec<E::BaseField>,
+    },
+    /// A transition constraint over the main trace segment does not evaluate to ZERO, and some
+    /// columns of the main trace segment hold extension field elements.
+    MainExtensionTransition {
+        /// Index of the constraint among main transition constraints of the AIR.
+        constraint: usize,
+        /// Step at which the constraint does not evaluate to ZERO.
+        step: usize,
+        /// Value to which the constraint evaluates.
+        evaluation: E,
+        /// Values of the main evaluation frame columns at the step.
+        current: Vec<E>,
+        /// Values of the main evaluation frame columns at the next step.
+        next: Vec<E>,
+    },
+    /// A transition constraint over auxiliary trace segments does not evaluate to ZERO.
+    AuxTransition {
+        /// Index of the constraint among auxiliary transition constraints of the AIR.
+        constraint: usize,
+        /// Step at which the constraint does not evaluate to ZERO.
+        step: usize,
+        /// Value to which the constraint evaluates.
+        evaluation: E,
+        /// Values of the main trace segment columns at the step.
+        main_current: Vec<E::BaseField>,
+        /// Values of the main trace segment columns at the next step.
+        main_next: Vec<E::BaseField>,
+        /// Values of all auxiliary trace segment columns at the step.
+        aux_current: Vec<E>,
+        /// Values of all auxiliary trace segment columns at the next step.
+        aux_next: Vec<E>,
+    },
+}
+
+impl<E: FieldElement> ConstraintViolation<E> {
+    /// Returns the step at which the constraint is not satisfied.
+    pub fn step(&self) -> usize {
+        match self {
+            Self::MainAssertion { step, .. }
+            | Self::AuxAssertion { step, .. }
+            | Self::MainTransition { step, .. }
+            | Self::MainExtensionTransition { step, .. }
+            | Self::AuxTransition { step, .. } => *step,
+        }
+    }
+}
+
+impl<E: FieldElement> fmt::Display for ConstraintViolation<E> {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MainAssertion { column, step, expected, actual } => {
+                write!(f, "trace does not satisfy assertion main_trace({column}, {step}) == {expected}; the trace contains {actual}")
+            }
+            Self::AuxAssertion { column, step, expected, actual } => {
+                write!(f, "trace does not satisfy assertion aux_trace({column}, {step}) == {expected}; the trace contains {actual}")
+            }
+            Self::MainTransition { constraint, step, evaluation, current, next } => {
+                write!(f, "main transition constraint {constraint} evaluated to {evaluation} instead of ZERO at step {step}; ")?;
+                write!(f, "current row: {current:?}, next row: {next:?}")
+            }
+            Self::MainExtensionTransition { constraint, step, evaluation, current, next } => {
+                write!(f, "main transition constraint {constraint} evaluated to {evaluation} instead of ZERO at step {step}; ")?;
+                write!(f, "current row: {current:?}, next row: {next:?}")
+            }
+            Self::AuxTransition { constraint, step, evaluation, main_current, main_next, aux_current, aux_next } => {
+                write!(f, "auxiliary transition constraint {constraint} evaluated to {evaluation} instead of ZERO at step {step}; ")?;
+                write!(f, "current main row: {main_current:?}, next main row: {main_next:?}, ")?;
+                write!(f, "current auxiliary row: {aux_current:?}, next auxiliary row: {aux_next:?}")
+            }
+        }
+    }
+}
+
+// VALIDATION REPORT
+// ================================================================================================
+/// A report describing all constraints of an AIR which are not satisfied by an execution trace.
+///
+/// A report is built via [Trace::check_constraints()](crate::Trace::check_constraints) or
+/// [Prover::validate_trace()](crate::Prover::validate_trace). Violations are listed in the order
+/// in which they were found: first, violated assertions against the main trace segment, then
+/// violated assertions against auxiliary trace segments, and finally, violated transition
+/// constraints ordered by step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport<E: FieldElement> {
+    violations: Vec<ConstraintViolation<E>>,
+}
+
+impl<E: FieldElement> ValidationReport<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new report containing the specified violations.
+    pub fn new(violations: Vec<ConstraintViolation<E>>) -> Self {
+        Self { violations }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the trace satisfies all constraints of the AIR.
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Returns all constraint violations found in the trace.
+    pub fn violations(&self) -> &[ConstraintViolation<E>] {
+        &self.violations
+    }
+
+    /// Consumes this report and returns all constraint violations found in the trace.
+    pub fn into_violations(self) -> Vec<ConstraintViolation<E>> {
+        self.violations
+    }
+}
+
+impl<E: FieldElement> fmt::Display for ValidationReport<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.violations.len() {
+            0 => write!(f, "trace satisfies all constraints"),
+            n => {
+                write!(f, "trace violates {n} constraint(s)")?;
+                for violation in self.violations.iter() {
+                    write!(f, "\n- {violation}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    matrix::ColMatrix, AuxTraceRandElements, CancellationToken, CompositionPoly,
+    CompositionPolyTrace, ConstraintCommitment, ConstraintCompositionCoefficients,
+    PeriodicColumnCache, ProofOptions, Prover, ProverError, ProvingPhase, StarkDomain, TraceInfo,
+    TracePolyTable,
+};
+use air::Air;
+use crypto::{TranscriptCoin, TranscriptRecorder};
+use math::FieldElement;
+
+// TRANSCRIPT PROVER
+// ================================================================================================
+
+/// A prover which delegates all work to the wrapped prover while recording all operations
+/// performed on the public coin of the wrapped prover.
+pub(crate) struct TranscriptProver<'p, P: Prover> {
+    prover: &'p P,
+    recorder: TranscriptRecorder,
+}
+
+impl<'p, P: Prover> TranscriptProver<'p, P> {
+    pub fn new(prover: &'p P) -> Self {
+        Self {
+            prover,
+            recorder: TranscriptRecorder::new(),
+        }
+    }
+
+    /// Returns the recorder of the public coin transcript.
+    pub fn recorder(&self) -> &TranscriptRecorder {
+        &self.recorder
+    }
+}
+
+impl<'p, P: Prover> Prover for TranscriptProver<'p, P> {
+    type BaseField = P::BaseField;
+    type Air = P::Air;
+    type Trace = P::Trace;
+    type HashFn = P::HashFn;
+    type RandomCoin = TranscriptCoin<P::RandomCoin>;
+    type TraceLde<E>
+        = P::TraceLde<E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+    type ConstraintEvaluator<'a, E>
+        = P::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> <Self::Air as Air>::PublicInputs {
+        self.prover.get_pub_inputs(trace)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.prover.options()
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.prover.new_trace_lde(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.prover.new_evaluator(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn on_phase_start(&self, phase: ProvingPhase) {
+        self.prover.on_phase_start(phase);
+    }
+
+    fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.prover.cancellation_token()
+    }
+
+    fn low_memory_mode(&self) -> bool {
+        self.prover.low_memory_mode()
+    }
+
+    fn lde_directory(&self) -> Option<std::path::PathBuf> {
+        self.prover.lde_directory()
+    }
+
+    fn rng_seed(&self) -> Option<[u8; 32]> {
+        self.prover.rng_seed()
+    }
+
+    fn salt_transcript(&self) -> bool {
+        self.prover.salt_transcript()
+    }
+
+    fn periodic_column_cache(&self) -> Option<&PeriodicColumnCache<Self::BaseField>> {
+        self.prover.periodic_column_cache()
+    }
+
+    fn new_public_coin(&self, seed: &[Self::BaseField]) -> Self::RandomCoin {
+        self.recorder.wrap(seed, self.prover.new_public_coin(seed))
+    }
+
+    fn build_constraint_commitment<E>(
+        &self,
+        composition_poly_trace: CompositionPolyTrace<E>,
+        num_trace_poly_columns: usize,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> Result<(ConstraintCommitment<E, Self::HashFn>, CompositionPoly<E>), ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.prover.build_constraint_commitment(
+            composition_poly_trace,
+            num_trace_poly_columns,
+            domain,
+        )
+    }
+}
@@ -0,0 +1,364 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate exposes Winterfell prover and verifier to Python via [PyO3](https://pyo3.rs).
+//!
+//! The Python module `winterfell` built from this crate provides:
+//! * `Proof` class, which can be deserialized from bytes and provides information about a proof
+//!   (e.g., its size, proof options, and security level).
+//! * `Example` class, which can instantiate, prove, and verify any of the examples from the
+//!   examples crate using the same arguments as the `winterfell` command line tool.
+//! * `prove()` and `verify()` functions, which work with serialized proofs and public inputs for
+//!   a fixed set of AIRs (the same ones as supported by the `winterfell-ffi` crate); `verify()`
+//!   dispatches a proof to the right AIR using the AIR identifier recorded in the proof.
+
+use examples::{fibonacci::fib2, fibonacci::fib_small, fibonacci::mulfib2, ExampleOptions};
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+    types::PyBytes,
+};
+use structopt::StructOpt;
+use winterfell::{
+    crypto::{
+        hashers::{Blake3_192, Blake3_256, GriffinJive64_256, Rp64_256, RpJive64_256, Sha3_256},
+        DefaultRandomCoin, Hasher,
+    },
+    math::fields::f128,
+    AcceptableOptions, Air, AirRegistry, ProofOptions, Prover, Serializable, StarkProof,
+};
+
+type Blake3 = Blake3_256<f128::BaseElement>;
+
+create_exception!(
+    winterfell,
+    ProvingError,
+    PyException,
+    "Raised when the prover fails to generate a proof."
+);
+create_exception!(
+    winterfell,
+    VerificationError,
+    PyException,
+    "Raised when a proof is malformed or fails verification."
+);
+
+// MODULE
+// ================================================================================================
+
+#[pymodule]
+#[pyo3(name = "winterfell")]
+fn winterfell_py(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("AIR_FIB2", fib2::FibAir::AIR_ID)?;
+    m.add("AIR_FIB_SMALL", fib_small::FibSmall::AIR_ID)?;
+    m.add("AIR_MULFIB2", mulfib2::MulFib2Air::AIR_ID)?;
+    m.add("ProvingError", py.get_type::<ProvingError>())?;
+    m.add("VerificationError", py.get_type::<VerificationError>())?;
+    m.add_class::<Proof>()?;
+    m.add_class::<Example>()?;
+    m.add_function(wrap_pyfunction!(prove, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    Ok(())
+}
+
+// PROOF
+// ================================================================================================
+
+/// A STARK proof.
+///
+/// The hash function used to generate a proof is not recorded in the proof, and thus, methods
+/// which compute security level of the proof take the name of the hash function as an argument
+/// (one of `blake3_192`, `blake3_256`, `sha3_256`, `rp64_256`, `rp_jive64_256`, or
+/// `griffin_jive64_256`).
+#[pyclass(module = "winterfell")]
+#[derive(Clone)]
+pub struct Proof {
+    proof: StarkProof,
+}
+
+#[pymethods]
+impl Proof {
+    /// Deserializes a proof from the specified bytes.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        StarkProof::from_bytes(data)
+            .map(|proof| Proof { proof })
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// Serializes the proof into bytes.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.proof.to_bytes())
+    }
+
+    /// Size of the serialized proof in bytes.
+    #[getter]
+    fn size(&self) -> usize {
+        self.proof.to_bytes().len()
+    }
+
+    /// Proof options in the compact format (e.g., `q=54,b=8,g=16,ext=quad,fri=4/255`).
+    #[getter]
+    fn options(&self) -> String {
+        self.proof.options().to_string()
+    }
+
+    #[getter]
+    fn num_queries(&self) -> usize {
+        self.proof.options().num_queries()
+    }
+
+    /// Number of distinct positions queried by the verifier.
+    #[getter]
+    fn num_unique_queries(&self) -> u16 {
+        self.proof.num_unique_queries
+    }
+
+    #[getter]
+    fn blowup_factor(&self) -> usize {
+        self.proof.options().blowup_factor()
+    }
+
+    #[getter]
+    fn grinding_factor(&self) -> u32 {
+        self.proof.options().grinding_factor()
+    }
+
+    /// Degree of the field extension (1 if no extension is used).
+    #[getter]
+    fn field_extension(&self) -> u32 {
+        self.proof.options().field_extension().degree()
+    }
+
+    /// Identifier of the AIR the proof was generated for.
+    #[getter]
+    fn air_id(&self) -> u32 {
+        self.proof.context.air_id()
+    }
+
+    #[getter]
+    fn trace_length(&self) -> usize {
+        self.proof.trace_length()
+    }
+
+    /// Total number of columns in the execution trace, including auxiliary columns.
+    #[getter]
+    fn trace_width(&self) -> usize {
+        let layout = self.proof.trace_layout();
+        layout.main_trace_width() + layout.aux_trace_width()
+    }
+
+    #[getter]
+    fn lde_domain_size(&self) -> usize {
+        self.proof.lde_domain_size()
+    }
+
+    /// Returns security level of the proof (in bits) for the specified hash function; the
+    /// conjectured security level is returned unless `conjectured` is false.
+    #[pyo3(signature = (hash_fn, conjectured = true))]
+    fn security_level(&self, hash_fn: &str, conjectured: bool) -> PyResult<u32> {
+        let (conjectured_security, proven_security, _) = get_security(&self.proof, hash_fn)?;
+        Ok(if conjectured {
+            conjectured_security
+        } else {
+            proven_security
+        })
+    }
+
+    /// Returns a human-readable breakdown of the security level of the proof for the specified
+    /// hash function.
+    fn security_report(&self, hash_fn: &str) -> PyResult<String> {
+        get_security(&self.proof, hash_fn).map(|(_, _, report)| report)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Proof(air_id={}, trace={}x{}, options={}, size={})",
+            self.air_id(),
+            self.trace_width(),
+            self.trace_length(),
+            self.options(),
+            self.size()
+        )
+    }
+}
+
+// EXAMPLE
+// ================================================================================================
+
+/// One of the examples from the examples crate.
+///
+/// An example is specified in the same way as for the `winterfell` command line tool, e.g.,
+/// `Example("fib-small -n 1024", options="q=28,b=8,g=16,ext=quad,fri=8/31,hash=rp64_256")`.
+#[pyclass(module = "winterfell", unsendable)]
+pub struct Example {
+    options: ExampleOptions,
+    example: Box<dyn examples::Example>,
+}
+
+#[pymethods]
+impl Example {
+    #[new]
+    #[pyo3(signature = (command, options = None))]
+    fn new(command: &str, options: Option<&str>) -> PyResult<Self> {
+        let mut args = vec!["winterfell"];
+        if let Some(options) = options {
+            args.extend(["--options", options]);
+        }
+        args.extend(command.split_whitespace());
+
+        let options = ExampleOptions::from_iter_safe(args)
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        let example = examples::get_example(&options).map_err(PyValueError::new_err)?;
+        Ok(Example { options, example })
+    }
+
+    /// Generates a proof for the example.
+    fn prove(&self) -> Proof {
+        Proof {
+            proof: self.example.prove(),
+        }
+    }
+
+    /// Verifies the proof against the public inputs of the example.
+    ///
+    /// Raises `VerificationError` if the verification fails.
+    fn verify(&self, proof: &Proof) -> PyResult<()> {
+        self.example
+            .verify(proof.proof.clone())
+            .map_err(|err| VerificationError::new_err(err.to_string()))
+    }
+
+    /// Verifies the proof against a modified version of the public inputs of the example; this
+    /// is expected to fail for a valid proof.
+    ///
+    /// Raises `VerificationError` if the verification fails.
+    fn verify_with_wrong_inputs(&self, proof: &Proof) -> PyResult<()> {
+        self.example
+            .verify_with_wrong_inputs(proof.proof.clone())
+            .map_err(|err| VerificationError::new_err(err.to_string()))
+    }
+
+    /// Returns security level of the proof (in bits) for the hash function of the example.
+    #[pyo3(signature = (proof, conjectured = true))]
+    fn security_level(&self, proof: &Proof, conjectured: bool) -> usize {
+        self.options.get_proof_security_level(&proof.proof, conjectured)
+    }
+}
+
+// PROVER AND VERIFIER
+// ================================================================================================
+
+/// Generates a proof for computing a sequence of the specified length using the AIR with the
+/// specified identifier, and returns a tuple of the serialized proof and the serialized public
+/// inputs.
+///
+/// Raises `ValueError` if the arguments are invalid, and `ProvingError` if the prover fails.
+#[pyfunction]
+#[pyo3(signature = (air_id, sequence_length, options = ""))]
+fn prove<'py>(
+    py: Python<'py>,
+    air_id: u32,
+    sequence_length: usize,
+    options: &str,
+) -> PyResult<(&'py PyBytes, &'py PyBytes)> {
+    let options = options
+        .parse::<ProofOptions>()
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+    if !sequence_length.is_power_of_two() || sequence_length < 16 {
+        return Err(PyValueError::new_err(format!(
+            "sequence length must be a power of two and at least 16, but was {sequence_length}"
+        )));
+    }
+
+    let (proof, pub_inputs) = match air_id {
+        id if id == fib2::FibAir::AIR_ID => {
+            let prover = fib2::FibProver::<Blake3>::new(options);
+            let trace = prover.build_trace(sequence_length);
+            prove_trace(prover, trace)?
+        }
+        id if id == fib_small::FibSmall::AIR_ID => {
+            let prover = fib_small::FibSmallProver::<Rp64_256>::new(options);
+            let trace = prover.build_trace(sequence_length);
+            prove_trace(prover, trace)?
+        }
+        id if id == mulfib2::MulFib2Air::AIR_ID => {
+            let prover = mulfib2::MulFib2Prover::<Blake3>::new(options);
+            let trace = prover.build_trace(sequence_length);
+            prove_trace(prover, trace)?
+        }
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "AIR with identifier {air_id} is not supported"
+            )))
+        }
+    };
+
+    Ok((PyBytes::new(py, &proof), PyBytes::new(py, &pub_inputs)))
+}
+
+/// Verifies the serialized proof against the serialized public inputs; the proof is accepted
+/// only if it provides at least `min_security_level` bits of conjectured security.
+///
+/// Raises `VerificationError` if the proof is malformed, was generated for an unsupported AIR,
+/// or fails verification.
+#[pyfunction]
+#[pyo3(signature = (proof, pub_inputs, min_security_level = 0))]
+fn verify(proof: &[u8], pub_inputs: &[u8], min_security_level: u32) -> PyResult<()> {
+    let acceptable_options = || AcceptableOptions::MinConjecturedSecurity(min_security_level);
+    let mut registry = AirRegistry::new();
+    registry.register::<fib2::FibAir, Blake3, DefaultRandomCoin<Blake3>>(acceptable_options());
+    registry.register::<fib_small::FibSmall, Rp64_256, DefaultRandomCoin<Rp64_256>>(
+        acceptable_options(),
+    );
+    registry
+        .register::<mulfib2::MulFib2Air, Blake3, DefaultRandomCoin<Blake3>>(acceptable_options());
+
+    registry
+        .verify_any(proof, pub_inputs)
+        .map_err(|err| VerificationError::new_err(err.to_string()))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Generates a proof for the specified trace and returns the serialized proof and public inputs.
+fn prove_trace<P: Prover>(prover: P, trace: P::Trace) -> PyResult<(Vec<u8>, Vec<u8>)>
+where
+    <P::Air as Air>::PublicInputs: Serializable,
+{
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).map_err(|err| ProvingError::new_err(err.to_string()))?;
+    Ok((proof.to_bytes(), pub_inputs.to_bytes()))
+}
+
+/// Returns the conjectured and the proven security levels of the proof together with a
+/// human-readable security report, assuming the proof was generated using the hash function with
+/// the specified name.
+fn get_security(proof: &StarkProof, hash_fn: &str) -> PyResult<(u32, u32, String)> {
+    fn security<H: Hasher>(proof: &StarkProof) -> (u32, u32, String) {
+        let report = proof.security_report::<H>();
+        (
+            report.conjectured.security_level,
+            report.proven.security_level,
+            report.to_string(),
+        )
+    }
+
+    Ok(match hash_fn {
+        "blake3_192" => security::<Blake3_192<f128::BaseElement>>(proof),
+        "blake3_256" => security::<Blake3>(proof),
+        "sha3_256" => security::<Sha3_256<f128::BaseElement>>(proof),
+        "rp64_256" => security::<Rp64_256>(proof),
+        "rp_jive64_256" => security::<RpJive64_256>(proof),
+        "griffin_jive64_256" => security::<GriffinJive64_256>(proof),
+        val => {
+            return Err(PyValueError::new_err(format!(
+                "'{val}' is not a valid hash function option"
+            )))
+        }
+    })
+}
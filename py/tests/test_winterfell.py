@@ -0,0 +1,51 @@
+# Copyright (c) Facebook, Inc. and its affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+import pytest
+import winterfell
+
+OPTIONS = "q=28,b=8,g=0,ext=none,fri=4/31"
+
+
+def test_example_prove_verify():
+    example = winterfell.Example("fib-small -n 64", options=OPTIONS + ",hash=rp64_256")
+    proof = example.prove()
+    example.verify(proof)
+    with pytest.raises(winterfell.VerificationError):
+        example.verify_with_wrong_inputs(proof)
+    assert example.security_level(proof) == proof.security_level("rp64_256")
+
+
+def test_proof_introspection():
+    proof_bytes, _ = winterfell.prove(winterfell.AIR_FIB2, 64, OPTIONS)
+    proof = winterfell.Proof.from_bytes(proof_bytes)
+    assert proof.size == len(proof_bytes)
+    assert proof.to_bytes() == proof_bytes
+    assert proof.options == OPTIONS
+    assert proof.air_id == winterfell.AIR_FIB2
+    assert (proof.trace_width, proof.trace_length) == (2, 32)
+    assert proof.lde_domain_size == 256
+    assert proof.security_level("blake3_256", conjectured=False) <= proof.security_level("blake3_256")
+    with pytest.raises(ValueError):
+        proof.security_level("md5")
+
+
+def test_prove_verify_bytes():
+    for air_id in [winterfell.AIR_FIB2, winterfell.AIR_FIB_SMALL, winterfell.AIR_MULFIB2]:
+        proof, pub_inputs = winterfell.prove(air_id, 64, OPTIONS)
+        winterfell.verify(proof, pub_inputs)
+        with pytest.raises(winterfell.VerificationError):
+            winterfell.verify(proof, pub_inputs, min_security_level=100)
+        with pytest.raises(winterfell.VerificationError):
+            winterfell.verify(proof, bytes([pub_inputs[0] ^ 1]) + pub_inputs[1:])
+
+
+def test_invalid_arguments():
+    with pytest.raises(ValueError):
+        winterfell.prove(0, 64, OPTIONS)
+    with pytest.raises(ValueError):
+        winterfell.prove(winterfell.AIR_FIB2, 63, OPTIONS)
+    with pytest.raises(ValueError):
+        winterfell.Example("no-such-example")
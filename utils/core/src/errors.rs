@@ -32,3 +32,20 @@ impl fmt::Display for DeserializationError {
         }
     }
 }
+
+impl DeserializationError {
+    /// Returns a stable code identifying the kind of this error.
+    ///
+    /// Codes have the form `deserialization.<kind>` and are stable across releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidValue(_) => "deserialization.invalid_value",
+            Self::UnexpectedEOF => "deserialization.unexpected_eof",
+            Self::UnconsumedBytes => "deserialization.unconsumed_bytes",
+            Self::UnknownError(_) => "deserialization.unknown",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeserializationError {}
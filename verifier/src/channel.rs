@@ -11,7 +11,7 @@ use air::{
 use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
 use fri::VerifierChannel as FriVerifierChannel;
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, string::ToString};
+use utils::{collections::Vec, DeserializationError};
 
 // VERIFIER CHANNEL
 // ================================================================================================
@@ -34,9 +34,10 @@ pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::Base
     fri_layer_queries: Vec<Vec<E>>,
     fri_remainder: Option<Vec<E>>,
     fri_num_partitions: usize,
+    fri_layer_nonces: Vec<u64>,
     // out-of-domain frame
-    ood_trace_frame: Option<TraceOodFrame<E>>,
-    ood_constraint_evaluations: Option<Vec<E>>,
+    ood_trace_frames: Option<Vec<TraceOodFrame<E>>>,
+    ood_constraint_evaluations: Option<Vec<Vec<E>>>,
     // query proof-of-work
     pow_nonce: u64,
 }
@@ -58,6 +59,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             ood_frame,
             fri_proof,
             pow_nonce,
+            metadata: _,
         } = proof;
 
         // make sure AIR and proof base fields are the same
@@ -75,7 +77,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         // --- parse commitments ------------------------------------------------------------------
         let (trace_roots, constraint_root, fri_roots) = commitments
             .parse::<H>(num_trace_segments, fri_options.num_fri_layers(lde_domain_size))
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+            .map_err(VerifierError::ProofDeserializationError)?;
 
         // --- parse trace and constraint queries -------------------------------------------------
         let trace_queries = TraceQueries::new(trace_queries, air, num_unique_queries as usize)?;
@@ -84,19 +86,28 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
 
         // --- parse FRI proofs -------------------------------------------------------------------
         let fri_num_partitions = fri_proof.num_partitions();
-        let fri_remainder = fri_proof
-            .parse_remainder()
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let fri_layer_nonces = fri_proof.layer_nonces().to_vec();
+        let fri_remainder =
+            fri_proof.parse_remainder().map_err(VerifierError::ProofDeserializationError)?;
         let (fri_layer_queries, fri_layer_proofs) = fri_proof
             .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+            .map_err(VerifierError::ProofDeserializationError)?;
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
+        // the frame contains evaluations at all out-of-domain points one point after another;
+        // every trace frame consists of two rows of the trace
+        let num_ood_points = air.options().num_ood_points();
         let (ood_trace_evaluations, ood_constraint_evaluations) = ood_frame
-            .parse(main_trace_width, aux_trace_width, constraint_frame_width)
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let ood_trace_frame =
-            TraceOodFrame::new(ood_trace_evaluations, main_trace_width, aux_trace_width);
+            .parse(main_trace_width, aux_trace_width, constraint_frame_width, num_ood_points)
+            .map_err(VerifierError::ProofDeserializationError)?;
+        let ood_trace_frames = ood_trace_evaluations
+            .chunks(2 * (main_trace_width + aux_trace_width))
+            .map(|values| TraceOodFrame::new(values.to_vec(), main_trace_width, aux_trace_width))
+            .collect();
+        let ood_constraint_evaluations = ood_constraint_evaluations
+            .chunks(constraint_frame_width)
+            .map(|evaluations| evaluations.to_vec())
+            .collect();
 
         Ok(VerifierChannel {
             // trace queries
@@ -111,8 +122,9 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             fri_layer_queries,
             fri_remainder: Some(fri_remainder),
             fri_num_partitions,
+            fri_layer_nonces,
             // out-of-domain evaluation
-            ood_trace_frame: Some(ood_trace_frame),
+            ood_trace_frames: Some(ood_trace_frames),
             ood_constraint_evaluations: Some(ood_constraint_evaluations),
             // query seed
             pow_nonce,
@@ -135,18 +147,19 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         self.constraint_root
     }
 
-    /// Returns trace polynomial evaluations at out-of-domain points z and z * g, where g is the
-    /// generator of the LDE domain.
+    /// Returns trace polynomial evaluations at points z and z * g for every out-of-domain point
+    /// z, where g is the generator of the LDE domain; frames are returned in the order in which
+    /// the out-of-domain points were drawn.
     ///
     /// For computations requiring multiple trace segments, evaluations of auxiliary trace
     /// polynomials are also included.
-    pub fn read_ood_trace_frame(&mut self) -> TraceOodFrame<E> {
-        self.ood_trace_frame.take().expect("already read")
+    pub fn read_ood_trace_frames(&mut self) -> Vec<TraceOodFrame<E>> {
+        self.ood_trace_frames.take().expect("already read")
     }
 
-    /// Returns evaluations of composition polynomial columns at z^m, where z is the out-of-domain
-    /// point, and m is the number of composition polynomial columns.
-    pub fn read_ood_constraint_evaluations(&mut self) -> Vec<E> {
+    /// Returns evaluations of composition polynomial columns at z^m for every out-of-domain
+    /// point z, where m is the number of composition polynomial columns.
+    pub fn read_ood_constraint_evaluations(&mut self) -> Vec<Vec<E>> {
         self.ood_constraint_evaluations.take().expect("already read")
     }
 
@@ -155,8 +168,9 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         self.pow_nonce
     }
 
-    /// Returns trace states at the specified positions of the LDE domain. This also checks if
-    /// the trace states are valid against the trace commitment sent by the prover.
+    /// Returns trace states at the specified positions of the LDE domain. If `check_commitment`
+    /// is true, this also checks if the trace states are valid against the trace commitment sent
+    /// by the prover.
     ///
     /// For computations requiring multiple trace segments, trace states for auxiliary segments
     /// are also included as the second value of the returned tuple (trace states for all auxiliary
@@ -165,26 +179,50 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     pub fn read_queried_trace_states(
         &mut self,
         positions: &[usize],
+        check_commitment: bool,
     ) -> Result<(Table<E::BaseField>, Option<Table<E>>), VerifierError> {
         let queries = self.trace_queries.take().expect("already read");
 
+        // the number of queried states must match the number of positions even if the commitment
+        // is not checked, as otherwise the states cannot correspond to the positions
+        if queries.main_states.num_rows() != positions.len() {
+            return Err(VerifierError::TraceQueryDoesNotMatchCommitment(0));
+        }
+        if let Some(ref aux_states) = queries.aux_states {
+            if aux_states.num_rows() != positions.len() {
+                return Err(VerifierError::TraceQueryDoesNotMatchCommitment(1));
+            }
+        }
+        if !check_commitment {
+            return Ok((queries.main_states, queries.aux_states));
+        }
+
         // make sure the states included in the proof correspond to the trace commitment
-        for (root, proof) in self.trace_roots.iter().zip(queries.query_proofs.iter()) {
+        for (i, (root, proof)) in
+            self.trace_roots.iter().zip(queries.query_proofs.iter()).enumerate()
+        {
             MerkleTree::verify_batch(root, positions, proof)
-                .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
+                .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment(i))?;
         }
 
         Ok((queries.main_states, queries.aux_states))
     }
 
-    /// Returns constraint evaluations at the specified positions of the LDE domain. This also
-    /// checks if the constraint evaluations are valid against the constraint commitment sent by
-    /// the prover.
+    /// Returns constraint evaluations at the specified positions of the LDE domain. If
+    /// `check_commitment` is true, this also checks if the constraint evaluations are valid
+    /// against the constraint commitment sent by the prover.
     pub fn read_constraint_evaluations(
         &mut self,
         positions: &[usize],
+        check_commitment: bool,
     ) -> Result<Table<E>, VerifierError> {
         let queries = self.constraint_queries.take().expect("already read");
+        if queries.evaluations.num_rows() != positions.len() {
+            return Err(VerifierError::ConstraintQueryDoesNotMatchCommitment);
+        }
+        if !check_commitment {
+            return Ok(queries.evaluations);
+        }
 
         MerkleTree::verify_batch(&self.constraint_root, positions, &queries.query_proofs)
             .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
@@ -222,6 +260,10 @@ where
     fn take_fri_remainder(&mut self) -> Vec<E> {
         self.fri_remainder.take().expect("already read")
     }
+
+    fn read_fri_layer_nonces(&mut self) -> Vec<u64> {
+        core::mem::take(&mut self.fri_layer_nonces)
+    }
 }
 
 // TRACE QUERIES
@@ -261,8 +303,8 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
         let (main_segment_query_proofs, main_segment_states) = main_segment_queries
             .parse::<H, E::BaseField>(air.lde_domain_size(), num_queries, main_segment_width)
             .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "main trace segment query deserialization failed: {err}"
+                VerifierError::ProofDeserializationError(DeserializationError::InvalidValue(
+                    format!("main trace segment query deserialization failed: {err}"),
                 ))
             })?;
 
@@ -279,9 +321,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
                 let (segment_query_proof, segment_trace_states) = segment_queries
                     .parse::<H, E>(air.lde_domain_size(), num_queries, segment_width)
                     .map_err(|err| {
-                        VerifierError::ProofDeserializationError(format!(
-                            "auxiliary trace segment query deserialization failed: {err}"
-                        ))
+                        VerifierError::ProofDeserializationError(
+                            DeserializationError::InvalidValue(format!(
+                                "auxiliary trace segment query deserialization failed: {err}"
+                            )),
+                        )
                     })?;
 
                 query_proofs.push(segment_query_proof);
@@ -326,8 +370,8 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
         let (query_proofs, evaluations) = queries
             .parse::<H, E>(air.lde_domain_size(), num_queries, constraint_frame_width)
             .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "constraint evaluation query deserialization failed: {err}"
+                VerifierError::ProofDeserializationError(DeserializationError::InvalidValue(
+                    format!("constraint evaluation query deserialization failed: {err}"),
                 ))
             })?;
 
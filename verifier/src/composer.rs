@@ -13,15 +13,16 @@ use utils::collections::Vec;
 pub struct DeepComposer<E: FieldElement> {
     cc: DeepCompositionCoefficients<E>,
     x_coordinates: Vec<E>,
-    z: [E; 2],
+    z: Vec<[E; 2]>,
 }
 
 impl<E: FieldElement> DeepComposer<E> {
-    /// Creates a new composer for computing DEEP composition polynomial values.
+    /// Creates a new composer for computing DEEP composition polynomial values at the specified
+    /// out-of-domain points.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         air: &A,
         query_positions: &[usize],
-        z: E,
+        z: &[E],
         cc: DeepCompositionCoefficients<E>,
     ) -> Self {
         // compute LDE domain coordinates for all query positions
@@ -32,10 +33,11 @@ impl<E: FieldElement> DeepComposer<E> {
             .map(|&p| E::from(g_lde.exp_vartime((p as u64).into()) * domain_offset))
             .collect();
 
+        let g_trace = E::from(air.trace_domain_generator());
         DeepComposer {
             cc,
             x_coordinates,
-            z: [z, z * E::from(air.trace_domain_generator())],
+            z: z.iter().map(|&z| [z, z * g_trace]).collect(),
         }
     }
 
@@ -50,84 +52,80 @@ impl<E: FieldElement> DeepComposer<E> {
     ///   T(x) = sum((T'_i(x) + T''_i(x)) * cc_i) for all i, where cc_i is the coefficient for
     ///   for the random linear combination drawn from the public coin.
     ///
+    /// Since the combination is linear, T(x) is computed as
+    /// (S(x) - S(z)) / (x - z) + (S(x) - S(z * g)) / (x - z * g), where S(x) = sum(T_i(x) * cc_i).
+    /// S(x) is computed once for every query, and S(z) and S(z * g) are computed once for every
+    /// out-of-domain point; thus, every queried value is multiplied by a coefficient only once.
+    /// When there are several out-of-domain points, T(x) is computed for every point, and the
+    /// results are added together.
+    ///
     /// Note that values of T_i(z) and T_i(z * g) are received from the prover and passed into
-    /// this function via the `ood_main_frame` and `ood_aux_frame` parameters.
+    /// this function via the `ood_main_frames` and `ood_aux_frames` parameters; these parameters
+    /// contain one frame per out-of-domain point.
     pub fn compose_trace_columns(
         &self,
         queried_main_trace_states: Table<E::BaseField>,
         queried_aux_trace_states: Option<Table<E>>,
-        ood_main_frame: EvaluationFrame<E>,
-        ood_aux_frame: Option<EvaluationFrame<E>>,
+        ood_main_frames: Vec<EvaluationFrame<E>>,
+        ood_aux_frames: Vec<Option<EvaluationFrame<E>>>,
     ) -> Vec<E> {
-        let ood_main_trace_states = [ood_main_frame.current(), ood_main_frame.next()];
-
-        // compose columns of of the main trace segment; we do this separately for numerators of
-        // each query; we also track common denominator for each query separately; this way we can
-        // use a batch inversion in the end.
-        let n = queried_main_trace_states.num_rows();
-        let mut result_num = Vec::<E>::with_capacity(n);
-        let mut result_den = Vec::<E>::with_capacity(n);
-
-        for ((_, row), &x) in (0..n).zip(queried_main_trace_states.rows()).zip(&self.x_coordinates)
-        {
-            let mut t1_num = E::ZERO;
-            let mut t2_num = E::ZERO;
-
-            for (i, &value) in row.iter().enumerate() {
-                let value = E::from(value);
-                // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t1_num += (value - ood_main_trace_states[0][i]) * self.cc.trace[i];
-
-                // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t2_num += (value - ood_main_trace_states[1][i]) * self.cc.trace[i];
+        assert_eq!(queried_main_trace_states.num_rows(), self.x_coordinates.len());
+        assert_eq!(ood_main_frames.len(), self.z.len());
+        assert_eq!(ood_aux_frames.len(), self.z.len());
+
+        // compute S(x) for every query; columns of the main trace segment are over the base
+        // field, and thus, their values are multiplied by the coefficients via mul_base(). we
+        // define the offset for the coefficients of the auxiliary segments here because
+        // composition of the main trace columns consumes some number of coefficients.
+        let cc_offset = queried_main_trace_states.num_columns();
+        let (main_cc, aux_cc) = self.cc.trace.split_at(cc_offset);
+        let mut combined_rows = queried_main_trace_states
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .zip(main_cc)
+                    .fold(E::ZERO, |acc, (&value, &k)| acc + k.mul_base(value))
+            })
+            .collect::<Vec<_>>();
+        if let Some(queried_aux_trace_states) = queried_aux_trace_states {
+            for (combined, row) in combined_rows.iter_mut().zip(queried_aux_trace_states.rows()) {
+                *combined += linear_combination(row, aux_cc);
             }
-            // compute the common denominator as (x - z) * (x - z * g)
-            let t1_den = x - self.z[0];
-            let t2_den = x - self.z[1];
-            result_den.push(t1_den * t2_den);
-
-            // add the numerators of T'_i(x) and T''_i(x) together; we can do this because later on
-            // we'll use the common denominator computed above.
-            result_num.push(t1_num * t2_den + t2_num * t1_den);
         }
 
-        // if the trace has auxiliary segments, compose columns from these segments as well; we
-        // also do this separately for numerators and denominators.
-        if let Some(queried_aux_trace_states) = queried_aux_trace_states {
-            let ood_aux_frame = ood_aux_frame.expect("missing auxiliary OOD frame");
-            let ood_aux_trace_states = [ood_aux_frame.current(), ood_aux_frame.next()];
-
-            // we define this offset here because composition of the main trace columns has
-            // consumed some number of composition coefficients already.
-            let cc_offset = queried_main_trace_states.num_columns();
-
-            for ((j, row), &x) in
-                (0..n).zip(queried_aux_trace_states.rows()).zip(&self.x_coordinates)
-            {
-                let mut t1_num = E::ZERO;
-                let mut t2_num = E::ZERO;
-                for (i, &value) in row.iter().enumerate() {
-                    // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t1_num += (value - ood_aux_trace_states[0][i]) * self.cc.trace[cc_offset + i];
-
-                    // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t2_num += (value - ood_aux_trace_states[1][i]) * self.cc.trace[cc_offset + i];
+        // compute S(z) and S(z * g) for every out-of-domain point from the values of the
+        // out-of-domain frames
+        let ood_values = ood_main_frames
+            .iter()
+            .zip(ood_aux_frames.iter())
+            .map(|(main_frame, aux_frame)| {
+                let mut s_at_z = linear_combination(main_frame.current(), main_cc);
+                let mut s_at_next_z = linear_combination(main_frame.next(), main_cc);
+                if let Some(aux_frame) = aux_frame {
+                    s_at_z += linear_combination(aux_frame.current(), aux_cc);
+                    s_at_next_z += linear_combination(aux_frame.next(), aux_cc);
                 }
-
-                // compute the common denominators (x - z) and (x - z * g), and use the to aggregate
-                // numerators into the common numerator computed for the main trace of this query
-                let t1_den = x - self.z[0];
-                let t2_den = x - self.z[1];
-                result_num[j] += t1_num * t2_den + t2_num * t1_den;
+                [s_at_z, s_at_next_z]
+            })
+            .collect::<Vec<_>>();
+
+        // compute the numerators for all queries and points, and the common denominator
+        // (x - z) * (x - z * g) for every query and every point; this way we can use a batch
+        // inversion in the end.
+        let num_values = combined_rows.len() * self.z.len();
+        let mut result_num = Vec::<E>::with_capacity(num_values);
+        let mut result_den = Vec::<E>::with_capacity(num_values);
+        for (&s_at_x, &x) in combined_rows.iter().zip(&self.x_coordinates) {
+            for (&[s_at_z, s_at_next_z], &[z, next_z]) in ood_values.iter().zip(&self.z) {
+                let t1_den = x - z;
+                let t2_den = x - next_z;
+                result_num.push((s_at_x - s_at_z) * t2_den + (s_at_x - s_at_next_z) * t1_den);
+                result_den.push(t1_den * t2_den);
             }
         }
 
         result_den = batch_inversion(&result_den);
-        result_num.iter().zip(result_den).map(|(n, d)| *n * d).collect()
+        sum_fractions(&result_num, &result_den, self.z.len())
     }
 
     /// For each queried set of composition polynomial column evaluations, combine evaluations
@@ -139,36 +137,41 @@ impl<E: FieldElement> DeepComposer<E> {
     ///   all i, where cc_i is the coefficient for the random linear combination drawn from the
     ///   public coin.
     ///
+    /// Similarly to trace columns, the evaluations of every query and the out-of-domain
+    /// evaluations at every point are combined only once. When there are several out-of-domain
+    /// points, H(x) is computed for every point, and the results are added together.
+    ///
     /// Note that values of H_i(z) are received from the prover and passed into this function
-    /// via the `ood_evaluations` parameter.
+    /// via the `ood_evaluations` parameter; this parameter contains one vector of evaluations per
+    /// out-of-domain point.
     pub fn compose_constraint_evaluations(
         &self,
         queried_evaluations: Table<E>,
-        ood_evaluations: Vec<E>,
+        ood_evaluations: Vec<Vec<E>>,
     ) -> Vec<E> {
         assert_eq!(queried_evaluations.num_rows(), self.x_coordinates.len());
+        assert_eq!(ood_evaluations.len(), self.z.len());
 
-        let n = queried_evaluations.num_rows();
-        let mut result_num = Vec::<E>::with_capacity(n);
-        let mut result_den = Vec::<E>::with_capacity(n);
-
-        let z = self.z[0];
+        let ood_values = ood_evaluations
+            .iter()
+            .map(|evaluations| linear_combination(evaluations, &self.cc.constraints))
+            .collect::<Vec<_>>();
 
         // combine composition polynomial columns separately for numerators and denominators;
         // this way we can use batch inversion in the end.
+        let num_values = queried_evaluations.num_rows() * self.z.len();
+        let mut result_num = Vec::<E>::with_capacity(num_values);
+        let mut result_den = Vec::<E>::with_capacity(num_values);
         for (query_values, &x) in queried_evaluations.rows().zip(&self.x_coordinates) {
-            let mut composition_num = E::ZERO;
-            for (i, &evaluation) in query_values.iter().enumerate() {
-                // compute the numerator of H'_i(x) as (H_i(x) - H_i(z)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                composition_num += (evaluation - ood_evaluations[i]) * self.cc.constraints[i];
+            let h_at_x = linear_combination(query_values, &self.cc.constraints);
+            for (&h_at_z, &[z, _]) in ood_values.iter().zip(&self.z) {
+                result_num.push(h_at_x - h_at_z);
+                result_den.push(x - z);
             }
-            result_num.push(composition_num);
-            result_den.push(x - z);
         }
 
         result_den = batch_inversion(&result_den);
-        result_num.iter().zip(result_den).map(|(n, d)| *n * d).collect()
+        sum_fractions(&result_num, &result_den, self.z.len())
     }
 
     /// Combines trace and constraint compositions together.
@@ -185,3 +188,25 @@ impl<E: FieldElement> DeepComposer<E> {
         result
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns sum(values_i * k_i) for all i.
+fn linear_combination<E: FieldElement>(values: &[E], coefficients: &[E]) -> E {
+    values.iter().zip(coefficients).fold(E::ZERO, |acc, (&v, &k)| acc + v * k)
+}
+
+/// Computes the fractions defined by the specified numerators and inverted denominators, and
+/// returns the sums of every consecutive `chunk_size` fractions.
+fn sum_fractions<E: FieldElement>(
+    numerators: &[E],
+    inv_denominators: &[E],
+    chunk_size: usize,
+) -> Vec<E> {
+    numerators
+        .chunks(chunk_size)
+        .zip(inv_denominators.chunks(chunk_size))
+        .map(|(nums, dens)| linear_combination(nums, dens))
+        .collect()
+}
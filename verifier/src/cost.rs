@@ -0,0 +1,252 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{proof::Context, Air, ProofOptions};
+use core::mem;
+use crypto::Hasher;
+use fri::FriOptions;
+use math::FieldElement;
+use utils::Serializable;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Largest max degree of the FRI remainder supported by [ProofOptions].
+const MAX_FRI_REMAINDER_DEGREE: usize = 65535;
+
+// VERIFIER COST ESTIMATE
+// ================================================================================================
+/// An estimate of the work performed by the verifier to verify a proof for a given instance of a
+/// computation, and of the size of the proof.
+///
+/// An estimate is computed via [VerifierCostEstimate::new()] from the dimensions of the execution
+/// trace, the number of constraints of the computation, and the proof options alone, without
+/// generating a proof. This makes it possible to compare different parameter choices for
+/// environments in which verification is expensive (e.g., on-chain or in-circuit verification).
+///
+/// All values are upper bounds: Merkle authentication paths for different queries usually share
+/// nodes which are hashed and included in the proof only once, and queries drawn more than once
+/// are opened only once. The work needed to evaluate transition constraints of the AIR over the
+/// out-of-domain frame depends on the AIR and is not included.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct VerifierCostEstimate {
+    /// Number of hash function invocations needed to update the public coin and to draw random
+    /// values from it (including proof-of-work verification).
+    pub coin_hashes: usize,
+    /// Number of hash function invocations needed to verify Merkle authentication paths of all
+    /// trace, constraint, and FRI layer queries.
+    pub merkle_hashes: usize,
+    /// Number of extension field multiplications needed to combine constraint evaluations at the
+    /// out-of-domain point, to compute the DEEP composition polynomial at all queried positions,
+    /// and to verify FRI folding and the FRI remainder.
+    pub field_mults: usize,
+    /// Size of the proof in bytes.
+    pub proof_size: usize,
+}
+
+impl VerifierCostEstimate {
+    /// Returns a cost estimate for verifying a proof for the specified AIR generated using the
+    /// hash function `H`.
+    pub fn new<A: Air, H: Hasher>(air: &A) -> Self {
+        let context = air.context();
+        let options = air.options();
+        let base_bytes = <A::BaseField as FieldElement>::ELEMENT_BYTES;
+        let ext_bytes = base_bytes * options.field_extension().degree() as usize;
+        let digest_bytes = mem::size_of::<H::Digest>();
+
+        let lde_domain_size = air.lde_domain_size();
+        let lde_depth = lde_domain_size.ilog2() as usize;
+        let num_queries = options.num_queries();
+        let layout = air.trace_layout();
+        let num_trace_segments = layout.num_segments();
+        let trace_width = layout.main_trace_width() + layout.aux_trace_width();
+        let num_composition_columns = context.num_constraint_composition_columns();
+        let num_ood_points = options.num_ood_points();
+
+        let fri_options = options.to_fri_options();
+        let folding_factor = fri_options.folding_factor();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let remainder_size =
+            lde_domain_size / folding_factor.pow(num_fri_layers as u32) / options.blowup_factor();
+
+        // every query to a tree hashes a leaf and one node at every level of the tree; the proof
+        // contains a leaf and a sibling node at every level of the tree for every query
+        let mut merkle_hashes = num_trace_segments * num_queries * (lde_depth + 1);
+        merkle_hashes += num_queries * (lde_depth + 1);
+        let mut proof_size = num_trace_segments * num_queries * lde_depth * digest_bytes;
+        proof_size += num_queries * lde_depth * digest_bytes;
+        proof_size += num_queries * layout.main_trace_width() * base_bytes;
+        proof_size += num_queries * layout.aux_trace_width() * ext_bytes;
+        proof_size += num_queries * num_composition_columns * ext_bytes;
+
+        // every FRI layer is committed to via a tree with one leaf per folded coset
+        let mut fri_mults = 0;
+        let mut layer_depth = lde_depth;
+        for _ in 0..num_fri_layers {
+            layer_depth -= folding_factor.ilog2() as usize;
+            merkle_hashes += num_queries * (layer_depth + 1);
+            proof_size += num_queries * layer_depth * digest_bytes;
+            proof_size += num_queries * folding_factor * ext_bytes;
+            // folding a coset interpolates a polynomial of degree folding_factor - 1 and
+            // evaluates it at the random point
+            fri_mults += num_queries * folding_factor * folding_factor;
+        }
+        // the remainder polynomial is evaluated at every queried position
+        fri_mults += num_queries * remainder_size;
+        proof_size += remainder_size * ext_bytes;
+
+        // the coin is seeded once, reseeded with every commitment (including the commitment to the
+        // FRI remainder), with the out-of-domain frame and with the out-of-domain constraint
+        // evaluations at every out-of-domain point, and every drawn value and every proof-of-work
+        // check (including the checks for ground FRI layers) require one hash; hashing of the
+        // out-of-domain values is counted as one invocation each. when DEEP coefficients are
+        // powers of a single challenge, only this challenge is drawn.
+        let num_commitments = num_trace_segments + 1 + num_fri_layers + 1;
+        let num_deep_terms = trace_width + num_composition_columns;
+        let num_deep_draws = if options.deep_coefficient_powers() {
+            1
+        } else {
+            num_deep_terms
+        };
+        let num_draws = context.num_transition_constraints()
+            + context.num_assertions()
+            + num_ood_points
+            + num_deep_draws
+            + (num_fri_layers + 1)
+            + num_queries;
+        let num_layer_pow_checks = if fri_options.layer_grinding_factor() > 0 {
+            num_fri_layers
+        } else {
+            0
+        };
+        let coin_hashes =
+            1 + num_commitments + 2 * 2 * num_ood_points + num_draws + 1 + num_layer_pow_checks;
+        proof_size += num_commitments * digest_bytes;
+        proof_size += num_layer_pow_checks * mem::size_of::<u64>();
+
+        // the out-of-domain frame contains two rows of the trace and evaluations of all
+        // composition polynomial columns for every out-of-domain point
+        proof_size += num_ood_points * (2 * trace_width + num_composition_columns) * ext_bytes;
+        // context and proof-of-work nonce
+        let proof_context = Context::new::<A::BaseField>(air.trace_info(), options.clone())
+            .set_air_id(A::AIR_ID)
+            .set_air_fingerprint(air.fingerprint());
+        proof_size += proof_context.to_bytes().len() + mem::size_of::<u64>();
+
+        // constraint evaluations are combined with random coefficients and divided by their
+        // divisors, and composition polynomial columns are combined at every out-of-domain point
+        // via Horner's method; the DEEP composition combines the out-of-domain values of every
+        // trace column at two points and of every composition polynomial column at one point,
+        // and the queried values of every column once for every query; every query then requires
+        // a constant number of multiplications (including batch inversion) for every point; the
+        // coefficients are computed from their challenge with one multiplication each
+        let ood_mults = num_ood_points
+            * (2 * (context.num_transition_constraints() + context.num_assertions())
+                + num_composition_columns);
+        let power_mults = if options.deep_coefficient_powers() {
+            num_deep_terms
+        } else {
+            0
+        };
+        let deep_mults = num_ood_points * (2 * trace_width + num_composition_columns)
+            + num_queries * num_deep_terms
+            + num_queries * num_ood_points * 12
+            + power_mults;
+
+        Self {
+            coin_hashes,
+            merkle_hashes,
+            field_mults: ood_mults + deep_mults + fri_mults,
+            proof_size,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the total number of hash function invocations performed by the verifier.
+    pub fn hash_invocations(&self) -> usize {
+        self.coin_hashes + self.merkle_hashes
+    }
+
+    // FRI PARAMETER SELECTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a copy of the specified proof options with the FRI folding factor and the max
+    /// degree of the FRI remainder chosen to minimize the specified cost of verification.
+    ///
+    /// All supported folding factors and all remainder degrees for which the remainder fits into
+    /// the LDE domain are considered; the AIR for every candidate set of options is built via
+    /// `build_air`, and the candidates are compared using estimates computed via
+    /// [VerifierCostEstimate::new()]. Ties are broken by the other of the two objectives.
+    ///
+    /// Neither the folding factor nor the remainder degree affect the security level estimated
+    /// for a proof, and thus, the returned options provide the same security level as the
+    /// specified ones; options for a target security level can be obtained via
+    /// [ProofOptions::for_security()] before selecting FRI parameters. The chosen parameters are a
+    /// part of the proof options, and so, they are recorded in the proof context and used by the
+    /// verifier.
+    ///
+    /// All layers of FRI use the same folding factor.
+    pub fn optimize_fri_options<A, H, F>(
+        options: &ProofOptions,
+        objective: CostObjective,
+        build_air: F,
+    ) -> ProofOptions
+    where
+        A: Air,
+        H: Hasher,
+        F: Fn(ProofOptions) -> A,
+    {
+        let lde_domain_size = build_air(options.clone()).lde_domain_size();
+
+        let mut best: Option<((usize, usize), ProofOptions)> = None;
+        let mut folding_factor = FriOptions::MIN_FOLDING_FACTOR;
+        while folding_factor <= FriOptions::MAX_FOLDING_FACTOR {
+            let mut remainder_size = 1;
+            while remainder_size * options.blowup_factor() <= lde_domain_size
+                && remainder_size - 1 <= MAX_FRI_REMAINDER_DEGREE
+            {
+                let candidate = ProofOptions::new(
+                    options.num_queries(),
+                    options.blowup_factor(),
+                    options.grinding_factor(),
+                    options.field_extension(),
+                    folding_factor,
+                    remainder_size - 1,
+                )
+                .with_fri_layer_grinding_factor(options.fri_layer_grinding_factor());
+
+                let estimate = Self::new::<A, H>(&build_air(candidate.clone()));
+                let cost = match objective {
+                    CostObjective::ProofSize => (estimate.proof_size, estimate.hash_invocations()),
+                    CostObjective::HashInvocations => {
+                        (estimate.hash_invocations(), estimate.proof_size)
+                    }
+                };
+                if best.as_ref().map_or(true, |(best_cost, _)| cost < *best_cost) {
+                    best = Some((cost, candidate));
+                }
+                remainder_size *= 2;
+            }
+            folding_factor *= 2;
+        }
+
+        best.map(|(_, options)| options).unwrap_or_else(|| options.clone())
+    }
+}
+
+// COST OBJECTIVE
+// ================================================================================================
+
+/// Defines the cost minimized when selecting FRI parameters via
+/// [VerifierCostEstimate::optimize_fri_options()].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CostObjective {
+    /// Size of the proof in bytes.
+    ProofSize,
+    /// Total number of hash function invocations performed by the verifier.
+    HashInvocations,
+}
@@ -6,11 +6,18 @@
 //! Contains common error types for prover and verifier.
 
 use core::fmt;
-use utils::string::String;
+use crypto::RandomCoinError;
+use utils::{string::String, Box, DeserializationError};
 
 // VERIFIER ERROR
 // ================================================================================================
 /// Represents an error returned by the verifier during an execution of the protocol.
+///
+/// By default, errors are displayed tersely so that they can be safely reported by production
+/// deployments. The alternate format (`{:#}`) additionally displays structured details of the
+/// failure: the trace segment whose queries did not match the commitment, the mismatched
+/// out-of-domain constraint evaluations, the FRI layer at which verification failed, and the same
+/// details for errors nested in continuation and batch verification errors.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VerifierError {
     /// This error occurs when base field read by a verifier from a proof does not match the
@@ -19,17 +26,34 @@ pub enum VerifierError {
     /// This error occurs when the base field in which the proof was generated does not support
     /// field extension of degree specified by the proof.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when the AIR identifier read by a verifier from a proof does not match
+    /// the identifier of AIR with which the verifier was instantiated. The values hold the
+    /// identifier of the AIR and the identifier read from the proof respectively.
+    UnexpectedAirId(u32, u32),
+    /// This error occurs when no AIR is registered for the AIR identifier read from a proof.
+    UnknownAirId(u32),
+    /// This error occurs when the digest of the AIR structure read from a proof differs from the
+    /// digest of the structure of AIR with which the verifier was instantiated.
+    InconsistentAirFingerprint,
+    /// This error occurs when a proof or the computation described by it exceeds the limits
+    /// specified for the verifier.
+    LimitExceeded(String),
     /// This error occurs when a verifier cannot deserialize the specified proof.
-    ProofDeserializationError(String),
+    ProofDeserializationError(DeserializationError),
+    /// This error occurs when a verifier cannot deserialize the specified public inputs.
+    PublicInputsDeserializationError(DeserializationError),
     /// This error occurs when a verifier fails to draw a random value from a random coin
     /// within a specified number of tries.
-    RandomCoinError,
+    RandomCoinError(RandomCoinError),
     /// This error occurs when constraints evaluated over out-of-domain trace rows do not match
-    /// evaluations of the constraint composition polynomial at the out-of-domain point.
-    InconsistentOodConstraintEvaluations,
+    /// evaluations of the constraint composition polynomial at the out-of-domain point. The
+    /// values hold the evaluation of the constraints and the evaluation of the composition
+    /// polynomial columns respectively.
+    InconsistentOodConstraintEvaluations(String, String),
     /// This error occurs when Merkle authentication paths of trace queries do not resolve to the
-    /// execution trace commitment included in the proof.
-    TraceQueryDoesNotMatchCommitment,
+    /// execution trace commitment included in the proof. The value holds the index of the trace
+    /// segment whose commitment did not match.
+    TraceQueryDoesNotMatchCommitment(usize),
     /// This error occurs when Merkle authentication paths of constraint evaluation queries do not
     /// resolve to the constraint evaluation commitment included in the proof.
     ConstraintQueryDoesNotMatchCommitment,
@@ -52,6 +76,27 @@ pub enum VerifierError {
     /// This error occurs when the parameters, that were used to generate the proof, do not match
     /// any of the set of parameters expected by the verifier.
     UnacceptableProofOptions,
+    /// This error occurs when the hash function, that was used to generate the proof, is not one
+    /// of the hash functions accepted by the verifier.
+    UnacceptableHashFunction,
+    /// This error occurs when the blowup factor, that was used to generate the proof, is greater
+    /// than the maximum blowup factor accepted by the verifier.
+    UnacceptableBlowupFactor(usize, usize),
+    /// This error occurs when the domain offset, that was used to generate the proof, is not an
+    /// element of the base field outside of the subgroup spanned by the LDE domain of the proof.
+    UnsafeDomainOffset(u64),
+    /// This error occurs when the initial or the final state of a continuation proof does not
+    /// match the state expected by the verifier.
+    UnexpectedContinuationState,
+    /// This error occurs when a proof for one of the segments of a continuation proof could not
+    /// be verified against the boundary states adjacent to the segment.
+    ContinuationSegmentVerificationFailed(usize, Box<VerifierError>),
+    /// This error occurs when one of the proofs in a batch could not be verified against the
+    /// public inputs paired with it.
+    BatchProofVerificationFailed(usize, Box<VerifierError>),
+    /// This error occurs when an opening of a trace column refers to a column which is not in the
+    /// main trace segment, or to a point in the LDE domain of the proof.
+    InvalidTraceOpening(String),
 }
 
 impl fmt::Display for VerifierError {
@@ -64,17 +109,44 @@ impl fmt::Display for VerifierError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the proof base field")
             }
-            Self::ProofDeserializationError(msg) => {
-                write!(f, "proof deserialization failed: {msg}")
+            Self::UnexpectedAirId(expected, actual) => {
+                write!(f, "AIR identifier of the proof {actual} does not match AIR identifier {expected} of the specified AIR")
             }
-            Self::RandomCoinError => {
-                write!(f, "failed to draw a random value from a random coin")
+            Self::UnknownAirId(air_id) => {
+                write!(f, "no AIR is registered for AIR identifier {air_id}")
             }
-            Self::InconsistentOodConstraintEvaluations => {
-                write!(f, "constraint evaluations over the out-of-domain frame are inconsistent")
+            Self::InconsistentAirFingerprint => {
+                write!(f, "AIR fingerprint of the proof does not match the structure of the specified AIR")
             }
-            Self::TraceQueryDoesNotMatchCommitment => {
-                write!(f, "trace query did not match the commitment")
+            Self::LimitExceeded(msg) => {
+                write!(f, "proof exceeds verifier limits: {msg}")
+            }
+            Self::ProofDeserializationError(err) => {
+                write!(f, "proof deserialization failed: {err}")
+            }
+            Self::PublicInputsDeserializationError(err) => {
+                write!(f, "public inputs deserialization failed: {err}")
+            }
+            Self::RandomCoinError(err) => {
+                write!(f, "failed to draw a random value from a random coin")?;
+                if f.alternate() {
+                    write!(f, ": {err}")?;
+                }
+                Ok(())
+            }
+            Self::InconsistentOodConstraintEvaluations(constraints, composition) => {
+                write!(f, "constraint evaluations over the out-of-domain frame are inconsistent")?;
+                if f.alternate() {
+                    write!(f, ": constraints evaluated to {constraints}, but composition polynomial columns evaluated to {composition}")?;
+                }
+                Ok(())
+            }
+            Self::TraceQueryDoesNotMatchCommitment(segment) => {
+                write!(f, "trace query did not match the commitment")?;
+                if f.alternate() {
+                    write!(f, " to trace segment {segment}")?;
+                }
+                Ok(())
             }
             Self::ConstraintQueryDoesNotMatchCommitment => {
                 write!(f, "constraint query did not match the commitment")
@@ -83,7 +155,11 @@ impl fmt::Display for VerifierError {
                 write!(f, "query seed proof-of-work verification failed")
             }
             Self::FriVerificationFailed(err) => {
-                write!(f, "verification of low-degree proof failed: {err}")
+                if f.alternate() {
+                    write!(f, "verification of low-degree proof failed: {err:#}")
+                } else {
+                    write!(f, "verification of low-degree proof failed: {err}")
+                }
             }
             Self::InsufficientConjecturedSecurity(minimal_security, proof_security)=> {
                 write!(f, "insufficient proof security level: expected at least {minimal_security} bits of conjectured security, but was {proof_security} bits")
@@ -92,6 +168,97 @@ impl fmt::Display for VerifierError {
                 write!(f, "insufficient proof security level: expected at least {minimal_security} bits of proven security, but was {proof_security} bits")
             }
             Self::UnacceptableProofOptions => {write!(f, "invalid proof options: security parameters do not match the acceptable parameter set")}
+            Self::UnacceptableHashFunction => {
+                write!(f, "invalid proof options: hash function is not one of the acceptable hash functions")
+            }
+            Self::UnacceptableBlowupFactor(max_blowup_factor, blowup_factor) => {
+                write!(f, "invalid proof options: expected blowup factor of at most {max_blowup_factor}, but was {blowup_factor}")
+            }
+            Self::UnsafeDomainOffset(offset) => {
+                write!(f, "invalid proof options: domain offset {offset} cannot be used for the LDE domain of the proof")
+            }
+            Self::UnexpectedContinuationState => {
+                write!(f, "initial or final state of the continuation proof does not match the expected state")
+            }
+            Self::ContinuationSegmentVerificationFailed(segment, err) => {
+                if f.alternate() {
+                    write!(f, "verification of continuation segment {segment} failed: {err:#}")
+                } else {
+                    write!(f, "verification of continuation segment {segment} failed: {err}")
+                }
+            }
+            Self::BatchProofVerificationFailed(index, err) => {
+                if f.alternate() {
+                    write!(f, "verification of proof {index} in the batch failed: {err:#}")
+                } else {
+                    write!(f, "verification of proof {index} in the batch failed: {err}")
+                }
+            }
+            Self::InvalidTraceOpening(err) => {
+                write!(f, "invalid trace opening: {err}")
+            }
+        }
+    }
+}
+
+impl VerifierError {
+    /// Returns a stable code identifying the kind of this error.
+    ///
+    /// Codes have the form `verifier.<kind>` and are stable across releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InconsistentBaseField => "verifier.inconsistent_base_field",
+            Self::UnsupportedFieldExtension(_) => "verifier.unsupported_field_extension",
+            Self::UnexpectedAirId(_, _) => "verifier.unexpected_air_id",
+            Self::UnknownAirId(_) => "verifier.unknown_air_id",
+            Self::InconsistentAirFingerprint => "verifier.inconsistent_air_fingerprint",
+            Self::LimitExceeded(_) => "verifier.limit_exceeded",
+            Self::ProofDeserializationError(_) => "verifier.proof_deserialization",
+            Self::PublicInputsDeserializationError(_) => "verifier.public_inputs_deserialization",
+            Self::RandomCoinError(_) => "verifier.random_coin",
+            Self::InconsistentOodConstraintEvaluations(_, _) => {
+                "verifier.inconsistent_ood_constraint_evaluations"
+            }
+            Self::TraceQueryDoesNotMatchCommitment(_) => {
+                "verifier.trace_query_does_not_match_commitment"
+            }
+            Self::ConstraintQueryDoesNotMatchCommitment => {
+                "verifier.constraint_query_does_not_match_commitment"
+            }
+            Self::QuerySeedProofOfWorkVerificationFailed => {
+                "verifier.query_seed_proof_of_work_verification_failed"
+            }
+            Self::FriVerificationFailed(_) => "verifier.fri_verification_failed",
+            Self::InsufficientConjecturedSecurity(_, _) => {
+                "verifier.insufficient_conjectured_security"
+            }
+            Self::InsufficientProvenSecurity(_, _) => "verifier.insufficient_proven_security",
+            Self::UnacceptableProofOptions => "verifier.unacceptable_proof_options",
+            Self::UnacceptableHashFunction => "verifier.unacceptable_hash_function",
+            Self::UnacceptableBlowupFactor(_, _) => "verifier.unacceptable_blowup_factor",
+            Self::UnsafeDomainOffset(_) => "verifier.unsafe_domain_offset",
+            Self::UnexpectedContinuationState => "verifier.unexpected_continuation_state",
+            Self::ContinuationSegmentVerificationFailed(_, _) => {
+                "verifier.continuation_segment_verification_failed"
+            }
+            Self::BatchProofVerificationFailed(_, _) => "verifier.batch_proof_verification_failed",
+            Self::InvalidTraceOpening(_) => "verifier.invalid_trace_opening",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ProofDeserializationError(err) | Self::PublicInputsDeserializationError(err) => {
+                Some(err)
+            }
+            Self::RandomCoinError(err) => Some(err),
+            Self::FriVerificationFailed(err) => Some(err),
+            Self::ContinuationSegmentVerificationFailed(_, err)
+            | Self::BatchProofVerificationFailed(_, err) => Some(err.as_ref()),
+            _ => None,
         }
     }
 }
@@ -30,20 +30,32 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
         .iter()
         .map(|poly| {
             let num_cycles = air.trace_length() / poly.len();
-            let x = x.exp_vartime((num_cycles as u32).into());
+            let x = x.exp_vartime((num_cycles as u64).into());
             polynom::eval(poly, x)
         })
         .collect::<Vec<_>>();
 
+    // if some columns of the main trace segment hold extension field elements, combine their
+    // coordinates into extension field elements before evaluating transition constraints;
+    // boundary constraints are evaluated against the frame of the committed main trace segment
+    let context = air.context();
+    let main_frame = if context.num_main_extension_columns() > 0 {
+        let mut frame = EvaluationFrame::new(context.main_frame_width());
+        frame.read_extension_columns(main_trace_frame, context.num_main_base_columns());
+        frame
+    } else {
+        main_trace_frame.clone()
+    };
+
     // evaluate transition constraints for the main trace segment
     let mut t_evaluations1 = E::zeroed_vector(t_constraints.num_main_constraints());
-    air.evaluate_transition(main_trace_frame, &periodic_values, &mut t_evaluations1);
+    air.evaluate_transition(&main_frame, &periodic_values, &mut t_evaluations1);
 
     // evaluate transition constraints for auxiliary trace segments (if any)
     let mut t_evaluations2 = E::zeroed_vector(t_constraints.num_aux_constraints());
     if let Some(aux_trace_frame) = aux_trace_frame {
         air.evaluate_aux_transition(
-            main_trace_frame,
+            &main_frame,
             aux_trace_frame,
             &periodic_values,
             &aux_rand_elements,
@@ -25,6 +25,28 @@
 //! asserted values. But for the impact to be noticeable, the number of asserted values would
 //! need to be in tens of thousands. And even for hundreds of thousands of asserted values, the
 //! verification time should not exceed 50 ms.
+//!
+//! # Embedded targets
+//! The verifier can be compiled without the standard library (with `default-features = false`),
+//! in which case it depends only on `alloc`. For environments with small fixed-size heaps, such
+//! as microcontrollers and TEEs, proofs should be verified via [verify_with_limits()]: all memory
+//! allocated by the verifier is proportional to the size of the proof and to the dimensions of
+//! the execution trace, and [VerifierLimits] bounds these values before any large allocations
+//! are made. Verification code is not recursive, and apart from values allocated by the AIR
+//! itself, does not allocate large arrays on the stack: the largest stack-allocated values are
+//! FRI cosets of at most 32 field elements (the maximum FRI folding factor), while buffers used
+//! to interpolate these cosets are allocated on the heap. Thus, stack usage does not depend on
+//! the proof.
+//!
+//! # Minimal profile
+//! Verifiers running in environments where every instruction is expensive (e.g., guests of
+//! RISC-V zkVMs used for proof composition) should depend on this crate with
+//! `default-features = false`. In this profile, the verifier is built without the standard
+//! library and without any sources of randomness, and only the procedures for hash functions
+//! and random coins specified statically via type parameters are included. Procedures which
+//! select the AIR or the hash function at runtime ([verify_with_hash_fn()] and [AirRegistry])
+//! are available only with the `dispatch` feature (enabled by default); none of the other
+//! procedures use dynamic dispatch.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -33,26 +55,35 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    proof::{ContinuationProof, StarkProof, TraceOpening},
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
+    EvaluationFrame, FieldExtension, ProofOptions, TraceInfo, TransitionConstraintDegree,
 };
 
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement, ToElements,
+    FieldElement, StarkField, ToElements,
 };
 
+use air::{
+    metrics,
+    proof::{Commitments, Context, OodFrame, ProofMetadata, Queries},
+};
+use fri::FriProof;
 pub use utils::{
     collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
     SliceReader,
 };
+use utils::{string::ToString, Box};
 
 pub use crypto;
 use crypto::{ElementHasher, Hasher, RandomCoin};
 
+#[cfg(feature = "dispatch")]
+use crypto::{DefaultRandomCoin, HashFnVisitor, HashFunction};
+
 use fri::FriVerifier;
 
 mod channel;
@@ -67,6 +98,29 @@ use composer::DeepComposer;
 mod errors;
 pub use errors::VerifierError;
 
+pub mod stages;
+use stages::Checks;
+
+mod cost;
+pub use cost::{CostObjective, VerifierCostEstimate};
+
+#[cfg(feature = "dispatch")]
+mod registry;
+#[cfg(feature = "dispatch")]
+pub use registry::AirRegistry;
+
+mod limits;
+pub use limits::{verify_with_limits, VerifierLimits};
+
+mod opening;
+pub use opening::verify_trace_opening;
+
+mod policy;
+pub use policy::OptionsPolicy;
+
+#[cfg(test)]
+mod tests;
+
 // VERIFIER
 // ================================================================================================
 /// Verifies that the specified computation was executed correctly against the specified inputs.
@@ -81,6 +135,13 @@ pub use errors::VerifierError;
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
 /// - The specified proof was generated with parameters not providing an acceptable security level.
+///
+/// # Large public inputs
+/// All of `pub_inputs` is absorbed into the public coin, and thus, the verifier needs the public
+/// inputs in full. For computations over large amounts of public data, the data can be committed
+/// to via `air::gadgets::RpCommittedData`, in which case only the root of the commitment (and
+/// the number of committed elements) is included in `AIR::PublicInputs`, and the AIR constrains
+/// the elements read by the computation against this root.
 #[rustfmt::skip]
 pub fn verify<AIR, HashFn, RandCoin>(
     proof: StarkProof,
@@ -91,62 +152,458 @@ where
     AIR: Air, 
     HashFn: ElementHasher<BaseField = AIR::BaseField>,
     RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    verify_proof::<AIR, HashFn, RandCoin, _>(proof, pub_inputs, RandCoin::new, acceptable_options, Checks::ALL).map(|_| ())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// drawing all verifier challenges from the provided public coin.
+///
+/// This is equivalent to [verify()] except that the public coin is not instantiated from the
+/// proof context and `pub_inputs`; instead, the provided `public_coin` is used as is. Together
+/// with a prover whose `Prover::new_public_coin()` returns a coin supplying the same challenges
+/// (e.g., a [ScriptedRandomCoin](crypto::ScriptedRandomCoin)), this runs the protocol in an
+/// interactive mode in which challenges are chosen externally rather than derived from the
+/// transcript via the Fiat-Shamir transform. This is intended for testing soundness properties
+/// of the protocol and for fuzzing individual protocol rounds; when the challenges are known to
+/// the prover in advance, a successful verification does NOT imply that the computation was
+/// executed correctly.
+///
+/// # Errors
+/// Returns an error for the same reasons as [verify()]; in addition, verification fails if a
+/// challenge could not be drawn from `public_coin`.
+#[rustfmt::skip]
+pub fn verify_with_public_coin<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    public_coin: RandCoin,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError> 
+where 
+    AIR: Air, 
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    verify_proof::<AIR, HashFn, RandCoin, _>(proof, pub_inputs, |_| public_coin, acceptable_options, Checks::ALL).map(|_| ())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and returns the transcript of all operations performed on the public coin during verification.
+///
+/// This is equivalent to [verify()] except that the public coin is wrapped into a
+/// [TranscriptCoin](crypto::TranscriptCoin) which records every value absorbed into the coin and
+/// every challenge drawn from it. The transcript is returned regardless of the outcome of the
+/// verification; when a proof is rejected, comparing the transcript against the one recorded by
+/// the prover (e.g., via `Prover::prove_with_transcript()`) shows whether the prover and the
+/// verifier derived the same challenges up to the point of failure. The transcript is empty if
+/// the proof was rejected before the public coin was instantiated.
+///
+/// This function is available only when the crate is compiled with `std` feature.
+#[cfg(feature = "std")]
+pub fn verify_with_transcript<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> (Result<(), VerifierError>, crypto::Transcript)
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let recorder = crypto::TranscriptRecorder::new();
+    let build_public_coin = |seed: &[AIR::BaseField]| recorder.wrap(seed, RandCoin::new(seed));
+    let result = verify_proof::<AIR, HashFn, _, _>(
+        proof,
+        pub_inputs,
+        build_public_coin,
+        acceptable_options,
+        Checks::ALL,
+    );
+    (result.map(|_| ()), recorder.transcript())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// reading the proof incrementally from the provided `source`.
+///
+/// This is equivalent to deserializing a [StarkProof] from `source` and calling [verify()] with
+/// it, except that the proof is checked while it is being read: the proof context is checked
+/// against the base field of `AIR` and `acceptable_options` before anything else is read, and the
+/// commitments are checked to have the shape defined by the context before any of the queries,
+/// the out-of-domain frame, and the FRI proof are read. Thus, proofs generated for a different
+/// computation or with unacceptable parameters are rejected after reading only a few bytes.
+///
+/// Bytes following the proof in `source` are not read.
+///
+/// # Errors
+/// Returns an error if a valid proof could not be read from `source`, or if the proof does not
+/// attest to a correct execution of the computation (see [verify()] for details).
+pub fn verify_from_reader<AIR, HashFn, RandCoin, R>(
+    source: &mut R,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    R: ByteReader,
+{
+    let map_err = VerifierError::ProofDeserializationError;
+
+    // read the context and make sure the proof was generated in the field of the AIR and with
+    // acceptable parameters
+    let context = Context::read_from(source).map_err(map_err)?;
+    if context.field_modulus_bytes() != AIR::BaseField::get_modulus_le_bytes() {
+        return Err(VerifierError::InconsistentBaseField);
+    }
+    if context.air_id() != AIR::AIR_ID {
+        return Err(VerifierError::UnexpectedAirId(AIR::AIR_ID, context.air_id()));
+    }
+    acceptable_options.validate_context::<HashFn>(&context)?;
+
+    let num_unique_queries = source.read_u16().map_err(map_err)?;
+    if num_unique_queries == 0 || num_unique_queries as usize > context.options().num_queries() {
+        return Err(VerifierError::ProofDeserializationError(DeserializationError::InvalidValue(
+            format!(
+                "number of unique queries must be between 1 and {}, but was {num_unique_queries}",
+                context.options().num_queries()
+            ),
+        )));
+    }
+
+    // read the commitments and make sure there is one commitment for every trace segment, the
+    // constraint composition polynomial, and every FRI layer
+    let commitments = Commitments::read_from(source).map_err(map_err)?;
+    let num_trace_segments = context.trace_layout().num_segments();
+    let num_fri_layers =
+        context.options().to_fri_options().num_fri_layers(context.lde_domain_size());
+    commitments
+        .clone()
+        .parse::<HashFn>(num_trace_segments, num_fri_layers)
+        .map_err(map_err)?;
+
+    // read the rest of the proof
+    let mut trace_queries = Vec::with_capacity(num_trace_segments);
+    for _ in 0..num_trace_segments {
+        trace_queries.push(Queries::read_from(source).map_err(map_err)?);
+    }
+    let proof = StarkProof {
+        context,
+        num_unique_queries,
+        commitments,
+        trace_queries,
+        constraint_queries: Queries::read_from(source).map_err(map_err)?,
+        ood_frame: OodFrame::read_from(source).map_err(map_err)?,
+        fri_proof: FriProof::read_from(source).map_err(map_err)?,
+        pow_nonce: source.read_u64().map_err(map_err)?,
+        metadata: ProofMetadata::read_from(source).map_err(map_err)?,
+    };
+
+    verify::<AIR, HashFn, RandCoin>(proof, pub_inputs, acceptable_options)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// where both the proof and the public inputs are provided in serialized form.
+///
+/// This is intended to be used as the entry point for bindings to other languages (e.g., for
+/// exposing the verifier of a specific computation to JavaScript via WebAssembly), where only
+/// byte strings can be passed from the host environment. The proof is checked while it is being
+/// read from `proof_bytes` (see [verify_from_reader()]).
+///
+/// # Errors
+/// Returns an error if:
+/// * Public inputs could not be deserialized from `pub_inputs_bytes`, or the bytes contain
+///   data beyond the public inputs.
+/// * A valid proof could not be deserialized from `proof_bytes`, or the bytes contain data
+///   beyond the proof.
+/// * The proof does not attest to a correct execution of the computation (see [verify()] for
+///   details).
+pub fn verify_from_bytes<AIR, HashFn, RandCoin>(
+    proof_bytes: &[u8],
+    pub_inputs_bytes: &[u8],
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    AIR::PublicInputs: Deserializable,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let mut source = SliceReader::new(pub_inputs_bytes);
+    let pub_inputs = AIR::PublicInputs::read_from(&mut source)
+        .map_err(VerifierError::PublicInputsDeserializationError)?;
+    if source.has_more_bytes() {
+        return Err(VerifierError::PublicInputsDeserializationError(
+            DeserializationError::UnconsumedBytes,
+        ));
+    }
+
+    let mut source = SliceReader::new(proof_bytes);
+    verify_from_reader::<AIR, HashFn, RandCoin, _>(&mut source, pub_inputs, acceptable_options)?;
+    if source.has_more_bytes() {
+        return Err(VerifierError::ProofDeserializationError(
+            DeserializationError::UnconsumedBytes,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// using a hash function selected at runtime.
+///
+/// This is equivalent to calling [verify()] with a hasher corresponding to `hash_fn` and
+/// [DefaultRandomCoin] instantiated with this hasher. Thus, a single instance of this function
+/// can verify proofs generated with any of the hash functions listed in [HashFunction].
+///
+/// # Errors
+/// Returns an error if combination of the provided proof and public inputs does not attest to
+/// a correct execution of the computation (see [verify()] for details). This includes the case
+/// when the proof was generated using a hash function different from `hash_fn`.
+#[cfg(feature = "dispatch")]
+pub fn verify_with_hash_fn<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    hash_fn: HashFunction,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError> {
+    hash_fn.dispatch::<AIR::BaseField, _>(DynVerifier::<AIR> {
+        proof,
+        pub_inputs,
+        acceptable_options,
+    })
+}
+
+/// Verifies that a long computation split into a chain of segments was executed correctly,
+/// starting in `initial_state` and ending in `final_state`.
+///
+/// Every segment proof contained in `proof` is verified via [verify()] against public inputs
+/// built by `build_pub_inputs` from the initial and the final state of the segment. Since
+/// adjacent segments share a boundary state, this checks that every segment starts in the same
+/// state in which the previous segment ended, and thus, that the entire chain of segments
+/// transitions the computation from `initial_state` to `final_state`.
+///
+/// For the check to be sound, the AIR of the computation must assert that the execution trace
+/// of a segment starts and ends in the states from which its public inputs were built.
+///
+/// # Errors
+/// Returns an error if:
+/// * The initial or the final state of `proof` is different from `initial_state` or
+///   `final_state` respectively.
+/// * Any of the segment proofs does not attest to a correct execution of the computation between
+///   the boundary states adjacent to the segment.
+pub fn verify_continuation<AIR, HashFn, RandCoin, F>(
+    proof: ContinuationProof<AIR::BaseField>,
+    initial_state: &[AIR::BaseField],
+    final_state: &[AIR::BaseField],
+    build_pub_inputs: F,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    F: Fn(&[AIR::BaseField], &[AIR::BaseField]) -> AIR::PublicInputs,
+{
+    if proof.initial_state() != initial_state || proof.final_state() != final_state {
+        return Err(VerifierError::UnexpectedContinuationState);
+    }
+
+    let (boundary_states, segment_proofs) = proof.into_parts();
+    for (i, segment_proof) in segment_proofs.into_iter().enumerate() {
+        let pub_inputs = build_pub_inputs(&boundary_states[i], &boundary_states[i + 1]);
+        verify::<AIR, HashFn, RandCoin>(segment_proof, pub_inputs, acceptable_options).map_err(
+            |err| VerifierError::ContinuationSegmentVerificationFailed(i, Box::new(err)),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Verifies that the specified computation was executed correctly against every set of inputs
+/// in the provided batch.
+///
+/// Every proof in `proofs` is verified via [verify()] against the public inputs paired with it,
+/// and all proofs must be generated for the computation specified by `AIR` using `HashFn`. Since
+/// Fiat-Shamir transcripts of different proofs are independent, the random elements drawn by the
+/// verifier (and thus, the queried positions and the Merkle authentication paths) cannot be
+/// shared between proofs. Verification stops at the first proof which cannot be verified.
+///
+/// # Errors
+/// Returns an error if any of the proofs does not attest to a correct execution of the
+/// computation against the public inputs paired with it; the error contains the index of the
+/// proof in the batch.
+pub fn verify_batch<AIR, HashFn, RandCoin>(
+    proofs: Vec<(StarkProof, AIR::PublicInputs)>,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    for (i, (proof, pub_inputs)) in proofs.into_iter().enumerate() {
+        verify::<AIR, HashFn, RandCoin>(proof, pub_inputs, acceptable_options)
+            .map_err(|err| VerifierError::BatchProofVerificationFailed(i, Box::new(err)))?;
+    }
+
+    Ok(())
+}
+
+/// Verifies a proof for the hash function selected via [HashFunction::dispatch()].
+#[cfg(feature = "dispatch")]
+struct DynVerifier<'a, AIR: Air> {
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &'a AcceptableOptions,
+}
+
+#[cfg(feature = "dispatch")]
+impl<'a, AIR: Air> HashFnVisitor<AIR::BaseField> for DynVerifier<'a, AIR> {
+    type Output = Result<(), VerifierError>;
+
+    fn visit<H: ElementHasher<BaseField = AIR::BaseField>>(self) -> Self::Output {
+        verify::<AIR, H, DefaultRandomCoin<H>>(self.proof, self.pub_inputs, self.acceptable_options)
+    }
+}
+
+// VERIFICATION PROCEDURE
+// ================================================================================================
+
+/// Verifies the proof against the specified public inputs, performing only the specified checks,
+/// and returns the (sorted and deduplicated) query positions drawn during verification.
+///
+/// The public coin is instantiated via `build_public_coin` from the seed built from the proof
+/// context and the public inputs. If all checks are performed, the outcome of verification is
+/// reported to the installed [Metrics](air::metrics::Metrics) backend.
+fn verify_proof<AIR, HashFn, RandCoin, F>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    build_public_coin: F,
+    acceptable_options: &AcceptableOptions,
+    checks: Checks,
+) -> Result<Vec<usize>, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    F: FnOnce(&[AIR::BaseField]) -> RandCoin,
+{
+    let result = check_proof::<AIR, HashFn, RandCoin, F>(
+        proof,
+        pub_inputs,
+        build_public_coin,
+        acceptable_options,
+        checks,
+    );
+    if checks == Checks::ALL {
+        let name = match result {
+            Ok(_) => metrics::PROOFS_VERIFIED,
+            Err(_) => metrics::VERIFICATION_FAILURES,
+        };
+        metrics::metrics().increment_counter(name, 1);
+    }
+    result
+}
+
+/// Performs the checks of [verify_proof()] function.
+#[rustfmt::skip]
+fn check_proof<AIR, HashFn, RandCoin, F>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    build_public_coin: F,
+    acceptable_options: &AcceptableOptions,
+    checks: Checks,
+) -> Result<Vec<usize>, VerifierError> 
+where 
+    AIR: Air, 
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    F: FnOnce(&[AIR::BaseField]) -> RandCoin,
 {
     // check that `proof` was generated with an acceptable set of parameters from the point of view
     // of the verifier
     acceptable_options.validate::<HashFn>(&proof)?;
 
+    // make sure the proof was generated for the computation described by `AIR`
+    if proof.context.air_id() != AIR::AIR_ID {
+        return Err(VerifierError::UnexpectedAirId(AIR::AIR_ID, proof.context.air_id()));
+    }
+
     // build a seed for the public coin; the initial seed is a hash of the proof context and the
     // public inputs, but as the protocol progresses, the coin will be reseeded with the info
     // received from the prover
     let mut public_coin_seed = proof.context.to_elements();
     public_coin_seed.append(&mut pub_inputs.to_elements());
-    
+    let public_coin = build_public_coin(&public_coin_seed);
+
+    // make sure the LDE domain of the proof is disjoint from the trace domain
+    let options = proof.options();
+    if options.validate_domain_offset::<AIR::BaseField>(proof.context.lde_domain_size()).is_err() {
+        let offset = options.custom_domain_offset().expect("default domain offset is always safe");
+        return Err(VerifierError::UnsafeDomainOffset(offset));
+    }
+
     // create AIR instance for the computation specified in the proof
     let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    if air.context().validate_options().is_err() {
+        return Err(VerifierError::UnacceptableProofOptions);
+    }
+
+    // make sure the structure of the computation described by `AIR` is the same as the structure
+    // of the computation for which the proof was generated
+    if proof.context.air_fingerprint() != &air.fingerprint() {
+        return Err(VerifierError::InconsistentAirFingerprint);
+    }
 
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => {
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(air, channel, public_coin)
+            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(air, channel, public_coin, checks)
         },
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(2));
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin, checks)
         },
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(3));
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin, checks)
         },
     }
 }
 
-// VERIFICATION PROCEDURE
-// ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
-/// attests to a correct execution of the computation specified by the provided `air`.
+/// attests to a correct execution of the computation specified by the provided `air`; returns
+/// the query positions drawn from the public coin.
 fn perform_verification<A, E, H, R>(
     air: A,
     mut channel: VerifierChannel<E, H>,
     mut public_coin: R,
-) -> Result<(), VerifierError>
+    checks: Checks,
+) -> Result<Vec<usize>, VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
     R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
 {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!(
+        "verify",
+        trace_width = air.trace_info().width(),
+        trace_length = air.trace_length(),
+        lde_domain_size = air.lde_domain_size()
+    )
+    .entered();
+
     // 1 ----- trace commitment -------------------------------------------------------------------
     // Read the commitments to evaluations of the trace polynomials over the LDE domain sent by the
     // prover. The commitments are used to update the public coin, and draw sets of random elements
@@ -166,7 +623,7 @@ where
     for (i, commitment) in trace_commitments.iter().skip(1).enumerate() {
         let rand_elements = air
             .get_aux_trace_segment_random_elements(i, &mut public_coin)
-            .map_err(|_| VerifierError::RandomCoinError)?;
+            .map_err(VerifierError::RandomCoinError)?;
         aux_trace_rand_elements.add_segment_elements(rand_elements);
         public_coin.reseed(*commitment);
     }
@@ -174,59 +631,85 @@ where
     // build random coefficients for the composition polynomial
     let constraint_coeffs = air
         .get_constraint_composition_coefficients(&mut public_coin)
-        .map_err(|_| VerifierError::RandomCoinError)?;
+        .map_err(VerifierError::RandomCoinError)?;
 
     // 2 ----- constraint commitment --------------------------------------------------------------
     // read the commitment to evaluations of the constraint composition polynomial over the LDE
-    // domain sent by the prover, use it to update the public coin, and draw an out-of-domain point
-    // z from the coin; in the interactive version of the protocol, the verifier sends this point z
-    // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
-    // and sends the results back to the verifier.
+    // domain sent by the prover, and use it to update the public coin.
     let constraint_commitment = channel.read_constraint_commitment();
     public_coin.reseed(constraint_commitment);
-    let z = public_coin.draw::<E>().map_err(|_| VerifierError::RandomCoinError)?;
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
-    // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
-    // are consistent with the evaluations of composition polynomial columns sent by the prover
-
-    // read the out-of-domain trace frames (the main trace frame and auxiliary trace frame, if
-    // provided) sent by the prover and evaluate constraints over them; also, reseed the public
-    // coin with the OOD frames received from the prover.
-    let ood_trace_frame = channel.read_ood_trace_frame();
-    let ood_main_trace_frame = ood_trace_frame.main_frame();
-    let ood_aux_trace_frame = ood_trace_frame.aux_frame();
-    let ood_constraint_evaluation_1 = evaluate_constraints(
-        &air,
-        constraint_coeffs,
-        &ood_main_trace_frame,
-        &ood_aux_trace_frame,
-        aux_trace_rand_elements,
-        z,
-    );
-    public_coin.reseed(H::hash_elements(ood_trace_frame.values()));
-
-    // read evaluations of composition polynomial columns sent by the prover, and reduce them into
-    // a single value by computing \sum_{i=0}^{m-1}(z^(i * l) * value_i), where value_i is the
-    // evaluation of the ith column polynomial H_i(X) at z, l is the trace length and m is
-    // the number of composition column polynomials. This computes H(z) (i.e.
-    // the evaluation of the composition polynomial at z) using the fact that
-    // H(X) = \sum_{i=0}^{m-1} X^{i * l} H_i(X).
-    // Also, reseed the public coin with the OOD constraint evaluations received from the prover.
+    // for every out-of-domain point, make sure that evaluations obtained by evaluating constraints
+    // over the out-of-domain frame are consistent with the evaluations of composition polynomial
+    // columns sent by the prover
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!(
+        "ood_consistency",
+        num_constraints = air.context().num_transition_constraints()
+    )
+    .entered();
+    let ood_trace_frames = channel.read_ood_trace_frames();
     let ood_constraint_evaluations = channel.read_ood_constraint_evaluations();
-    let ood_constraint_evaluation_2 =
-        ood_constraint_evaluations
+    let num_ood_points = ood_trace_frames.len();
+    let mut ood_points = Vec::with_capacity(num_ood_points);
+    let mut ood_main_trace_frames = Vec::with_capacity(num_ood_points);
+    let mut ood_aux_trace_frames = Vec::with_capacity(num_ood_points);
+    for (ood_trace_frame, ood_evaluations) in
+        ood_trace_frames.iter().zip(ood_constraint_evaluations.iter())
+    {
+        // draw an out-of-domain point z from the coin; in the interactive version of the
+        // protocol, the verifier sends this point z to the prover, and the prover evaluates
+        // trace and constraint composition polynomials at z, and sends the results back to the
+        // verifier. the next point is drawn only after the coin has been reseeded with these
+        // results.
+        let z = public_coin.draw::<E>().map_err(VerifierError::RandomCoinError)?;
+
+        // read the out-of-domain trace frames (the main trace frame and auxiliary trace frame,
+        // if provided) sent by the prover and evaluate constraints over them; also, reseed the
+        // public coin with the OOD frames received from the prover.
+        let ood_main_trace_frame = ood_trace_frame.main_frame();
+        let ood_aux_trace_frame = ood_trace_frame.aux_frame();
+        let ood_constraint_evaluation_1 = evaluate_constraints(
+            &air,
+            constraint_coeffs.clone(),
+            &ood_main_trace_frame,
+            &ood_aux_trace_frame,
+            aux_trace_rand_elements.clone(),
+            z,
+        );
+        public_coin.reseed(H::hash_elements(ood_trace_frame.values()));
+
+        // reduce evaluations of composition polynomial columns sent by the prover into a single
+        // value by computing \sum_{i=0}^{m-1}(z^(i * l) * value_i), where value_i is the
+        // evaluation of the ith column polynomial H_i(X) at z, l is the trace length and m is
+        // the number of composition column polynomials. This computes H(z) (i.e.
+        // the evaluation of the composition polynomial at z) using the fact that
+        // H(X) = \sum_{i=0}^{m-1} X^{i * l} H_i(X); the sum is evaluated via Horner's method in
+        // z^l, and thus, requires a single exponentiation.
+        // Also, reseed the public coin with the OOD constraint evaluations received from the
+        // prover.
+        let z_pow_l = z.exp_vartime((air.trace_length() as u64).into());
+        let ood_constraint_evaluation_2 = ood_evaluations
             .iter()
-            .enumerate()
-            .fold(E::ZERO, |result, (i, &value)| {
-                result + z.exp_vartime(((i * (air.trace_length())) as u32).into()) * value
-            });
-    public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
-
-    // finally, make sure the values are the same
-    if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
-        return Err(VerifierError::InconsistentOodConstraintEvaluations);
+            .rev()
+            .fold(E::ZERO, |result, &value| result * z_pow_l + value);
+        public_coin.reseed(H::hash_elements(ood_evaluations));
+
+        // finally, make sure the values are the same
+        if checks.ood_consistency && ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
+            return Err(VerifierError::InconsistentOodConstraintEvaluations(
+                ood_constraint_evaluation_1.to_string(),
+                ood_constraint_evaluation_2.to_string(),
+            ));
+        }
+
+        ood_points.push(z);
+        ood_main_trace_frames.push(ood_main_trace_frame);
+        ood_aux_trace_frames.push(ood_aux_trace_frame);
     }
+    #[cfg(feature = "tracing")]
+    drop(span);
 
     // 4 ----- FRI commitments --------------------------------------------------------------------
     // draw coefficients for computing DEEP composition polynomial from the public coin; in the
@@ -235,7 +718,7 @@ where
     // applies FRI protocol to the evaluations of the DEEP composition polynomial.
     let deep_coefficients = air
         .get_deep_composition_coefficients::<E, R>(&mut public_coin)
-        .map_err(|_| VerifierError::RandomCoinError)?;
+        .map_err(VerifierError::RandomCoinError)?;
 
     // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
     // verifier's perspective, this is equivalent to executing the commit phase of the FRI protocol.
@@ -256,7 +739,9 @@ where
     let pow_nonce = channel.read_pow_nonce();
 
     // make sure the proof-of-work specified by the grinding factor is satisfied
-    if public_coin.check_leading_zeros(pow_nonce) < air.options().grinding_factor() {
+    if checks.proof_of_work
+        && public_coin.check_leading_zeros(pow_nonce) < air.options().grinding_factor()
+    {
         return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
     }
 
@@ -264,9 +749,13 @@ where
     // interactive version of the protocol, the verifier sends these query positions to the prover,
     // and the prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
-    let mut query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size(), pow_nonce)
-        .map_err(|_| VerifierError::RandomCoinError)?;
+    let num_queries = air.options().num_queries();
+    let mut query_positions = if air.options().distinct_queries() {
+        public_coin.draw_distinct_integers(num_queries, air.lde_domain_size(), pow_nonce)
+    } else {
+        public_coin.draw_integers(num_queries, air.lde_domain_size(), pow_nonce)
+    }
+    .map_err(VerifierError::RandomCoinError)?;
 
     // remove any potential duplicates from the positions as the prover will send openings only
     // for unique queries
@@ -275,29 +764,50 @@ where
 
     // read evaluations of trace and constraint composition polynomials at the queried positions;
     // this also checks that the read values are valid against trace and constraint commitments
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!("queries", num_queries = query_positions.len()).entered();
     let (queried_main_trace_states, queried_aux_trace_states) =
-        channel.read_queried_trace_states(&query_positions)?;
-    let queried_constraint_evaluations = channel.read_constraint_evaluations(&query_positions)?;
+        channel.read_queried_trace_states(&query_positions, checks.queries)?;
+    let queried_constraint_evaluations =
+        channel.read_constraint_evaluations(&query_positions, checks.queries)?;
+    #[cfg(feature = "tracing")]
+    drop(span);
 
     // 6 ----- DEEP composition -------------------------------------------------------------------
     // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
+    #[cfg(feature = "tracing")]
+    let span =
+        tracing::info_span!("deep_composition", num_queries = query_positions.len()).entered();
+    let composer = DeepComposer::new(&air, &query_positions, &ood_points, deep_coefficients);
     let t_composition = composer.compose_trace_columns(
         queried_main_trace_states,
         queried_aux_trace_states,
-        ood_main_trace_frame,
-        ood_aux_trace_frame,
+        ood_main_trace_frames,
+        ood_aux_trace_frames,
     );
     let c_composition = composer
         .compose_constraint_evaluations(queried_constraint_evaluations, ood_constraint_evaluations);
     let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
+    #[cfg(feature = "tracing")]
+    drop(span);
 
     // 7 ----- Verify low-degree proof -------------------------------------------------------------
     // make sure that evaluations of the DEEP composition polynomial we computed in the previous
     // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
-    fri_verifier
-        .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+    if checks.fri {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "fri",
+            domain_size = fri_verifier.domain_size(),
+            folding_factor = air.options().to_fri_options().folding_factor()
+        )
+        .entered();
+        fri_verifier
+            .verify(&mut channel, &deep_evaluations, &query_positions)
+            .map_err(VerifierError::FriVerificationFailed)?;
+    }
+
+    Ok(query_positions)
 }
 
 // ACCEPTABLE OPTIONS
@@ -311,14 +821,22 @@ pub enum AcceptableOptions {
     MinProvenSecurity(u32),
     /// Set of acceptable proof parameters
     OptionSet(Vec<ProofOptions>),
+    /// Set of requirements which acceptable proof parameters must satisfy
+    Policy(OptionsPolicy),
 }
 
 impl AcceptableOptions {
     /// Checks that a proof was generated using an acceptable set of parameters.
     pub fn validate<H: Hasher>(&self, proof: &StarkProof) -> Result<(), VerifierError> {
+        self.validate_context::<H>(&proof.context)
+    }
+
+    /// Checks that a proof generated in the specified context would use an acceptable set of
+    /// parameters.
+    pub fn validate_context<H: Hasher>(&self, context: &Context) -> Result<(), VerifierError> {
         match self {
             AcceptableOptions::MinConjecturedSecurity(minimal_security) => {
-                let proof_security = proof.security_level::<H>(true);
+                let proof_security = context.security_level::<H>(true);
                 if proof_security < *minimal_security {
                     return Err(VerifierError::InsufficientConjecturedSecurity(
                         *minimal_security,
@@ -327,7 +845,7 @@ impl AcceptableOptions {
                 }
             }
             AcceptableOptions::MinProvenSecurity(minimal_security) => {
-                let proof_security = proof.security_level::<H>(false);
+                let proof_security = context.security_level::<H>(false);
                 if proof_security < *minimal_security {
                     return Err(VerifierError::InsufficientProvenSecurity(
                         *minimal_security,
@@ -336,10 +854,11 @@ impl AcceptableOptions {
                 }
             }
             AcceptableOptions::OptionSet(options) => {
-                if !options.iter().any(|opt| opt == proof.options()) {
+                if !options.iter().any(|opt| opt == context.options()) {
                     return Err(VerifierError::UnacceptableProofOptions);
                 }
             }
+            AcceptableOptions::Policy(policy) => policy.validate_context::<H>(context)?,
         }
         Ok(())
     }
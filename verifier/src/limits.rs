@@ -0,0 +1,92 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{verify_from_reader, AcceptableOptions, Air, ElementHasher, RandomCoin, VerifierError};
+use air::proof::Context;
+use utils::{ByteReader, Deserializable, DeserializationError, SliceReader};
+
+// VERIFIER LIMITS
+// ================================================================================================
+
+/// Limits on the size of proofs and of the computations described by them, which bound the amount
+/// of memory allocated by the verifier.
+///
+/// All memory allocated by the verifier is proportional to the size of the proof, the width of
+/// the execution trace, and (for AIRs with periodic columns or sequence assertions) the length of
+/// the execution trace. Thus, by bounding these values, [verify_with_limits()] bounds the amount
+/// of memory the verifier may need, and rejects proofs exceeding the limits before any of these
+/// allocations are made. This makes it possible to run the verifier in environments with small
+/// fixed-size heaps (e.g., microcontrollers and TEEs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierLimits {
+    /// Maximum size of a serialized proof in bytes.
+    pub max_proof_size: usize,
+    /// Maximum length of the execution trace.
+    pub max_trace_length: usize,
+    /// Maximum width of the execution trace, including all auxiliary trace segments.
+    pub max_trace_width: usize,
+}
+
+// BOUNDED VERIFICATION
+// ================================================================================================
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// rejecting proofs which exceed the specified `limits`.
+///
+/// The size of the proof is checked before anything is read from it, and the dimensions of the
+/// execution trace are checked right after the proof context is read; then, the proof is
+/// verified via [verify_from_reader()](crate::verify_from_reader). Bytes following the proof in
+/// `proof_bytes` are not allowed.
+///
+/// # Errors
+/// Returns an error if the proof exceeds any of the `limits`, or for any of the reasons described
+/// in [verify_from_reader()](crate::verify_from_reader).
+pub fn verify_with_limits<AIR, HashFn, RandCoin>(
+    proof_bytes: &[u8],
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+    limits: &VerifierLimits,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    if proof_bytes.len() > limits.max_proof_size {
+        return Err(VerifierError::LimitExceeded(format!(
+            "proof size cannot be greater than {} bytes, but was {} bytes",
+            limits.max_proof_size,
+            proof_bytes.len()
+        )));
+    }
+
+    let map_err = VerifierError::ProofDeserializationError;
+    let context = Context::read_from(&mut SliceReader::new(proof_bytes)).map_err(map_err)?;
+    if context.trace_length() > limits.max_trace_length {
+        return Err(VerifierError::LimitExceeded(format!(
+            "trace length cannot be greater than {}, but was {}",
+            limits.max_trace_length,
+            context.trace_length()
+        )));
+    }
+    let trace_width =
+        context.trace_layout().main_trace_width() + context.trace_layout().aux_trace_width();
+    if trace_width > limits.max_trace_width {
+        return Err(VerifierError::LimitExceeded(format!(
+            "trace width cannot be greater than {}, but was {trace_width}",
+            limits.max_trace_width
+        )));
+    }
+
+    let mut source = SliceReader::new(proof_bytes);
+    verify_from_reader::<AIR, HashFn, RandCoin, _>(&mut source, pub_inputs, acceptable_options)?;
+    if source.has_more_bytes() {
+        return Err(VerifierError::ProofDeserializationError(
+            DeserializationError::UnconsumedBytes,
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::VerifierError;
+use air::proof::{StarkProof, TraceOpening};
+use crypto::{ElementHasher, MerkleTree, RandomCoin};
+use fri::{DefaultVerifierChannel, FriVerifier};
+use math::{FieldElement, StarkField};
+use utils::{collections::Vec, string::ToString};
+
+// TRACE OPENING VERIFICATION
+// ================================================================================================
+
+/// Verifies that the polynomial interpolated from a column of the main execution trace segment
+/// committed to in `proof` evaluates to [TraceOpening::value()] at [TraceOpening::point()].
+///
+/// The opening is checked against the commitment to the main trace segment included in `proof`
+/// only; the proof itself is NOT verified by this function. Thus, to make sure that the opened
+/// column belongs to a correct execution of the computation, the proof should be verified via
+/// [verify()](crate::verify()) as well (once, regardless of the number of openings).
+///
+/// The challenges of the opening are drawn from a public coin of type `RandCoin` seeded with the
+/// context of the proof and the opened claim, and the opening is checked with the same FRI
+/// parameters and number of queries as the proof; thus, the opening provides the same level of
+/// security as the proof.
+///
+/// # Errors
+/// Returns an error if:
+/// * The base field of `E` is not the base field of the proof.
+/// * The domain offset of the proof is not a valid offset for its LDE domain.
+/// * The opened column is not a column of the main trace segment, or the opening point is in the
+///   LDE domain of the proof.
+/// * The opening or the commitments of `proof` could not be parsed.
+/// * Openings of the main trace segment do not match the commitment included in `proof`.
+/// * The proof-of-work of the opening is invalid.
+/// * The quotient of the column polynomial by `x - point` is not of low degree, i.e., the column
+///   polynomial does not evaluate to the claimed value at the opening point.
+pub fn verify_trace_opening<HashFn, RandCoin, E>(
+    proof: &StarkProof,
+    opening: &TraceOpening<E, HashFn>,
+) -> Result<(), VerifierError>
+where
+    E: FieldElement,
+    HashFn: ElementHasher<BaseField = E::BaseField>,
+    RandCoin: RandomCoin<BaseField = E::BaseField, Hasher = HashFn>,
+{
+    let context = &proof.context;
+    if context.field_modulus_bytes() != E::BaseField::get_modulus_le_bytes() {
+        return Err(VerifierError::InconsistentBaseField);
+    }
+
+    let options = context.options();
+    let trace_layout = context.trace_layout();
+    let main_trace_width = trace_layout.main_trace_width();
+    let lde_domain_size = context.lde_domain_size();
+    if options.validate_domain_offset::<E::BaseField>(lde_domain_size).is_err() {
+        let offset = options.custom_domain_offset().expect("default domain offset is always safe");
+        return Err(VerifierError::UnsafeDomainOffset(offset));
+    }
+    let domain_offset = options.domain_offset::<E::BaseField>();
+    let fri_options = options.to_fri_options();
+
+    let column = opening.column();
+    if column >= main_trace_width {
+        return Err(VerifierError::InvalidTraceOpening(format!(
+            "column {column} is out of bounds for the main trace segment of {main_trace_width} columns"
+        )));
+    }
+    let point = opening.point();
+    let n = lde_domain_size as u64;
+    if point.exp(n.into()) == E::from(domain_offset.exp(n.into())) {
+        return Err(VerifierError::InvalidTraceOpening(
+            "opening point must not be in the LDE domain".to_string(),
+        ));
+    }
+
+    // read the commitment to the main trace segment from the proof
+    let (trace_roots, ..) = proof
+        .commitments
+        .clone()
+        .parse::<HashFn>(trace_layout.num_segments(), fri_options.num_fri_layers(lde_domain_size))
+        .map_err(VerifierError::ProofDeserializationError)?;
+    let trace_root = trace_roots[0];
+
+    // instantiate the public coin in the same way as the prover did, and use FRI layer
+    // commitments of the opening to draw FRI challenges
+    let value = opening.value();
+    let seed = TraceOpening::<E, HashFn>::coin_seed(context, column, point, value);
+    let mut public_coin = RandCoin::new(&seed);
+    public_coin.reseed(trace_root);
+
+    let mut channel = DefaultVerifierChannel::<E, HashFn>::new(
+        opening.fri_proof().clone(),
+        opening.fri_commitments().to_vec(),
+        lde_domain_size,
+        fri_options.folding_factor(),
+    )
+    .map_err(VerifierError::ProofDeserializationError)?;
+    let fri_verifier =
+        FriVerifier::new(&mut channel, &mut public_coin, fri_options, context.trace_length() - 1)
+            .map_err(VerifierError::FriVerificationFailed)?;
+
+    // check proof-of-work and draw query positions
+    let pow_nonce = opening.pow_nonce();
+    if public_coin.check_leading_zeros(pow_nonce) < options.grinding_factor() {
+        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
+    }
+    let mut positions = if options.distinct_queries() {
+        public_coin.draw_distinct_integers(options.num_queries(), lde_domain_size, pow_nonce)
+    } else {
+        public_coin.draw_integers(options.num_queries(), lde_domain_size, pow_nonce)
+    }
+    .map_err(VerifierError::RandomCoinError)?;
+    positions.sort_unstable();
+    positions.dedup();
+
+    // authenticate the rows of the main trace segment at the queried positions
+    let (trace_proof, trace_states) = opening
+        .trace_queries()
+        .clone()
+        .parse::<HashFn, E::BaseField>(lde_domain_size, positions.len(), main_trace_width)
+        .map_err(VerifierError::ProofDeserializationError)?;
+    MerkleTree::verify_batch(&trace_root, &positions, &trace_proof)
+        .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment(0))?;
+
+    // compute evaluations of the quotient (T(x) - value) / (x - point) at the queried positions,
+    // and check that they are evaluations of a polynomial of low degree
+    let domain_generator = E::BaseField::get_root_of_unity(lde_domain_size.ilog2());
+    let evaluations = positions
+        .iter()
+        .zip(trace_states.rows())
+        .map(|(&position, row)| {
+            let x = domain_generator.exp_vartime((position as u64).into()) * domain_offset;
+            (E::from(row[column]) - value) / (E::from(x) - point)
+        })
+        .collect::<Vec<_>>();
+    fri_verifier
+        .verify(&mut channel, &evaluations, &positions)
+        .map_err(VerifierError::FriVerificationFailed)
+}
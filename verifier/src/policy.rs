@@ -0,0 +1,116 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::VerifierError;
+use air::proof::Context;
+use crypto::{HashFunction, Hasher};
+use utils::collections::Vec;
+
+// OPTIONS POLICY
+// ================================================================================================
+
+/// A policy defining which proofs are acceptable by the verification procedure via a set of
+/// requirements on proof parameters.
+///
+/// A proof is acceptable if it satisfies all requirements of the policy; a policy without any
+/// requirements accepts all proofs. For example, the following policy accepts proofs providing at
+/// least 100 bits of conjectured security generated using either BLAKE3 or SHA3 hash functions:
+///
+/// ```
+/// # use winter_verifier::{crypto::HashFunction, AcceptableOptions, OptionsPolicy};
+/// let policy = OptionsPolicy::new()
+///     .with_min_conjectured_security(100)
+///     .with_hash_functions(&[HashFunction::Blake3_256, HashFunction::Sha3_256]);
+/// let acceptable_options = AcceptableOptions::Policy(policy);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptionsPolicy {
+    min_conjectured_security: Option<u32>,
+    min_proven_security: Option<u32>,
+    hash_functions: Option<Vec<HashFunction>>,
+    max_blowup_factor: Option<usize>,
+}
+
+impl OptionsPolicy {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new policy without any requirements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires proofs to provide at least the specified conjectured security level (in bits).
+    pub fn with_min_conjectured_security(mut self, min_security: u32) -> Self {
+        self.min_conjectured_security = Some(min_security);
+        self
+    }
+
+    /// Requires proofs to provide at least the specified proven security level (in bits).
+    pub fn with_min_proven_security(mut self, min_security: u32) -> Self {
+        self.min_proven_security = Some(min_security);
+        self
+    }
+
+    /// Requires proofs to be generated using one of the specified hash functions.
+    ///
+    /// Hash functions which do not have a corresponding [HashFunction] variant (e.g., algebraic
+    /// hash functions) are never accepted by a policy with this requirement.
+    pub fn with_hash_functions(mut self, hash_functions: &[HashFunction]) -> Self {
+        self.hash_functions = Some(hash_functions.to_vec());
+        self
+    }
+
+    /// Requires proofs to be generated with a blowup factor of at most the specified value.
+    pub fn with_max_blowup_factor(mut self, max_blowup_factor: usize) -> Self {
+        self.max_blowup_factor = Some(max_blowup_factor);
+        self
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that a proof generated in the specified context using hash function `H` satisfies
+    /// all requirements of this policy.
+    ///
+    /// # Errors
+    /// Returns an error describing the first requirement which is not satisfied.
+    pub fn validate_context<H: Hasher>(&self, context: &Context) -> Result<(), VerifierError> {
+        if let Some(hash_functions) = &self.hash_functions {
+            match H::HASH_FUNCTION {
+                Some(hash_fn) if hash_functions.contains(&hash_fn) => (),
+                _ => return Err(VerifierError::UnacceptableHashFunction),
+            }
+        }
+        if let Some(max_blowup_factor) = self.max_blowup_factor {
+            let blowup_factor = context.options().blowup_factor();
+            if blowup_factor > max_blowup_factor {
+                return Err(VerifierError::UnacceptableBlowupFactor(
+                    max_blowup_factor,
+                    blowup_factor,
+                ));
+            }
+        }
+        if let Some(min_security) = self.min_conjectured_security {
+            let proof_security = context.security_level::<H>(true);
+            if proof_security < min_security {
+                return Err(VerifierError::InsufficientConjecturedSecurity(
+                    min_security,
+                    proof_security,
+                ));
+            }
+        }
+        if let Some(min_security) = self.min_proven_security {
+            let proof_security = context.security_level::<H>(false);
+            if proof_security < min_security {
+                return Err(VerifierError::InsufficientProvenSecurity(
+                    min_security,
+                    proof_security,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{verify_from_bytes, AcceptableOptions, Air, ElementHasher, RandomCoin, VerifierError};
+use air::proof::Context;
+use utils::{collections::BTreeMap, Deserializable, SliceReader};
+
+// AIR REGISTRY
+// ================================================================================================
+
+/// Signature of a function verifying a serialized proof against serialized public inputs.
+type VerifyFn = fn(&[u8], &[u8], &AcceptableOptions) -> Result<(), VerifierError>;
+
+/// A registry of AIRs which dispatches proofs of different computations to the AIRs they were
+/// generated for.
+///
+/// Every AIR is registered under its [Air::AIR_ID] together with the hash function and the random
+/// coin used to generate its proofs, and the set of acceptable proof options. A proof is then
+/// verified via [AirRegistry::verify_any()]: the AIR identifier is read from the context of the
+/// proof, and the proof and its public inputs are deserialized and verified using the AIR
+/// registered under this identifier.
+#[derive(Default)]
+pub struct AirRegistry {
+    verifiers: BTreeMap<u32, (VerifyFn, AcceptableOptions)>,
+}
+
+impl AirRegistry {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new registry with no registered AIRs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // REGISTRATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers `AIR` under its [Air::AIR_ID]; proofs for this AIR will be verified using hash
+    /// function `HashFn` and random coin `RandCoin`, and must be generated with one of the
+    /// `acceptable_options`.
+    ///
+    /// # Panics
+    /// Panics if an AIR with the same identifier has already been registered.
+    pub fn register<AIR, HashFn, RandCoin>(&mut self, acceptable_options: AcceptableOptions)
+    where
+        AIR: Air,
+        AIR::PublicInputs: Deserializable,
+        HashFn: ElementHasher<BaseField = AIR::BaseField>,
+        RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    {
+        let verify_fn: VerifyFn = verify_from_bytes::<AIR, HashFn, RandCoin>;
+        let previous = self.verifiers.insert(AIR::AIR_ID, (verify_fn, acceptable_options));
+        assert!(previous.is_none(), "AIR with identifier {} is already registered", AIR::AIR_ID);
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if an AIR is registered under the specified identifier.
+    pub fn contains(&self, air_id: u32) -> bool {
+        self.verifiers.contains_key(&air_id)
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies the serialized proof against the serialized public inputs using the AIR
+    /// registered under the AIR identifier of the proof.
+    ///
+    /// Once the AIR is determined, this is equivalent to calling
+    /// [verify_from_bytes()](crate::verify_from_bytes) for it.
+    ///
+    /// # Errors
+    /// Returns an error if the context of the proof could not be read, if no AIR is registered
+    /// under the AIR identifier of the proof, or if the verification against the registered AIR
+    /// fails.
+    pub fn verify_any(
+        &self,
+        proof_bytes: &[u8],
+        pub_inputs_bytes: &[u8],
+    ) -> Result<(), VerifierError> {
+        let context = Context::read_from(&mut SliceReader::new(proof_bytes))
+            .map_err(VerifierError::ProofDeserializationError)?;
+        let (verify_fn, acceptable_options) = self
+            .verifiers
+            .get(&context.air_id())
+            .ok_or(VerifierError::UnknownAirId(context.air_id()))?;
+        verify_fn(proof_bytes, pub_inputs_bytes, acceptable_options)
+    }
+}
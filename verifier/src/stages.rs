@@ -0,0 +1,192 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Individual stages of the verification procedure, intended for debugging.
+//!
+//! **These functions do not verify proofs.** Each of them replays the entire protocol transcript
+//! of the proof (so that all values drawn from the public coin are the same as during full
+//! verification), but performs only the checks of a single stage and skips all others. They are
+//! meant to be used by debugging tools and research code to pinpoint which stage of a failing
+//! proof is wrong. A proof is valid only if [verify()](crate::verify) succeeds for it.
+//!
+//! In addition, [get_query_positions()] replays the transcript to recover the positions queried
+//! by the verifier; these are needed to build witnesses for verifying a proof inside of another
+//! computation (see the `gadgets` module of the `winter-air` crate).
+
+use super::{
+    verify_proof, AcceptableOptions, Air, ElementHasher, RandomCoin, StarkProof, Vec, VerifierError,
+};
+
+// CHECKS
+// ================================================================================================
+
+/// Specifies which checks are performed by the verification procedure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Checks {
+    /// Constraint evaluations over the out-of-domain frame are consistent with the evaluations of
+    /// the composition polynomial columns.
+    pub ood_consistency: bool,
+    /// Query proof-of-work nonce satisfies the grinding factor.
+    pub proof_of_work: bool,
+    /// Trace and constraint queries match the trace and constraint commitments.
+    pub queries: bool,
+    /// DEEP composition polynomial evaluations at queried positions are consistent with the FRI
+    /// proof.
+    pub fri: bool,
+}
+
+impl Checks {
+    /// All checks; a proof is valid only if it passes all of them.
+    pub const ALL: Self = Self {
+        ood_consistency: true,
+        proof_of_work: true,
+        queries: true,
+        fri: true,
+    };
+
+    /// No checks; the transcript is replayed without checking anything.
+    const NONE: Self = Self {
+        ood_consistency: false,
+        proof_of_work: false,
+        queries: false,
+        fri: false,
+    };
+}
+
+// STAGES
+// ================================================================================================
+
+/// Checks only that constraints evaluated over the out-of-domain frame of the `proof` are
+/// consistent with the out-of-domain evaluations of the constraint composition polynomial
+/// columns.
+///
+/// **This does not verify the proof**; see [module documentation](self) for details.
+///
+/// # Errors
+/// Returns an error if the proof was generated with unacceptable parameters, if the proof is
+/// malformed, or if the out-of-domain evaluations are inconsistent.
+pub fn check_ood_consistency<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let checks = Checks {
+        ood_consistency: true,
+        ..Checks::NONE
+    };
+    verify_proof::<AIR, HashFn, RandCoin, _>(
+        proof,
+        pub_inputs,
+        RandCoin::new,
+        acceptable_options,
+        checks,
+    )
+    .map(|_| ())
+}
+
+/// Checks only that the trace and constraint queries of the `proof` match the trace and
+/// constraint commitments.
+///
+/// **This does not verify the proof**; see [module documentation](self) for details.
+///
+/// # Errors
+/// Returns an error if the proof was generated with unacceptable parameters, if the proof is
+/// malformed, or if Merkle authentication paths of the queries do not resolve to the
+/// commitments.
+pub fn check_trace_queries<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let checks = Checks {
+        queries: true,
+        ..Checks::NONE
+    };
+    verify_proof::<AIR, HashFn, RandCoin, _>(
+        proof,
+        pub_inputs,
+        RandCoin::new,
+        acceptable_options,
+        checks,
+    )
+    .map(|_| ())
+}
+
+/// Checks only that the evaluations of the DEEP composition polynomial at the queried positions
+/// (computed from the queries and the out-of-domain frame of the `proof`) are consistent with
+/// the FRI proof.
+///
+/// **This does not verify the proof**; see [module documentation](self) for details.
+///
+/// # Errors
+/// Returns an error if the proof was generated with unacceptable parameters, if the proof is
+/// malformed, or if verification of the FRI proof fails.
+pub fn check_fri<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let checks = Checks {
+        fri: true,
+        ..Checks::NONE
+    };
+    verify_proof::<AIR, HashFn, RandCoin, _>(
+        proof,
+        pub_inputs,
+        RandCoin::new,
+        acceptable_options,
+        checks,
+    )
+    .map(|_| ())
+}
+
+// TRANSCRIPT REPLAY
+// ================================================================================================
+
+/// Returns the positions of the LDE domain queried by the verifier for the `proof`, sorted and
+/// without duplicates.
+///
+/// The positions are in the same order as the trace states, the constraint evaluations, and the
+/// leaves of the batch Merkle proofs included in the proof; thus, the proof openings can be
+/// split into individual Merkle authentication paths using these positions.
+///
+/// **This does not verify the proof**; see [module documentation](self) for details.
+///
+/// # Errors
+/// Returns an error if the proof was generated with unacceptable parameters, or if the proof is
+/// malformed.
+pub fn get_query_positions<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<Vec<usize>, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    verify_proof::<AIR, HashFn, RandCoin, _>(
+        proof,
+        pub_inputs,
+        RandCoin::new,
+        acceptable_options,
+        Checks::NONE,
+    )
+}
@@ -0,0 +1,860 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    stages, verify, verify_batch, verify_from_bytes, verify_from_reader, verify_trace_opening,
+    verify_with_limits, verify_with_public_coin, verify_with_transcript, AcceptableOptions,
+    CostObjective, OptionsPolicy, VerifierCostEstimate, VerifierError, VerifierLimits,
+};
+use air::{
+    proof::{Context, ProofMetadata, StarkProof, TraceOpening},
+    Air, AuxTraceRandElements, ConstraintCompositionCoefficients, FieldExtension, ProofOptions,
+    TraceInfo,
+};
+use crypto::{DefaultRandomCoin, HashFunction, ScriptedRandomCoin, TranscriptEvent};
+use math::{FieldElement, StarkField};
+use prover::{
+    matrix::ColMatrix,
+    testing::{build_mock_trace, mutate_proof, BaseElement, Blake3, MockAir, MockProver},
+    DefaultConstraintEvaluator, DefaultTraceLde, Prover, StarkDomain, Trace, TracePolyTable,
+    TraceTable,
+};
+use utils::{collections::Vec, Deserializable, DeserializationError, Serializable, SliceReader};
+
+type Coin = DefaultRandomCoin<Blake3>;
+
+// VERIFICATION TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_custom_domain_offset() {
+    let options = build_options().with_domain_offset(7);
+    let (proof, result) = build_proof(options.clone(), 16);
+    assert_eq!(Some(7), proof.options().custom_domain_offset());
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options, build_options()]);
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert!(verify::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options).is_ok());
+
+    // the offset is a part of the statement; claiming a different offset for the same proof fails
+    let mut forged = proof.clone();
+    forged.context = Context::new::<BaseElement>(&proof.get_trace_info(), build_options())
+        .set_air_id(proof.context.air_id())
+        .set_air_fingerprint(*proof.context.air_fingerprint());
+    assert!(verify::<MockAir, Blake3, Coin>(forged, result, &acceptable_options).is_err());
+}
+
+#[test]
+#[cfg(feature = "dispatch")]
+fn verify_with_runtime_hash_fn() {
+    use super::verify_with_hash_fn;
+
+    let (proof, result) = build_proof(build_options(), 16);
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    let result_with = |hash_fn| {
+        verify_with_hash_fn::<MockAir>(proof.clone(), result, hash_fn, &acceptable_options)
+    };
+    assert!(result_with(HashFunction::Blake3_256).is_ok());
+
+    // verification with a different hash function should fail
+    assert!(result_with(HashFunction::Sha3_256).is_err());
+}
+
+#[test]
+fn verify_proof_batch() {
+    let mut batch = [16, 32, 16]
+        .iter()
+        .map(|&length| build_proof(build_options(), length))
+        .collect::<Vec<_>>();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    let verify = |batch| verify_batch::<MockAir, Blake3, Coin>(batch, &acceptable_options);
+    assert!(verify(batch.clone()).is_ok());
+
+    // a proof verified against wrong public inputs should fail the entire batch
+    batch[1].1 += BaseElement::ONE;
+    assert!(matches!(verify(batch), Err(VerifierError::BatchProofVerificationFailed(1, _))));
+}
+
+#[test]
+fn verify_proof_from_reader() {
+    let (proof, result) = build_proof(build_options(), 16);
+    let context_size = proof.context.to_bytes().len();
+    let proof_bytes = proof.to_bytes();
+    let verify = |bytes: &[u8], acceptable_options: &AcceptableOptions| {
+        verify_from_reader::<MockAir, Blake3, Coin, _>(
+            &mut SliceReader::new(bytes),
+            result,
+            acceptable_options,
+        )
+    };
+
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    assert!(verify(&proof_bytes, &acceptable_options).is_ok());
+
+    // a proof with unacceptable options is rejected after reading just the context, even if the
+    // rest of the proof is missing
+    let other_options = AcceptableOptions::OptionSet(vec![build_extension_options()]);
+    assert_eq!(
+        Err(VerifierError::UnacceptableProofOptions),
+        verify(&proof_bytes[..context_size], &other_options)
+    );
+
+    // a truncated proof is rejected
+    assert!(matches!(
+        verify(&proof_bytes[..proof_bytes.len() - 1], &acceptable_options),
+        Err(VerifierError::ProofDeserializationError(_))
+    ));
+}
+
+#[test]
+fn verify_proof_from_bytes() {
+    let (proof, result) = build_proof(build_options(), 16);
+    let proof_bytes = proof.to_bytes();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    let verify = |proof_bytes: &[u8], pub_inputs_bytes: &[u8]| {
+        verify_from_bytes::<MockAir, Blake3, Coin>(
+            proof_bytes,
+            pub_inputs_bytes,
+            &acceptable_options,
+        )
+    };
+
+    let pub_inputs_bytes = result.to_bytes();
+    assert!(verify(&proof_bytes, &pub_inputs_bytes).is_ok());
+
+    // trailing bytes after the public inputs or after the proof are rejected
+    let mut longer_bytes = pub_inputs_bytes.clone();
+    longer_bytes.push(0);
+    assert_eq!(
+        Err(VerifierError::PublicInputsDeserializationError(
+            DeserializationError::UnconsumedBytes
+        )),
+        verify(&proof_bytes, &longer_bytes)
+    );
+    let mut longer_bytes = proof_bytes.clone();
+    longer_bytes.push(0);
+    assert_eq!(
+        Err(VerifierError::ProofDeserializationError(DeserializationError::UnconsumedBytes)),
+        verify(&longer_bytes, &pub_inputs_bytes)
+    );
+}
+
+#[test]
+fn verify_proof_with_metadata() {
+    let (mut proof, result) = build_proof(build_options(), 16);
+    assert!(proof.metadata.is_empty());
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    let verify = |proof_bytes: &[u8]| {
+        verify_from_bytes::<MockAir, Blake3, Coin>(
+            proof_bytes,
+            &result.to_bytes(),
+            &acceptable_options,
+        )
+    };
+
+    // metadata is preserved through serialization
+    proof.metadata.insert(ProofMetadata::PROVER_VERSION, "0.7.0");
+    proof.metadata.insert(ProofMetadata::CIRCUIT_ID, "mock");
+    let proof_bytes = proof.to_bytes();
+    let parsed_proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert_eq!(Some("mock"), parsed_proof.metadata.get(ProofMetadata::CIRCUIT_ID));
+    assert_eq!(proof.metadata, parsed_proof.metadata);
+    assert!(verify(&proof_bytes).is_ok());
+
+    // metadata is not bound to the transcript, and so changing it does not invalidate the proof
+    proof.metadata.insert(ProofMetadata::CIRCUIT_ID, "something else");
+    assert!(verify(&proof.to_bytes()).is_ok());
+}
+
+#[test]
+fn verify_salted_proof() {
+    let prover = MockProver::<Blake3>::new(build_options()).with_salted_transcript();
+    let trace = build_mock_trace(2, 16);
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+
+    // the salt is carried in the proof and is bound to the transcript
+    let parsed_proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert!(verify::<MockAir, Blake3, Coin>(parsed_proof, result, &acceptable_options).is_ok());
+    let mut forged = proof;
+    forged.context = forged.context.set_salt([0; 32]);
+    assert!(verify::<MockAir, Blake3, Coin>(forged, result, &acceptable_options).is_err());
+}
+
+#[test]
+fn verify_proof_with_public_coin() {
+    let elements = (1..=64).map(|i| BaseElement::new(i * 7 + 3)).collect::<Vec<_>>();
+    let integers = (0..28).map(|i| (i * 5 + 1) % 64).collect::<Vec<_>>();
+    let public_coin = ScriptedRandomCoin::<Blake3>::from_challenges(elements, integers);
+
+    let prover = ScriptedMockProver {
+        prover: MockProver::new(build_options()),
+        public_coin: public_coin.clone(),
+    };
+    let trace = build_mock_trace(2, 16);
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    let verify_with_coin = |public_coin| {
+        verify_with_public_coin::<MockAir, Blake3, ScriptedRandomCoin<Blake3>>(
+            proof.clone(),
+            result,
+            public_coin,
+            &acceptable_options,
+        )
+    };
+
+    // the proof verifies when the verifier supplies the same challenges as the prover
+    assert!(verify_with_coin(public_coin).is_ok());
+
+    // but not when the challenges differ or are derived from the transcript
+    let elements = (1..=64).map(|i| BaseElement::new(i * 11 + 5)).collect::<Vec<_>>();
+    let integers = (0..28).map(|i| (i * 5 + 1) % 64).collect::<Vec<_>>();
+    let other_coin = ScriptedRandomCoin::<Blake3>::from_challenges(elements, integers);
+    assert!(verify_with_coin(other_coin).is_err());
+    assert!(verify::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options).is_err());
+}
+
+#[test]
+#[cfg(feature = "dispatch")]
+fn verify_with_air_registry() {
+    use super::AirRegistry;
+
+    let (mut proof, result) = build_proof(build_options(), 16);
+    let pub_inputs_bytes = result.to_bytes();
+
+    let mut registry = AirRegistry::new();
+    registry.register::<MockAir, Blake3, Coin>(AcceptableOptions::OptionSet(vec![build_options()]));
+    assert!(registry.contains(MockAir::AIR_ID));
+    assert!(registry.verify_any(&proof.to_bytes(), &pub_inputs_bytes).is_ok());
+
+    // a proof with a different AIR identifier is not dispatched to the AIR
+    proof.context = proof.context.clone().set_air_id(MockAir::AIR_ID + 7);
+    assert_eq!(
+        Err(VerifierError::UnknownAirId(MockAir::AIR_ID + 7)),
+        registry.verify_any(&proof.to_bytes(), &pub_inputs_bytes)
+    );
+
+    // and direct verification against the AIR rejects it as well
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    assert_eq!(
+        Err(VerifierError::UnexpectedAirId(MockAir::AIR_ID, MockAir::AIR_ID + 7)),
+        verify::<MockAir, Blake3, Coin>(proof, result, &acceptable_options)
+    );
+}
+
+#[test]
+fn verify_air_fingerprint() {
+    let (mut proof, result) = build_proof(build_options(), 16);
+
+    // the proof is bound to the structure of the AIR it was generated for
+    let air = MockAir::new(proof.get_trace_info(), result, proof.options().clone());
+    assert_eq!(&air.fingerprint(), proof.context.air_fingerprint());
+
+    // AIRs instantiated with different proof options have different structure
+    let other_air = MockAir::new(proof.get_trace_info(), result, build_extension_options());
+    assert_ne!(air.fingerprint(), other_air.fingerprint());
+
+    // a proof claiming to be generated for a structurally different AIR is rejected
+    proof.context = proof.context.clone().set_air_fingerprint(other_air.fingerprint());
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    assert_eq!(
+        Err(VerifierError::InconsistentAirFingerprint),
+        verify::<MockAir, Blake3, Coin>(proof, result, &acceptable_options)
+    );
+}
+
+#[test]
+fn verify_fri_layer_grinding() {
+    let options = build_options().with_fri_layer_grinding_factor(8);
+    let (proof, result) = build_proof(options.clone(), 16);
+    assert_eq!(proof.fri_proof.num_layers(), proof.fri_proof.layer_nonces().len());
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options]);
+    assert!(verify::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options).is_ok());
+
+    // a proof with an invalid layer nonce is rejected; nonces are serialized at the end of the
+    // FRI proof
+    let mut tampered_proof = proof;
+    let mut fri_proof_bytes = tampered_proof.fri_proof.to_bytes();
+    let nonce_start = fri_proof_bytes.len() - 8;
+    fri_proof_bytes[nonce_start] ^= 1;
+    tampered_proof.fri_proof = Deserializable::read_from_bytes(&fri_proof_bytes).unwrap();
+    assert!(verify::<MockAir, Blake3, Coin>(tampered_proof, result, &acceptable_options).is_err());
+}
+
+#[test]
+fn verify_multiple_ood_points() {
+    let (single_point_proof, _) = build_proof(build_options(), 16);
+    let options = build_options().with_num_ood_points(3);
+    let (proof, result) = build_proof(options.clone(), 16);
+    assert_eq!(3, proof.options().num_ood_points());
+    assert!(proof.ood_frame.to_bytes().len() > 2 * single_point_proof.ood_frame.to_bytes().len());
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options]);
+    assert!(verify::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options).is_ok());
+
+    // evaluations at every point are checked; the last element of the frame is the evaluation
+    // of the last composition column at the last point
+    let mut ood_frame_bytes = proof.ood_frame.to_bytes();
+    let last_element_start = ood_frame_bytes.len() - BaseElement::ELEMENT_BYTES;
+    ood_frame_bytes[last_element_start] ^= 1;
+    let mut tampered_proof = proof;
+    tampered_proof.ood_frame = Deserializable::read_from_bytes(&ood_frame_bytes).unwrap();
+    assert!(matches!(
+        verify::<MockAir, Blake3, Coin>(tampered_proof, result, &acceptable_options),
+        Err(VerifierError::InconsistentOodConstraintEvaluations(..))
+    ));
+}
+
+#[test]
+fn verify_deep_coefficient_powers() {
+    let options = build_options();
+    let power_options = options.clone().with_deep_coefficient_powers(true);
+    let (proof, result) = build_proof(power_options.clone(), 16);
+    assert!(proof.options().deep_coefficient_powers());
+    let acceptable_options = AcceptableOptions::OptionSet(vec![power_options.clone()]);
+    assert!(verify::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options).is_ok());
+
+    // a single DEEP challenge is drawn instead of one challenge per column
+    let estimate = |options: ProofOptions| {
+        let air = MockAir::new(proof.get_trace_info(), result, options);
+        VerifierCostEstimate::new::<_, Blake3>(&air)
+    };
+    let random_estimate = estimate(options);
+    let power_estimate = estimate(power_options);
+    assert!(power_estimate.coin_hashes < random_estimate.coin_hashes);
+    assert_eq!(random_estimate.proof_size, power_estimate.proof_size);
+}
+
+#[test]
+fn verify_with_options_policy() {
+    let (proof, result) = build_proof(build_options(), 16);
+    let security = proof.security_level::<Blake3>(true);
+    let verify_with_policy = |policy: OptionsPolicy| {
+        verify::<MockAir, Blake3, Coin>(proof.clone(), result, &AcceptableOptions::Policy(policy))
+    };
+
+    let policy = OptionsPolicy::new()
+        .with_min_conjectured_security(security)
+        .with_hash_functions(&[HashFunction::Blake3_256, HashFunction::Sha3_256])
+        .with_max_blowup_factor(8);
+    assert!(verify_with_policy(policy.clone()).is_ok());
+
+    // proofs which do not satisfy any of the requirements are rejected
+    assert_eq!(
+        Err(VerifierError::InsufficientConjecturedSecurity(security + 1, security)),
+        verify_with_policy(policy.clone().with_min_conjectured_security(security + 1))
+    );
+    assert_eq!(
+        Err(VerifierError::UnacceptableHashFunction),
+        verify_with_policy(policy.clone().with_hash_functions(&[HashFunction::Sha3_256]))
+    );
+    assert_eq!(
+        Err(VerifierError::UnacceptableBlowupFactor(4, 8)),
+        verify_with_policy(policy.with_max_blowup_factor(4))
+    );
+}
+
+#[test]
+fn verify_proof_with_limits() {
+    let (proof, result) = build_proof(build_options(), 16);
+    let proof_bytes = proof.to_bytes();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    let verify = |limits: &VerifierLimits| {
+        verify_with_limits::<MockAir, Blake3, Coin>(
+            &proof_bytes,
+            result,
+            &acceptable_options,
+            limits,
+        )
+    };
+
+    let limits = VerifierLimits {
+        max_proof_size: proof_bytes.len(),
+        max_trace_length: 16,
+        max_trace_width: 2,
+    };
+    assert!(verify(&limits).is_ok());
+
+    // proofs exceeding any of the limits are rejected
+    let smaller_limits = [
+        VerifierLimits {
+            max_proof_size: proof_bytes.len() - 1,
+            ..limits
+        },
+        VerifierLimits {
+            max_trace_length: 8,
+            ..limits
+        },
+        VerifierLimits {
+            max_trace_width: 1,
+            ..limits
+        },
+    ];
+    for limits in smaller_limits.iter() {
+        assert!(matches!(verify(limits), Err(VerifierError::LimitExceeded(_))));
+    }
+}
+
+#[test]
+fn verify_in_stages() {
+    let (proof, result) = build_proof(build_options(), 16);
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+
+    let check_all = |result| {
+        [
+            stages::check_ood_consistency::<MockAir, Blake3, Coin>(
+                proof.clone(),
+                result,
+                &acceptable_options,
+            ),
+            stages::check_trace_queries::<MockAir, Blake3, Coin>(
+                proof.clone(),
+                result,
+                &acceptable_options,
+            ),
+            stages::check_fri::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options),
+        ]
+    };
+
+    assert!(check_all(result).iter().all(|result| result.is_ok()));
+
+    // wrong public inputs change the transcript, and thus, every stage detects them
+    let [ood, queries, fri] = check_all(result + BaseElement::ONE);
+    assert!(matches!(ood, Err(VerifierError::InconsistentOodConstraintEvaluations(_, _))));
+    assert!(matches!(queries, Err(VerifierError::TraceQueryDoesNotMatchCommitment(0))));
+    assert!(fri.is_err());
+}
+
+#[test]
+fn verify_mutated_proofs() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options.clone()]);
+    let prover = MockProver::<Blake3>::new(options);
+
+    // a valid trace of any width yields a proof which passes verification
+    for width in [2, 5] {
+        let trace = build_mock_trace(width, 64);
+        let result = prover.get_pub_inputs(&trace);
+        let proof = prover.prove(trace).unwrap();
+        let verified = verify::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options);
+        assert!(verified.is_ok());
+
+        // every mutation of the proof is rejected
+        for mutation in prover::testing::ProofMutation::ALL {
+            let verified = verify::<MockAir, Blake3, Coin>(
+                mutate_proof(&proof, mutation),
+                result,
+                &acceptable_options,
+            );
+            assert!(verified.is_err(), "{mutation:?} was not rejected");
+        }
+    }
+}
+
+#[test]
+fn verify_trace_column_opening() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let trace = build_mock_trace(2, 64);
+    let proof = prover.prove(trace.clone()).unwrap();
+
+    // openings at points of the trace domain are accepted
+    let g = BaseElement::get_root_of_unity(trace.length().ilog2());
+    for (column, step) in [(0, 0), (1, 5), (0, trace.length() - 1)] {
+        let opening = prover.open_trace_column(&trace, &proof, column, g.exp(step as u64)).unwrap();
+        assert!(verify_trace_opening::<Blake3, Coin, _>(&proof, &opening).is_ok());
+    }
+
+    // an opening at a point outside of the trace domain survives serialization
+    let opening = prover.open_trace_column(&trace, &proof, 1, BaseElement::new(11)).unwrap();
+    let opening = TraceOpening::<BaseElement, Blake3>::from_bytes(&opening.to_bytes()).unwrap();
+    assert!(verify_trace_opening::<Blake3, Coin, _>(&proof, &opening).is_ok());
+
+    // a wrong value or a different column is rejected
+    let rebuild = |column: usize, value: BaseElement| {
+        TraceOpening::<BaseElement, Blake3>::new(
+            column,
+            opening.point(),
+            value,
+            opening.fri_commitments().to_vec(),
+            opening.pow_nonce(),
+            opening.trace_queries().clone(),
+            opening.fri_proof().clone(),
+        )
+    };
+    let forged = rebuild(1, opening.value() + BaseElement::ONE);
+    assert!(verify_trace_opening::<Blake3, Coin, _>(&proof, &forged).is_err());
+    let forged = rebuild(0, opening.value());
+    assert!(verify_trace_opening::<Blake3, Coin, _>(&proof, &forged).is_err());
+    let forged = rebuild(2, opening.value());
+    let err = verify_trace_opening::<Blake3, Coin, _>(&proof, &forged).unwrap_err();
+    assert_eq!("verifier.invalid_trace_opening", err.code());
+}
+
+#[test]
+fn verify_evm_profile() {
+    use air::{proof::evm, ProofOptionsBuilder};
+    type Keccak256 = crypto::hashers::Keccak256<BaseElement>;
+    type F128 = math::fields::f128::BaseElement;
+
+    // grinding factor is reduced to keep the test fast
+    let options = ProofOptionsBuilder::evm_friendly().with_grinding_factor(4).build().unwrap();
+    let prover = MockProver::<Keccak256>::new(options.clone());
+    let trace = build_mock_trace(2, 64);
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options]);
+
+    // the proof is encoded as a sequence of words which decode back into the same proof
+    let calldata = proof.to_evm_calldata::<Keccak256>().unwrap();
+    assert_eq!(0, calldata.len() % evm::WORD_SIZE);
+    let decoded = StarkProof::from_evm_calldata::<BaseElement, Keccak256>(&calldata).unwrap();
+    assert_eq!(proof, decoded);
+    let verified = verify::<MockAir, Keccak256, DefaultRandomCoin<Keccak256>>(
+        decoded,
+        result,
+        &acceptable_options,
+    );
+    assert!(verified.is_ok());
+
+    // the proof can also be verified with the hash function selected at runtime
+    #[cfg(feature = "dispatch")]
+    assert!(super::verify_with_hash_fn::<MockAir>(
+        proof.clone(),
+        result,
+        HashFunction::Keccak256,
+        &acceptable_options,
+    )
+    .is_ok());
+
+    // calldata encoded for a different field or truncated calldata is rejected
+    assert!(StarkProof::from_evm_calldata::<F128, Keccak256>(&calldata).is_err());
+    let truncated = &calldata[..calldata.len() - evm::WORD_SIZE];
+    assert!(StarkProof::from_evm_calldata::<BaseElement, Keccak256>(truncated).is_err());
+    let unaligned = &calldata[..calldata.len() - 1];
+    assert!(StarkProof::from_evm_calldata::<BaseElement, Keccak256>(unaligned).is_err());
+
+    // public inputs are committed to by a single BN254 scalar
+    let pub_inputs_hash = evm::hash_public_inputs(&result);
+    assert_eq!(0, pub_inputs_hash[0] >> 5);
+    assert_ne!(pub_inputs_hash, evm::hash_public_inputs(&(result + BaseElement::ONE)));
+}
+
+// ERROR TESTS
+// ================================================================================================
+
+#[test]
+fn error_sources() {
+    use std::error::Error;
+
+    let (proof, result) = build_proof(build_options(), 16);
+    let proof_bytes = proof.to_bytes();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+
+    // a truncated proof is rejected with the deserialization error as the cause
+    let err = verify_from_bytes::<MockAir, Blake3, Coin>(
+        &proof_bytes[..proof_bytes.len() - 1],
+        &result.to_bytes(),
+        &acceptable_options,
+    )
+    .unwrap_err();
+    assert_eq!("verifier.proof_deserialization", err.code());
+    let source = err.source().and_then(|err| err.downcast_ref::<DeserializationError>());
+    assert_eq!(Some("deserialization.unexpected_eof"), source.map(DeserializationError::code));
+
+    // errors of proofs in a batch are chained to the error of the batch
+    let err = verify_batch::<MockAir, Blake3, Coin>(
+        vec![(proof, result + BaseElement::ONE)],
+        &acceptable_options,
+    )
+    .unwrap_err();
+    assert_eq!("verifier.batch_proof_verification_failed", err.code());
+    let source = err.source().and_then(|err| err.downcast_ref::<VerifierError>()).unwrap();
+    assert!(source.code().starts_with("verifier."));
+}
+
+#[test]
+fn verbose_error_messages() {
+    let (proof, result) = build_proof(build_options(), 16);
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+
+    // a proof verified against wrong public inputs fails the out-of-domain consistency check
+    let err =
+        verify::<MockAir, Blake3, Coin>(proof, result + BaseElement::ONE, &acceptable_options)
+            .unwrap_err();
+    assert!(matches!(err, VerifierError::InconsistentOodConstraintEvaluations(_, _)));
+
+    // the default message is terse, while the alternate message includes the mismatched values
+    let terse = format!("{err}");
+    let verbose = format!("{err:#}");
+    assert_eq!("constraint evaluations over the out-of-domain frame are inconsistent", terse);
+    assert!(verbose.starts_with(&terse));
+    assert!(verbose.contains("composition polynomial columns evaluated to"));
+
+    let err = VerifierError::TraceQueryDoesNotMatchCommitment(1);
+    assert_eq!("trace query did not match the commitment", format!("{err}"));
+    assert_eq!(
+        "trace query did not match the commitment to trace segment 1",
+        format!("{err:#}")
+    );
+}
+
+// COST ESTIMATE TESTS
+// ================================================================================================
+
+#[test]
+fn cost_estimate() {
+    let (proof, result) = build_proof(build_options(), 32);
+    let air = MockAir::new(proof.get_trace_info(), result, build_options());
+    let estimate = VerifierCostEstimate::new::<_, Blake3>(&air);
+
+    // estimates are upper bounds on the actual proof size
+    assert!(estimate.proof_size >= proof.to_bytes().len());
+    assert!(estimate.merkle_hashes > estimate.coin_hashes);
+    assert_eq!(estimate.coin_hashes + estimate.merkle_hashes, estimate.hash_invocations());
+
+    // fewer queries make the verification cheaper
+    let options = ProofOptions::new(14, 8, 0, FieldExtension::None, 4, 7);
+    let air = MockAir::new(proof.get_trace_info(), result, options);
+    let cheaper = VerifierCostEstimate::new::<_, Blake3>(&air);
+    assert!(cheaper.hash_invocations() < estimate.hash_invocations());
+    assert!(cheaper.field_mults < estimate.field_mults);
+    assert!(cheaper.proof_size < estimate.proof_size);
+}
+
+#[test]
+fn cost_estimate_optimize_fri_options() {
+    let (proof, result) = build_proof(build_options(), 32);
+    let trace_info = proof.get_trace_info();
+    let build_air = |options| MockAir::new(trace_info.clone(), result, options);
+    let estimate = |options| VerifierCostEstimate::new::<_, Blake3>(&build_air(options));
+
+    let options = build_options();
+    let smaller = VerifierCostEstimate::optimize_fri_options::<_, Blake3, _>(
+        &options,
+        CostObjective::ProofSize,
+        &build_air,
+    );
+    assert!(estimate(smaller.clone()).proof_size <= estimate(options.clone()).proof_size);
+
+    let cheaper = VerifierCostEstimate::optimize_fri_options::<_, Blake3, _>(
+        &options,
+        CostObjective::HashInvocations,
+        &build_air,
+    );
+    assert!(
+        estimate(cheaper.clone()).hash_invocations()
+            <= estimate(options.clone()).hash_invocations()
+    );
+
+    // only FRI parameters are changed, and proofs generated with the chosen options can be
+    // verified
+    for optimized in [smaller, cheaper] {
+        assert_eq!(options.num_queries(), optimized.num_queries());
+        assert_eq!(options.blowup_factor(), optimized.blowup_factor());
+        assert_eq!(options.grinding_factor(), optimized.grinding_factor());
+        let (proof, result) = build_proof(optimized.clone(), 32);
+        let acceptable_options = AcceptableOptions::OptionSet(vec![optimized]);
+        assert!(verify::<MockAir, Blake3, Coin>(proof, result, &acceptable_options).is_ok());
+    }
+}
+
+// TRANSCRIPT TESTS
+// ================================================================================================
+
+#[test]
+fn transcripts_of_prover_and_verifier() {
+    let prover = MockProver::<Blake3>::new(build_options());
+    let trace = build_mock_trace(2, 32);
+    let result = prover.get_pub_inputs(&trace);
+    let (proof, prover_transcript) = prover.prove_with_transcript(trace).unwrap();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+
+    // for a valid proof, the prover and the verifier perform the same operations on their coins
+    let (verified, verifier_transcript) =
+        verify_with_transcript::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options);
+    assert!(verified.is_ok());
+    assert_eq!(None, prover_transcript.first_divergence(&verifier_transcript));
+    assert_eq!(prover_transcript.to_string(), verifier_transcript.to_string());
+    assert!(matches!(prover_transcript.events()[0], TranscriptEvent::Seed(_)));
+    assert!(matches!(
+        prover_transcript.events().last(),
+        Some(TranscriptEvent::DrawIntegers { .. })
+    ));
+
+    // a corrupted OOD frame changes the data the verifier absorbs after drawing the OOD point
+    let invalid_proof = mutate_proof(&proof, prover::testing::ProofMutation::OodTraceState);
+    let (verified, verifier_transcript) =
+        verify_with_transcript::<MockAir, Blake3, Coin>(invalid_proof, result, &acceptable_options);
+    assert!(verified.is_err());
+    let index = prover_transcript.first_divergence(&verifier_transcript).unwrap();
+    assert!(index > 0);
+    assert_eq!(prover_transcript.events()[..index], verifier_transcript.events()[..index]);
+    assert!(matches!(verifier_transcript.events()[index], TranscriptEvent::Reseed(_)));
+    assert!(matches!(verifier_transcript.events()[index - 1], TranscriptEvent::Draw(_)));
+}
+
+// INSTRUMENTATION TESTS
+// ================================================================================================
+
+#[test]
+#[cfg(feature = "tracing")]
+fn verify_with_tracing_spans() {
+    use std::sync::{Arc, Mutex};
+    use tracing::{span, subscriber, Event, Metadata, Subscriber};
+
+    /// Records names of all spans created while it is the default subscriber.
+    #[derive(Default)]
+    struct SpanRecorder(Mutex<Vec<&'static str>>);
+
+    impl Subscriber for SpanRecorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            span::Id::from_u64(names.len() as u64)
+        }
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+        fn event(&self, _event: &Event<'_>) {}
+        fn enter(&self, _span: &span::Id) {}
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    let (proof, result) = build_proof(build_options(), 64);
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    let recorder = Arc::new(SpanRecorder::default());
+    subscriber::with_default(recorder.clone(), || {
+        assert!(verify::<MockAir, Blake3, Coin>(proof, result, &acceptable_options).is_ok())
+    });
+
+    let spans = recorder.0.lock().unwrap().clone();
+    assert_eq!(["verify", "ood_consistency", "queries", "deep_composition", "fri"], spans[..]);
+}
+
+#[test]
+fn verify_with_metrics() {
+    use air::metrics::{self, Metrics};
+    use std::{collections::BTreeMap, sync::Mutex};
+
+    struct CountingMetrics(Mutex<BTreeMap<&'static str, u64>>);
+
+    impl Metrics for CountingMetrics {
+        fn increment_counter(&self, name: &'static str, value: u64) {
+            *self.0.lock().unwrap().entry(name).or_default() += value;
+        }
+
+        fn record_histogram(&self, name: &'static str, _value: f64) {
+            *self.0.lock().unwrap().entry(name).or_default() += 1;
+        }
+    }
+
+    impl CountingMetrics {
+        fn get(&self, name: &'static str) -> u64 {
+            self.0.lock().unwrap().get(name).copied().unwrap_or_default()
+        }
+    }
+
+    // metrics are global and other tests may report them concurrently, so only lower bounds on
+    // the reported values can be checked
+    static METRICS: CountingMetrics = CountingMetrics(Mutex::new(BTreeMap::new()));
+    assert!(metrics::set_metrics(&METRICS).is_ok(), "metrics backend already installed");
+    assert!(metrics::is_enabled());
+
+    let (proof, result) = build_proof(build_options(), 16);
+    let acceptable_options = AcceptableOptions::OptionSet(vec![build_options()]);
+    assert!(verify::<MockAir, Blake3, Coin>(proof.clone(), result, &acceptable_options).is_ok());
+    assert!(METRICS.get(metrics::PROOFS_VERIFIED) >= 1);
+    let wrong_result = result + BaseElement::ONE;
+    assert!(verify::<MockAir, Blake3, Coin>(proof, wrong_result, &acceptable_options).is_err());
+    assert!(METRICS.get(metrics::VERIFICATION_FAILURES) >= 1);
+}
+
+proptest::proptest! {
+    #![proptest_config(proptest::test_runner::Config::with_cases(32))]
+
+    #[test]
+    fn verify_random_proof(
+        proof in prover::testing::proof_strategy::<BaseElement, Blake3>(
+            TraceInfo::new(2, 64),
+            MockAir::new(TraceInfo::new(2, 64), BaseElement::ONE, build_options())
+                .context()
+                .num_constraint_composition_columns(),
+        )
+    ) {
+        // random proofs are well-formed, and thus, the verifier must reject them without panicking
+        let acceptable_options = AcceptableOptions::MinConjecturedSecurity(0);
+        let result = verify::<MockAir, Blake3, Coin>(proof, BaseElement::ONE, &acceptable_options);
+        proptest::prop_assert!(result.is_err());
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// A prover for [MockAir] which draws all challenges from a scripted coin.
+struct ScriptedMockProver {
+    prover: MockProver<Blake3>,
+    public_coin: ScriptedRandomCoin<Blake3>,
+}
+
+impl Prover for ScriptedMockProver {
+    type BaseField = BaseElement;
+    type Air = MockAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type RandomCoin = ScriptedRandomCoin<Blake3>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        self.prover.get_pub_inputs(trace)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.prover.options()
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        self.prover.new_trace_lde(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        self.prover.new_evaluator(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn new_public_coin(&self, _seed: &[BaseElement]) -> Self::RandomCoin {
+        self.public_coin.clone()
+    }
+}
+
+/// Returns a proof for a mock trace of two columns and the specified length, together with the
+/// public inputs against which the proof should be verified.
+fn build_proof(options: ProofOptions, trace_length: usize) -> (StarkProof, BaseElement) {
+    let prover = MockProver::<Blake3>::new(options);
+    let trace = build_mock_trace(2, trace_length);
+    let result = prover.get_pub_inputs(&trace);
+    (prover.prove(trace).unwrap(), result)
+}
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7)
+}
+
+fn build_extension_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 7)
+}
@@ -580,13 +580,38 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(all(feature = "export", feature = "std"))]
+pub use prover::ir;
+#[cfg(feature = "testing")]
+pub use prover::testing;
+pub use prover::trace_row;
+#[cfg(feature = "std")]
+pub use prover::FileTraceTable;
 pub use prover::{
-    crypto, iterators, math, matrix, Air, AirContext, Assertion, AuxTraceRandElements,
-    BoundaryConstraint, BoundaryConstraintGroup, ByteReader, ByteWriter, CompositionPolyTrace,
-    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintEvaluator,
+    crypto, distributed, gadgets, iterators, math, matrix, metrics, proof, remote, Air, AirContext,
+    Assertion, AuxTraceBuilder, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ByteReader, ByteWriter, CancellationToken, CellDiff, CheckReport, ColumnDiff, ColumnDomain,
+    CompositionPolyTrace, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintEvaluator, ConstraintViolation, ContinuationProof, CpuBackend,
     DeepCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde, Deserializable,
-    DeserializationError, EvaluationFrame, FieldExtension, ProofOptions, Prover, ProverError,
-    Serializable, SliceReader, StarkDomain, StarkProof, Trace, TraceInfo, TraceLayout, TraceLde,
-    TracePolyTable, TraceTable, TraceTableFragment, TransitionConstraintDegree,
+    DeserializationError, EvaluationFrame, FieldExtension, IncrementalTrace, MainTraceRow,
+    MainTraceRows, MemoryEstimate, PackedTraceTable, ProofMetadata, ProofOptions,
+    ProofOptionsBuilder, ProofOptionsError, Prover, ProverBackend, ProverError, ProverRng,
+    ProvingConfig, ProvingKey, ProvingPhase, Serializable, SliceReader, StarkDomain, StarkProof,
+    Trace, TraceBuilder, TraceCheckpoint, TraceDiff, TraceInfo, TraceLayout, TraceLde,
+    TraceOpening, TracePolyTable, TraceRow, TraceStorage, TraceTable, TraceTableFragment,
+    TraceWithAux, TransitionConstraintDegree, TuningObjective, ValidationReport,
+};
+#[cfg(feature = "std")]
+pub use prover::{
+    CoefficientCommitment, CoefficientHash, PeriodicColumnCache, PhaseStats, ProofStats,
+};
+
+#[cfg(feature = "std")]
+pub use verifier::verify_with_transcript;
+pub use verifier::{
+    stages, verify, verify_batch, verify_continuation, verify_from_bytes, verify_from_reader,
+    verify_trace_opening, verify_with_hash_fn, verify_with_limits, verify_with_public_coin,
+    AcceptableOptions, AirRegistry, CostObjective, OptionsPolicy, VerifierCostEstimate,
+    VerifierError, VerifierLimits,
 };
-pub use verifier::{verify, AcceptableOptions, VerifierError};